@@ -0,0 +1,413 @@
+//! Find the keys kept by ACME clients.
+//!
+//! An ACME client keeps the key of the account it registered with the
+//! certificate authority, the keys of the certificates it obtained and
+//! the settings it renews them with in a state directory:
+//!
+//! - certbot keeps account keys as JSON Web Keys in
+//!   `accounts/<server>/directory/<id>/private_key.json`, certificate
+//!   keys in `live/<name>/privkey.pem` and renewal settings in
+//!   `renewal/<name>.conf`.
+//! - acme.sh keeps account keys in `ca/<server>/.../account.key`, and
+//!   certificate keys and renewal settings in `<domain>/<domain>.key`
+//!   and `<domain>/<domain>.conf`, with an `_ecc` suffix on the
+//!   directories of ECDSA certificates.
+//! - lego keeps account keys in `accounts/<server>/<email>/keys/` and
+//!   certificate keys in `certificates/<domain>.key`. The key type is
+//!   given on the command line for every renewal, so it is not kept.
+//!
+//! A renewal setting that leaves out the key type follows the default
+//! of the client, which is taken to be that of its current version.
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use wardstone_core::primitive::asymmetric::Asymmetric;
+use wardstone_core::primitive::ecc::*;
+use wardstone_core::primitive::hash::Hash;
+use wardstone_core::primitive::ifc::{Ifc, ID_RSA_PKCS1};
+use wardstone_core::primitive::Primitive;
+
+use crate::assess::{audit_audiences, audit_key, Audiences, Settings};
+use crate::key::private::PrivateKey;
+use crate::key::{Error, Key};
+use crate::reader::PrivateKeyReader;
+use crate::report::Report;
+
+/// An ACME client.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Client {
+  Certbot,
+  AcmeSh,
+  Lego,
+}
+
+/// What a file kept by an ACME client is used for.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Role {
+  /// The key of an account with a certificate authority.
+  Account,
+  /// The key of a certificate.
+  Certificate,
+  /// The settings used to renew a certificate.
+  Renewal,
+}
+
+/// A key or renewal setting kept by an ACME client.
+pub struct Artifact {
+  pub client: Client,
+  pub role: Role,
+  pub path: PathBuf,
+}
+
+/// The key type a certificate is renewed with.
+struct Renewal {
+  signature_algorithm: Asymmetric,
+  /// Whether the key type is left to the default of the client.
+  default: bool,
+}
+
+impl Key for Renewal {
+  fn hash_function(&self) -> Option<Hash> {
+    None
+  }
+
+  fn signature_algorithm(&self) -> Asymmetric {
+    self.signature_algorithm
+  }
+}
+
+/// The key types that ACME clients can be configured with.
+enum KeyType {
+  Ec(u16),
+  Rsa(u16),
+}
+
+impl KeyType {
+  fn of(want: Asymmetric) -> Option<Self> {
+    match want {
+      Asymmetric::Ecc(ecc) if ecc == P256 || ecc == ECC_256 => Some(Self::Ec(256)),
+      Asymmetric::Ecc(ecc) if ecc == P384 || ecc == ECC_384 => Some(Self::Ec(384)),
+      Asymmetric::Ecc(ecc) if ecc == P521 || ecc == ECC_512 => Some(Self::Ec(521)),
+      Asymmetric::Ifc(ifc) if !ifc.is_placeholder() => Some(Self::Rsa(ifc.k)),
+      _ => None,
+    }
+  }
+}
+
+impl Artifact {
+  /// Reads the key, or the key type used to renew a certificate along
+  /// with whether it is left to the default of the client.
+  pub fn read(&self) -> Result<(Box<dyn Key>, bool), Error> {
+    match self.role {
+      Role::Account | Role::Certificate => {
+        Ok((Box::new(PrivateKey::from_file(&self.path)?), false))
+      },
+      Role::Renewal => {
+        let contents = fs::read_to_string(&self.path)?;
+        let renewal = match self.client {
+          Client::Certbot => certbot_renewal(&contents),
+          _ => acme_sh_renewal(&contents),
+        }
+        .ok_or_else(|| Error::Acme("unrecognised key type".to_string()))?;
+        let default = renewal.default;
+        Ok((Box::new(renewal), default))
+      },
+    }
+  }
+
+  /// Returns the change to the settings of the client that makes it
+  /// use `want` instead, if the client supports it.
+  pub fn remediation(&self, want: Asymmetric) -> Option<String> {
+    let key_type = KeyType::of(want)?;
+    let hint = match (self.client, self.role) {
+      (Client::Certbot, Role::Account) => return None,
+      (Client::Certbot, _) => {
+        let settings = match key_type {
+          KeyType::Ec(bits) => format!("key_type = ecdsa and elliptic_curve = secp{}r1", bits),
+          KeyType::Rsa(bits) => format!("key_type = rsa and rsa_key_size = {}", bits),
+        };
+        let conf = match self.role {
+          Role::Renewal => self.path.clone(),
+          _ => self.certbot_renewal_path()?,
+        };
+        format!(
+          "set {} under [renewalparams] in {}",
+          settings,
+          conf.display()
+        )
+      },
+      (Client::AcmeSh, role) => {
+        let length = match key_type {
+          KeyType::Ec(bits) => format!("ec-{}", bits),
+          KeyType::Rsa(bits) => bits.to_string(),
+        };
+        if role == Role::Account {
+          format!(
+            "acme.sh --create-account-key --accountkeylength {} --force and register the account again",
+            length
+          )
+        } else {
+          let conf = self.path.with_extension("conf");
+          format!("set Le_Keylength='{}' in {}", length, conf.display())
+        }
+      },
+      (Client::Lego, Role::Account) => return None,
+      (Client::Lego, _) => {
+        let key_type = match key_type {
+          KeyType::Ec(521) => return None,
+          KeyType::Ec(bits) => format!("ec{}", bits),
+          KeyType::Rsa(bits) => format!("rsa{}", bits),
+        };
+        format!("renew with lego --key-type {}", key_type)
+      },
+    };
+    Some(hint)
+  }
+
+  /// Returns the renewal settings of the certificate whose key is kept
+  /// in `live/<name>/privkey.pem`.
+  fn certbot_renewal_path(&self) -> Option<PathBuf> {
+    let live = self.path.parent()?;
+    let name = live.file_name()?.to_str()?;
+    let root = live.parent()?.parent()?;
+    Some(root.join("renewal").join(format!("{}.conf", name)))
+  }
+}
+
+/// Returns the state directories of the ACME clients in the places the
+/// clients keep them by default that exist.
+pub fn default_dirs() -> Vec<PathBuf> {
+  let mut dirs = vec![PathBuf::from("/etc/letsencrypt")];
+  if let Some(home) = env::var_os("HOME") {
+    dirs.push(PathBuf::from(home).join(".acme.sh"));
+  }
+  dirs.push(PathBuf::from(".lego"));
+  dirs.into_iter().filter(|dir| dir.is_dir()).collect()
+}
+
+/// Finds the keys and renewal settings in the state directory of an
+/// ACME client, in the order of their paths.
+pub fn discover(dir: &Path) -> Result<Vec<Artifact>, Error> {
+  let client = if dir.join("renewal").is_dir() || dir.join("live").is_dir() {
+    Client::Certbot
+  } else if dir.join("account.conf").is_file() || dir.join("ca").is_dir() {
+    Client::AcmeSh
+  } else if dir.join("accounts").is_dir() && dir.join("certificates").is_dir() {
+    Client::Lego
+  } else {
+    return Err(Error::Acme(format!(
+      "{} is not the state directory of certbot, acme.sh or lego",
+      dir.display()
+    )));
+  };
+  let mut artifacts = Vec::new();
+  let mut push = |role, path| {
+    artifacts.push(Artifact { client, role, path });
+  };
+  match client {
+    Client::Certbot => {
+      for path in walk(&dir.join("accounts")) {
+        if path
+          .file_name()
+          .is_some_and(|name| name == "private_key.json")
+        {
+          push(Role::Account, path);
+        }
+      }
+      for live in entries(&dir.join("live")) {
+        let key = live.join("privkey.pem");
+        if key.is_file() {
+          push(Role::Certificate, key);
+        }
+      }
+      for conf in entries(&dir.join("renewal")) {
+        if conf
+          .extension()
+          .is_some_and(|extension| extension == "conf")
+        {
+          push(Role::Renewal, conf);
+        }
+      }
+    },
+    Client::AcmeSh => {
+      for path in walk(&dir.join("ca")) {
+        if path.file_name().is_some_and(|name| name == "account.key") {
+          push(Role::Account, path);
+        }
+      }
+      // Every certificate has a directory named after its domain, with
+      // a suffix for ECDSA certificates, holding the key and settings.
+      for domain in entries(dir) {
+        let Some(name) = domain.file_name().and_then(|name| name.to_str()) else {
+          continue;
+        };
+        let name = name.strip_suffix("_ecc").unwrap_or(name);
+        let conf = domain.join(format!("{}.conf", name));
+        if !conf.is_file() {
+          continue;
+        }
+        let key = domain.join(format!("{}.key", name));
+        if key.is_file() {
+          push(Role::Certificate, key);
+        }
+        push(Role::Renewal, conf);
+      }
+    },
+    Client::Lego => {
+      for path in walk(&dir.join("accounts")) {
+        let in_keys = path
+          .parent()
+          .and_then(Path::file_name)
+          .is_some_and(|name| name == "keys");
+        if in_keys && path.extension().is_some_and(|extension| extension == "key") {
+          push(Role::Account, path);
+        }
+      }
+      for path in entries(&dir.join("certificates")) {
+        if path.extension().is_some_and(|extension| extension == "key") {
+          push(Role::Certificate, path);
+        }
+      }
+    },
+  }
+  Ok(artifacts)
+}
+
+/// Returns the entries of a directory in order, or none if it cannot be
+/// read.
+fn entries(dir: &Path) -> Vec<PathBuf> {
+  let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+    .into_iter()
+    .flatten()
+    .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+    .collect();
+  paths.sort();
+  paths
+}
+
+/// Returns the files in a directory and those below it in order.
+fn walk(dir: &Path) -> Vec<PathBuf> {
+  let mut files = Vec::new();
+  for path in entries(dir) {
+    if path.is_dir() {
+      files.extend(walk(&path));
+    } else {
+      files.push(path);
+    }
+  }
+  files
+}
+
+/// Reads the key type from the `[renewalparams]` of a certbot renewal
+/// configuration file. Since version 2.0, certbot generates ECDSA keys
+/// on P-256 unless told otherwise and RSA keys of 2048 bits if asked
+/// for RSA keys.
+fn certbot_renewal(contents: &str) -> Option<Renewal> {
+  let mut section = "";
+  let (mut key_type, mut rsa_key_size, mut elliptic_curve) = (None, None, None);
+  for line in contents.lines().map(str::trim) {
+    if let Some(name) = line
+      .strip_prefix('[')
+      .and_then(|line| line.strip_suffix(']'))
+    {
+      section = name;
+      continue;
+    }
+    let Some((name, value)) = line.split_once('=') else {
+      continue;
+    };
+    if section == "renewalparams" {
+      match name.trim() {
+        "key_type" => key_type = Some(value.trim()),
+        "rsa_key_size" => rsa_key_size = Some(value.trim()),
+        "elliptic_curve" => elliptic_curve = Some(value.trim()),
+        _ => {},
+      }
+    }
+  }
+  // Older versions of certbot only wrote the size of RSA keys.
+  let default = key_type.is_none() && rsa_key_size.is_none();
+  let rsa = key_type.map_or(rsa_key_size.is_some(), |key_type| key_type == "rsa");
+  let signature_algorithm = if rsa {
+    let k = rsa_key_size.unwrap_or("2048").parse().ok()?;
+    rsa_key(k)
+  } else {
+    match elliptic_curve.unwrap_or("secp256r1") {
+      "secp256r1" => P256.into(),
+      "secp384r1" => P384.into(),
+      "secp521r1" => P521.into(),
+      _ => return None,
+    }
+  };
+  Some(Renewal {
+    signature_algorithm,
+    default,
+  })
+}
+
+/// Reads the key type from the `Le_Keylength` of an acme.sh domain
+/// configuration file. Since version 3.0, acme.sh generates ECDSA keys
+/// on P-256 unless told otherwise.
+fn acme_sh_renewal(contents: &str) -> Option<Renewal> {
+  let length = contents
+    .lines()
+    .filter_map(|line| line.trim().strip_prefix("Le_Keylength="))
+    .map(|value| value.trim_matches(['\'', '"']))
+    .next_back()
+    .filter(|length| !length.is_empty());
+  let signature_algorithm = match length.unwrap_or("ec-256") {
+    "ec-256" => P256.into(),
+    "ec-384" => P384.into(),
+    "ec-521" => P521.into(),
+    k => rsa_key(k.parse().ok()?),
+  };
+  Some(Renewal {
+    signature_algorithm,
+    default: length.is_none(),
+  })
+}
+
+fn rsa_key(k: u16) -> Asymmetric {
+  Ifc::new(ID_RSA_PKCS1, k).into()
+}
+
+/// Audits the keys and renewal settings kept by ACME clients. Keys
+/// that cannot be read are skipped.
+pub fn assess(audiences: &Audiences, artifacts: Vec<Artifact>, report: &mut Report) {
+  for artifact in artifacts {
+    let (key, default) = match artifact.read() {
+      Ok(read) => read,
+      Err(err) => {
+        report.skip(err.in_file(&artifact.path));
+        continue;
+      },
+    };
+    for audit in audit_audiences(audiences, |settings| {
+      // The key type is changed in the settings of the client rather
+      // than by generating a key by hand.
+      let without = Settings {
+        remediation: false,
+        ..settings
+      };
+      let mut audit = audit_key(without, &PrivateKeyReader, &artifact.path, key.as_ref());
+      if default {
+        audit.warn(format!(
+          "the key type is not set, so renewals use the default of the client, {}",
+          key.signature_algorithm()
+        ));
+      }
+      if settings.remediation && !audit.passed() {
+        let Settings { ctx, guide, .. } = settings;
+        let got = key.signature_algorithm();
+        let (Ok(want) | Err(want)) = guide.validate_signature_algorithm(ctx, got);
+        if let Some(hint) = artifact.remediation(want) {
+          audit.remediate(hint);
+        }
+      }
+      audit
+    }) {
+      report.push(audit);
+    }
+  }
+}
@@ -0,0 +1,417 @@
+//! Find the signers of Android application packages.
+//!
+//! An APK is a ZIP file signed with one or more schemes:
+//!
+//! - v1 is JAR signing, where `META-INF/<name>.SF` lists the digests of
+//!   the files in the package and `META-INF/<name>.RSA`, `.DSA` or
+//!   `.EC` is a PKCS #7 signature over it.
+//! - v2, v3 and v3.1 sign the whole file and keep their signers in the
+//!   APK Signing Block, which comes right before the central directory
+//!   of the ZIP file. Every signer lists its certificates, the digests
+//!   of the file and its signatures, which all use the same algorithms.
+//!
+//! Every signer of every scheme is reported on its own, since devices
+//! verify the newest scheme they support and ignore the rest.
+use std::fmt;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use flate2::read::DeflateDecoder;
+use wardstone_core::primitive::hash::*;
+
+use crate::assess::{audit_audiences, audit_key, Audiences};
+use crate::input;
+use crate::key::signed::Signed;
+use crate::key::Error;
+use crate::reader::X509Reader;
+use crate::report::Report;
+
+/// The magic at the end of the APK Signing Block.
+const SIGNING_BLOCK_MAGIC: &[u8] = b"APK Sig Block 42";
+
+/// A signature scheme of APKs.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Scheme {
+  V1,
+  V2,
+  V3,
+  V31,
+}
+
+impl Scheme {
+  /// Returns the scheme whose signers are kept under the given ID in the
+  /// APK Signing Block.
+  fn from_id(id: u32) -> Option<Self> {
+    match id {
+      0x7109871a => Some(Self::V2),
+      0xf05368c0 => Some(Self::V3),
+      0x1b93ad61 => Some(Self::V31),
+      _ => None,
+    }
+  }
+}
+
+impl fmt::Display for Scheme {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::V1 => write!(f, "v1"),
+      Self::V2 => write!(f, "v2"),
+      Self::V3 => write!(f, "v3"),
+      Self::V31 => write!(f, "v3.1"),
+    }
+  }
+}
+
+/// A signer of an APK.
+pub struct Signer {
+  pub scheme: Scheme,
+  /// Where the signer was found, such as `app.apk:v2` or
+  /// `app.apk:META-INF/CERT.RSA`.
+  pub label: PathBuf,
+  pub signed: Result<Signed, Error>,
+}
+
+/// A file in a ZIP file.
+struct Entry {
+  name: String,
+  method: u16,
+  size: usize,
+  /// The size of the contents once inflated, which they may not exceed.
+  uncompressed: usize,
+  offset: usize,
+}
+
+/// Returns the signers of the APK at `path`, those of v1 first.
+pub fn signers(path: &Path) -> Result<Vec<Signer>, Error> {
+  let data = input::open(path)?;
+  let (directory, entries) = central_directory(&data)?;
+  let mut signers = Vec::new();
+  for entry in &entries {
+    let Some(stem) = entry.name.strip_prefix("META-INF/").and_then(|name| {
+      [".RSA", ".DSA", ".EC"]
+        .iter()
+        .find_map(|extension| name.strip_suffix(extension))
+    }) else {
+      continue;
+    };
+    // The signature file names the algorithm that digests the manifest
+    // and the files in it.
+    let manifest = entries
+      .iter()
+      .find(|sf| sf.name == format!("META-INF/{}.SF", stem))
+      .and_then(|sf| sf.read(&data).ok())
+      .and_then(|sf| manifest_digest(&String::from_utf8_lossy(&sf)));
+    let signed = entry.read(&data).and_then(|block| {
+      let signed = Signed::from_pkcs7(&block)?;
+      Ok(match manifest {
+        Some(hash) => signed.relying_on(hash),
+        None => signed,
+      })
+    });
+    signers.push(Signer {
+      scheme: Scheme::V1,
+      label: format!("{}:{}", path.display(), entry.name).into(),
+      signed,
+    });
+  }
+  for (scheme, block) in signing_block(&data, directory)? {
+    let (blocks, _) = sequence(block)?;
+    let several = blocks.len() > 1;
+    for (i, signer) in blocks.into_iter().enumerate() {
+      let label = if several {
+        format!("{}:{}:{}", path.display(), scheme, i + 1)
+      } else {
+        format!("{}:{}", path.display(), scheme)
+      };
+      signers.push(Signer {
+        scheme,
+        label: label.into(),
+        signed: read_signer(signer),
+      });
+    }
+  }
+  Ok(signers)
+}
+
+/// Returns the offset of the central directory and the files it lists.
+fn central_directory(data: &[u8]) -> Result<(usize, Vec<Entry>), Error> {
+  // The end of central directory record is 22 bytes long and may be
+  // followed by a comment of up to 65535 bytes.
+  let start = data.len().saturating_sub(22 + 0xffff);
+  let end = (start..data.len().saturating_sub(21))
+    .rev()
+    .find(|&i| data[i..].starts_with(b"PK\x05\x06"))
+    .ok_or_else(|| Error::Apk("not a ZIP file".to_string()))?;
+  let count = le16(data, end + 10)?;
+  let offset = le32(data, end + 16)?;
+  let mut entries = Vec::with_capacity(count);
+  let mut at = offset;
+  for _ in 0..count {
+    if !data
+      .get(at..)
+      .is_some_and(|rest| rest.starts_with(b"PK\x01\x02"))
+    {
+      return Err(Error::Apk("malformed central directory".to_string()));
+    }
+    let name_len = le16(data, at + 28)?;
+    let name = data
+      .get(at + 46..at + 46 + name_len)
+      .ok_or_else(|| Error::Apk("malformed central directory".to_string()))?;
+    entries.push(Entry {
+      name: String::from_utf8_lossy(name).into_owned(),
+      method: le16(data, at + 10)? as u16,
+      size: le32(data, at + 20)?,
+      uncompressed: le32(data, at + 24)?,
+      offset: le32(data, at + 42)?,
+    });
+    at += 46 + name_len + le16(data, at + 30)? + le16(data, at + 32)?;
+  }
+  Ok((offset, entries))
+}
+
+impl Entry {
+  /// Reads the contents of the file, which are stored or deflated.
+  fn read(&self, data: &[u8]) -> Result<Vec<u8>, Error> {
+    let malformed = || Error::Apk(format!("malformed entry {}", self.name));
+    let header = data.get(self.offset..).ok_or_else(malformed)?;
+    if !header.starts_with(b"PK\x03\x04") {
+      return Err(malformed());
+    }
+    let start = 30 + le16(header, 26)? + le16(header, 28)?;
+    let end = start.checked_add(self.size).ok_or_else(malformed)?;
+    let contents = header.get(start..end).ok_or_else(malformed)?;
+    match self.method {
+      0 => Ok(contents.to_vec()),
+      8 => {
+        // Inflating is stopped a byte past the size the central directory
+        // gives, so that a deflate bomb is told apart without being
+        // inflated whole.
+        let mut inflated = Vec::new();
+        DeflateDecoder::new(contents)
+          .take(self.uncompressed as u64 + 1)
+          .read_to_end(&mut inflated)?;
+        if inflated.len() > self.uncompressed {
+          return Err(Error::Apk(format!(
+            "{} inflates past its size of {} bytes",
+            self.name, self.uncompressed
+          )));
+        }
+        Ok(inflated)
+      },
+      method => Err(Error::Apk(format!(
+        "unsupported compression method {} in {}",
+        method, self.name
+      ))),
+    }
+  }
+}
+
+/// Returns the hash function named by the `<algorithm>-Digest-Manifest`
+/// attribute of a signature file.
+fn manifest_digest(sf: &str) -> Option<Hash> {
+  sf.lines().find_map(|line| {
+    let (algorithm, _) = line.split_once("-Digest-Manifest:")?;
+    match algorithm.to_ascii_uppercase().as_str() {
+      "MD5" => Some(MD5),
+      "SHA1" | "SHA-1" => Some(SHA1),
+      "SHA-256" => Some(SHA256),
+      "SHA-384" => Some(SHA384),
+      "SHA-512" => Some(SHA512),
+      _ => None,
+    }
+  })
+}
+
+/// Returns the blocks of signers in the APK Signing Block, which ends
+/// where the central directory starts, by the scheme they belong to.
+fn signing_block(data: &[u8], directory: usize) -> Result<Vec<(Scheme, &[u8])>, Error> {
+  // The block ends with its size and magic, and starts with its size
+  // again.
+  let Some(footer) = directory
+    .checked_sub(24)
+    .and_then(|at| data.get(at..directory))
+  else {
+    return Ok(Vec::new());
+  };
+  if !footer.ends_with(SIGNING_BLOCK_MAGIC) {
+    return Ok(Vec::new());
+  }
+  let malformed = || Error::Apk("malformed APK Signing Block".to_string());
+  let size = usize::try_from(u64::from_le_bytes(footer[..8].try_into().unwrap()))
+    .map_err(|_| malformed())?;
+  let start = size
+    .checked_add(8)
+    .and_then(|size| directory.checked_sub(size))
+    .ok_or_else(malformed)?;
+  let mut pairs = data.get(start + 8..directory - 24).ok_or_else(malformed)?;
+  let mut blocks = Vec::new();
+  // Every pair is its length, an ID and a value.
+  while pairs.len() >= 12 {
+    let len = usize::try_from(u64::from_le_bytes(pairs[..8].try_into().unwrap()))
+      .map_err(|_| malformed())?;
+    let end = len.checked_add(8).ok_or_else(malformed)?;
+    let pair = pairs.get(8..end).ok_or_else(malformed)?;
+    let id = le32(pair, 0)? as u32;
+    if let Some(scheme) = Scheme::from_id(id) {
+      blocks.push((scheme, &pair[4..]));
+    }
+    pairs = &pairs[end..];
+  }
+  Ok(blocks)
+}
+
+/// Reads a signer of v2 or later (see "APK Signature Scheme v2" in the
+/// Android documentation).
+fn read_signer(signer: &[u8]) -> Result<Signed, Error> {
+  let (signed_data, _) = prefixed(signer)?;
+  let (digests, rest) = sequence(signed_data)?;
+  let (certificates, _) = sequence(rest)?;
+  let signer = certificates
+    .first()
+    .ok_or_else(|| Error::Apk("signer without a certificate".to_string()))?;
+  let hashes = digests
+    .into_iter()
+    .map(|digest| Ok(Some(digest_hash(le32(digest, 0)? as u32)?)))
+    .collect::<Result<Vec<_>, Error>>()?;
+  Signed::from_certificate(signer, certificates[1..].iter().copied(), hashes)
+}
+
+/// Returns the hash function of a signature algorithm of v2 and later.
+fn digest_hash(id: u32) -> Result<Hash, Error> {
+  match id {
+    // RSASSA-PSS, RSASSA-PKCS1-v1_5, ECDSA and DSA, and the same over
+    // the verity tree of the file.
+    0x0101 | 0x0103 | 0x0201 | 0x0301 | 0x0421 | 0x0423 | 0x0425 => Ok(SHA256),
+    0x0102 | 0x0104 | 0x0202 => Ok(SHA512),
+    id => Err(Error::Unrecognised(format!(
+      "APK signature algorithm {:#06x}",
+      id
+    ))),
+  }
+}
+
+/// Splits off a value that starts with its length as a 32-bit integer.
+fn prefixed(data: &[u8]) -> Result<(&[u8], &[u8]), Error> {
+  let len = le32(data, 0)?;
+  let value = data
+    .get(4..4 + len)
+    .ok_or_else(|| Error::Apk("malformed signer".to_string()))?;
+  Ok((value, &data[4 + len..]))
+}
+
+/// Splits off a length-prefixed sequence of length-prefixed values.
+fn sequence(data: &[u8]) -> Result<(Vec<&[u8]>, &[u8]), Error> {
+  let (mut inner, rest) = prefixed(data)?;
+  let mut values = Vec::new();
+  while !inner.is_empty() {
+    let (value, next) = prefixed(inner)?;
+    values.push(value);
+    inner = next;
+  }
+  Ok((values, rest))
+}
+
+fn le16(data: &[u8], at: usize) -> Result<usize, Error> {
+  data
+    .get(at..at + 2)
+    .map(|b| u16::from_le_bytes([b[0], b[1]]) as usize)
+    .ok_or_else(|| Error::Apk("unexpected end of file".to_string()))
+}
+
+fn le32(data: &[u8], at: usize) -> Result<usize, Error> {
+  data
+    .get(at..at + 4)
+    .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize)
+    .ok_or_else(|| Error::Apk("unexpected end of file".to_string()))
+}
+
+/// Audits the signers of APKs. An APK that cannot be read, or a
+/// signer that cannot be, is skipped.
+pub fn assess(audiences: &Audiences, paths: &[PathBuf], report: &mut Report) {
+  for path in paths {
+    let signers = match signers(path) {
+      Ok(signers) => signers,
+      Err(err) => {
+        report.skip(err.in_file(path));
+        continue;
+      },
+    };
+    if signers.is_empty() {
+      report.skip(Error::Unsigned.in_file(path));
+    }
+    // Android 7.0 and later only verify the whole file if a newer
+    // scheme is present.
+    let v1_only = signers.iter().all(|signer| signer.scheme == Scheme::V1);
+    for signer in signers {
+      let signed = match signer.signed {
+        Ok(signed) => signed,
+        Err(err) => {
+          report.skip(err.in_file(signer.label));
+          continue;
+        },
+      };
+      for audit in audit_audiences(audiences, |settings| {
+        let mut audit = audit_key(settings, &X509Reader, &signer.label, &signed);
+        if v1_only {
+          audit.warn(
+            "the APK is only signed with JAR signing (v1), which does not protect the whole file and is open to CVE-2017-13156 on Android 5.0 to 8.0"
+              .to_string(),
+          );
+        }
+        audit
+      }) {
+        report.push(audit);
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::io::Write;
+
+  use flate2::write::DeflateEncoder;
+  use flate2::Compression;
+
+  use super::*;
+
+  /// Returns a local file header named `name` followed by `contents`.
+  fn local_file(name: &str, contents: &[u8]) -> Vec<u8> {
+    let mut data = b"PK\x03\x04".to_vec();
+    data.resize(26, 0);
+    data.extend((name.len() as u16).to_le_bytes());
+    data.extend([0, 0]);
+    data.extend(name.as_bytes());
+    data.extend(contents);
+    data
+  }
+
+  #[test]
+  fn deflated_past_size() {
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(&[0; 4096]).unwrap();
+    let deflated = encoder.finish().unwrap();
+    let data = local_file("META-INF/CERT.RSA", &deflated);
+    let entry = |uncompressed| Entry {
+      name: "META-INF/CERT.RSA".to_string(),
+      method: 8,
+      size: deflated.len(),
+      uncompressed,
+      offset: 0,
+    };
+    assert_eq!(entry(4096).read(&data).unwrap().len(), 4096);
+    assert!(entry(1024).read(&data).is_err());
+  }
+
+  #[test]
+  fn signing_block_pair_overflow() {
+    // A pair whose length does not fit alongside its own, in a block
+    // that ends where the central directory starts.
+    let mut data = 40u64.to_le_bytes().to_vec();
+    data.extend(u64::MAX.to_le_bytes());
+    data.extend([0; 8]);
+    data.extend(40u64.to_le_bytes());
+    data.extend(SIGNING_BLOCK_MAGIC);
+    assert!(signing_block(&data, data.len()).is_err());
+  }
+}
@@ -0,0 +1,322 @@
+//! Assess keys in the settings given on the command line.
+//!
+//! Every subcommand that assesses keys or settings against a guide takes
+//! the same [`CommonArgs`], from which the [`Settings`] of every audience
+//! and the report that the audits are pushed to are made. The audits of
+//! keys themselves are made by [`audit_key`], which subcommands extend
+//! with findings of their own.
+use std::env;
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use wardstone_core::advisory::{Hazard, Hazards};
+use wardstone_core::context::Context;
+use wardstone_core::locale::{Locale, Use};
+use wardstone_core::primitive::symmetric::{Mode, Symmetric};
+use wardstone_core::primitive::Security;
+use wardstone_core::standard::verdict::Verdict;
+
+use crate::audience::Config;
+use crate::guide::Guide;
+use crate::key::{Error, Key};
+use crate::reader::{ArtifactReader, Blob};
+use crate::report::{Audit, Output, Report, Verbosity};
+use crate::template::Template;
+
+/// The options shared by every subcommand that assesses keys or
+/// settings against a guide.
+#[derive(Args)]
+pub struct CommonArgs {
+  /// Guide to assess against.
+  #[arg(short, long, value_enum)]
+  pub guide: Guide,
+  /// Assess for an audience defined in the configuration file rather
+  /// than with the security and year given here. Can be given more
+  /// than once to assess for several audiences in one scan.
+  #[arg(short, long, value_name = "NAME")]
+  pub audience: Vec<String>,
+  /// The format of the output.
+  #[arg(long, value_enum, conflicts_with = "json")]
+  pub format: Option<Output>,
+  /// JSON formatted output, short for --format json.
+  #[arg(short, long)]
+  pub json: bool,
+  /// Do not print output.
+  #[arg(short, long, conflicts_with = "verbose")]
+  pub quiet: bool,
+  /// The minimum security level required.
+  ///
+  /// If a sufficiently low value is used then the application will
+  /// default to the minimum security specified by the standard.
+  #[arg(short, long, default_value_t = 0)]
+  pub security: Security,
+  /// Render the report with a Handlebars style template instead.
+  #[arg(short, long, value_name = "FILE", conflicts_with_all = ["format", "json"])]
+  pub template: Option<PathBuf>,
+  /// Verbose output.
+  #[arg(short, long, conflicts_with = "quiet")]
+  pub verbose: bool,
+  /// The year in which a recommendation is expected to be valid.
+  ///
+  /// Note that this does not necessarily mean that a primitive will
+  /// be deemed insecure beyond this point. Indeed, recommendations
+  /// are usually done with a longer horizon in mind. For example,
+  /// setting this value to 2023, one would expect any passing
+  /// primitive to be secure for the next 5 to 7 years,
+  /// conservatively, subject to cryptanalytic developments.
+  #[arg(short, long, default_value_t = 2023)]
+  pub year: u16,
+}
+
+impl CommonArgs {
+  /// Returns the settings given by the options, to which subcommands
+  /// add those of their own options.
+  pub fn settings(&self) -> Settings {
+    Settings::new(self.guide, Context::new(self.security, self.year))
+  }
+
+  /// Returns the settings of every audience named on the command line,
+  /// which replace the context and, if they name one, the guide of
+  /// `settings`, or `settings` alone if no audience is named.
+  pub fn audiences(&self, settings: Settings) -> Result<Vec<(Option<String>, Settings)>, Error> {
+    if self.audience.is_empty() {
+      return Ok(vec![(None, settings)]);
+    }
+    let config = Config::open()?;
+    self
+      .audience
+      .iter()
+      .map(|name| {
+        let audience = config.audience(name)?;
+        let settings = Settings {
+          ctx: audience.ctx,
+          guide: audience.guide.unwrap_or(settings.guide),
+          hazards: audience.hazards,
+          ..settings
+        };
+        Ok((Some(audience.name), settings))
+      })
+      .collect()
+  }
+
+  /// Returns an empty report that is printed as the options ask.
+  pub fn report(&self) -> Result<Report, Error> {
+    let verbosity = Verbosity::from_flags(self.verbose, self.quiet);
+    let output = Output::from_flags(self.json, self.format);
+    let template = self
+      .template
+      .as_deref()
+      .map(Template::from_file)
+      .transpose()?;
+    Ok(Report::new(verbosity, output).with_template(template))
+  }
+}
+
+/// Settings that apply to every key that is audited.
+#[derive(Clone, Copy)]
+pub struct Settings {
+  pub ctx: Context,
+  pub guide: Guide,
+  pub locale: Locale,
+  pub max_validity: Option<u64>,
+  pub remediation: bool,
+  /// The hazards to raise advisories for if not those of the guide.
+  pub hazards: Option<Hazards>,
+}
+
+impl Settings {
+  /// Returns the settings of a guide in a context, with messages in the
+  /// language of the environment and no other option set.
+  pub fn new(guide: Guide, ctx: Context) -> Self {
+    Self {
+      ctx,
+      guide,
+      locale: locale(),
+      max_validity: None,
+      remediation: false,
+      hazards: None,
+    }
+  }
+}
+
+/// The settings of every audience keys are assessed for, along with
+/// the name of the audience if one was named.
+pub type Audiences = [(Option<String>, Settings)];
+
+/// Audits a key once for every audience.
+pub fn audit_audiences(audiences: &Audiences, audit: impl Fn(Settings) -> Audit) -> Vec<Audit> {
+  audiences
+    .iter()
+    .map(|(name, settings)| {
+      let mut audit = audit(*settings);
+      if let Some(name) = name {
+        audit.address(name.clone());
+      }
+      audit
+    })
+    .collect()
+}
+
+/// Advises against a cipher with 64-bit blocks in a mode that encrypts
+/// bulk data, with the number of blocks the guide lets one key encrypt,
+/// unless the settings leave the hazard out.
+pub fn advise_cipher(settings: Settings, got: Symmetric, mode: Mode, audit: &mut Audit) {
+  let hazards = settings.hazards.unwrap_or(settings.guide.hazards());
+  let Some(hazard) = Hazard::of_cipher(got, mode).filter(|&h| hazards.contains(h)) else {
+    return;
+  };
+  match settings.guide.block_limit(got, mode) {
+    Some(blocks) => audit.advise_limit(hazard.advisory(), got, mode, blocks),
+    None => audit.advise(hazard.advisory(), got),
+  }
+}
+
+/// Audits a key read by `reader` from `path`, whose remediation is
+/// suggested by the reader if the settings ask for one.
+pub fn audit_key(
+  settings: Settings,
+  reader: &dyn ArtifactReader,
+  path: &Path,
+  key: &dyn Key,
+) -> Audit {
+  let Settings {
+    ctx,
+    guide,
+    locale,
+    max_validity,
+    remediation,
+    hazards,
+  } = settings;
+  let hazards = hazards.unwrap_or(guide.hazards());
+  let hash_function = key.hash_function();
+  let signature_algorithm = key.signature_algorithm();
+  let mut audit = Audit::new(path, guide.name(), hash_function, signature_algorithm);
+  if let Some(location) = key.location() {
+    audit.locate(location);
+  }
+  if key.explicit_curve() {
+    audit.warn(locale.explicit_curve().to_string());
+  }
+  // Only the primitives that fail the assessment are replaced when
+  // suggesting a remediation.
+  let fix_hash_function = hash_function.map(|got| {
+    let verdict = guide.assess_hash_function(ctx, got);
+    audit.cite(verdict.citation);
+    audit.explain(&verdict, ctx, got, locale);
+    match verdict.into_result() {
+      Ok(want) => {
+        audit.compliant_hash_function(want);
+        got
+      },
+      Err(want) => {
+        audit.noncompliant_hash_function(want);
+        want
+      },
+    }
+  });
+  let verdict = guide.assess_signature_algorithm(ctx, signature_algorithm);
+  audit.cite(verdict.citation);
+  audit.explain(&verdict, ctx, signature_algorithm, locale);
+  let fix_signature_algorithm = match verdict.into_result() {
+    Ok(want) => {
+      audit.compliant_signature(want);
+      signature_algorithm
+    },
+    Err(want) => {
+      audit.noncompliant_signature(want);
+      want
+    },
+  };
+  if let Some(hazard) = Hazard::of(signature_algorithm).filter(|&h| hazards.contains(h)) {
+    audit.advise(hazard.advisory(), signature_algorithm);
+  }
+  if remediation && !audit.passed() {
+    if let Some(hint) = reader.remediation(fix_hash_function, fix_signature_algorithm) {
+      audit.remediate(hint);
+    }
+  }
+  // Generating a new key does not change the key of the issuer so it
+  // is only assessed once a remediation has been suggested.
+  if let Some(issuer_key) = key.issuer_key() {
+    let verdict = guide.assess_signature_algorithm(ctx, issuer_key);
+    audit.cite(verdict.citation);
+    audit.explain(&verdict, ctx, issuer_key, locale);
+    match verdict.into_result() {
+      Ok(want) => audit.compliant_issuer_key(issuer_key, want),
+      Err(want) => audit.noncompliant_issuer_key(issuer_key, want),
+    }
+  }
+  if let Some(validity) = key.validity() {
+    if let Some(max) = max_validity {
+      if validity.days > max {
+        audit.noncompliant_validity(validity.days, max);
+      }
+    }
+    // A primitive that is compliant now might not be by the time the
+    // key expires.
+    if validity.expires > ctx.year() {
+      let then = ctx.with_year(validity.expires);
+      if let Some(hash) = hash_function {
+        if guide.validate_hash_function(ctx, hash).is_ok()
+          && guide.validate_hash_function(then, hash).is_err()
+        {
+          audit.warn(locale.expires(Use::HashFunction, &hash.to_string(), validity.expires));
+        }
+      }
+      if guide
+        .validate_signature_algorithm(ctx, signature_algorithm)
+        .is_ok()
+        && guide
+          .validate_signature_algorithm(then, signature_algorithm)
+          .is_err()
+      {
+        audit.warn(locale.expires(
+          Use::SignatureAlgorithm,
+          &signature_algorithm.to_string(),
+          validity.expires,
+        ));
+      }
+    }
+  }
+  audit
+}
+
+/// Audits the keys and certificates found in a repository or image.
+/// Files that look like keys but cannot be read are skipped.
+pub fn assess_blobs(audiences: &Audiences, blobs: Vec<Blob>, report: &mut Report) {
+  for blob in blobs {
+    match blob.reader.read_bytes(&blob.data) {
+      Ok(key) => {
+        for audit in audit_audiences(audiences, |settings| {
+          audit_key(settings, blob.reader, &blob.label, key.as_ref())
+        }) {
+          report.push(audit);
+        }
+      },
+      // Executables and modules are only of interest when signed.
+      Err(Error::Unsigned) => {},
+      Err(err) => report.skip(err.in_file(blob.label)),
+    }
+  }
+}
+
+/// Returns the weakest primitive that fails, or else the weakest, of
+/// those that were assessed.
+pub fn weakest<T>(assessed: &[(T, Verdict<T>)]) -> Option<&(T, Verdict<T>)> {
+  assessed
+    .iter()
+    .min_by_key(|(_, verdict)| (verdict.compliant, verdict.security))
+}
+
+/// Returns the language of the messages as set by the environment,
+/// falling back to English if there is no translation for it.
+pub fn locale() -> Locale {
+  // The variables are checked in the order of precedence POSIX gives
+  // them.
+  ["LC_ALL", "LC_MESSAGES", "LANG"]
+    .iter()
+    .filter_map(|name| env::var(name).ok())
+    .find(|value| !value.is_empty())
+    .and_then(|value| Locale::from_name(&value))
+    .unwrap_or_default()
+}
@@ -0,0 +1,127 @@
+//! Assess keys for several audiences in one scan.
+//!
+//! An audience names a context that keys are assessed in, such as keys
+//! used internally until 2025 or archives that must stay protected
+//! until 2040, and may set a guide of its own. Audiences are defined in
+//! the configuration file of the user, which is
+//! `$XDG_CONFIG_HOME/wardstone/config.json` or otherwise
+//! `~/.config/wardstone/config.json`:
+//!
+//! ```json
+//! {
+//!   "audiences": {
+//!     "internal-2025": { "year": 2025 },
+//!     "archival-2040": { "security": 128, "year": 2040 },
+//!     "quantum-2035": { "guide": "cnsa", "year": 2035 },
+//!     "firmware": { "year": 2030, "advisories": ["nonce-sensitivity"] }
+//!   }
+//! }
+//! ```
+//!
+//! The security defaults to that of the guide and the year to 2023, as
+//! on the command line. An audience may list the implementation hazards
+//! it is warned about (see [`wardstone_core::advisory`]), in place of
+//! every hazard the guide raises, and an empty list silences them.
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+use serde::Deserialize;
+use wardstone_core::advisory::{Hazard, Hazards};
+use wardstone_core::context::Context;
+use wardstone_core::primitive::Security;
+
+use crate::guide::Guide;
+use crate::key::Error;
+
+/// An audience as written in the configuration file.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Entry {
+  advisories: Option<Vec<String>>,
+  guide: Option<String>,
+  #[serde(default)]
+  security: Security,
+  #[serde(default = "default_year")]
+  year: u16,
+}
+
+fn default_year() -> u16 {
+  2023
+}
+
+/// The contents of the configuration file.
+#[derive(Default, Deserialize)]
+pub struct Config {
+  #[serde(default)]
+  audiences: BTreeMap<String, Entry>,
+}
+
+/// A context to assess keys in, along with the guide to assess them
+/// against if it is not the one given on the command line.
+pub struct Audience {
+  pub name: String,
+  pub ctx: Context,
+  pub guide: Option<Guide>,
+  /// The hazards to raise advisories for if not those of the guide.
+  pub hazards: Option<Hazards>,
+}
+
+impl Config {
+  /// Reads the configuration file of the user. A missing file is the
+  /// same as an empty one.
+  pub fn open() -> Result<Self, Error> {
+    let dir = env::var_os("XDG_CONFIG_HOME")
+      .filter(|dir| !dir.is_empty())
+      .map(PathBuf::from)
+      .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")));
+    match dir.map(|dir| dir.join("wardstone").join("config.json")) {
+      Some(path) if path.exists() => Self::at(&path),
+      _ => Ok(Self::default()),
+    }
+  }
+
+  /// Reads the configuration file at `path`.
+  pub fn at(path: &Path) -> Result<Self, Error> {
+    let data = fs::read(path).map_err(|err| Error::Config(err.to_string()))?;
+    serde_json::from_slice(&data).map_err(|err| Error::Config(err.to_string()))
+  }
+
+  /// Returns the audience with the given name.
+  pub fn audience(&self, name: &str) -> Result<Audience, Error> {
+    let entry = self
+      .audiences
+      .get(name)
+      .ok_or_else(|| Error::Config(format!("no audience named {}", name)))?;
+    let guide = entry
+      .guide
+      .as_deref()
+      .map(|guide| {
+        Guide::from_str(guide, true)
+          .map_err(|_| Error::Config(format!("unknown guide {} for {}", guide, name)))
+      })
+      .transpose()?;
+    let hazards = entry
+      .advisories
+      .as_ref()
+      .map(|hazards| {
+        hazards
+          .iter()
+          .map(|hazard| {
+            hazard
+              .parse::<Hazard>()
+              .map_err(|err| Error::Config(format!("{} for {}", err, name)))
+          })
+          .collect::<Result<Hazards, Error>>()
+      })
+      .transpose()?;
+    Ok(Audience {
+      name: name.to_string(),
+      ctx: Context::new(entry.security, entry.year),
+      guide,
+      hazards,
+    })
+  }
+}
@@ -0,0 +1,238 @@
+//! Remember what was read from keys between scans.
+//!
+//! Scanning a large directory again usually finds most keys unchanged.
+//! The primitives read from every key are kept in a file in the cache
+//! directory of the user, keyed by the SHA-256 digest of the contents of
+//! the key, so that a key is only parsed again once it changes. Verdicts
+//! are not kept since assessing the primitives is cheap, which lets the
+//! same cache serve every guide and context. The cache is discarded when
+//! the version of the application changes because a newer version may
+//! read keys differently.
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use ring::digest::{self, SHA256};
+use serde::{Deserialize, Serialize};
+use wardstone_core::primitive::asymmetric::Asymmetric;
+use wardstone_core::primitive::ecc::Ecc;
+use wardstone_core::primitive::ffc::Ffc;
+use wardstone_core::primitive::hash::Hash;
+use wardstone_core::primitive::hybrid::Hybrid;
+use wardstone_core::primitive::ifc::Ifc;
+use wardstone_core::primitive::pqc::Pqc;
+use wardstone_core::primitive::stateful::Stateful;
+
+use crate::input;
+use crate::key::{Error, Key, Location, Validity};
+use crate::reader::ArtifactReader;
+
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// A hash function in the form it is written to the cache.
+#[derive(Clone, Copy, Deserialize, Serialize)]
+struct HashFunction {
+  id: u16,
+  n: u16,
+}
+
+/// An asymmetric primitive in the form it is written to the cache.
+#[derive(Clone, Copy, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+enum Algorithm {
+  Ecc { id: u16, f: u16 },
+  Ffc { id: u16, l: u16, n: u16 },
+  Ifc { id: u16, k: u16 },
+  Pqc { id: u16, security: u16 },
+  Hybrid { pqc: (u16, u16), ecc: (u16, u16) },
+  Stateful { id: u16, n: u16 },
+}
+
+impl From<Hash> for HashFunction {
+  fn from(hash: Hash) -> Self {
+    Self {
+      id: hash.id,
+      n: hash.n,
+    }
+  }
+}
+
+impl From<HashFunction> for Hash {
+  fn from(hash: HashFunction) -> Self {
+    Hash::new(hash.id, hash.n)
+  }
+}
+
+impl From<Asymmetric> for Algorithm {
+  fn from(asymmetric: Asymmetric) -> Self {
+    match asymmetric {
+      Asymmetric::Ecc(Ecc { id, f }) => Self::Ecc { id, f },
+      Asymmetric::Ffc(Ffc { id, l, n }) => Self::Ffc { id, l, n },
+      Asymmetric::Ifc(Ifc { id, k }) => Self::Ifc { id, k },
+      Asymmetric::Pqc(Pqc { id, security }) => Self::Pqc { id, security },
+      Asymmetric::Hybrid(Hybrid { pqc, ecc }) => Self::Hybrid {
+        pqc: (pqc.id, pqc.security),
+        ecc: (ecc.id, ecc.f),
+      },
+      Asymmetric::Stateful(Stateful { id, n }) => Self::Stateful { id, n },
+    }
+  }
+}
+
+impl From<Algorithm> for Asymmetric {
+  fn from(algorithm: Algorithm) -> Self {
+    match algorithm {
+      Algorithm::Ecc { id, f } => Ecc { id, f }.into(),
+      Algorithm::Ffc { id, l, n } => Ffc { id, l, n }.into(),
+      Algorithm::Ifc { id, k } => Ifc { id, k }.into(),
+      Algorithm::Pqc { id, security } => Pqc { id, security }.into(),
+      Algorithm::Hybrid { pqc, ecc } => {
+        Hybrid::new(Pqc::new(pqc.0, pqc.1), Ecc::new(ecc.0, ecc.1)).into()
+      },
+      Algorithm::Stateful { id, n } => Stateful { id, n }.into(),
+    }
+  }
+}
+
+/// What is read from a key in order to assess it.
+#[derive(Clone, Deserialize, Serialize)]
+struct Facts {
+  hash_function: Option<HashFunction>,
+  signature_algorithm: Algorithm,
+  issuer_key: Option<Algorithm>,
+  validity: Option<Validity>,
+  location: Option<Location>,
+  #[serde(default)]
+  explicit_curve: bool,
+}
+
+/// A key as remembered by the cache.
+struct Cached {
+  facts: Facts,
+}
+
+impl Cached {
+  fn of(key: &dyn Key) -> Self {
+    Self {
+      facts: Facts {
+        hash_function: key.hash_function().map(HashFunction::from),
+        signature_algorithm: key.signature_algorithm().into(),
+        issuer_key: key.issuer_key().map(Algorithm::from),
+        validity: key.validity(),
+        location: key.location(),
+        explicit_curve: key.explicit_curve(),
+      },
+    }
+  }
+}
+
+impl Key for Cached {
+  fn hash_function(&self) -> Option<Hash> {
+    self.facts.hash_function.map(Hash::from)
+  }
+
+  fn signature_algorithm(&self) -> Asymmetric {
+    self.facts.signature_algorithm.into()
+  }
+
+  fn issuer_key(&self) -> Option<Asymmetric> {
+    self.facts.issuer_key.map(Asymmetric::from)
+  }
+
+  fn validity(&self) -> Option<Validity> {
+    self.facts.validity
+  }
+
+  fn location(&self) -> Option<Location> {
+    self.facts.location
+  }
+
+  fn explicit_curve(&self) -> bool {
+    self.facts.explicit_curve
+  }
+}
+
+/// The contents of the cache file.
+#[derive(Deserialize, Serialize)]
+struct Contents {
+  version: String,
+  keys: HashMap<String, Facts>,
+}
+
+/// The keys read in previous scans.
+pub struct Cache {
+  path: PathBuf,
+  keys: Mutex<HashMap<String, Facts>>,
+}
+
+impl Cache {
+  /// Opens the cache in the cache directory of the user, which is
+  /// `$XDG_CACHE_HOME` or otherwise `~/.cache`, or returns `None` if the
+  /// directory is not known.
+  pub fn open() -> Option<Self> {
+    let dir = env::var_os("XDG_CACHE_HOME")
+      .filter(|dir| !dir.is_empty())
+      .map(PathBuf::from)
+      .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))?;
+    Some(Self::at(dir.join("wardstone").join("keys.json")))
+  }
+
+  /// Opens the cache kept in the file at `path`. A cache that cannot be
+  /// read or was written by another version starts out empty.
+  pub fn at(path: PathBuf) -> Self {
+    let keys = fs::read(&path)
+      .ok()
+      .and_then(|data| serde_json::from_slice::<Contents>(&data).ok())
+      .filter(|contents| contents.version == VERSION)
+      .map(|contents| contents.keys)
+      .unwrap_or_default();
+    Self {
+      path,
+      keys: Mutex::new(keys),
+    }
+  }
+
+  /// Reads the key at `path` with `reader` unless a key with the same
+  /// contents was read in the same format before.
+  pub fn read(&self, reader: &dyn ArtifactReader, path: &Path) -> Result<Box<dyn Key>, Error> {
+    // The same contents may be read in different formats.
+    let id = format!("{}:{}", reader.name(), fingerprint(&input::open(path)?));
+    if let Some(facts) = self.keys().get(&id) {
+      let facts = facts.clone();
+      return Ok(Box::new(Cached { facts }));
+    }
+    let key = Cached::of(reader.read(path)?.as_ref());
+    self.keys().insert(id, key.facts.clone());
+    Ok(Box::new(key))
+  }
+
+  /// Writes the cache back to its file.
+  pub fn save(&self) -> Result<(), Error> {
+    let contents = Contents {
+      version: VERSION.to_string(),
+      keys: self.keys().clone(),
+    };
+    let data = serde_json::to_vec(&contents).map_err(|err| Error::Cache(err.to_string()))?;
+    if let Some(dir) = self.path.parent() {
+      fs::create_dir_all(dir).map_err(|err| Error::Cache(err.to_string()))?;
+    }
+    fs::write(&self.path, data).map_err(|err| Error::Cache(err.to_string()))
+  }
+
+  fn keys(&self) -> MutexGuard<'_, HashMap<String, Facts>> {
+    // The map is left consistent even if a thread panicked while
+    // holding the lock.
+    self.keys.lock().unwrap_or_else(|err| err.into_inner())
+  }
+}
+
+/// Returns the SHA-256 digest of `data` in hexadecimal.
+fn fingerprint(data: &[u8]) -> String {
+  digest::digest(&SHA256, data)
+    .as_ref()
+    .iter()
+    .map(|b| format!("{:02x}", b))
+    .collect()
+}
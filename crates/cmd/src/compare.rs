@@ -0,0 +1,84 @@
+//! Contrast primitives by how each guide assesses them.
+use std::fmt::{self, Display, Formatter};
+
+use clap::ValueEnum;
+use serde_json::json;
+
+use crate::guide::Guide;
+use crate::list::Item;
+
+/// The security and compliance status of several primitives side by
+/// side.
+pub struct Comparison {
+  items: Vec<Item>,
+  json: bool,
+}
+
+impl Comparison {
+  pub fn new(items: Vec<Item>, json: bool) -> Self {
+    Self { items, json }
+  }
+
+  pub fn to_json_string(&self) -> String {
+    json!({ "comparison": &self.items }).to_string()
+  }
+
+  /// Prints the comparison to the standard output.
+  pub fn print(&self) {
+    if self.json {
+      println!("{}", self.to_json_string())
+    } else {
+      print!("{}", self)
+    }
+  }
+}
+
+impl Display for Comparison {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    let mut rows = vec![(
+      "",
+      self
+        .items
+        .iter()
+        .map(|item| item.name.to_string())
+        .collect::<Vec<_>>(),
+    )];
+    rows.push((
+      "security",
+      self
+        .items
+        .iter()
+        .map(|item| {
+          item
+            .parameters
+            .get("security")
+            .map_or(String::new(), u16::to_string)
+        })
+        .collect(),
+    ));
+    for guide in Guide::value_variants() {
+      let row = self
+        .items
+        .iter()
+        .map(|item| match item.compliant.get(guide.name()) {
+          Some(true) => "ok".to_string(),
+          Some(false) => "fail".to_string(),
+          None => String::new(),
+        })
+        .collect();
+      rows.push((guide.name(), row));
+    }
+    let label = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+    let widths: Vec<_> = (0..self.items.len())
+      .map(|i| rows.iter().map(|(_, row)| row[i].len()).max().unwrap_or(0))
+      .collect();
+    for (name, row) in rows.iter() {
+      let mut s = format!("{:label$}", name);
+      for (cell, width) in row.iter().zip(widths.iter()) {
+        s.push_str(format!("  {:width$}", cell).as_str());
+      }
+      writeln!(f, "{}", s.trim_end())?;
+    }
+    Ok(())
+  }
+}
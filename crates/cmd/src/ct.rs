@@ -0,0 +1,159 @@
+//! Find the certificates issued for a domain in Certificate
+//! Transparency logs.
+//!
+//! Every publicly trusted certificate is submitted to Certificate
+//! Transparency logs (RFC 6962) before it is issued, so the logs list
+//! certificates that the owner of a domain may not know were issued for
+//! it. Rather than reading the logs themselves, which are only indexed
+//! by time, an aggregator that indexes them by name is searched. The
+//! aggregator is expected to answer like [crt.sh] does:
+//!
+//! - `GET /?q=<domain>&output=json` lists the entries naming the domain
+//!   as objects with the fields `id`, `issuer_name` and `serial_number`,
+//!   and `&exclude=expired` leaves out expired certificates.
+//! - `GET /?d=<id>` downloads the PEM encoded certificate of an entry.
+//!
+//! A certificate is logged once as a precertificate and again once it is
+//! issued, so entries with the same issuer and serial number are only
+//! downloaded once.
+//!
+//! [crt.sh]: https://crt.sh
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use crate::assess::{audit_audiences, audit_key, Audiences};
+use crate::http::Endpoint;
+use crate::key::certificate::Certificate;
+use crate::key::Error;
+use crate::reader::X509Reader;
+use crate::report::Report;
+
+/// The aggregator that is searched unless another one is given.
+pub const CRT_SH: &str = "https://crt.sh";
+
+/// An entry of a log as listed by the aggregator.
+#[derive(Debug, Deserialize)]
+struct Entry {
+  id: u64,
+  #[serde(default)]
+  issuer_name: String,
+  #[serde(default)]
+  serial_number: String,
+}
+
+/// A certificate found in the logs.
+pub struct Logged {
+  /// The URL of the certificate at the aggregator.
+  pub label: PathBuf,
+  pub certificate: Result<Certificate, Error>,
+}
+
+/// Checks that `domain` is a name that can be searched for, which may
+/// start with a wildcard label.
+pub fn parse_domain(domain: &str) -> Result<String, String> {
+  let name = domain.strip_prefix("*.").unwrap_or(domain);
+  let valid = !name.is_empty()
+    && name.split('.').all(|label| {
+      !label.is_empty()
+        && label.len() <= 63
+        && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    });
+  if !valid {
+    return Err(format!("not a domain name: {}", domain));
+  }
+  Ok(domain.to_ascii_lowercase())
+}
+
+/// Searches the aggregator for the certificates issued for `domain`, or
+/// for any name below it if `subdomains` is set, and downloads at most
+/// `limit` of them, newest first.
+pub fn search(
+  aggregator: &str,
+  domain: &str,
+  subdomains: bool,
+  expired: bool,
+  limit: Option<usize>,
+) -> Result<Vec<Logged>, Error> {
+  let aggregator = aggregator.trim_end_matches('/');
+  // The aggregator matches `%` as any sequence of characters, which is
+  // escaped in the URL.
+  let query = if subdomains {
+    format!("%25.{}", domain)
+  } else {
+    domain.replace('*', "%25")
+  };
+  let mut url = format!("{}/?q={}&output=json", aggregator, query);
+  if !expired {
+    url.push_str("&exclude=expired");
+  }
+  let mut entries: Vec<Entry> = serde_json::from_slice(&get(&url)?)
+    .map_err(|err| Error::Ct(format!("unexpected response from {}: {}", aggregator, err)))?;
+  entries.sort_by_key(|entry| Reverse(entry.id));
+  let mut seen = HashSet::new();
+  entries.retain(|entry| {
+    entry.serial_number.is_empty()
+      || seen.insert((entry.issuer_name.clone(), entry.serial_number.clone()))
+  });
+  entries.truncate(limit.unwrap_or(usize::MAX));
+  Ok(
+    entries
+      .into_iter()
+      .map(|entry| Logged {
+        label: PathBuf::from(format!("{}/?id={}", aggregator, entry.id)),
+        certificate: get(&format!("{}/?d={}", aggregator, entry.id))
+          .and_then(|pem| Certificate::from_bytes(&pem)),
+      })
+      .collect(),
+  )
+}
+
+/// Audits the certificates the aggregator finds for every domain in
+/// `domains` for every audience, searching as [`search`] does. A domain
+/// that cannot be searched or a certificate that cannot be read is
+/// skipped.
+pub fn assess(
+  audiences: &Audiences,
+  aggregator: &str,
+  domains: &[String],
+  subdomains: bool,
+  expired: bool,
+  limit: Option<usize>,
+  report: &mut Report,
+) {
+  for domain in domains {
+    let logged = match search(aggregator, domain, subdomains, expired, limit) {
+      Ok(logged) => logged,
+      Err(err) => {
+        report.skip(err.in_file(domain));
+        continue;
+      },
+    };
+    for Logged { label, certificate } in logged {
+      match certificate {
+        Ok(certificate) => {
+          for audit in audit_audiences(audiences, |settings| {
+            audit_key(settings, &X509Reader, &label, &certificate)
+          }) {
+            report.push(audit);
+          }
+        },
+        Err(err) => report.skip(err.in_file(label)),
+      }
+    }
+  }
+}
+
+fn get(url: &str) -> Result<Vec<u8>, Error> {
+  let endpoint = Endpoint::parse(url).map_err(Error::Ct)?;
+  let response = endpoint.get(&[])?;
+  match response.status {
+    200 => response.bytes(),
+    status => Err(Error::Ct(format!(
+      "{} responded with {} to {}",
+      endpoint.host, status, endpoint.target
+    ))),
+  }
+}
@@ -0,0 +1,519 @@
+//! Read the 802.1X and MACsec settings of wpa_supplicant and hostapd.
+//!
+//! Both keep their settings as `name=value` lines. wpa_supplicant groups
+//! the settings of every network it may join in a `network={ ... }`
+//! block, and hostapd keeps those of the interface it serves in a single
+//! file, with the EAP methods its integrated server offers listed in
+//! the file named by `eap_user_file`.
+//!
+//! Every network, or hostapd file, is read into a [`Profile`]. The
+//! settings that are unsafe under any guide, such as EAP-MD5 or TLS 1.0,
+//! are returned as [`Issue`]s while the primitives that depend on the
+//! guide, such as the MACsec cipher suite, are left to the caller.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use wardstone_core::primitive::hash::{Hash, MD4, MD5};
+use wardstone_core::primitive::symmetric::{Mode, Symmetric, AES128, AES256};
+use wardstone_core::standard::rule::Citation;
+
+use crate::assess::{advise_cipher, audit_audiences, audit_key, Audiences, Settings};
+use crate::key::Error;
+use crate::reader::{ArtifactReader, X509Reader};
+use crate::report::{Audit, Report};
+
+/// The EAP methods that run inside a TLS tunnel or are TLS themselves.
+const TLS_METHODS: [&str; 5] = ["TLS", "PEAP", "TTLS", "FAST", "TEAP"];
+
+/// The settings that name the server a supplicant expects to talk to.
+const SERVER_NAMES: [&str; 4] = [
+  "domain_suffix_match",
+  "domain_match",
+  "subject_match",
+  "altsubject_match",
+];
+
+/// Which end of 802.1X the settings are for.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Role {
+  /// wpa_supplicant, which authenticates to the network.
+  Supplicant,
+  /// hostapd, which authenticates the supplicant.
+  Authenticator,
+}
+
+/// A setting that is unsafe whatever the guide.
+pub struct Issue {
+  /// The setting at fault, such as `eap=MD5`.
+  pub setting: String,
+  pub message: String,
+  pub citation: Option<Citation>,
+}
+
+/// The settings of a network of wpa_supplicant or of a hostapd file.
+pub struct Profile {
+  pub role: Role,
+  /// The file, followed by the network for wpa_supplicant, such as
+  /// `wpa_supplicant.conf:corp`.
+  pub label: PathBuf,
+  /// The directory relative paths are resolved against.
+  dir: PathBuf,
+  settings: Vec<(String, String)>,
+  /// The outer EAP methods an integrated hostapd server offers.
+  offered: Vec<String>,
+}
+
+/// Returns the profiles in the configuration file at `path`, which is
+/// taken to be for wpa_supplicant if it has a `network` block.
+pub fn profiles(path: &Path) -> Result<Vec<Profile>, Error> {
+  let text = fs::read_to_string(path)?;
+  let dir = path.parent().unwrap_or(Path::new("")).to_path_buf();
+  let mut global = Vec::new();
+  let mut networks: Vec<Vec<(String, String)>> = Vec::new();
+  let mut inside = false;
+  for (i, line) in text.lines().enumerate() {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+      continue;
+    }
+    if line == "}" {
+      if !inside {
+        return Err(Error::Eap(format!("line {}: unexpected }}", i + 1)));
+      }
+      inside = false;
+      continue;
+    }
+    let (name, value) = line
+      .split_once('=')
+      .ok_or_else(|| Error::Eap(format!("line {}: expected name=value", i + 1)))?;
+    let (name, value) = (name.trim(), unquote(value.trim()));
+    if name == "network" && value == "{" {
+      inside = true;
+      networks.push(Vec::new());
+    } else if inside {
+      networks
+        .last_mut()
+        .expect("a network is open")
+        .push((name.to_string(), value.to_string()));
+    } else {
+      global.push((name.to_string(), value.to_string()));
+    }
+  }
+  if inside {
+    return Err(Error::Eap("network block is not closed".to_string()));
+  }
+  if networks.is_empty() {
+    let mut profile = Profile {
+      role: Role::Authenticator,
+      label: path.to_path_buf(),
+      dir,
+      settings: global,
+      offered: Vec::new(),
+    };
+    if profile.get("eap_server") == Some("1") {
+      if let Some(users) = profile.path_of("eap_user_file") {
+        profile.offered = offered_methods(&fs::read_to_string(users)?);
+      }
+    }
+    return Ok(vec![profile]);
+  }
+  Ok(
+    networks
+      .into_iter()
+      .enumerate()
+      .map(|(i, settings)| {
+        let name = settings
+          .iter()
+          .find(|(name, _)| name == "ssid")
+          .map_or_else(|| format!("network {}", i + 1), |(_, ssid)| ssid.clone());
+        Profile {
+          role: Role::Supplicant,
+          label: format!("{}:{}", path.display(), name).into(),
+          dir: dir.clone(),
+          settings,
+          offered: Vec::new(),
+        }
+      })
+      .collect(),
+  )
+}
+
+/// Removes the quotes around a string value.
+fn unquote(value: &str) -> &str {
+  value
+    .strip_prefix('"')
+    .and_then(|value| value.strip_suffix('"'))
+    .unwrap_or(value)
+}
+
+/// Returns the outer EAP methods listed in a hostapd EAP user file,
+/// where every line is an identity followed by its methods, and lines
+/// ending in `[2]` are for the inner methods of a tunnel.
+fn offered_methods(users: &str) -> Vec<String> {
+  let mut methods = Vec::new();
+  for line in users.lines().map(str::trim) {
+    if line.is_empty() || line.starts_with('#') || line.ends_with("[2]") {
+      continue;
+    }
+    // The identity may be quoted and contain spaces.
+    let rest = match line.strip_prefix('"') {
+      Some(quoted) => quoted.split_once('"').map_or("", |(_, rest)| rest),
+      None => line
+        .split_once(char::is_whitespace)
+        .map_or("", |(_, rest)| rest),
+    };
+    let Some(list) = rest.split_whitespace().next() else {
+      continue;
+    };
+    for method in list.split(',') {
+      let method = method.to_ascii_uppercase();
+      if !methods.contains(&method) {
+        methods.push(method);
+      }
+    }
+  }
+  methods
+}
+
+/// Returns the hash function an EAP method authenticates with, for the
+/// methods that rest on a broken one.
+pub fn method_hash(method: &str) -> Option<Hash> {
+  match method {
+    "MD5" => Some(MD5),
+    // The responses of MS-CHAPv1 are derived from the MD4 hash of the
+    // password.
+    "LEAP" => Some(MD4),
+    _ => None,
+  }
+}
+
+impl Profile {
+  /// Returns the last value of a setting.
+  fn get(&self, name: &str) -> Option<&str> {
+    self
+      .settings
+      .iter()
+      .rev()
+      .find(|(n, _)| n == name)
+      .map(|(_, value)| value.as_str())
+  }
+
+  /// Returns the file named by a setting, resolved against the
+  /// directory of the configuration, unless it names a blob or a key
+  /// store rather than a file.
+  fn path_of(&self, name: &str) -> Option<PathBuf> {
+    let value = self.get(name).filter(|value| !value.is_empty())?;
+    if value.contains("://") {
+      return None;
+    }
+    Some(self.dir.join(value))
+  }
+
+  /// Returns the outer EAP methods of the profile.
+  pub fn methods(&self) -> Vec<String> {
+    match self.role {
+      Role::Supplicant => self
+        .get("eap")
+        .map(|methods| {
+          methods
+            .split_whitespace()
+            .map(str::to_ascii_uppercase)
+            .collect()
+        })
+        .unwrap_or_default(),
+      Role::Authenticator => self.offered.clone(),
+    }
+  }
+
+  /// Returns the certificates the profile names, by the setting they
+  /// are named with, its own first.
+  pub fn certificates(&self) -> Vec<(String, PathBuf)> {
+    let own = match self.role {
+      Role::Supplicant => "client_cert",
+      Role::Authenticator => "server_cert",
+    };
+    [own, "ca_cert"]
+      .into_iter()
+      .filter_map(|name| Some((name.to_string(), self.path_of(name)?)))
+      .collect()
+  }
+
+  /// Returns whether MACsec is configured.
+  fn macsec(&self) -> bool {
+    self
+      .settings
+      .iter()
+      .any(|(name, _)| name.starts_with("macsec_") || name == "mka_cak" || name == "mka_ckn")
+  }
+
+  /// Returns the cipher of the MACsec cipher suite, which is GCM-AES-128
+  /// unless `macsec_csindex` selects GCM-AES-256.
+  pub fn macsec_cipher(&self) -> Option<Symmetric> {
+    if !self.macsec() {
+      return None;
+    }
+    match self.get("macsec_csindex") {
+      Some("1") => Some(AES256),
+      _ => Some(AES128),
+    }
+  }
+
+  /// Returns the cipher that a pre-shared MACsec connectivity
+  /// association key is used with, by its length.
+  pub fn cak_cipher(&self) -> Option<Symmetric> {
+    match self.get("mka_cak")?.len() {
+      32 => Some(AES128),
+      64 => Some(AES256),
+      _ => None,
+    }
+  }
+
+  /// Returns whether the EAP methods of the profile use TLS.
+  fn uses_tls(&self) -> bool {
+    self
+      .methods()
+      .iter()
+      .any(|method| TLS_METHODS.contains(&method.as_str()))
+  }
+
+  /// Returns whether a version of TLS is enabled on purpose, or `None`
+  /// if it is left to the default.
+  fn tls_version(&self, version: &str) -> Option<bool> {
+    match self.role {
+      Role::Supplicant => {
+        let flag = format!("tls_disable_tlsv{}=", version.replace('.', "_"));
+        let phase1 = self.get("phase1")?;
+        let at = phase1.find(&flag)?;
+        Some(phase1[at + flag.len()..].starts_with('0'))
+      },
+      Role::Authenticator => {
+        let flags = self.get("tls_flags")?;
+        if flags.contains(&format!("[ENABLE-TLSv{}]", version)) {
+          Some(true)
+        } else if flags.contains(&format!("[DISABLE-TLSv{}]", version)) {
+          Some(false)
+        } else {
+          None
+        }
+      },
+    }
+  }
+
+  /// Returns the settings that are unsafe whatever the guide.
+  pub fn issues(&self) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let methods = self.methods();
+    for method in &methods {
+      let message = match method.as_str() {
+        "MD5" => "EAP-MD5 neither authenticates the server nor derives keys, and its responses give away the password to offline dictionary attacks",
+        "LEAP" => "LEAP rests on MS-CHAPv1, whose responses give away the password to offline dictionary attacks",
+        _ => continue,
+      };
+      issues.push(Issue {
+        setting: format!("eap={}", method),
+        message: message.to_string(),
+        citation: Some(Citation {
+          document: "RFC 4017",
+          section: "§2.2",
+          url: Some("https://datatracker.ietf.org/doc/html/rfc4017#section-2.2"),
+        }),
+      });
+    }
+    if self.uses_tls() {
+      for (version, section, url) in [
+        (
+          "1.0",
+          "§4",
+          "https://datatracker.ietf.org/doc/html/rfc8996#section-4",
+        ),
+        (
+          "1.1",
+          "§5",
+          "https://datatracker.ietf.org/doc/html/rfc8996#section-5",
+        ),
+      ] {
+        if self.tls_version(version) == Some(true) {
+          issues.push(Issue {
+            setting: self.tls_setting().to_string(),
+            message: format!("TLS {} is enabled, which is deprecated", version),
+            citation: Some(Citation {
+              document: "RFC 8996",
+              section,
+              url: Some(url),
+            }),
+          });
+        }
+      }
+      let validates = self
+        .get("ca_cert")
+        .is_some_and(|ca| !ca.is_empty() && !ca.starts_with("probe://"))
+        || self.get("ca_path").is_some();
+      if self.role == Role::Supplicant && !validates {
+        issues.push(Issue {
+          setting: "ca_cert".to_string(),
+          message: "the certificate of the server is not validated, so a rogue access point can capture the credentials".to_string(),
+          citation: None,
+        });
+      }
+    }
+    if self.get("macsec_integ_only") == Some("1") {
+      issues.push(Issue {
+        setting: "macsec_integ_only=1".to_string(),
+        message: "MACsec only protects the integrity of frames and does not encrypt them"
+          .to_string(),
+        citation: None,
+      });
+    }
+    issues
+  }
+
+  /// Returns the settings that deserve attention without being unsafe.
+  pub fn warnings(&self) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if self.uses_tls() {
+      let defaults: Vec<_> = ["1.0", "1.1"]
+        .into_iter()
+        .filter(|version| self.tls_version(version).is_none())
+        .collect();
+      if !defaults.is_empty() {
+        warnings.push(format!(
+          "TLS {} is not disabled in {}, which older versions of {} allow by default",
+          defaults.join(" and "),
+          self.tls_setting(),
+          match self.role {
+            Role::Supplicant => "wpa_supplicant",
+            Role::Authenticator => "hostapd",
+          }
+        ));
+      }
+      if self.role == Role::Supplicant
+        && self.get("ca_cert").is_some()
+        && SERVER_NAMES.iter().all(|name| self.get(name).is_none())
+      {
+        warnings.push(
+          "the name of the server is not checked, so any certificate issued by the CA is accepted; set domain_suffix_match"
+            .to_string(),
+        );
+      }
+    }
+    if self.macsec() && self.get("macsec_policy") != Some("1") {
+      warnings.push(
+        "MACsec is not required (macsec_policy=1), so frames are sent unprotected when MKA fails"
+          .to_string(),
+      );
+    }
+    warnings
+  }
+
+  /// Returns the setting that enables and disables versions of TLS.
+  fn tls_setting(&self) -> &'static str {
+    match self.role {
+      Role::Supplicant => "phase1",
+      Role::Authenticator => "tls_flags",
+    }
+  }
+}
+
+/// Audits the certificates named by the 802.1X and MACsec settings of
+/// every profile, along with the settings themselves, which are
+/// reported with its first certificate. A file or certificate that
+/// cannot be read is skipped.
+pub fn assess(audiences: &Audiences, paths: &[PathBuf], report: &mut Report) {
+  for path in paths {
+    let profiles = match profiles(path) {
+      Ok(profiles) => profiles,
+      Err(err) => {
+        report.skip(err.in_file(path));
+        continue;
+      },
+    };
+    for profile in profiles {
+      let mut reported = false;
+      for (setting, file) in profile.certificates() {
+        let label = PathBuf::from(format!("{}:{}", profile.label.display(), setting));
+        let key = match X509Reader.read(&file) {
+          Ok(key) => key,
+          Err(err) => {
+            report.skip(err.in_file(label));
+            continue;
+          },
+        };
+        let first = !reported;
+        reported = true;
+        for audit in audit_audiences(audiences, |settings| {
+          let mut audit = audit_key(settings, &X509Reader, &label, key.as_ref());
+          if first {
+            audit_profile(settings, &profile, &mut audit);
+          }
+          audit
+        }) {
+          report.push(audit);
+        }
+      }
+      // Every audit is of a key, so settings that name no certificate
+      // are reported as the reason they could not be assessed.
+      let issues = profile.issues();
+      if !reported && !issues.is_empty() {
+        let issues: Vec<_> = issues
+          .iter()
+          .map(|issue| format!("{} ({})", issue.message, issue.setting))
+          .collect();
+        report.skip(
+          Error::Eap(format!(
+            "no certificate is named to report them with, and {}",
+            issues.join("; ")
+          ))
+          .in_file(&profile.label),
+        );
+      }
+    }
+  }
+}
+
+/// Assesses the 802.1X and MACsec settings of a profile.
+fn audit_profile(settings: Settings, profile: &Profile, audit: &mut Audit) {
+  let Settings {
+    ctx, guide, locale, ..
+  } = settings;
+  for issue in profile.issues() {
+    audit.cite(issue.citation);
+    audit.noncompliant_setting(&issue.setting, issue.message);
+  }
+  for method in profile.methods() {
+    if let Some(got) = method_hash(&method) {
+      let verdict = guide.assess_hash_function(ctx, got);
+      audit.cite(verdict.citation);
+      audit.explain(&verdict, ctx, got, locale);
+    }
+  }
+  if let Some(got) = profile.macsec_cipher() {
+    let verdict = guide.assess_symmetric(ctx, got);
+    audit.cite(verdict.citation);
+    audit.explain(&verdict, ctx, got, locale);
+    match verdict.into_result() {
+      Ok(want) => audit.compliant_cipher(got, want),
+      Err(want) => audit.noncompliant_cipher(got, want),
+    }
+    advise_cipher(settings, got, Mode::Gcm, audit);
+  }
+  if let Some(got) = profile.cak_cipher() {
+    let verdict = guide.assess_symmetric(ctx, got);
+    audit.cite(verdict.citation);
+    audit.explain(&verdict, ctx, got, locale);
+    if let Err(want) = verdict.into_result() {
+      audit.noncompliant_setting(
+        "mka_cak",
+        format!(
+          "the pre-shared CAK is used with {} rather than {}",
+          got, want
+        ),
+      );
+    }
+    // The key encryption key derived from the CAK only wraps the keys
+    // that MACsec encrypts with.
+    advise_cipher(settings, got, Mode::Kw, audit);
+  }
+  for warning in profile.warnings() {
+    audit.warn(warning);
+  }
+}
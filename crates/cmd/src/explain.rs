@@ -0,0 +1,53 @@
+//! Describe the rules a guide uses to assess keys.
+use std::fmt::{self, Display, Formatter};
+
+use serde_json::json;
+use wardstone_core::standard::rule::Rule;
+
+/// The thresholds, cutoff years and references for the rules of a
+/// guide.
+pub struct Explanation {
+  rules: Vec<&'static Rule>,
+  json: bool,
+}
+
+impl Explanation {
+  pub fn new(rules: Vec<&'static Rule>, json: bool) -> Self {
+    Self { rules, json }
+  }
+
+  pub fn to_json_string(&self) -> String {
+    json!({ "rules": &self.rules }).to_string()
+  }
+
+  /// Prints the explanation to the standard output.
+  pub fn print(&self) {
+    if self.json {
+      println!("{}", self.to_json_string())
+    } else {
+      print!("{}", self)
+    }
+  }
+}
+
+impl Display for Explanation {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    if self.rules.is_empty() {
+      return writeln!(f, "no rules are documented");
+    }
+    let mut s = String::new();
+    for rule in self.rules.iter() {
+      s.push_str(format!("{}: {}\n", rule.scope, rule.summary).as_str());
+      s.push_str(format!("  id: {}\n", rule.id).as_str());
+      match rule.minimum {
+        Some(minimum) => s.push_str(format!("  minimum security: {} bits\n", minimum).as_str()),
+        None => s.push_str("  minimum security: depends on the year\n"),
+      }
+      if let Some(cutoff) = rule.cutoff {
+        s.push_str(format!("  cutoff year: {}\n", cutoff).as_str());
+      }
+      s.push_str(format!("  reference: {}\n", rule.citation).as_str());
+    }
+    write!(f, "{}", s)
+  }
+}
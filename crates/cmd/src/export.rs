@@ -0,0 +1,93 @@
+//! Export the rules of guides and the primitives they accept as data.
+//!
+//! The tables are generated from the same validation functions that
+//! assess keys, so documentation and governance, risk and compliance
+//! tools built from them cannot drift from the program. They are
+//! written either as JSON or as CSV with one row for every primitive a
+//! rule accepts:
+//!
+//! ```text
+//! guide,rule,scope,minimum,cutoff,document,section,primitive
+//! nist,NIST-FFC-001,ffc,112,2023,NIST SP 800-57 Part 1 Rev. 5,"§5.6.1.1, Table 2, pp. 54-55",dsa_2048_224
+//! ```
+use std::fmt::{self, Display, Formatter};
+
+use serde_json::json;
+use wardstone_core::standard::table::Table;
+
+use crate::guide::Guide;
+
+/// The columns of the CSV output.
+const HEADER: &str = "guide,rule,scope,minimum,cutoff,document,section,primitive";
+
+/// The tables of several guides.
+pub struct Export {
+  tables: Vec<(Guide, Table)>,
+  csv: bool,
+}
+
+impl Export {
+  pub fn new(tables: Vec<(Guide, Table)>, csv: bool) -> Self {
+    Self { tables, csv }
+  }
+
+  pub fn to_json_string(&self) -> String {
+    let tables: Vec<_> = self
+      .tables
+      .iter()
+      .map(|(guide, table)| json!({ "guide": guide.name(), "rules": &table.sections }))
+      .collect();
+    json!({ "tables": tables }).to_string()
+  }
+
+  /// Prints the tables to the standard output.
+  pub fn print(&self) {
+    if self.csv {
+      print!("{}", self)
+    } else {
+      println!("{}", self.to_json_string())
+    }
+  }
+}
+
+/// Quotes a field if it contains a character that CSV reserves.
+fn field(s: &str) -> String {
+  if s.contains([',', '"', '\n']) {
+    format!("\"{}\"", s.replace('"', "\"\""))
+  } else {
+    s.to_string()
+  }
+}
+
+impl Display for Export {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    writeln!(f, "{}", HEADER)?;
+    for (guide, table) in self.tables.iter() {
+      for section in table.sections.iter() {
+        let rule = section.rule;
+        let row = [
+          guide.name().to_string(),
+          rule.id.to_string(),
+          rule.scope.to_string(),
+          rule.minimum.map(|m| m.to_string()).unwrap_or_default(),
+          rule.cutoff.map(|c| c.to_string()).unwrap_or_default(),
+          field(rule.citation.document),
+          field(rule.citation.section),
+        ]
+        .join(",");
+        // A rule that accepts no built-in primitive, or whose primitives
+        // are not listed, still gets a row so that its thresholds are
+        // exported.
+        match section.allowed.as_deref() {
+          Some(allowed) if !allowed.is_empty() => {
+            for name in allowed {
+              writeln!(f, "{},{}", row, field(name))?;
+            }
+          },
+          _ => writeln!(f, "{},", row)?,
+        }
+      }
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,224 @@
+//! Find the keys and certificates embedded in firmware images.
+//!
+//! Firmware is often only available as a binary blob, such as an image
+//! dumped from flash, in which certificates and keys are stored without
+//! any file system around them. They are found by their encoding alone:
+//!
+//! - DER structures start with a SEQUENCE whose length fits in the rest
+//!   of the blob, and are kept if they parse as a certificate, a
+//!   SubjectPublicKeyInfo, an RSAPublicKey (RFC 8017 Appendix A.1.1) or a
+//!   private key of PKCS #8, PKCS #1 or SEC 1.
+//! - PEM blocks start with `-----BEGIN ` and are read by their label.
+//!
+//! This is a heuristic. Random data may look like a short DER structure
+//! now and then, which is why only structures that parse completely are
+//! kept, and keys that are compressed or encrypted are not found at all.
+use std::fmt;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use wardstone_core::primitive::asymmetric::Asymmetric;
+use wardstone_core::primitive::hash::Hash;
+use x509_parser::pem::Pem;
+use x509_parser::prelude::{FromDer, SubjectPublicKeyInfo, X509Certificate};
+use x509_parser::public_key::RSAPublicKey;
+
+use crate::assess::{audit_audiences, audit_key, Audiences};
+use crate::input;
+use crate::key::certificate::Certificate;
+use crate::key::private::PrivateKey;
+use crate::key::{Error, Key};
+use crate::reader::{ArtifactReader, PrivateKeyReader, X509Reader};
+use crate::report::Report;
+
+/// The length of the shortest structure that is looked at, which is that
+/// of an Ed25519 SubjectPublicKeyInfo.
+const MIN_LEN: usize = 44;
+
+/// The smallest RSA modulus in bits that a bare RSAPublicKey is taken to
+/// be, since pairs of small integers are common in any binary.
+const MIN_MODULUS: usize = 512;
+
+/// What was found.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Kind {
+  Certificate,
+  PublicKey,
+  PrivateKey,
+}
+
+impl fmt::Display for Kind {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Certificate => write!(f, "certificate"),
+      Self::PublicKey => write!(f, "public-key"),
+      Self::PrivateKey => write!(f, "private-key"),
+    }
+  }
+}
+
+/// A key or certificate found in a blob.
+pub struct Embedded {
+  pub kind: Kind,
+  /// Where it was found, such as `router.bin:0x3f2c0:certificate`.
+  pub label: PathBuf,
+  /// The offset in bytes from the start of the blob.
+  pub offset: usize,
+  pub key: Result<Box<dyn Key>, Error>,
+}
+
+/// What a structure is, along with its key or why that cannot be read.
+type Carved = (Kind, Result<Box<dyn Key>, Error>);
+
+/// A public key stored on its own, which has no hash function.
+pub(crate) struct PublicKey(pub(crate) Asymmetric);
+
+impl Key for PublicKey {
+  fn hash_function(&self) -> Option<Hash> {
+    None
+  }
+
+  fn signature_algorithm(&self) -> Asymmetric {
+    self.0
+  }
+}
+
+/// Returns the keys and certificates in the blob at `path` in the order
+/// they are found in.
+pub fn carve(path: &Path) -> Result<Vec<Embedded>, Error> {
+  let data = input::open(path)?;
+  let mut found = Vec::new();
+  let mut at = 0;
+  while at < data.len() {
+    let rest = &data[at..];
+    let carved = match rest {
+      [0x30, ..] => der_len(rest)
+        .filter(|&len| len >= MIN_LEN)
+        .and_then(|len| Some((len, identify(&rest[..len])?))),
+      [b'-', ..] if rest.starts_with(b"-----BEGIN ") => pem(rest),
+      _ => None,
+    };
+    match carved {
+      Some((len, (kind, key))) => {
+        found.push(Embedded {
+          kind,
+          label: format!("{}:{:#x}:{}", path.display(), at, kind).into(),
+          offset: at,
+          key,
+        });
+        // What is inside a certificate or key is not looked at again.
+        at += len;
+      },
+      None => at += 1,
+    }
+  }
+  Ok(found)
+}
+
+/// Returns the length of the DER structure at the start of `data`,
+/// including its header, if it fits.
+fn der_len(data: &[u8]) -> Option<usize> {
+  let (header, len) = match *data.get(1)? {
+    len @ 0..=0x7f => (2, len as usize),
+    0x81 => (3, *data.get(2)? as usize),
+    0x82 => (
+      4,
+      u16::from_be_bytes([*data.get(2)?, *data.get(3)?]) as usize,
+    ),
+    0x83 => (
+      5,
+      u32::from_be_bytes([0, *data.get(2)?, *data.get(3)?, *data.get(4)?]) as usize,
+    ),
+    _ => return None,
+  };
+  Some(header + len).filter(|&len| len <= data.len())
+}
+
+/// Tells what a DER structure is. Those that parse as a certificate or
+/// key but whose key cannot be read are still returned, with why.
+fn identify(der: &[u8]) -> Option<Carved> {
+  if matches!(X509Certificate::from_der(der), Ok((rest, _)) if rest.is_empty()) {
+    let key = Certificate::from_bytes(der).map(|key| Box::new(key) as Box<dyn Key>);
+    return Some((Kind::Certificate, key));
+  }
+  if let Ok((rest, spki)) = SubjectPublicKeyInfo::from_der(der) {
+    if rest.is_empty() {
+      let key = Certificate::key(&spki).map(|key| Box::new(PublicKey(key)) as Box<dyn Key>);
+      return Some((Kind::PublicKey, key));
+    }
+  }
+  if let Ok((rest, key)) = RSAPublicKey::from_der(der) {
+    let plausible = matches!(key.exponent, [0x03] | [0x11] | [0x01, 0x00, 0x01]);
+    if rest.is_empty() && plausible && key.key_size() >= MIN_MODULUS {
+      let key = Certificate::rsa(key.key_size()).into();
+      return Some((Kind::PublicKey, Ok(Box::new(PublicKey(key)))));
+    }
+  }
+  match PrivateKey::from_der(der) {
+    Ok(key) => Some((Kind::PrivateKey, Ok(Box::new(key)))),
+    Err(_) => None,
+  }
+}
+
+/// Reads the PEM block at the start of `data` by its label, returning its
+/// length along with what it is.
+fn pem(data: &[u8]) -> Option<(usize, Carved)> {
+  let (pem, len) = Pem::read(Cursor::new(data)).ok()?;
+  let carved = match pem.label.as_str() {
+    "CERTIFICATE" | "TRUSTED CERTIFICATE" | "X509 CERTIFICATE" => {
+      let key = Certificate::from_bytes(&pem.contents).map(|key| Box::new(key) as Box<dyn Key>);
+      (Kind::Certificate, key)
+    },
+    "PUBLIC KEY" | "RSA PUBLIC KEY" => identify(&pem.contents)?,
+    "PRIVATE KEY" | "RSA PRIVATE KEY" | "EC PRIVATE KEY" => {
+      let key = PrivateKey::from_der(&pem.contents).map(|key| Box::new(key) as Box<dyn Key>);
+      (Kind::PrivateKey, key)
+    },
+    "ENCRYPTED PRIVATE KEY" => (
+      Kind::PrivateKey,
+      Err(Error::Unrecognised("encrypted private key".to_string())),
+    ),
+    _ => return None,
+  };
+  Some((len, carved))
+}
+
+/// Audits the keys and certificates found in firmware images. Private
+/// keys are reported as shipping with the image whatever their
+/// primitive. A file or key that cannot be read is skipped.
+pub fn assess(audiences: &Audiences, paths: &[PathBuf], report: &mut Report) {
+  for path in paths {
+    let found = match carve(path) {
+      Ok(found) => found,
+      Err(err) => {
+        report.skip(err.in_file(path));
+        continue;
+      },
+    };
+    for embedded in found {
+      let key = match embedded.key {
+        Ok(key) => key,
+        Err(err) => {
+          report.skip(err.in_file(embedded.label));
+          continue;
+        },
+      };
+      let reader: &dyn ArtifactReader = match embedded.kind {
+        Kind::Certificate => &X509Reader,
+        Kind::PublicKey | Kind::PrivateKey => &PrivateKeyReader,
+      };
+      for audit in audit_audiences(audiences, |settings| {
+        let mut audit = audit_key(settings, reader, &embedded.label, key.as_ref());
+        if embedded.kind == Kind::PrivateKey {
+          audit.warn(
+            "a private key ships with the image, so it is shared by every device that runs it"
+              .to_string(),
+          );
+        }
+        audit
+      }) {
+        report.push(audit);
+      }
+    }
+  }
+}
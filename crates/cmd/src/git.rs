@@ -0,0 +1,128 @@
+//! Find keys and certificates committed to git repositories.
+//!
+//! The tree of the commit that is checked out, or of every commit in
+//! the history, is listed with the `git` command and every file that
+//! looks like a key or certificate is read from the object database, so
+//! the repository does not need to be checked out. A file is read once
+//! however many commits it appears in, and is reported with the first
+//! commit it appears in as `<commit>:<path>`, which is how git names it.
+use std::collections::HashSet;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread;
+
+use crate::key::Error;
+use crate::reader::{Blob, MAX_BLOB_SIZE};
+
+/// A file in the tree of a commit.
+struct Entry {
+  commit: String,
+  path: String,
+  object: String,
+}
+
+/// Returns the files that look like keys or certificates in the tree of
+/// the commit checked out in `repo`, or in that of every commit
+/// reachable from a branch or tag if `history` is set, in the order of
+/// the commits from oldest to newest.
+pub fn sweep(repo: &Path, history: bool) -> Result<Vec<Blob>, Error> {
+  let commits = if history {
+    git(repo, &["rev-list", "--all", "--reverse"])?
+  } else {
+    git(repo, &["rev-parse", "HEAD"])?
+  };
+  let commits = String::from_utf8_lossy(&commits);
+  let mut seen = HashSet::new();
+  let mut entries = Vec::new();
+  for commit in commits.lines() {
+    let tree = git(repo, &["ls-tree", "-r", "-l", "-z", commit])?;
+    for line in tree.split(|&b| b == 0).filter(|line| !line.is_empty()) {
+      let line = String::from_utf8_lossy(line);
+      // Every line reads `<mode> <type> <object> <size>\t<path>`, with
+      // the size padded with spaces.
+      let Some((meta, path)) = line.split_once('\t') else {
+        continue;
+      };
+      let [mode, kind, object, size] = meta.split_whitespace().collect::<Vec<_>>()[..] else {
+        continue;
+      };
+      // Symbolic links are blobs holding the path they point to.
+      if kind != "blob" || mode == "120000" {
+        continue;
+      }
+      if size
+        .parse::<u64>()
+        .map_or(true, |size| size > MAX_BLOB_SIZE)
+      {
+        continue;
+      }
+      if seen.insert(object.to_string()) {
+        entries.push(Entry {
+          commit: commit[..12.min(commit.len())].to_string(),
+          path: path.to_string(),
+          object: object.to_string(),
+        });
+      }
+    }
+  }
+  cat(repo, entries)
+}
+
+/// Runs git in `repo` and returns what it writes to the standard output.
+fn git(repo: &Path, args: &[&str]) -> Result<Vec<u8>, Error> {
+  let output = Command::new("git")
+    .arg("-C")
+    .arg(repo)
+    .args(args)
+    .output()
+    .map_err(|err| Error::Git(err.to_string()))?;
+  if !output.status.success() {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    return Err(Error::Git(stderr.trim().to_string()));
+  }
+  Ok(output.stdout)
+}
+
+/// Reads the files in one run of `git cat-file`, keeping those that
+/// look like keys or certificates.
+fn cat(repo: &Path, entries: Vec<Entry>) -> Result<Vec<Blob>, Error> {
+  let mut child = Command::new("git")
+    .arg("-C")
+    .arg(repo)
+    .args(["cat-file", "--batch"])
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::null())
+    .spawn()
+    .map_err(|err| Error::Git(err.to_string()))?;
+  // The names of the objects are written while their contents are read
+  // so that neither pipe fills up.
+  let mut stdin = child.stdin.take().expect("a piped standard input");
+  let names: String = entries
+    .iter()
+    .map(|entry| format!("{}\n", entry.object))
+    .collect();
+  let writer = thread::spawn(move || stdin.write_all(names.as_bytes()));
+  let mut stdout = BufReader::new(child.stdout.take().expect("a piped standard output"));
+  let mut blobs = Vec::new();
+  for entry in entries {
+    // Every object is written as `<object> blob <size>\n<data>\n`.
+    let mut header = String::new();
+    stdout.read_line(&mut header)?;
+    let size = header
+      .split_whitespace()
+      .nth(2)
+      .and_then(|size| size.parse().ok())
+      .ok_or_else(|| Error::Git(format!("unexpected output: {}", header.trim())))?;
+    let mut data = vec![0; size];
+    stdout.read_exact(&mut data)?;
+    stdout.read_exact(&mut [0])?;
+    let label = format!("{}:{}", entry.commit, entry.path);
+    blobs.extend(Blob::sniff(label, data));
+  }
+  drop(stdout);
+  writer.join().expect("the writer not to panic")?;
+  child.wait()?;
+  Ok(blobs)
+}
@@ -0,0 +1,228 @@
+//! Select the standard or research publication to assess keys against.
+use clap::ValueEnum;
+use wardstone_core::advisory::Hazards;
+use wardstone_core::context::Context;
+use wardstone_core::primitive::asymmetric::Asymmetric;
+use wardstone_core::primitive::hash::Hash;
+use wardstone_core::primitive::symmetric::{Mode, Symmetric};
+use wardstone_core::primitive::threshold::Threshold;
+use wardstone_core::standard::bsi::Bsi;
+use wardstone_core::standard::cnsa::Cnsa;
+use wardstone_core::standard::ecrypt::Ecrypt;
+use wardstone_core::standard::iot::Iot;
+use wardstone_core::standard::lenstra::Lenstra;
+use wardstone_core::standard::nist::Nist;
+use wardstone_core::standard::rule::{self, Rule, Scope};
+use wardstone_core::standard::table::Table;
+use wardstone_core::standard::testing::strong::Strong;
+use wardstone_core::standard::testing::weak::Weak;
+use wardstone_core::standard::verdict::Verdict;
+use wardstone_core::standard::Standard;
+
+// Having this type in the core crate would reduce the amount of case
+// analysis done to find the function to execute but this would run
+// counter to the ability of users to create their own first-class
+// guides/standards.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum Guide {
+  /// BSI TR-02102 series of technical guidelines.
+  Bsi,
+  /// Commercial National Security Algorithm Suites, CNSA 1.0 and
+  /// CNSA 2.0.
+  Cnsa,
+  /// ECRYPT-CSA D5.4 Algorithms, Key Size and Protocols Report.
+  Ecrypt,
+  /// Profile for constrained devices after BSI TR-03116-3 and NIST IR
+  /// 8259A.
+  Iot,
+  /// Key Lengths, Arjen K. Lenstra, The Handbook of Information
+  /// Security, 06/2004.
+  Lenstra,
+  /// NIST Special Publication 800-57 Part 1 Revision 5 standard.
+  Nist,
+  /// Mock standard with a minimum security requirement of at least
+  /// 256-bits.
+  Strong,
+  /// Mock standard with a minimum security requirement of at least
+  /// 64-bits.
+  Weak,
+}
+
+impl Guide {
+  /// Returns the name used to select the guide on the command line.
+  pub fn name(&self) -> &'static str {
+    match self {
+      Self::Bsi => "bsi",
+      Self::Cnsa => "cnsa",
+      Self::Ecrypt => "ecrypt",
+      Self::Iot => "iot",
+      Self::Lenstra => "lenstra",
+      Self::Nist => "nist",
+      Self::Strong => "strong",
+      Self::Weak => "weak",
+    }
+  }
+
+  pub fn validate_hash_function(&self, ctx: Context, hash: Hash) -> Result<Hash, Hash> {
+    match self {
+      Self::Bsi => Bsi::validate_hash(ctx, hash),
+      Self::Cnsa => Cnsa::validate_hash(ctx, hash),
+      Self::Ecrypt => Ecrypt::validate_hash(ctx, hash),
+      Self::Iot => Iot::validate_hash(ctx, hash),
+      Self::Lenstra => Lenstra::validate_hash(ctx, hash),
+      Self::Nist => Nist::validate_hash(ctx, hash),
+      Self::Strong => Strong::validate_hash(ctx, hash),
+      Self::Weak => Weak::validate_hash(ctx, hash),
+    }
+  }
+
+  pub fn validate_symmetric(&self, ctx: Context, key: Symmetric) -> Result<Symmetric, Symmetric> {
+    match self {
+      Self::Bsi => Bsi::validate_symmetric(ctx, key),
+      Self::Cnsa => Cnsa::validate_symmetric(ctx, key),
+      Self::Ecrypt => Ecrypt::validate_symmetric(ctx, key),
+      Self::Iot => Iot::validate_symmetric(ctx, key),
+      Self::Lenstra => Lenstra::validate_symmetric(ctx, key),
+      Self::Nist => Nist::validate_symmetric(ctx, key),
+      Self::Strong => Strong::validate_symmetric(ctx, key),
+      Self::Weak => Weak::validate_symmetric(ctx, key),
+    }
+  }
+
+  pub fn validate_signature_algorithm(
+    &self,
+    ctx: Context,
+    key: Asymmetric,
+  ) -> Result<Asymmetric, Asymmetric> {
+    match self {
+      Self::Bsi => Bsi::validate_asymmetric(ctx, key),
+      Self::Cnsa => Cnsa::validate_asymmetric(ctx, key),
+      Self::Ecrypt => Ecrypt::validate_asymmetric(ctx, key),
+      Self::Iot => Iot::validate_asymmetric(ctx, key),
+      Self::Lenstra => Lenstra::validate_asymmetric(ctx, key),
+      Self::Nist => Nist::validate_asymmetric(ctx, key),
+      Self::Strong => Strong::validate_asymmetric(ctx, key),
+      Self::Weak => Weak::validate_asymmetric(ctx, key),
+    }
+  }
+
+  pub fn validate_threshold(&self, ctx: Context, key: Threshold) -> Result<Threshold, Threshold> {
+    match self {
+      Self::Bsi => Bsi::validate_threshold(ctx, key),
+      Self::Cnsa => Cnsa::validate_threshold(ctx, key),
+      Self::Ecrypt => Ecrypt::validate_threshold(ctx, key),
+      Self::Iot => Iot::validate_threshold(ctx, key),
+      Self::Lenstra => Lenstra::validate_threshold(ctx, key),
+      Self::Nist => Nist::validate_threshold(ctx, key),
+      Self::Strong => Strong::validate_threshold(ctx, key),
+      Self::Weak => Weak::validate_threshold(ctx, key),
+    }
+  }
+
+  pub fn rules(&self) -> &'static [Rule] {
+    match self {
+      Self::Bsi => Bsi::rules(),
+      Self::Cnsa => Cnsa::rules(),
+      Self::Ecrypt => Ecrypt::rules(),
+      Self::Iot => Iot::rules(),
+      Self::Lenstra => Lenstra::rules(),
+      Self::Nist => Nist::rules(),
+      Self::Strong => Strong::rules(),
+      Self::Weak => Weak::rules(),
+    }
+  }
+
+  /// Returns the rules of the guide along with the built-in primitives
+  /// each of them accepts in the given context.
+  pub fn table(&self, ctx: Context) -> Table {
+    match self {
+      Self::Bsi => Table::of::<Bsi>(ctx),
+      Self::Cnsa => Table::of::<Cnsa>(ctx),
+      Self::Ecrypt => Table::of::<Ecrypt>(ctx),
+      Self::Iot => Table::of::<Iot>(ctx),
+      Self::Lenstra => Table::of::<Lenstra>(ctx),
+      Self::Nist => Table::of::<Nist>(ctx),
+      Self::Strong => Table::of::<Strong>(ctx),
+      Self::Weak => Table::of::<Weak>(ctx),
+    }
+  }
+
+  /// Returns the implementation hazards the guide raises advisories for.
+  pub fn hazards(&self) -> Hazards {
+    match self {
+      Self::Bsi => Bsi::hazards(),
+      Self::Cnsa => Cnsa::hazards(),
+      Self::Ecrypt => Ecrypt::hazards(),
+      Self::Iot => Iot::hazards(),
+      Self::Lenstra => Lenstra::hazards(),
+      Self::Nist => Nist::hazards(),
+      Self::Strong => Strong::hazards(),
+      Self::Weak => Weak::hazards(),
+    }
+  }
+
+  /// Returns how many blocks a cipher may encrypt under one key in
+  /// `mode` according to the guide, if that is limited.
+  pub fn block_limit(&self, key: Symmetric, mode: Mode) -> Option<u64> {
+    match self {
+      Self::Bsi => Bsi::block_limit(key, mode),
+      Self::Cnsa => Cnsa::block_limit(key, mode),
+      Self::Ecrypt => Ecrypt::block_limit(key, mode),
+      Self::Iot => Iot::block_limit(key, mode),
+      Self::Lenstra => Lenstra::block_limit(key, mode),
+      Self::Nist => Nist::block_limit(key, mode),
+      Self::Strong => Strong::block_limit(key, mode),
+      Self::Weak => Weak::block_limit(key, mode),
+    }
+  }
+
+  /// Returns the rule that applies to the given type of primitive.
+  pub fn rule(&self, scope: Scope) -> Option<&'static Rule> {
+    self.rules().iter().find(|rule| rule.scope == scope)
+  }
+
+  /// Validates a hash function and cites the rule that the outcome is
+  /// based on.
+  pub fn assess_hash_function(&self, ctx: Context, hash: Hash) -> Verdict<Hash> {
+    Verdict::validate(
+      ctx,
+      hash,
+      |ctx, hash| self.validate_hash_function(ctx, hash),
+      self.rule(Scope::Hash),
+    )
+  }
+
+  /// Validates a symmetric key primitive and cites the rule that the
+  /// outcome is based on.
+  pub fn assess_symmetric(&self, ctx: Context, key: Symmetric) -> Verdict<Symmetric> {
+    Verdict::validate(
+      ctx,
+      key,
+      |ctx, key| self.validate_symmetric(ctx, key),
+      self.rule(Scope::Symmetric),
+    )
+  }
+
+  /// Validates an asymmetric key primitive and cites the rule that the
+  /// outcome is based on.
+  pub fn assess_signature_algorithm(&self, ctx: Context, key: Asymmetric) -> Verdict<Asymmetric> {
+    let rule = rule::asymmetric_rule(self.rules(), key);
+    Verdict::validate(
+      ctx,
+      key,
+      |ctx, key| self.validate_signature_algorithm(ctx, key),
+      rule,
+    )
+  }
+
+  /// Validates a threshold or multi-signature configuration and cites
+  /// the rule that the outcome is based on.
+  pub fn assess_threshold(&self, ctx: Context, key: Threshold) -> Verdict<Threshold> {
+    Verdict::validate(
+      ctx,
+      key,
+      |ctx, key| self.validate_threshold(ctx, key),
+      self.rule(Scope::Ecc),
+    )
+  }
+}
@@ -0,0 +1,302 @@
+//! Make the few HTTP requests wardstone needs without an HTTP client.
+//!
+//! Requests are sent over a fresh connection that is closed once the
+//! response has been read, which is all that notifying a webhook,
+//! pulling an image or listing managed keys takes. HTTPS connections
+//! verify the certificate of the server against the trust store of the
+//! operating system.
+use std::io::{self, BufRead, BufReader, Read, Write};
+
+use crate::key::Error;
+use crate::tls;
+
+/// Where to send a request.
+pub struct Endpoint {
+  pub tls: bool,
+  pub host: String,
+  pub port: u16,
+  /// The path and query of the URL.
+  pub target: String,
+}
+
+/// A response whose body is read as it arrives.
+pub struct Response {
+  pub status: u16,
+  headers: Vec<(String, String)>,
+  body: Box<dyn Read>,
+}
+
+/// A connection to a server, which is encrypted or not.
+trait Stream: Read + Write {}
+
+impl<S: Read + Write> Stream for S {}
+
+impl Endpoint {
+  /// Splits an `http` or `https` URL into where to connect to and what
+  /// to ask for.
+  pub fn parse(url: &str) -> Result<Self, String> {
+    let (tls, rest) = if let Some(rest) = url.strip_prefix("https://") {
+      (true, rest)
+    } else if let Some(rest) = url.strip_prefix("http://") {
+      (false, rest)
+    } else {
+      return Err("only http and https URLs are supported".to_string());
+    };
+    let (authority, target) = match rest.find(['/', '?']) {
+      Some(i) => (&rest[..i], rest[i..].to_string()),
+      None => (rest, "/".to_string()),
+    };
+    let target = if target.starts_with('?') {
+      format!("/{}", target)
+    } else {
+      target
+    };
+    let (host, port) = tls::parse_url(authority)?;
+    // The port defaults to that of HTTPS so plain HTTP needs its own
+    // default. The closing bracket of an IPv6 address tells it apart
+    // from the port.
+    let authority = authority.rsplit('@').next().unwrap_or_default();
+    let explicit = authority
+      .rsplit_once(':')
+      .is_some_and(|(host, _)| !host.starts_with('[') || host.ends_with(']'));
+    let port = if tls || explicit { port } else { 80 };
+    Ok(Self {
+      tls,
+      host,
+      port,
+      target,
+    })
+  }
+
+  /// Returns the host as it is written in the `Host` header.
+  pub fn authority(&self) -> String {
+    if self.host.contains(':') {
+      format!("[{}]", self.host)
+    } else {
+      self.host.clone()
+    }
+  }
+
+  /// Returns the endpoint that a redirect to `location` points to,
+  /// which may only give a path on the same server.
+  pub fn follow(&self, location: &str) -> Result<Self, String> {
+    if location.starts_with('/') {
+      return Ok(Self {
+        tls: self.tls,
+        host: self.host.clone(),
+        port: self.port,
+        target: location.to_string(),
+      });
+    }
+    Self::parse(location)
+  }
+
+  /// Sends a GET request with the given headers.
+  pub fn get(&self, headers: &[(&str, &str)]) -> Result<Response, Error> {
+    self.send("GET", headers, None)
+  }
+
+  /// Sends a POST request with the given headers and body.
+  pub fn post(&self, headers: &[(&str, &str)], body: &[u8]) -> Result<Response, Error> {
+    self.send("POST", headers, Some(body))
+  }
+
+  fn send(
+    &self,
+    method: &str,
+    headers: &[(&str, &str)],
+    body: Option<&[u8]>,
+  ) -> Result<Response, Error> {
+    let mut request = format!(
+      "{} {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: wardstone/{}\r\nConnection: close\r\n",
+      method,
+      self.target,
+      self.authority(),
+      env!("CARGO_PKG_VERSION"),
+    );
+    for (name, value) in headers {
+      // A line break would end the header early and have what follows
+      // it read as another header or as the body.
+      if name.contains(['\r', '\n']) || value.contains(['\r', '\n']) {
+        return Err(
+          io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("line break in HTTP header {}", name.escape_debug()),
+          )
+          .into(),
+        );
+      }
+      request.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    if let Some(body) = body {
+      request.push_str(&format!("Content-Length: {}\r\n", body.len()));
+    }
+    request.push_str("\r\n");
+    let mut stream: Box<dyn Stream> = if self.tls {
+      Box::new(tls::connect_verified(&self.host, self.port)?)
+    } else {
+      Box::new(tls::connect(&self.host, self.port, tls::TIMEOUT)?)
+    };
+    stream.write_all(request.as_bytes())?;
+    if let Some(body) = body {
+      stream.write_all(body)?;
+    }
+    stream.flush()?;
+    Response::read(BufReader::new(stream))
+  }
+}
+
+impl Response {
+  fn read(mut stream: BufReader<Box<dyn Stream>>) -> Result<Self, Error> {
+    let mut line = String::new();
+    stream.read_line(&mut line)?;
+    let status = line
+      .split_whitespace()
+      .nth(1)
+      .and_then(|code| code.parse().ok())
+      .ok_or_else(|| invalid(format!("malformed status line: {}", line.trim())))?;
+    let mut headers = Vec::new();
+    loop {
+      line.clear();
+      stream.read_line(&mut line)?;
+      let line = line.trim_end();
+      if line.is_empty() {
+        break;
+      }
+      if let Some((name, value)) = line.split_once(':') {
+        headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+      }
+    }
+    let mut response = Self {
+      status,
+      headers,
+      body: Box::new(io::empty()),
+    };
+    let chunked = response
+      .header("transfer-encoding")
+      .is_some_and(|encoding| encoding.eq_ignore_ascii_case("chunked"));
+    let length = response
+      .header("content-length")
+      .and_then(|length| length.parse().ok());
+    response.body = match (chunked, length) {
+      (true, _) => Box::new(Chunked {
+        inner: stream,
+        left: 0,
+        done: false,
+      }),
+      (false, Some(length)) => Box::new(stream.take(length)),
+      (false, None) => Box::new(stream),
+    };
+    Ok(response)
+  }
+
+  /// Returns the value of the header with the given lowercase name.
+  pub fn header(&self, name: &str) -> Option<&str> {
+    self
+      .headers
+      .iter()
+      .find(|(header, _)| header == name)
+      .map(|(_, value)| value.as_str())
+  }
+
+  /// Returns the body, which is read from the connection as it is used.
+  pub fn into_body(self) -> Box<dyn Read> {
+    self.body
+  }
+
+  /// Reads the whole body.
+  pub fn bytes(self) -> Result<Vec<u8>, Error> {
+    let mut body = Vec::new();
+    self.into_body().read_to_end(&mut body)?;
+    Ok(body)
+  }
+}
+
+/// Decodes a body sent in chunks (RFC 9112 Section 7.1).
+struct Chunked<R> {
+  inner: R,
+  /// The number of bytes left in the current chunk.
+  left: usize,
+  done: bool,
+}
+
+impl<R: BufRead> Read for Chunked<R> {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    if self.done || buf.is_empty() {
+      return Ok(0);
+    }
+    if self.left == 0 {
+      let mut line = String::new();
+      self.inner.read_line(&mut line)?;
+      // Chunk extensions follow the size and are ignored.
+      let size = line.split(';').next().unwrap_or_default().trim();
+      self.left = usize::from_str_radix(size, 16)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "malformed chunk size"))?;
+      if self.left == 0 {
+        // Trailers are not used.
+        self.done = true;
+        return Ok(0);
+      }
+    }
+    let len = buf.len().min(self.left);
+    let n = self.inner.read(&mut buf[..len])?;
+    if n == 0 {
+      return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    self.left -= n;
+    if self.left == 0 {
+      let mut end = [0; 2];
+      self.inner.read_exact(&mut end)?;
+    }
+    Ok(n)
+  }
+}
+
+/// Percent-encodes a segment of the path of a URL, or a value in its
+/// query, leaving only the characters that are never reserved.
+pub fn encode(segment: &str) -> String {
+  segment
+    .bytes()
+    .map(|b| match b {
+      b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+        (b as char).to_string()
+      },
+      _ => format!("%{:02X}", b),
+    })
+    .collect()
+}
+
+/// Percent-encodes every segment of a path, keeping the slashes that
+/// separate them.
+pub fn encode_path(path: &str) -> String {
+  path.split('/').map(encode).collect::<Vec<_>>().join("/")
+}
+
+fn invalid(reason: String) -> Error {
+  io::Error::new(io::ErrorKind::InvalidData, reason).into()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn header_with_line_break() {
+    // The request is refused before connecting to the endpoint.
+    let endpoint = Endpoint::parse("http://127.0.0.1:9/").unwrap();
+    let injected = [("Authorization", "Bearer x\r\nX-Injected: 1")];
+    assert!(matches!(
+      endpoint.get(&injected),
+      Err(Error::Io(err)) if err.kind() == io::ErrorKind::InvalidInput
+    ));
+  }
+
+  #[test]
+  fn encoded_path() {
+    assert_eq!(encode("a/b?c#d"), "a%2Fb%3Fc%23d");
+    assert_eq!(
+      encode_path("projects/p/keyRings/ring one"),
+      "projects/p/keyRings/ring%20one"
+    );
+  }
+}
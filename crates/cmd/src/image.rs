@@ -0,0 +1,416 @@
+//! Find keys and certificates in container images.
+//!
+//! Images are read from the tarballs written by `docker save` and
+//! `podman save`, in either the Docker or the OCI image layout, or are
+//! pulled from a registry with the OCI Distribution API. Every file in
+//! every layer that looks like a key or certificate is reported as
+//! `<layer>:<path>`, naming the layer by the start of its digest. Files
+//! that a later layer removes are found too, since they still ship with
+//! the image.
+//!
+//! Layers compressed with gzip are decompressed as they are read, while
+//! those compressed with zstd cannot be read. Registries are accessed
+//! anonymously, so only public images can be pulled.
+use std::collections::{HashMap, HashSet};
+use std::env::consts::ARCH;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+
+use flate2::read::GzDecoder;
+use serde_json::Value;
+use tar::Archive;
+
+use crate::http::{Endpoint, Response};
+use crate::key::Error;
+use crate::reader::{Blob, MAX_BLOB_SIZE};
+
+/// The media types of the manifests accepted from a registry, which are
+/// either the manifest of an image or an index of the manifests of the
+/// image for every platform.
+const MANIFEST_TYPES: &str = "application/vnd.oci.image.index.v1+json, application/vnd.oci.image.manifest.v1+json, application/vnd.docker.distribution.manifest.list.v2+json, application/vnd.docker.distribution.manifest.v2+json";
+
+/// The number of redirects followed before giving up on a request.
+const MAX_REDIRECTS: usize = 5;
+
+/// Returns the files that look like keys or certificates in every layer
+/// of the images saved in a tarball, in the order of the layers.
+pub fn open(path: &Path) -> Result<Vec<Blob>, Error> {
+  // The manifests that list the layers may come after the layers in the
+  // tarball, so it is read once for the manifests and once more for the
+  // layers rather than keeping the layers in memory.
+  let mut documents = HashMap::new();
+  each_file(path, |name, size, file| {
+    let listed = name == "manifest.json" || name == "index.json" || name.starts_with("blobs/");
+    if listed && size <= MAX_BLOB_SIZE {
+      let mut data = Vec::new();
+      file.read_to_end(&mut data).map_err(image)?;
+      if serde_json::from_slice::<Value>(&data).is_ok() {
+        documents.insert(name.to_string(), data);
+      }
+    }
+    Ok(())
+  })?;
+  let layers = saved_layers(&documents)?;
+  let position: HashMap<&str, usize> = layers
+    .iter()
+    .enumerate()
+    .map(|(i, (name, _))| (name.as_str(), i))
+    .collect();
+  let mut found: Vec<Vec<Blob>> = layers.iter().map(|_| Vec::new()).collect();
+  each_file(path, |name, _, file| match position.get(name) {
+    Some(&i) => scan_layer(&layers[i].1, file, &mut found[i]),
+    None => Ok(()),
+  })?;
+  Ok(found.into_iter().flatten().collect())
+}
+
+/// Returns the files that look like keys or certificates in every layer
+/// of an image in a registry, such as
+/// `registry.opensuse.org/opensuse/tumbleweed:latest`, in the order of
+/// the layers. Images on Docker Hub can be named as with `docker pull`.
+pub fn pull(reference: &str) -> Result<Vec<Blob>, Error> {
+  let mut registry = Registry::new(reference)?;
+  let mut manifest = registry.manifest(&registry.tag.clone())?;
+  if let Some(manifests) = manifest["manifests"].as_array() {
+    let digest = pick(manifests)
+      .and_then(|chosen| chosen["digest"].as_str())
+      .ok_or_else(|| Error::Image("the index lists no manifests".to_string()))?
+      .to_string();
+    manifest = registry.manifest(&digest)?;
+  }
+  let layers = manifest["layers"]
+    .as_array()
+    .ok_or_else(|| Error::Image("unsupported manifest".to_string()))?;
+  let mut blobs = Vec::new();
+  for digest in layers.iter().filter_map(|layer| layer["digest"].as_str()) {
+    let target = format!("/v2/{}/blobs/{}", registry.repository, digest);
+    let layer = registry.get(&target, "*/*")?;
+    scan_layer(digest, layer.into_body(), &mut blobs)?;
+  }
+  Ok(blobs)
+}
+
+/// Calls `f` with the name, size and contents of every file in a
+/// tarball, which may be compressed with gzip.
+fn each_file(
+  path: &Path,
+  mut f: impl FnMut(&str, u64, &mut dyn Read) -> Result<(), Error>,
+) -> Result<(), Error> {
+  let mut archive = Archive::new(decompress(File::open(path)?)?);
+  for entry in archive.entries().map_err(image)? {
+    let mut entry = entry.map_err(image)?;
+    if !entry.header().entry_type().is_file() {
+      continue;
+    }
+    let name = entry.path().map_err(image)?;
+    let name = name.to_string_lossy();
+    let name = name.trim_start_matches("./").to_string();
+    let size = entry.size();
+    f(&name, size, &mut entry)?;
+  }
+  Ok(())
+}
+
+/// Returns the files in the tarball that hold the layers of the saved
+/// images along with their digests.
+fn saved_layers(documents: &HashMap<String, Vec<u8>>) -> Result<Vec<(String, String)>, Error> {
+  let mut layers = Vec::new();
+  // Docker lists the layers of every image in manifest.json, naming
+  // them <id>/layer.tar before version 25 and by digest since.
+  if let Some(manifest) = documents.get("manifest.json") {
+    let manifest: Value = serde_json::from_slice(manifest).map_err(|err| image(err.into()))?;
+    for image in manifest.as_array().into_iter().flatten() {
+      for name in image["Layers"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+      {
+        let digest = name
+          .strip_prefix("blobs/")
+          .map(|digest| digest.replacen('/', ":", 1))
+          .or_else(|| name.strip_suffix("/layer.tar").map(str::to_string))
+          .unwrap_or_else(|| name.to_string());
+        layers.push((name.to_string(), digest));
+      }
+    }
+  } else {
+    let index = documents
+      .get("index.json")
+      .and_then(|index| serde_json::from_slice(index).ok())
+      .ok_or_else(|| Error::Image("neither manifest.json nor index.json found".to_string()))?;
+    oci_layers(&index, documents, &mut layers);
+  }
+  // Images saved together often share their base layers.
+  let mut seen = HashSet::new();
+  layers.retain(|(name, _)| seen.insert(name.clone()));
+  Ok(layers)
+}
+
+/// Adds the layers of every image an OCI index or manifest refers to,
+/// choosing one platform from indexes of multi-platform images.
+fn oci_layers(
+  manifest: &Value,
+  documents: &HashMap<String, Vec<u8>>,
+  layers: &mut Vec<(String, String)>,
+) {
+  let blob = |descriptor: &&Value| -> Option<Value> {
+    let document = documents.get(&blob_name(descriptor["digest"].as_str()?))?;
+    serde_json::from_slice(document).ok()
+  };
+  if let Some(manifests) = manifest["manifests"].as_array() {
+    let multi_platform = manifests.iter().any(|m| m["platform"].is_object());
+    let chosen: Vec<&Value> = if multi_platform {
+      pick(manifests).into_iter().collect()
+    } else {
+      manifests.iter().collect()
+    };
+    for manifest in chosen.iter().filter_map(blob) {
+      oci_layers(&manifest, documents, layers);
+    }
+  }
+  for layer in manifest["layers"].as_array().into_iter().flatten() {
+    if let Some(digest) = layer["digest"].as_str() {
+      layers.push((blob_name(digest), digest.to_string()));
+    }
+  }
+}
+
+/// Returns the name of the file that holds a blob in an OCI layout.
+fn blob_name(digest: &str) -> String {
+  format!("blobs/{}", digest.replacen(':', "/", 1))
+}
+
+/// Chooses the manifest for the platform wardstone runs on from an
+/// index, or otherwise the first one that is an image rather than an
+/// attestation.
+fn pick(manifests: &[Value]) -> Option<&Value> {
+  let arch = match ARCH {
+    "x86_64" => "amd64",
+    "aarch64" => "arm64",
+    "powerpc64" => "ppc64le",
+    arch => arch,
+  };
+  manifests
+    .iter()
+    .find(|m| os(m) == Some("linux") && m["platform"]["architecture"] == arch)
+    .or_else(|| manifests.iter().find(|m| os(m) != Some("unknown")))
+}
+
+fn os(manifest: &Value) -> Option<&str> {
+  manifest["platform"]["os"].as_str()
+}
+
+/// Adds the files that look like keys or certificates in a layer, which
+/// is a tarball that may be compressed with gzip.
+fn scan_layer(digest: &str, layer: impl Read, blobs: &mut Vec<Blob>) -> Result<(), Error> {
+  // Layers are named by the start of their digest as `docker history`
+  // does.
+  let hex = digest.rsplit(':').next().unwrap_or(digest);
+  let layer_name = &hex[..hex.len().min(12)];
+  let mut archive = Archive::new(decompress(layer)?);
+  for entry in archive.entries().map_err(image)? {
+    let mut entry = entry.map_err(image)?;
+    if !entry.header().entry_type().is_file() || entry.size() > MAX_BLOB_SIZE {
+      continue;
+    }
+    let path = entry.path().map_err(image)?;
+    let path = path.to_string_lossy();
+    let label = format!(
+      "{}:/{}",
+      layer_name,
+      path.trim_start_matches("./").trim_start_matches('/')
+    );
+    let mut data = Vec::new();
+    entry.read_to_end(&mut data).map_err(image)?;
+    blobs.extend(Blob::sniff(label, data));
+  }
+  Ok(())
+}
+
+/// Decompresses a tarball compressed with gzip, telling it apart from
+/// one that is not by its first bytes.
+fn decompress<'a>(tarball: impl Read + 'a) -> Result<Box<dyn Read + 'a>, Error> {
+  let mut tarball = BufReader::new(tarball);
+  match tarball.fill_buf().map_err(image)? {
+    [0x1f, 0x8b, ..] => Ok(Box::new(GzDecoder::new(tarball))),
+    [0x28, 0xb5, 0x2f, 0xfd, ..] => Err(Error::Image(
+      "layers compressed with zstd are not supported".to_string(),
+    )),
+    _ => Ok(Box::new(tarball)),
+  }
+}
+
+fn image(err: io::Error) -> Error {
+  Error::Image(err.to_string())
+}
+
+/// A repository in a registry along with the token it handed out.
+struct Registry {
+  /// The host and port of the registry.
+  host: String,
+  repository: String,
+  /// The tag or digest of the image.
+  tag: String,
+  token: Option<String>,
+}
+
+impl Registry {
+  /// Splits a reference such as `docker.io/library/alpine:3.19`, where
+  /// the registry defaults to Docker Hub and the tag to `latest`.
+  fn new(reference: &str) -> Result<Self, Error> {
+    let (name, tag) = match reference.split_once('@') {
+      Some((name, digest)) => (name, digest),
+      None => match reference.rsplit_once(':') {
+        Some((name, tag)) if !tag.contains('/') => (name, tag),
+        _ => (reference, "latest"),
+      },
+    };
+    // A tag given along with a digest is ignored as with `docker pull`.
+    let name = match name.rsplit_once(':') {
+      Some((name, tag)) if !tag.contains('/') => name,
+      _ => name,
+    };
+    let (host, repository) = match name.split_once('/') {
+      Some((host, repository)) if host.contains(['.', ':']) || host == "localhost" => {
+        (host, repository.to_string())
+      },
+      _ => ("docker.io", name.to_string()),
+    };
+    if repository.is_empty() || tag.is_empty() {
+      return Err(Error::Image(format!("invalid reference: {}", reference)));
+    }
+    let (host, repository) = match host {
+      "docker.io" if !repository.contains('/') => {
+        ("registry-1.docker.io", format!("library/{}", repository))
+      },
+      "docker.io" => ("registry-1.docker.io", repository),
+      host => (host, repository),
+    };
+    Ok(Self {
+      host: host.to_string(),
+      repository,
+      tag: tag.to_string(),
+      token: None,
+    })
+  }
+
+  /// Fetches the manifest with the given tag or digest.
+  fn manifest(&mut self, reference: &str) -> Result<Value, Error> {
+    let target = format!("/v2/{}/manifests/{}", self.repository, reference);
+    let body = self.get(&target, MANIFEST_TYPES)?.bytes()?;
+    serde_json::from_slice(&body).map_err(|err| image(err.into()))
+  }
+
+  /// Sends a GET request to the registry, authenticating when asked to
+  /// and following redirects to where blobs are stored.
+  fn get(&mut self, target: &str, accept: &str) -> Result<Response, Error> {
+    // Registries on the local host are reached over plain HTTP as
+    // Docker does.
+    let local = self.host.starts_with("localhost") || self.host.starts_with("127.0.0.1");
+    let scheme = if local { "http" } else { "https" };
+    let url = format!("{}://{}{}", scheme, self.host, target);
+    let mut endpoint = Endpoint::parse(&url).map_err(Error::Image)?;
+    // The token is only for the registry, not for where it redirects.
+    let mut on_registry = true;
+    for _ in 0..=MAX_REDIRECTS {
+      let authorization = self
+        .token
+        .as_ref()
+        .filter(|_| on_registry)
+        .map(|token| format!("Bearer {}", token));
+      let mut headers = vec![("Accept", accept)];
+      if let Some(authorization) = &authorization {
+        headers.push(("Authorization", authorization));
+      }
+      let response = endpoint.get(&headers)?;
+      match response.status {
+        200 => return Ok(response),
+        401 if on_registry && self.token.is_none() => {
+          let challenge = response
+            .header("www-authenticate")
+            .ok_or_else(|| Error::Image(format!("{} requires credentials", self.host)))?;
+          self.token = Some(authenticate(challenge)?);
+        },
+        301 | 302 | 303 | 307 | 308 => {
+          let location = response
+            .header("location")
+            .ok_or_else(|| Error::Image("redirect without a location".to_string()))?;
+          let next = endpoint.follow(location).map_err(Error::Image)?;
+          on_registry = on_registry && next.host == endpoint.host;
+          endpoint = next;
+        },
+        status => {
+          return Err(Error::Image(format!(
+            "{} responded with {} to {}",
+            endpoint.host, status, endpoint.target
+          )))
+        },
+      }
+    }
+    Err(Error::Image(format!("too many redirects for {}", target)))
+  }
+}
+
+/// Fetches an anonymous token as asked by the `WWW-Authenticate` header
+/// of a registry, following the token authentication of the Docker
+/// Registry.
+fn authenticate(challenge: &str) -> Result<String, Error> {
+  let unsupported = || Error::Image(format!("unsupported authentication: {}", challenge));
+  let parameters = challenge.strip_prefix("Bearer ").ok_or_else(unsupported)?;
+  let mut realm = None;
+  let mut query = Vec::new();
+  for (name, value) in parse_parameters(parameters) {
+    if name.eq_ignore_ascii_case("realm") {
+      realm = Some(value);
+    } else {
+      query.push(format!("{}={}", name, encode(&value)));
+    }
+  }
+  let realm = realm.ok_or_else(unsupported)?;
+  let url = format!("{}?{}", realm, query.join("&"));
+  let response = Endpoint::parse(&url).map_err(Error::Image)?.get(&[])?;
+  if response.status != 200 {
+    return Err(Error::Image(format!(
+      "{} responded with {} when asked for a token",
+      realm, response.status
+    )));
+  }
+  let body: Value = serde_json::from_slice(&response.bytes()?).map_err(|err| image(err.into()))?;
+  body["token"]
+    .as_str()
+    .or_else(|| body["access_token"].as_str())
+    .map(str::to_string)
+    .ok_or_else(|| Error::Image(format!("{} did not hand out a token", realm)))
+}
+
+/// Splits the parameters of a challenge, such as `realm="...",scope="..."`,
+/// whose quoted values may hold commas.
+fn parse_parameters(s: &str) -> Vec<(String, String)> {
+  let mut parameters = Vec::new();
+  let mut rest = s.trim();
+  while let Some((name, after)) = rest.split_once('=') {
+    let name = name.trim().trim_start_matches(',').trim().to_string();
+    let (value, after) = match after.strip_prefix('"') {
+      Some(quoted) => quoted.split_once('"').unwrap_or((quoted, "")),
+      None => after.split_once(',').unwrap_or((after, "")),
+    };
+    parameters.push((name, value.to_string()));
+    rest = after.trim_start_matches(',').trim();
+  }
+  parameters
+}
+
+/// Percent-encodes a value for the query of a URL.
+fn encode(value: &str) -> String {
+  value
+    .bytes()
+    .map(|b| match b {
+      b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b':' | b'/' => {
+        (b as char).to_string()
+      },
+      _ => format!("%{:02X}", b),
+    })
+    .collect()
+}
@@ -0,0 +1,52 @@
+//! Read the contents of files without copying large ones into memory.
+//!
+//! Large inputs are mapped into memory rather than read, so that
+//! assessing a file of several gigabytes does not take as much memory
+//! again. The pages of a mapped file are shared with the page cache of
+//! the operating system and are only loaded as they are used. Small
+//! files are read as usual since mapping them costs more than it saves.
+use std::fs::File;
+use std::io::Read;
+use std::ops::Deref;
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use crate::key::Error;
+
+/// The size in bytes from which files are mapped into memory.
+pub const MAP_THRESHOLD: u64 = 16 * 1024 * 1024;
+
+/// The contents of a file.
+pub enum Contents {
+  Mapped(Mmap),
+  Read(Vec<u8>),
+}
+
+impl Deref for Contents {
+  type Target = [u8];
+
+  fn deref(&self) -> &[u8] {
+    match self {
+      Self::Mapped(map) => map,
+      Self::Read(data) => data,
+    }
+  }
+}
+
+/// Returns the contents of the file at `path`, mapping it into memory
+/// if it is at least [`MAP_THRESHOLD`] bytes long.
+pub fn open(path: &Path) -> Result<Contents, Error> {
+  let mut file = File::open(path)?;
+  let len = file.metadata()?.len();
+  if len < MAP_THRESHOLD {
+    let mut data = Vec::with_capacity(len as usize);
+    file.read_to_end(&mut data)?;
+    return Ok(Contents::Read(data));
+  }
+  // SAFETY: the map is read only and dropped with the contents. Like
+  // other tools that map their inputs, this relies on files not being
+  // modified or truncated by other processes while they are assessed.
+  let map = unsafe { Mmap::map(&file)? };
+  Ok(Contents::Mapped(map))
+}
@@ -1,56 +1,185 @@
 //! Key types supported by the application.
-use std::path::Path;
+use std::path::PathBuf;
 use std::{fmt, io};
 
 use openssh_keys::errors::OpenSSHKeyError;
-use openssl::error::ErrorStack;
+use serde::{Deserialize, Serialize};
 use wardstone_core::primitive::asymmetric::Asymmetric;
 use wardstone_core::primitive::hash::Hash;
 use x509_parser::nom::Err as NomError;
 use x509_parser::prelude::{PEMError, X509Error};
 
 pub mod certificate;
+pub mod private;
+pub mod signed;
 pub mod ssh;
 
 /// Represents a cryptographic key.
+///
+/// Keys are read by the [`ArtifactReader`](crate::reader::ArtifactReader)
+/// of their format.
 pub trait Key {
-  fn from_file(path: &Path) -> Result<Self, Error>
-  where
-    Self: Sized;
   fn hash_function(&self) -> Option<Hash>;
   fn signature_algorithm(&self) -> Asymmetric;
+  /// Returns the key that was used to sign this key, such as that of
+  /// the issuer of a certificate, if it is known.
+  fn issuer_key(&self) -> Option<Asymmetric> {
+    None
+  }
+  /// Returns the period during which the key may be used if it is
+  /// bounded.
+  fn validity(&self) -> Option<Validity> {
+    None
+  }
+  /// Returns where the key starts within its file if it is known, such
+  /// as the line of a PEM block that follows the text dump of a
+  /// certificate.
+  fn location(&self) -> Option<Location> {
+    None
+  }
+  /// Returns whether the key gives the domain parameters of its
+  /// elliptic curve explicitly rather than naming the curve.
+  fn explicit_curve(&self) -> bool {
+    false
+  }
+}
+
+/// The period during which a key may be used.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct Validity {
+  /// The number of days between the start and end of the period.
+  pub days: u64,
+  /// The year in which the period ends.
+  pub expires: u16,
+}
+
+/// A position within the file a key was read from.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+pub struct Location {
+  /// The line number, starting at 1.
+  pub line: usize,
+  /// The column in bytes, starting at 1.
+  pub column: usize,
+}
+
+impl Location {
+  /// Returns the location of the byte at `offset` in `data`.
+  pub fn of(data: &[u8], offset: usize) -> Self {
+    let before = &data[..offset.min(data.len())];
+    let line = before.iter().filter(|&&b| b == b'\n').count() + 1;
+    let start = before
+      .iter()
+      .rposition(|&b| b == b'\n')
+      .map_or(0, |i| i + 1);
+    Self {
+      line,
+      column: before.len() - start + 1,
+    }
+  }
 }
 
 /// Represents an error that could arise as a result of reading a key or
 /// parsing its contents.
 #[derive(Debug)]
 pub enum Error {
+  Acme(String),
+  Apk(String),
+  Cache(String),
+  Config(String),
+  Ct(String),
+  Eap(String),
+  /// An error that arose while reading the key in a file.
+  File(PathBuf, Box<Error>),
+  Git(String),
+  Image(String),
   Io(io::Error),
+  Kms(String),
+  Ldap(String),
+  Log(String),
+  Metrics(String),
   ParsePEM(NomError<PEMError>),
   ParseSsh(OpenSSHKeyError),
-  ParseX509(ErrorStack),
   ParseX509Certificate(NomError<X509Error>),
+  Pins(String),
+  Profile(String),
+  Provider(String),
+  Source(String),
+  Storage(String),
+  Template(String),
+  Timestamp(String),
+  Tls(String),
+  TrustStore(String),
+  UnknownFormat,
   Unrecognised(String),
+  Unsigned,
+  Webhook(String),
 }
 
 impl fmt::Display for Error {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     match self {
+      Error::Acme(reason) => write!(f, "Cannot read ACME client state: {}.", reason),
+      Error::Apk(reason) => write!(f, "Cannot read APK: {}.", reason),
+      Error::Cache(reason) => write!(f, "Cannot write scan cache: {}.", reason),
+      Error::Config(reason) => write!(f, "Cannot use configuration: {}.", reason),
+      Error::Ct(reason) => {
+        write!(
+          f,
+          "Cannot search Certificate Transparency logs: {}.",
+          reason
+        )
+      },
+      Error::Eap(reason) => write!(f, "Cannot assess 802.1X settings: {}.", reason),
+      Error::File(path, err) => write!(f, "{}: {}", path.display(), err),
+      Error::Git(reason) => write!(f, "Cannot read git repository: {}.", reason),
+      Error::Image(reason) => write!(f, "Cannot read container image: {}.", reason),
       Error::Io(err) => match err.kind() {
         io::ErrorKind::NotFound => write!(f, "Key not found."),
         io::ErrorKind::PermissionDenied => write!(f, "Permission denied."),
-        _ => write!(f, "Unexpected error. Please file an issue."),
+        _ => write!(f, "Cannot read key: {}.", err),
       },
-      Error::ParsePEM(_) => write!(f, "Cannot parse PEM file."),
-      Error::ParseSsh(_) => write!(f, "Cannot parse SSH public key."),
-      Error::ParseX509Certificate(_) | Error::ParseX509(_) => {
-        write!(f, "Cannot parse X.509 certificate.")
+      Error::Kms(reason) => write!(f, "Cannot list managed keys: {}.", reason),
+      Error::Ldap(reason) => write!(f, "Cannot search LDAP directory: {}.", reason),
+      Error::Log(reason) => write!(f, "Cannot write to system log: {}.", reason),
+      Error::Metrics(reason) => write!(f, "Cannot serve metrics: {}.", reason),
+      Error::ParsePEM(err) => write!(f, "Cannot parse PEM file: {}.", reason(err)),
+      Error::ParseSsh(err) => write!(f, "Cannot parse SSH public key: {}.", err),
+      Error::ParseX509Certificate(err) => {
+        write!(f, "Cannot parse X.509 certificate: {}.", reason(err))
       },
+      Error::Pins(reason) => write!(f, "Cannot assess pin set: {}.", reason),
+      Error::Profile(reason) => write!(f, "Cannot read protocol profile: {}.", reason),
+      Error::Provider(reason) => write!(f, "Cannot read OpenSSL configuration: {}.", reason),
+      Error::Source(reason) => write!(f, "Cannot scan source code: {}.", reason),
+      Error::Storage(reason) => write!(f, "Cannot read encryption settings: {}.", reason),
+      Error::Template(reason) => write!(f, "Cannot use template: {}.", reason),
+      Error::Timestamp(reason) => write!(f, "Cannot read time-stamp: {}.", reason),
+      Error::Tls(reason) => write!(f, "Cannot complete TLS handshake: {}.", reason),
+      Error::TrustStore(reason) => write!(f, "Cannot read trust store: {}.", reason),
+      Error::UnknownFormat => write!(f, "Cannot tell the format of the key."),
       Error::Unrecognised(oid) => write!(f, "Unrecognised key: {}. Please file an issue.", oid),
+      Error::Unsigned => write!(f, "The file is not signed."),
+      Error::Webhook(reason) => write!(f, "Cannot notify webhook: {}.", reason),
     }
   }
 }
 
+impl Error {
+  /// Attaches the path of the file being read to the error.
+  pub fn in_file(self, path: impl Into<PathBuf>) -> Self {
+    Self::File(path.into(), Box::new(self))
+  }
+}
+
+/// Returns why the parser gave up, without the wrapping of the parser
+/// combinator library which only adds noise.
+fn reason<E: fmt::Display>(err: &NomError<E>) -> String {
+  match err {
+    NomError::Incomplete(_) => "unexpected end of input".to_string(),
+    NomError::Error(err) | NomError::Failure(err) => err.to_string(),
+  }
+}
+
 impl From<io::Error> for Error {
   fn from(err: io::Error) -> Self {
     Self::Io(err)
@@ -63,18 +192,18 @@ impl From<NomError<PEMError>> for Error {
   }
 }
 
-impl From<ErrorStack> for Error {
-  fn from(err: ErrorStack) -> Self {
-    Self::ParseX509(err)
-  }
-}
-
 impl From<NomError<X509Error>> for Error {
   fn from(err: NomError<X509Error>) -> Self {
     Self::ParseX509Certificate(err)
   }
 }
 
+impl From<X509Error> for Error {
+  fn from(err: X509Error) -> Self {
+    Self::ParseX509Certificate(NomError::Error(err))
+  }
+}
+
 impl From<OpenSSHKeyError> for Error {
   fn from(err: OpenSSHKeyError) -> Self {
     Self::ParseSsh(err)
@@ -1,266 +1,432 @@
 //! Create X.509 certificate representations and perform actions on
 //! them.
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::Read;
 use std::path::Path;
 
-use once_cell::sync::Lazy;
-use openssl::x509::X509;
 use wardstone_core::primitive::asymmetric::Asymmetric;
 use wardstone_core::primitive::ecc::*;
+use wardstone_core::primitive::ffc::*;
 use wardstone_core::primitive::hash::*;
 use wardstone_core::primitive::ifc::*;
+use wardstone_core::registry::{self, Builtin, Family};
+use x509_parser::der_parser::asn1_rs::{Any, Tag};
+use x509_parser::der_parser::ber::BerObject;
+use x509_parser::der_parser::der::{parse_der_integer, parse_der_octetstring, parse_der_sequence};
+use x509_parser::der_parser::oid;
 use x509_parser::pem;
-use x509_parser::prelude::{FromDer, TbsCertificate, X509Certificate};
-
-use crate::key::{Error, Key};
-
-static ASYMMETRIC: Lazy<HashMap<&str, Asymmetric>> = Lazy::new(|| {
-  let mut m = HashMap::new();
-  m.insert("1.2.840.10045.3.0.1", C2PNB163V1.into());
-  m.insert("1.2.840.10045.3.0.10", C2PNB208W1.into());
-  m.insert("1.2.840.10045.3.0.11", C2TNB239V1.into());
-  m.insert("1.2.840.10045.3.0.12", C2TNB239V2.into());
-  m.insert("1.2.840.10045.3.0.13", C2TNB239V3.into());
-  m.insert("1.2.840.10045.3.0.16", C2PNB272W1.into());
-  m.insert("1.2.840.10045.3.0.17", C2PNB304W1.into());
-  m.insert("1.2.840.10045.3.0.18", C2TNB359V1.into());
-  m.insert("1.2.840.10045.3.0.19", C2PNB368W1.into());
-  m.insert("1.2.840.10045.3.0.2", C2PNB163V2.into());
-  m.insert("1.2.840.10045.3.0.20", C2TNB431R1.into());
-  m.insert("1.2.840.10045.3.0.3", C2PNB163V3.into());
-  m.insert("1.2.840.10045.3.0.4", C2PNB176V1.into());
-  m.insert("1.2.840.10045.3.0.5", C2TNB191V1.into());
-  m.insert("1.2.840.10045.3.0.6", C2TNB191V2.into());
-  m.insert("1.2.840.10045.3.0.7", C2TNB191V3.into());
-  m.insert("1.2.840.10045.3.1.1", PRIME192V1.into());
-  m.insert("1.2.840.10045.3.1.2", PRIME192V2.into());
-  m.insert("1.2.840.10045.3.1.3", PRIME192V3.into());
-  m.insert("1.2.840.10045.3.1.4", PRIME239V1.into());
-  m.insert("1.2.840.10045.3.1.5", PRIME239V2.into());
-  m.insert("1.2.840.10045.3.1.6", PRIME239V3.into());
-  m.insert("1.2.840.10045.3.1.7", PRIME256V1.into());
-  m.insert("1.3.132.0.1", SECT163K1.into());
-  m.insert("1.3.132.0.10", SECP256K1.into());
-  m.insert("1.3.132.0.15", SECT163R2.into());
-  m.insert("1.3.132.0.16", SECT283K1.into());
-  m.insert("1.3.132.0.17", SECT283R1.into());
-  m.insert("1.3.132.0.2", SECT163R1.into());
-  m.insert("1.3.132.0.22", SECT131R1.into());
-  m.insert("1.3.132.0.23", SECT131R2.into());
-  m.insert("1.3.132.0.24", SECT193R1.into());
-  m.insert("1.3.132.0.25", SECT193R2.into());
-  m.insert("1.3.132.0.26", SECT233K1.into());
-  m.insert("1.3.132.0.27", SECT233R1.into());
-  m.insert("1.3.132.0.28", SECP128R1.into());
-  m.insert("1.3.132.0.29", SECP128R2.into());
-  m.insert("1.3.132.0.3", SECT239K1.into());
-  m.insert("1.3.132.0.30", SECP160R2.into());
-  m.insert("1.3.132.0.31", SECP192K1.into());
-  m.insert("1.3.132.0.32", SECP224K1.into());
-  m.insert("1.3.132.0.33", SECP224R1.into());
-  m.insert("1.3.132.0.34", SECP384R1.into());
-  m.insert("1.3.132.0.35", SECP521R1.into());
-  m.insert("1.3.132.0.36", SECT409K1.into());
-  m.insert("1.3.132.0.37", SECT409R1.into());
-  m.insert("1.3.132.0.38", SECT571K1.into());
-  m.insert("1.3.132.0.39", SECT571R1.into());
-  m.insert("1.3.132.0.4", SECT113R1.into());
-  m.insert("1.3.132.0.5", SECT113R2.into());
-  m.insert("1.3.132.0.6", SECP112R1.into());
-  m.insert("1.3.132.0.7", SECP112R2.into());
-  m.insert("1.3.132.0.8", SECP160R1.into());
-  m.insert("1.3.132.0.9", SECP160K1.into());
-  m.insert("1.3.36.3.3.2.8.1.1.1", BRAINPOOLP160R1.into());
-  m.insert("1.3.36.3.3.2.8.1.1.10", BRAINPOOLP320T1.into());
-  m.insert("1.3.36.3.3.2.8.1.1.11", BRAINPOOLP384R1.into());
-  m.insert("1.3.36.3.3.2.8.1.1.12", BRAINPOOLP384T1.into());
-  m.insert("1.3.36.3.3.2.8.1.1.13", BRAINPOOLP512R1.into());
-  m.insert("1.3.36.3.3.2.8.1.1.14", BRAINPOOLP512T1.into());
-  m.insert("1.3.36.3.3.2.8.1.1.2", BRAINPOOLP160T1.into());
-  m.insert("1.3.36.3.3.2.8.1.1.3", BRAINPOOLP192R1.into());
-  m.insert("1.3.36.3.3.2.8.1.1.4", BRAINPOOLP192T1.into());
-  m.insert("1.3.36.3.3.2.8.1.1.5", BRAINPOOLP224R1.into());
-  m.insert("1.3.36.3.3.2.8.1.1.6", BRAINPOOLP224T1.into());
-  m.insert("1.3.36.3.3.2.8.1.1.7", BRAINPOOLP256R1.into());
-  m.insert("1.3.36.3.3.2.8.1.1.8", BRAINPOOLP256T1.into());
-  m.insert("1.3.36.3.3.2.8.1.1.9", BRAINPOOLP320R1.into());
-  m.insert("1.2.156.10197.1.301", SM2.into());
-  m.insert("2.23.43.1.4.12", WAP_WSG_IDM_ECID_WTLS11.into());
-  m.insert("2.23.43.1.4.1", WAP_WSG_IDM_ECID_WTLS1.into());
-  m.insert("2.23.43.1.4.3", WAP_WSG_IDM_ECID_WTLS3.into());
-  m.insert("2.23.43.1.4.4", WAP_WSG_IDM_ECID_WTLS4.into());
-  m.insert("2.23.43.1.4.5", WAP_WSG_IDM_ECID_WTLS5.into());
-  m.insert("2.23.43.1.4.6", WAP_WSG_IDM_ECID_WTLS6.into());
-  m.insert("2.23.43.1.4.7", WAP_WSG_IDM_ECID_WTLS7.into());
-  m.insert("2.23.43.1.4.8", WAP_WSG_IDM_ECID_WTLS8.into());
-  m.insert("2.23.43.1.4.9", WAP_WSG_IDM_ECID_WTLS9.into());
-  m.insert("2.23.43.1.4.10", WAP_WSG_IDM_ECID_WTLS10.into());
-  m.insert("2.23.43.1.4.11", WAP_WSG_IDM_ECID_WTLS11.into());
-  m.insert("2.23.43.1.4.12", WAP_WSG_IDM_ECID_WTLS12.into());
-  m
-});
+use x509_parser::prelude::{
+  FromDer, SubjectPublicKeyInfo, TbsCertificate, X509Certificate, X509Error,
+};
+use x509_parser::public_key::RSAPublicKey;
+use x509_parser::signature_algorithm::SignatureAlgorithm;
+
+use crate::input;
+use crate::key::{Error, Key, Location, Validity};
 
 /// Represents a TLS certificate.
 #[derive(Debug)]
 pub struct Certificate {
   hash_function: Option<Hash>,
   signature_algorithm: Asymmetric,
+  issuer_key: Option<Asymmetric>,
+  validity: Validity,
+  location: Option<Location>,
+  explicit_curve: bool,
 }
 
 impl Certificate {
-  fn is_likely_pem(data: &[u8]) -> bool {
-    !matches!((data[0], data[1]), (0x30, 0x81..=0x83))
+  /// Reads the DER or PEM encoded certificate in a file.
+  pub fn from_file(path: &Path) -> Result<Certificate, Error> {
+    Self::from_bytes(&input::open(path)?)
   }
 
-  fn edsa_with_sha(tbs_certificate: &TbsCertificate, sha: Hash) -> Result<Certificate, Error> {
-    let hash_function = Some(sha);
-    let parameters = tbs_certificate
-      .subject_pki
-      .algorithm
-      .parameters
-      .as_ref()
-      .expect("elliptic curve should specify curve");
-    let oid = parameters
-      .clone()
-      .oid()
-      .expect("elliptic curve should have identifier")
-      .to_id_string();
-    let signature_algorithm = ASYMMETRIC
-      .get(&oid.as_str())
-      .cloned()
-      .ok_or(Error::Unrecognised(oid))?;
-    let certificate = Self {
-      hash_function,
-      signature_algorithm,
+  /// Reads a DER or PEM encoded certificate.
+  pub fn from_bytes(data: &[u8]) -> Result<Certificate, Error> {
+    // Certificates do not own their data.
+    let pem;
+    let tbs_certificate = if Self::is_likely_pem(data) {
+      (_, pem) = pem::parse_x509_pem(data)?;
+      let x509_certificate = pem.parse_x509()?;
+      x509_certificate.tbs_certificate
+    } else {
+      let (_, x509_certificate) = X509Certificate::from_der(data)?;
+      x509_certificate.tbs_certificate
     };
-    Ok(certificate)
-  }
 
-  fn id_ed25519() -> Result<Certificate, Error> {
-    let certificate = Self {
-      hash_function: None,
-      signature_algorithm: ED25519.into(),
+    let oid = tbs_certificate.signature.oid().to_id_string();
+    let signature = registry::find_signature(&oid)
+      .ok_or(Error::Unrecognised(oid))?
+      .primitive;
+    let mut certificate = match signature.family {
+      Family::RsaPss => Self::rsassa_pss(&tbs_certificate)?,
+      _ => Self {
+        hash_function: signature.hash,
+        signature_algorithm: Self::key(&tbs_certificate.subject_pki)?,
+        issuer_key: None,
+        validity: Self::validity(&tbs_certificate),
+        location: None,
+        explicit_curve: false,
+      },
     };
+    certificate.issuer_key = Self::issuer_key(&tbs_certificate, certificate.signature_algorithm);
+    certificate.location = Self::location(data);
+    certificate.explicit_curve = Self::curve_parameters(&tbs_certificate.subject_pki)
+      .is_some_and(|parameters| parameters.tag() == Tag::Sequence);
     Ok(certificate)
   }
 
-  fn id_ed448() -> Result<Certificate, Error> {
-    let certificate = Self {
-      hash_function: None,
-      signature_algorithm: ED448.into(),
-    };
-    Ok(certificate)
+  /// Finds the start of the PEM block which may be preceded by text,
+  /// such as the output of `openssl x509 -text`.
+  fn location(data: &[u8]) -> Option<Location> {
+    if !Self::is_likely_pem(data) {
+      return None;
+    }
+    let marker = b"-----BEGIN";
+    let offset = data
+      .windows(marker.len())
+      .position(|window| window == marker)?;
+    Some(Location::of(data, offset))
   }
 
-  fn rsassa_pss(data: &[u8]) -> Result<Certificate, Error> {
-    // The x509_parser crate cannot seem to read rsassa-pss keys so
-    // resort to openssl for that. But even that cannot seem to
-    // extract the hash function so a lower level interface may be
-    // required.
-    let certificate = if Self::is_likely_pem(data) {
-      X509::from_pem(data)?
-    } else {
-      X509::from_der(data)?
+  fn is_likely_pem(data: &[u8]) -> bool {
+    !matches!(data, [0x30, 0x81..=0x83, ..])
+  }
+
+  /// Extracts the subject public key based on the algorithm identifier
+  /// in the subject public key info, which is also how public keys are
+  /// stored on their own.
+  pub(crate) fn key(subject_pki: &SubjectPublicKeyInfo) -> Result<Asymmetric, Error> {
+    let algorithm = &subject_pki.algorithm;
+    let oid = algorithm.algorithm.to_id_string();
+    match oid.as_str() {
+      "1.2.840.10045.2.1" => {
+        // Only named curves are allowed in certificates (RFC 5480
+        // Section 2.1.1) but some still carry the domain parameters of
+        // the curve, which are judged by their size alone.
+        let parameters = Self::curve_parameters(subject_pki).ok_or(X509Error::InvalidSPKI)?;
+        if parameters.tag() == Tag::Sequence {
+          return Ok(Self::explicit_curve(parameters.data)?.into());
+        }
+        let curve = parameters
+          .clone()
+          .oid()
+          .map_err(|_| X509Error::InvalidSPKI)?
+          .to_id_string();
+        match registry::find_oid(&curve) {
+          Some(Builtin::Ecc(entry)) => Ok(entry.primitive.into()),
+          _ => Err(Error::Unrecognised(curve)),
+        }
+      },
+      "1.2.840.10040.4.1" => Self::dsa(subject_pki),
+      "1.2.840.113549.1.1.1" => {
+        let k = subject_pki.parsed()?.key_size();
+        Ok(Self::rsa(k).into())
+      },
+      "1.2.840.113549.1.1.10" => {
+        // The public key has the same encoding as an rsaEncryption key
+        // but is not recognised as such by the parser due to the
+        // different algorithm identifier.
+        let (_, public_key) = RSAPublicKey::from_der(&subject_pki.subject_public_key.data)?;
+        let k = public_key.key_size();
+        let ifc = match k {
+          1024 => RSA_PSS_1024,
+          1536 => RSA_PSS_1536,
+          2048 => RSA_PSS_2048,
+          3072 => RSA_PSS_3072,
+          4096 => RSA_PSS_4096,
+          7680 => RSA_PSS_7680,
+          8192 => RSA_PSS_8192,
+          15360 => RSA_PSS_15360,
+          _ => Ifc::new(ID_RSA_PSS, k as u16),
+        };
+        Ok(ifc.into())
+      },
+      // See RFC 8410 Section 3.
+      "1.3.101.110" => Ok(X25519.into()),
+      "1.3.101.111" => Ok(X448.into()),
+      "1.3.101.112" => Ok(ED25519.into()),
+      "1.3.101.113" => Ok(ED448.into()),
+      _ => Err(Error::Unrecognised(oid)),
+    }
+  }
+
+  /// Returns the rsaEncryption instance with a modulus of `k` bits.
+  pub(crate) fn rsa(k: usize) -> Ifc {
+    match k {
+      1024 => RSA_PKCS1_1024,
+      1536 => RSA_PKCS1_1536,
+      2048 => RSA_PKCS1_2048,
+      3072 => RSA_PKCS1_3072,
+      4096 => RSA_PKCS1_4096,
+      7680 => RSA_PKCS1_7680,
+      8192 => RSA_PKCS1_8192,
+      15360 => RSA_PKCS1_15360,
+      _ => Ifc::new(ID_RSA_PKCS1, k as u16),
+    }
+  }
+
+  /// Returns the parameters of an elliptic curve key, which either name
+  /// the curve or give its domain parameters.
+  fn curve_parameters<'a>(subject_pki: &'a SubjectPublicKeyInfo) -> Option<&'a Any<'a>> {
+    let algorithm = &subject_pki.algorithm;
+    if algorithm.algorithm != oid!(1.2.840 .10045 .2 .1) {
+      return None;
+    }
+    algorithm.parameters.as_ref()
+  }
+
+  /// Reads the size of the order of the base point from the explicit
+  /// ECParameters of a key (RFC 3279 Section 2.3.5).
+  ///
+  /// The order and cofactor must agree with the size of the field, as
+  /// their product is the number of points on the curve which, by
+  /// Hasse's theorem, is within a bit of the size of the field. Sizes
+  /// that do not fit a primitive are rejected rather than truncated so
+  /// that a bogus curve cannot pass for a named one.
+  fn explicit_curve(data: &[u8]) -> Result<Ecc, X509Error> {
+    fn invalid<E>(_: E) -> X509Error {
+      X509Error::InvalidSPKI
+    }
+    let bits = |object: &BerObject| -> Result<u16, X509Error> {
+      let n = object.as_biguint().map_err(invalid)?.bits();
+      u16::try_from(n).map_err(invalid)
     };
-    let public_key = certificate.public_key()?;
-    let k = public_key.bits();
-    let signature_algorithm = match k {
-      1024 => RSA_PSS_1024.into(),
-      1536 => RSA_PSS_1536.into(),
-      2048 => RSA_PSS_2048.into(),
-      3072 => RSA_PSS_3072.into(),
-      4096 => RSA_PSS_4096.into(),
-      7680 => RSA_PSS_7680.into(),
-      8192 => RSA_PSS_8192.into(),
-      15360 => RSA_PSS_15360.into(),
-      _ => Ifc::new(ID_RSA_PSS, k as u16).into(),
+    let (rest, _version) = parse_der_integer(data).map_err(invalid)?;
+    let (rest, field_id) = parse_der_sequence(rest).map_err(invalid)?;
+    let (rest, _curve) = parse_der_sequence(rest).map_err(invalid)?;
+    let (rest, _base) = parse_der_octetstring(rest).map_err(invalid)?;
+    let (rest, order) = parse_der_integer(rest).map_err(invalid)?;
+    let cofactor = match parse_der_integer(rest) {
+      Ok((_, cofactor)) => bits(&cofactor)?,
+      Err(_) => 1,
     };
-    let certificate = Self {
-      hash_function: None,
-      signature_algorithm,
+    let field = match field_id.as_sequence().map_err(invalid)?.as_slice() {
+      [field_type, p] if field_type.as_oid().ok() == Some(&oid!(1.2.840 .10045 .1 .1)) => bits(p)?,
+      // The parameters of a binary field start with its degree m.
+      [field_type, parameters] if field_type.as_oid().ok() == Some(&oid!(1.2.840 .10045 .1 .2)) => {
+        let m = parameters.as_sequence().map_err(invalid)?.first();
+        let m = m.ok_or(X509Error::InvalidSPKI)?.as_u32().map_err(invalid)?;
+        u16::try_from(m).map_err(invalid)?
+      },
+      _ => return Err(X509Error::InvalidSPKI),
     };
-    Ok(certificate)
+    let f = bits(&order)?;
+    // The sums are taken in 32 bits as the sizes can each be as large
+    // as a 16-bit value can hold.
+    let (field, points) = (u32::from(field), u32::from(f) + u32::from(cofactor));
+    if !(field.saturating_sub(1)..=field + 2).contains(&points) {
+      return Err(X509Error::InvalidSPKI);
+    }
+    Ok(Ecc::new(ID_ECC, f))
   }
 
-  fn with_rsa_encryption(
-    tbs_certificate: &TbsCertificate,
-    sha: Hash,
-  ) -> Result<Certificate, Error> {
-    let hash_function = Some(sha);
-    let k = tbs_certificate
-      .subject_pki
-      .parsed()
-      .expect("should parse rsa public key")
-      .key_size();
-    let signature_algorithm = match k {
-      1024 => RSA_PKCS1_1024.into(),
-      1536 => RSA_PKCS1_1536.into(),
-      2048 => RSA_PKCS1_2048.into(),
-      3072 => RSA_PKCS1_3072.into(),
-      4096 => RSA_PKCS1_4096.into(),
-      7680 => RSA_PKCS1_7680.into(),
-      8192 => RSA_PKCS1_8192.into(),
-      15360 => RSA_PKCS1_15360.into(),
-      _ => Ifc::new(ID_RSA_PKCS1, k as u16).into(),
+  /// Reads the sizes of the prime modulus and subgroup order from the
+  /// Dss-Parms of a DSA key (RFC 3279 Section 2.3.2).
+  fn dsa(subject_pki: &SubjectPublicKeyInfo) -> Result<Asymmetric, Error> {
+    let parameters = subject_pki
+      .algorithm
+      .parameters
+      .as_ref()
+      .ok_or(X509Error::InvalidSPKI)?;
+    let bits = |input| -> Result<(&[u8], u16), X509Error> {
+      let (rest, integer) = parse_der_integer(input).map_err(|_| X509Error::InvalidSPKI)?;
+      let n = integer
+        .as_biguint()
+        .map_err(|_| X509Error::InvalidSPKI)?
+        .bits();
+      Ok((rest, u16::try_from(n).map_err(|_| X509Error::InvalidSPKI)?))
+    };
+    let (rest, l) = bits(parameters.data)?;
+    let (_, n) = bits(rest)?;
+    let ffc = match (l, n) {
+      (1024, 160) => DSA_1024_160,
+      (2048, 224) => DSA_2048_224,
+      (2048, 256) => DSA_2048_256,
+      (3072, 256) => DSA_3072_256,
+      (7680, 384) => DSA_7680_384,
+      (15360, 512) => DSA_15360_512,
+      _ => Ffc::new(ID_DSA, l, n),
+    };
+    Ok(ffc.into())
+  }
+
+  fn validity(tbs_certificate: &TbsCertificate) -> Validity {
+    let not_before = tbs_certificate.validity.not_before;
+    let not_after = tbs_certificate.validity.not_after;
+    let seconds = (not_after.timestamp() - not_before.timestamp()).max(0) as u64;
+    Validity {
+      days: seconds / 86400,
+      expires: not_after.to_datetime().year() as u16,
+    }
+  }
+
+  /// Returns the key used by the issuer to sign the certificate if it
+  /// can be determined from the certificate alone. This is the case for
+  /// self-signed certificates and for EdDSA signatures which each
+  /// correspond to a single curve.
+  fn issuer_key(tbs_certificate: &TbsCertificate, subject_key: Asymmetric) -> Option<Asymmetric> {
+    if tbs_certificate.issuer.as_raw() == tbs_certificate.subject.as_raw() {
+      return Some(subject_key);
+    }
+    match tbs_certificate.signature.oid().to_id_string().as_str() {
+      "1.3.101.112" => Some(ED25519.into()),
+      "1.3.101.113" => Some(ED448.into()),
+      _ => None,
+    }
+  }
+
+  fn rsassa_pss(tbs_certificate: &TbsCertificate) -> Result<Certificate, Error> {
+    let signature_algorithm = Self::key(&tbs_certificate.subject_pki)?;
+    // The hash function is given in the parameters of the signature
+    // rather than by its object identifier.
+    let hash_function = match SignatureAlgorithm::try_from(&tbs_certificate.signature)? {
+      SignatureAlgorithm::RSASSA_PSS(parameters) => {
+        let oid = parameters.hash_algorithm_oid().to_id_string();
+        match registry::find_oid(&oid) {
+          Some(Builtin::Hash(entry)) => Some(entry.primitive),
+          _ => return Err(Error::Unrecognised(oid)),
+        }
+      },
+      _ => None,
     };
     let certificate = Self {
       hash_function,
       signature_algorithm,
+      issuer_key: None,
+      validity: Self::validity(tbs_certificate),
+      location: None,
+      explicit_curve: false,
     };
     Ok(certificate)
   }
 }
 
 impl Key for Certificate {
-  fn from_file(path: &Path) -> Result<Certificate, Error> {
-    let mut file = File::open(path)?;
-    let mut data = Vec::new();
-    file.read_to_end(&mut data)?;
+  fn hash_function(&self) -> Option<Hash> {
+    self.hash_function
+  }
 
-    // Certificates do not own their data.
-    let pem;
-    let tbs_certificate = if Self::is_likely_pem(&data) {
-      (_, pem) = pem::parse_x509_pem(&data)?;
-      let x509_certificate = pem.parse_x509()?;
-      x509_certificate.tbs_certificate
-    } else {
-      let (_, x509_certificate) = X509Certificate::from_der(&data)?;
-      x509_certificate.tbs_certificate
-    };
+  fn signature_algorithm(&self) -> Asymmetric {
+    self.signature_algorithm
+  }
 
-    let oid = tbs_certificate.signature.oid().to_id_string();
-    match oid.as_str() {
-      "1.2.840.10045.4.1" => Self::edsa_with_sha(&tbs_certificate, SHA1),
-      "1.2.840.10045.4.3.1" => Self::edsa_with_sha(&tbs_certificate, SHA224),
-      "1.2.840.10045.4.3.2" => Self::edsa_with_sha(&tbs_certificate, SHA256),
-      "1.2.840.10045.4.3.3" => Self::edsa_with_sha(&tbs_certificate, SHA384),
-      "1.2.840.10045.4.3.4" => Self::edsa_with_sha(&tbs_certificate, SHA512),
-      "1.2.840.113549.1.1.10" => Self::rsassa_pss(&data),
-      "1.2.840.113549.1.1.11" => Self::with_rsa_encryption(&tbs_certificate, SHA256),
-      "1.2.840.113549.1.1.12" => Self::with_rsa_encryption(&tbs_certificate, SHA384),
-      "1.2.840.113549.1.1.13" => Self::with_rsa_encryption(&tbs_certificate, SHA512),
-      "1.2.840.113549.1.1.14" => Self::with_rsa_encryption(&tbs_certificate, SHA224),
-      "1.2.840.113549.1.1.15" => Self::with_rsa_encryption(&tbs_certificate, SHA512_224),
-      "1.2.840.113549.1.1.16" => Self::with_rsa_encryption(&tbs_certificate, SHA512_256),
-      "1.2.840.113549.1.1.3" => Self::with_rsa_encryption(&tbs_certificate, MD4),
-      "1.2.840.113549.1.1.4" => Self::with_rsa_encryption(&tbs_certificate, MD5),
-      "1.2.840.113549.1.1.5" => Self::with_rsa_encryption(&tbs_certificate, SHA1),
-      "1.3.101.112" => Self::id_ed25519(),
-      "1.3.101.113" => Self::id_ed448(),
-      "2.16.840.1.101.3.4.3.10" => Self::edsa_with_sha(&tbs_certificate, SHA3_256),
-      "2.16.840.1.101.3.4.3.11" => Self::edsa_with_sha(&tbs_certificate, SHA3_384),
-      "2.16.840.1.101.3.4.3.12" => Self::edsa_with_sha(&tbs_certificate, SHA3_512),
-      _ => Err(Error::Unrecognised(oid)),
+  fn issuer_key(&self) -> Option<Asymmetric> {
+    self.issuer_key
+  }
+
+  fn validity(&self) -> Option<Validity> {
+    Some(self.validity)
+  }
+
+  fn location(&self) -> Option<Location> {
+    self.location
+  }
+
+  fn explicit_curve(&self) -> bool {
+    self.explicit_curve
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// A self-signed RSASSA-PSS certificate whose signature uses SHA-1,
+  /// the default hash function of the parameters.
+  const RSA_PSS_SHA1: &str = "\
+-----BEGIN CERTIFICATE-----\n\
+MIIB9jCCAV+gAwIBAgIUehRfZ+TAX2xn10f4L0uJ4thhOG8wDQYJKoZIhvcNAQEK\n\
+MAAwDjEMMAoGA1UEAwwDcHNzMB4XDTI2MTAxNzAzNDQ1NloXDTM2MTAxNDAzNDQ1\n\
+NlowDjEMMAoGA1UEAwwDcHNzMIGdMAsGCSqGSIb3DQEBCgOBjQAwgYkCgYEAvnw/\n\
+zHRL6NZXC6eT04UwlVmjEmk2CIe3k45FpoIj9jHGclf3ctvm0k3w8ZgsZc7ddp3m\n\
+4nzEIjA3TBv9dcTdyLJxdN1zKp3oqhORXuTsPYkhSev6QWYsoW96E83KrCaXMPto\n\
+fVY8NYRSWK7FjTCajTw67rIX4gEE9N/4xo/NNzUCAwEAAaNTMFEwHQYDVR0OBBYE\n\
+FIaPpK9GiplDJEbRr7EjRlWXLvtVMB8GA1UdIwQYMBaAFIaPpK9GiplDJEbRr7Ej\n\
+RlWXLvtVMA8GA1UdEwEB/wQFMAMBAf8wDQYJKoZIhvcNAQEKMAADgYEAaLyP1FYQ\n\
+tCt8tXhFZXmEoGBYA5E2Um++QLKhOVyipFHEG9HHqY7HIIXSffCQBKr35xqFF/85\n\
+oTEROBM05VF1BkkIVUpAYJ13jx9fQ7khQFyuI4LkuW3bIYnio+pNBw/lEgaLIUod\n\
+vh6uEp2AWuVcZTOPG3REkokDRHDmPCPJM4k=\n\
+-----END CERTIFICATE-----\n\
+";
+
+  #[test]
+  fn rsassa_pss_hash_function() {
+    let certificate = Certificate::from_bytes(RSA_PSS_SHA1.as_bytes()).unwrap();
+    assert_eq!(certificate.hash_function(), Some(SHA1));
+    assert_eq!(certificate.signature_algorithm(), RSA_PSS_1024.into());
+  }
+
+  fn tlv(tag: u8, value: &[u8]) -> Vec<u8> {
+    let mut der = vec![tag];
+    match value.len() {
+      n @ 0..=127 => der.push(n as u8),
+      n => der.extend([0x82, (n >> 8) as u8, n as u8]),
     }
+    der.extend(value);
+    der
   }
 
-  fn hash_function(&self) -> Option<Hash> {
-    self.hash_function
+  /// Returns a DER encoded integer of `8 * n + 1` bits.
+  fn integer(n: usize) -> Vec<u8> {
+    let mut value = vec![0x01];
+    value.resize(n + 1, 0);
+    tlv(0x02, &value)
   }
 
-  fn signature_algorithm(&self) -> Asymmetric {
-    self.signature_algorithm
+  /// Returns the contents of explicit ECParameters over a prime field,
+  /// with only the sizes of the numbers that matter set.
+  fn ec_parameters(p: Vec<u8>, order: Vec<u8>, cofactor: Vec<u8>) -> Vec<u8> {
+    let prime_field = [0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x01, 0x01];
+    let field_id = tlv(0x30, &[&prime_field[..], &p].concat());
+    let curve = tlv(0x30, &[tlv(0x04, &[0]), tlv(0x04, &[0])].concat());
+    [
+      tlv(0x02, &[1]),
+      field_id,
+      curve,
+      tlv(0x04, &[4]),
+      order,
+      cofactor,
+    ]
+    .concat()
+  }
+
+  #[test]
+  fn explicit_curve() {
+    let data = ec_parameters(integer(32), integer(32), integer(0));
+    assert_eq!(
+      Certificate::explicit_curve(&data),
+      Ok(Ecc::new(ID_ECC, 257))
+    );
+  }
+
+  #[test]
+  fn explicit_curve_oversized_field() {
+    // A field of 65537 bits would be taken for one of a single bit if
+    // its size were truncated.
+    let data = ec_parameters(integer(8192), integer(0), integer(0));
+    assert_eq!(
+      Certificate::explicit_curve(&data),
+      Err(X509Error::InvalidSPKI)
+    );
+  }
+
+  #[test]
+  fn explicit_curve_oversized_cofactor() {
+    let data = ec_parameters(integer(32), integer(0), integer(8192));
+    assert_eq!(
+      Certificate::explicit_curve(&data),
+      Err(X509Error::InvalidSPKI)
+    );
+  }
+
+  #[test]
+  fn explicit_curve_largest_sizes() {
+    // The order and cofactor add up to more than a 16-bit value holds.
+    let p = tlv(0x02, &[0x7f; 8192]);
+    let order = tlv(0x02, &[0x7f; 8192]);
+    let data = ec_parameters(p, order, integer(0));
+    assert_eq!(
+      Certificate::explicit_curve(&data),
+      Ok(Ecc::new(ID_ECC, 65535))
+    );
   }
 }
@@ -0,0 +1,249 @@
+//! Create private key representations and perform actions on them.
+//!
+//! Only what is needed to tell the primitive of a key is read, so the
+//! secret parts of the key are never decoded. Keys encrypted with a
+//! passphrase cannot be read.
+use std::io::Cursor;
+use std::path::Path;
+
+use serde_json::Value;
+use wardstone_core::primitive::asymmetric::Asymmetric;
+use wardstone_core::primitive::ecc::*;
+use wardstone_core::primitive::ffc::*;
+use wardstone_core::primitive::hash::Hash;
+use wardstone_core::registry::{self, Builtin};
+use x509_parser::der_parser::ber::{BerObject, BerObjectContent};
+use x509_parser::der_parser::der::{parse_der_oid, parse_der_sequence};
+use x509_parser::pem::Pem;
+use x509_parser::prelude::X509Error;
+
+use crate::input;
+use crate::key::certificate::Certificate;
+use crate::key::{Error, Key, Location};
+
+/// Represents a private key.
+#[derive(Debug)]
+pub struct PrivateKey {
+  signature_algorithm: Asymmetric,
+  location: Option<Location>,
+}
+
+impl PrivateKey {
+  /// Reads the PEM encoded private key or JSON Web Key in a file.
+  pub fn from_file(path: &Path) -> Result<Self, Error> {
+    Self::from_bytes(&input::open(path)?)
+  }
+
+  /// Reads a PKCS #8, PKCS #1 or SEC 1 private key in PEM, such as
+  /// those written by OpenSSL, or a JSON Web Key (RFC 7517) such as
+  /// those certbot keeps its account keys in.
+  pub fn from_bytes(data: &[u8]) -> Result<Self, Error> {
+    if data.trim_ascii_start().starts_with(b"{") {
+      let signature_algorithm = Self::jwk(data)?;
+      return Ok(Self {
+        signature_algorithm,
+        location: None,
+      });
+    }
+    // OpenSSL writes the parameters of a curve before the key when
+    // asked to generate both.
+    for (offset, pem) in Self::blocks(data) {
+      let signature_algorithm = match pem.label.as_str() {
+        "PRIVATE KEY" => Self::pkcs8(&pem.contents)?,
+        "RSA PRIVATE KEY" => Certificate::rsa(Self::rsa_modulus(&pem.contents)?).into(),
+        "EC PRIVATE KEY" => Self::sec1(&pem.contents)?,
+        "ENCRYPTED PRIVATE KEY" => {
+          return Err(Error::Unrecognised("encrypted private key".to_string()))
+        },
+        _ => continue,
+      };
+      return Ok(Self {
+        signature_algorithm,
+        location: Some(Location::of(data, offset)),
+      });
+    }
+    Err(Error::UnknownFormat)
+  }
+
+  /// Reads a DER encoded PKCS #8, PKCS #1 or SEC 1 private key, which is
+  /// told by the fields it starts with, such as one embedded in a
+  /// firmware image.
+  pub fn from_der(der: &[u8]) -> Result<Self, Error> {
+    let (_, key) = parse_der_sequence(der).map_err(invalid)?;
+    let fields = key.as_sequence().map_err(invalid)?;
+    let version = fields.first().and_then(|version| version.as_u32().ok());
+    let signature_algorithm = match (version, fields.get(1).map(|field| &field.content)) {
+      (Some(0), Some(BerObjectContent::Sequence(_))) => Self::pkcs8(der)?,
+      (Some(0), Some(BerObjectContent::Integer(_))) if fields.len() == 9 => {
+        Certificate::rsa(Self::rsa_modulus(der)?).into()
+      },
+      (Some(1), Some(BerObjectContent::OctetString(_))) => Self::sec1(der)?,
+      _ => return Err(Error::UnknownFormat),
+    };
+    Ok(Self {
+      signature_algorithm,
+      location: None,
+    })
+  }
+
+  /// Returns the PEM blocks in `data` along with where each starts.
+  fn blocks(data: &[u8]) -> impl Iterator<Item = (usize, Pem)> + '_ {
+    let marker = b"-----BEGIN";
+    data
+      .windows(marker.len())
+      .enumerate()
+      .filter(move |(_, window)| window == marker)
+      .filter_map(|(offset, _)| {
+        let (pem, _) = Pem::read(Cursor::new(&data[offset..])).ok()?;
+        Some((offset, pem))
+      })
+  }
+
+  /// Reads the algorithm of a PrivateKeyInfo (RFC 5208 Section 5).
+  fn pkcs8(der: &[u8]) -> Result<Asymmetric, Error> {
+    let (_, info) = parse_der_sequence(der).map_err(invalid)?;
+    let [_version, algorithm, private_key, ..] = info.as_sequence().map_err(invalid)?.as_slice()
+    else {
+      return Err(invalid(()));
+    };
+    let (oid, parameters) = match algorithm.as_sequence().map_err(invalid)?.as_slice() {
+      [oid, parameters, ..] => (oid, Some(parameters)),
+      [oid] => (oid, None),
+      _ => return Err(invalid(())),
+    };
+    let oid = oid.as_oid().map_err(invalid)?.to_id_string();
+    match oid.as_str() {
+      "1.2.840.113549.1.1.1" => {
+        let k = Self::rsa_modulus(private_key.as_slice().map_err(invalid)?)?;
+        Ok(Certificate::rsa(k).into())
+      },
+      "1.2.840.10045.2.1" => {
+        let curve = parameters.ok_or_else(|| invalid(()))?;
+        Self::named_curve(curve)
+      },
+      "1.2.840.10040.4.1" => {
+        let parameters = parameters.ok_or_else(|| invalid(()))?;
+        match parameters.as_sequence().map_err(invalid)?.as_slice() {
+          [p, q, ..] => Ok(Ffc::new(ID_DSA, bits(p)?, bits(q)?).into()),
+          _ => Err(invalid(())),
+        }
+      },
+      // See RFC 8410 Section 3.
+      "1.3.101.110" => Ok(X25519.into()),
+      "1.3.101.111" => Ok(X448.into()),
+      "1.3.101.112" => Ok(ED25519.into()),
+      "1.3.101.113" => Ok(ED448.into()),
+      _ => Err(Error::Unrecognised(oid)),
+    }
+  }
+
+  /// Reads the size of the modulus of an RSAPrivateKey (RFC 8017
+  /// Appendix A.1.2).
+  fn rsa_modulus(der: &[u8]) -> Result<usize, Error> {
+    let (_, key) = parse_der_sequence(der).map_err(invalid)?;
+    match key.as_sequence().map_err(invalid)?.as_slice() {
+      [_version, n, ..] => Ok(bits(n)? as usize),
+      _ => Err(invalid(())),
+    }
+  }
+
+  /// Reads the curve of an ECPrivateKey (RFC 5915 Section 3), which is
+  /// named in the parameters tagged `[0]`.
+  fn sec1(der: &[u8]) -> Result<Asymmetric, Error> {
+    let (_, key) = parse_der_sequence(der).map_err(invalid)?;
+    let parameters = key
+      .as_sequence()
+      .map_err(invalid)?
+      .iter()
+      .find_map(|field| match &field.content {
+        BerObjectContent::Tagged(_, tag, inner) if tag.0 == 0 => Some(inner.as_ref().clone()),
+        BerObjectContent::Unknown(any) if any.tag().0 == 0 => {
+          parse_der_oid(any.data).ok().map(|(_, oid)| oid)
+        },
+        _ => None,
+      })
+      .ok_or_else(|| invalid(()))?;
+    Self::named_curve(&parameters)
+  }
+
+  fn named_curve(parameters: &BerObject) -> Result<Asymmetric, Error> {
+    let curve = parameters.as_oid().map_err(invalid)?.to_id_string();
+    match registry::find_oid(&curve) {
+      Some(Builtin::Ecc(entry)) => Ok(entry.primitive.into()),
+      _ => Err(Error::Unrecognised(curve)),
+    }
+  }
+
+  /// Reads the type and size of a JSON Web Key (RFC 7518 Section 6 and
+  /// RFC 8037).
+  pub(crate) fn jwk(data: &[u8]) -> Result<Asymmetric, Error> {
+    let jwk: Value = serde_json::from_slice(data).map_err(|_| Error::UnknownFormat)?;
+    let field = |name: &str| jwk.get(name).and_then(Value::as_str);
+    match field("kty") {
+      Some("RSA") => {
+        let n = field("n")
+          .and_then(base64url_bits)
+          .ok_or(Error::UnknownFormat)?;
+        Ok(Certificate::rsa(n).into())
+      },
+      Some("EC") | Some("OKP") => {
+        let curve = field("crv").ok_or(Error::UnknownFormat)?;
+        match registry::find(curve) {
+          Some(Builtin::Ecc(entry)) => Ok(entry.primitive.into()),
+          _ => Err(Error::Unrecognised(curve.to_string())),
+        }
+      },
+      Some(kty) => Err(Error::Unrecognised(kty.to_string())),
+      None => Err(Error::UnknownFormat),
+    }
+  }
+}
+
+impl Key for PrivateKey {
+  fn hash_function(&self) -> Option<Hash> {
+    None
+  }
+
+  fn signature_algorithm(&self) -> Asymmetric {
+    self.signature_algorithm
+  }
+
+  fn location(&self) -> Option<Location> {
+    self.location
+  }
+}
+
+fn invalid<E>(_: E) -> Error {
+  X509Error::InvalidSPKI.into()
+}
+
+/// Returns the size in bits of a DER integer.
+fn bits(integer: &BerObject) -> Result<u16, Error> {
+  let n = integer.as_biguint().map_err(invalid)?.bits();
+  Ok(n as u16)
+}
+
+/// Returns the size in bits of the big-endian integer encoded in
+/// unpadded base64url, as the members of a JSON Web Key are.
+fn base64url_bits(s: &str) -> Option<usize> {
+  let mut bytes = Vec::with_capacity(s.len() * 3 / 4);
+  let (mut buffer, mut held) = (0u32, 0);
+  for c in s.bytes() {
+    let value = match c {
+      b'A'..=b'Z' => c - b'A',
+      b'a'..=b'z' => c - b'a' + 26,
+      b'0'..=b'9' => c - b'0' + 52,
+      b'-' => 62,
+      b'_' => 63,
+      _ => return None,
+    };
+    buffer = (buffer << 6 | value as u32) & 0xffff;
+    held += 6;
+    if held >= 8 {
+      held -= 8;
+      bytes.push((buffer >> held) as u8);
+    }
+  }
+  let start = bytes.iter().position(|&b| b != 0)?;
+  Some((bytes.len() - start) * 8 - bytes[start].leading_zeros() as usize)
+}
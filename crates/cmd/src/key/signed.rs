@@ -0,0 +1,628 @@
+//! Create representations of the signatures of signed artifacts, such
+//! as executables and packages, and perform actions on them.
+//!
+//! The signature is assessed along with the certificate of its signer
+//! where one is included. Its hash function is the weakest of those the
+//! signature relies on, which are the digest of the signed content, the
+//! digest that is signed and the one the issuer of the certificate of
+//! the signer used, since code signing chains are where MD5 and SHA-1
+//! tend to persist.
+use wardstone_core::primitive::asymmetric::Asymmetric;
+use wardstone_core::primitive::ecc::*;
+use wardstone_core::primitive::ffc::*;
+use wardstone_core::primitive::hash::*;
+use wardstone_core::registry::{self, Builtin, Family};
+use x509_parser::der_parser::asn1_rs::{Any, Class, Oid, Tag};
+use x509_parser::der_parser::oid;
+use x509_parser::prelude::{FromDer, X509Certificate, X509Error};
+
+use crate::key::certificate::Certificate;
+use crate::key::{Error, Key, Validity};
+
+/// The object identifier of PKCS #7 signed data (RFC 5652 Section 5.1).
+const SIGNED_DATA: Oid = oid!(1.2.840 .113549 .1 .7 .2);
+
+/// The object identifier of the content Authenticode signs, which holds
+/// the digest of the executable.
+const SPC_INDIRECT_DATA: Oid = oid!(1.3.6 .1 .4 .1 .311 .2 .1 .4);
+
+/// The object identifier of the content of a time-stamp token, which
+/// holds the digest of the data it is for (RFC 3161 Section 2.4.2).
+const TST_INFO: Oid = oid!(1.2.840 .113549 .1 .9 .16 .1 .4);
+
+/// The trailer Linux appends to signed kernel modules.
+const MODULE_SIGNATURE: &[u8] = b"~Module signature appended~\n";
+
+/// Represents the signature of a signed artifact.
+#[derive(Debug)]
+pub struct Signed {
+  hash_function: Option<Hash>,
+  signature_algorithm: Asymmetric,
+  issuer_key: Option<Asymmetric>,
+  validity: Option<Validity>,
+}
+
+impl Signed {
+  /// Reads the Authenticode signature of a Windows executable, such as
+  /// an `.exe`, `.dll`, `.sys` or `.efi` file. Only the first signature
+  /// is read, so an executable that is signed with SHA-1 and SHA-256 is
+  /// assessed by the former.
+  pub fn from_pe(data: &[u8]) -> Result<Self, Error> {
+    let (offset, len) = certificate_table(data).ok_or(Error::Unsigned)?;
+    let mut table = data
+      .get(offset..offset.saturating_add(len))
+      .ok_or_else(invalid)?;
+    // The table holds WIN_CERTIFICATE structures that each start with
+    // their length, revision and type and are aligned to 8 bytes.
+    while table.len() >= 8 {
+      let length = u32::from_le_bytes(table[..4].try_into().unwrap()) as usize;
+      let kind = u16::from_le_bytes(table[6..8].try_into().unwrap());
+      let entry = table.get(8..length).ok_or_else(invalid)?;
+      // WIN_CERT_TYPE_PKCS_SIGNED_DATA
+      if kind == 2 {
+        return Self::from_pkcs7(entry);
+      }
+      table = table.get(length.next_multiple_of(8)..).unwrap_or_default();
+    }
+    Err(Error::Unsigned)
+  }
+
+  /// Reads the signature appended to a Linux kernel module.
+  pub fn from_module(data: &[u8]) -> Result<Self, Error> {
+    let rest = data.strip_suffix(MODULE_SIGNATURE).ok_or(Error::Unsigned)?;
+    // struct module_signature is 12 bytes long and ends with the length
+    // of the signature in big-endian order.
+    let header = rest
+      .len()
+      .checked_sub(12)
+      .map(|start| &rest[start..])
+      .ok_or_else(invalid)?;
+    // Only PKCS #7 signatures have been used since Linux 4.3.
+    if header[2] != 2 {
+      return Err(Error::Unrecognised("module signature".to_string()));
+    }
+    let len = u32::from_be_bytes(header[8..12].try_into().unwrap()) as usize;
+    let start = (rest.len() - 12).checked_sub(len).ok_or_else(invalid)?;
+    Self::from_pkcs7(&rest[start..rest.len() - 12])
+  }
+
+  /// Reads the signature of an RPM package from its signature header.
+  pub fn from_rpm(data: &[u8]) -> Result<Self, Error> {
+    // The signature header follows the lead of 96 bytes and lists its
+    // entries before the data they point to.
+    let header = data.get(96..).ok_or_else(invalid)?;
+    if !header.starts_with(&[0x8e, 0xad, 0xe8, 0x01]) || header.len() < 16 {
+      return Err(invalid());
+    }
+    let count = be32(&header[8..12]) as usize;
+    let store = 16 + count * 16;
+    let entries = header.get(16..store).ok_or_else(invalid)?;
+    let tag = |entry: &[u8]| be32(&entry[..4]);
+    // The signatures over the header alone are preferred, as those over
+    // the header and payload are no longer made since RPM 4.16.
+    for wanted in [268, 267, 1002, 1005] {
+      if let Some(entry) = entries.chunks_exact(16).find(|entry| tag(entry) == wanted) {
+        let offset = store + be32(&entry[8..12]) as usize;
+        let packet = header.get(offset..).ok_or_else(invalid)?;
+        return Self::openpgp(packet);
+      }
+    }
+    Err(Error::Unsigned)
+  }
+
+  /// Reads a PKCS #7 SignedData structure (RFC 5652 Section 5), such as
+  /// the signature block of a signed JAR file.
+  pub fn from_pkcs7(data: &[u8]) -> Result<Self, Error> {
+    let fields = signed_data(data)?;
+    let encapsulated = fields.get(2).ok_or_else(invalid)?;
+    let certificates = match fields.iter().find(|field| is_tagged(field, 0)) {
+      Some(field) => encodings(field.data)?,
+      None => Vec::new(),
+    };
+    let signer = first_signer(&fields)?;
+    // The signed attributes tagged [0] may come between the digest and
+    // signature algorithms.
+    let signer: Vec<Any> = all(signer.data)?
+      .into_iter()
+      .filter(|field| !is_tagged(field, 0))
+      .collect();
+    let [_version, id, digest_algorithm, signature_algorithm, signature, ..] = &signer[..] else {
+      return Err(invalid());
+    };
+    let mut hashes = vec![hash_function(digest_algorithm)?];
+    hashes.extend(Self::content_digest(encapsulated)?);
+    // The signer is named by the issuer and serial number of its
+    // certificate.
+    let signer_certificate = match encodings(id.data)?[..] {
+      [issuer, serial] if id.tag() == Tag::Sequence => {
+        let serial = element(serial)?;
+        certificates.iter().find(|certificate| {
+          X509Certificate::from_der(certificate).is_ok_and(|(_, certificate)| {
+            certificate.issuer().as_raw() == issuer && certificate.raw_serial() == serial.data
+          })
+        })
+      },
+      _ => None,
+    };
+    let Some(signer_certificate) = signer_certificate else {
+      // Kernel modules are signed without including the certificate.
+      let signature_algorithm = Self::bare_signature(signature_algorithm, signature.data)?;
+      return Ok(Self {
+        hash_function: weakest(hashes),
+        signature_algorithm,
+        issuer_key: None,
+        validity: None,
+      });
+    };
+    Self::from_certificate(signer_certificate, certificates.iter().copied(), hashes)
+  }
+
+  /// Reads the signature of a signer from its certificate, the other
+  /// certificates that came with it and the hash functions that the
+  /// signature relies on besides the one the certificate is signed with.
+  pub fn from_certificate<'a>(
+    signer: &[u8],
+    certificates: impl IntoIterator<Item = &'a [u8]>,
+    mut hashes: Vec<Option<Hash>>,
+  ) -> Result<Self, Error> {
+    let certificate = Certificate::from_bytes(signer)?;
+    hashes.push(certificate.hash_function());
+    let (_, parsed) = X509Certificate::from_der(signer)?;
+    let issuer_key = certificates
+      .into_iter()
+      .filter_map(|issuer| {
+        X509Certificate::from_der(issuer)
+          .ok()
+          .map(|(_, c)| (issuer, c))
+      })
+      .find(|(_, issuer)| issuer.subject().as_raw() == parsed.issuer().as_raw())
+      .and_then(|(issuer, _)| Certificate::from_bytes(issuer).ok())
+      .map(|issuer| issuer.signature_algorithm())
+      .or_else(|| certificate.issuer_key());
+    Ok(Self {
+      hash_function: weakest(hashes),
+      signature_algorithm: certificate.signature_algorithm(),
+      issuer_key,
+      validity: certificate.validity(),
+    })
+  }
+
+  /// Adds a hash function that the signature also relies on, such as
+  /// the one that digests the files a signature covers.
+  pub fn relying_on(self, hash_function: Hash) -> Self {
+    Self {
+      hash_function: weakest(vec![self.hash_function, Some(hash_function)]),
+      ..self
+    }
+  }
+
+  /// Returns the algorithm of the digest of the executable that
+  /// Authenticode signs, or of the data a time-stamp token is for, if
+  /// the content is either.
+  fn content_digest(encapsulated: &Any) -> Result<Option<Option<Hash>>, Error> {
+    if let Some(tst_info) = tst_info(encapsulated)? {
+      // TSTInfo starts with its version, policy and message imprint,
+      // which is a DigestInfo.
+      let imprint = tst_info.get(2).ok_or_else(invalid)?;
+      return Ok(Some(digest_algorithm(imprint)?));
+    }
+    let fields = all(encapsulated.data)?;
+    let [content_type, content] = &fields[..] else {
+      return Ok(None);
+    };
+    if content_type.as_oid().ok() != Some(SPC_INDIRECT_DATA) {
+      return Ok(None);
+    }
+    // SpcIndirectDataContent is a sequence of the data that is signed
+    // and a DigestInfo of the executable.
+    let indirect = element(content.data)?;
+    match &all(indirect.data)?[..] {
+      [_, digest_info] => Ok(Some(digest_algorithm(digest_info)?)),
+      _ => Err(invalid()),
+    }
+  }
+
+  /// Tells the key of a signature made without a certificate from the
+  /// size of the signature, which is that of the modulus for RSA and
+  /// twice that of the order of the curve for ECDSA.
+  fn bare_signature(algorithm: &Any, signature: &[u8]) -> Result<Asymmetric, Error> {
+    let oid = algorithm_oid(algorithm)?.to_id_string();
+    let family = match oid.as_str() {
+      "1.2.840.113549.1.1.1" => Family::RsaPkcs1,
+      "1.2.840.10045.2.1" => Family::Ecdsa,
+      _ => {
+        registry::find_signature(&oid)
+          .ok_or_else(|| Error::Unrecognised(oid.clone()))?
+          .primitive
+          .family
+      },
+    };
+    match family {
+      Family::RsaPkcs1 => Ok(Certificate::rsa(signature.len() * 8).into()),
+      Family::Ecdsa => {
+        let value = element(signature)?;
+        let bits = all(value.data)?
+          .iter()
+          .map(|integer| integer.data.len() * 8)
+          .max()
+          .unwrap_or_default();
+        Ok(curve(bits).into())
+      },
+      _ => Err(Error::Unrecognised(oid)),
+    }
+  }
+
+  /// Reads an OpenPGP signature packet (RFC 9580 Section 5.2).
+  ///
+  /// A signature does not carry the public key, so the size of an RSA
+  /// key is told from that of the signature, the curve of an ECDSA key
+  /// from the size of its scalars and the size of the modulus of a DSA
+  /// key is assumed to be the smallest FIPS 186 allows for its order.
+  fn openpgp(data: &[u8]) -> Result<Self, Error> {
+    let (&first, rest) = data.split_first().ok_or_else(invalid)?;
+    let (tag, body) = if first & 0x40 != 0 {
+      let (len, rest) = match *rest {
+        [len @ 0..=191, ref rest @ ..] => (len as usize, rest),
+        [high @ 192..=223, low, ref rest @ ..] => {
+          (((high as usize - 192) << 8) + low as usize + 192, rest)
+        },
+        [255, a, b, c, d, ref rest @ ..] => (be32(&[a, b, c, d]) as usize, rest),
+        _ => return Err(invalid()),
+      };
+      (first & 0x3f, rest.get(..len).ok_or_else(invalid)?)
+    } else {
+      let size = 1 << (first & 3);
+      let len = rest.get(..size).ok_or_else(invalid)?;
+      let len = len.iter().fold(0, |len, &b| len << 8 | b as usize);
+      let end = size.checked_add(len).ok_or_else(invalid)?;
+      let body = rest.get(size..end).ok_or_else(invalid)?;
+      ((first >> 2) & 0x0f, body)
+    };
+    if tag != 2 {
+      return Err(Error::Unrecognised(format!("OpenPGP packet {}", tag)));
+    }
+    let (public_key_algorithm, hash_algorithm, material) = match body {
+      [3, 5, _, _, _, _, _, _, _, _, _, _, _, _, _, algorithm, hash, _, _, material @ ..] => {
+        (*algorithm, *hash, material)
+      },
+      [4 | 5, _, algorithm, hash, rest @ ..] => {
+        let hashed = be16(rest).ok_or_else(invalid)?;
+        let rest = rest.get(2 + hashed..).ok_or_else(invalid)?;
+        let unhashed = be16(rest).ok_or_else(invalid)?;
+        let material = rest.get(4 + unhashed..).ok_or_else(invalid)?;
+        (*algorithm, *hash, material)
+      },
+      [6, _, algorithm, hash, rest @ ..] => {
+        let hashed = be32(rest.get(..4).ok_or_else(invalid)?) as usize;
+        let rest = rest.get(4 + hashed..).ok_or_else(invalid)?;
+        let unhashed = be32(rest.get(..4).ok_or_else(invalid)?) as usize;
+        let rest = rest.get(4 + unhashed + 2..).ok_or_else(invalid)?;
+        let salt = *rest.first().ok_or_else(invalid)? as usize;
+        (*algorithm, *hash, rest.get(1 + salt..).unwrap_or_default())
+      },
+      _ => return Err(invalid()),
+    };
+    let hash_function = match hash_algorithm {
+      1 => MD5,
+      2 => SHA1,
+      3 => RIPEMD160,
+      8 => SHA256,
+      9 => SHA384,
+      10 => SHA512,
+      11 => SHA224,
+      12 => SHA3_256,
+      14 => SHA3_512,
+      hash => {
+        return Err(Error::Unrecognised(format!(
+          "OpenPGP hash algorithm {}",
+          hash
+        )))
+      },
+    };
+    // Signatures are multiprecision integers that start with their
+    // length in bits.
+    let mpi_bits = || be16(material);
+    let signature_algorithm = match public_key_algorithm {
+      1 | 3 => {
+        let bits = mpi_bits().ok_or_else(invalid)?;
+        Certificate::rsa(bits.next_multiple_of(256)).into()
+      },
+      17 => {
+        let q = mpi_bits().ok_or_else(invalid)?;
+        match q {
+          0..=160 => DSA_1024_160,
+          161..=224 => DSA_2048_224,
+          _ => DSA_2048_256,
+        }
+        .into()
+      },
+      19 => curve(mpi_bits().ok_or_else(invalid)?).into(),
+      22 if mpi_bits().is_some_and(|bits| bits > 256) => ED448.into(),
+      22 | 27 => ED25519.into(),
+      28 => ED448.into(),
+      algorithm => {
+        return Err(Error::Unrecognised(format!(
+          "OpenPGP public key algorithm {}",
+          algorithm
+        )))
+      },
+    };
+    Ok(Self {
+      hash_function: Some(hash_function),
+      signature_algorithm,
+      issuer_key: None,
+      validity: None,
+    })
+  }
+}
+
+impl Key for Signed {
+  fn hash_function(&self) -> Option<Hash> {
+    self.hash_function
+  }
+
+  fn signature_algorithm(&self) -> Asymmetric {
+    self.signature_algorithm
+  }
+
+  fn issuer_key(&self) -> Option<Asymmetric> {
+    self.issuer_key
+  }
+
+  fn validity(&self) -> Option<Validity> {
+    self.validity
+  }
+}
+
+/// Returns the file offset and size of the table of certificates of a
+/// Windows executable, if it has one. Only the headers are read, so the
+/// start of the file is enough.
+pub fn certificate_table(data: &[u8]) -> Option<(usize, usize)> {
+  if !data.starts_with(b"MZ") {
+    return None;
+  }
+  let pe = u32::from_le_bytes(data.get(0x3c..0x40)?.try_into().ok()?) as usize;
+  if data.get(pe..pe + 4)? != b"PE\0\0" {
+    return None;
+  }
+  // The optional header follows the COFF header and its magic tells
+  // 32-bit executables from 64-bit ones, whose fields are wider.
+  let optional = pe + 24;
+  let directories = match u16::from_le_bytes(data.get(optional..optional + 2)?.try_into().ok()?) {
+    0x10b => optional + 96,
+    0x20b => optional + 112,
+    _ => return None,
+  };
+  // The certificate table is the fifth data directory and, unlike the
+  // others, is located by its offset in the file.
+  let entry = data.get(directories + 32..directories + 40)?;
+  let offset = u32::from_le_bytes(entry[..4].try_into().ok()?) as usize;
+  let size = u32::from_le_bytes(entry[4..].try_into().ok()?) as usize;
+  (offset != 0 && size != 0).then_some((offset, size))
+}
+
+/// Returns whether the start of a file is that of an ELF relocatable
+/// object, which is what kernel modules are.
+pub fn is_module(data: &[u8]) -> bool {
+  match data {
+    [0x7f, b'E', b'L', b'F', _, 1, ..] => data.get(16..18) == Some(&[1, 0]),
+    [0x7f, b'E', b'L', b'F', _, 2, ..] => data.get(16..18) == Some(&[0, 1]),
+    _ => false,
+  }
+}
+
+/// An attribute of a signer as its type and the encodings of its values.
+pub type Attribute<'a> = (String, Vec<&'a [u8]>);
+
+/// Returns the unsigned attributes of the first signer of a PKCS #7
+/// SignedData structure, such as the time-stamp tokens that CAdES adds
+/// to a signature.
+pub fn unsigned_attributes(data: &[u8]) -> Result<Vec<Attribute<'_>>, Error> {
+  let fields = signed_data(data)?;
+  let signer = first_signer(&fields)?;
+  let Some(attributes) = all(signer.data)?
+    .into_iter()
+    .find(|field| is_tagged(field, 1))
+  else {
+    return Ok(Vec::new());
+  };
+  all(attributes.data)?
+    .into_iter()
+    .map(|attribute| match &all(attribute.data)?[..] {
+      [kind, values] => {
+        let kind = kind.as_oid().map_err(|_| invalid())?;
+        Ok((kind.to_id_string(), encodings(values.data)?))
+      },
+      _ => Err(invalid()),
+    })
+    .collect()
+}
+
+/// Returns when a time-stamp token was made as the GeneralizedTime it
+/// holds, such as `20240301120000Z`, or `None` if the PKCS #7 SignedData
+/// structure is not a time-stamp token.
+pub fn time_stamped(data: &[u8]) -> Result<Option<String>, Error> {
+  let fields = signed_data(data)?;
+  let encapsulated = fields.get(2).ok_or_else(invalid)?;
+  let Some(tst_info) = tst_info(encapsulated)? else {
+    return Ok(None);
+  };
+  // The serial number comes between the message imprint and the time.
+  let time = tst_info.get(4).ok_or_else(invalid)?;
+  Ok(Some(String::from_utf8_lossy(time.data).into_owned()))
+}
+
+/// Returns the time-stamp token in a TimeStampResp (RFC 3161 Section
+/// 2.4.2), or `None` if `data` is a token on its own rather than the
+/// response of a time-stamping authority.
+pub fn response_token(data: &[u8]) -> Result<Option<&[u8]>, Error> {
+  let response = element(data)?;
+  // A token starts with its content type, whereas a response starts
+  // with the status of the request.
+  let fields = encodings(response.data)?;
+  match fields[..] {
+    [status, ..] if element(status)?.tag() != Tag::Sequence => Ok(None),
+    [_, token, ..] => Ok(Some(token)),
+    _ => Err(Error::Timestamp(
+      "the response holds no time-stamp token".to_string(),
+    )),
+  }
+}
+
+/// Returns the fields of the SignedData structure in a PKCS #7
+/// ContentInfo.
+fn signed_data(data: &[u8]) -> Result<Vec<Any<'_>>, Error> {
+  let info = element(data)?;
+  let [content_type, content] = &all(info.data)?[..] else {
+    return Err(invalid());
+  };
+  let content_type = content_type.as_oid().map_err(|_| invalid())?;
+  if content_type != SIGNED_DATA {
+    return Err(Error::Unrecognised(content_type.to_id_string()));
+  }
+  all(element(content.data)?.data)
+}
+
+/// Returns the first SignerInfo of a SignedData structure, which is the
+/// last of its fields.
+fn first_signer<'a>(fields: &[Any<'a>]) -> Result<Any<'a>, Error> {
+  let signer_infos = fields.last().ok_or_else(invalid)?;
+  all(signer_infos.data)?
+    .into_iter()
+    .next()
+    .ok_or(Error::Unsigned)
+}
+
+/// Returns the fields of the TSTInfo an encapsulated content holds, if
+/// it is that of a time-stamp token. It is wrapped in an octet string.
+fn tst_info<'a>(encapsulated: &Any<'a>) -> Result<Option<Vec<Any<'a>>>, Error> {
+  let fields = all(encapsulated.data)?;
+  let [content_type, content] = &fields[..] else {
+    return Ok(None);
+  };
+  if content_type.as_oid().ok() != Some(TST_INFO) {
+    return Ok(None);
+  }
+  let octets = element(content.data)?;
+  let tst_info = element(octets.data)?;
+  Ok(Some(all(tst_info.data)?))
+}
+
+/// Returns the curve whose order has as many bits as the scalars of an
+/// ECDSA signature, which may be shorter by a few bytes.
+fn curve(bits: usize) -> Ecc {
+  match bits {
+    0..=256 => P256,
+    257..=384 => P384,
+    _ => P521,
+  }
+}
+
+/// Returns the hash function with the shortest digest.
+fn weakest(hashes: Vec<Option<Hash>>) -> Option<Hash> {
+  hashes.into_iter().flatten().min_by_key(|hash| hash.n)
+}
+
+fn be16(bytes: &[u8]) -> Option<usize> {
+  Some(u16::from_be_bytes(bytes.get(..2)?.try_into().ok()?) as usize)
+}
+
+fn be32(bytes: &[u8]) -> u32 {
+  u32::from_be_bytes(bytes[..4].try_into().unwrap())
+}
+
+fn invalid() -> Error {
+  X509Error::InvalidCertificate.into()
+}
+
+/// Reads the element that `data` starts with.
+fn element(data: &[u8]) -> Result<Any<'_>, Error> {
+  Any::from_der(data)
+    .map(|(_, element)| element)
+    .map_err(|_| invalid())
+}
+
+/// Reads every element in `data`, such as the contents of a sequence.
+fn all(data: &[u8]) -> Result<Vec<Any<'_>>, Error> {
+  encodings(data)?.into_iter().map(element).collect()
+}
+
+/// Returns the encoding of every element in `data`, such as the
+/// certificates in a set, which are read again by their own parsers.
+fn encodings(mut data: &[u8]) -> Result<Vec<&[u8]>, Error> {
+  let mut encodings = Vec::new();
+  while !data.is_empty() {
+    let (rest, _) = Any::from_der(data).map_err(|_| invalid())?;
+    let (encoding, rest) = data.split_at(data.len() - rest.len());
+    encodings.push(encoding);
+    data = rest;
+  }
+  Ok(encodings)
+}
+
+/// Returns whether an element is tagged `[tag]`, as the optional fields
+/// of SignedData and SignerInfo are.
+fn is_tagged(element: &Any, tag: u32) -> bool {
+  element.class() == Class::ContextSpecific && element.tag() == Tag(tag)
+}
+
+/// Returns the object identifier of an AlgorithmIdentifier.
+fn algorithm_oid<'a>(algorithm: &Any<'a>) -> Result<Oid<'a>, Error> {
+  Oid::from_der(algorithm.data)
+    .map(|(_, oid)| oid)
+    .map_err(|_| invalid())
+}
+
+/// Returns the hash function an AlgorithmIdentifier names.
+fn hash_function(algorithm: &Any) -> Result<Option<Hash>, Error> {
+  let oid = algorithm_oid(algorithm)?.to_id_string();
+  match registry::find_oid(&oid) {
+    Some(Builtin::Hash(entry)) => Ok(Some(entry.primitive)),
+    _ => Err(Error::Unrecognised(oid)),
+  }
+}
+
+/// Returns the hash function of a DigestInfo, which starts with its
+/// AlgorithmIdentifier.
+fn digest_algorithm(digest_info: &Any) -> Result<Option<Hash>, Error> {
+  hash_function(&element(digest_info.data)?)
+}
+
+#[cfg(test)]
+mod tests {
+  use wardstone_core::primitive::ifc::{RSA_PKCS1_2048, RSA_PKCS1_3072};
+
+  use super::*;
+
+  /// A PE32+ image holding only its headers and an Authenticode
+  /// signature whose digest of the executable uses SHA-1, made with
+  /// SHA-256 by an RSA-2048 key that an RSA-3072 CA issued.
+  const SIGNED_EXE: &[u8] = include_bytes!("../../testdata/signed.exe");
+
+  /// The lead and signature header of an RPM package, whose header is
+  /// signed by an RSA-3072 OpenPGP key with SHA-256.
+  const SIGNED_RPM: &[u8] = include_bytes!("../../testdata/signed.rpm");
+
+  #[test]
+  fn authenticode_signature() {
+    let signed = Signed::from_pe(SIGNED_EXE).unwrap();
+    assert_eq!(signed.hash_function(), Some(SHA1));
+    assert_eq!(signed.signature_algorithm(), RSA_PKCS1_2048.into());
+    assert_eq!(signed.issuer_key(), Some(RSA_PKCS1_3072.into()));
+  }
+
+  #[test]
+  fn rpm_signature() {
+    let signed = Signed::from_rpm(SIGNED_RPM).unwrap();
+    assert_eq!(signed.hash_function(), Some(SHA256));
+    assert_eq!(signed.signature_algorithm(), RSA_PKCS1_3072.into());
+  }
+
+  #[test]
+  fn openpgp_length_overflow() {
+    // An old format packet whose eight bytes of length do not fit
+    // alongside them.
+    let packet = [0x8b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff];
+    assert!(Signed::openpgp(&packet).is_err());
+  }
+}
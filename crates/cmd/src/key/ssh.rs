@@ -18,10 +18,16 @@ pub struct Ssh {
   signature_algorithm: Asymmetric,
 }
 
-impl Key for Ssh {
-  fn from_file(path: &Path) -> Result<Self, Error> {
-    let contents = fs::read_to_string(path)?;
-    let key = PublicKey::parse(contents.as_str())?;
+impl Ssh {
+  /// Reads an OpenSSH public key such as that in `id_ed25519.pub`.
+  pub fn from_file(path: &Path) -> Result<Self, Error> {
+    Self::from_bytes(fs::read_to_string(path)?.as_bytes())
+  }
+
+  /// Reads an OpenSSH public key from the contents of a file.
+  pub fn from_bytes(data: &[u8]) -> Result<Self, Error> {
+    let contents = String::from_utf8_lossy(data);
+    let key = PublicKey::parse(&contents)?;
 
     // It is not possible to infer the hash function used by looking at
     // the public key for RSA keys. RFC 4253 Section 6.6 specifies SHA-1
@@ -75,7 +81,9 @@ impl Key for Ssh {
     };
     Ok(key)
   }
+}
 
+impl Key for Ssh {
   fn hash_function(&self) -> Option<Hash> {
     self.hash_function
   }
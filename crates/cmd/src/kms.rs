@@ -0,0 +1,613 @@
+//! List the keys kept by key management services.
+//!
+//! Keys that never leave a key management service cannot be read from a
+//! file, so they are listed through the API of the service instead, which
+//! tells the type and size of every key by a name of its own:
+//!
+//! - HashiCorp Vault, whose transit engine is read at `VAULT_ADDR` with
+//!   `VAULT_TOKEN` and, for Vault Enterprise, `VAULT_NAMESPACE`.
+//! - AWS KMS, whose requests are signed with Signature Version 4 using
+//!   `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and, for temporary
+//!   credentials, `AWS_SESSION_TOKEN`.
+//! - Google Cloud KMS, with the OAuth access token in
+//!   `GOOGLE_OAUTH_ACCESS_TOKEN`, such as that printed by
+//!   `gcloud auth print-access-token`.
+//! - Azure Key Vault, with the access token in `AZURE_ACCESS_TOKEN`, such
+//!   as that printed by `az account get-access-token --resource
+//!   https://vault.azure.net`.
+//!
+//! Talking to the services is left out unless wardstone is built with the
+//! `kms` feature.
+use std::fmt;
+use std::path::PathBuf;
+
+use wardstone_core::advisory::Hazard;
+use wardstone_core::primitive::asymmetric::Asymmetric;
+use wardstone_core::primitive::hash::Hash;
+use wardstone_core::primitive::symmetric::Symmetric;
+
+use crate::assess::{audit_audiences, Audiences, Settings};
+use crate::key::Error;
+use crate::report::{Audit, Report};
+
+/// A key management service and the part of it whose keys are listed.
+#[derive(Clone, Debug)]
+pub enum Service {
+  /// The transit engine mounted at the given path.
+  Vault { mount: String },
+  /// The keys of the account in the given region, or in that of
+  /// `AWS_REGION` if none is given.
+  Aws { region: Option<String> },
+  /// The keys of a key ring, given as
+  /// `projects/<project>/locations/<location>/keyRings/<ring>`.
+  Gcp { key_ring: String },
+  /// The keys of a vault, given by its name or URL.
+  Azure { vault: String },
+}
+
+impl Service {
+  /// Reads a service written as `vault[:<mount>]`, `aws[:<region>]`,
+  /// `gcp:<key ring>` or `azure:<vault>`.
+  pub fn parse(s: &str) -> Result<Self, String> {
+    let (name, rest) = match s.split_once(':') {
+      Some((name, rest)) => (name, Some(rest.to_string())),
+      None => (s, None),
+    };
+    match (name, rest) {
+      ("vault", mount) => Ok(Self::Vault {
+        mount: mount.unwrap_or_else(|| "transit".to_string()),
+      }),
+      ("aws", region) => Ok(Self::Aws { region }),
+      ("gcp", Some(key_ring)) if key_ring.starts_with("projects/") => Ok(Self::Gcp { key_ring }),
+      ("gcp", _) => {
+        Err("expected gcp:projects/<project>/locations/<location>/keyRings/<ring>".to_string())
+      },
+      ("azure", Some(vault)) => Ok(Self::Azure { vault }),
+      ("azure", None) => Err("expected azure:<vault>".to_string()),
+      _ => {
+        Err("expected vault[:<mount>], aws[:<region>], gcp:<key ring> or azure:<vault>".to_string())
+      },
+    }
+  }
+}
+
+impl fmt::Display for Service {
+  /// Writes the service as it is read.
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Vault { mount } => write!(f, "vault:{}", mount),
+      Self::Aws {
+        region: Some(region),
+      } => write!(f, "aws:{}", region),
+      Self::Aws { region: None } => write!(f, "aws"),
+      Self::Gcp { key_ring } => write!(f, "gcp:{}", key_ring),
+      Self::Azure { vault } => write!(f, "azure:{}", vault),
+    }
+  }
+}
+
+/// What a managed key is, as far as it is assessed.
+#[derive(Clone, Copy, Debug)]
+pub enum Kind {
+  /// A key pair, along with the weakest hash function it signs with if
+  /// the service restricts it.
+  Asymmetric(Asymmetric, Option<Hash>),
+  Symmetric(Symmetric),
+  /// A key for HMAC over the given hash function.
+  Mac(Hash),
+  /// A key that no guide assesses, such as one for SM2 or ML-DSA.
+  Unassessed,
+}
+
+/// A key kept by a key management service.
+pub struct Managed {
+  /// The ARN, resource name or URL that identifies the key.
+  pub label: PathBuf,
+  /// The type of the key as the service names it, such as `RSA_2048`.
+  pub spec: String,
+  pub kind: Kind,
+}
+
+/// Returns the keys of a service in the order it lists them.
+#[cfg(feature = "kms")]
+pub fn inventory(service: &Service) -> Result<Vec<Managed>, Error> {
+  match service {
+    Service::Vault { mount } => api::vault(mount),
+    Service::Aws { region } => api::aws(region.as_deref()),
+    Service::Gcp { key_ring } => api::gcp(key_ring),
+    Service::Azure { vault } => api::azure(vault),
+  }
+}
+
+/// Returns the keys of a service.
+///
+/// Always fails because the program was built without the `kms` feature.
+#[cfg(not(feature = "kms"))]
+pub fn inventory(_service: &Service) -> Result<Vec<Managed>, Error> {
+  Err(Error::Kms(
+    "built without support for key management services".to_string(),
+  ))
+}
+
+/// Audits the keys of every service in `services` for every audience.
+/// A service that cannot be listed is skipped, as are keys that no guide
+/// assesses.
+pub fn assess(audiences: &Audiences, services: &[Service], report: &mut Report) {
+  for service in services {
+    let keys = match inventory(service) {
+      Ok(keys) => keys,
+      Err(err) => {
+        report.skip(err.in_file(service.to_string()));
+        continue;
+      },
+    };
+    for key in &keys {
+      if let Kind::Unassessed = key.kind {
+        let reason = format!("no guide assesses {} keys", key.spec);
+        report.skip(Error::Kms(reason).in_file(&key.label));
+        continue;
+      }
+      for audit in audit_audiences(audiences, |settings| audit(settings, key)) {
+        report.push(audit);
+      }
+    }
+  }
+}
+
+/// Audits a key kept by a key management service by its type and size
+/// alone, along with the weakest hash function it may sign with if the
+/// service tells. Keys that no guide assesses are left to the caller.
+pub fn audit(settings: Settings, key: &Managed) -> Audit {
+  let Settings {
+    ctx,
+    guide,
+    locale,
+    hazards,
+    ..
+  } = settings;
+  let hazards = hazards.unwrap_or(guide.hazards());
+  match key.kind {
+    Kind::Asymmetric(got, hash) => {
+      let mut audit = Audit::new(&key.label, guide.name(), hash, got);
+      if let Some(hash) = hash {
+        let verdict = guide.assess_hash_function(ctx, hash);
+        audit.cite(verdict.citation);
+        audit.explain(&verdict, ctx, hash, locale);
+        match verdict.into_result() {
+          Ok(want) => audit.compliant_hash_function(want),
+          Err(want) => audit.noncompliant_hash_function(want),
+        }
+      }
+      let verdict = guide.assess_signature_algorithm(ctx, got);
+      audit.cite(verdict.citation);
+      audit.explain(&verdict, ctx, got, locale);
+      match verdict.into_result() {
+        Ok(want) => audit.compliant_signature(want),
+        Err(want) => audit.noncompliant_signature(want),
+      }
+      if let Some(hazard) = Hazard::of(got).filter(|&h| hazards.contains(h)) {
+        audit.advise(hazard.advisory(), got);
+      }
+      audit
+    },
+    Kind::Symmetric(got) => {
+      let mut audit = Audit::of_settings(&key.label, guide.name());
+      let verdict = guide.assess_symmetric(ctx, got);
+      audit.cite(verdict.citation);
+      audit.explain(&verdict, ctx, got, locale);
+      match verdict.into_result() {
+        Ok(want) => audit.compliant_cipher(got, want),
+        Err(want) => audit.noncompliant_cipher(got, want),
+      }
+      audit
+    },
+    Kind::Mac(got) => {
+      let mut audit = Audit::of_settings(&key.label, guide.name()).with_hash_function(Some(got));
+      let verdict = guide.assess_hash_function(ctx, got);
+      audit.cite(verdict.citation);
+      audit.explain(&verdict, ctx, got, locale);
+      match verdict.into_result() {
+        Ok(want) => audit.compliant_hash_function(want),
+        Err(want) => audit.noncompliant_hash_function(want),
+      }
+      audit
+    },
+    Kind::Unassessed => {
+      let mut audit = Audit::of_settings(&key.label, guide.name());
+      audit.warn(format!("no guide assesses {} keys", key.spec));
+      audit
+    },
+  }
+}
+
+#[cfg(feature = "kms")]
+mod api {
+  use std::env;
+  use std::time::{SystemTime, UNIX_EPOCH};
+
+  use ring::{digest, hmac};
+  use serde_json::{json, Value};
+  use wardstone_core::primitive::ecc::*;
+  use wardstone_core::primitive::hash::*;
+  use wardstone_core::primitive::symmetric::{AES128, AES192, AES256};
+  use wardstone_core::primitive::Primitive;
+  use wardstone_core::registry::{self, Builtin};
+  use x509_parser::prelude::ASN1Time;
+
+  use super::{Kind, Managed};
+  use crate::http::{self, Endpoint, Response};
+  use crate::key::certificate::Certificate;
+  use crate::key::private::PrivateKey;
+  use crate::key::Error;
+
+  fn variable(name: &str) -> Result<String, Error> {
+    env::var(name).map_err(|_| Error::Kms(format!("{} is not set", name)))
+  }
+
+  fn endpoint(url: &str) -> Result<Endpoint, Error> {
+    Endpoint::parse(url).map_err(|err| Error::Kms(format!("{}: {}", url, err)))
+  }
+
+  /// Reads a JSON response, failing with what the service said if it
+  /// is not a success.
+  fn json(response: Response) -> Result<Value, Error> {
+    let status = response.status;
+    let body = response.bytes()?;
+    if !(200..300).contains(&status) {
+      let text = String::from_utf8_lossy(&body);
+      return Err(Error::Kms(format!(
+        "the service answered {}: {}",
+        status,
+        text.trim()
+      )));
+    }
+    serde_json::from_slice(&body).map_err(|err| Error::Kms(format!("invalid response: {}", err)))
+  }
+
+  fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+  }
+
+  fn hash(name: &str) -> Option<Hash> {
+    match registry::find(name) {
+      Some(Builtin::Hash(entry)) => Some(entry.primitive),
+      _ => None,
+    }
+  }
+
+  fn curve(name: &str) -> Option<Ecc> {
+    match registry::find(name) {
+      Some(Builtin::Ecc(entry)) => Some(entry.primitive),
+      _ => None,
+    }
+  }
+
+  fn aes(bits: &str) -> Kind {
+    match bits {
+      "128" => Kind::Symmetric(AES128),
+      "192" => Kind::Symmetric(AES192),
+      "256" => Kind::Symmetric(AES256),
+      _ => Kind::Unassessed,
+    }
+  }
+
+  /// Lists the keys of a transit engine, which are named by type such as
+  /// `rsa-2048` or `aes256-gcm96`. The hash function is chosen with every
+  /// request rather than by the key.
+  pub fn vault(mount: &str) -> Result<Vec<Managed>, Error> {
+    let address = env::var("VAULT_ADDR").unwrap_or_else(|_| "https://127.0.0.1:8200".to_string());
+    let address = address.trim_end_matches('/');
+    let token = variable("VAULT_TOKEN")?;
+    let namespace = env::var("VAULT_NAMESPACE").ok();
+    let mut headers = vec![("X-Vault-Token", token.as_str())];
+    if let Some(namespace) = &namespace {
+      headers.push(("X-Vault-Namespace", namespace));
+    }
+    let mount = http::encode_path(mount.trim_matches('/'));
+    let list = json(endpoint(&format!("{}/v1/{}/keys?list=true", address, mount))?.get(&headers)?)?;
+    let mut keys = Vec::new();
+    for name in list["data"]["keys"].as_array().into_iter().flatten() {
+      let Some(name) = name.as_str() else {
+        continue;
+      };
+      let url = format!("{}/v1/{}/keys/{}", address, mount, http::encode(name));
+      let key = json(endpoint(&url)?.get(&headers)?)?;
+      let spec = key["data"]["type"].as_str().unwrap_or_default().to_string();
+      let kind = match spec.split_once('-') {
+        Some(("rsa", bits)) => match bits.parse() {
+          Ok(bits) => Kind::Asymmetric(Certificate::rsa(bits).into(), None),
+          Err(_) => Kind::Unassessed,
+        },
+        Some(("ecdsa", name)) => match curve(name) {
+          Some(curve) => Kind::Asymmetric(curve.into(), None),
+          None => Kind::Unassessed,
+        },
+        _ if spec == "ed25519" => Kind::Asymmetric(ED25519.into(), None),
+        Some(("aes128", _)) => Kind::Symmetric(AES128),
+        Some(("aes256", _)) => Kind::Symmetric(AES256),
+        _ => Kind::Unassessed,
+      };
+      keys.push(Managed {
+        label: url.into(),
+        spec,
+        kind,
+      });
+    }
+    Ok(keys)
+  }
+
+  /// Returns the current time as `(YYYYMMDD'T'HHMMSS'Z', YYYYMMDD)`.
+  fn amz_date() -> (String, String) {
+    let seconds = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .map_or(0, |d| d.as_secs());
+    let time = ASN1Time::from_timestamp(seconds as i64)
+      .unwrap_or(ASN1Time::from_timestamp(0).expect("the epoch is a valid time"))
+      .to_datetime();
+    let date = format!(
+      "{:04}{:02}{:02}",
+      time.year(),
+      time.month() as u8,
+      time.day()
+    );
+    let stamp = format!(
+      "{}T{:02}{:02}{:02}Z",
+      date,
+      time.hour(),
+      time.minute(),
+      time.second()
+    );
+    (stamp, date)
+  }
+
+  /// Calls an action of the AWS KMS JSON API, signing the request with
+  /// Signature Version 4.
+  fn aws_call(
+    endpoint: &Endpoint,
+    region: &str,
+    action: &str,
+    body: &Value,
+  ) -> Result<Value, Error> {
+    let access_key = variable("AWS_ACCESS_KEY_ID")?;
+    let secret_key = variable("AWS_SECRET_ACCESS_KEY")?;
+    let session_token = env::var("AWS_SESSION_TOKEN").ok();
+    let body = body.to_string();
+    let (stamp, date) = amz_date();
+    let target = format!("TrentService.{}", action);
+    let host = endpoint.authority();
+    let mut signed = vec![
+      ("content-type", "application/x-amz-json-1.1"),
+      ("host", host.as_str()),
+      ("x-amz-date", stamp.as_str()),
+    ];
+    if let Some(token) = &session_token {
+      signed.push(("x-amz-security-token", token));
+    }
+    signed.push(("x-amz-target", target.as_str()));
+    let names: Vec<_> = signed.iter().map(|(name, _)| *name).collect();
+    let names = names.join(";");
+    let canonical: String = signed
+      .iter()
+      .map(|(name, value)| format!("{}:{}\n", name, value))
+      .collect();
+    let request = format!(
+      "POST\n{}\n\n{}\n{}\n{}",
+      endpoint.target,
+      canonical,
+      names,
+      hex(digest::digest(&digest::SHA256, body.as_bytes()).as_ref())
+    );
+    let scope = format!("{}/{}/kms/aws4_request", date, region);
+    let to_sign = format!(
+      "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+      stamp,
+      scope,
+      hex(digest::digest(&digest::SHA256, request.as_bytes()).as_ref())
+    );
+    let mut key = format!("AWS4{}", secret_key).into_bytes();
+    for part in [date.as_str(), region, "kms", "aws4_request"] {
+      key = hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, &key), part.as_bytes())
+        .as_ref()
+        .to_vec();
+    }
+    let signature = hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, &key), to_sign.as_bytes());
+    let authorization = format!(
+      "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+      access_key,
+      scope,
+      names,
+      hex(signature.as_ref())
+    );
+    let mut headers: Vec<(&str, &str)> = signed
+      .iter()
+      .filter(|(name, _)| *name != "host")
+      .copied()
+      .collect();
+    headers.push(("authorization", &authorization));
+    json(endpoint.post(&headers, body.as_bytes())?)
+  }
+
+  /// Lists the keys of an AWS account in a region along with their key
+  /// specs, such as `RSA_2048` or `SYMMETRIC_DEFAULT`, which is AES-256
+  /// in GCM. Signing keys are reported with the weakest hash function of
+  /// the signing algorithms they allow.
+  pub fn aws(region: Option<&str>) -> Result<Vec<Managed>, Error> {
+    let region = match region {
+      Some(region) => region.to_string(),
+      None => variable("AWS_REGION").or_else(|_| variable("AWS_DEFAULT_REGION"))?,
+    };
+    let url = env::var("AWS_ENDPOINT_URL_KMS")
+      .or_else(|_| env::var("AWS_ENDPOINT_URL"))
+      .unwrap_or_else(|_| format!("https://kms.{}.amazonaws.com/", region));
+    let endpoint = endpoint(&url)?;
+    let mut keys = Vec::new();
+    let mut marker: Option<String> = None;
+    loop {
+      let mut request = json!({ "Limit": 1000 });
+      if let Some(marker) = &marker {
+        request["Marker"] = json!(marker);
+      }
+      let page = aws_call(&endpoint, &region, "ListKeys", &request)?;
+      for key in page["Keys"].as_array().into_iter().flatten() {
+        let Some(id) = key["KeyArn"].as_str().or(key["KeyId"].as_str()) else {
+          continue;
+        };
+        let described = aws_call(&endpoint, &region, "DescribeKey", &json!({ "KeyId": id }))?;
+        let metadata = &described["KeyMetadata"];
+        let spec = metadata["KeySpec"]
+          .as_str()
+          .or(metadata["CustomerMasterKeySpec"].as_str())
+          .unwrap_or_default()
+          .to_string();
+        let weakest = metadata["SigningAlgorithms"]
+          .as_array()
+          .into_iter()
+          .flatten()
+          .filter_map(|algorithm| {
+            let algorithm = algorithm.as_str()?;
+            hash(&algorithm[algorithm.find("SHA_")?..])
+          })
+          .min_by_key(Primitive::security);
+        let kind = match spec.split_once('_') {
+          Some(("RSA", bits)) => match bits.parse() {
+            Ok(bits) => Kind::Asymmetric(Certificate::rsa(bits).into(), weakest),
+            Err(_) => Kind::Unassessed,
+          },
+          Some(("ECC", name)) => {
+            let name = name.trim_start_matches("NIST_").trim_start_matches("SECG_");
+            match curve(if name == "P256K1" { "secp256k1" } else { name }) {
+              Some(curve) => Kind::Asymmetric(curve.into(), weakest),
+              None => Kind::Unassessed,
+            }
+          },
+          Some(("HMAC", bits)) => match hash(&format!("sha{}", bits)) {
+            Some(hash) => Kind::Mac(hash),
+            None => Kind::Unassessed,
+          },
+          _ if spec == "SYMMETRIC_DEFAULT" => Kind::Symmetric(AES256),
+          _ => Kind::Unassessed,
+        };
+        let label = metadata["Arn"].as_str().unwrap_or(id);
+        keys.push(Managed {
+          label: label.into(),
+          spec,
+          kind,
+        });
+      }
+      marker = page["NextMarker"].as_str().map(str::to_string);
+      if page["Truncated"] != json!(true) || marker.is_none() {
+        break;
+      }
+    }
+    Ok(keys)
+  }
+
+  /// Lists the keys of a key ring by the algorithm of their primary
+  /// version, or of the version template for keys that have none, such as
+  /// `EC_SIGN_P256_SHA256` or `GOOGLE_SYMMETRIC_ENCRYPTION`, which is
+  /// AES-256 in GCM.
+  pub fn gcp(key_ring: &str) -> Result<Vec<Managed>, Error> {
+    let token = variable("GOOGLE_OAUTH_ACCESS_TOKEN")?;
+    let authorization = format!("Bearer {}", token);
+    let base = env::var("CLOUDSDK_API_ENDPOINT_OVERRIDES_CLOUDKMS")
+      .unwrap_or_else(|_| "https://cloudkms.googleapis.com/".to_string());
+    let base = base.trim_end_matches('/');
+    let mut keys = Vec::new();
+    let mut page_token: Option<String> = None;
+    loop {
+      let mut url = format!(
+        "{}/v1/{}/cryptoKeys?pageSize=1000",
+        base,
+        http::encode_path(key_ring)
+      );
+      if let Some(page_token) = &page_token {
+        url.push_str(&format!("&pageToken={}", http::encode(page_token)));
+      }
+      let page = json(endpoint(&url)?.get(&[("Authorization", &authorization)])?)?;
+      for key in page["cryptoKeys"].as_array().into_iter().flatten() {
+        let Some(name) = key["name"].as_str() else {
+          continue;
+        };
+        let spec = key["primary"]["algorithm"]
+          .as_str()
+          .or(key["versionTemplate"]["algorithm"].as_str())
+          .unwrap_or_default()
+          .to_string();
+        let parts: Vec<_> = spec.split('_').collect();
+        let digest = parts.iter().find_map(|part| hash(part));
+        let kind = match parts.as_slice() {
+          ["GOOGLE", "SYMMETRIC", "ENCRYPTION"] => Kind::Symmetric(AES256),
+          ["AES", bits, ..] => aes(bits),
+          ["HMAC", ..] => digest.map_or(Kind::Unassessed, Kind::Mac),
+          ["RSA", ..] => match parts.iter().find_map(|part| part.parse().ok()) {
+            Some(bits) => Kind::Asymmetric(Certificate::rsa(bits).into(), digest),
+            None => Kind::Unassessed,
+          },
+          ["EC", "SIGN", "ED25519"] => Kind::Asymmetric(ED25519.into(), None),
+          ["EC", "SIGN", name, ..] => match curve(name) {
+            Some(curve) => Kind::Asymmetric(curve.into(), digest),
+            None => Kind::Unassessed,
+          },
+          _ => Kind::Unassessed,
+        };
+        keys.push(Managed {
+          label: name.into(),
+          spec,
+          kind,
+        });
+      }
+      page_token = page["nextPageToken"]
+        .as_str()
+        .filter(|token| !token.is_empty())
+        .map(str::to_string);
+      if page_token.is_none() {
+        break;
+      }
+    }
+    Ok(keys)
+  }
+
+  /// Lists the keys of a vault by their current version, which is read as
+  /// a JSON Web Key. The size of symmetric keys is not returned.
+  pub fn azure(vault: &str) -> Result<Vec<Managed>, Error> {
+    const API_VERSION: &str = "api-version=7.4";
+
+    let token = variable("AZURE_ACCESS_TOKEN")?;
+    let authorization = format!("Bearer {}", token);
+    let headers = [("Authorization", authorization.as_str())];
+    let base = if vault.contains("://") {
+      vault.trim_end_matches('/').to_string()
+    } else {
+      format!("https://{}.vault.azure.net", vault)
+    };
+    let mut keys = Vec::new();
+    let mut next = Some(format!("{}/keys?{}", base, API_VERSION));
+    while let Some(url) = next {
+      let page = json(endpoint(&url)?.get(&headers)?)?;
+      for item in page["value"].as_array().into_iter().flatten() {
+        let Some(kid) = item["kid"].as_str() else {
+          continue;
+        };
+        let key = json(endpoint(&format!("{}?{}", kid, API_VERSION))?.get(&headers)?)?;
+        let mut jwk = key["key"].clone();
+        let spec = jwk["kty"].as_str().unwrap_or_default().to_string();
+        // Key Vault names secp256k1 as P-256K, which JOSE does not.
+        if jwk["crv"] == "P-256K" {
+          jwk["crv"] = "secp256k1".into();
+        }
+        jwk["kty"] = spec.trim_end_matches("-HSM").into();
+        let kind = match PrivateKey::jwk(jwk.to_string().as_bytes()) {
+          Ok(key) => Kind::Asymmetric(key, None),
+          Err(_) => Kind::Unassessed,
+        };
+        keys.push(Managed {
+          label: key["key"]["kid"].as_str().unwrap_or(kid).into(),
+          spec,
+          kind,
+        });
+      }
+      next = page["nextLink"].as_str().map(str::to_string);
+    }
+    Ok(keys)
+  }
+}
@@ -0,0 +1,513 @@
+//! Find the certificates published in an LDAP directory.
+//!
+//! Enterprise PKIs, Active Directory Certificate Services among them,
+//! publish the certificates they issue to the entries of their subjects
+//! in the `userCertificate` attribute, and those of certification
+//! authorities in `cACertificate`. The directory is searched for every
+//! entry with either attribute, below the base DN or else the default
+//! naming context of the directory, and certificates are reported by the
+//! DN of their entry.
+//!
+//! The few LDAPv3 operations needed (RFC 4511) are encoded by hand, and
+//! the responses decoded with asn1-rs: a simple bind, unless the search
+//! is anonymous, and a subtree search whose results are fetched in pages
+//! (RFC 2696) so that directories that cap the size of results, such as
+//! Active Directory, list every entry.
+//! `ldaps://` URLs connect over TLS and verify the certificate of the
+//! directory against the trust store of the operating system.
+use std::io::{Read, Write};
+use std::path::PathBuf;
+
+use x509_parser::der_parser::asn1_rs::{Any, Class, Enumerated, FromBer, Header, Tag};
+use x509_parser::nom::{Err as NomError, Needed};
+
+use crate::assess::{audit_audiences, audit_key, Audiences};
+use crate::key::certificate::Certificate;
+use crate::key::Error;
+use crate::reader::X509Reader;
+use crate::report::Report;
+use crate::tls;
+
+/// The number of entries asked for in every page of a search.
+const PAGE_SIZE: u32 = 500;
+
+/// The largest message that is read from a directory. Certificates are
+/// far smaller, so larger messages are more likely to be a broken or
+/// hostile server.
+const MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
+
+/// The control of simple paged results (RFC 2696).
+const PAGED_RESULTS: &str = "1.2.840.113556.1.4.319";
+
+/// The attributes that hold certificates. The binary transfer option
+/// (RFC 4522) is asked for since some directories, such as OpenLDAP,
+/// only return certificates with it.
+const ATTRIBUTES: [&str; 2] = ["userCertificate;binary", "cACertificate;binary"];
+
+// The tags of the parts of requests that are used.
+const BOOLEAN: u8 = 0x01;
+const INTEGER: u8 = 0x02;
+const OCTET_STRING: u8 = 0x04;
+const ENUMERATED: u8 = 0x0a;
+const SEQUENCE: u8 = 0x30;
+const BIND_REQUEST: u8 = 0x60;
+const UNBIND_REQUEST: u8 = 0x42;
+const SEARCH_REQUEST: u8 = 0x63;
+const CONTROLS: u8 = 0xa0;
+const SIMPLE: u8 = 0x80;
+const FILTER_OR: u8 = 0xa1;
+const FILTER_PRESENT: u8 = 0x87;
+
+// The tags of the operations of responses that are used, which are of
+// the application class.
+const BIND_RESPONSE: Tag = Tag(1);
+const SEARCH_RESULT_ENTRY: Tag = Tag(4);
+const SEARCH_RESULT_DONE: Tag = Tag(5);
+
+/// An attribute of an entry, along with the DN of the entry and the
+/// values of the attribute.
+type Attribute = (String, String, Vec<Vec<u8>>);
+
+/// The ID of a message, its operation as a tag and contents, and its
+/// controls.
+type Message = (u32, (Tag, Vec<u8>), Vec<u8>);
+
+/// A directory to search.
+#[derive(Clone, Debug)]
+pub struct Directory {
+  pub tls: bool,
+  pub host: String,
+  pub port: u16,
+}
+
+impl Directory {
+  /// Reads an `ldap://` or `ldaps://` URL, whose port defaults to 389 or
+  /// 636 respectively.
+  pub fn parse(url: &str) -> Result<Self, String> {
+    let (tls, rest) = if let Some(rest) = url.strip_prefix("ldaps://") {
+      (true, rest)
+    } else if let Some(rest) = url.strip_prefix("ldap://") {
+      (false, rest)
+    } else {
+      return Err("only ldap and ldaps URLs are supported".to_string());
+    };
+    let authority = rest.split('/').next().unwrap_or_default();
+    let (host, port) = tls::parse_url(authority)?;
+    // The closing bracket of an IPv6 address tells it apart from the
+    // port.
+    let explicit = authority
+      .rsplit_once(':')
+      .is_some_and(|(host, _)| !host.starts_with('[') || host.ends_with(']'));
+    let port = match (explicit, tls) {
+      (true, _) => port,
+      (false, true) => 636,
+      (false, false) => 389,
+    };
+    Ok(Self { tls, host, port })
+  }
+}
+
+/// A certificate found in the directory.
+pub struct Published {
+  /// The DN of the entry and the attribute the certificate is in, such
+  /// as `CN=alice,DC=example,DC=com:userCertificate`.
+  pub label: PathBuf,
+  pub certificate: Result<Certificate, Error>,
+}
+
+/// A connection to a server, which is encrypted or not.
+trait Stream: Read + Write {}
+
+impl<S: Read + Write> Stream for S {}
+
+/// A connection to a directory over which messages are exchanged.
+struct Connection {
+  stream: Box<dyn Stream>,
+  id: u32,
+}
+
+/// Searches the directory for every certificate below `base`, binding as
+/// `bind`, a DN and its password, first if it is given.
+pub fn sweep(
+  directory: &Directory,
+  bind: Option<(&str, &str)>,
+  base: Option<&str>,
+) -> Result<Vec<Published>, Error> {
+  let stream: Box<dyn Stream> = if directory.tls {
+    Box::new(tls::connect_verified(&directory.host, directory.port)?)
+  } else {
+    Box::new(tls::connect(&directory.host, directory.port, tls::TIMEOUT)?)
+  };
+  let mut connection = Connection { stream, id: 0 };
+  if let Some((dn, password)) = bind {
+    connection.bind(dn, password)?;
+  }
+  let base = match base {
+    Some(base) => base.to_string(),
+    None => connection.naming_context()?,
+  };
+  let filter = tlv(
+    FILTER_OR,
+    &[
+      tlv(FILTER_PRESENT, b"userCertificate"),
+      tlv(FILTER_PRESENT, b"cACertificate"),
+    ]
+    .concat(),
+  );
+  let mut found = Vec::new();
+  for (dn, attribute, values) in connection.search(&base, 2, &filter, &ATTRIBUTES)? {
+    let name = attribute.split(';').next().unwrap_or_default();
+    let many = values.len() > 1;
+    for (i, value) in values.iter().enumerate() {
+      let label = if many {
+        format!("{}:{}:{}", dn, name, i + 1)
+      } else {
+        format!("{}:{}", dn, name)
+      };
+      found.push(Published {
+        label: label.into(),
+        certificate: Certificate::from_bytes(value),
+      });
+    }
+  }
+  // The directory closes the connection once it has been unbound, so
+  // whether that is acknowledged does not matter.
+  let _ = connection.unbind();
+  Ok(found)
+}
+
+/// Audits the certificates published in every directory in
+/// `directories` for every audience, searching as [`sweep`] does. A
+/// directory that cannot be searched or a certificate that cannot be
+/// read is skipped.
+pub fn assess(
+  audiences: &Audiences,
+  directories: &[Directory],
+  bind: Option<(&str, &str)>,
+  base: Option<&str>,
+  report: &mut Report,
+) {
+  for directory in directories {
+    let published = match sweep(directory, bind, base) {
+      Ok(published) => published,
+      Err(err) => {
+        report.skip(err.in_file(&directory.host));
+        continue;
+      },
+    };
+    for Published { label, certificate } in published {
+      match certificate {
+        Ok(certificate) => {
+          for audit in audit_audiences(audiences, |settings| {
+            audit_key(settings, &X509Reader, &label, &certificate)
+          }) {
+            report.push(audit);
+          }
+        },
+        Err(err) => report.skip(err.in_file(label)),
+      }
+    }
+  }
+}
+
+impl Connection {
+  fn bind(&mut self, dn: &str, password: &str) -> Result<(), Error> {
+    let request = tlv(
+      BIND_REQUEST,
+      &[
+        integer(INTEGER, 3),
+        tlv(OCTET_STRING, dn.as_bytes()),
+        tlv(SIMPLE, password.as_bytes()),
+      ]
+      .concat(),
+    );
+    let id = self.send(&request, &[])?;
+    loop {
+      let (reply, op, _) = self.receive()?;
+      if reply == id && op.0 == BIND_RESPONSE {
+        return result(&op.1, "bind");
+      }
+    }
+  }
+
+  /// Returns the default naming context of the directory, as its root
+  /// DSE (RFC 4512 Section 5.1) gives it, or else its first naming
+  /// context.
+  fn naming_context(&mut self) -> Result<String, Error> {
+    let filter = tlv(FILTER_PRESENT, b"objectClass");
+    let attributes = ["defaultNamingContext", "namingContexts"];
+    let found = self.search("", 0, &filter, &attributes)?;
+    attributes
+      .iter()
+      .find_map(|name| {
+        found
+          .iter()
+          .find(|(_, attribute, _)| attribute.eq_ignore_ascii_case(name))
+          .and_then(|(_, _, values)| values.first())
+      })
+      .map(|value| String::from_utf8_lossy(value).into_owned())
+      .ok_or_else(|| Error::Ldap("no base DN is given and the directory names none".to_string()))
+  }
+
+  /// Searches the directory in pages, returning the values of every
+  /// attribute of every entry found along with the DN of the entry.
+  fn search(
+    &mut self,
+    base: &str,
+    scope: u32,
+    filter: &[u8],
+    attributes: &[&str],
+  ) -> Result<Vec<Attribute>, Error> {
+    let attributes: Vec<u8> = attributes
+      .iter()
+      .flat_map(|name| tlv(OCTET_STRING, name.as_bytes()))
+      .collect();
+    let mut found = Vec::new();
+    let mut cookie = Vec::new();
+    loop {
+      let request = tlv(
+        SEARCH_REQUEST,
+        &[
+          tlv(OCTET_STRING, base.as_bytes()),
+          integer(ENUMERATED, scope),
+          // Aliases are never dereferenced.
+          integer(ENUMERATED, 0),
+          // Neither the size nor the time of the search is limited.
+          integer(INTEGER, 0),
+          integer(INTEGER, 0),
+          tlv(BOOLEAN, &[0]),
+          filter.to_vec(),
+          tlv(SEQUENCE, &attributes),
+        ]
+        .concat(),
+      );
+      // The control is not critical so that directories that do not page
+      // results answer in one go.
+      let paging = tlv(
+        SEQUENCE,
+        &[
+          tlv(OCTET_STRING, PAGED_RESULTS.as_bytes()),
+          tlv(
+            OCTET_STRING,
+            &tlv(
+              SEQUENCE,
+              &[integer(INTEGER, PAGE_SIZE), tlv(OCTET_STRING, &cookie)].concat(),
+            ),
+          ),
+        ]
+        .concat(),
+      );
+      let id = self.send(&request, &paging)?;
+      cookie = loop {
+        let (reply, (tag, content), controls) = self.receive()?;
+        if reply != id {
+          continue;
+        }
+        match tag {
+          SEARCH_RESULT_ENTRY => found.extend(entry(&content)?),
+          SEARCH_RESULT_DONE => {
+            result(&content, "search")?;
+            break next_page(&controls)?;
+          },
+          // References to other directories are not followed.
+          _ => {},
+        }
+      };
+      if cookie.is_empty() {
+        return Ok(found);
+      }
+    }
+  }
+
+  fn unbind(&mut self) -> Result<(), Error> {
+    self.send(&[UNBIND_REQUEST, 0], &[])?;
+    Ok(())
+  }
+
+  /// Sends a request with the given control, returning its message ID.
+  fn send(&mut self, request: &[u8], control: &[u8]) -> Result<u32, Error> {
+    self.id += 1;
+    let mut message = [integer(INTEGER, self.id), request.to_vec()].concat();
+    if !control.is_empty() {
+      message.extend(tlv(CONTROLS, control));
+    }
+    self.stream.write_all(&tlv(SEQUENCE, &message))?;
+    self.stream.flush()?;
+    Ok(self.id)
+  }
+
+  /// Receives a message, returning its ID, its operation and its
+  /// controls.
+  fn receive(&mut self) -> Result<Message, Error> {
+    // The header is read for as long as it takes to tell the length of
+    // the message.
+    let mut header = vec![0; 2];
+    self.stream.read_exact(&mut header)?;
+    let len = loop {
+      let parsed = Header::from_ber(&header).map(|(_, header)| (header.tag(), header.length()));
+      match parsed {
+        Ok((Tag::Sequence, len)) => break len.definite().map_err(|_| malformed())?,
+        Err(NomError::Incomplete(Needed::Size(n))) => {
+          let start = header.len();
+          header.resize(start + n.get(), 0);
+          self.stream.read_exact(&mut header[start..])?;
+        },
+        _ => return Err(malformed()),
+      }
+    };
+    if len > MAX_MESSAGE_SIZE {
+      return Err(Error::Ldap(format!(
+        "message of {} bytes from the directory is larger than {} bytes",
+        len, MAX_MESSAGE_SIZE
+      )));
+    }
+    let mut message = vec![0; len];
+    self.stream.read_exact(&mut message)?;
+    let fields = elements(&message)?;
+    let [id, op, rest @ ..] = &fields[..] else {
+      return Err(malformed());
+    };
+    let id = id.as_u32().map_err(|_| malformed())?;
+    let controls = match rest {
+      [controls, ..] if is_tagged(controls, 0) => controls.data.to_vec(),
+      _ => Vec::new(),
+    };
+    Ok((id, (op.tag(), op.data.to_vec()), controls))
+  }
+}
+
+/// Returns the attributes of a search result entry along with its DN.
+fn entry(content: &[u8]) -> Result<Vec<Attribute>, Error> {
+  let fields = elements(content)?;
+  let [dn, attributes] = &fields[..] else {
+    return Err(malformed());
+  };
+  let dn = String::from_utf8_lossy(dn.data).into_owned();
+  elements(attributes.data)?
+    .iter()
+    .map(|attribute| {
+      let fields = elements(attribute.data)?;
+      let [name, values] = &fields[..] else {
+        return Err(malformed());
+      };
+      let values = elements(values.data)?
+        .iter()
+        .map(|value| value.data.to_vec())
+        .collect();
+      Ok((
+        dn.clone(),
+        String::from_utf8_lossy(name.data).into_owned(),
+        values,
+      ))
+    })
+    .collect()
+}
+
+/// Returns the cookie that asks for the next page of a search, which is
+/// empty once every page has been received.
+fn next_page(controls: &[u8]) -> Result<Vec<u8>, Error> {
+  for control in elements(controls)? {
+    let fields = elements(control.data)?;
+    let Some((oid, rest)) = fields.split_first() else {
+      return Err(malformed());
+    };
+    if oid.data != PAGED_RESULTS.as_bytes() {
+      continue;
+    }
+    // The criticality is left out when it is false.
+    let value = rest
+      .iter()
+      .find(|field| field.tag() == Tag::OctetString)
+      .ok_or_else(malformed)?;
+    let value = elements(value.data)?;
+    let [paged] = &value[..] else {
+      return Err(malformed());
+    };
+    let fields = elements(paged.data)?;
+    let [_size, cookie] = &fields[..] else {
+      return Err(malformed());
+    };
+    return Ok(cookie.data.to_vec());
+  }
+  Ok(Vec::new())
+}
+
+/// Fails unless the result of an operation is a success.
+fn result(content: &[u8], operation: &str) -> Result<(), Error> {
+  let fields = elements(content)?;
+  let code = match fields.first() {
+    Some(code) => {
+      Enumerated::try_from(code.clone())
+        .map_err(|_| malformed())?
+        .0
+    },
+    None => return Err(malformed()),
+  };
+  if code == 0 {
+    return Ok(());
+  }
+  // The matched DN comes between the code and the message.
+  let message = fields
+    .get(2)
+    .map(|message| String::from_utf8_lossy(message.data).into_owned())
+    .unwrap_or_default();
+  let reason = match code {
+    49 => "invalid credentials".to_string(),
+    50 => "insufficient access rights".to_string(),
+    code => format!("result code {}", code),
+  };
+  Err(Error::Ldap(if message.is_empty() {
+    format!("{} failed with {}", operation, reason)
+  } else {
+    format!("{} failed with {}: {}", operation, reason, message.trim())
+  }))
+}
+
+/// Reads every BER encoded value in `data`, such as the contents of a
+/// sequence.
+fn elements(mut data: &[u8]) -> Result<Vec<Any<'_>>, Error> {
+  let mut elements = Vec::new();
+  while !data.is_empty() {
+    let (rest, element) = Any::from_ber(data).map_err(|_| malformed())?;
+    elements.push(element);
+    data = rest;
+  }
+  Ok(elements)
+}
+
+/// Returns whether a value is tagged `[tag]`, as the controls of a
+/// message are.
+fn is_tagged(element: &Any, tag: u32) -> bool {
+  element.class() == Class::ContextSpecific && element.tag() == Tag(tag)
+}
+
+/// Encodes a value with the given tag.
+fn tlv(tag: u8, content: &[u8]) -> Vec<u8> {
+  let mut encoded = vec![tag];
+  match content.len() {
+    len @ 0..=0x7f => encoded.push(len as u8),
+    len => {
+      let bytes = (len as u32).to_be_bytes();
+      let skip = bytes.iter().take_while(|&&b| b == 0).count();
+      encoded.push(0x80 | (4 - skip) as u8);
+      encoded.extend(&bytes[skip..]);
+    },
+  }
+  encoded.extend(content);
+  encoded
+}
+
+/// Encodes a non-negative integer with the given tag.
+fn integer(tag: u8, n: u32) -> Vec<u8> {
+  let bytes = n.to_be_bytes();
+  let skip = bytes.iter().take_while(|&&b| b == 0).count().min(3);
+  let mut content = bytes[skip..].to_vec();
+  if content[0] & 0x80 != 0 {
+    content.insert(0, 0);
+  }
+  tlv(tag, &content)
+}
+
+fn malformed() -> Error {
+  Error::Ldap("malformed message from the directory".to_string())
+}
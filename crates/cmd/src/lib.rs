@@ -9,13 +9,73 @@
 //! Usage: wardstone <COMMAND>
 //!
 //! Commands:
-//!   ssh   Check an SSH public key for compliance
-//!   x509  Check X.509 public key certificates for compliance
-//!   help  Print this message or the help of the given subcommand(s)
+//!   ssh            Check an SSH public key for compliance
+//!   x509           Check X.509 public key certificates for compliance
+//!   store          Check the certificates in a trust store for compliance
+//!   url            Check the certificates and parameters of an HTTPS server for compliance
+//!   ct             Check the certificates logged for a domain for compliance
+//!   pins           Check a set of pinned keys for compliance
+//!   acme           Check the keys kept by ACME clients for compliance
+//!   git            Check the keys and certificates committed to a git repository for compliance
+//!   image          Check the keys and certificates in a container image for compliance
+//!   signed         Check the signatures of signed executables and packages for compliance
+//!   apk            Check the signers of Android application packages for compliance
+//!   eap            Check the 802.1X and MACsec settings of wpa_supplicant and hostapd for compliance
+//!   timestamp      Check time-stamps and long-term signatures for compliance
+//!   threshold      Check threshold and multi-signature signing setups for compliance
+//!   openssl        Check the providers and algorithms OpenSSL is configured with for compliance
+//!   firmware       Check the keys and certificates embedded in firmware images for compliance
+//!   storage        Check the encryption of disks and databases for compliance
+//!   protocol       Check the primitives declared for a protocol for compliance
+//!   kms            Check the keys kept by key management services for compliance
+//!   ldap           Check the certificates published in an LDAP directory for compliance
+//!   source         Check the TLS settings written in source code for compliance
+//!   watch          Continuously check keys in files or directories for compliance
+//!   list           List the primitives and guides that are supported
+//!   compare        Compare primitives across all guides
+//!   explain        Show the thresholds and references used by a guide
+//!   export-tables  Export the rules of guides and the primitives they accept
+//!   timeline       Plan the migration of keys to compliant primitives
+//!   schema         Print the JSON Schema of the JSON output
+//!   help           Print this message or the help of the given subcommand(s)
 //!
 //! Options:
 //!   -h, --help     Print help
 //!   -V, --version  Print version
 //! ```
+pub mod acme;
+pub mod apk;
+pub mod assess;
+pub mod audience;
+pub mod cache;
+pub mod compare;
+pub mod ct;
+pub mod eap;
+pub mod explain;
+pub mod export;
+pub mod firmware;
+pub mod git;
+pub mod guide;
+pub mod http;
+pub mod image;
+pub mod input;
 pub mod key;
+pub mod kms;
+pub mod ldap;
+pub mod list;
+pub mod pins;
+pub mod profile;
+pub mod provider;
+pub mod reader;
+pub mod remediation;
 pub mod report;
+pub mod scan;
+pub mod source;
+pub mod storage;
+pub mod store;
+pub mod template;
+pub mod threshold;
+pub mod timeline;
+pub mod timestamp;
+pub mod tls;
+pub mod watch;
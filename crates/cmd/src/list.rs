@@ -0,0 +1,178 @@
+//! Describe the primitives and guides that are built into the program.
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::json;
+use wardstone_core::context::Context;
+use wardstone_core::primitive::Primitive;
+use wardstone_core::registry::Builtin;
+
+use crate::guide::Guide;
+
+/// A single primitive or guide.
+#[derive(Serialize)]
+pub struct Item {
+  pub(crate) name: &'static str,
+  #[serde(skip_serializing_if = "<[_]>::is_empty")]
+  aliases: &'static [&'static str],
+  #[serde(skip_serializing_if = "<[_]>::is_empty")]
+  oids: &'static [&'static str],
+  #[serde(skip_serializing_if = "Option::is_none")]
+  description: Option<String>,
+  #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+  pub(crate) parameters: BTreeMap<&'static str, u16>,
+  /// Whether each guide deems the primitive compliant.
+  #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+  pub(crate) compliant: BTreeMap<&'static str, bool>,
+}
+
+impl Item {
+  /// Describes a primitive and assesses it against every guide using
+  /// the given context.
+  pub fn primitive(ctx: Context, builtin: Builtin) -> Self {
+    let (aliases, oids, parameters) = match builtin {
+      Builtin::Ecc(e) => (
+        e.aliases,
+        e.oids,
+        [("f", e.primitive.f), ("security", e.primitive.security())].into(),
+      ),
+      Builtin::Ffc(e) => (
+        e.aliases,
+        e.oids,
+        [
+          ("l", e.primitive.l),
+          ("n", e.primitive.n),
+          ("security", e.primitive.security()),
+        ]
+        .into(),
+      ),
+      Builtin::Hash(e) => (
+        e.aliases,
+        e.oids,
+        [("n", e.primitive.n), ("security", e.primitive.security())].into(),
+      ),
+      Builtin::Ifc(e) => (
+        e.aliases,
+        e.oids,
+        [("k", e.primitive.k), ("security", e.primitive.security())].into(),
+      ),
+      Builtin::Symmetric(e) => (
+        e.aliases,
+        e.oids,
+        [("security", e.primitive.security())].into(),
+      ),
+    };
+    let compliant = Guide::value_variants()
+      .iter()
+      .map(|guide| (guide.name(), complies(*guide, ctx, builtin)))
+      .collect();
+    Self {
+      name: builtin.name(),
+      aliases,
+      oids,
+      description: None,
+      parameters,
+      compliant,
+    }
+  }
+
+  /// Describes a guide.
+  pub fn guide(guide: Guide) -> Self {
+    let description = guide
+      .to_possible_value()
+      .and_then(|value| value.get_help().map(ToString::to_string));
+    Self {
+      name: guide.name(),
+      aliases: &[],
+      oids: &[],
+      description,
+      parameters: BTreeMap::new(),
+      compliant: BTreeMap::new(),
+    }
+  }
+}
+
+impl Display for Item {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    let mut s = String::from(self.name);
+    if !self.aliases.is_empty() {
+      s.push_str(format!(" ({})", self.aliases.join(", ")).as_str());
+    }
+    if let Some(description) = &self.description {
+      s.push_str(format!(": {}", description).as_str());
+    }
+    s.push('\n');
+    if !self.parameters.is_empty() {
+      let parameters: Vec<_> = self
+        .parameters
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect();
+      s.push_str(format!("  parameters: {}\n", parameters.join(", ")).as_str());
+    }
+    if !self.oids.is_empty() {
+      s.push_str(format!("  oids: {}\n", self.oids.join(", ")).as_str());
+    }
+    if !self.compliant.is_empty() {
+      let compliant: Vec<_> = self
+        .compliant
+        .iter()
+        .map(|(guide, ok)| format!("{} {}", guide, if *ok { "ok" } else { "fail" }))
+        .collect();
+      s.push_str(format!("  guides: {}\n", compliant.join(", ")).as_str());
+    }
+    write!(f, "{}", s)
+  }
+}
+
+/// A list of primitives or guides.
+pub struct Listing {
+  items: Vec<Item>,
+  json: bool,
+}
+
+impl Listing {
+  pub fn new(items: Vec<Item>, json: bool) -> Self {
+    Self { items, json }
+  }
+
+  pub fn to_json_string(&self) -> String {
+    json!({ "items": &self.items }).to_string()
+  }
+
+  /// Prints the listing to the standard output.
+  pub fn print(&self) {
+    if self.json {
+      println!("{}", self.to_json_string())
+    } else {
+      print!("{}", self)
+    }
+  }
+}
+
+impl Display for Listing {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    for item in self.items.iter() {
+      write!(f, "{}", item)?;
+    }
+    Ok(())
+  }
+}
+
+fn complies(guide: Guide, ctx: Context, builtin: Builtin) -> bool {
+  match builtin {
+    Builtin::Ecc(e) => guide
+      .validate_signature_algorithm(ctx, e.primitive.into())
+      .is_ok(),
+    Builtin::Ffc(e) => guide
+      .validate_signature_algorithm(ctx, e.primitive.into())
+      .is_ok(),
+    Builtin::Hash(e) => guide.validate_hash_function(ctx, e.primitive).is_ok(),
+    Builtin::Ifc(e) => guide
+      .validate_signature_algorithm(ctx, e.primitive.into())
+      .is_ok(),
+    Builtin::Symmetric(e) => guide.validate_symmetric(ctx, e.primitive).is_ok(),
+  }
+}
@@ -1,75 +1,134 @@
-use std::path::PathBuf;
+use std::env;
+use std::io::{self, Write};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
 
+use clap::builder::{PossibleValuesParser, TypedValueParser};
 use clap::{Parser, Subcommand, ValueEnum};
+use wardstone::acme;
+use wardstone::apk;
+use wardstone::assess::{
+  assess_blobs, audit_audiences, audit_key, Audiences, CommonArgs, Settings,
+};
+use wardstone::cache::Cache;
+use wardstone::compare::Comparison;
+use wardstone::ct;
+use wardstone::eap;
+use wardstone::explain::Explanation;
+use wardstone::export::Export;
+use wardstone::firmware;
+use wardstone::git;
+use wardstone::guide::Guide;
+use wardstone::image;
+use wardstone::input;
 use wardstone::key::certificate::Certificate;
-use wardstone::key::ssh::Ssh;
-use wardstone::key::Key;
-use wardstone::report::{Audit, Exit, Report, Verbosity};
+use wardstone::key::Error;
+use wardstone::kms::{self, Service};
+use wardstone::ldap::{self, Directory};
+use wardstone::list::{Item, Listing};
+use wardstone::pins;
+use wardstone::profile;
+use wardstone::provider;
+use wardstone::reader::{self, ArtifactReader, SshReader, X509Reader};
+use wardstone::report::metrics::Metrics;
+use wardstone::report::syslog::{Destination, Logger};
+use wardstone::report::webhook::{Payload, Webhook};
+use wardstone::report::{self, Audit, Exit, Output, Report, Verbosity};
+use wardstone::scan::Policy;
+use wardstone::source;
+use wardstone::storage;
+use wardstone::store::{self, Entry};
+use wardstone::template::Template;
+use wardstone::threshold;
+use wardstone::timeline::Timeline;
+use wardstone::timestamp;
+use wardstone::tls;
+use wardstone::watch::Watcher;
+use wardstone_core::batch;
 use wardstone_core::context::Context;
-use wardstone_core::primitive::asymmetric::Asymmetric;
-use wardstone_core::primitive::hash::Hash;
+use wardstone_core::primitive::threshold::Threshold;
 use wardstone_core::primitive::Security;
-use wardstone_core::standard::bsi::Bsi;
-use wardstone_core::standard::cnsa::Cnsa;
-use wardstone_core::standard::ecrypt::Ecrypt;
-use wardstone_core::standard::lenstra::Lenstra;
-use wardstone_core::standard::nist::Nist;
-use wardstone_core::standard::testing::strong::Strong;
-use wardstone_core::standard::testing::weak::Weak;
-use wardstone_core::standard::Standard;
-
-// Having this type in the core crate would reduce the amount of case
-// analysis done to find the function to execute but this would run
-// counter to the ability of users to create their own first-class
-// guides/standards.
+use wardstone_core::registry::{self, Builtin};
+use wardstone_core::standard::rule::Scope;
+use wardstone_core::standard::window::HORIZON;
+
+/// The type of primitive a rule applies to.
 #[derive(Clone, Copy, Debug, ValueEnum)]
-enum Guide {
-  /// BSI TR-02102 series of technical guidelines.
-  Bsi,
-  /// Commercial National Security Algorithm Suites, CNSA 1.0 and
-  /// CNSA 2.0.
-  Cnsa,
-  /// ECRYPT-CSA D5.4 Algorithms, Key Size and Protocols Report.
-  Ecrypt,
-  /// Key Lengths, Arjen K. Lenstra, The Handbook of Information
-  /// Security, 06/2004.
-  Lenstra,
-  /// NIST Special Publication 800-57 Part 1 Revision 5 standard.
-  Nist,
-  /// Mock standard with a minimum security requirement of at least
-  /// 256-bits.
-  Strong,
-  /// Mock standard with a minimum security requirement of at least
-  /// 64-bits.
-  Weak,
+enum Primitive {
+  /// Elliptic curve cryptography.
+  Ecc,
+  /// Finite field cryptography.
+  Ffc,
+  /// Integer factorization cryptography.
+  Ifc,
+  /// Hash functions used in digital signatures.
+  Hash,
+  /// Hash functions used in HMAC, key derivation and random bit
+  /// generation.
+  HashBased,
+  /// Symmetric key primitives.
+  Symmetric,
 }
 
-impl Guide {
-  fn validate_hash_function(&self, ctx: Context, hash: Hash) -> Result<Hash, Hash> {
-    match self {
-      Self::Bsi => Bsi::validate_hash(ctx, hash),
-      Self::Cnsa => Cnsa::validate_hash(ctx, hash),
-      Self::Ecrypt => Ecrypt::validate_hash(ctx, hash),
-      Self::Lenstra => Ecrypt::validate_hash(ctx, hash),
-      Self::Nist => Nist::validate_hash(ctx, hash),
-      Self::Strong => Strong::validate_hash(ctx, hash),
-      Self::Weak => Weak::validate_hash(ctx, hash),
+impl From<Primitive> for Scope {
+  fn from(primitive: Primitive) -> Self {
+    match primitive {
+      Primitive::Ecc => Self::Ecc,
+      Primitive::Ffc => Self::Ffc,
+      Primitive::Ifc => Self::Ifc,
+      Primitive::Hash => Self::Hash,
+      Primitive::HashBased => Self::HashBased,
+      Primitive::Symmetric => Self::Symmetric,
     }
   }
+}
 
-  fn validate_signature_algorithm(
-    &self,
-    ctx: Context,
-    key: Asymmetric,
-  ) -> Result<Asymmetric, Asymmetric> {
+/// The primitives or guides to list.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Catalog {
+  /// Elliptic curves.
+  Curves,
+  /// DSA domain parameters.
+  Ffc,
+  /// Guides that keys can be assessed against.
+  Guides,
+  /// Hash functions.
+  Hashes,
+  /// RSA instances.
+  Ifc,
+  /// Symmetric key primitives.
+  Symmetric,
+}
+
+impl Catalog {
+  fn items(&self, ctx: Context) -> Vec<Item> {
     match self {
-      Self::Bsi => Bsi::validate_asymmetric(ctx, key),
-      Self::Cnsa => Cnsa::validate_asymmetric(ctx, key),
-      Self::Ecrypt => Ecrypt::validate_asymmetric(ctx, key),
-      Self::Lenstra => Lenstra::validate_asymmetric(ctx, key),
-      Self::Nist => Nist::validate_asymmetric(ctx, key),
-      Self::Strong => Strong::validate_asymmetric(ctx, key),
-      Self::Weak => Weak::validate_asymmetric(ctx, key),
+      Self::Curves => registry::CURVES
+        .iter()
+        .map(|e| Item::primitive(ctx, Builtin::Ecc(e)))
+        .collect(),
+      Self::Ffc => registry::FFC
+        .iter()
+        .map(|e| Item::primitive(ctx, Builtin::Ffc(e)))
+        .collect(),
+      Self::Guides => Guide::value_variants()
+        .iter()
+        .map(|guide| Item::guide(*guide))
+        .collect(),
+      Self::Hashes => registry::HASH_FUNCTIONS
+        .iter()
+        .map(|e| Item::primitive(ctx, Builtin::Hash(e)))
+        .collect(),
+      Self::Ifc => registry::IFC
+        .iter()
+        .map(|e| Item::primitive(ctx, Builtin::Ifc(e)))
+        .collect(),
+      Self::Symmetric => registry::SYMMETRIC
+        .iter()
+        .map(|e| Item::primitive(ctx, Builtin::Symmetric(e)))
+        .collect(),
     }
   }
 }
@@ -86,138 +145,1352 @@ struct Options {
 enum Subcommands {
   /// Check an SSH public key for compliance.
   Ssh {
-    /// Guide to assess the key against.
-    #[arg(short, long, value_enum)]
-    guide: Guide,
+    #[command(flatten)]
+    common: CommonArgs,
+    /// Read every key again instead of reusing what was read from keys
+    /// whose contents have not changed since an earlier scan.
+    #[arg(long)]
+    no_cache: bool,
+    /// Suggest a command that generates a compliant replacement for
+    /// keys that fail the assessment.
+    #[arg(short, long)]
+    remediation: bool,
+    /// The paths to the public key file(s).
+    #[clap(value_name = "FILE")]
+    files: Vec<PathBuf>,
+  },
+  /// Check X.509 public key certificates for compliance.
+  X509 {
+    #[command(flatten)]
+    common: CommonArgs,
+    /// The maximum number of days a certificate may be valid for.
+    ///
+    /// For example, the CA/Browser Forum Baseline Requirements limit
+    /// the validity of TLS server certificates to 398 days.
+    #[arg(short, long, value_name = "DAYS")]
+    max_validity: Option<u64>,
+    /// Read every key again instead of reusing what was read from keys
+    /// whose contents have not changed since an earlier scan.
+    #[arg(long)]
+    no_cache: bool,
+    /// Suggest a command that generates a compliant replacement for
+    /// keys that fail the assessment.
+    #[arg(short, long)]
+    remediation: bool,
+    /// The certificates as DER or PEM encoded files.
+    #[clap(value_name = "FILE")]
+    files: Vec<PathBuf>,
+  },
+  /// Check the certificates in a trust store for compliance.
+  ///
+  /// Reads the certificates trusted by the operating system unless NSS
+  /// databases or PEM bundles are given.
+  Store {
+    #[command(flatten)]
+    common: CommonArgs,
+    /// Bundles of concatenated PEM certificates to read instead, such
+    /// as an exported trust store. Certificates are assessed as they are
+    /// read so bundles of any size can be checked.
+    #[arg(short, long, value_name = "FILE")]
+    bundle: Vec<PathBuf>,
+    /// The maximum number of days a certificate may be valid for.
+    #[arg(short, long, value_name = "DAYS")]
+    max_validity: Option<u64>,
+    /// NSS databases to read instead, such as a Firefox profile
+    /// directory or the path to its cert9.db file.
+    #[arg(short, long, value_name = "PATH")]
+    nss: Vec<PathBuf>,
+    /// Suggest a command that generates a compliant replacement for
+    /// keys that fail the assessment.
+    #[arg(short, long)]
+    remediation: bool,
+  },
+  /// Check the certificates and parameters of an HTTPS server for
+  /// compliance.
+  ///
+  /// A single handshake is performed and the certificate chain, key
+  /// exchange group and cipher the server picks are assessed. This is a
+  /// quick check rather than a full scan since the server might support
+  /// weaker parameters that are not offered.
+  ///
+  /// When several servers are given, the report groups the audits by
+  /// host and port with a verdict for each host.
+  Url {
+    #[command(flatten)]
+    common: CommonArgs,
+    /// The longest time in milliseconds to wait at random before every
+    /// connection so that connections do not arrive in bursts.
+    #[arg(long, value_name = "MILLISECONDS", default_value_t = 0)]
+    jitter: u64,
+    /// The maximum number of servers scanned at the same time.
+    #[arg(long, alias = "concurrency", value_name = "N", default_value_t = 16)]
+    max_concurrency: usize,
+    /// The maximum number of days a certificate may be valid for.
+    #[arg(short, long, value_name = "DAYS")]
+    max_validity: Option<u64>,
+    /// The maximum number of connections made to each host per second,
+    /// which may be a fraction such as 0.5. Connections are not limited
+    /// if not given.
+    #[arg(long, value_name = "N", value_parser = parse_rate)]
+    rate: Option<f64>,
+    /// Suggest a command that generates a compliant replacement for
+    /// keys that fail the assessment.
+    #[arg(short, long)]
+    remediation: bool,
+    /// Make a second handshake to find out how the server resumes
+    /// sessions.
+    #[arg(long)]
+    resumption: bool,
+    /// How many more times to try a server that could not be scanned.
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    retries: u32,
+    /// The number of seconds to wait for a server to respond.
+    #[arg(long, value_name = "SECONDS", default_value_t = 10)]
+    timeout: u64,
+    /// The URLs of the servers, e.g. https://example.com:8443.
+    #[arg(value_parser = tls::parse_url, required = true)]
+    urls: Vec<(String, u16)>,
+  },
+  /// Check the certificates logged for a domain for compliance.
+  ///
+  /// Searches Certificate Transparency logs through an aggregator such as
+  /// crt.sh for the certificates issued for a domain, downloads them and
+  /// assesses each, to find weak certificates issued for the domain that
+  /// its owner may not know of. Certificates are reported by their URL
+  /// at the aggregator.
+  Ct {
+    #[command(flatten)]
+    common: CommonArgs,
+    /// The aggregator to search, which must answer like crt.sh does.
+    #[arg(long, value_name = "URL", default_value = ct::CRT_SH)]
+    aggregator: String,
+    /// Also assess certificates that have expired.
+    #[arg(long)]
+    expired: bool,
+    /// The maximum number of certificates to download for every domain,
+    /// starting with the most recently logged.
+    #[arg(long, value_name = "N")]
+    limit: Option<usize>,
+    /// The maximum number of days a certificate may be valid for.
+    #[arg(short, long, value_name = "DAYS")]
+    max_validity: Option<u64>,
+    /// Suggest a command that generates a compliant replacement for
+    /// keys that fail the assessment.
+    #[arg(short, long)]
+    remediation: bool,
+    /// Also search for the certificates of every name below the domain.
+    #[arg(long)]
+    subdomains: bool,
+    /// The domains to search for, e.g. example.com.
+    #[arg(value_name = "DOMAIN", value_parser = ct::parse_domain, required = true)]
+    domains: Vec<String>,
+  },
+  /// Check a set of pinned keys for compliance.
+  ///
+  /// Reads the certificates and public keys of a pin set, the first of
+  /// which is the primary key and the rest backups, and assesses every
+  /// pinned key against the guide. Backup keys weaker than the primary
+  /// key fail, and the pin set as a whole fails if any pinned key does
+  /// or if it has no backup pin. Pins, such as those of an HPKP header,
+  /// an Android network security configuration or OkHttp, can be given
+  /// to only assess the keys they name.
+  Pins {
+    #[command(flatten)]
+    common: CommonArgs,
+    /// A pin, which is the base64 encoded SHA-256 hash of the
+    /// SubjectPublicKeyInfo of a key. Can be given more than once.
+    #[arg(long, value_name = "PIN")]
+    pin: Vec<String>,
+    /// A file the pins are read from, such as an HPKP header or an
+    /// Android network security configuration.
+    #[arg(long, value_name = "FILE")]
+    pins: Option<PathBuf>,
+    /// The certificates or public keys of the pin set, starting with the
+    /// primary key.
+    #[clap(value_name = "FILE", required = true)]
+    files: Vec<PathBuf>,
+  },
+  /// Check the keys kept by ACME clients for compliance.
+  ///
+  /// Reads the state directories of certbot, acme.sh and lego, which are
+  /// looked for where the clients keep them by default unless given, and
+  /// assesses the account keys, the keys of the certificates obtained
+  /// and the key type certificates are renewed with.
+  Acme {
+    #[command(flatten)]
+    common: CommonArgs,
+    /// Suggest the change to the settings of the client that makes it
+    /// use a compliant key type for keys that fail the assessment.
+    #[arg(short, long)]
+    remediation: bool,
+    /// The state directories of the clients, such as /etc/letsencrypt,
+    /// ~/.acme.sh or .lego.
+    #[clap(value_name = "DIR")]
+    dirs: Vec<PathBuf>,
+  },
+  /// Check the keys and certificates committed to a git repository for
+  /// compliance.
+  ///
+  /// Every file in the commit that is checked out that looks like a key
+  /// or certificate is assessed, and is reported by the commit it first
+  /// appears in and its path. Files are read from the repository
+  /// itself, so keys that were committed and later removed are found
+  /// with --history.
+  Git {
+    #[command(flatten)]
+    common: CommonArgs,
+    /// Assess the files in every commit reachable from a branch or tag
+    /// rather than only the one that is checked out.
+    #[arg(long)]
+    history: bool,
+    /// The maximum number of days a certificate may be valid for.
+    #[arg(short, long, value_name = "DAYS")]
+    max_validity: Option<u64>,
+    /// Suggest a command that generates a compliant replacement for
+    /// keys that fail the assessment.
+    #[arg(short, long)]
+    remediation: bool,
+    /// The path to the repository.
+    #[clap(value_name = "REPO", default_value = ".")]
+    repo: PathBuf,
+  },
+  /// Check the keys and certificates in a container image for
+  /// compliance.
+  ///
+  /// Every file in every layer of the image that looks like a key or
+  /// certificate is assessed, and is reported by the layer it is in and
+  /// its path. Files removed by a later layer are assessed too since
+  /// they still ship with the image.
+  Image {
+    #[command(flatten)]
+    common: CommonArgs,
+    /// The maximum number of days a certificate may be valid for.
+    #[arg(short, long, value_name = "DAYS")]
+    max_validity: Option<u64>,
+    /// Suggest a command that generates a compliant replacement for
+    /// keys that fail the assessment.
+    #[arg(short, long)]
+    remediation: bool,
+    /// A tarball written by docker save or podman save, or the name of
+    /// an image to pull from a registry such as
+    /// registry.opensuse.org/opensuse/tumbleweed.
+    #[clap(value_name = "IMAGE")]
+    image: String,
+  },
+  /// Check the signatures of signed executables and packages for
+  /// compliance.
+  ///
+  /// Reads the Authenticode signatures of Windows executables, the
+  /// signatures appended to Linux kernel modules and the OpenPGP
+  /// signatures of RPM packages. The certificate of the signer is
+  /// assessed along with the hash functions the signature relies on,
+  /// which is where MD5 and SHA-1 tend to persist.
+  Signed {
+    #[command(flatten)]
+    common: CommonArgs,
+    /// The maximum number of days a certificate may be valid for.
+    #[arg(short, long, value_name = "DAYS")]
+    max_validity: Option<u64>,
+    /// Read every file again instead of reusing what was read from
+    /// files whose contents have not changed since an earlier scan.
+    #[arg(long)]
+    no_cache: bool,
+    /// Suggest a command that generates a compliant replacement for
+    /// keys that fail the assessment.
+    #[arg(short, long)]
+    remediation: bool,
+    /// The signed files, whose format is told from their extension or
+    /// contents.
+    #[clap(value_name = "FILE")]
+    files: Vec<PathBuf>,
+  },
+  /// Check the signers of Android application packages for compliance.
+  ///
+  /// Reads the signers of every scheme an APK is signed with, that is
+  /// JAR signing (v1) and the APK Signing Block (v2, v3 and v3.1), and
+  /// assesses the certificate of every signer along with the digests
+  /// it signs. Signers are reported as `<apk>:<scheme>`, and those of v1
+  /// by the path of their signature block.
+  Apk {
+    #[command(flatten)]
+    common: CommonArgs,
+    /// The maximum number of days a certificate may be valid for.
+    #[arg(short, long, value_name = "DAYS")]
+    max_validity: Option<u64>,
+    /// Suggest a command that generates a compliant replacement for
+    /// keys that fail the assessment.
+    #[arg(short, long)]
+    remediation: bool,
+    /// The paths to the APKs.
+    #[clap(value_name = "FILE")]
+    files: Vec<PathBuf>,
+  },
+  /// Check the 802.1X and MACsec settings of wpa_supplicant and hostapd
+  /// for compliance.
+  ///
+  /// Reads every network of a wpa_supplicant configuration, or the
+  /// settings of hostapd, and assesses the EAP methods and versions of
+  /// TLS they allow, the MACsec cipher suite and the certificates they
+  /// name. Certificates are reported as `<file>:<network>:<setting>`,
+  /// and the settings along with the first certificate of each network.
+  Eap {
+    #[command(flatten)]
+    common: CommonArgs,
+    /// The maximum number of days a certificate may be valid for.
+    #[arg(short, long, value_name = "DAYS")]
+    max_validity: Option<u64>,
+    /// Suggest a command that generates a compliant replacement for
+    /// keys that fail the assessment.
+    #[arg(short, long)]
+    remediation: bool,
+    /// The paths to the configuration files.
+    #[clap(value_name = "FILE", required = true)]
+    files: Vec<PathBuf>,
+  },
+  /// Check time-stamps and long-term signatures for compliance.
+  ///
+  /// Reads RFC 3161 time-stamp tokens and responses, CAdES signatures
+  /// and PDF documents signed with PAdES, and assesses every layer, that
+  /// is the signature and each time-stamp added over it, by the key of
+  /// its signer and the hash functions it relies on. Layers are reported
+  /// as `<file>:<layer>`, and those that fail without a later time-stamp
+  /// that complies covering them are the ones to time-stamp again.
+  Timestamp {
+    #[command(flatten)]
+    common: CommonArgs,
+    /// The maximum number of days a certificate may be valid for.
+    #[arg(short, long, value_name = "DAYS")]
+    max_validity: Option<u64>,
+    /// Suggest a command that generates a compliant replacement for
+    /// keys that fail the assessment.
+    #[arg(short, long)]
+    remediation: bool,
+    /// The time-stamp tokens, CAdES signatures or signed PDF documents.
+    #[clap(value_name = "FILE", required = true)]
+    files: Vec<PathBuf>,
+  },
+  /// Check threshold and multi-signature signing setups for compliance.
+  ///
+  /// Assesses setups in which any t of n parties sign together, given
+  /// as `<scheme>_<curve>_<t>of<n>` and optionally named, such as
+  /// `treasury=frost_ed25519_3of5`. The scheme is one of ecdsa, frost,
+  /// bls or multisig. The curve is assessed as the signature algorithm,
+  /// along with whether the setup actually splits the key.
+  Threshold {
+    #[command(flatten)]
+    common: CommonArgs,
+    /// The signing setups, e.g. ecdsa_secp256k1_2of3 or
+    /// cold=bls_bls12_381_5of7.
+    #[arg(value_name = "SETUP", value_parser = threshold::parse, required = true)]
+    setups: Vec<(PathBuf, Threshold)>,
+  },
+  /// Check the providers and algorithms OpenSSL is configured with for
+  /// compliance.
+  ///
+  /// Reads an OpenSSL 3 configuration, following the files it includes
+  /// such as fipsmodule.cnf, and assesses the weakest hash function and
+  /// cipher of the providers it activates, along with the smallest RSA
+  /// key it accepts. Configurations are reported as
+  /// `<file>:<providers>`. Without arguments, the file named by
+  /// OPENSSL_CONF or the one in the default place is read.
+  Openssl {
+    #[command(flatten)]
+    common: CommonArgs,
+    /// Also require the approved mode of FIPS 140-3, that is an
+    /// installed FIPS provider that algorithms are fetched from.
+    #[arg(long)]
+    fips: bool,
+    /// The paths to the configuration files, such as
+    /// /etc/ssl/openssl.cnf.
+    #[clap(value_name = "FILE")]
+    files: Vec<PathBuf>,
+  },
+  /// Check the keys and certificates embedded in firmware images for
+  /// compliance.
+  ///
+  /// Searches binary blobs, such as images dumped from flash, for DER
+  /// and PEM encoded certificates, public keys and private keys, which
+  /// are reported as `<file>:<offset>:<kind>`. This is a heuristic, so
+  /// keys that are compressed or encrypted are not found.
+  Firmware {
+    #[command(flatten)]
+    common: CommonArgs,
+    /// The maximum number of days a certificate may be valid for.
+    #[arg(short, long, value_name = "DAYS")]
+    max_validity: Option<u64>,
+    /// Suggest a command that generates a compliant replacement for
+    /// keys that fail the assessment.
+    #[arg(short, long)]
+    remediation: bool,
+    /// The paths to the firmware images.
+    #[clap(value_name = "FILE", required = true)]
+    files: Vec<PathBuf>,
+  },
+  /// Check the encryption of disks and databases for compliance.
+  ///
+  /// Reads LUKS1 and LUKS2 headers, from the device or a header backup,
+  /// and the data-at-rest encryption settings of MySQL and MariaDB
+  /// option files, MongoDB mongod.conf and Oracle init.ora. The weakest
+  /// cipher is assessed against the guide, and the key derivation of
+  /// every key slot against the costs of the OWASP Password Storage
+  /// Cheat Sheet. Results are reported as `<file>:<kind>`, such as
+  /// `/dev/sda2:luks2`.
+  Storage {
+    #[command(flatten)]
+    common: CommonArgs,
+    /// The paths to the LUKS devices, header backups or database
+    /// configuration files.
+    #[clap(value_name = "FILE", required = true)]
+    files: Vec<PathBuf>,
+  },
+  /// Check the primitives declared for a protocol for compliance.
+  ///
+  /// Reads protocol profiles, YAML documents that declare the key
+  /// exchange, signature, AEAD, KDF and hash primitives of a protocol
+  /// by the names `wardstone list` gives, and assesses them together.
+  /// The weakest primitive of every role is reported, along with the
+  /// one that bounds the security of the whole protocol. Protocols are
+  /// reported as `<file>:<name>`.
+  Protocol {
+    #[command(flatten)]
+    common: CommonArgs,
+    /// The paths to the protocol profiles.
+    #[clap(value_name = "FILE", required = true)]
+    files: Vec<PathBuf>,
+  },
+  /// Check the keys kept by key management services for compliance.
+  ///
+  /// Lists the keys of HashiCorp Vault transit engines, AWS KMS, Google
+  /// Cloud KMS key rings and Azure Key Vault through their APIs, with
+  /// the credentials of their usual environment variables, and assesses
+  /// the type and size of every key against the guide. Keys are reported
+  /// by their ARN, resource name or URL. Requires wardstone to be built
+  /// with the `kms` feature.
+  Kms {
+    #[command(flatten)]
+    common: CommonArgs,
+    /// The services to list keys from, as vault[:<mount>],
+    /// aws[:<region>], gcp:projects/<project>/locations/<location>/keyRings/<ring>
+    /// or azure:<vault>.
+    #[arg(value_name = "SERVICE", value_parser = Service::parse, required = true)]
+    services: Vec<Service>,
+  },
+  /// Check the certificates published in an LDAP directory for
+  /// compliance.
+  ///
+  /// Searches the directory for every entry with a userCertificate or
+  /// cACertificate attribute, such as those Active Directory Certificate
+  /// Services publishes to, and assesses each certificate, reporting it
+  /// by the DN of its entry. The search is anonymous unless a bind DN is
+  /// given, whose password is read from the LDAP_PASSWORD environment
+  /// variable.
+  Ldap {
+    #[command(flatten)]
+    common: CommonArgs,
+    /// The DN to search below. Defaults to the default naming context of
+    /// the directory.
+    #[arg(short, long, value_name = "DN")]
+    base: Option<String>,
+    /// The DN to bind as, e.g. CN=scanner,CN=Users,DC=example,DC=com.
+    #[arg(short = 'D', long, value_name = "DN")]
+    bind_dn: Option<String>,
+    /// The maximum number of days a certificate may be valid for.
+    #[arg(short, long, value_name = "DAYS")]
+    max_validity: Option<u64>,
+    /// The directories to search, e.g. ldaps://dc.example.com.
+    #[arg(value_name = "URL", value_parser = Directory::parse, required = true)]
+    directories: Vec<Directory>,
+  },
+  /// Check the TLS settings written in source code for compliance.
+  ///
+  /// Scans Go, Python, Java, Kotlin and Rust files, or every such file
+  /// below a directory, for the idioms that set the versions, cipher
+  /// suites and certificate verification of TLS. The ciphers of the
+  /// suites are assessed against the guide, while deprecated versions,
+  /// unsafe suites and disabled verification fail whatever the guide.
+  /// Findings carry the line they were found at, which `--format sarif`
+  /// anchors them to for code scanning. This is a best-effort scan of
+  /// the text rather than a parse of the code.
+  Source {
+    #[command(flatten)]
+    common: CommonArgs,
+    /// The source files or the directories to scan.
+    #[clap(value_name = "PATH", required = true)]
+    paths: Vec<PathBuf>,
+  },
+  /// Continuously check keys in files or directories for compliance.
+  ///
+  /// Every path is scanned on startup and then rescanned at the given
+  /// interval. Only keys that have been created or modified since the
+  /// previous scan are assessed again.
+  Watch {
+    #[command(flatten)]
+    common: CommonArgs,
+    /// The format of the keys that are being watched, which is told
+    /// from the extension or contents of every file if not given.
+    #[arg(short = 'f', long, value_parser = format_parser())]
+    key_format: Option<&'static dyn ArtifactReader>,
+    /// The number of seconds to wait between scans.
+    #[arg(short, long, default_value_t = 10)]
+    interval: u64,
+    /// Send the results to the system log instead of the standard
+    /// output, with a priority that follows the severity of each
+    /// finding.
+    #[arg(short, long, value_enum, conflicts_with = "json")]
+    log: Option<Destination>,
+    /// The maximum number of days a certificate may be valid for.
+    ///
+    /// For example, the CA/Browser Forum Baseline Requirements limit
+    /// the validity of TLS server certificates to 398 days.
+    #[arg(short, long, value_name = "DAYS")]
+    max_validity: Option<u64>,
+    /// Serve Prometheus metrics on /metrics at the given address, e.g.
+    /// 127.0.0.1:9464.
+    #[arg(long, value_name = "ADDRESS")]
+    metrics: Option<SocketAddr>,
+    /// Suggest a command that generates a compliant replacement for
+    /// keys that fail the assessment.
+    #[arg(short, long)]
+    remediation: bool,
+    /// Send new findings that fail the assessment to a webhook with an
+    /// HTTP POST request.
+    #[arg(long, value_name = "URL")]
+    webhook: Option<String>,
+    /// The payload sent to the webhook.
+    #[arg(long, value_enum, default_value_t = Payload::Json, requires = "webhook")]
+    webhook_payload: Payload,
+    /// Render the payload sent to the webhook with a Handlebars style
+    /// template instead.
+    #[arg(long, value_name = "FILE", requires = "webhook")]
+    webhook_template: Option<PathBuf>,
+    /// The files or directories to watch.
+    #[clap(value_name = "PATH")]
+    paths: Vec<PathBuf>,
+  },
+  /// List the primitives and guides that are supported.
+  ///
+  /// Each primitive is shown with its parameters, aliases and object
+  /// identifiers along with whether each guide deems it compliant.
+  List {
+    /// The primitives or guides to list.
+    #[arg(value_enum)]
+    catalog: Catalog,
     /// JSON formatted output.
     #[arg(short, long)]
     json: bool,
-    /// Do not print output.
-    #[arg(short, long, conflicts_with = "verbose")]
-    quiet: bool,
     /// The minimum security level required.
-    ///
-    /// If a sufficiently low value is used then the application will
-    /// default to the minimum security specified by the standard.
     #[arg(short, long, default_value_t = 0)]
     security: Security,
-    /// Verbose output.
-    #[arg(short, long, conflicts_with = "quiet")]
-    verbose: bool,
     /// The year in which a recommendation is expected to be valid.
-    ///
-    /// Note that this does not necessarily mean that a primitive will
-    /// be deemed insecure beyond this point. Indeed, recommendations
-    /// are usually done with a longer horizon in mind. For example,
-    /// setting this value to 2023, one would expect any passing
-    /// primitive to be secure for the next 5 to 7 years,
-    /// conservatively, subject to cryptanalytic developments.
     #[arg(short, long, default_value_t = 2023)]
     year: u16,
-    /// The paths to the public key file(s).
-    #[clap(value_name = "FILE")]
-    files: Vec<PathBuf>,
   },
-  /// Check X.509 public key certificates for compliance.
-  X509 {
-    /// Guide to assess the certificate against.
-    #[arg(short, long, value_enum)]
+  /// Compare primitives across all guides.
+  ///
+  /// Shows the security of each primitive and whether every guide
+  /// deems it compliant side by side.
+  Compare {
+    /// The names of the primitives to compare, e.g. p256 ed25519
+    /// rsa3072.
+    #[arg(num_args = 2.., required = true, value_parser = parse_builtin)]
+    primitives: Vec<Builtin>,
+    /// JSON formatted output.
+    #[arg(short, long)]
+    json: bool,
+    /// The minimum security level required.
+    #[arg(short, long, default_value_t = 0)]
+    security: Security,
+    /// The year in which a recommendation is expected to be valid.
+    #[arg(short, long, default_value_t = 2023)]
+    year: u16,
+  },
+  /// Show the thresholds and references used by a guide.
+  ///
+  /// Prints the minimum security, cutoff year and the part of the
+  /// publication each rule of the guide is based on.
+  Explain {
+    /// Guide to explain.
+    #[arg(value_enum)]
     guide: Guide,
+    /// Only explain the rule for this type of primitive.
+    #[arg(value_enum)]
+    primitive: Option<Primitive>,
     /// JSON formatted output.
     #[arg(short, long)]
     json: bool,
-    /// Do not print output.
-    #[arg(short, long, conflicts_with = "verbose")]
-    quiet: bool,
+  },
+  /// Export the rules of guides and the primitives they accept.
+  ///
+  /// Writes the minimum security, cutoff year and reference of every
+  /// rule together with the built-in primitives the guide deems
+  /// compliant under it, as JSON or CSV for documentation and compliance
+  /// tools.
+  ExportTables {
+    /// Guides to export, which can be given more than once. Defaults to
+    /// every guide based on a publication.
+    #[arg(short, long, value_enum)]
+    guide: Vec<Guide>,
+    /// CSV formatted output instead of JSON.
+    #[arg(long)]
+    csv: bool,
     /// The minimum security level required.
-    ///
-    /// If a sufficiently low value is used then the application will
-    /// default to the minimum security specified by the standard.
     #[arg(short, long, default_value_t = 0)]
     security: Security,
-    /// Verbose output.
-    #[arg(short, long, conflicts_with = "quiet")]
-    verbose: bool,
     /// The year in which a recommendation is expected to be valid.
-    ///
-    /// Note that this does not necessarily mean that a primitive will
-    /// be deemed insecure beyond this point. Indeed, recommendations
-    /// are usually done with a longer horizon in mind. For example,
-    /// setting this value to 2023, one would expect any passing
-    /// primitive to be secure for the next 5 to 7 years,
-    /// conservatively, subject to cryptanalytic developments.
     #[arg(short, long, default_value_t = 2023)]
     year: u16,
-    /// The certificates as DER or PEM encoded files.
+  },
+  /// Plan the migration of keys to compliant primitives.
+  ///
+  /// Lists the year in which every primitive used by the keys ceases to
+  /// be compliant under each guide, starting with the keys that need to
+  /// be replaced first.
+  Timeline {
+    /// The format of the keys to plan for, which is told from the
+    /// extension or contents of every file if not given.
+    #[arg(short, long, value_parser = format_parser())]
+    format: Option<&'static dyn ArtifactReader>,
+    /// Guides to assess the keys against, which can be given more than
+    /// once. Defaults to every guide based on a publication.
+    #[arg(short, long, value_enum)]
+    guide: Vec<Guide>,
+    /// JSON formatted output.
+    #[arg(short, long)]
+    json: bool,
+    /// The minimum security level required.
+    #[arg(short, long, default_value_t = 0)]
+    security: Security,
+    /// The year from which to plan.
+    #[arg(short = 'y', long, alias = "year", default_value_t = 2023)]
+    from_year: u16,
+    /// The last year to plan for. Primitives still compliant by then are
+    /// not given a year.
+    #[arg(long, default_value_t = HORIZON)]
+    to_year: u16,
+    /// The paths to the keys.
     #[clap(value_name = "FILE")]
     files: Vec<PathBuf>,
   },
+  /// Print the JSON Schema of the JSON output.
+  ///
+  /// The output carries the version of the schema it conforms to so
+  /// that integrations can validate against it.
+  Schema,
+}
+
+/// Where the results of every scan of `watch` are sent in addition to
+/// or instead of the standard output.
+struct Sinks {
+  logger: Option<Logger>,
+  metrics: Option<Metrics>,
+  webhook: Option<Webhook>,
 }
 
 impl Subcommands {
-  fn assess<T: Key>(
-    ctx: Context,
+  fn assess(
+    audiences: &Audiences,
+    reader: Option<&'static dyn ArtifactReader>,
+    paths: &[PathBuf],
+    cache: Option<Cache>,
+    report: &mut Report,
+  ) {
+    // Keys are reported in the order of their paths rather than that of
+    // the arguments so that runs over the same files can be compared,
+    // and so that keys that cannot be read are listed in the same order.
+    let mut paths: Vec<&Path> = paths.iter().map(PathBuf::as_path).collect();
+    paths.sort();
+    let audits = Self::audit_all(audiences, reader, &paths, cache.as_ref());
+    // Failing to update the cache only makes the next scan slower.
+    if let Some(Err(err)) = cache.map(|cache| cache.save()) {
+      eprintln!("{}", err);
+    }
+    // A key that cannot be read should not stop the rest from being
+    // assessed, but is still reflected in the exit code.
+    for audit in audits {
+      match audit {
+        Ok(audits) => audits.into_iter().for_each(|audit| report.push(audit)),
+        Err(err) => report.skip(err),
+      }
+    }
+  }
+
+  /// Reads and audits the keys in parallel, returning the outcomes in
+  /// the order of the paths. Every key is read once and audited for
+  /// every audience. The format of every key is told from its file
+  /// unless a reader is given. Keys found in the cache are not read
+  /// again. Errors name the file they arose in.
+  fn audit_all(
+    audiences: &Audiences,
+    reader: Option<&'static dyn ArtifactReader>,
+    paths: &[&Path],
+    cache: Option<&Cache>,
+  ) -> Vec<Result<Vec<Audit>, Error>> {
+    batch::validate_all(Context::default(), paths, |_, path| {
+      let reader = match reader {
+        Some(reader) => reader,
+        None => reader::detect(path).map_err(|err| err.in_file(path))?,
+      };
+      let key = match cache {
+        Some(cache) => cache.read(reader, path),
+        None => reader.read(path),
+      };
+      let key = key.map_err(|err| err.in_file(path))?;
+      Ok(audit_audiences(audiences, |settings| {
+        audit_key(settings, reader, path, key.as_ref())
+      }))
+    })
+  }
+
+  fn assess_store(
+    audiences: &Audiences,
+    entries: impl Iterator<Item = Result<Entry, Error>>,
+    report: &mut Report,
+  ) {
+    for entry in entries {
+      // A certificate that cannot be read should not prevent the rest
+      // of the store from being assessed. Each certificate is dropped
+      // once assessed so that only its audit is kept.
+      let entry = match entry {
+        Ok(entry) => entry,
+        Err(err) => {
+          report.skip(err);
+          continue;
+        },
+      };
+      match Certificate::from_bytes(&entry.der) {
+        Ok(certificate) => {
+          for audit in audit_audiences(audiences, |settings| {
+            audit_key(settings, &X509Reader, &entry.label, &certificate)
+          }) {
+            report.push(audit);
+          }
+        },
+        Err(err) => report.skip(err.in_file(entry.label)),
+      }
+    }
+    // The trust store of the operating system is not listed in the same
+    // order everywhere.
+    report.sort();
+  }
+
+  fn plan(
+    mut timeline: Timeline,
+    reader: Option<&'static dyn ArtifactReader>,
     paths: &Vec<PathBuf>,
-    guide: Guide,
-    json: bool,
-    verbosity: Verbosity,
-  ) -> Exit {
-    let mut report = Report::new(verbosity, json);
+  ) -> Result<(), Error> {
     for path in paths {
-      let key = match T::from_file(path) {
-        Ok(got) => got,
-        Err(err) => return Exit::Failure(err),
+      let key = match reader {
+        Some(reader) => reader.read(path),
+        None => reader::detect(path).and_then(|reader| reader.read(path)),
       };
-      let hash_function = key.hash_function();
-      let signature_algorithm = key.signature_algorithm();
-      let mut audit = Audit::new(path, hash_function, signature_algorithm);
-      if let Some(got) = hash_function {
-        match guide.validate_hash_function(ctx, got) {
-          Ok(want) => audit.compliant_hash_function(want),
-          Err(want) => audit.noncompliant_hash_function(want),
+      let key = key.map_err(|err| err.in_file(path))?;
+      timeline.push(path, key.as_ref());
+    }
+    timeline.print();
+    Ok(())
+  }
+
+  fn watch(
+    audiences: &Audiences,
+    reader: Option<&'static dyn ArtifactReader>,
+    paths: &[PathBuf],
+    report: impl Fn() -> Report,
+    mut sinks: Sinks,
+    interval: Duration,
+  ) -> ! {
+    let mut watcher = Watcher::new(paths);
+    loop {
+      let mut report = report();
+      let changed = watcher.changed();
+      let paths: Vec<&Path> = changed.iter().map(PathBuf::as_path).collect();
+      // A key that cannot be read should not stop other keys from
+      // being watched, nor the watch itself.
+      for audit in Self::audit_all(audiences, reader, &paths, None) {
+        match audit {
+          Ok(audits) => audits.into_iter().for_each(|audit| report.push(audit)),
+          Err(err) => eprintln!("{}", err),
         }
       }
-      match guide.validate_signature_algorithm(ctx, signature_algorithm) {
-        Ok(want) => audit.compliant_signature(want),
-        Err(want) => audit.noncompliant_signature(want),
+      if let Some(metrics) = &sinks.metrics {
+        metrics.update(&report, watcher.files());
       }
-      report.push(audit);
+      if let Some(webhook) = &mut sinks.webhook {
+        if let Err(err) = webhook.notify(&report) {
+          eprintln!("{}", err);
+        }
+      }
+      match &sinks.logger {
+        Some(logger) => {
+          if let Err(err) = logger.log(&report) {
+            eprintln!("{}", Error::Log(err.to_string()));
+          }
+        },
+        None => {
+          report.print();
+          // Findings should show up promptly even when the output is
+          // piped into another program.
+          let _ = io::stdout().flush();
+        },
+      }
+      thread::sleep(interval);
     }
-    Exit::Success(report)
   }
 
   pub fn run(&self) -> Exit {
+    match self.execute() {
+      Ok(exit) => exit,
+      Err(err) => Exit::Failure(err),
+    }
+  }
+
+  fn execute(&self) -> Result<Exit, Error> {
     match self {
       Self::Ssh {
-        guide,
-        json,
-        quiet,
-        verbose,
+        common,
+        no_cache,
+        remediation,
         files,
+      } => {
+        let settings = Settings {
+          remediation: *remediation,
+          ..common.settings()
+        };
+        let audiences = common.audiences(settings)?;
+        let mut report = common.report()?;
+        let cache = if *no_cache { None } else { Cache::open() };
+        Self::assess(&audiences, Some(&SshReader), files, cache, &mut report);
+        Ok(Exit::Success(report))
+      },
+      Self::X509 {
+        common,
+        max_validity,
+        no_cache,
+        remediation,
+        files,
+      } => {
+        let settings = Settings {
+          max_validity: *max_validity,
+          remediation: *remediation,
+          ..common.settings()
+        };
+        let audiences = common.audiences(settings)?;
+        let mut report = common.report()?;
+        let cache = if *no_cache { None } else { Cache::open() };
+        Self::assess(&audiences, Some(&X509Reader), files, cache, &mut report);
+        Ok(Exit::Success(report))
+      },
+      Self::Store {
+        common,
+        bundle,
+        max_validity,
+        nss,
+        remediation,
+      } => {
+        let settings = Settings {
+          max_validity: *max_validity,
+          remediation: *remediation,
+          ..common.settings()
+        };
+        let audiences = common.audiences(settings)?;
+        let mut report = common.report()?;
+        let mut sources: Vec<Box<dyn Iterator<Item = Result<Entry, Error>>>> = Vec::new();
+        if nss.is_empty() && bundle.is_empty() {
+          sources.push(Box::new(store::system()?.into_iter().map(Ok)));
+        }
+        for path in nss.iter() {
+          sources.push(Box::new(store::nss(path)?.into_iter().map(Ok)));
+        }
+        for path in bundle.iter() {
+          sources.push(Box::new(store::bundle(path)?));
+        }
+        let entries = sources.into_iter().flatten();
+        Self::assess_store(&audiences, entries, &mut report);
+        Ok(Exit::Success(report))
+      },
+      Self::Url {
+        common,
+        jitter,
+        max_concurrency,
+        max_validity,
+        rate,
+        remediation,
+        resumption,
+        retries,
+        timeout,
+        urls,
+      } => {
+        let settings = Settings {
+          max_validity: *max_validity,
+          remediation: *remediation,
+          ..common.settings()
+        };
+        let audiences = common.audiences(settings)?;
+        let mut report = common.report()?;
+        let policy = Policy {
+          concurrency: *max_concurrency,
+          timeout: Duration::from_secs(*timeout),
+          retries: *retries,
+          interval: rate.map_or(Duration::ZERO, |rate| Duration::from_secs_f64(1.0 / rate)),
+          jitter: Duration::from_millis(*jitter),
+        };
+        let handshakes = policy.scan(urls, |host, port, timeout| {
+          tls::handshake(host, port, timeout, *resumption)
+        });
+        if urls.len() == 1 {
+          for handshake in handshakes {
+            for audit in tls::audit(&audiences, handshake?) {
+              report.push(audit);
+            }
+          }
+        } else {
+          // A server that cannot be reached should not prevent the rest
+          // of the estate from being assessed.
+          for ((host, port), handshake) in urls.iter().zip(handshakes) {
+            let audits = handshake.map(|handshake| tls::audit(&audiences, handshake));
+            report.push_service(host, *port, audits);
+          }
+        }
+        Ok(Exit::Success(report))
+      },
+      Self::Ct {
+        common,
+        aggregator,
+        expired,
+        limit,
+        max_validity,
+        remediation,
+        subdomains,
+        domains,
+      } => {
+        let settings = Settings {
+          max_validity: *max_validity,
+          remediation: *remediation,
+          ..common.settings()
+        };
+        let audiences = common.audiences(settings)?;
+        let mut report = common.report()?;
+        ct::assess(
+          &audiences,
+          aggregator,
+          domains,
+          *subdomains,
+          *expired,
+          *limit,
+          &mut report,
+        );
+        Ok(Exit::Success(report))
+      },
+      Self::Pins {
+        common,
+        pin,
+        pins,
+        files,
+      } => {
+        let audiences = common.audiences(common.settings())?;
+        let mut report = common.report()?;
+        let mut declared = pin.join(" ");
+        if let Some(path) = pins {
+          let data = input::open(path).map_err(|err| err.in_file(path))?;
+          declared.push_str(&format!(" {}", String::from_utf8_lossy(&data)));
+        }
+        let declared = pins::parse(&declared);
+        if (!pin.is_empty() || pins.is_some()) && declared.is_empty() {
+          return Err(Error::Pins("no pin is given".to_string()));
+        }
+        // The pin set is reported under the file its pins were read from.
+        let set = pins.as_deref().unwrap_or(Path::new("pin set"));
+        pins::assess(&audiences, set, files, &declared, &mut report)?;
+        Ok(Exit::Success(report))
+      },
+      Self::Acme {
+        common,
+        remediation,
+        dirs,
+      } => {
+        let settings = Settings {
+          remediation: *remediation,
+          ..common.settings()
+        };
+        let audiences = common.audiences(settings)?;
+        let mut report = common.report()?;
+        let dirs = if dirs.is_empty() {
+          acme::default_dirs()
+        } else {
+          dirs.clone()
+        };
+        if dirs.is_empty() {
+          return Err(Error::Acme("no state directory found".to_string()));
+        }
+        let mut artifacts = Vec::new();
+        for dir in dirs.iter() {
+          artifacts.extend(acme::discover(dir)?);
+        }
+        acme::assess(&audiences, artifacts, &mut report);
+        Ok(Exit::Success(report))
+      },
+      Self::Git {
+        common,
+        history,
+        max_validity,
+        remediation,
+        repo,
+      } => {
+        let settings = Settings {
+          max_validity: *max_validity,
+          remediation: *remediation,
+          ..common.settings()
+        };
+        let audiences = common.audiences(settings)?;
+        let mut report = common.report()?;
+        assess_blobs(&audiences, git::sweep(repo, *history)?, &mut report);
+        Ok(Exit::Success(report))
+      },
+      Self::Image {
+        common,
+        max_validity,
+        remediation,
+        image,
+      } => {
+        let settings = Settings {
+          max_validity: *max_validity,
+          remediation: *remediation,
+          ..common.settings()
+        };
+        let audiences = common.audiences(settings)?;
+        let mut report = common.report()?;
+        let path = Path::new(image);
+        let blobs = if path.is_file() {
+          image::open(path)?
+        } else {
+          image::pull(image)?
+        };
+        assess_blobs(&audiences, blobs, &mut report);
+        Ok(Exit::Success(report))
+      },
+      Self::Signed {
+        common,
+        max_validity,
+        no_cache,
+        remediation,
+        files,
+      } => {
+        let settings = Settings {
+          max_validity: *max_validity,
+          remediation: *remediation,
+          ..common.settings()
+        };
+        let audiences = common.audiences(settings)?;
+        let mut report = common.report()?;
+        let cache = if *no_cache { None } else { Cache::open() };
+        Self::assess(&audiences, None, files, cache, &mut report);
+        Ok(Exit::Success(report))
+      },
+      Self::Apk {
+        common,
+        max_validity,
+        remediation,
+        files,
+      } => {
+        let settings = Settings {
+          max_validity: *max_validity,
+          remediation: *remediation,
+          ..common.settings()
+        };
+        let audiences = common.audiences(settings)?;
+        let mut report = common.report()?;
+        apk::assess(&audiences, files, &mut report);
+        Ok(Exit::Success(report))
+      },
+      Self::Eap {
+        common,
+        max_validity,
+        remediation,
+        files,
+      } => {
+        let settings = Settings {
+          max_validity: *max_validity,
+          remediation: *remediation,
+          ..common.settings()
+        };
+        let audiences = common.audiences(settings)?;
+        let mut report = common.report()?;
+        eap::assess(&audiences, files, &mut report);
+        Ok(Exit::Success(report))
+      },
+      Self::Timestamp {
+        common,
+        max_validity,
+        remediation,
+        files,
+      } => {
+        let settings = Settings {
+          max_validity: *max_validity,
+          remediation: *remediation,
+          ..common.settings()
+        };
+        let audiences = common.audiences(settings)?;
+        let mut report = common.report()?;
+        timestamp::assess(&audiences, files, &mut report);
+        Ok(Exit::Success(report))
+      },
+      Self::Threshold { common, setups } => {
+        let audiences = common.audiences(common.settings())?;
+        let mut report = common.report()?;
+        for (label, key) in setups {
+          for audit in audit_audiences(&audiences, |settings| {
+            threshold::audit(settings, label, *key)
+          }) {
+            report.push(audit);
+          }
+        }
+        Ok(Exit::Success(report))
+      },
+      Self::Openssl {
+        common,
+        fips,
+        files,
+      } => {
+        let audiences = common.audiences(common.settings())?;
+        let mut report = common.report()?;
+        let files = if files.is_empty() {
+          provider::default_files()
+        } else {
+          files.clone()
+        };
+        if files.is_empty() {
+          return Err(Error::Provider("no configuration file found".to_string()));
+        }
+        provider::assess(&audiences, &files, *fips, &mut report);
+        Ok(Exit::Success(report))
+      },
+      Self::Firmware {
+        common,
+        max_validity,
+        remediation,
+        files,
+      } => {
+        let settings = Settings {
+          max_validity: *max_validity,
+          remediation: *remediation,
+          ..common.settings()
+        };
+        let audiences = common.audiences(settings)?;
+        let mut report = common.report()?;
+        firmware::assess(&audiences, files, &mut report);
+        Ok(Exit::Success(report))
+      },
+      Self::Storage { common, files } => {
+        let audiences = common.audiences(common.settings())?;
+        let mut report = common.report()?;
+        storage::assess(&audiences, files, &mut report);
+        Ok(Exit::Success(report))
+      },
+      Self::Protocol { common, files } => {
+        let audiences = common.audiences(common.settings())?;
+        let mut report = common.report()?;
+        profile::assess(&audiences, files, &mut report);
+        Ok(Exit::Success(report))
+      },
+      Self::Kms { common, services } => {
+        let audiences = common.audiences(common.settings())?;
+        let mut report = common.report()?;
+        kms::assess(&audiences, services, &mut report);
+        Ok(Exit::Success(report))
+      },
+      Self::Ldap {
+        common,
+        base,
+        bind_dn,
+        max_validity,
+        directories,
+      } => {
+        let settings = Settings {
+          max_validity: *max_validity,
+          ..common.settings()
+        };
+        let audiences = common.audiences(settings)?;
+        let mut report = common.report()?;
+        // The password is not taken as an argument so that it does not
+        // show in the list of processes.
+        let password = match bind_dn {
+          Some(_) => env::var("LDAP_PASSWORD")
+            .map_err(|_| Error::Ldap("LDAP_PASSWORD is not set".to_string()))?,
+          None => String::new(),
+        };
+        let bind = bind_dn.as_deref().map(|dn| (dn, password.as_str()));
+        ldap::assess(&audiences, directories, bind, base.as_deref(), &mut report);
+        Ok(Exit::Success(report))
+      },
+      Self::Source { common, paths } => {
+        let audiences = common.audiences(common.settings())?;
+        let mut report = common.report()?;
+        source::assess(&audiences, paths, &mut report);
+        Ok(Exit::Success(report))
+      },
+      Self::Watch {
+        common,
+        key_format,
+        interval,
+        log,
+        max_validity,
+        metrics,
+        remediation,
+        webhook,
+        webhook_payload,
+        webhook_template,
+        paths,
+      } => {
+        let settings = Settings {
+          max_validity: *max_validity,
+          remediation: *remediation,
+          ..common.settings()
+        };
+        let audiences = common.audiences(settings)?;
+        let verbosity = Verbosity::from_flags(common.verbose, common.quiet);
+        let output = Output::from_flags(common.json, common.format);
+        let template = common
+          .template
+          .as_deref()
+          .map(Template::from_file)
+          .transpose()?;
+        let interval = Duration::from_secs(*interval);
+        let logger = log
+          .map(Logger::connect)
+          .transpose()
+          .map_err(|err| Error::Log(err.to_string()))?;
+        let metrics = metrics
+          .map(|addr| Metrics::serve(addr, common.guide.name()))
+          .transpose()
+          .map_err(|err| Error::Metrics(err.to_string()))?;
+        let webhook = webhook
+          .as_deref()
+          .map(|url| {
+            let template = webhook_template
+              .as_deref()
+              .map(Template::from_file)
+              .transpose()?;
+            Webhook::new(url, *webhook_payload, template)
+          })
+          .transpose()?;
+        let sinks = Sinks {
+          logger,
+          metrics,
+          webhook,
+        };
+        // Every scan is printed as a report of its own.
+        let report = || Report::new(verbosity, output).with_template(template.clone());
+        Self::watch(&audiences, *key_format, paths, report, sinks, interval)
+      },
+      Self::List {
+        catalog,
+        json,
         security,
         year,
       } => {
         let ctx = Context::new(*security, *year);
-        let verbosity = Verbosity::from_flags(*verbose, *quiet);
-        Self::assess::<Ssh>(ctx, files, *guide, *json, verbosity)
+        Listing::new(catalog.items(ctx), *json).print();
+        Ok(Exit::Done)
       },
-      Self::X509 {
+      Self::Compare {
+        primitives,
+        json,
+        security,
+        year,
+      } => {
+        let ctx = Context::new(*security, *year);
+        let items = primitives
+          .iter()
+          .map(|builtin| Item::primitive(ctx, *builtin))
+          .collect();
+        Comparison::new(items, *json).print();
+        Ok(Exit::Done)
+      },
+      Self::Explain {
         guide,
+        primitive,
         json,
-        quiet,
-        verbose,
-        files,
+      } => {
+        let rules = guide
+          .rules()
+          .iter()
+          .filter(|rule| primitive.is_none_or(|p| rule.scope == p.into()))
+          .collect();
+        Explanation::new(rules, *json).print();
+        Ok(Exit::Done)
+      },
+      Self::ExportTables {
+        guide,
+        csv,
         security,
         year,
       } => {
         let ctx = Context::new(*security, *year);
-        let verbosity = Verbosity::from_flags(*verbose, *quiet);
-        Self::assess::<Certificate>(ctx, files, *guide, *json, verbosity)
+        let guides = if guide.is_empty() {
+          Guide::value_variants()
+            .iter()
+            .copied()
+            .filter(|guide| !guide.rules().is_empty())
+            .collect()
+        } else {
+          guide.clone()
+        };
+        let tables = guides
+          .into_iter()
+          .map(|guide| (guide, guide.table(ctx)))
+          .collect();
+        Export::new(tables, *csv).print();
+        Ok(Exit::Done)
+      },
+      Self::Timeline {
+        format,
+        guide,
+        json,
+        security,
+        from_year,
+        to_year,
+        files,
+      } => {
+        let ctx = Context::new(*security, *from_year);
+        let guides = if guide.is_empty() {
+          Guide::value_variants()
+            .iter()
+            .copied()
+            .filter(|guide| !guide.rules().is_empty())
+            .collect()
+        } else {
+          guide.clone()
+        };
+        let timeline = Timeline::new(ctx, guides, *json).until(*to_year);
+        Self::plan(timeline, *format, files)?;
+        Ok(Exit::Done)
+      },
+      Self::Schema => {
+        print!("{}", report::SCHEMA);
+        Ok(Exit::Done)
       },
     }
   }
 }
 
+/// Parses the name of a format into the reader of the format.
+fn format_parser() -> impl TypedValueParser<Value = &'static dyn ArtifactReader> {
+  PossibleValuesParser::new(reader::names())
+    .map(|name| reader::find(&name).expect("the name of a format"))
+}
+
+fn parse_rate(s: &str) -> Result<f64, String> {
+  // Lower rates would keep a scan waiting for the better part of an
+  // hour between connections.
+  match s.parse::<f64>() {
+    Ok(rate) if rate.is_finite() && rate >= 0.001 => Ok(rate),
+    _ => Err("expected a number no less than 0.001".to_string()),
+  }
+}
+
+fn parse_builtin(name: &str) -> Result<Builtin, String> {
+  registry::find(name).ok_or_else(|| "unknown primitive, see `wardstone list`".to_string())
+}
+
 fn main() -> Exit {
   let options = Options::parse();
   options.subcommands.run()
@@ -0,0 +1,233 @@
+//! Read the keys of a set of pins and the pins declared for them.
+//!
+//! A pin is the base64 encoded SHA-256 hash of the SubjectPublicKeyInfo
+//! of a key (RFC 7469 Section 2.4), which is what HTTP Public Key
+//! Pinning, the network security configuration of Android and most
+//! HTTP clients pin. A pin only names a key, so the keys themselves are
+//! read from certificates or public keys and matched to the pins by
+//! hashing them the same way.
+//!
+//! Pins are taken from any text in which they are written as 44
+//! characters of base64, such as the `Public-Key-Pins` header, the
+//! `<pin digest="SHA-256">` elements of Android or the `sha256/` pins
+//! of OkHttp, or one pin per line.
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use ring::digest::{self, SHA256};
+use wardstone_core::primitive::asymmetric::Asymmetric;
+use wardstone_core::primitive::Primitive;
+use x509_parser::nom::Err as NomError;
+use x509_parser::pem::Pem;
+use x509_parser::prelude::{FromDer, SubjectPublicKeyInfo, X509Certificate};
+
+use crate::assess::{audit_key, Audiences};
+use crate::firmware::PublicKey;
+use crate::input;
+use crate::key::certificate::Certificate;
+use crate::key::{Error, Key};
+use crate::reader::X509Reader;
+use crate::report::{Audit, Report};
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// The length of a SHA-256 pin in base64.
+const PIN_LEN: usize = 44;
+
+/// The pin of a key, along with the key or why it cannot be read.
+type Identified = (String, Result<Box<dyn Key>, Error>);
+
+/// A key that can be pinned.
+pub struct Pinned {
+  /// Where the key was read from, such as `chain.pem:2` for the second
+  /// certificate of a chain.
+  pub label: PathBuf,
+  pub pin: String,
+  pub key: Result<Box<dyn Key>, Error>,
+}
+
+/// Returns the pin of a SubjectPublicKeyInfo.
+pub fn pin(spki: &[u8]) -> String {
+  base64(digest::digest(&SHA256, spki).as_ref())
+}
+
+/// Returns whether a backup key offers less security than the primary
+/// key it stands in for.
+pub fn is_weaker(backup: Asymmetric, primary: Asymmetric) -> bool {
+  backup.security() < primary.security()
+}
+
+/// Returns the pins written in `text`, in the order they are written and
+/// without repeats.
+pub fn parse(text: &str) -> Vec<String> {
+  let text = text.replace("sha256/", " ");
+  let mut pins: Vec<String> = Vec::new();
+  for token in text.split(|c: char| !(c.is_ascii_alphanumeric() || "+/=".contains(c))) {
+    let pin = token.len() == PIN_LEN && token.ends_with('=') && !token[..PIN_LEN - 1].contains('=');
+    if pin && !pins.iter().any(|p| p == token) {
+      pins.push(token.to_string());
+    }
+  }
+  pins
+}
+
+/// Reads every certificate and public key in a DER or PEM encoded file,
+/// such as a certificate chain.
+pub fn read(path: &Path) -> Result<Vec<Pinned>, Error> {
+  let data = input::open(path)?;
+  let mut found = Vec::new();
+  if data.windows(11).any(|w| w == b"-----BEGIN ") {
+    for pem in Pem::iter_from_reader(Cursor::new(&data[..])) {
+      let pem = pem.map_err(NomError::Error)?;
+      match pem.label.as_str() {
+        "CERTIFICATE" | "TRUSTED CERTIFICATE" | "X509 CERTIFICATE" | "PUBLIC KEY" => {
+          found.push(identify(&pem.contents)?)
+        },
+        _ => {},
+      }
+    }
+  } else {
+    found.push(identify(&data)?);
+  }
+  if found.is_empty() {
+    return Err(Error::UnknownFormat);
+  }
+  let many = found.len() > 1;
+  Ok(
+    found
+      .into_iter()
+      .enumerate()
+      .map(|(i, (pin, key))| Pinned {
+        label: if many {
+          format!("{}:{}", path.display(), i + 1).into()
+        } else {
+          path.to_path_buf()
+        },
+        pin,
+        key,
+      })
+      .collect(),
+  )
+}
+
+/// Returns the pin and key of a DER encoded certificate or
+/// SubjectPublicKeyInfo.
+fn identify(der: &[u8]) -> Result<Identified, Error> {
+  if let Ok((_, certificate)) = X509Certificate::from_der(der) {
+    let key = Certificate::from_bytes(der).map(|key| Box::new(key) as Box<dyn Key>);
+    return Ok((pin(certificate.tbs_certificate.subject_pki.raw), key));
+  }
+  let (_, spki) = SubjectPublicKeyInfo::from_der(der)?;
+  let key = Certificate::key(&spki).map(|key| Box::new(PublicKey(key)) as Box<dyn Key>);
+  Ok((pin(spki.raw), key))
+}
+
+/// Encodes bytes as padded base64 (RFC 4648 Section 4).
+fn base64(bytes: &[u8]) -> String {
+  let mut s = String::new();
+  for chunk in bytes.chunks(3) {
+    let n = chunk
+      .iter()
+      .enumerate()
+      .fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+    for i in 0..4 {
+      if i <= chunk.len() {
+        s.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+      } else {
+        s.push('=');
+      }
+    }
+  }
+  s
+}
+
+/// Audits every pinned key, failing backup keys that are weaker than
+/// the primary key, and then the pin set as a whole, which fails if
+/// any pinned key does or if there is no backup pin. Keys that no
+/// declared pin names are not assessed, and declared pins that name
+/// none of the keys are warned about. A file or key that cannot be
+/// read is skipped.
+pub fn assess(
+  audiences: &Audiences,
+  set: &Path,
+  paths: &[PathBuf],
+  declared: &[String],
+  report: &mut Report,
+) -> Result<(), Error> {
+  let mut keys = Vec::new();
+  for path in paths {
+    match read(path) {
+      Ok(found) => keys.extend(found),
+      Err(err) => report.skip(err.in_file(path)),
+    }
+  }
+  if !declared.is_empty() {
+    keys.retain(|pinned| declared.contains(&pinned.pin));
+  }
+  let mut pinned = Vec::new();
+  for Pinned { label, pin, key } in keys {
+    match key {
+      Ok(key) => pinned.push((label, pin, key)),
+      Err(err) => report.skip(err.in_file(label)),
+    }
+  }
+  let Some((_, _, primary)) = pinned.first() else {
+    return Err(Error::Pins("none of the keys is pinned".to_string()));
+  };
+  let primary = primary.signature_algorithm();
+  for (name, settings) in audiences.iter() {
+    let mut failed = 0;
+    for (i, (label, _, key)) in pinned.iter().enumerate() {
+      let mut audit = audit_key(*settings, &X509Reader, label, key.as_ref());
+      let got = key.signature_algorithm();
+      if i > 0 && is_weaker(got, primary) {
+        audit.noncompliant_setting(
+          "backup pin",
+          format!(
+            "{} is weaker than the primary key {}, so falling back to it lowers the security of the pin set",
+            got, primary
+          ),
+        );
+      }
+      if !audit.passed() {
+        failed += 1;
+      }
+      if let Some(name) = name {
+        audit.address(name.clone());
+      }
+      report.push(audit);
+    }
+    let mut audit = Audit::of_settings(set, settings.guide.name());
+    let count = declared.len().max(pinned.len());
+    if count < 2 {
+      audit.noncompliant_setting(
+        "pin set",
+        "there is no backup pin, so losing the primary key locks clients out (RFC 7469 Section 4.3)"
+          .to_string(),
+      );
+    }
+    if failed > 0 {
+      audit.noncompliant_setting(
+        "pin set",
+        format!(
+          "{} of the {} pinned keys fail the guide",
+          failed,
+          pinned.len()
+        ),
+      );
+    }
+    for pin in declared {
+      if !pinned.iter().any(|(_, p, _)| p == pin) {
+        audit.warn(format!(
+          "pin {} names none of the keys given, so its key is not assessed",
+          pin
+        ));
+      }
+    }
+    if let Some(name) = name {
+      audit.address(name.clone());
+    }
+    report.push(audit);
+  }
+  Ok(())
+}
@@ -0,0 +1,312 @@
+//! Read protocol profiles, which declare the primitives a protocol uses.
+//!
+//! Bespoke protocols, such as Olm and Megolm of Matrix or the Signal
+//! protocol, have no configuration that their primitives could be read
+//! from, so they are declared in YAML instead, one document per protocol:
+//!
+//! ```yaml
+//! name: signal
+//! key_exchange: x25519
+//! signature: ed25519
+//! aead: aes256
+//! kdf: hkdf_sha256
+//! hash: [sha256, sha512]
+//! ```
+//!
+//! Every role takes a primitive or a list of them by any of the names
+//! `wardstone list` knows them by. A KDF is either HKDF or HMAC over a
+//! hash function, written as `hkdf_<hash>` or `hmac_<hash>`, whose hash
+//! function is assessed along with the others, or a password-based
+//! derivation such as `argon2id_t2_m19456_p1`. The ciphers are taken
+//! to be used in CBC mode unless `mode` gives another, such as `gcm` or
+//! `kw` for ciphers that only wrap keys.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Deserializer};
+use wardstone_core::primitive::asymmetric::Asymmetric;
+use wardstone_core::primitive::hash::Hash;
+use wardstone_core::primitive::kdf::Kdf;
+use wardstone_core::primitive::symmetric::{Mode, Symmetric};
+use wardstone_core::registry::{self, Builtin};
+
+use crate::assess::{advise_cipher, audit_audiences, weakest, Audiences, Settings};
+use crate::key::Error;
+use crate::report::{Audit, Report};
+
+/// A protocol as it is written in a profile.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Document {
+  name: Option<String>,
+  #[serde(default, deserialize_with = "names")]
+  key_exchange: Vec<String>,
+  #[serde(default, deserialize_with = "names")]
+  signature: Vec<String>,
+  #[serde(default, alias = "cipher", deserialize_with = "names")]
+  aead: Vec<String>,
+  mode: Option<String>,
+  #[serde(default, deserialize_with = "names")]
+  kdf: Vec<String>,
+  #[serde(default, deserialize_with = "names")]
+  hash: Vec<String>,
+}
+
+/// Reads a role given as a single name or as a list of names.
+fn names<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  #[derive(Deserialize)]
+  #[serde(untagged)]
+  enum Names {
+    One(String),
+    Many(Vec<String>),
+  }
+
+  Ok(match Names::deserialize(deserializer)? {
+    Names::One(name) => vec![name],
+    Names::Many(names) => names,
+  })
+}
+
+/// The primitives a protocol declares, by role.
+pub struct Protocol {
+  /// The profile along with the name of the protocol, such as
+  /// `protocols.yaml:signal`.
+  pub label: PathBuf,
+  pub key_exchanges: Vec<Asymmetric>,
+  pub signatures: Vec<Asymmetric>,
+  pub ciphers: Vec<Symmetric>,
+  /// The mode the ciphers are used in.
+  pub mode: Mode,
+  /// The hash functions, including those HKDF and HMAC are built on.
+  pub hashes: Vec<Hash>,
+  /// The password-based key derivations.
+  pub kdfs: Vec<Kdf>,
+}
+
+/// Returns the protocols declared in the profile at `path`, of which
+/// there may be several separated by `---`.
+pub fn protocols(path: &Path) -> Result<Vec<Protocol>, Error> {
+  let text = fs::read_to_string(path)?;
+  let mut protocols = Vec::new();
+  for (i, document) in serde_yaml_ng::Deserializer::from_str(&text).enumerate() {
+    let document = Document::deserialize(document)
+      .map_err(|err| Error::Profile(format!("document {}: {}", i + 1, err)))?;
+    let name = document.name.clone().unwrap_or_else(|| (i + 1).to_string());
+    let err = |reason: String| Error::Profile(format!("{}: {}", name, reason));
+    let mut protocol = Protocol {
+      label: format!("{}:{}", path.display(), name).into(),
+      key_exchanges: Vec::new(),
+      signatures: Vec::new(),
+      ciphers: Vec::new(),
+      mode: Mode::Cbc,
+      hashes: Vec::new(),
+      kdfs: Vec::new(),
+    };
+    for name in &document.key_exchange {
+      protocol.key_exchanges.push(asymmetric(name).map_err(err)?);
+    }
+    for name in &document.signature {
+      protocol.signatures.push(asymmetric(name).map_err(err)?);
+    }
+    for name in &document.aead {
+      match registry::find(name) {
+        Some(Builtin::Symmetric(entry)) => protocol.ciphers.push(entry.primitive),
+        _ => return Err(err(format!("{} is not a cipher", name))),
+      }
+    }
+    if let Some(mode) = &document.mode {
+      protocol.mode = mode
+        .parse()
+        .map_err(|_| err(format!("{} is not a mode, such as cbc or gcm", mode)))?;
+    }
+    for name in &document.hash {
+      protocol.hashes.push(hash(name).map_err(err)?);
+    }
+    for name in &document.kdf {
+      let lower = name.to_ascii_lowercase();
+      match lower
+        .strip_prefix("hkdf_")
+        .or_else(|| lower.strip_prefix("hmac_"))
+      {
+        Some(name) => protocol.hashes.push(hash(name).map_err(err)?),
+        None => protocol.kdfs.push(name.parse().map_err(|_| {
+          err(format!(
+            "{} is not a KDF, such as hkdf_sha256 or argon2id_t2_m19456_p1",
+            name
+          ))
+        })?),
+      }
+    }
+    if protocol.key_exchanges.is_empty()
+      && protocol.signatures.is_empty()
+      && protocol.ciphers.is_empty()
+      && protocol.hashes.is_empty()
+      && protocol.kdfs.is_empty()
+    {
+      return Err(err("no primitives are declared".to_string()));
+    }
+    protocols.push(protocol);
+  }
+  if protocols.is_empty() {
+    return Err(Error::Profile("no protocols are declared".to_string()));
+  }
+  Ok(protocols)
+}
+
+fn asymmetric(name: &str) -> Result<Asymmetric, String> {
+  match registry::find(name) {
+    Some(Builtin::Ecc(entry)) => Ok(entry.primitive.into()),
+    Some(Builtin::Ffc(entry)) => Ok(entry.primitive.into()),
+    Some(Builtin::Ifc(entry)) => Ok(entry.primitive.into()),
+    _ => Err(format!("{} is not an asymmetric primitive", name)),
+  }
+}
+
+fn hash(name: &str) -> Result<Hash, String> {
+  match registry::find(name) {
+    Some(Builtin::Hash(entry)) => Ok(entry.primitive),
+    _ => Err(format!("{} is not a hash function", name)),
+  }
+}
+
+/// Audits every protocol declared in the profiles in `files` for every
+/// audience. A profile that cannot be read is skipped.
+pub fn assess(audiences: &Audiences, files: &[PathBuf], report: &mut Report) {
+  for file in files {
+    let protocols = match protocols(file) {
+      Ok(protocols) => protocols,
+      Err(err) => {
+        report.skip(err.in_file(file));
+        continue;
+      },
+    };
+    for protocol in &protocols {
+      for audit in audit_audiences(audiences, |settings| audit(settings, protocol)) {
+        report.push(audit);
+      }
+    }
+  }
+}
+
+/// Audits the primitives a protocol declares, of which every one that
+/// fails is explained while the one reported of each role is the
+/// weakest that fails, or else the weakest. The primitive with the
+/// least security is called out as the weakest link of the protocol.
+pub fn audit(settings: Settings, protocol: &Protocol) -> Audit {
+  let Settings {
+    ctx, guide, locale, ..
+  } = settings;
+  let key_exchanges: Vec<_> = protocol
+    .key_exchanges
+    .iter()
+    .map(|got| (*got, guide.assess_signature_algorithm(ctx, *got)))
+    .collect();
+  let signatures: Vec<_> = protocol
+    .signatures
+    .iter()
+    .map(|got| (*got, guide.assess_signature_algorithm(ctx, *got)))
+    .collect();
+  let ciphers: Vec<_> = protocol
+    .ciphers
+    .iter()
+    .map(|got| (*got, guide.assess_symmetric(ctx, *got)))
+    .collect();
+  let hashes: Vec<_> = protocol
+    .hashes
+    .iter()
+    .map(|got| (*got, guide.assess_hash_function(ctx, *got)))
+    .collect();
+  let hash = weakest(&hashes);
+  let signature = weakest(&signatures);
+  // Every primitive along with its role and security, of which the
+  // least secure bounds the protocol.
+  let mut links = Vec::new();
+  let mut audit = match signature {
+    Some((got, _)) => Audit::new(
+      &protocol.label,
+      guide.name(),
+      hash.map(|(got, _)| *got),
+      *got,
+    ),
+    None => Audit::of_settings(&protocol.label, guide.name())
+      .with_hash_function(hash.map(|(got, _)| *got)),
+  };
+  for (got, verdict) in &key_exchanges {
+    audit.cite(verdict.citation);
+    audit.explain(verdict, ctx, *got, locale);
+    links.push(("key exchange", got.to_string(), verdict.security));
+  }
+  match weakest(&key_exchanges) {
+    Some((got, verdict)) if verdict.compliant => {
+      audit.compliant_key_exchange(*got, verdict.alternative)
+    },
+    Some((got, verdict)) => audit.noncompliant_key_exchange(*got, verdict.alternative),
+    None => {},
+  }
+  for (got, verdict) in &signatures {
+    audit.cite(verdict.citation);
+    audit.explain(verdict, ctx, *got, locale);
+    links.push(("signature algorithm", got.to_string(), verdict.security));
+  }
+  match signature {
+    Some((_, verdict)) if verdict.compliant => audit.compliant_signature(verdict.alternative),
+    Some((_, verdict)) => audit.noncompliant_signature(verdict.alternative),
+    None => {},
+  }
+  for (got, verdict) in &ciphers {
+    audit.cite(verdict.citation);
+    audit.explain(verdict, ctx, *got, locale);
+    links.push(("cipher", got.to_string(), verdict.security));
+    advise_cipher(settings, *got, protocol.mode, &mut audit);
+  }
+  match weakest(&ciphers) {
+    Some((got, verdict)) if verdict.compliant => audit.compliant_cipher(*got, verdict.alternative),
+    Some((got, verdict)) => audit.noncompliant_cipher(*got, verdict.alternative),
+    None => {},
+  }
+  for (got, verdict) in &hashes {
+    audit.cite(verdict.citation);
+    audit.explain(verdict, ctx, *got, locale);
+    links.push(("hash function", got.to_string(), verdict.security));
+  }
+  match hash {
+    Some((_, verdict)) if verdict.compliant => audit.compliant_hash_function(verdict.alternative),
+    Some((_, verdict)) => audit.noncompliant_hash_function(verdict.alternative),
+    None => {},
+  }
+  for kdf in &protocol.kdfs {
+    if !kdf.is_costly_enough() {
+      audit.cite(Some(kdf.citation()));
+      audit.noncompliant_setting(
+        &kdf.to_string(),
+        format!(
+          "the KDF costs less to guess passwords against than {}",
+          kdf.minimum()
+        ),
+      );
+    }
+  }
+  let roles = [
+    key_exchanges.len(),
+    signatures.len(),
+    ciphers.len(),
+    hashes.len(),
+  ]
+  .into_iter()
+  .filter(|&len| len > 0)
+  .count();
+  let link = links
+    .into_iter()
+    .filter_map(|(role, got, security)| Some((role, got, security?)))
+    .min_by_key(|(_, _, security)| *security);
+  if let (true, Some((role, got, security))) = (roles > 1, link) {
+    audit.warn(format!(
+      "the weakest link is the {} {}, which bounds the protocol at {} bits of security",
+      role, got, security
+    ));
+  }
+  audit
+}
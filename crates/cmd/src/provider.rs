@@ -0,0 +1,607 @@
+//! Read the providers and algorithms that OpenSSL is configured with.
+//!
+//! OpenSSL 3 fetches its algorithms from providers, which the file named
+//! by `OPENSSL_CONF`, usually `openssl.cnf`, loads and activates:
+//!
+//! - `openssl_conf` names the section that initialises the library,
+//!   whose `providers` setting names a section listing every provider
+//!   by the section of its own settings, and whose `alg_section` may set
+//!   the `default_properties` algorithms are fetched with.
+//! - The FIPS provider keeps its settings in the `fipsmodule.cnf` that
+//!   `openssl fipsinstall` writes, which is included from `openssl.cnf`.
+//! - The default provider is loaded on its own when no provider is
+//!   activated.
+//!
+//! The configuration is read into a [`Setup`], which tells the providers
+//! that are activated and the algorithms that are fetched without being
+//! asked for by their provider. The settings that do not meet FIPS 140-3
+//! are returned as [`Issue`]s while the algorithms are left to the caller
+//! to assess against the guide.
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+use wardstone_core::primitive::asymmetric::Asymmetric;
+use wardstone_core::primitive::hash::*;
+use wardstone_core::primitive::ifc::{Ifc, ID_RSA_PKCS1};
+use wardstone_core::primitive::symmetric::*;
+use wardstone_core::primitive::Primitive;
+use wardstone_core::standard::rule::Citation;
+
+use crate::assess::{advise_cipher, audit_audiences, Audiences, Settings};
+use crate::key::Error;
+use crate::report::{Audit, Report};
+
+/// The deepest `.include` directives are followed, which stops files that
+/// include one another.
+const MAX_DEPTH: usize = 8;
+
+/// The algorithms of the legacy provider, of which CAST5, MDC2, RC2,
+/// RC4, RC5 and SEED are not assessed by any guide.
+const LEGACY: [&str; 12] = [
+  "MD4",
+  "MDC2",
+  "Whirlpool",
+  "DES",
+  "DESX",
+  "IDEA",
+  "Blowfish",
+  "CAST5",
+  "RC2",
+  "RC4",
+  "RC5",
+  "SEED",
+];
+
+/// The settings of a section, in the order they are given.
+type Section = (String, Vec<(String, String)>);
+
+/// A setting that does not meet FIPS 140-3.
+pub struct Issue {
+  /// The setting at fault, such as `default_properties`.
+  pub setting: String,
+  pub message: String,
+  pub citation: Option<Citation>,
+}
+
+/// A provider that is activated.
+pub struct Provider {
+  /// The name it is listed by, such as `fips` or `legacy`.
+  pub name: String,
+  settings: Vec<(String, String)>,
+}
+
+/// The providers and algorithm properties of an OpenSSL configuration.
+pub struct Setup {
+  /// The configuration along with the providers that are activated, such
+  /// as `openssl.cnf:fips+base`.
+  pub label: PathBuf,
+  pub providers: Vec<Provider>,
+  /// The properties algorithms are fetched with, such as `fips=yes`.
+  default_properties: Option<String>,
+  /// The security level of TLS, which bounds the keys it accepts.
+  security_level: Option<u8>,
+}
+
+/// Returns the configuration named by `OPENSSL_CONF`, or else the first
+/// of those in the places distributions keep it that exists.
+pub fn default_files() -> Vec<PathBuf> {
+  if let Some(conf) = env::var_os("OPENSSL_CONF") {
+    return vec![PathBuf::from(conf)];
+  }
+  [
+    "/etc/ssl/openssl.cnf",
+    "/etc/pki/tls/openssl.cnf",
+    "/usr/lib/ssl/openssl.cnf",
+  ]
+  .into_iter()
+  .map(PathBuf::from)
+  .find(|file| file.is_file())
+  .into_iter()
+  .collect()
+}
+
+/// Returns the setup of the OpenSSL configuration at `path`, following the
+/// files it includes.
+pub fn setup(path: &Path) -> Result<Setup, Error> {
+  let mut sections = vec![(String::new(), Vec::new())];
+  read(path, &mut sections, 0)?;
+  // A section goes on wherever its name is given again, and around the
+  // files included in it.
+  let mut merged: Vec<Section> = Vec::new();
+  for (name, settings) in sections {
+    match merged.iter_mut().find(|(other, _)| *other == name) {
+      Some((_, existing)) => existing.extend(settings),
+      None => merged.push((name, settings)),
+    }
+  }
+  let find = |name: &str| {
+    merged
+      .iter()
+      .find(|(section, _)| section == name)
+      .map(|(_, settings)| settings)
+  };
+  let get = |section: Option<&Vec<(String, String)>>, name: &str| {
+    section.and_then(|settings| {
+      settings
+        .iter()
+        .rev()
+        .find(|(n, _)| n == name)
+        .map(|(_, value)| value.clone())
+    })
+  };
+  let init = get(find(""), "openssl_conf").and_then(|name| find(&name));
+  let mut providers = Vec::new();
+  if let Some(list) = get(init, "providers").and_then(|name| find(&name)) {
+    for (name, section) in list {
+      let settings = find(section).cloned().unwrap_or_default();
+      let activated = settings
+        .iter()
+        .rev()
+        .find(|(n, _)| n == "activate")
+        .is_some_and(|(_, value)| {
+          matches!(
+            value.to_ascii_lowercase().as_str(),
+            "1" | "yes" | "true" | "on"
+          )
+        });
+      if activated
+        && !providers
+          .iter()
+          .any(|provider: &Provider| provider.name == *name)
+      {
+        providers.push(Provider {
+          name: name.clone(),
+          settings,
+        });
+      }
+    }
+  }
+  if providers.is_empty() {
+    providers.push(Provider {
+      name: "default".to_string(),
+      settings: Vec::new(),
+    });
+  }
+  let default_properties = get(init, "alg_section")
+    .and_then(|name| get(find(&name), "default_properties"))
+    .filter(|properties| !properties.is_empty());
+  let system_default = get(init, "ssl_conf")
+    .and_then(|name| get(find(&name), "system_default"))
+    .and_then(|name| find(&name));
+  let security_level = get(system_default, "CipherString").and_then(|ciphers| {
+    let at = ciphers.find("@SECLEVEL=")? + "@SECLEVEL=".len();
+    ciphers[at..].get(..1)?.parse().ok()
+  });
+  let names: Vec<_> = providers
+    .iter()
+    .map(|provider| provider.name.as_str())
+    .collect();
+  Ok(Setup {
+    label: format!("{}:{}", path.display(), names.join("+")).into(),
+    providers,
+    default_properties,
+    security_level,
+  })
+}
+
+/// Reads the sections of a configuration file into `sections`, which
+/// start with the unnamed one, following `.include` directives. Settings
+/// that follow an included file are still in the section before it.
+fn read(path: &Path, sections: &mut Vec<Section>, depth: usize) -> Result<(), Error> {
+  if depth > MAX_DEPTH {
+    return Err(Error::Provider(format!(
+      "{} is included more than {} files deep",
+      path.display(),
+      MAX_DEPTH
+    )));
+  }
+  let text = fs::read_to_string(path)?;
+  let dir = path.parent().unwrap_or(Path::new(""));
+  let mut current = sections
+    .last()
+    .map(|(name, _)| name.clone())
+    .unwrap_or_default();
+  let mut lines = text.lines().enumerate();
+  while let Some((i, line)) = lines.next() {
+    // A line ending in a backslash goes on in the next.
+    let mut line = uncomment(line).to_string();
+    while let Some(start) = line.strip_suffix('\\') {
+      line = start.to_string();
+      match lines.next() {
+        Some((_, next)) => line.push_str(uncomment(next)),
+        None => break,
+      }
+    }
+    let line = line.trim();
+    if line.is_empty() || line.starts_with(".pragma") {
+      continue;
+    }
+    if let Some(include) = line.strip_prefix(".include") {
+      let include = include.trim_start().trim_start_matches('=').trim();
+      for file in included(&dir.join(unquote(include)))? {
+        read(&file, sections, depth + 1)?;
+      }
+      sections.push((current.clone(), Vec::new()));
+      continue;
+    }
+    if let Some(name) = line.strip_prefix('[') {
+      let name = name
+        .strip_suffix(']')
+        .ok_or_else(|| Error::Provider(format!("line {}: expected ]", i + 1)))?;
+      current = name.trim().to_string();
+      sections.push((current.clone(), Vec::new()));
+      continue;
+    }
+    let (name, value) = line
+      .split_once('=')
+      .ok_or_else(|| Error::Provider(format!("line {}: expected name = value", i + 1)))?;
+    sections
+      .last_mut()
+      .expect("the unnamed section is first")
+      .1
+      .push((name.trim().to_string(), unquote(value.trim()).to_string()));
+  }
+  Ok(())
+}
+
+/// Returns the files an `.include` directive names, which are every file
+/// ending in `.cnf` or `.conf` if it names a directory.
+fn included(path: &Path) -> Result<Vec<PathBuf>, Error> {
+  if !path.is_dir() {
+    return Ok(vec![path.to_path_buf()]);
+  }
+  let mut files: Vec<PathBuf> = fs::read_dir(path)?
+    .filter_map(|entry| Some(entry.ok()?.path()))
+    .filter(|file| {
+      file
+        .extension()
+        .is_some_and(|extension| extension == "cnf" || extension == "conf")
+    })
+    .collect();
+  files.sort();
+  Ok(files)
+}
+
+/// Removes what follows a `#` that is not escaped.
+fn uncomment(line: &str) -> &str {
+  let mut escaped = false;
+  for (i, c) in line.char_indices() {
+    match c {
+      '#' if !escaped => return &line[..i],
+      '\\' => escaped = !escaped,
+      _ => escaped = false,
+    }
+  }
+  line
+}
+
+/// Removes the quotes around a string value.
+fn unquote(value: &str) -> &str {
+  ['"', '\'']
+    .into_iter()
+    .find_map(|quote| value.strip_prefix(quote)?.strip_suffix(quote))
+    .unwrap_or(value)
+}
+
+/// Returns the hash functions a provider signs with, the weakest first.
+/// The FIPS provider does not sign with SHA-1 while its security checks
+/// are on.
+fn hashes(provider: &str, checks: bool) -> Vec<Hash> {
+  match provider {
+    "default" => vec![
+      MD5,
+      SHA1,
+      RIPEMD160,
+      SHA224,
+      SHA512_224,
+      SHA3_224,
+      SHA256,
+      SHA512_256,
+      SHA3_256,
+      BLAKE2S_256,
+      SHA384,
+      SHA3_384,
+      SHA512,
+      SHA3_512,
+      BLAKE2B_512,
+    ],
+    "legacy" => vec![MD4, RIPEMD160, WHIRLPOOL],
+    "fips" if checks => vec![
+      SHA224, SHA512_224, SHA3_224, SHA256, SHA512_256, SHA3_256, SHA384, SHA3_384, SHA512,
+      SHA3_512,
+    ],
+    "fips" => vec![
+      SHA1, SHA224, SHA512_224, SHA3_224, SHA256, SHA512_256, SHA3_256, SHA384, SHA3_384, SHA512,
+      SHA3_512,
+    ],
+    _ => Vec::new(),
+  }
+}
+
+/// Returns the ciphers a provider encrypts with, the weakest first. The
+/// FIPS provider only decrypts with three-key TDEA.
+fn ciphers(provider: &str) -> Vec<Symmetric> {
+  match provider {
+    "default" => vec![
+      TDEA2,
+      TDEA3,
+      AES128,
+      CAMELLIA128,
+      AES192,
+      CAMELLIA192,
+      AES256,
+      CAMELLIA256,
+    ],
+    "legacy" => vec![DES, DESX, IDEA, BLOWFISH],
+    "fips" => vec![AES128, AES192, AES256],
+    _ => Vec::new(),
+  }
+}
+
+impl Provider {
+  /// Returns the last value of a setting.
+  fn get(&self, name: &str) -> Option<&str> {
+    self
+      .settings
+      .iter()
+      .rev()
+      .find(|(n, _)| n == name)
+      .map(|(_, value)| value.as_str())
+  }
+
+  /// Returns whether the checks of the FIPS provider on keys and digests
+  /// are on, which they are unless `security-checks` turns them off.
+  fn checks(&self) -> bool {
+    self.get("security-checks") != Some("0")
+  }
+}
+
+impl Setup {
+  fn provider(&self, name: &str) -> Option<&Provider> {
+    self.providers.iter().find(|provider| provider.name == name)
+  }
+
+  /// Returns whether only algorithms of the FIPS provider are fetched
+  /// unless others are asked for, which `?fips=yes` merely prefers.
+  fn fips_only(&self) -> bool {
+    self
+      .default_properties
+      .as_deref()
+      .is_some_and(|properties| {
+        properties
+          .split(',')
+          .any(|property| property.trim() == "fips=yes")
+      })
+  }
+
+  /// Returns the providers whose algorithms are fetched without being
+  /// asked for by name.
+  fn effective(&self) -> Vec<&Provider> {
+    self
+      .providers
+      .iter()
+      .filter(|provider| !self.fips_only() || provider.name == "fips")
+      .collect()
+  }
+
+  /// Returns the hash functions that are fetched for signatures, the
+  /// weakest first.
+  pub fn hashes(&self) -> Vec<Hash> {
+    let mut found: Vec<Hash> = Vec::new();
+    for provider in self.effective() {
+      for hash in hashes(&provider.name, provider.checks()) {
+        if !found.contains(&hash) {
+          found.push(hash);
+        }
+      }
+    }
+    found.sort_by_key(Primitive::security);
+    found
+  }
+
+  /// Returns the ciphers that are fetched for encryption, the weakest
+  /// first.
+  pub fn ciphers(&self) -> Vec<Symmetric> {
+    let mut found: Vec<Symmetric> = Vec::new();
+    for provider in self.effective() {
+      for cipher in ciphers(&provider.name) {
+        if !found.contains(&cipher) {
+          found.push(cipher);
+        }
+      }
+    }
+    found.sort_by_key(Primitive::security);
+    found
+  }
+
+  /// Returns the smallest RSA key that is accepted, by the security level
+  /// of TLS, which is 1 unless set, and by the FIPS provider, which does
+  /// not sign with keys of less than 2048 bits while its checks are on.
+  /// OpenSSL does not generate keys of less than 512 bits.
+  pub fn smallest_rsa_key(&self) -> Ifc {
+    let level = match self.security_level.unwrap_or(1) {
+      0 => 512,
+      1 => 1024,
+      2 => 2048,
+      3 => 3072,
+      4 => 7680,
+      _ => 15360,
+    };
+    let fips = match self.effective().as_slice() {
+      [provider] if provider.name == "fips" && provider.checks() => 2048,
+      _ => 512,
+    };
+    Ifc::new(ID_RSA_PKCS1, level.max(fips))
+  }
+
+  /// Returns the settings that keep OpenSSL from running in the approved
+  /// mode of FIPS 140-3.
+  pub fn fips_issues(&self) -> Vec<Issue> {
+    let citation = |section| {
+      Some(Citation {
+        document: "OpenSSL fips_config(5)",
+        section,
+        url: Some("https://docs.openssl.org/3.0/man5/fips_config/"),
+      })
+    };
+    let mut issues = Vec::new();
+    let Some(fips) = self.provider("fips") else {
+      issues.push(Issue {
+        setting: "providers".to_string(),
+        message: "the FIPS provider is not activated".to_string(),
+        citation: citation("DESCRIPTION"),
+      });
+      return issues;
+    };
+    if !self.fips_only() {
+      issues.push(Issue {
+        setting: "default_properties".to_string(),
+        message: "algorithms outside the FIPS provider are fetched unless fips=yes is set"
+          .to_string(),
+        citation: None,
+      });
+    }
+    if fips.get("module-mac").is_none() {
+      issues.push(Issue {
+        setting: "module-mac".to_string(),
+        message: "the FIPS provider was not installed with openssl fipsinstall, so its integrity is not checked".to_string(),
+        citation: citation("FIPS provider section"),
+      });
+    }
+    if !fips.checks() {
+      issues.push(Issue {
+        setting: "security-checks=0".to_string(),
+        message: "the FIPS provider accepts keys and digests that are not approved".to_string(),
+        citation: citation("FIPS provider section"),
+      });
+    }
+    issues
+  }
+
+  /// Returns the settings that deserve attention without being unsafe.
+  pub fn warnings(&self) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if self.provider("legacy").is_some() {
+      let fetched = if self.fips_only() {
+        " when they are asked for"
+      } else {
+        ""
+      };
+      warnings.push(format!(
+        "the legacy provider is activated, which enables {} and {}{}",
+        LEGACY[..LEGACY.len() - 1].join(", "),
+        LEGACY[LEGACY.len() - 1],
+        fetched
+      ));
+    }
+    if self.fips_only() {
+      let others: Vec<_> = self
+        .providers
+        .iter()
+        .map(|provider| provider.name.as_str())
+        .filter(|&name| !matches!(name, "fips" | "base" | "legacy"))
+        .collect();
+      if !others.is_empty() {
+        warnings.push(format!(
+          "the {} provider{} can still be asked for algorithms that are not approved",
+          others.join(" and "),
+          if others.len() > 1 { "s" } else { "" }
+        ));
+      }
+    }
+    if let Some(fips) = self.provider("fips") {
+      if fips.get("conditional-errors") == Some("0") {
+        warnings.push(
+          "the FIPS provider keeps running after a conditional self test fails (conditional-errors=0)"
+            .to_string(),
+        );
+      }
+    }
+    warnings
+  }
+}
+
+/// Audits the OpenSSL configurations in `files` for every audience. A
+/// configuration that cannot be read is skipped.
+pub fn assess(audiences: &Audiences, files: &[PathBuf], fips: bool, report: &mut Report) {
+  for file in files {
+    let setup = match setup(file) {
+      Ok(setup) => setup,
+      Err(err) => {
+        report.skip(err.in_file(file));
+        continue;
+      },
+    };
+    for audit in audit_audiences(audiences, |settings| audit(settings, &setup, fips)) {
+      report.push(audit);
+    }
+  }
+}
+
+/// Audits the algorithms an OpenSSL configuration enables, of which
+/// every one that fails is explained while the one reported of each
+/// kind is the weakest that fails, or else the weakest. The settings
+/// that keep it from the approved mode of FIPS 140-3 fail if that is
+/// required.
+pub fn audit(settings: Settings, setup: &Setup, fips: bool) -> Audit {
+  let Settings {
+    ctx, guide, locale, ..
+  } = settings;
+  let hashes: Vec<_> = setup
+    .hashes()
+    .into_iter()
+    .map(|got| (got, guide.assess_hash_function(ctx, got)))
+    .collect();
+  let ciphers: Vec<_> = setup
+    .ciphers()
+    .into_iter()
+    .map(|got| (got, guide.assess_symmetric(ctx, got)))
+    .collect();
+  let hash = hashes
+    .iter()
+    .find(|(_, verdict)| !verdict.compliant)
+    .or(hashes.first());
+  let cipher = ciphers
+    .iter()
+    .find(|(_, verdict)| !verdict.compliant)
+    .or(ciphers.first());
+  let key = Asymmetric::Ifc(setup.smallest_rsa_key());
+  let mut audit = Audit::new(&setup.label, guide.name(), hash.map(|(got, _)| *got), key);
+  let verdict = guide.assess_signature_algorithm(ctx, key);
+  audit.cite(verdict.citation);
+  audit.explain(&verdict, ctx, key, locale);
+  match verdict.into_result() {
+    Ok(want) => audit.compliant_signature(want),
+    Err(want) => audit.noncompliant_signature(want),
+  }
+  for (got, verdict) in &hashes {
+    audit.cite(verdict.citation);
+    audit.explain(verdict, ctx, *got, locale);
+  }
+  match hash {
+    Some((_, verdict)) if verdict.compliant => audit.compliant_hash_function(verdict.alternative),
+    Some((_, verdict)) => audit.noncompliant_hash_function(verdict.alternative),
+    None => {},
+  }
+  for (got, verdict) in &ciphers {
+    audit.cite(verdict.citation);
+    audit.explain(verdict, ctx, *got, locale);
+    // Providers offer every cipher in CBC among other bulk modes.
+    advise_cipher(settings, *got, Mode::Cbc, &mut audit);
+  }
+  match cipher {
+    Some((got, verdict)) if verdict.compliant => audit.compliant_cipher(*got, verdict.alternative),
+    Some((got, verdict)) => audit.noncompliant_cipher(*got, verdict.alternative),
+    None => {},
+  }
+  if fips {
+    for issue in setup.fips_issues() {
+      audit.cite(issue.citation);
+      audit.noncompliant_setting(&issue.setting, issue.message);
+    }
+  }
+  for warning in setup.warnings() {
+    audit.warn(warning);
+  }
+  audit
+}
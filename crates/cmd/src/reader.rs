@@ -0,0 +1,368 @@
+//! Read keys from the formats they are stored in.
+//!
+//! Every format is read by an [`ArtifactReader`] registered in
+//! [`READERS`]. A reader describes the media types and file extensions
+//! of its format and recognises its contents, so that the format of a
+//! file can be told without being given. Supporting a new format only
+//! takes implementing the trait and adding the reader to the registry.
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use wardstone_core::primitive::asymmetric::Asymmetric;
+use wardstone_core::primitive::hash::Hash;
+
+use crate::input;
+use crate::key::certificate::Certificate;
+use crate::key::private::PrivateKey;
+use crate::key::signed::{self, Signed};
+use crate::key::ssh::Ssh;
+use crate::key::{Error, Key};
+use crate::remediation;
+
+/// The number of bytes at the start of a file that are looked at to
+/// tell its format.
+const SNIFF_LEN: u64 = 4096;
+
+/// Reads keys of a given format.
+pub trait ArtifactReader: Sync {
+  /// Returns the name of the format, which is how it is chosen on the
+  /// command line.
+  fn name(&self) -> &'static str;
+
+  /// Returns the media types the format is known by.
+  fn media_types(&self) -> &'static [&'static str] {
+    &[]
+  }
+
+  /// Returns the extensions that files in the format usually have,
+  /// without the leading dot.
+  fn extensions(&self) -> &'static [&'static str] {
+    &[]
+  }
+
+  /// Returns whether the start of a file looks like the format.
+  fn sniff(&self, data: &[u8]) -> bool;
+
+  /// Reads the key in a file.
+  fn read(&self, path: &Path) -> Result<Box<dyn Key>, Error> {
+    self.read_bytes(&input::open(path)?)
+  }
+
+  /// Reads a key from the contents of a file, such as a file that was
+  /// never checked out of a repository.
+  fn read_bytes(&self, data: &[u8]) -> Result<Box<dyn Key>, Error>;
+
+  /// Returns a command that can be used to generate a replacement key
+  /// that uses the given primitives if one is known.
+  fn remediation(
+    &self,
+    _hash_function: Option<Hash>,
+    _signature_algorithm: Asymmetric,
+  ) -> Option<String> {
+    None
+  }
+}
+
+/// Reads DER or PEM encoded X.509 certificates.
+pub struct X509Reader;
+
+impl ArtifactReader for X509Reader {
+  fn name(&self) -> &'static str {
+    "x509"
+  }
+
+  fn media_types(&self) -> &'static [&'static str] {
+    &[
+      "application/pkix-cert",
+      "application/x-x509-ca-cert",
+      "application/x-pem-file",
+    ]
+  }
+
+  fn extensions(&self) -> &'static [&'static str] {
+    &["crt", "cer", "der", "pem"]
+  }
+
+  fn sniff(&self, data: &[u8]) -> bool {
+    // A DER encoded certificate is a sequence whose length takes more
+    // than one byte. A PEM block may follow a text dump.
+    matches!(data, [0x30, 0x81..=0x83, ..])
+      || data.starts_with(b"Certificate:")
+      || contains(data, b"-----BEGIN CERTIFICATE-----")
+      || contains(data, b"-----BEGIN TRUSTED CERTIFICATE-----")
+  }
+
+  fn read_bytes(&self, data: &[u8]) -> Result<Box<dyn Key>, Error> {
+    Ok(Box::new(Certificate::from_bytes(data)?))
+  }
+
+  fn remediation(
+    &self,
+    hash_function: Option<Hash>,
+    signature_algorithm: Asymmetric,
+  ) -> Option<String> {
+    remediation::openssl(hash_function, signature_algorithm)
+  }
+}
+
+/// Reads OpenSSH public keys.
+pub struct SshReader;
+
+impl ArtifactReader for SshReader {
+  fn name(&self) -> &'static str {
+    "ssh"
+  }
+
+  fn extensions(&self) -> &'static [&'static str] {
+    &["pub"]
+  }
+
+  fn sniff(&self, data: &[u8]) -> bool {
+    ["ssh-", "ecdsa-sha2-", "sk-"]
+      .iter()
+      .any(|prefix| data.starts_with(prefix.as_bytes()))
+  }
+
+  fn read_bytes(&self, data: &[u8]) -> Result<Box<dyn Key>, Error> {
+    Ok(Box::new(Ssh::from_bytes(data)?))
+  }
+
+  fn remediation(
+    &self,
+    _hash_function: Option<Hash>,
+    signature_algorithm: Asymmetric,
+  ) -> Option<String> {
+    // The hash function used with SSH keys is negotiated by the client
+    // and server so it cannot be changed by generating a new key.
+    remediation::ssh_keygen(signature_algorithm)
+  }
+}
+
+/// Reads PEM encoded private keys and JSON Web Keys.
+pub struct PrivateKeyReader;
+
+impl ArtifactReader for PrivateKeyReader {
+  fn name(&self) -> &'static str {
+    "key"
+  }
+
+  fn media_types(&self) -> &'static [&'static str] {
+    &["application/pkcs8", "application/jwk+json"]
+  }
+
+  fn extensions(&self) -> &'static [&'static str] {
+    &["key", "jwk"]
+  }
+
+  fn sniff(&self, data: &[u8]) -> bool {
+    contains(data, b"PRIVATE KEY-----")
+  }
+
+  fn read_bytes(&self, data: &[u8]) -> Result<Box<dyn Key>, Error> {
+    Ok(Box::new(PrivateKey::from_bytes(data)?))
+  }
+
+  fn remediation(
+    &self,
+    hash_function: Option<Hash>,
+    signature_algorithm: Asymmetric,
+  ) -> Option<String> {
+    remediation::openssl(hash_function, signature_algorithm)
+  }
+}
+
+/// Reads the Authenticode signatures of Windows executables.
+pub struct AuthenticodeReader;
+
+impl ArtifactReader for AuthenticodeReader {
+  fn name(&self) -> &'static str {
+    "pe"
+  }
+
+  fn media_types(&self) -> &'static [&'static str] {
+    &["application/vnd.microsoft.portable-executable"]
+  }
+
+  fn extensions(&self) -> &'static [&'static str] {
+    &["exe", "dll", "sys", "efi"]
+  }
+
+  fn sniff(&self, data: &[u8]) -> bool {
+    // Unsigned executables have no certificate table.
+    signed::certificate_table(data).is_some()
+  }
+
+  fn read_bytes(&self, data: &[u8]) -> Result<Box<dyn Key>, Error> {
+    Ok(Box::new(Signed::from_pe(data)?))
+  }
+
+  fn remediation(
+    &self,
+    hash_function: Option<Hash>,
+    signature_algorithm: Asymmetric,
+  ) -> Option<String> {
+    remediation::openssl(hash_function, signature_algorithm)
+  }
+}
+
+/// Reads the signatures appended to Linux kernel modules.
+pub struct ModuleReader;
+
+impl ArtifactReader for ModuleReader {
+  fn name(&self) -> &'static str {
+    "module"
+  }
+
+  fn extensions(&self) -> &'static [&'static str] {
+    &["ko"]
+  }
+
+  fn sniff(&self, data: &[u8]) -> bool {
+    // The signature is appended to the end of the file so whether it is
+    // signed cannot be told from its start.
+    signed::is_module(data)
+  }
+
+  fn read_bytes(&self, data: &[u8]) -> Result<Box<dyn Key>, Error> {
+    Ok(Box::new(Signed::from_module(data)?))
+  }
+
+  fn remediation(
+    &self,
+    hash_function: Option<Hash>,
+    signature_algorithm: Asymmetric,
+  ) -> Option<String> {
+    remediation::openssl(hash_function, signature_algorithm)
+  }
+}
+
+/// Reads the OpenPGP signatures of RPM packages.
+pub struct RpmReader;
+
+impl ArtifactReader for RpmReader {
+  fn name(&self) -> &'static str {
+    "rpm"
+  }
+
+  fn media_types(&self) -> &'static [&'static str] {
+    &["application/x-rpm"]
+  }
+
+  fn extensions(&self) -> &'static [&'static str] {
+    &["rpm"]
+  }
+
+  fn sniff(&self, data: &[u8]) -> bool {
+    data.starts_with(&[0xed, 0xab, 0xee, 0xdb])
+  }
+
+  fn read_bytes(&self, data: &[u8]) -> Result<Box<dyn Key>, Error> {
+    Ok(Box::new(Signed::from_rpm(data)?))
+  }
+
+  fn remediation(
+    &self,
+    _hash_function: Option<Hash>,
+    signature_algorithm: Asymmetric,
+  ) -> Option<String> {
+    // The hash function is chosen by the %_gpg_digest_algo macro of rpm
+    // when signing so it cannot be changed by generating a new key.
+    remediation::gpg(signature_algorithm)
+  }
+}
+
+/// The size in bytes from which files found in repositories and images
+/// are not read. Keys and certificates are far smaller, while larger
+/// files are more likely to be build artifacts or data.
+pub const MAX_BLOB_SIZE: u64 = 1024 * 1024;
+
+/// A file that looks like a key or certificate, read from somewhere
+/// other than the file system such as a repository or an image.
+pub struct Blob {
+  /// Where the file was found, such as `3f2a9c1e0b7d:tests/server.key`.
+  pub label: PathBuf,
+  pub reader: &'static dyn ArtifactReader,
+  pub data: Vec<u8>,
+}
+
+impl Blob {
+  /// Keeps the contents of a file if they look like a key or
+  /// certificate.
+  pub fn sniff(label: impl Into<PathBuf>, data: Vec<u8>) -> Option<Self> {
+    let reader = sniff(&data)?;
+    Some(Self {
+      label: label.into(),
+      reader,
+      data,
+    })
+  }
+}
+
+/// The readers of every supported format.
+pub static READERS: [&dyn ArtifactReader; 6] = [
+  &X509Reader,
+  &SshReader,
+  &PrivateKeyReader,
+  &AuthenticodeReader,
+  &ModuleReader,
+  &RpmReader,
+];
+
+/// Returns the names of the supported formats.
+pub fn names() -> impl Iterator<Item = &'static str> {
+  READERS.iter().map(|reader| reader.name())
+}
+
+/// Returns the reader of the format with the given name.
+pub fn find(name: &str) -> Option<&'static dyn ArtifactReader> {
+  READERS.iter().copied().find(|reader| reader.name() == name)
+}
+
+/// Returns the reader of the format known by the given media type.
+pub fn find_media_type(media_type: &str) -> Option<&'static dyn ArtifactReader> {
+  // Parameters such as the character set do not change the format.
+  let media_type = media_type.split(';').next().unwrap_or_default().trim();
+  READERS.iter().copied().find(|reader| {
+    reader
+      .media_types()
+      .iter()
+      .any(|t| t.eq_ignore_ascii_case(media_type))
+  })
+}
+
+/// Returns the reader of the format of the file at `path`.
+///
+/// The format is told from the extension of the file if only one
+/// format uses it and otherwise from its contents.
+pub fn detect(path: &Path) -> Result<&'static dyn ArtifactReader, Error> {
+  let extension = path
+    .extension()
+    .and_then(|extension| extension.to_str())
+    .map(str::to_ascii_lowercase);
+  if let Some(extension) = extension {
+    let mut readers = READERS
+      .iter()
+      .copied()
+      .filter(|reader| reader.extensions().contains(&extension.as_str()));
+    if let (Some(reader), None) = (readers.next(), readers.next()) {
+      return Ok(reader);
+    }
+  }
+  let mut data = Vec::new();
+  File::open(path)?.take(SNIFF_LEN).read_to_end(&mut data)?;
+  sniff(&data).ok_or(Error::UnknownFormat)
+}
+
+/// Returns the reader of the format that the contents of a file look
+/// like, if any.
+pub fn sniff(data: &[u8]) -> Option<&'static dyn ArtifactReader> {
+  let data = &data[..data.len().min(SNIFF_LEN as usize)];
+  READERS.iter().copied().find(|reader| reader.sniff(data))
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+  haystack
+    .windows(needle.len())
+    .any(|window| window == needle)
+}
@@ -0,0 +1,347 @@
+//! Suggest commands that produce keys using recommended primitives.
+//!
+//! The recommendations made by a standard are primitives, which leaves
+//! users to figure out how to actually generate a key that uses them.
+//! The functions in this module bridge that gap by mapping primitives
+//! to invocations of widely available tools.
+use wardstone_core::primitive::asymmetric::Asymmetric;
+use wardstone_core::primitive::ecc::*;
+use wardstone_core::primitive::ffc::Ffc;
+use wardstone_core::primitive::hash::*;
+use wardstone_core::primitive::ifc::Ifc;
+
+/// Returns an OpenSSL command that creates a certificate signing
+/// request for a new key using the given primitives.
+pub fn openssl(hash: Option<Hash>, signature: Asymmetric) -> Option<String> {
+  let newkey = match signature {
+    Asymmetric::Ecc(ecc) => match ecc {
+      // The Edwards curves have their own key types and mandate the
+      // hash function used.
+      ed if ed == ED25519 => "ed25519".to_string(),
+      ed if ed == ED448 => "ed448".to_string(),
+      _ => format!("ec -pkeyopt ec_paramgen_curve:{}", openssl_curve(ecc)?),
+    },
+    Asymmetric::Ifc(ifc) => openssl_rsa(ifc)?,
+    Asymmetric::Ffc(ffc) => return openssl_dsa(hash, ffc),
+    // OpenSSL 3.5 and later generate ML-DSA and SLH-DSA keys under the
+    // names of their parameter sets, but neither hybrid nor stateful
+    // hash-based keys.
+    Asymmetric::Pqc(pqc) if !is_placeholder(pqc.id) => pqc.name().to_string(),
+    Asymmetric::Pqc(_) | Asymmetric::Hybrid(_) | Asymmetric::Stateful(_) => return None,
+  };
+  let mut cmd = format!(
+    "openssl req -new -newkey {} -nodes -keyout key.pem -out request.csr",
+    newkey
+  );
+  if let Some(digest) = hash.and_then(openssl_digest) {
+    let separate_digest = match signature {
+      Asymmetric::Ecc(ed) => ed != ED25519 && ed != ED448,
+      Asymmetric::Pqc(_) => false,
+      _ => true,
+    };
+    if separate_digest {
+      cmd.push_str(format!(" -{}", digest).as_str());
+    }
+  }
+  Some(cmd)
+}
+
+/// Returns an `ssh-keygen` command that creates a new key using the
+/// given signature algorithm.
+///
+/// Only the key types supported by OpenSSH are considered. DSA keys
+/// are omitted since they have been disabled by default in OpenSSH
+/// since version 7.0.
+pub fn ssh_keygen(signature: Asymmetric) -> Option<String> {
+  let args = match signature {
+    Asymmetric::Ecc(ecc) => match ecc {
+      ed if ed == ED25519 => "-t ed25519".to_string(),
+      p if p == P256 || p == ECC_256 => "-t ecdsa -b 256".to_string(),
+      p if p == P384 || p == ECC_384 => "-t ecdsa -b 384".to_string(),
+      p if p == P521 || p == ECC_512 => "-t ecdsa -b 521".to_string(),
+      _ => return None,
+    },
+    Asymmetric::Ifc(ifc) if !is_placeholder(ifc.id) => format!("-t rsa -b {}", ifc.k),
+    _ => return None,
+  };
+  Some(format!("ssh-keygen {} -f id_wardstone", args))
+}
+
+/// Returns a `gpg` command that creates a new OpenPGP signing key
+/// using the given signature algorithm.
+///
+/// GnuPG does not generate post-quantum signing keys, and it chooses
+/// the size of the subgroup of DSA keys itself.
+pub fn gpg(signature: Asymmetric) -> Option<String> {
+  let algorithm = match signature {
+    Asymmetric::Ecc(ecc) => match ecc {
+      ed if ed == ED25519 => "ed25519".to_string(),
+      ed if ed == ED448 => "ed448".to_string(),
+      p if p == P256 || p == ECC_256 => "nistp256".to_string(),
+      p if p == P384 || p == ECC_384 => "nistp384".to_string(),
+      p if p == P521 || p == ECC_512 => "nistp521".to_string(),
+      c if c == BRAINPOOLP256R1 => "brainpoolP256r1".to_string(),
+      c if c == BRAINPOOLP384R1 => "brainpoolP384r1".to_string(),
+      c if c == BRAINPOOLP512R1 => "brainpoolP512r1".to_string(),
+      c if c == SECP256K1 => "secp256k1".to_string(),
+      _ => return None,
+    },
+    Asymmetric::Ifc(ifc) if !is_placeholder(ifc.id) => format!("rsa{}", ifc.k),
+    Asymmetric::Ffc(ffc) if !is_placeholder(ffc.id) => format!("dsa{}", ffc.l),
+    _ => return None,
+  };
+  Some(format!(
+    "gpg --quick-generate-key 'Wardstone <wardstone@example.org>' {} sign",
+    algorithm
+  ))
+}
+
+// Identifiers at the end of the range are reserved for placeholders
+// such as those signifying that a primitive is not allowed.
+fn is_placeholder(id: u16) -> bool {
+  id == u16::MAX
+}
+
+fn openssl_curve(ecc: Ecc) -> Option<&'static str> {
+  let name = match ecc {
+    c if c == P224 || c == ECC_224 => "secp224r1",
+    c if c == P256 || c == ECC_256 => "prime256v1",
+    c if c == P384 || c == ECC_384 => "secp384r1",
+    c if c == P521 || c == ECC_512 => "secp521r1",
+    c if c == BRAINPOOLP256R1 => "brainpoolP256r1",
+    c if c == BRAINPOOLP320R1 => "brainpoolP320r1",
+    c if c == BRAINPOOLP384R1 => "brainpoolP384r1",
+    c if c == BRAINPOOLP512R1 => "brainpoolP512r1",
+    c if c == SECP256K1 => "secp256k1",
+    c if c == SM2 => "SM2",
+    _ => return None,
+  };
+  Some(name)
+}
+
+fn openssl_digest(hash: Hash) -> Option<&'static str> {
+  let name = match hash {
+    h if h == SHA224 => "sha224",
+    h if h == SHA256 => "sha256",
+    h if h == SHA384 => "sha384",
+    h if h == SHA512 => "sha512",
+    h if h == SHA512_224 => "sha512-224",
+    h if h == SHA512_256 => "sha512-256",
+    h if h == SHA3_224 => "sha3-224",
+    h if h == SHA3_256 => "sha3-256",
+    h if h == SHA3_384 => "sha3-384",
+    h if h == SHA3_512 => "sha3-512",
+    _ => return None,
+  };
+  Some(name)
+}
+
+fn openssl_rsa(ifc: Ifc) -> Option<String> {
+  if is_placeholder(ifc.id) {
+    return None;
+  }
+  if ifc.is_pss() {
+    Some(format!("rsa-pss -pkeyopt rsa_keygen_bits:{}", ifc.k))
+  } else {
+    Some(format!("rsa:{}", ifc.k))
+  }
+}
+
+fn openssl_dsa(hash: Option<Hash>, ffc: Ffc) -> Option<String> {
+  if is_placeholder(ffc.id) {
+    return None;
+  }
+  let mut cmd = format!(
+    "openssl genpkey -genparam -algorithm DSA -pkeyopt dsa_paramgen_bits:{} -pkeyopt dsa_paramgen_q_bits:{} -out dsa.param && openssl req -new -newkey dsa:dsa.param -nodes -keyout key.pem -out request.csr",
+    ffc.l, ffc.n
+  );
+  if let Some(digest) = hash.and_then(openssl_digest) {
+    cmd.push_str(format!(" -{}", digest).as_str());
+  }
+  Some(cmd)
+}
+
+#[cfg(test)]
+mod tests {
+  use wardstone_core::primitive::ffc::*;
+  use wardstone_core::primitive::hybrid::Hybrid;
+  use wardstone_core::primitive::ifc::*;
+  use wardstone_core::primitive::pqc::*;
+  use wardstone_core::primitive::stateful::*;
+
+  use super::*;
+
+  const CSR: &str = "-nodes -keyout key.pem -out request.csr";
+
+  #[test]
+  fn openssl_ecc() {
+    let cases = [
+      (P256, "ec -pkeyopt ec_paramgen_curve:prime256v1"),
+      (ECC_384, "ec -pkeyopt ec_paramgen_curve:secp384r1"),
+      (
+        BRAINPOOLP320R1,
+        "ec -pkeyopt ec_paramgen_curve:brainpoolP320r1",
+      ),
+    ];
+    for (ecc, newkey) in cases {
+      assert_eq!(
+        openssl(Some(SHA256), ecc.into()),
+        Some(format!(
+          "openssl req -new -newkey {} {} -sha256",
+          newkey, CSR
+        )),
+        "{}",
+        ecc
+      );
+    }
+    assert_eq!(openssl(Some(SHA256), X25519.into()), None);
+    assert_eq!(openssl(Some(SHA256), ECC_NOT_ALLOWED.into()), None);
+  }
+
+  #[test]
+  fn openssl_edwards_curves_mandate_their_hash() {
+    for (ecc, newkey) in [(ED25519, "ed25519"), (ED448, "ed448")] {
+      assert_eq!(
+        openssl(Some(SHA512), ecc.into()),
+        Some(format!("openssl req -new -newkey {} {}", newkey, CSR))
+      );
+    }
+  }
+
+  #[test]
+  fn openssl_rsa() {
+    let cases = [
+      (RSA_PKCS1_3072, "rsa:3072"),
+      (RSA_PSS_4096, "rsa-pss -pkeyopt rsa_keygen_bits:4096"),
+      (Ifc::new(ID_RSA_PKCS1, 2048), "rsa:2048"),
+      (
+        Ifc::new(ID_RSA_PSS, 2048),
+        "rsa-pss -pkeyopt rsa_keygen_bits:2048",
+      ),
+    ];
+    for (ifc, newkey) in cases {
+      assert_eq!(
+        openssl(Some(SHA384), ifc.into()),
+        Some(format!(
+          "openssl req -new -newkey {} {} -sha384",
+          newkey, CSR
+        )),
+        "{}",
+        ifc
+      );
+    }
+    assert_eq!(openssl(Some(SHA384), IFC_NOT_ALLOWED.into()), None);
+  }
+
+  #[test]
+  fn openssl_dsa() {
+    let cmd = openssl(Some(SHA256), DSA_3072_256.into()).unwrap();
+    assert!(cmd.starts_with("openssl genpkey -genparam -algorithm DSA -pkeyopt dsa_paramgen_bits:3072 -pkeyopt dsa_paramgen_q_bits:256 -out dsa.param && "));
+    assert!(cmd.ends_with(&format!("-newkey dsa:dsa.param {} -sha256", CSR)));
+    assert_eq!(openssl(Some(SHA256), FFC_NOT_SUPPORTED.into()), None);
+  }
+
+  #[test]
+  fn openssl_post_quantum() {
+    assert_eq!(
+      openssl(Some(SHA256), ML_DSA_65.into()),
+      Some(format!("openssl req -new -newkey ML-DSA-65 {}", CSR))
+    );
+    assert_eq!(
+      openssl(None, SLH_DSA_SHAKE_128S.into()),
+      Some(format!(
+        "openssl req -new -newkey SLH-DSA-SHAKE-128s {}",
+        CSR
+      ))
+    );
+    assert_eq!(openssl(None, PQC_NOT_ALLOWED.into()), None);
+    assert_eq!(openssl(None, Hybrid::new(ML_DSA_65, P256).into()), None);
+    assert_eq!(openssl(None, LMS_SHA256_256.into()), None);
+  }
+
+  #[test]
+  fn openssl_digests() {
+    let cases = [
+      (SHA224, Some("sha224")),
+      (SHA512_256, Some("sha512-256")),
+      (SHA3_384, Some("sha3-384")),
+      (SHA1, None),
+      (MD5, None),
+    ];
+    for (hash, digest) in cases {
+      assert_eq!(openssl_digest(hash), digest, "{}", hash);
+    }
+    // A digest OpenSSL has no name for is left to its default.
+    assert_eq!(
+      openssl(Some(SHA1), RSA_PKCS1_2048.into()),
+      Some(format!("openssl req -new -newkey rsa:2048 {}", CSR))
+    );
+  }
+
+  #[test]
+  fn openssl_curves() {
+    let cases = [
+      (P224, Some("secp224r1")),
+      (ECC_256, Some("prime256v1")),
+      (P521, Some("secp521r1")),
+      (ECC_512, Some("secp521r1")),
+      (BRAINPOOLP512R1, Some("brainpoolP512r1")),
+      (SECP256K1, Some("secp256k1")),
+      (SM2, Some("SM2")),
+      (ED25519, None),
+    ];
+    for (ecc, name) in cases {
+      assert_eq!(openssl_curve(ecc), name, "{}", ecc);
+    }
+  }
+
+  #[test]
+  fn ssh_keygen_key_types() {
+    let cases: [(Asymmetric, Option<&str>); 8] = [
+      (ED25519.into(), Some("-t ed25519")),
+      (P256.into(), Some("-t ecdsa -b 256")),
+      (ECC_384.into(), Some("-t ecdsa -b 384")),
+      (ECC_512.into(), Some("-t ecdsa -b 521")),
+      (RSA_PSS_3072.into(), Some("-t rsa -b 3072")),
+      (ED448.into(), None),
+      (DSA_1024_160.into(), None),
+      (ML_DSA_44.into(), None),
+    ];
+    for (signature, args) in cases {
+      assert_eq!(
+        ssh_keygen(signature),
+        args.map(|args| format!("ssh-keygen {} -f id_wardstone", args)),
+        "{}",
+        signature
+      );
+    }
+    assert_eq!(ssh_keygen(IFC_NOT_ALLOWED.into()), None);
+  }
+
+  #[test]
+  fn gpg_algorithms() {
+    let cases: [(Asymmetric, Option<&str>); 9] = [
+      (ED25519.into(), Some("ed25519")),
+      (ED448.into(), Some("ed448")),
+      (ECC_256.into(), Some("nistp256")),
+      (BRAINPOOLP384R1.into(), Some("brainpoolP384r1")),
+      (RSA_PKCS1_4096.into(), Some("rsa4096")),
+      (DSA_3072_256.into(), Some("dsa3072")),
+      (SM2.into(), None),
+      (SLH_DSA_SHA2_128S.into(), None),
+      (XMSS_SHA256_256.into(), None),
+    ];
+    for (signature, algorithm) in cases {
+      assert_eq!(
+        gpg(signature),
+        algorithm.map(|algorithm| format!(
+          "gpg --quick-generate-key 'Wardstone <wardstone@example.org>' {} sign",
+          algorithm
+        )),
+        "{}",
+        signature
+      );
+    }
+    assert_eq!(gpg(FFC_NOT_SUPPORTED.into()), None);
+  }
+}
@@ -1,23 +1,58 @@
 //! Compose a single report on the results of multiple audits.
+use std::env;
 use std::fmt::{self, Display, Formatter};
+use std::io::{self, IsTerminal};
 use std::path::{Path, PathBuf};
 use std::process::{ExitCode, Termination};
 
+use anstyle::{AnsiColor, Color, Style};
+use clap::ValueEnum;
 use serde::Serialize;
-use serde_json::json;
+use serde_json::{json, Value};
+use wardstone_core::advisory::Advisory;
+use wardstone_core::context::Context;
+use wardstone_core::locale::Locale;
 use wardstone_core::primitive::asymmetric::Asymmetric;
 use wardstone_core::primitive::hash::Hash;
+use wardstone_core::primitive::symmetric::{Mode, Symmetric};
+use wardstone_core::primitive::{Primitive, Security};
+use wardstone_core::standard::rule::Citation;
+use wardstone_core::standard::verdict::{Severity, Verdict};
 
-use crate::key::Error;
+use crate::key::{Error, Location};
+use crate::template::Template;
+use crate::tls::Resumption;
+
+mod decision;
+mod document;
+mod estate;
+pub mod metrics;
+mod sarif;
+pub mod syslog;
+pub mod webhook;
+
+/// The version of the JSON output, which is incremented whenever a
+/// change would break existing consumers.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// The JSON Schema that the JSON output conforms to.
+pub const SCHEMA: &str = include_str!("../schema/report.schema.json");
+
+/// The exit code when a key or server could not be assessed, which
+/// sets errors apart from keys that were assessed and failed.
+pub const EXIT_ERROR: u8 = 2;
 
 /// Represents the exit status of the program.
 ///
-/// It implements [`Termination`] such that if any one of the audits
-/// fail or an error occurs, a helpful message is printed and the exit
-/// code is set to [`ExitCode::FAILURE`].
+/// It implements [`Termination`] such that the exit code is
+/// [`ExitCode::FAILURE`] if any one of the audits fail and
+/// [`EXIT_ERROR`] if an error occurs, in which case a helpful message
+/// is printed.
 pub enum Exit {
   Success(Report),
   Failure(Error),
+  /// The command completed without assessing any keys.
+  Done,
 }
 
 impl Termination for Exit {
@@ -26,8 +61,9 @@ impl Termination for Exit {
       Exit::Success(report) => report.report(),
       Exit::Failure(err) => {
         eprintln!("{}", err);
-        ExitCode::FAILURE
+        ExitCode::from(EXIT_ERROR)
       },
+      Exit::Done => ExitCode::SUCCESS,
     }
   }
 }
@@ -60,31 +96,205 @@ impl Verbosity {
   }
 }
 
+const OK: Style = Style::new()
+  .bold()
+  .fg_color(Some(Color::Ansi(AnsiColor::Green)));
+const FAIL: Style = Style::new()
+  .bold()
+  .fg_color(Some(Color::Ansi(AnsiColor::Red)));
+const WARNING: Style = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Yellow)));
+
+fn severity_style(severity: Severity) -> Style {
+  let color = match severity {
+    Severity::Info => return Style::new(),
+    Severity::Legacy => AnsiColor::Yellow,
+    Severity::Deprecated => AnsiColor::Magenta,
+    Severity::NonCompliant => AnsiColor::Red,
+  };
+  Style::new().bold().fg_color(Some(Color::Ansi(color)))
+}
+
+/// Wraps the text in the escape codes of the style if `color` is set.
+fn paint(style: Style, text: impl Display, color: bool) -> String {
+  if color {
+    format!("{}{}{}", style.render(), text, style.render_reset())
+  } else {
+    text.to_string()
+  }
+}
+
+/// Whether the human readable output should be coloured, which is the
+/// case if it is written to a terminal and `NO_COLOR` is not set.
+///
+/// See <https://no-color.org>.
+fn color_enabled() -> bool {
+  let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+  !no_color && io::stdout().is_terminal()
+}
+
+/// Writes a number of bytes in the largest binary unit it is a whole
+/// multiple of, such as `8 MiB`.
+fn size(bytes: u64) -> String {
+  for (unit, shift) in [("GiB", 30), ("MiB", 20), ("KiB", 10)] {
+    if bytes >= 1 << shift && bytes.is_multiple_of(1 << shift) {
+      return format!("{} {}", bytes >> shift, unit);
+    }
+  }
+  format!("{} bytes", bytes)
+}
+
+/// A primitive that is not compliant or will not be after the cutoff
+/// year of a rule, or that is hard to implement safely.
+///
+/// The identifier of the rule is stable across releases so that it can
+/// be used to track a finding over time.
+#[derive(Clone, Eq, PartialEq, Serialize)]
+pub struct Finding {
+  /// The name of the primitive the finding is about.
+  #[serde(skip)]
+  primitive: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  rule_id: Option<&'static str>,
+  severity: Severity,
+  message: String,
+  /// The bits of security the primitive offers.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  security: Option<Security>,
+  /// The bits of security the guide and context require.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  required: Option<Security>,
+  /// Where the offending key is found within its file.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  location: Option<Location>,
+}
+
+impl Finding {
+  fn render(&self, color: bool) -> String {
+    let severity = paint(severity_style(self.severity), self.severity, color);
+    match self.rule_id {
+      Some(id) => format!("{} [{}]: {}", severity, id, self.message),
+      None => format!("{}: {}", severity, self.message),
+    }
+  }
+}
+
+impl Display for Finding {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.render(false))
+  }
+}
+
 /// Represents an audit of a single key.
 #[derive(Serialize)]
 pub struct Audit {
   passed: bool,
   path: PathBuf,
+  /// The audience the key is assessed for, if one was named.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  audience: Option<String>,
   #[serde(skip_serializing_if = "Option::is_none")]
   got_hash_function: Option<Hash>,
   #[serde(skip_serializing_if = "Option::is_none")]
   want_hash_function: Option<Hash>,
-  got_signature: Asymmetric,
-  want_signature: Asymmetric,
+  /// The key the audit is about, which settings such as those of disk
+  /// encryption do not have.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  got_signature: Option<Asymmetric>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  want_signature: Option<Asymmetric>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  got_issuer_key: Option<Asymmetric>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  want_issuer_key: Option<Asymmetric>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  got_key_exchange: Option<Asymmetric>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  want_key_exchange: Option<Asymmetric>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  got_cipher: Option<Symmetric>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  want_cipher: Option<Symmetric>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  got_validity_days: Option<u64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  want_validity_days: Option<u64>,
+  /// How the server resumes sessions, if it was found out.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  resumption: Option<Resumption>,
+  /// Explains the primitives that are not compliant or will not be
+  /// after the cutoff year of a rule, along with advisories.
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  findings: Vec<Finding>,
+  /// The parts of the guide the outcome is based on.
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  references: Vec<Citation>,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  warnings: Vec<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  remediation: Option<String>,
+  /// Where the key is found within its file, which is carried into
+  /// each finding.
+  #[serde(skip)]
+  location: Option<Location>,
+  /// The name of the guide the key is assessed against.
+  #[serde(skip)]
+  standard: &'static str,
+  /// Every assessment made, including those of compliant primitives.
+  #[serde(skip)]
+  decisions: Vec<decision::Decision>,
 }
 
 impl Audit {
-  pub fn new(path: &Path, hash: Option<Hash>, signature: Asymmetric) -> Self {
+  pub fn new(
+    path: &Path,
+    standard: &'static str,
+    hash: Option<Hash>,
+    signature: Asymmetric,
+  ) -> Self {
+    Self {
+      got_hash_function: hash,
+      got_signature: Some(signature),
+      want_signature: Some(signature),
+      ..Self::of_settings(path, standard)
+    }
+  }
+
+  /// Returns an audit of settings that are not about a key, such as the
+  /// cipher and key derivation of an encrypted volume.
+  pub fn of_settings(path: &Path, standard: &'static str) -> Self {
     Self {
       passed: true,
       path: path.to_path_buf(),
-      got_hash_function: hash,
+      audience: None,
+      got_hash_function: None,
       want_hash_function: None,
-      got_signature: signature,
-      want_signature: signature,
+      got_signature: None,
+      want_signature: None,
+      got_issuer_key: None,
+      want_issuer_key: None,
+      got_key_exchange: None,
+      want_key_exchange: None,
+      got_cipher: None,
+      want_cipher: None,
+      got_validity_days: None,
+      want_validity_days: None,
+      resumption: None,
+      findings: Vec::new(),
+      references: Vec::new(),
+      warnings: Vec::new(),
+      remediation: None,
+      location: None,
+      standard,
+      decisions: Vec::new(),
     }
   }
 
+  /// Records the hash function of an audit of settings.
+  pub fn with_hash_function(mut self, hash: Option<Hash>) -> Self {
+    self.got_hash_function = hash;
+    self
+  }
+
   pub fn noncompliant_hash_function(&mut self, want: Hash) {
     self.passed = false;
     self.want_hash_function = Some(want);
@@ -96,33 +306,299 @@ impl Audit {
 
   pub fn noncompliant_signature(&mut self, want: Asymmetric) {
     self.passed = false;
-    self.want_signature = want;
+    self.want_signature = Some(want);
   }
 
   pub fn compliant_signature(&mut self, want: Asymmetric) {
-    self.want_signature = want;
+    self.want_signature = Some(want);
+  }
+
+  pub fn noncompliant_issuer_key(&mut self, got: Asymmetric, want: Asymmetric) {
+    self.passed = false;
+    self.got_issuer_key = Some(got);
+    self.want_issuer_key = Some(want);
+  }
+
+  pub fn compliant_issuer_key(&mut self, got: Asymmetric, want: Asymmetric) {
+    self.got_issuer_key = Some(got);
+    self.want_issuer_key = Some(want);
+  }
+
+  pub fn noncompliant_key_exchange(&mut self, got: Asymmetric, want: Asymmetric) {
+    self.passed = false;
+    self.got_key_exchange = Some(got);
+    self.want_key_exchange = Some(want);
+  }
+
+  pub fn compliant_key_exchange(&mut self, got: Asymmetric, want: Asymmetric) {
+    self.got_key_exchange = Some(got);
+    self.want_key_exchange = Some(want);
+  }
+
+  pub fn noncompliant_cipher(&mut self, got: Symmetric, want: Symmetric) {
+    self.passed = false;
+    self.got_cipher = Some(got);
+    self.want_cipher = Some(want);
+  }
+
+  pub fn compliant_cipher(&mut self, got: Symmetric, want: Symmetric) {
+    self.got_cipher = Some(got);
+    self.want_cipher = Some(want);
+  }
+
+  pub fn noncompliant_validity(&mut self, got: u64, want: u64) {
+    self.passed = false;
+    self.got_validity_days = Some(got);
+    self.want_validity_days = Some(want);
+  }
+
+  /// Records a setting that is unsafe whatever the guide, such as an
+  /// EAP method that does not authenticate the server.
+  pub fn noncompliant_setting(&mut self, setting: &str, message: String) {
+    self.passed = false;
+    let finding = Finding {
+      primitive: setting.to_string(),
+      rule_id: None,
+      severity: Severity::NonCompliant,
+      message: format!("{} ({})", message, setting),
+      security: None,
+      required: None,
+      location: self.location,
+    };
+    if !self.findings.contains(&finding) {
+      self.findings.push(finding);
+    }
+  }
+
+  /// Records the audience the key is assessed for.
+  pub fn address(&mut self, audience: String) {
+    self.audience = Some(audience);
+  }
+
+  /// Records how the server resumes sessions.
+  pub fn resume(&mut self, resumption: Resumption) {
+    self.resumption = Some(resumption);
+  }
+
+  /// Records the part of the guide that an outcome is based on. Each
+  /// reference is only recorded once.
+  pub fn cite(&mut self, citation: Option<Citation>) {
+    if let Some(citation) = citation {
+      if !self.references.contains(&citation) {
+        self.references.push(citation);
+      }
+    }
+  }
+
+  /// Records the decision on a primitive along with a finding if it is
+  /// not compliant or will not be after the cutoff year of a rule.
+  /// Primitives without concerns have no finding, and a primitive used
+  /// more than once, such as the key of a self-signed certificate, only
+  /// has one.
+  ///
+  /// A compliant primitive that offers less security than the context
+  /// asks for is noted with a warning that quantifies the gap.
+  pub fn explain<T: Display + Primitive + Serialize>(
+    &mut self,
+    verdict: &Verdict<T>,
+    ctx: Context,
+    got: T,
+    locale: Locale,
+  ) {
+    self
+      .decisions
+      .push(decision::Decision::new(verdict, ctx, got.to_string()));
+    if verdict.severity == Severity::Info {
+      let security = verdict.security.unwrap_or_default();
+      if let Some(required) = verdict.required.filter(|&required| security < required) {
+        let warning = format!(
+          "{} provides ~{}-bit security; context requires {}",
+          got, security, required
+        );
+        if !self.warnings.contains(&warning) {
+          self.warn(warning);
+        }
+      }
+      return;
+    }
+    let finding = Finding {
+      primitive: got.to_string(),
+      rule_id: verdict.rule.map(|rule| rule.id),
+      severity: verdict.severity,
+      message: verdict.rationale_in(ctx, got, locale),
+      security: verdict.security,
+      required: verdict.required,
+      location: self.location,
+    };
+    if !self.findings.contains(&finding) {
+      self.findings.push(finding);
+    }
+  }
+
+  /// Records an informational finding about a hazard of implementing
+  /// a primitive, which does not affect the outcome of the audit.
+  pub fn advise(&mut self, advisory: &Advisory, got: impl Display) {
+    self.cite(Some(advisory.citation));
+    let finding = Finding {
+      primitive: got.to_string(),
+      rule_id: Some(advisory.id),
+      severity: Severity::Info,
+      message: advisory.message(got),
+      security: None,
+      required: None,
+      location: self.location,
+    };
+    if !self.findings.contains(&finding) {
+      self.findings.push(finding);
+    }
+  }
+
+  /// Records an advisory about a cipher used in `mode` like
+  /// [`Audit::advise`], along with the number of blocks the guide lets
+  /// one key encrypt.
+  pub fn advise_limit(&mut self, advisory: &Advisory, got: Symmetric, mode: Mode, blocks: u64) {
+    let bytes = blocks.saturating_mul(u64::from(got.block_size() / 8));
+    let message = format!(
+      "{}; the guide limits one key to 2^{} blocks ({})",
+      advisory.message(format!("{} in {} mode", got, mode)),
+      blocks.ilog2(),
+      size(bytes)
+    );
+    self.cite(Some(advisory.citation));
+    let finding = Finding {
+      primitive: got.to_string(),
+      rule_id: Some(advisory.id),
+      severity: Severity::Info,
+      message,
+      security: None,
+      required: None,
+      location: self.location,
+    };
+    if !self.findings.contains(&finding) {
+      self.findings.push(finding);
+    }
+  }
+
+  /// Records a finding that does not affect the outcome of the audit.
+  pub fn warn(&mut self, warning: String) {
+    self.warnings.push(warning);
+  }
+
+  pub fn passed(&self) -> bool {
+    self.passed
+  }
+
+  /// Records a command that can be used to replace a non-compliant key.
+  pub fn remediate(&mut self, hint: String) {
+    self.remediation = Some(hint);
+  }
+
+  /// Records where the key is found within its file. This should be
+  /// done before any findings are explained.
+  pub fn locate(&mut self, location: Location) {
+    self.location = Some(location);
+  }
+}
+
+impl Audit {
+  /// Returns the primitives and properties that were assessed along with
+  /// what the guide wants in their place.
+  fn rows(&self) -> Vec<(&'static str, String, String)> {
+    let mut rows = Vec::new();
+    if let (Some(got), Some(want)) = (self.got_hash_function, self.want_hash_function) {
+      rows.push(("hash function", got.to_string(), want.to_string()));
+    }
+    if let (Some(got), Some(want)) = (self.got_signature, self.want_signature) {
+      rows.push(("signature algorithm", got.to_string(), want.to_string()));
+    }
+    if let (Some(got), Some(want)) = (self.got_issuer_key, self.want_issuer_key) {
+      rows.push(("issuer key", got.to_string(), want.to_string()));
+    }
+    if let (Some(got), Some(want)) = (self.got_key_exchange, self.want_key_exchange) {
+      rows.push(("key exchange", got.to_string(), want.to_string()));
+    }
+    if let (Some(got), Some(want)) = (self.got_cipher, self.want_cipher) {
+      rows.push(("cipher", got.to_string(), want.to_string()));
+    }
+    if let (Some(got), Some(want)) = (self.got_validity_days, self.want_validity_days) {
+      rows.push((
+        "validity",
+        format!("{} days", got),
+        format!("at most {} days", want),
+      ));
+    }
+    rows
+  }
+
+  /// Renders the audit as a heading with the outcome and path followed
+  /// by its details, highlighting the outcome and the severity of
+  /// findings if `color` is set.
+  fn render(&self, color: bool) -> String {
+    let mut s = if self.passed {
+      format!("{} {}\n", paint(OK, "ok:", color), self.path.display())
+    } else {
+      format!("{} {}\n", paint(FAIL, "fail:", color), self.path.display())
+    };
+    let mut line = |line: String| s.push_str(format!("  {}\n", line).as_str());
+    if let Some(audience) = &self.audience {
+      line(format!("audience: {} ({})", audience, self.standard));
+    }
+    for (label, got, want) in self.rows() {
+      line(format!("{}: got {}, want {}", label, got, want));
+    }
+    if let Some(resumption) = self.resumption {
+      line(format!("resumption: {}", resumption));
+    }
+    for finding in self.findings.iter() {
+      line(finding.render(color));
+    }
+    for reference in self.references.iter() {
+      line(format!("reference: {}", reference));
+    }
+    for warning in self.warnings.iter() {
+      line(format!("{} {}", paint(WARNING, "warning:", color), warning));
+    }
+    if let Some(remediation) = &self.remediation {
+      line(format!("remediation: {}", remediation));
+    }
+    s
   }
 }
 
 impl Display for Audit {
   fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-    let mut s = String::new();
-    if let (Some(got), Some(want)) = (self.got_hash_function, self.want_hash_function) {
-      s.push_str(format!("hash function: got {}, want {}\n", got, want).as_str());
-    }
-    s.push_str(
-      format!(
-        "signature algorithm: got {}, want {}\n",
-        self.got_signature, self.want_signature
-      )
-      .as_str(),
-    );
-    if self.passed {
-      s.push_str(format!("ok: {}", self.path.display()).as_str());
+    write!(f, "{}", self.render(false))
+  }
+}
+
+/// The format a report is printed in.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum Output {
+  /// Human readable text.
+  Text,
+  /// JSON for other programs to consume.
+  Json,
+  /// A Markdown document with summary tables and references.
+  Markdown,
+  /// A self-contained HTML document with summary tables and references.
+  Html,
+  /// A JSON event per line for every primitive assessed, in the style
+  /// of the decision logs of Open Policy Agent.
+  Decisions,
+  /// SARIF 2.1.0 for code scanning, with every finding anchored at the
+  /// line it was found at where that is known.
+  Sarif,
+}
+
+impl Output {
+  /// Returns the format selected by `--format`, which `--json` is a
+  /// shorthand for.
+  pub fn from_flags(json: bool, format: Option<Output>) -> Output {
+    if json {
+      Self::Json
     } else {
-      s.push_str(format!("fail: {}", self.path.display()).as_str());
+      format.unwrap_or(Self::Text)
     }
-    write!(f, "{s}")
   }
 }
 
@@ -130,23 +606,59 @@ impl Display for Audit {
 pub struct Report {
   audits: Vec<Audit>,
   verbosity: Verbosity,
-  json: bool,
+  output: Output,
+  template: Option<Template>,
+  /// The hosts the audits were made of if they are grouped by host.
+  hosts: Vec<estate::Host>,
+  /// The number of keys that could not be read.
+  errors: usize,
 }
 
 impl Report {
-  pub fn new(verbosity: Verbosity, json: bool) -> Self {
+  pub fn new(verbosity: Verbosity, output: Output) -> Self {
     Self {
       audits: Vec::new(),
       verbosity,
-      json,
+      output,
+      template: None,
+      hosts: Vec::new(),
+      errors: 0,
     }
   }
 
+  /// Renders the report with the template instead of the output format
+  /// when it is printed.
+  pub fn with_template(mut self, template: Option<Template>) -> Self {
+    self.template = template;
+    self
+  }
+
   pub fn push(&mut self, audit: Audit) {
     self.audits.push(audit);
   }
 
+  /// Reports a key that could not be read, leaving it out of the report
+  /// but not the exit code.
+  pub fn skip(&mut self, err: Error) {
+    eprintln!("{}", err);
+    self.errors += 1;
+  }
+
+  /// Orders the audits by the path of their key and then by where the
+  /// key is found within its file, so that the report does not depend
+  /// on the order in which keys were read. Audits of keys at the same
+  /// place keep their order.
+  pub fn sort(&mut self) {
+    self
+      .audits
+      .sort_by(|a, b| (&a.path, a.location).cmp(&(&b.path, b.location)));
+  }
+
   pub fn to_json_string(&self) -> String {
+    if self.is_grouped() {
+      let hosts = self.hosts_value(self.verbosity.is_verbose());
+      return json!({ "version": SCHEMA_VERSION, "hosts": hosts }).to_string();
+    }
     let mut v = Vec::new();
     for audit in self.audits.iter() {
       if audit.passed {
@@ -160,41 +672,170 @@ impl Report {
     // Partition by compliance status.
     let (mut v, failed): (Vec<_>, Vec<_>) = v.into_iter().partition(|a| a.passed);
     v.extend::<Vec<&Audit>>(failed);
-    json!({ "report": &v }).to_string()
+    json!({ "version": SCHEMA_VERSION, "report": &v }).to_string()
   }
-}
 
-impl Display for Report {
-  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+  /// Renders the report as a Markdown document listing every audit.
+  pub fn to_markdown_string(&self) -> String {
+    document::markdown(self)
+  }
+
+  /// Renders the report as an HTML document listing every audit.
+  pub fn to_html_string(&self) -> String {
+    document::html(self)
+  }
+
+  /// Returns the data that templates are rendered with. Unlike the
+  /// JSON output, it holds every audit along with a summary, and every
+  /// host if the audits are grouped by host.
+  pub fn to_template_data(&self) -> Value {
+    let [(_, non_compliant), (_, deprecated), (_, legacy)] = self.counts();
+    let worst = self.worst().map(|(finding, path)| {
+      json!({
+        "path": path,
+        "rule_id": finding.rule_id,
+        "severity": finding.severity,
+        "message": finding.message,
+      })
+    });
+    let mut data = json!({
+      "summary": {
+        "checked": self.audits.len(),
+        "failed": self.failed(),
+        "non_compliant": non_compliant,
+        "deprecated": deprecated,
+        "legacy": legacy,
+        "worst": worst,
+      },
+      "report": &self.audits,
+    });
+    if self.is_grouped() {
+      data["hosts"] = self.hosts_value(true);
+    }
+    data
+  }
+
+  /// Prints the report to the standard output unless the verbosity
+  /// level is set to quiet.
+  pub fn print(&self) {
+    if !self.verbosity.is_quiet() {
+      if let Some(template) = &self.template {
+        print!("{}", template.render(&self.to_template_data()));
+        return;
+      }
+      let repr = match self.output {
+        Output::Text => self.render(color_enabled()),
+        Output::Json => self.to_json_string(),
+        Output::Markdown => self.to_markdown_string(),
+        Output::Html => self.to_html_string(),
+        Output::Decisions => self.to_decisions_string(),
+        Output::Sarif => self.to_sarif_string(),
+      };
+      print!("{}", repr)
+    }
+  }
+
+  /// Renders the audits grouped by the key they assessed, followed by a
+  /// summary of the outcome and the most severe finding.
+  fn render(&self, color: bool) -> String {
+    if self.is_grouped() {
+      let mut s = self.render_hosts(color);
+      s.push_str(self.summary(color).as_str());
+      return s;
+    }
     // Partition by compliance status.
     let (mut v, failed): (Vec<_>, Vec<_>) = self.audits.iter().partition(|a| a.passed);
     v.extend::<Vec<&Audit>>(failed);
     let mut s = String::new();
     for audit in v.iter() {
-      if audit.passed {
-        if self.verbosity.is_verbose() {
-          s.push_str(format!("{}\n", audit).as_str());
-        }
-      } else {
-        s.push_str(format!("{}\n", audit).as_str())
+      if !audit.passed || self.verbosity.is_verbose() {
+        s.push_str(format!("{}\n", audit.render(color)).as_str());
       }
     }
-    write!(f, "{}", s)
+    if !self.audits.is_empty() {
+      s.push_str(self.summary(color).as_str());
+    }
+    s
+  }
+
+  fn failed(&self) -> usize {
+    self.audits.iter().filter(|audit| !audit.passed).count()
+  }
+
+  /// Returns the findings of every audit along with the path of the key
+  /// they were found in.
+  fn findings(&self) -> impl DoubleEndedIterator<Item = (&Finding, &Path)> + Clone {
+    self.audits.iter().flat_map(|audit| {
+      audit
+        .findings
+        .iter()
+        .map(move |finding| (finding, audit.path.as_path()))
+    })
+  }
+
+  /// Counts the findings of each severity, from most to least severe.
+  fn counts(&self) -> [(Severity, usize); 3] {
+    [
+      Severity::NonCompliant,
+      Severity::Deprecated,
+      Severity::Legacy,
+    ]
+    .map(|severity| {
+      let n = self
+        .findings()
+        .filter(|(finding, _)| finding.severity == severity)
+        .count();
+      (severity, n)
+    })
+  }
+
+  /// Returns the first of the most severe findings, which is the one to
+  /// address first. Advisories are informational so they are left out.
+  fn worst(&self) -> Option<(&Finding, &Path)> {
+    self
+      .findings()
+      .rev()
+      .filter(|(finding, _)| finding.severity > Severity::Info)
+      .max_by_key(|(finding, _)| finding.severity)
+  }
+
+  /// Summarises the number of audits, failures and findings by severity
+  /// and names the most severe finding.
+  fn summary(&self, color: bool) -> String {
+    let mut s = format!("{} checked, {} failed", self.audits.len(), self.failed());
+    let counts: Vec<_> = self
+      .counts()
+      .into_iter()
+      .filter(|&(_, n)| n > 0)
+      .map(|(severity, n)| format!("{} {}", n, paint(severity_style(severity), severity, color)))
+      .collect();
+    if !counts.is_empty() {
+      s.push_str(format!(": {}", counts.join(", ")).as_str());
+    }
+    if self.errors > 0 {
+      s.push_str(format!("; {} could not be read", self.errors).as_str());
+    }
+    s.push('\n');
+    if let Some((finding, path)) = self.worst() {
+      s.push_str(format!("worst: {}: {}\n", path.display(), finding.render(color)).as_str());
+    }
+    s
+  }
+}
+
+impl Display for Report {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.render(false))
   }
 }
 
 impl Termination for Report {
   fn report(self) -> ExitCode {
     let (failed, _): (Vec<_>, Vec<_>) = self.audits.iter().partition(|audit| !audit.passed);
-    if !self.verbosity.is_quiet() {
-      let repr = if self.json {
-        self.to_json_string()
-      } else {
-        format!("{}", self)
-      };
-      print!("{}", repr)
-    }
-    if failed.is_empty() {
+    self.print();
+    if self.errors > 0 || self.unreachable() {
+      ExitCode::from(EXIT_ERROR)
+    } else if failed.is_empty() {
       ExitCode::SUCCESS
     } else {
       ExitCode::FAILURE
@@ -0,0 +1,107 @@
+//! Log every decision made while assessing keys for policy engines.
+//!
+//! Each primitive that is assessed yields an event in the style of the
+//! decision logs of Open Policy Agent, with the primitive and context
+//! as the input and the verdict as the result, so that organisations
+//! that centralise policy can collect the decisions and replay them
+//! against their own rules. Compliant primitives are logged as well.
+//! Events are printed one per line.
+//!
+//! ```text
+//! {
+//!   "timestamp": "2024-05-01T12:00:00Z",
+//!   "labels": {"app": "wardstone", "version": "0.2.0"},
+//!   "standard": "nist",
+//!   "input": {
+//!     "path": "cert.pem",
+//!     "primitive": "sha1",
+//!     "scope": "hash",
+//!     "context": {"security": 0, "year": 2023}
+//!   },
+//!   "result": {"compliant": false, "severity": "non-compliant", "alternative": "sha224", ...}
+//! }
+//! ```
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use wardstone_core::context::Context;
+use wardstone_core::standard::rule::Scope;
+use wardstone_core::standard::verdict::Verdict;
+use x509_parser::prelude::ASN1Time;
+
+use super::Report;
+
+/// The assessment of a single primitive.
+pub(super) struct Decision {
+  primitive: String,
+  scope: Option<Scope>,
+  ctx: Context,
+  result: Value,
+}
+
+impl Decision {
+  pub(super) fn new<T: Serialize>(verdict: &Verdict<T>, ctx: Context, primitive: String) -> Self {
+    Self {
+      primitive,
+      scope: verdict.rule.map(|rule| rule.scope),
+      ctx,
+      result: json!(verdict),
+    }
+  }
+}
+
+/// Returns the current time in the format used by RFC 3339.
+fn timestamp() -> String {
+  let seconds = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map_or(0, |d| d.as_secs());
+  match ASN1Time::from_timestamp(seconds as i64) {
+    Ok(time) => {
+      let t = time.to_datetime();
+      format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        t.year(),
+        t.month() as u8,
+        t.day(),
+        t.hour(),
+        t.minute(),
+        t.second()
+      )
+    },
+    Err(_) => String::new(),
+  }
+}
+
+impl Report {
+  /// Renders every decision made in the audits as a JSON event per
+  /// line.
+  pub fn to_decisions_string(&self) -> String {
+    let timestamp = timestamp();
+    let mut s = String::new();
+    for audit in self.audits.iter() {
+      for decision in audit.decisions.iter() {
+        let event = json!({
+          "timestamp": timestamp,
+          "labels": {
+            "app": "wardstone",
+            "version": env!("CARGO_PKG_VERSION"),
+          },
+          "standard": audit.standard,
+          "input": {
+            "path": audit.path,
+            "primitive": decision.primitive,
+            "scope": decision.scope,
+            "context": {
+              "security": decision.ctx.security(),
+              "year": decision.ctx.year(),
+            },
+          },
+          "result": decision.result,
+        });
+        s.push_str(&format!("{}\n", event));
+      }
+    }
+    s
+  }
+}
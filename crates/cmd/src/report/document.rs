@@ -0,0 +1,211 @@
+//! Render a report as a document that can be attached to an audit.
+//!
+//! Unlike the text output, the documents list every key that was
+//! checked, starting with those that failed, so that they serve as a
+//! record of the whole audit.
+use wardstone_core::standard::rule::Citation;
+
+use super::{Audit, Report};
+
+const TITLE: &str = "Compliance report";
+
+const STYLE: &str = "body{font-family:sans-serif;margin:2em auto;max-width:60em;padding:0 1em}\
+table{border-collapse:collapse;margin:1em 0}\
+th,td{border:1px solid #ccc;padding:.3em .6em;text-align:left;vertical-align:top}\
+code{overflow-wrap:anywhere}\
+.ok{color:#1a7f37}.fail,.non-compliant{color:#cf222e}\
+.deprecated{color:#8250df}.legacy{color:#9a6700}";
+
+// Failed audits come first since they are the ones that need action.
+fn audits(report: &Report) -> impl Iterator<Item = &Audit> {
+  let (failed, passed): (Vec<_>, Vec<_>) = report.audits.iter().partition(|a| !a.passed);
+  failed.into_iter().chain(passed)
+}
+
+fn outcome(audit: &Audit) -> &'static str {
+  if audit.passed {
+    "ok"
+  } else {
+    "fail"
+  }
+}
+
+/// Escapes text so that it can be placed in a cell of a Markdown table.
+fn cell(text: &str) -> String {
+  text.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Renders the report as a Markdown document.
+pub(super) fn markdown(report: &Report) -> String {
+  let mut s = format!("# {}\n\n", TITLE);
+  s.push_str("| Checked | Failed | Non-compliant | Deprecated | Legacy |\n");
+  s.push_str("| ---: | ---: | ---: | ---: | ---: |\n");
+  s.push_str(&format!(
+    "| {} | {} |",
+    report.audits.len(),
+    report.failed()
+  ));
+  for (_, n) in report.counts() {
+    s.push_str(&format!(" {} |", n));
+  }
+  s.push('\n');
+  if let Some((finding, path)) = report.worst() {
+    s.push_str(&format!(
+      "\nWorst finding in `{}`: **{}** {}\n",
+      path.display(),
+      finding.severity,
+      finding.message
+    ));
+  }
+  for audit in audits(report) {
+    s.push_str(&format!(
+      "\n## `{}`\n\nOutcome: **{}**\n\n",
+      audit.path.display(),
+      outcome(audit)
+    ));
+    s.push_str("| Property | Got | Want |\n| --- | --- | --- |\n");
+    for (label, got, want) in audit.rows() {
+      s.push_str(&format!(
+        "| {} | {} | {} |\n",
+        label,
+        cell(&got),
+        cell(&want)
+      ));
+    }
+    if !audit.findings.is_empty() {
+      s.push_str("\n| Severity | Rule | Finding |\n| --- | --- | --- |\n");
+      for finding in audit.findings.iter() {
+        s.push_str(&format!(
+          "| {} | {} | {} |\n",
+          finding.severity,
+          finding.rule_id.unwrap_or_default(),
+          cell(&finding.message)
+        ));
+      }
+    }
+    if !audit.warnings.is_empty() {
+      s.push_str("\nWarnings:\n\n");
+      for warning in audit.warnings.iter() {
+        s.push_str(&format!("- {}\n", warning));
+      }
+    }
+    if let Some(remediation) = &audit.remediation {
+      s.push_str(&format!("\nRemediation: `{}`\n", remediation));
+    }
+    if !audit.references.is_empty() {
+      s.push_str("\nReferences:\n\n");
+      for reference in audit.references.iter() {
+        s.push_str(&format!("- {}\n", reference));
+      }
+    }
+  }
+  s
+}
+
+/// Escapes text so that it can be placed in an HTML element or
+/// attribute.
+fn escape(text: &str) -> String {
+  let mut s = String::with_capacity(text.len());
+  for c in text.chars() {
+    match c {
+      '&' => s.push_str("&amp;"),
+      '<' => s.push_str("&lt;"),
+      '>' => s.push_str("&gt;"),
+      '"' => s.push_str("&quot;"),
+      '\'' => s.push_str("&#39;"),
+      c => s.push(c),
+    }
+  }
+  s
+}
+
+fn citation(citation: &Citation) -> String {
+  let mut s = format!(
+    "{}, {}",
+    escape(citation.document),
+    escape(citation.section)
+  );
+  if let Some(url) = citation.url {
+    s.push_str(&format!(" <a href=\"{0}\">{0}</a>", escape(url)));
+  }
+  s
+}
+
+/// Renders the report as a self-contained HTML document.
+pub(super) fn html(report: &Report) -> String {
+  let mut s = format!(
+    "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{0}</title>\n<style>{1}</style>\n</head>\n<body>\n<h1>{0}</h1>\n",
+    TITLE, STYLE
+  );
+  s.push_str("<table>\n<tr><th>Checked</th><th>Failed</th><th>Non-compliant</th><th>Deprecated</th><th>Legacy</th></tr>\n");
+  s.push_str(&format!(
+    "<tr><td>{}</td><td>{}</td>",
+    report.audits.len(),
+    report.failed()
+  ));
+  for (_, n) in report.counts() {
+    s.push_str(&format!("<td>{}</td>", n));
+  }
+  s.push_str("</tr>\n</table>\n");
+  if let Some((finding, path)) = report.worst() {
+    s.push_str(&format!(
+      "<p>Worst finding in <code>{}</code>: <strong class=\"{}\">{}</strong> {}</p>\n",
+      escape(&path.display().to_string()),
+      finding.severity,
+      finding.severity,
+      escape(&finding.message)
+    ));
+  }
+  for audit in audits(report) {
+    s.push_str(&format!(
+      "<section>\n<h2><code>{}</code></h2>\n<p>Outcome: <strong class=\"{1}\">{1}</strong></p>\n",
+      escape(&audit.path.display().to_string()),
+      outcome(audit)
+    ));
+    s.push_str("<table>\n<tr><th>Property</th><th>Got</th><th>Want</th></tr>\n");
+    for (label, got, want) in audit.rows() {
+      s.push_str(&format!(
+        "<tr><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+        label,
+        escape(&got),
+        escape(&want)
+      ));
+    }
+    s.push_str("</table>\n");
+    if !audit.findings.is_empty() {
+      s.push_str("<table>\n<tr><th>Severity</th><th>Rule</th><th>Finding</th></tr>\n");
+      for finding in audit.findings.iter() {
+        s.push_str(&format!(
+          "<tr><td class=\"{0}\">{0}</td><td>{1}</td><td>{2}</td></tr>\n",
+          finding.severity,
+          finding.rule_id.unwrap_or_default(),
+          escape(&finding.message)
+        ));
+      }
+      s.push_str("</table>\n");
+    }
+    if !audit.warnings.is_empty() {
+      s.push_str("<p>Warnings:</p>\n<ul>\n");
+      for warning in audit.warnings.iter() {
+        s.push_str(&format!("<li>{}</li>\n", escape(warning)));
+      }
+      s.push_str("</ul>\n");
+    }
+    if let Some(remediation) = &audit.remediation {
+      s.push_str(&format!(
+        "<p>Remediation: <code>{}</code></p>\n",
+        escape(remediation)
+      ));
+    }
+    if !audit.references.is_empty() {
+      s.push_str("<p>References:</p>\n<ul>\n");
+      for reference in audit.references.iter() {
+        s.push_str(&format!("<li>{}</li>\n", citation(reference)));
+      }
+      s.push_str("</ul>\n");
+    }
+    s.push_str("</section>\n");
+  }
+  s.push_str("</body>\n</html>\n");
+  s
+}
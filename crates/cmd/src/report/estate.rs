@@ -0,0 +1,184 @@
+//! Group the audits of many servers by host.
+//!
+//! Scanning an estate yields a certificate chain and the negotiated
+//! parameters of every service, which quickly becomes unreadable as a
+//! flat list. Audits are instead listed under the service they were
+//! made of and services under their host, each with a verdict that
+//! rolls up those below it. A host passes only if every one of its
+//! services could be scanned and passed.
+use std::ops::Range;
+
+use serde_json::{json, Value};
+
+use super::{paint, Audit, Report, FAIL, OK, WARNING};
+use crate::key::Error;
+
+/// The audits made of a server listening on a port.
+pub(super) struct Service {
+  port: u16,
+  /// The indices of the audits of the service within the report.
+  audits: Range<usize>,
+  /// Why the service could not be scanned, if it could not.
+  error: Option<String>,
+}
+
+/// The services scanned on a host.
+pub(super) struct Host {
+  name: String,
+  services: Vec<Service>,
+}
+
+impl Report {
+  /// Adds the audits of the service at `host` and `port`, or why it
+  /// could not be scanned, grouping them under the host.
+  pub fn push_service(&mut self, host: &str, port: u16, audits: Result<Vec<Audit>, Error>) {
+    let start = self.audits.len();
+    let error = match audits {
+      Ok(audits) => {
+        self.audits.extend(audits);
+        None
+      },
+      Err(err) => Some(err.to_string()),
+    };
+    let service = Service {
+      port,
+      audits: start..self.audits.len(),
+      error,
+    };
+    match self.hosts.iter_mut().find(|h| h.name == host) {
+      Some(h) => h.services.push(service),
+      None => self.hosts.push(Host {
+        name: host.to_string(),
+        services: vec![service],
+      }),
+    }
+  }
+
+  pub(super) fn is_grouped(&self) -> bool {
+    !self.hosts.is_empty()
+  }
+
+  fn service_passed(&self, service: &Service) -> bool {
+    service.error.is_none() && self.audits[service.audits.clone()].iter().all(|a| a.passed)
+  }
+
+  fn host_passed(&self, host: &Host) -> bool {
+    host
+      .services
+      .iter()
+      .all(|service| self.service_passed(service))
+  }
+
+  /// Returns whether a service could not be scanned.
+  pub(super) fn unreachable(&self) -> bool {
+    self
+      .hosts
+      .iter()
+      .flat_map(|host| host.services.iter())
+      .any(|service| service.error.is_some())
+  }
+
+  /// Returns the hosts with those that passed first, like the audits
+  /// of a flat report.
+  fn sorted_hosts(&self) -> Vec<&Host> {
+    let (mut v, failed): (Vec<_>, Vec<_>) =
+      self.hosts.iter().partition(|host| self.host_passed(host));
+    v.extend(failed);
+    v
+  }
+
+  /// Returns the hosts along with their services and audits. Passing
+  /// audits are left out unless `all` is set.
+  pub(super) fn hosts_value(&self, all: bool) -> Value {
+    let hosts: Vec<Value> = self
+      .sorted_hosts()
+      .into_iter()
+      .map(|host| {
+        let services: Vec<Value> = host
+          .services
+          .iter()
+          .map(|service| {
+            let audits: Vec<&Audit> = self.audits[service.audits.clone()]
+              .iter()
+              .filter(|audit| all || !audit.passed)
+              .collect();
+            let mut value = json!({
+              "port": service.port,
+              "passed": self.service_passed(service),
+              "report": audits,
+            });
+            if let Some(error) = &service.error {
+              value["error"] = json!(error);
+            }
+            value
+          })
+          .collect();
+        json!({
+          "host": host.name,
+          "passed": self.host_passed(host),
+          "services": services,
+        })
+      })
+      .collect();
+    json!(hosts)
+  }
+
+  /// Renders every host with its verdict, followed by its services and
+  /// the audits that failed, or every audit if the output is verbose.
+  pub(super) fn render_hosts(&self, color: bool) -> String {
+    let verdict = |passed: bool| {
+      if passed {
+        paint(OK, "ok:", color)
+      } else {
+        paint(FAIL, "fail:", color)
+      }
+    };
+    let mut s = String::new();
+    for host in self.sorted_hosts() {
+      s.push_str(&format!(
+        "{} {}\n",
+        verdict(self.host_passed(host)),
+        host.name
+      ));
+      for service in host.services.iter() {
+        let origin = if host.name.contains(':') {
+          format!("[{}]:{}", host.name, service.port)
+        } else {
+          format!("{}:{}", host.name, service.port)
+        };
+        if let Some(error) = &service.error {
+          s.push_str(&format!(
+            "  {} {}: {}\n",
+            paint(WARNING, "error:", color),
+            origin,
+            error
+          ));
+          continue;
+        }
+        s.push_str(&format!(
+          "  {} {}\n",
+          verdict(self.service_passed(service)),
+          origin
+        ));
+        for audit in self.audits[service.audits.clone()].iter() {
+          if !audit.passed || self.verbosity.is_verbose() {
+            for line in audit.render(color).lines() {
+              s.push_str(&format!("    {}\n", line));
+            }
+          }
+        }
+      }
+    }
+    let failed = self
+      .hosts
+      .iter()
+      .filter(|host| !self.host_passed(host))
+      .count();
+    s.push_str(&format!(
+      "\nhosts: {} checked, {} failed\n",
+      self.hosts.len(),
+      failed
+    ));
+    s
+  }
+}
@@ -0,0 +1,233 @@
+//! Expose the state of watched keys to Prometheus.
+//!
+//! While `watch` runs, the outcome of the latest assessment of every key
+//! is kept and served in the Prometheus text format on `/metrics` so
+//! that standard monitoring stacks can alert when keys drift out of
+//! compliance. Keys whose files are removed are dropped on the next
+//! scan.
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write as _;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use wardstone_core::standard::verdict::Severity;
+
+use super::{Audit, Report};
+
+const SEVERITIES: [Severity; 4] = [
+  Severity::Info,
+  Severity::Legacy,
+  Severity::Deprecated,
+  Severity::NonCompliant,
+];
+
+/// The latest assessment of a key.
+struct Status {
+  severity: Severity,
+  primitives: Vec<(String, Severity)>,
+  scanned: f64,
+}
+
+impl Status {
+  fn new(audit: &Audit, scanned: f64) -> Self {
+    let worst = audit.findings.iter().map(|finding| finding.severity).max();
+    let mut severity = worst.unwrap_or(Severity::Info);
+    // Properties such as the validity period fail an audit without a
+    // finding.
+    if !audit.passed && severity < Severity::Deprecated {
+      severity = Severity::NonCompliant;
+    }
+    let names = [
+      audit.got_hash_function.map(|hash| hash.to_string()),
+      audit.got_signature.map(|key| key.to_string()),
+      audit.got_issuer_key.map(|key| key.to_string()),
+      audit.got_key_exchange.map(|key| key.to_string()),
+      audit.got_cipher.map(|key| key.to_string()),
+    ];
+    let mut primitives: Vec<(String, Severity)> = Vec::new();
+    for name in names.into_iter().flatten() {
+      // The key of a self-signed certificate is also its issuer key.
+      if primitives.iter().any(|(n, _)| *n == name) {
+        continue;
+      }
+      let severity = audit
+        .findings
+        .iter()
+        .filter(|finding| finding.primitive == name)
+        .map(|finding| finding.severity)
+        .max()
+        .unwrap_or(Severity::Info);
+      primitives.push((name, severity));
+    }
+    Self {
+      severity,
+      primitives,
+      scanned,
+    }
+  }
+}
+
+#[derive(Default)]
+struct State {
+  keys: BTreeMap<PathBuf, Status>,
+  scans: u64,
+  last_scan: Option<f64>,
+}
+
+/// Serves metrics about the keys that are being watched.
+pub struct Metrics {
+  state: Arc<Mutex<State>>,
+}
+
+impl Metrics {
+  /// Listens on `addr` and serves the metrics from a background thread.
+  pub fn serve(addr: SocketAddr, guide: &'static str) -> io::Result<Self> {
+    let listener = TcpListener::bind(addr)?;
+    let state = Arc::new(Mutex::new(State::default()));
+    let shared = Arc::clone(&state);
+    thread::spawn(move || {
+      for stream in listener.incoming().flatten() {
+        // A client that misbehaves should not stop the others from
+        // being served.
+        let _ = respond(stream, guide, &shared);
+      }
+    });
+    Ok(Self { state })
+  }
+
+  /// Records the outcome of a scan. `files` are the files that are
+  /// still being watched, which may not all be part of the report since
+  /// only keys that changed are assessed again.
+  pub fn update<'a>(&self, report: &Report, files: impl Iterator<Item = &'a Path>) {
+    let now = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .map_or(0.0, |d| d.as_secs_f64());
+    let files: HashSet<_> = files.collect();
+    let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+    state.keys.retain(|path, _| files.contains(path.as_path()));
+    for audit in report.audits.iter() {
+      state
+        .keys
+        .insert(audit.path.clone(), Status::new(audit, now));
+    }
+    state.scans += 1;
+    state.last_scan = Some(now);
+  }
+}
+
+/// Returns the status of a key or primitive as used in labels.
+fn status(severity: Severity) -> String {
+  match severity {
+    Severity::Info => "compliant".to_string(),
+    severity => severity.to_string(),
+  }
+}
+
+fn escape(value: &str) -> String {
+  value
+    .replace('\\', "\\\\")
+    .replace('"', "\\\"")
+    .replace('\n', "\\n")
+}
+
+fn render(guide: &str, state: &State) -> String {
+  let guide = escape(guide);
+  let mut s = String::new();
+  s.push_str(
+    "# HELP wardstone_keys The number of keys by the status of their most severe finding.\n",
+  );
+  s.push_str("# TYPE wardstone_keys gauge\n");
+  for severity in SEVERITIES {
+    let n = state
+      .keys
+      .values()
+      .filter(|key| key.severity == severity)
+      .count();
+    let _ = writeln!(
+      s,
+      "wardstone_keys{{guide=\"{}\",status=\"{}\"}} {}",
+      guide,
+      status(severity),
+      n
+    );
+  }
+  let mut primitives: BTreeMap<(&str, Severity), usize> = BTreeMap::new();
+  for key in state.keys.values() {
+    for (name, severity) in key.primitives.iter() {
+      *primitives.entry((name, *severity)).or_default() += 1;
+    }
+  }
+  s.push_str("# HELP wardstone_primitives The number of keys using a primitive by its status.\n");
+  s.push_str("# TYPE wardstone_primitives gauge\n");
+  for ((name, severity), n) in primitives {
+    let _ = writeln!(
+      s,
+      "wardstone_primitives{{guide=\"{}\",primitive=\"{}\",status=\"{}\"}} {}",
+      guide,
+      escape(name),
+      status(severity),
+      n
+    );
+  }
+  s.push_str("# HELP wardstone_key_scan_timestamp_seconds When a key was last assessed.\n");
+  s.push_str("# TYPE wardstone_key_scan_timestamp_seconds gauge\n");
+  for (path, key) in state.keys.iter() {
+    let _ = writeln!(
+      s,
+      "wardstone_key_scan_timestamp_seconds{{path=\"{}\"}} {}",
+      escape(&path.display().to_string()),
+      key.scanned
+    );
+  }
+  if let Some(last_scan) = state.last_scan {
+    s.push_str(
+      "# HELP wardstone_last_scan_timestamp_seconds When the watched paths were last scanned.\n",
+    );
+    s.push_str("# TYPE wardstone_last_scan_timestamp_seconds gauge\n");
+    let _ = writeln!(s, "wardstone_last_scan_timestamp_seconds {}", last_scan);
+  }
+  s.push_str("# HELP wardstone_scans_total The number of scans of the watched paths.\n");
+  s.push_str("# TYPE wardstone_scans_total counter\n");
+  let _ = writeln!(s, "wardstone_scans_total {}", state.scans);
+  s
+}
+
+fn respond(stream: TcpStream, guide: &str, state: &Mutex<State>) -> io::Result<()> {
+  stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+  let mut reader = BufReader::new(&stream);
+  let mut request = String::new();
+  reader.read_line(&mut request)?;
+  // The headers are read so that the connection is not reset when it
+  // is closed with unread data.
+  let mut header = String::new();
+  while reader.read_line(&mut header)? > 2 {
+    header.clear();
+  }
+  let mut parts = request.split_whitespace();
+  let method = parts.next().unwrap_or_default();
+  let target = parts.next().unwrap_or_default();
+  let path = target.split('?').next().unwrap_or_default();
+  let (status, body) = match (method, path) {
+    ("GET", "/metrics") => {
+      let state = state.lock().unwrap_or_else(|err| err.into_inner());
+      ("200 OK", render(guide, &state))
+    },
+    ("GET", _) => ("404 Not Found", "Not found, see /metrics.\n".to_string()),
+    _ => (
+      "405 Method Not Allowed",
+      "Method not allowed.\n".to_string(),
+    ),
+  };
+  write!(
+    &stream,
+    "HTTP/1.1 {}\r\nContent-Type: text/plain; version=0.0.4; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+    status,
+    body.len(),
+    body
+  )?;
+  (&stream).flush()
+}
@@ -0,0 +1,111 @@
+//! Render the findings of a report as SARIF for code scanning.
+//!
+//! Every finding becomes a result of a single run in the Static Analysis
+//! Results Interchange Format 2.1.0, anchored at the file of its audit
+//! and, where it is known, the line and column it was found at, so that
+//! code scanning services such as that of GitHub annotate the offending
+//! line. Findings of settings that no rule of the guide covers are
+//! reported under the rule `SETTING`.
+//!
+//! ```text
+//! {
+//!   "version": "2.1.0",
+//!   "runs": [{
+//!     "tool": {"driver": {"name": "wardstone", "rules": [{"id": "NIST-HASH-001"}, ...]}},
+//!     "results": [{
+//!       "ruleId": "NIST-HASH-001",
+//!       "level": "error",
+//!       "message": {"text": "sha1 offers at most 80 bits of collision resistance, ..."},
+//!       "locations": [{"physicalLocation": {"artifactLocation": {"uri": "src/main.go"}, "region": {"startLine": 12}}}]
+//!     }]
+//!   }]
+//! }
+//! ```
+use std::path::Path;
+
+use serde_json::{json, Value};
+use wardstone_core::standard::verdict::Severity;
+
+use super::Report;
+
+/// The rule of findings that are not about a primitive, such as a
+/// deprecated version of TLS.
+const SETTING: &str = "SETTING";
+
+/// Returns the level of a result, of which only non-compliant findings
+/// are errors.
+fn level(severity: Severity) -> &'static str {
+  match severity {
+    Severity::NonCompliant => "error",
+    Severity::Deprecated => "warning",
+    Severity::Legacy | Severity::Info => "note",
+  }
+}
+
+/// Returns the path as a relative URI reference, with the separators
+/// and characters that URIs reserve escaped.
+fn uri(path: &Path) -> String {
+  let mut s = String::new();
+  for c in path.to_string_lossy().chars() {
+    match c {
+      '\\' => s.push('/'),
+      c if c.is_ascii_alphanumeric() || "/-._~:".contains(c) => s.push(c),
+      c => {
+        let mut buf = [0; 4];
+        for byte in c.encode_utf8(&mut buf).bytes() {
+          s.push_str(&format!("%{:02X}", byte));
+        }
+      },
+    }
+  }
+  s
+}
+
+impl Report {
+  /// Renders the findings of every audit as a SARIF log.
+  pub fn to_sarif_string(&self) -> String {
+    let mut rules: Vec<&str> = Vec::new();
+    let mut results = Vec::new();
+    for audit in self.audits.iter() {
+      for finding in audit.findings.iter() {
+        let rule = finding.rule_id.unwrap_or(SETTING);
+        if !rules.contains(&rule) {
+          rules.push(rule);
+        }
+        let mut location = json!({
+          "physicalLocation": {
+            "artifactLocation": { "uri": uri(&audit.path) },
+          },
+        });
+        if let Some(at) = finding.location {
+          location["physicalLocation"]["region"] = json!({
+            "startLine": at.line,
+            "startColumn": at.column,
+          });
+        }
+        results.push(json!({
+          "ruleId": rule,
+          "level": level(finding.severity),
+          "message": { "text": finding.message },
+          "locations": [location],
+        }));
+      }
+    }
+    let rules: Vec<Value> = rules.into_iter().map(|id| json!({ "id": id })).collect();
+    let log = json!({
+      "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+      "version": "2.1.0",
+      "runs": [{
+        "tool": {
+          "driver": {
+            "name": "wardstone",
+            "version": env!("CARGO_PKG_VERSION"),
+            "rules": rules,
+          },
+        },
+        "results": results,
+      }],
+    });
+    format!("{}\n", log)
+  }
+}
@@ -0,0 +1,191 @@
+//! Send the results of audits to the system log.
+//!
+//! This lets the findings of a long running `watch` be picked up by the
+//! same pipelines that collect the logs of other services. Each finding
+//! is logged as a separate entry whose priority follows its severity.
+//!
+//! The systemd journal receives the path, rule identifier and severity
+//! as fields of their own, `WARDSTONE_PATH`, `WARDSTONE_RULE_ID` and
+//! `WARDSTONE_SEVERITY`, so that entries can be filtered with
+//! `journalctl WARDSTONE_SEVERITY=non-compliant`. A syslog daemon
+//! receives them as part of the message.
+use std::io;
+use std::os::unix::net::UnixDatagram;
+use std::process;
+
+use clap::ValueEnum;
+use wardstone_core::standard::verdict::Severity;
+
+use super::{Audit, Report};
+
+const IDENTIFIER: &str = "wardstone";
+
+// Findings are reported by a monitoring service.
+const FACILITY: u8 = 3;
+
+/// The log to send the results of audits to.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum Destination {
+  /// The native protocol of the systemd journal.
+  Journal,
+  /// The local syslog daemon.
+  Syslog,
+}
+
+impl Destination {
+  fn socket(&self) -> &'static str {
+    match self {
+      Self::Journal => "/run/systemd/journal/socket",
+      Self::Syslog => "/dev/log",
+    }
+  }
+}
+
+/// The priority of a log entry as defined by RFC 5424.
+#[derive(Clone, Copy)]
+enum Priority {
+  Error = 3,
+  Warning = 4,
+  Notice = 5,
+  Info = 6,
+}
+
+impl From<Severity> for Priority {
+  fn from(severity: Severity) -> Self {
+    match severity {
+      Severity::Info => Self::Info,
+      Severity::Legacy => Self::Notice,
+      Severity::Deprecated => Self::Warning,
+      Severity::NonCompliant => Self::Error,
+    }
+  }
+}
+
+/// A single log entry about an audit.
+struct Entry {
+  priority: Priority,
+  message: String,
+  rule_id: Option<&'static str>,
+  severity: Option<Severity>,
+}
+
+/// Writes entries to the system log.
+pub struct Logger {
+  destination: Destination,
+  socket: UnixDatagram,
+}
+
+impl Logger {
+  /// Connects to the socket of the log.
+  pub fn connect(destination: Destination) -> io::Result<Self> {
+    let socket = UnixDatagram::unbound()?;
+    socket.connect(destination.socket())?;
+    Ok(Self {
+      destination,
+      socket,
+    })
+  }
+
+  /// Logs the outcome of every audit in the report along with its
+  /// findings and warnings.
+  pub fn log(&self, report: &Report) -> io::Result<()> {
+    for audit in report.audits.iter() {
+      for entry in entries(audit) {
+        let datagram = match self.destination {
+          Destination::Journal => journal(audit, &entry),
+          Destination::Syslog => syslog(&entry),
+        };
+        self.socket.send(&datagram)?;
+      }
+    }
+    Ok(())
+  }
+}
+
+fn entries(audit: &Audit) -> Vec<Entry> {
+  let path = audit.path.display().to_string();
+  let (priority, outcome) = if audit.passed {
+    (Priority::Info, "ok")
+  } else {
+    (Priority::Error, "fail")
+  };
+  let mut entries = vec![Entry {
+    priority,
+    message: format!("{}: {}", outcome, path),
+    rule_id: None,
+    severity: None,
+  }];
+  for finding in audit.findings.iter() {
+    entries.push(Entry {
+      priority: finding.severity.into(),
+      message: format!("{}: {}", path, finding),
+      rule_id: finding.rule_id,
+      severity: Some(finding.severity),
+    });
+  }
+  for warning in audit.warnings.iter() {
+    entries.push(Entry {
+      priority: Priority::Warning,
+      message: format!("{}: warning: {}", path, warning),
+      rule_id: None,
+      severity: None,
+    });
+  }
+  entries
+}
+
+/// Appends a field using the native protocol of the journal.
+fn field(datagram: &mut Vec<u8>, name: &str, value: &str) {
+  datagram.extend_from_slice(name.as_bytes());
+  // Values spanning multiple lines are prefixed with their length.
+  if value.contains('\n') {
+    datagram.push(b'\n');
+    datagram.extend_from_slice(&(value.len() as u64).to_le_bytes());
+  } else {
+    datagram.push(b'=');
+  }
+  datagram.extend_from_slice(value.as_bytes());
+  datagram.push(b'\n');
+}
+
+fn journal(audit: &Audit, entry: &Entry) -> Vec<u8> {
+  let mut datagram = Vec::new();
+  field(&mut datagram, "MESSAGE", &entry.message);
+  field(
+    &mut datagram,
+    "PRIORITY",
+    &(entry.priority as u8).to_string(),
+  );
+  field(&mut datagram, "SYSLOG_FACILITY", &FACILITY.to_string());
+  field(&mut datagram, "SYSLOG_IDENTIFIER", IDENTIFIER);
+  field(
+    &mut datagram,
+    "WARDSTONE_PATH",
+    &audit.path.display().to_string(),
+  );
+  field(
+    &mut datagram,
+    "WARDSTONE_PASSED",
+    if audit.passed { "1" } else { "0" },
+  );
+  if let Some(rule_id) = entry.rule_id {
+    field(&mut datagram, "WARDSTONE_RULE_ID", rule_id);
+  }
+  if let Some(severity) = entry.severity {
+    field(&mut datagram, "WARDSTONE_SEVERITY", &severity.to_string());
+  }
+  datagram
+}
+
+// The timestamp and host name are left for the daemon to fill in as is
+// customary for messages sent to the local socket.
+fn syslog(entry: &Entry) -> Vec<u8> {
+  format!(
+    "<{}>{}[{}]: {}",
+    FACILITY * 8 + entry.priority as u8,
+    IDENTIFIER,
+    process::id(),
+    entry.message.replace('\n', " ")
+  )
+  .into_bytes()
+}
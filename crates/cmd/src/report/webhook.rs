@@ -0,0 +1,167 @@
+//! Notify a webhook about findings that have not been seen before.
+//!
+//! While `watch` runs, the findings that fail the assessment of every
+//! key are remembered so that a notification is only sent when a key
+//! shows up with a new one, such as a freshly deployed certificate
+//! signed with SHA-1. Findings of the keys present when watching starts
+//! are all new.
+//!
+//! The payload is the JSON object below unless a template is used to
+//! render it, such as the built-in ones for Slack and Matrix.
+//!
+//! ```text
+//! {
+//!   "text": "1 new finding:\n...",
+//!   "findings": [{"path": ..., "rule_id": ..., "severity": ..., "message": ...}]
+//! }
+//! ```
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::PathBuf;
+
+use clap::ValueEnum;
+use serde_json::{json, Value};
+use wardstone_core::standard::verdict::Severity;
+
+use super::{Finding, Report};
+use crate::http::Endpoint;
+use crate::key::Error;
+use crate::template::Template;
+use crate::tls;
+
+/// The payload sent to the webhook.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum Payload {
+  /// The findings as a JSON object.
+  Json,
+  /// A message for a Slack incoming webhook.
+  Slack,
+  /// A message for a Matrix hookshot generic webhook.
+  Matrix,
+}
+
+impl Payload {
+  fn template(&self) -> Option<&'static str> {
+    match self {
+      Self::Json => None,
+      Self::Slack => Some("{\"text\": {{json text}}}"),
+      Self::Matrix => Some("{\"text\": {{json text}}, \"username\": \"wardstone\"}"),
+    }
+  }
+}
+
+/// Sends the payload to the endpoint.
+fn post(endpoint: &Endpoint, body: &str) -> Result<(), Error> {
+  let request = format!(
+    "POST {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: wardstone/{}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+    endpoint.target,
+    endpoint.authority(),
+    env!("CARGO_PKG_VERSION"),
+    body.len(),
+    body
+  );
+  let status = if endpoint.tls {
+    exchange(
+      tls::connect_verified(&endpoint.host, endpoint.port)?,
+      &request,
+    )
+  } else {
+    let socket = tls::connect(&endpoint.host, endpoint.port, tls::TIMEOUT)
+      .map_err(|err| Error::Webhook(err.to_string()))?;
+    exchange(socket, &request)
+  }?;
+  match status.split_whitespace().nth(1) {
+    Some(code) if code.starts_with('2') => Ok(()),
+    _ => Err(Error::Webhook(format!(
+      "{} responded with {}",
+      endpoint.host,
+      status.trim()
+    ))),
+  }
+}
+
+/// Sends the request and returns the status line of the response.
+fn exchange<S: Read + Write>(mut stream: S, request: &str) -> Result<String, Error> {
+  stream
+    .write_all(request.as_bytes())
+    .and_then(|_| stream.flush())
+    .map_err(|err| Error::Webhook(err.to_string()))?;
+  let mut status = String::new();
+  BufReader::new(stream)
+    .read_line(&mut status)
+    .map_err(|err| Error::Webhook(err.to_string()))?;
+  Ok(status)
+}
+
+/// Sends a notification whenever keys have findings that fail the
+/// assessment which have not been seen before.
+pub struct Webhook {
+  endpoint: Endpoint,
+  template: Option<Template>,
+  seen: HashMap<PathBuf, Vec<Finding>>,
+}
+
+impl Webhook {
+  /// Creates a webhook for `url` which renders the payload with
+  /// `template` if given or as `payload` otherwise.
+  pub fn new(url: &str, payload: Payload, template: Option<Template>) -> Result<Self, Error> {
+    let endpoint =
+      Endpoint::parse(url).map_err(|err| Error::Webhook(format!("{}: {}", url, err)))?;
+    let template = match template {
+      Some(template) => Some(template),
+      None => payload
+        .template()
+        .map(|source| Template::parse(source).expect("built-in template is valid")),
+    };
+    Ok(Self {
+      endpoint,
+      template,
+      seen: HashMap::new(),
+    })
+  }
+
+  /// Sends the findings of the report that were not present the last
+  /// time the same keys were audited, if any.
+  pub fn notify(&mut self, report: &Report) -> Result<(), Error> {
+    let mut fresh = Vec::new();
+    for audit in report.audits.iter() {
+      let failing: Vec<&Finding> = audit
+        .findings
+        .iter()
+        .filter(|finding| finding.severity >= Severity::Deprecated)
+        .collect();
+      let seen = self.seen.get(&audit.path);
+      for &finding in failing.iter() {
+        if !seen.is_some_and(|seen| seen.contains(finding)) {
+          fresh.push((audit.path.display().to_string(), finding));
+        }
+      }
+      let failing = failing.into_iter().cloned().collect();
+      self.seen.insert(audit.path.clone(), failing);
+    }
+    if fresh.is_empty() {
+      return Ok(());
+    }
+    let mut text = match fresh.len() {
+      1 => "1 new finding:".to_string(),
+      n => format!("{} new findings:", n),
+    };
+    for (path, finding) in fresh.iter() {
+      text.push_str(&format!("\n{}: {}", path, finding));
+    }
+    let findings: Vec<Value> = fresh
+      .iter()
+      .map(|(path, finding)| {
+        let mut value = json!(finding);
+        value["path"] = json!(path);
+        value
+      })
+      .collect();
+    let data = json!({ "text": text, "findings": findings });
+    let body = match &self.template {
+      Some(template) => template.render(&data),
+      None => data.to_string(),
+    };
+    post(&self.endpoint, &body)
+  }
+}
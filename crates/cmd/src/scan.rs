@@ -0,0 +1,123 @@
+//! Scan many servers at once.
+//!
+//! Most of the time spent scanning a server is spent waiting for it to
+//! respond, so several servers are scanned at the same time, each on a
+//! thread of its own. A server that does not respond within the timeout
+//! is given up on, after being retried if requested, so that a few
+//! unreachable hosts do not hold up the rest of the scan.
+//!
+//! Scans of production infrastructure should not look like an attack.
+//! Connections to the same host can be spread out to stay under a rate,
+//! and delayed at random so that they do not arrive in bursts that trip
+//! intrusion prevention systems.
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::key::Error;
+
+/// The time to wait before retrying a server, which doubles with every
+/// attempt.
+const BACKOFF: Duration = Duration::from_millis(500);
+
+/// How servers are scanned.
+#[derive(Clone, Copy, Debug)]
+pub struct Policy {
+  /// The maximum number of servers scanned at the same time.
+  pub concurrency: usize,
+  /// How long to wait for a server to accept a connection or respond.
+  pub timeout: Duration,
+  /// How many more times to try a server that could not be scanned.
+  pub retries: u32,
+  /// The minimum time between connections to the same host.
+  pub interval: Duration,
+  /// The longest random delay before a connection.
+  pub jitter: Duration,
+}
+
+/// When the next connection to every host may be made.
+type Slots<'a> = Mutex<HashMap<&'a str, Instant>>;
+
+impl Policy {
+  /// Applies `f` to every host and port, returning the outcomes in the
+  /// order of the targets. `f` is given the timeout to use.
+  pub fn scan<R, F>(&self, targets: &[(String, u16)], f: F) -> Vec<Result<R, Error>>
+  where
+    R: Send,
+    F: Fn(&str, u16, Duration) -> Result<R, Error> + Sync,
+  {
+    let next = AtomicUsize::new(0);
+    let slots = Slots::default();
+    let workers = self.concurrency.clamp(1, targets.len().max(1));
+    let mut outcomes: Vec<(usize, Result<R, Error>)> = thread::scope(|s| {
+      let handles: Vec<_> = (0..workers)
+        .map(|_| {
+          s.spawn(|| {
+            let mut outcomes = Vec::new();
+            loop {
+              let i = next.fetch_add(1, Ordering::Relaxed);
+              let Some((host, port)) = targets.get(i) else {
+                break;
+              };
+              outcomes.push((i, self.attempt(host, *port, &f, &slots)));
+            }
+            outcomes
+          })
+        })
+        .collect();
+      handles
+        .into_iter()
+        .flat_map(|handle| handle.join().expect("scanner thread panicked"))
+        .collect()
+    });
+    outcomes.sort_by_key(|&(i, _)| i);
+    outcomes.into_iter().map(|(_, outcome)| outcome).collect()
+  }
+
+  fn attempt<'a, R, F>(
+    &self,
+    host: &'a str,
+    port: u16,
+    f: &F,
+    slots: &Slots<'a>,
+  ) -> Result<R, Error>
+  where
+    F: Fn(&str, u16, Duration) -> Result<R, Error>,
+  {
+    let mut delay = BACKOFF;
+    self.pace(host, slots);
+    let mut outcome = f(host, port, self.timeout);
+    for _ in 0..self.retries {
+      if outcome.is_ok() {
+        break;
+      }
+      thread::sleep(delay);
+      delay *= 2;
+      self.pace(host, slots);
+      outcome = f(host, port, self.timeout);
+    }
+    outcome
+  }
+
+  /// Waits until a connection to `host` may be made.
+  fn pace<'a>(&self, host: &'a str, slots: &Slots<'a>) {
+    if !self.interval.is_zero() {
+      // Taking the next slot before waiting for it lets other threads
+      // line up behind it rather than all waking at the same time.
+      let now = Instant::now();
+      let slot = {
+        let mut slots = slots.lock().unwrap_or_else(|err| err.into_inner());
+        let slot = slots.get(host).map_or(now, |&slot| slot.max(now));
+        slots.insert(host, slot + self.interval);
+        slot
+      };
+      thread::sleep(slot - now);
+    }
+    if !self.jitter.is_zero() {
+      let nanos = fastrand::u64(..=self.jitter.as_nanos() as u64);
+      thread::sleep(Duration::from_nanos(nanos));
+    }
+  }
+}
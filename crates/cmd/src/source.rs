@@ -0,0 +1,496 @@
+//! Find how source code configures TLS clients and servers.
+//!
+//! The TLS settings of a program are often written in its source rather
+//! than in a configuration file, as the constants and strings its TLS
+//! library takes. They are found line by line in the idioms of:
+//!
+//! - Go, such as `MinVersion: tls.VersionTLS10`, `CipherSuites` of
+//!   `tls.TLS_*` constants and `InsecureSkipVerify: true`.
+//! - Python, such as `ssl.PROTOCOL_TLSv1`, `ssl.TLSVersion.TLSv1_1`,
+//!   `set_ciphers("...")` and `ssl.CERT_NONE`.
+//! - Java and Kotlin, such as `SSLContext.getInstance("TLSv1")` and the
+//!   names given to `setEnabledCipherSuites`.
+//! - Rust, such as `SslVersion::TLS1` of the openssl crate,
+//!   `Protocol::Tlsv10` of native-tls, `set_cipher_list("...")` and
+//!   `danger_accept_invalid_certs(true)`.
+//!
+//! This is a best-effort scan rather than a parse. Settings that are
+//! computed or read from elsewhere are not found, and lines that only
+//! mention a constant, such as in a comparison, are taken to set it.
+//! Lines that start with a comment are skipped.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use wardstone_core::advisory::Hazard;
+use wardstone_core::primitive::symmetric::{Mode, Symmetric};
+use wardstone_core::registry::{self, Builtin};
+use wardstone_core::standard::rule::Citation;
+
+use crate::assess::{advise_cipher, audit_audiences, weakest, Audiences, Settings};
+use crate::key::{Error, Location};
+use crate::report::{Audit, Report};
+
+/// The languages that are scanned, by the extension of their files.
+const EXTENSIONS: [&str; 5] = ["go", "py", "java", "kt", "rs"];
+
+/// The directories that hold build output or downloaded dependencies
+/// rather than the code of the project.
+const SKIPPED: [&str; 3] = ["target", "node_modules", "__pycache__"];
+
+/// The constants of deprecated versions of SSL and TLS in every language
+/// along with the version they stand for.
+const VERSIONS: [(&str, &str); 19] = [
+  // Go
+  ("VersionSSL30", "SSL 3.0"),
+  ("VersionTLS10", "TLS 1.0"),
+  ("VersionTLS11", "TLS 1.1"),
+  // Python
+  ("PROTOCOL_SSLv2", "SSL 2.0"),
+  ("PROTOCOL_SSLv3", "SSL 3.0"),
+  ("PROTOCOL_TLSv1", "TLS 1.0"),
+  ("PROTOCOL_TLSv1_1", "TLS 1.1"),
+  ("TLSVersion.SSLv3", "SSL 3.0"),
+  ("TLSVersion.TLSv1", "TLS 1.0"),
+  ("TLSVersion.TLSv1_1", "TLS 1.1"),
+  // Java and Kotlin
+  ("\"SSLv3\"", "SSL 3.0"),
+  ("\"TLSv1\"", "TLS 1.0"),
+  ("\"TLSv1.1\"", "TLS 1.1"),
+  // Rust
+  ("SslVersion::SSL3", "SSL 3.0"),
+  ("SslVersion::TLS1", "TLS 1.0"),
+  ("SslVersion::TLS1_1", "TLS 1.1"),
+  ("Protocol::Sslv3", "SSL 3.0"),
+  ("Protocol::Tlsv10", "TLS 1.0"),
+  ("Protocol::Tlsv11", "TLS 1.1"),
+];
+
+/// The idioms that turn off verifying the certificate of the peer.
+const UNVERIFIED: [&str; 5] = [
+  "InsecureSkipVerify: true",
+  "CERT_NONE",
+  "_create_unverified_context",
+  "danger_accept_invalid_certs(true)",
+  "SslVerifyMode::NONE",
+];
+
+/// The calls that take a cipher list in the syntax of OpenSSL.
+const CIPHER_LISTS: [&str; 3] = ["set_ciphers(", "set_cipher_list(", "ciphers="];
+
+/// A setting that is unsafe whatever the guide.
+pub struct Issue {
+  pub location: Location,
+  /// The idiom at fault, such as `tls.VersionTLS10`.
+  pub setting: String,
+  pub message: String,
+  pub citation: Option<Citation>,
+}
+
+/// A cipher suite that is enabled.
+pub struct Suite {
+  pub location: Location,
+  /// The name of the suite in the IANA TLS Cipher Suites registry, such
+  /// as `TLS_RSA_WITH_AES_128_CBC_SHA`, or in the cipher lists of
+  /// OpenSSL, such as `AES128-SHA`.
+  pub name: String,
+  /// The cipher of the suite, or `None` if it is not assessed by any
+  /// guide, such as ChaCha20.
+  pub cipher: Option<Symmetric>,
+}
+
+/// The TLS settings found in a source file.
+pub struct Code {
+  pub path: PathBuf,
+  pub suites: Vec<Suite>,
+  pub issues: Vec<Issue>,
+}
+
+impl Code {
+  fn issue(
+    &mut self,
+    location: Location,
+    setting: &str,
+    message: String,
+    citation: Option<Citation>,
+  ) {
+    self.issues.push(Issue {
+      location,
+      setting: setting.to_string(),
+      message,
+      citation,
+    });
+  }
+}
+
+/// Returns the TLS settings of the source file at `path`, or of every
+/// source file below it if it is a directory. Files in which none are
+/// found are left out.
+pub fn scan(path: &Path) -> Result<Vec<Code>, Error> {
+  let files = if path.is_dir() {
+    walk(path)
+  } else if is_source(path) {
+    vec![path.to_path_buf()]
+  } else {
+    return Err(Error::Source(format!(
+      "expected a file ending in .{}",
+      EXTENSIONS.join(", .")
+    )));
+  };
+  let mut found = Vec::new();
+  for file in files {
+    let text = String::from_utf8_lossy(&fs::read(&file)?).into_owned();
+    let code = read(&file, &text);
+    if !code.suites.is_empty() || !code.issues.is_empty() {
+      found.push(code);
+    }
+  }
+  Ok(found)
+}
+
+fn is_source(path: &Path) -> bool {
+  path
+    .extension()
+    .and_then(|extension| extension.to_str())
+    .is_some_and(|extension| EXTENSIONS.contains(&extension))
+}
+
+/// Returns the source files in a directory and those below it in order,
+/// leaving out hidden directories and those of build output.
+fn walk(dir: &Path) -> Vec<PathBuf> {
+  let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+    .into_iter()
+    .flatten()
+    .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+    .collect();
+  paths.sort();
+  let mut files = Vec::new();
+  for path in paths {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    if path.is_dir() {
+      if !name.starts_with('.') && !SKIPPED.contains(&name.as_ref()) {
+        files.extend(walk(&path));
+      }
+    } else if is_source(&path) {
+      files.push(path);
+    }
+  }
+  files
+}
+
+/// Returns whether `c` can be part of an identifier.
+fn is_ident(c: char) -> bool {
+  c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// Returns the byte offsets in `line` at which `needle` occurs as a whole
+/// identifier, or as a whole string if it is quoted.
+fn occurrences<'a>(line: &'a str, needle: &'a str) -> impl Iterator<Item = usize> + 'a {
+  line.match_indices(needle).filter_map(move |(at, _)| {
+    let before = line[..at].chars().next_back();
+    let after = line[at + needle.len()..].chars().next();
+    let starts = needle.starts_with(|c: char| !is_ident(c)) || !before.is_some_and(is_ident);
+    let ends = needle.ends_with(|c: char| !is_ident(c)) || !after.is_some_and(is_ident);
+    (starts && ends).then_some(at)
+  })
+}
+
+/// Reads the TLS settings of a source file line by line.
+fn read(path: &Path, text: &str) -> Code {
+  let mut code = Code {
+    path: path.to_path_buf(),
+    suites: Vec::new(),
+    issues: Vec::new(),
+  };
+  for (i, line) in text.lines().enumerate() {
+    let trimmed = line.trim_start();
+    if ["//", "#", "/*", "*"]
+      .iter()
+      .any(|comment| trimmed.starts_with(comment))
+    {
+      continue;
+    }
+    let location = |at: usize| Location {
+      line: i + 1,
+      column: at + 1,
+    };
+    for (needle, version) in VERSIONS {
+      for at in occurrences(line, needle) {
+        let (message, citation) = deprecated(version);
+        code.issue(
+          location(at),
+          needle.trim_matches('"'),
+          message,
+          Some(citation),
+        );
+      }
+    }
+    for needle in UNVERIFIED {
+      for at in occurrences(line, needle) {
+        code.issue(
+          location(at),
+          needle,
+          "the certificate of the peer is not verified, so anyone on the path can impersonate it"
+            .to_string(),
+          None,
+        );
+      }
+    }
+    if let Some(at) = line.find("check_hostname") {
+      if line[at..].contains("False") {
+        code.issue(
+          location(at),
+          "check_hostname = False",
+          "the name in the certificate of the server is not checked, so any certificate the CAs vouch for is accepted"
+            .to_string(),
+          None,
+        );
+      }
+    }
+    // Cipher suites are named by constants in Go and Rust, and by strings
+    // in Java, Kotlin and Python.
+    let mut at = 0;
+    for word in line.split(|c: char| !is_ident(c)) {
+      if is_suite(word) {
+        let name = word
+          .strip_prefix("SSL_")
+          .map_or(word.to_string(), |rest| format!("TLS_{}", rest));
+        let name = name.replacen("TLS13_", "TLS_", 1);
+        suite(&mut code, location(at), &name, iana(&name));
+      }
+      at += word.len() + 1;
+    }
+    for call in CIPHER_LISTS {
+      if let Some(start) = line.find(call) {
+        let Some((offset, list)) = quoted(&line[start..]) else {
+          continue;
+        };
+        let at = start + offset;
+        for name in list.split([':', ',', ' ']) {
+          openssl(&mut code, location(at), name);
+        }
+      }
+    }
+  }
+  code
+}
+
+/// Returns whether `word` names a cipher suite as the IANA registry does,
+/// or as Java did for the suites of SSL 3.0.
+fn is_suite(word: &str) -> bool {
+  let upper = word.to_ascii_uppercase();
+  word.replace("_anon_", "_ANON_") == upper
+    && (upper.starts_with("TLS_") || upper.starts_with("SSL_") || upper.starts_with("TLS13_"))
+    && (upper.contains("_WITH_")
+      || ["TLS_AES_", "TLS_CHACHA20_", "TLS13_AES_", "TLS13_CHACHA20_"]
+        .iter()
+        .any(|prefix| upper.starts_with(prefix)))
+}
+
+/// Returns the offset of the first string literal in `s` along with its
+/// contents.
+fn quoted(s: &str) -> Option<(usize, &str)> {
+  let start = s.find(['"', '\''])?;
+  let quote = s[start..].chars().next()?;
+  let end = s[start + 1..].find(quote)?;
+  Some((start + 1, &s[start + 1..start + 1 + end]))
+}
+
+/// Returns why a version of SSL or TLS must not be used, along with the
+/// RFC that deprecates it.
+fn deprecated(version: &str) -> (String, Citation) {
+  let (document, section, url) = match version {
+    "SSL 2.0" => (
+      "RFC 6176",
+      "§2",
+      "https://datatracker.ietf.org/doc/html/rfc6176#section-2",
+    ),
+    "SSL 3.0" => (
+      "RFC 7568",
+      "§2",
+      "https://datatracker.ietf.org/doc/html/rfc7568#section-2",
+    ),
+    "TLS 1.0" => (
+      "RFC 8996",
+      "§4",
+      "https://datatracker.ietf.org/doc/html/rfc8996#section-4",
+    ),
+    _ => (
+      "RFC 8996",
+      "§5",
+      "https://datatracker.ietf.org/doc/html/rfc8996#section-5",
+    ),
+  };
+  let message = match version {
+    "SSL 2.0" | "SSL 3.0" => format!("{} is enabled, which is prohibited", version),
+    _ => format!("{} is enabled, which is deprecated", version),
+  };
+  let citation = Citation {
+    document,
+    section,
+    url: Some(url),
+  };
+  (message, citation)
+}
+
+/// Records a cipher suite, along with an issue if it encrypts with RC4,
+/// does not encrypt at all, is export grade or leaves the server
+/// unauthenticated.
+fn suite(code: &mut Code, location: Location, name: &str, cipher: Option<Symmetric>) {
+  let upper = name.to_ascii_uppercase();
+  let issue = if upper.contains("RC4") {
+    Some((
+      "RC4 is prohibited in TLS",
+      Some(Citation {
+        document: "RFC 7465",
+        section: "§2",
+        url: Some("https://datatracker.ietf.org/doc/html/rfc7465#section-2"),
+      }),
+    ))
+  } else if upper.contains("NULL") && !upper.contains("ANULL") {
+    Some(("the suite does not encrypt", None))
+  } else if upper.contains("EXPORT") || upper.starts_with("EXP-") || upper.starts_with("EXP1024-") {
+    Some(("export grade suites have keys of at most 56 bits", None))
+  } else if upper.contains("_ANON_")
+    || upper.contains("ANULL")
+    || upper.starts_with("ADH-")
+    || upper.starts_with("AECDH-")
+  {
+    Some(("anonymous suites do not authenticate the server", None))
+  } else {
+    None
+  };
+  if let Some((message, citation)) = issue {
+    code.issue(location, name, message.to_string(), citation);
+  }
+  code.suites.push(Suite {
+    location,
+    name: name.to_string(),
+    cipher,
+  });
+}
+
+fn symmetric(name: &str) -> Option<Symmetric> {
+  match registry::find(name) {
+    Some(Builtin::Symmetric(entry)) => Some(entry.primitive),
+    _ => None,
+  }
+}
+
+/// Returns the cipher of a suite named as the IANA registry does, such
+/// as `TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256`.
+fn iana(name: &str) -> Option<Symmetric> {
+  let rest = name
+    .split_once("_WITH_")
+    .map_or(&name[4..], |(_, rest)| rest);
+  let mut parts = rest.split('_');
+  match (parts.next()?, parts.next()) {
+    ("3DES", _) => symmetric("3des"),
+    ("DES", _) => symmetric("des"),
+    ("IDEA", _) => symmetric("idea"),
+    (cipher @ ("AES" | "CAMELLIA" | "ARIA"), Some(bits)) => {
+      symmetric(&format!("{}{}", cipher, bits))
+    },
+    _ => None,
+  }
+}
+
+/// Records the cipher suites of an element of a cipher list in the
+/// syntax of OpenSSL, such as `ECDHE-RSA-AES128-GCM-SHA256`. Elements
+/// that are removed or excluded are left out, as are keywords that stand
+/// for many suites, save those of suites that are unsafe.
+fn openssl(code: &mut Code, location: Location, element: &str) {
+  let element = element.trim();
+  if element.is_empty() || element.starts_with(['!', '-', '+', '@']) {
+    return;
+  }
+  let upper = element.to_ascii_uppercase();
+  match upper.as_str() {
+    "RC4" | "NULL" | "ENULL" | "ANULL" | "EXP" | "EXPORT" | "ADH" | "AECDH" => {
+      suite(code, location, element, None);
+      return;
+    },
+    _ => {},
+  }
+  let parts: Vec<_> = upper.split('-').collect();
+  if parts.len() < 2 {
+    return;
+  }
+  let cipher = parts.iter().enumerate().find_map(|(i, part)| match *part {
+    "DES" if parts.get(i + 1) == Some(&"CBC3") => symmetric("3des"),
+    "DES" => symmetric("des"),
+    "IDEA" => symmetric("idea"),
+    part => ["AES", "CAMELLIA", "ARIA"].iter().find_map(|cipher| {
+      let bits = part.strip_prefix(cipher)?;
+      bits.parse::<u16>().ok().and_then(|_| symmetric(part))
+    }),
+  });
+  suite(code, location, element, cipher);
+}
+
+/// Audits the TLS settings of every source file under `paths` for
+/// every audience. A path that cannot be read is skipped.
+pub fn assess(audiences: &Audiences, paths: &[PathBuf], report: &mut Report) {
+  for path in paths {
+    let found = match scan(path) {
+      Ok(found) => found,
+      Err(err) => {
+        report.skip(err.in_file(path));
+        continue;
+      },
+    };
+    for code in &found {
+      for audit in audit_audiences(audiences, |settings| audit(settings, code)) {
+        report.push(audit);
+      }
+    }
+  }
+}
+
+/// Audits the TLS settings of a source file, whose findings carry the
+/// line they were found at. Every cipher is explained while the one
+/// reported is the weakest that fails, or else the weakest. Settings
+/// that are unsafe whatever the guide fail.
+pub fn audit(settings: Settings, code: &Code) -> Audit {
+  let Settings {
+    ctx,
+    guide,
+    locale,
+    hazards,
+    ..
+  } = settings;
+  let hazards = hazards.unwrap_or(guide.hazards());
+  let mut audit = Audit::of_settings(&code.path, guide.name());
+  let mut ciphers = Vec::new();
+  for suite in &code.suites {
+    audit.locate(suite.location);
+    if let Some(got) = suite.cipher {
+      let verdict = guide.assess_symmetric(ctx, got);
+      audit.cite(verdict.citation);
+      audit.explain(&verdict, ctx, got, locale);
+      ciphers.push((got, verdict));
+      // Every cipher suite with 64-bit blocks uses them in CBC mode.
+      advise_cipher(settings, got, Mode::Cbc, &mut audit);
+    }
+    for hazard in Hazard::of_tls_cipher_suite(&suite.name) {
+      if hazards.contains(hazard) {
+        audit.advise(hazard.advisory(), &suite.name);
+      }
+    }
+  }
+  ciphers.sort_by_key(|(got, _)| got.security);
+  ciphers.dedup_by_key(|(got, _)| *got);
+  match weakest(&ciphers) {
+    Some((got, verdict)) if verdict.compliant => audit.compliant_cipher(*got, verdict.alternative),
+    Some((got, verdict)) => audit.noncompliant_cipher(*got, verdict.alternative),
+    None => {},
+  }
+  for issue in &code.issues {
+    audit.locate(issue.location);
+    audit.cite(issue.citation);
+    audit.noncompliant_setting(
+      &format!("{} on line {}", issue.setting, issue.location.line),
+      issue.message.clone(),
+    );
+  }
+  audit
+}
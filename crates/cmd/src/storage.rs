@@ -0,0 +1,775 @@
+//! Read how encrypted volumes and databases protect the data they store.
+//!
+//! Data at rest is encrypted with a cipher under a volume or master key,
+//! which is in turn kept under a key derived from a passphrase or held by
+//! a key management service. The settings are read from:
+//!
+//! - LUKS1 and LUKS2 headers, either on the device or as a header backup
+//!   made with `cryptsetup luksHeaderBackup`, which give the cipher of
+//!   the volume and the key derivation of every key slot.
+//! - MySQL and MariaDB option files such as `my.cnf`, following the
+//!   files they include, for InnoDB encryption.
+//! - MongoDB `mongod.conf`, for the encrypted storage engine.
+//! - Oracle initialisation parameter files such as `init.ora`, for
+//!   transparent data encryption of tablespaces.
+//!
+//! The settings are read into an [`Encryption`], whose ciphers are left
+//! to the caller to assess against the guide while key derivations that
+//! cost too little and settings that leave data unencrypted are returned
+//! as [`Issue`]s.
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+use wardstone_core::primitive::hash::Hash;
+use wardstone_core::primitive::kdf::{Function, Kdf};
+use wardstone_core::primitive::symmetric::{Mode, Symmetric, AES128, AES256, TDEA3};
+use wardstone_core::standard::rule::Citation;
+
+use crate::assess::{advise_cipher, audit_audiences, Audiences, Settings};
+use crate::key::Error;
+use crate::report::{Audit, Report};
+
+/// The magic number that both versions of the LUKS header start with.
+const LUKS_MAGIC: &[u8] = b"LUKS\xba\xbe";
+
+/// The marker of a LUKS1 key slot that holds a key.
+const LUKS1_ACTIVE: u32 = 0x00ac_71f3;
+
+/// The largest LUKS2 header that is read, which is the largest size of
+/// its metadata area that cryptsetup allows.
+const LUKS2_MAX_HEADER: u64 = 4 << 20;
+
+/// The deepest `!include` directives of option files are followed, which
+/// stops files that include one another.
+const MAX_DEPTH: usize = 8;
+
+/// A setting that leaves data unencrypted or protected by too little.
+pub struct Issue {
+  /// The setting at fault, such as `keyslot 0` or `innodb_encrypt_tables`.
+  pub setting: String,
+  pub message: String,
+  pub citation: Option<Citation>,
+}
+
+/// The encryption of a volume or database.
+pub struct Encryption {
+  /// The file along with what it was read as, such as `disk.img:luks2`.
+  pub label: PathBuf,
+  /// The ciphers along with what they encrypt, such as `segment 0`, and
+  /// their mode if it is known.
+  pub ciphers: Vec<(String, Symmetric, Option<Mode>)>,
+  /// The key derivations along with the key slot they unlock.
+  pub kdfs: Vec<(String, Kdf)>,
+  pub issues: Vec<Issue>,
+  pub warnings: Vec<String>,
+}
+
+impl Encryption {
+  fn new(path: &Path, kind: &str) -> Self {
+    Self {
+      label: format!("{}:{}", path.display(), kind).into(),
+      ciphers: Vec::new(),
+      kdfs: Vec::new(),
+      issues: Vec::new(),
+      warnings: Vec::new(),
+    }
+  }
+
+  fn issue(&mut self, setting: &str, message: &str, citation: Option<Citation>) {
+    self.issues.push(Issue {
+      setting: setting.to_string(),
+      message: message.to_string(),
+      citation,
+    });
+  }
+
+  /// Records the key derivation of a key slot, along with an issue if it
+  /// costs less than recommended.
+  fn kdf(&mut self, slot: String, kdf: Kdf) {
+    if !kdf.is_costly_enough() {
+      self.issues.push(Issue {
+        setting: slot.clone(),
+        message: format!(
+          "{} costs less to guess passphrases against than {}",
+          kdf,
+          kdf.minimum()
+        ),
+        citation: Some(kdf.citation()),
+      });
+    }
+    if kdf.function == Function::Argon2i {
+      self.warnings.push(format!(
+        "{} derives its key with Argon2i, which RFC 9106 recommends Argon2id over",
+        slot
+      ));
+    }
+    self.kdfs.push((slot, kdf));
+  }
+}
+
+/// Returns the encryption settings of the LUKS header or database
+/// configuration at `path`, telling which it is by its contents.
+pub fn read(path: &Path) -> Result<Encryption, Error> {
+  // Devices have no length, so only the header is read from them.
+  let mut file = File::open(path)?;
+  let mut start = Vec::new();
+  file.by_ref().take(4096).read_to_end(&mut start)?;
+  if start.starts_with(LUKS_MAGIC) {
+    return match start.get(6..8) {
+      Some([0, 1]) => luks1(path, &start),
+      Some([0, 2]) => luks2(path, &start, &mut file),
+      _ => Err(Error::Storage("unsupported LUKS version".to_string())),
+    };
+  }
+  let text = fs::read_to_string(path)?;
+  let lower = text.to_ascii_lowercase();
+  let oracle = path.extension().is_some_and(|extension| extension == "ora")
+    || [
+      "encrypt_new_tablespaces",
+      "tablespace_encryption_default_algorithm",
+      "tde_configuration",
+      "wallet_root",
+    ]
+    .iter()
+    .any(|name| lower.contains(name));
+  if oracle {
+    return Ok(oracle_settings(path, &text));
+  }
+  let yaml = text.lines().any(|line| {
+    let line = line.trim_end();
+    matches!(line, "security:" | "storage:")
+  });
+  if yaml {
+    return Ok(mongodb(path, &text));
+  }
+  let options = text.lines().map(str::trim).any(|line| {
+    line.starts_with("[mysqld")
+      || line.starts_with("[mariadb")
+      || line == "[server]"
+      || line.starts_with("!include")
+  });
+  if options {
+    return mysql(path);
+  }
+  Err(Error::Storage(
+    "not a LUKS header or a MySQL, MariaDB, MongoDB or Oracle configuration".to_string(),
+  ))
+}
+
+/// Returns the NUL-padded string of a header field.
+fn field(header: &[u8], at: usize, len: usize) -> Result<String, Error> {
+  let bytes = header
+    .get(at..at + len)
+    .ok_or_else(|| Error::Storage("truncated header".to_string()))?;
+  let end = bytes.iter().position(|&b| b == 0).unwrap_or(len);
+  Ok(String::from_utf8_lossy(&bytes[..end]).into_owned())
+}
+
+/// Returns the big-endian integer of a header field.
+fn be32(header: &[u8], at: usize) -> Result<u32, Error> {
+  header
+    .get(at..at + 4)
+    .map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    .ok_or_else(|| Error::Storage("truncated header".to_string()))
+}
+
+/// Reads a LUKS1 header, whose eight key slots follow the fields of the
+/// volume at offset 208, 48 bytes each.
+fn luks1(path: &Path, header: &[u8]) -> Result<Encryption, Error> {
+  let mut encryption = Encryption::new(path, "luks1");
+  let name = field(header, 8, 32)?;
+  let mode = field(header, 40, 32)?;
+  let hash = field(header, 72, 32)?;
+  let key_bytes = be32(header, 108)?;
+  cipher(&mut encryption, "segment 0", &name, &mode, key_bytes);
+  let hash: Hash = hash
+    .parse()
+    .map_err(|_| Error::Storage(format!("unknown hash function {}", hash)))?;
+  for slot in 0..8 {
+    let at = 208 + slot * 48;
+    if be32(header, at)? == LUKS1_ACTIVE {
+      let iterations = be32(header, at + 4)?;
+      encryption.kdf(format!("keyslot {}", slot), Kdf::pbkdf2(hash, iterations));
+    }
+  }
+  encryption.warnings.push(
+    "LUKS1 only derives keys with PBKDF2, while LUKS2 also makes guessing passphrases cost memory with Argon2id (cryptsetup convert --type luks2)"
+      .to_string(),
+  );
+  Ok(encryption)
+}
+
+/// Reads a LUKS2 header, whose metadata is JSON that follows the binary
+/// header at offset 4096 and ends where the header does.
+fn luks2(path: &Path, start: &[u8], file: &mut File) -> Result<Encryption, Error> {
+  let size = start
+    .get(8..16)
+    .map(|bytes| u64::from_be_bytes(bytes.try_into().expect("eight bytes")))
+    .ok_or_else(|| Error::Storage("truncated header".to_string()))?;
+  if !(4096..=LUKS2_MAX_HEADER).contains(&size) {
+    return Err(Error::Storage(format!("implausible header size {}", size)));
+  }
+  let mut json = Vec::new();
+  file.take(size - 4096).read_to_end(&mut json)?;
+  let end = json.iter().position(|&b| b == 0).unwrap_or(json.len());
+  let metadata: Value = serde_json::from_slice(&json[..end])
+    .map_err(|err| Error::Storage(format!("invalid metadata: {}", err)))?;
+  let mut encryption = Encryption::new(path, "luks2");
+  let entries = |name: &str| {
+    let mut entries: Vec<(String, Value)> = metadata[name]
+      .as_object()
+      .map(|map| map.clone().into_iter().collect())
+      .unwrap_or_default();
+    entries.sort_by_key(|(id, _)| id.parse::<u32>().unwrap_or(u32::MAX));
+    entries
+  };
+  let keyslots = entries("keyslots");
+  // Every key slot holds the same volume key.
+  let volume_key = keyslots
+    .iter()
+    .find_map(|(_, keyslot)| keyslot["key_size"].as_u64());
+  for (id, segment) in entries("segments") {
+    let Some(spec) = segment["encryption"].as_str() else {
+      continue;
+    };
+    match volume_key {
+      Some(bytes) => spec_cipher(
+        &mut encryption,
+        &format!("segment {}", id),
+        spec,
+        bytes as u32,
+      ),
+      None => encryption.warnings.push(format!(
+        "segment {} is encrypted with {}, whose key size is not known without a key slot",
+        id, spec
+      )),
+    }
+  }
+  for (id, keyslot) in keyslots {
+    let slot = format!("keyslot {}", id);
+    let area = &keyslot["area"];
+    if let (Some(spec), Some(bytes)) = (area["encryption"].as_str(), area["key_size"].as_u64()) {
+      spec_cipher(
+        &mut encryption,
+        &format!("{} area", slot),
+        spec,
+        bytes as u32,
+      );
+    }
+    let kdf = &keyslot["kdf"];
+    let number = |name: &str| kdf[name].as_u64().unwrap_or(0).min(u32::MAX as u64) as u32;
+    let kdf = match kdf["type"].as_str() {
+      Some("pbkdf2") => {
+        let hash = kdf["hash"].as_str().unwrap_or("sha256");
+        match hash.parse() {
+          Ok(hash) => Kdf::pbkdf2(hash, number("iterations")),
+          Err(_) => {
+            encryption.warnings.push(format!(
+              "{} hashes with {}, which is not assessed",
+              slot, hash
+            ));
+            continue;
+          },
+        }
+      },
+      Some("argon2i") => Kdf::argon2(
+        Function::Argon2i,
+        number("time"),
+        number("memory"),
+        number("cpus"),
+      ),
+      Some("argon2id") => Kdf::argon2(
+        Function::Argon2id,
+        number("time"),
+        number("memory"),
+        number("cpus"),
+      ),
+      other => {
+        encryption.warnings.push(format!(
+          "{} derives its key with {}, which is not assessed",
+          slot,
+          other.unwrap_or("an unknown function")
+        ));
+        continue;
+      },
+    };
+    encryption.kdf(slot, kdf);
+  }
+  Ok(encryption)
+}
+
+/// Records the cipher of a LUKS2 specification such as `aes-xts-plain64`
+/// or, in the notation of the kernel, `capi:xts(aes)-plain64`.
+fn spec_cipher(encryption: &mut Encryption, what: &str, spec: &str, key_bytes: u32) {
+  let (name, mode) = match spec.strip_prefix("capi:") {
+    Some(capi) => {
+      let (mode, rest) = capi.split_once('(').unwrap_or((capi, ""));
+      let (name, iv) = rest.split_once(')').unwrap_or((rest, ""));
+      (name.to_string(), format!("{}{}", mode, iv))
+    },
+    None => match spec.split_once('-') {
+      Some((name, mode)) => (name.to_string(), mode.to_string()),
+      None => (spec.to_string(), String::new()),
+    },
+  };
+  cipher(encryption, what, &name, &mode, key_bytes);
+}
+
+/// Records the cipher of a volume along with the issues of its mode. XTS
+/// splits its key into two keys of half the size, of which only one
+/// encrypts.
+fn cipher(encryption: &mut Encryption, what: &str, name: &str, mode: &str, key_bytes: u32) {
+  let mut bits = key_bytes * 8;
+  if mode.starts_with("xts") {
+    bits /= 2;
+  }
+  let cipher = match name {
+    "des3_ede" => Some(TDEA3),
+    name => format!("{}{}", name, bits).parse().ok(),
+  };
+  // The mode comes before the IV generator, as in `cbc-essiv:sha256`.
+  let known = mode
+    .split(['-', ':'])
+    .next()
+    .and_then(|mode| mode.parse().ok());
+  match cipher {
+    Some(cipher) => encryption.ciphers.push((what.to_string(), cipher, known)),
+    None => encryption.warnings.push(format!(
+      "{} is encrypted with {} of {} bits, which is not assessed",
+      what, name, bits
+    )),
+  }
+  let citation = Some(Citation {
+    document: "cryptsetup FAQ",
+    section: "5. Security Aspects",
+    url: Some("https://gitlab.com/cryptsetup/cryptsetup/-/wikis/FrequentlyAskedQuestions"),
+  });
+  if mode == "ecb" {
+    encryption.issue(
+      what,
+      "every sector encrypts the same plaintext to the same ciphertext (ECB)",
+      citation,
+    );
+  } else if mode == "cbc-plain" || mode == "cbc-plain64" {
+    encryption.issue(
+      what,
+      "the IVs are predictable, which lets sectors be watermarked (CBC with plain IVs)",
+      citation,
+    );
+  }
+}
+
+/// Returns whether a setting of a database turns something on.
+fn on(value: Option<&str>) -> bool {
+  value.is_some_and(|value| {
+    matches!(
+      value.to_ascii_lowercase().as_str(),
+      "1" | "on" | "true" | "yes" | "force"
+    )
+  })
+}
+
+/// Removes the quotes around a string value.
+fn unquote(value: &str) -> &str {
+  ['"', '\'']
+    .into_iter()
+    .find_map(|quote| value.strip_prefix(quote)?.strip_suffix(quote))
+    .unwrap_or(value)
+}
+
+/// Reads the settings of the server from a MySQL or MariaDB option file,
+/// following `!include` and `!includedir`. Names are read with `-` as
+/// `_` and without the `loose_` prefix, and options without a value are
+/// on.
+fn options(path: &Path, settings: &mut Vec<(String, String)>, depth: usize) -> Result<(), Error> {
+  if depth > MAX_DEPTH {
+    return Err(Error::Storage(format!(
+      "{} is included more than {} files deep",
+      path.display(),
+      MAX_DEPTH
+    )));
+  }
+  let text = fs::read_to_string(path)?;
+  let mut server = false;
+  for line in text.lines() {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+      continue;
+    }
+    if let Some(file) = line.strip_prefix("!includedir") {
+      let mut files: Vec<PathBuf> = fs::read_dir(file.trim())?
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|file| file.extension().is_some_and(|extension| extension == "cnf"))
+        .collect();
+      files.sort();
+      for file in files {
+        options(&file, settings, depth + 1)?;
+      }
+      continue;
+    }
+    if let Some(file) = line.strip_prefix("!include") {
+      options(Path::new(file.trim()), settings, depth + 1)?;
+      continue;
+    }
+    if let Some(group) = line.strip_prefix('[') {
+      let group = group.trim_end_matches(']').trim();
+      server = ["mysqld", "mariadb", "mariadbd", "server"].contains(&group)
+        || group.starts_with("mysqld-")
+        || group.starts_with("mariadb-");
+      continue;
+    }
+    if !server {
+      continue;
+    }
+    let (name, value) = line.split_once('=').unwrap_or((line, "ON"));
+    let name = name.trim().replace('-', "_").to_ascii_lowercase();
+    let name = name.strip_prefix("loose_").unwrap_or(&name).to_string();
+    settings.push((name, unquote(value.trim()).to_string()));
+  }
+  Ok(())
+}
+
+/// Reads the InnoDB encryption of a MySQL or MariaDB server, which differ
+/// in how it is turned on. MariaDB encrypts with keys from a key
+/// management plugin and MySQL with AES-256 keys from a keyring.
+fn mysql(path: &Path) -> Result<Encryption, Error> {
+  let mut settings = Vec::new();
+  options(path, &mut settings, 0)?;
+  let get = |name: &str| {
+    settings
+      .iter()
+      .rev()
+      .find(|(n, _)| n == name)
+      .map(|(_, value)| value.as_str())
+  };
+  let loaded = |name: &str| {
+    settings
+      .iter()
+      .any(|(n, value)| n.starts_with("plugin_load") && value.contains(name))
+      || settings.iter().any(|(n, _)| n == name)
+  };
+  let mariadb = settings.iter().any(|(name, _)| {
+    name.starts_with("file_key_management")
+      || name.starts_with("innodb_encrypt_")
+      || name == "encrypt_binlog"
+      || name == "encrypt_tmp_files"
+  }) || loaded("key_management");
+  let mut unencrypted = Vec::new();
+  let mut encryption;
+  if mariadb {
+    let citation = Some(Citation {
+      document: "MariaDB Data-at-Rest Encryption",
+      section: "Enabling InnoDB Encryption",
+      url: Some("https://mariadb.com/kb/en/innodb-enabling-encryption/"),
+    });
+    encryption = Encryption::new(path, "mariadb");
+    if !loaded("key_management") {
+      encryption.issue(
+        "plugin_load_add",
+        "no key management plugin is loaded, so nothing is encrypted",
+        citation,
+      );
+    }
+    if !on(get("innodb_encrypt_tables")) {
+      encryption.issue(
+        "innodb_encrypt_tables",
+        "tables are only encrypted when they are created with ENCRYPTED=YES",
+        citation,
+      );
+    } else if get("innodb_encryption_threads").is_none_or(|threads| threads == "0") {
+      encryption.warnings.push(
+        "no background threads encrypt the tables that already exist (innodb_encryption_threads)"
+          .to_string(),
+      );
+    }
+    for (name, what) in [
+      ("innodb_encrypt_log", "the redo log"),
+      ("encrypt_binlog", "the binary log"),
+      ("encrypt_tmp_files", "temporary files"),
+    ] {
+      if !on(get(name)) {
+        unencrypted.push((name, what));
+      }
+    }
+    if loaded("file_key_management") {
+      let mode = get("file_key_management_encryption_algorithm").unwrap_or("AES_CBC");
+      let known = mode
+        .to_ascii_lowercase()
+        .strip_prefix("aes_")
+        .and_then(|mode| mode.parse().ok());
+      match get("file_key_management_filename") {
+        _ if get("file_key_management_filekey").is_some() => encryption
+          .warnings
+          .push("the key file is encrypted, so the sizes of its keys are not known".to_string()),
+        Some(file) => match fs::read_to_string(path.parent().unwrap_or(Path::new("")).join(file)) {
+          Ok(keys) => {
+            for line in keys.lines().map(str::trim) {
+              if line.starts_with('#') {
+                continue;
+              }
+              let Some((id, hex)) = line.split_once(';') else {
+                continue;
+              };
+              let what = format!("key {} ({})", id.trim(), mode.to_ascii_lowercase());
+              match format!("aes{}", hex.trim().len() * 4).parse() {
+                Ok(cipher) => encryption.ciphers.push((what, cipher, known)),
+                Err(_) => encryption.warnings.push(format!(
+                  "{} is not a key of AES, which is {} bits long",
+                  what,
+                  hex.trim().len() * 4
+                )),
+              }
+            }
+          },
+          Err(err) => encryption.warnings.push(format!(
+            "the key file {} cannot be read, so the sizes of its keys are not known: {}",
+            file, err
+          )),
+        },
+        None => encryption.issue(
+          "file_key_management_filename",
+          "no key file is given to the file key management plugin",
+          citation,
+        ),
+      }
+    }
+  } else {
+    let citation = Some(Citation {
+      document: "MySQL 8.0 Reference Manual",
+      section: "15.13 InnoDB Data-at-Rest Encryption",
+      url: Some("https://dev.mysql.com/doc/refman/8.0/en/innodb-data-encryption.html"),
+    });
+    encryption = Encryption::new(path, "mysql");
+    if !on(get("default_table_encryption")) {
+      encryption.issue(
+        "default_table_encryption",
+        "tables are only encrypted when they are created with ENCRYPTION='Y'",
+        citation,
+      );
+    } else {
+      encryption
+        .ciphers
+        .push(("tablespace keys".to_string(), AES256, Some(Mode::Cbc)));
+    }
+    if !loaded("keyring") {
+      encryption.warnings.push(
+        "no keyring plugin is loaded here, so a keyring component must be configured in its manifest for tables to be encrypted"
+          .to_string(),
+      );
+    }
+    for (name, what) in [
+      ("innodb_redo_log_encrypt", "the redo log"),
+      ("innodb_undo_log_encrypt", "the undo log"),
+      ("binlog_encryption", "the binary log"),
+    ] {
+      if !on(get(name)) {
+        unencrypted.push((name, what));
+      }
+    }
+  }
+  for (name, what) in unencrypted {
+    encryption.warnings.push(format!(
+      "what is written to {} is not encrypted ({})",
+      what, name
+    ));
+  }
+  Ok(encryption)
+}
+
+/// Reads the settings of a YAML configuration as dotted names, which is
+/// enough for the nested mappings of `mongod.conf`.
+fn yaml(text: &str) -> Vec<(String, String)> {
+  let mut settings = Vec::new();
+  let mut parents: Vec<(usize, String)> = Vec::new();
+  for line in text.lines() {
+    let content = line.split(" #").next().unwrap_or(line).trim_end();
+    let trimmed = content.trim_start();
+    if trimmed.is_empty() || trimmed.starts_with('#') {
+      continue;
+    }
+    let indent = content.len() - trimmed.len();
+    while parents.last().is_some_and(|(depth, _)| *depth >= indent) {
+      parents.pop();
+    }
+    let Some((name, value)) = trimmed.split_once(':') else {
+      continue;
+    };
+    let value = value.trim();
+    if value.is_empty() {
+      parents.push((indent, name.trim().to_string()));
+      continue;
+    }
+    let path: Vec<_> = parents
+      .iter()
+      .map(|(_, name)| name.as_str())
+      .chain([name.trim()])
+      .collect();
+    settings.push((path.join("."), unquote(value).to_string()));
+  }
+  settings
+}
+
+/// Reads the encrypted storage engine of MongoDB, which encrypts with
+/// AES-256 in CBC mode unless GCM is chosen.
+fn mongodb(path: &Path, text: &str) -> Encryption {
+  let settings = yaml(text);
+  let get = |name: &str| {
+    settings
+      .iter()
+      .rev()
+      .find(|(n, _)| n == name)
+      .map(|(_, value)| value.as_str())
+  };
+  let mut encryption = Encryption::new(path, "mongodb");
+  if !on(get("security.enableEncryption")) {
+    encryption.issue(
+      "security.enableEncryption",
+      "the storage engine does not encrypt data at rest",
+      Some(Citation {
+        document: "MongoDB Manual",
+        section: "Encryption at Rest",
+        url: Some("https://www.mongodb.com/docs/manual/core/security-encryption-at-rest/"),
+      }),
+    );
+    return encryption;
+  }
+  let mode = get("security.encryptionCipherMode").unwrap_or("AES256-CBC");
+  encryption.ciphers.push((
+    format!(
+      "security.encryptionCipherMode ({})",
+      mode.to_ascii_lowercase()
+    ),
+    AES256,
+    mode.rsplit('-').next().and_then(|mode| mode.parse().ok()),
+  ));
+  if get("security.encryptionKeyFile").is_some() {
+    encryption.warnings.push(
+      "the master key is kept in a local key file rather than by a key management server (security.kmip)"
+        .to_string(),
+    );
+  }
+  encryption
+}
+
+/// Reads the transparent data encryption of an Oracle database from its
+/// initialisation parameters, written as `*.name=value` or
+/// `<sid>.name=value`. Tablespaces are encrypted in CFB mode with
+/// AES-128 unless another algorithm is set.
+fn oracle_settings(path: &Path, text: &str) -> Encryption {
+  let mut settings = Vec::new();
+  for line in text.lines() {
+    let line = line.split('#').next().unwrap_or(line).trim();
+    let Some((name, value)) = line.split_once('=') else {
+      continue;
+    };
+    let name = name.trim().to_ascii_lowercase();
+    let name = name.rsplit('.').next().unwrap_or(&name);
+    let name = name.trim_start_matches('_').to_string();
+    settings.push((name, unquote(value.trim()).to_string()));
+  }
+  let get = |name: &str| {
+    settings
+      .iter()
+      .rev()
+      .find(|(n, _)| n == name)
+      .map(|(_, value)| value.as_str())
+  };
+  let mut encryption = Encryption::new(path, "oracle");
+  if get("wallet_root").is_none() && get("tde_configuration").is_none() {
+    encryption.warnings.push(
+      "no keystore is configured here, so tablespaces are only encrypted if sqlnet.ora gives one"
+        .to_string(),
+    );
+  }
+  let always =
+    get("encrypt_new_tablespaces").is_some_and(|value| value.eq_ignore_ascii_case("always"));
+  if !always {
+    encryption.issue(
+      "encrypt_new_tablespaces",
+      "tablespaces that are not in the cloud are only encrypted when they are created with ENCRYPTION",
+      Some(Citation {
+        document: "Oracle Database Reference",
+        section: "ENCRYPT_NEW_TABLESPACES",
+        url: Some("https://docs.oracle.com/en/database/oracle/oracle-database/19/refrn/ENCRYPT_NEW_TABLESPACES.html"),
+      }),
+    );
+  }
+  let algorithm = get("tablespace_encryption_default_algorithm").unwrap_or("AES128");
+  let what = "tablespace_encryption_default_algorithm".to_string();
+  let mode = Some(Mode::Cfb);
+  match algorithm.to_ascii_uppercase().as_str() {
+    "3DES168" => encryption.ciphers.push((what, TDEA3, mode)),
+    "AES128" => encryption.ciphers.push((what, AES128, mode)),
+    name => match name.to_ascii_lowercase().parse() {
+      Ok(cipher) => encryption.ciphers.push((what, cipher, mode)),
+      Err(_) => encryption.warnings.push(format!(
+        "tablespaces are encrypted with {}, which is not assessed",
+        name
+      )),
+    },
+  }
+  encryption
+}
+
+/// Audits the encryption of the volumes and databases in `files` for
+/// every audience. A file that cannot be read is skipped.
+pub fn assess(audiences: &Audiences, files: &[PathBuf], report: &mut Report) {
+  for file in files {
+    let encryption = match read(file) {
+      Ok(encryption) => encryption,
+      Err(err) => {
+        report.skip(err.in_file(file));
+        continue;
+      },
+    };
+    for audit in audit_audiences(audiences, |settings| audit(settings, &encryption)) {
+      report.push(audit);
+    }
+  }
+}
+
+/// Audits the encryption of a volume or database, of which every cipher
+/// that fails is explained while the one reported is the weakest that
+/// fails, or else the weakest. Key derivations that cost too little and
+/// settings that leave data unencrypted fail.
+pub fn audit(settings: Settings, encryption: &Encryption) -> Audit {
+  let Settings {
+    ctx, guide, locale, ..
+  } = settings;
+  let mut ciphers: Vec<_> = encryption
+    .ciphers
+    .iter()
+    .map(|(_, got, _)| (*got, guide.assess_symmetric(ctx, *got)))
+    .collect();
+  ciphers.sort_by_key(|(got, _)| got.security);
+  ciphers.dedup_by_key(|(got, _)| *got);
+  let cipher = ciphers
+    .iter()
+    .find(|(_, verdict)| !verdict.compliant)
+    .or(ciphers.first());
+  let mut audit = Audit::of_settings(&encryption.label, guide.name());
+  for (got, verdict) in &ciphers {
+    audit.cite(verdict.citation);
+    audit.explain(verdict, ctx, *got, locale);
+  }
+  for (_, got, mode) in &encryption.ciphers {
+    if let Some(mode) = mode {
+      advise_cipher(settings, *got, *mode, &mut audit);
+    }
+  }
+  match cipher {
+    Some((got, verdict)) if verdict.compliant => audit.compliant_cipher(*got, verdict.alternative),
+    Some((got, verdict)) => audit.noncompliant_cipher(*got, verdict.alternative),
+    None => {},
+  }
+  for issue in &encryption.issues {
+    audit.cite(issue.citation);
+    audit.noncompliant_setting(&issue.setting, issue.message.clone());
+  }
+  for warning in &encryption.warnings {
+    audit.warn(warning.clone());
+  }
+  audit
+}
@@ -0,0 +1,160 @@
+//! Read certificates from the trust stores of the operating system and
+//! from NSS databases.
+//!
+//! The trust store of the operating system is the Windows certificate
+//! store on Windows and the Keychain on macOS. Elsewhere, the bundle
+//! files used by OpenSSL are read. NSS databases are kept by
+//! applications such as Firefox and Thunderbird separately from the
+//! trust store of the operating system. Bundles of concatenated PEM
+//! certificates, such as exported trust stores, can be read as well.
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use x509_parser::nom::Err as NomError;
+use x509_parser::pem::{Pem, PemIterator};
+use x509_parser::prelude::{FromDer, PEMError, X509Certificate};
+
+use crate::key::Error;
+
+/// A DER encoded certificate read from a trust store.
+pub struct Entry {
+  /// A description of where the certificate was read from and who it
+  /// was issued to.
+  pub label: PathBuf,
+  pub der: Vec<u8>,
+}
+
+impl Entry {
+  /// Labels a certificate with where it was read from and the subject
+  /// it was issued to.
+  pub fn new(store: &str, der: Vec<u8>) -> Self {
+    let subject = match X509Certificate::from_der(&der) {
+      Ok((_, certificate)) => certificate.subject().to_string(),
+      Err(_) => "unknown subject".to_string(),
+    };
+    Self {
+      label: PathBuf::from(format!("{}: {}", store, subject)),
+      der,
+    }
+  }
+}
+
+/// The labels of the PEM blocks that hold a certificate. OpenSSL appends
+/// the trust settings to the certificate in a trusted certificate.
+const CERTIFICATE_LABELS: [&str; 2] = ["CERTIFICATE", "TRUSTED CERTIFICATE"];
+
+/// The certificates in a bundle of concatenated PEM blocks.
+///
+/// Bundles such as CA databases can hold tens of thousands of
+/// certificates, so rather than reading the whole file into memory the
+/// certificates are read one block at a time as the iterator advances.
+/// Blocks that do not hold a certificate, such as private keys, are
+/// skipped. A block that cannot be decoded yields an error but does not
+/// end the iteration.
+pub struct Bundle {
+  store: String,
+  blocks: PemIterator<BufReader<File>>,
+  done: bool,
+}
+
+impl Iterator for Bundle {
+  type Item = Result<Entry, Error>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.done {
+      return None;
+    }
+    loop {
+      match self.blocks.next()? {
+        Ok(pem) if CERTIFICATE_LABELS.contains(&pem.label.as_str()) => {
+          return Some(Ok(Entry::new(&self.store, pem.contents)))
+        },
+        Ok(_) => continue,
+        Err(PEMError::IOError(err)) => {
+          // The rest of the file cannot be read either.
+          self.done = true;
+          return Some(Err(err.into()));
+        },
+        Err(err) => return Some(Err(Error::ParsePEM(NomError::Error(err)))),
+      }
+    }
+  }
+}
+
+/// Opens a bundle of concatenated PEM certificates such as the
+/// `ca-certificates.crt` file used by OpenSSL.
+pub fn bundle(path: &Path) -> Result<Bundle, Error> {
+  let file = File::open(path)?;
+  Ok(Bundle {
+    store: path.display().to_string(),
+    blocks: Pem::iter_from_reader(BufReader::new(file)),
+    done: false,
+  })
+}
+
+/// Returns the certificates trusted by the operating system.
+///
+/// Certificates that cannot be read are skipped as long as at least one
+/// certificate could be read.
+pub fn system() -> Result<Vec<Entry>, Error> {
+  let result = rustls_native_certs::load_native_certs();
+  if result.certs.is_empty() {
+    if let Some(err) = result.errors.first() {
+      return Err(Error::TrustStore(err.to_string()));
+    }
+  }
+  let entries = result
+    .certs
+    .into_iter()
+    .map(|der| Entry::new("system", der.to_vec()))
+    .collect();
+  Ok(entries)
+}
+
+/// Returns the certificates in an NSS database.
+///
+/// The path is either that of the `cert9.db` file itself or the
+/// directory containing it, such as a Firefox profile directory.
+#[cfg(feature = "nss")]
+pub fn nss(path: &Path) -> Result<Vec<Entry>, Error> {
+  use rusqlite::{Connection, OpenFlags};
+
+  // Objects are stored as rows whose columns are named after the
+  // hexadecimal value of their PKCS #11 attribute type. Unsigned
+  // integers are encoded as four big-endian bytes.
+  const QUERY: &str = "SELECT a11 FROM nssPublic WHERE a0 = X'00000001'";
+
+  let path = if path.is_dir() {
+    path.join("cert9.db")
+  } else {
+    path.to_path_buf()
+  };
+  if !path.exists() {
+    return Err(Error::TrustStore(format!("{} not found", path.display())));
+  }
+  let store = path.display().to_string();
+  let connection = Connection::open_with_flags(&path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+    .map_err(|err| Error::TrustStore(err.to_string()))?;
+  let mut statement = connection
+    .prepare(QUERY)
+    .map_err(|err| Error::TrustStore(err.to_string()))?;
+  let entries = statement
+    .query_map([], |row| row.get::<_, Vec<u8>>(0))
+    .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+    .map_err(|err| Error::TrustStore(err.to_string()))?
+    .into_iter()
+    .map(|der| Entry::new(&store, der))
+    .collect();
+  Ok(entries)
+}
+
+/// Returns the certificates in an NSS database.
+///
+/// Always fails because the program was built without NSS support.
+#[cfg(not(feature = "nss"))]
+pub fn nss(_path: &Path) -> Result<Vec<Entry>, Error> {
+  Err(Error::TrustStore(
+    "built without support for NSS databases".to_string(),
+  ))
+}
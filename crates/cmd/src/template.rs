@@ -0,0 +1,325 @@
+//! Render reports with templates provided by the user.
+//!
+//! Templates use a subset of the [Handlebars] syntax so that results
+//! can be turned into the formats an organisation already uses, such as
+//! ticketing payloads or wiki tables, without a separate program:
+//!
+//! ```text
+//! {{#each report}}
+//! | {{path}} | {{#if passed}}ok{{else}}fail{{/if}} |
+//! {{/each}}
+//! ```
+//!
+//! The following expressions are supported:
+//!
+//! - `{{name}}` inserts a value, where `name` is a dotted path such as
+//!   `summary.failed`, `this` or `@index`. Names that are not found in
+//!   the current scope are looked up in the enclosing ones.
+//! - `{{json name}}` inserts a value as JSON, which is useful for
+//!   strings embedded in a JSON payload.
+//! - `{{#each name}}...{{else}}...{{/each}}` repeats the block for every
+//!   element of an array, or renders the `else` block if it is empty.
+//!   `@index`, `@first` and `@last` describe the current element.
+//! - `{{#if name}}...{{else}}...{{/if}}` and `{{#unless name}}...{{/unless}}`
+//!   render a block depending on whether a value is truthy, that is not
+//!   `null`, `false`, `0` or empty.
+//! - `{{! comment }}` is left out of the output.
+//!
+//! Values are inserted as is without escaping. A line holding nothing
+//! but a block or comment tag is removed entirely so that blocks can be
+//! written on lines of their own.
+//!
+//! [Handlebars]: https://handlebarsjs.com
+use std::borrow::Cow;
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::key::Error;
+
+#[derive(Clone, Debug)]
+enum Node {
+  Text(String),
+  Value(String),
+  Json(String),
+  Each(String, Vec<Node>, Vec<Node>),
+  If(String, Vec<Node>, Vec<Node>),
+  Unless(String, Vec<Node>, Vec<Node>),
+}
+
+#[derive(Debug)]
+enum Token {
+  Text(String),
+  Tag(String),
+}
+
+impl Token {
+  // Block and comment tags produce no output of their own.
+  fn is_standalone(&self) -> bool {
+    match self {
+      Self::Tag(tag) => {
+        tag.starts_with('#') || tag.starts_with('/') || tag.starts_with('!') || tag == "else"
+      },
+      Self::Text(_) => false,
+    }
+  }
+}
+
+/// A parsed template.
+#[derive(Clone, Debug)]
+pub struct Template {
+  nodes: Vec<Node>,
+}
+
+impl Template {
+  /// Reads and parses the template at `path`.
+  pub fn from_file(path: &Path) -> Result<Self, Error> {
+    let source = fs::read_to_string(path)
+      .map_err(|err| Error::Template(format!("{}: {}", path.display(), err)))?;
+    Self::parse(&source).map_err(|err| Error::Template(format!("{}: {}", path.display(), err)))
+  }
+
+  /// Parses the source of a template.
+  pub fn parse(source: &str) -> Result<Self, String> {
+    let mut tokens = tokenise(source)?.into_iter();
+    let (nodes, end) = parse_block(&mut tokens)?;
+    match end {
+      None => Ok(Self { nodes }),
+      Some(tag) => Err(format!("unexpected {{{{{}}}}}", tag)),
+    }
+  }
+
+  /// Renders the template with `data` as the outermost scope.
+  pub fn render(&self, data: &Value) -> String {
+    let mut s = String::new();
+    let mut scopes = vec![Scope::new(data)];
+    render(&self.nodes, &mut scopes, &mut s);
+    s
+  }
+}
+
+fn tokenise(source: &str) -> Result<Vec<Token>, String> {
+  let mut tokens = Vec::new();
+  let mut rest = source;
+  while let Some(start) = rest.find("{{") {
+    if start > 0 {
+      tokens.push(Token::Text(rest[..start].to_string()));
+    }
+    let after = &rest[start + 2..];
+    let end = after.find("}}").ok_or_else(|| {
+      format!(
+        "unterminated tag at byte {}",
+        source.len() - rest.len() + start
+      )
+    })?;
+    tokens.push(Token::Tag(after[..end].trim().to_string()));
+    rest = &after[end + 2..];
+  }
+  if !rest.is_empty() {
+    tokens.push(Token::Text(rest.to_string()));
+  }
+  strip_standalone(&mut tokens);
+  Ok(tokens)
+}
+
+/// Removes the indentation and line break around tags that are alone on
+/// their line.
+fn strip_standalone(tokens: &mut [Token]) {
+  // The text between two tags on consecutive lines is shared by both,
+  // so what to keep of every text is worked out before changing any.
+  let mut keep: Vec<(usize, usize)> = tokens
+    .iter()
+    .map(|token| match token {
+      Token::Text(text) => (0, text.len()),
+      Token::Tag(_) => (0, 0),
+    })
+    .collect();
+  for i in 0..tokens.len() {
+    if !tokens[i].is_standalone() {
+      continue;
+    }
+    let before = match i.checked_sub(1).map(|j| &tokens[j]) {
+      None => Some(0),
+      Some(Token::Text(text)) => {
+        let newline = text.rfind('\n');
+        let line = newline.map_or(0, |n| n + 1);
+        let at_start = newline.is_some() || i == 1;
+        (at_start && text[line..].trim().is_empty()).then_some(line)
+      },
+      Some(Token::Tag(_)) => None,
+    };
+    let after = match tokens.get(i + 1) {
+      None => Some(0),
+      Some(Token::Text(text)) => {
+        let newline = text.find('\n');
+        let line = newline.map_or(text.len(), |n| n + 1);
+        let at_end = newline.is_some() || i + 2 == tokens.len();
+        (at_end && text[..line].trim().is_empty()).then_some(line)
+      },
+      Some(Token::Tag(_)) => None,
+    };
+    if let (Some(before), Some(after)) = (before, after) {
+      if i > 0 {
+        keep[i - 1].1 = keep[i - 1].1.min(before);
+      }
+      if let Some(range) = keep.get_mut(i + 1) {
+        range.0 = range.0.max(after);
+      }
+    }
+  }
+  for (token, (start, end)) in tokens.iter_mut().zip(keep) {
+    if let Token::Text(text) = token {
+      *text = text.get(start..end).unwrap_or_default().to_string();
+    }
+  }
+}
+
+type Tokens = std::vec::IntoIter<Token>;
+
+/// Parses nodes until the end of the input or a closing or `else` tag,
+/// which is returned.
+fn parse_block(tokens: &mut Tokens) -> Result<(Vec<Node>, Option<String>), String> {
+  let mut nodes = Vec::new();
+  while let Some(token) = tokens.next() {
+    let tag = match token {
+      Token::Text(text) => {
+        nodes.push(Node::Text(text));
+        continue;
+      },
+      Token::Tag(tag) => tag,
+    };
+    if tag.starts_with('!') {
+      continue;
+    }
+    if tag.starts_with('/') || tag == "else" {
+      return Ok((nodes, Some(tag)));
+    }
+    if let Some(block) = tag.strip_prefix('#') {
+      let (helper, name) = block
+        .split_once(char::is_whitespace)
+        .map(|(helper, name)| (helper, name.trim()))
+        .ok_or_else(|| format!("{{{{{}}}}} is missing a name", tag))?;
+      let (body, end) = parse_block(tokens)?;
+      let (otherwise, end) = match end.as_deref() {
+        Some("else") => parse_block(tokens)?,
+        _ => (Vec::new(), end),
+      };
+      let close = format!("/{}", helper);
+      if end.as_deref() != Some(close.as_str()) {
+        return Err(format!("{{{{{}}}}} is not closed", tag));
+      }
+      let name = name.to_string();
+      nodes.push(match helper {
+        "each" => Node::Each(name, body, otherwise),
+        "if" => Node::If(name, body, otherwise),
+        "unless" => Node::Unless(name, body, otherwise),
+        _ => return Err(format!("unknown block helper {}", helper)),
+      });
+      continue;
+    }
+    match tag.split_once(char::is_whitespace) {
+      Some(("json", name)) => nodes.push(Node::Json(name.trim().to_string())),
+      Some((helper, _)) => return Err(format!("unknown helper {}", helper)),
+      None if tag.is_empty() => return Err("empty tag".to_string()),
+      None => nodes.push(Node::Value(tag)),
+    }
+  }
+  Ok((nodes, None))
+}
+
+/// A value being rendered along with its position if it is an element
+/// of an array.
+struct Scope<'a> {
+  value: &'a Value,
+  index: Option<(usize, usize)>,
+}
+
+impl<'a> Scope<'a> {
+  fn new(value: &'a Value) -> Self {
+    Self { value, index: None }
+  }
+
+  fn get(&self, name: &str) -> Option<Cow<'a, Value>> {
+    match name {
+      "this" | "." => return Some(Cow::Borrowed(self.value)),
+      "@index" => return self.index.map(|(i, _)| Cow::Owned(Value::from(i))),
+      "@first" => return self.index.map(|(i, _)| Cow::Owned(Value::from(i == 0))),
+      "@last" => return self.index.map(|(i, n)| Cow::Owned(Value::from(i + 1 == n))),
+      _ => {},
+    }
+    let name = name.strip_prefix("this.").unwrap_or(name);
+    name
+      .split('.')
+      .try_fold(self.value, |value, key| value.get(key))
+      .map(Cow::Borrowed)
+  }
+}
+
+// Looks the name up from the innermost scope outwards.
+fn lookup<'a>(scopes: &[Scope<'a>], name: &str) -> Cow<'a, Value> {
+  scopes
+    .iter()
+    .rev()
+    .find_map(|scope| scope.get(name))
+    .unwrap_or(Cow::Owned(Value::Null))
+}
+
+fn truthy(value: &Value) -> bool {
+  match value {
+    Value::Null => false,
+    Value::Bool(b) => *b,
+    Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+    Value::String(s) => !s.is_empty(),
+    Value::Array(a) => !a.is_empty(),
+    Value::Object(o) => !o.is_empty(),
+  }
+}
+
+fn render<'a>(nodes: &[Node], scopes: &mut Vec<Scope<'a>>, s: &mut String) {
+  for node in nodes {
+    match node {
+      Node::Text(text) => s.push_str(text),
+      Node::Value(name) => match lookup(scopes, name).as_ref() {
+        Value::Null => {},
+        Value::String(value) => s.push_str(value),
+        value => s.push_str(&value.to_string()),
+      },
+      Node::Json(name) => s.push_str(&lookup(scopes, name).to_string()),
+      Node::Each(name, body, otherwise) => {
+        // Only values within the data can be iterated over, which are
+        // always borrowed.
+        let items: Vec<&'a Value> = match lookup(scopes, name) {
+          Cow::Borrowed(Value::Array(items)) => items.iter().collect(),
+          Cow::Borrowed(Value::Object(map)) => map.values().collect(),
+          _ => Vec::new(),
+        };
+        if items.is_empty() {
+          render(otherwise, scopes, s);
+        }
+        for (i, item) in items.iter().enumerate() {
+          scopes.push(Scope {
+            value: item,
+            index: Some((i, items.len())),
+          });
+          render(body, scopes, s);
+          scopes.pop();
+        }
+      },
+      Node::If(name, body, otherwise) => {
+        if truthy(&lookup(scopes, name)) {
+          render(body, scopes, s);
+        } else {
+          render(otherwise, scopes, s);
+        }
+      },
+      Node::Unless(name, body, otherwise) => {
+        if truthy(&lookup(scopes, name)) {
+          render(otherwise, scopes, s);
+        } else {
+          render(body, scopes, s);
+        }
+      },
+    }
+  }
+}
@@ -0,0 +1,92 @@
+//! Assess threshold and multi-signature signing setups.
+//!
+//! A setup in which any t of n parties sign together is written as
+//! `[<name>=]<scheme>_<curve>_<t>of<n>`, such as
+//! `treasury=frost_ed25519_3of5`, and is reported under its name if it
+//! has one. The curve is assessed as the signature algorithm, while
+//! whether the setup actually splits the key is assessed whatever the
+//! guide.
+use std::path::{Path, PathBuf};
+
+use wardstone_core::advisory::Hazard;
+use wardstone_core::primitive::asymmetric::Asymmetric;
+use wardstone_core::primitive::threshold::Threshold;
+
+use crate::assess::Settings;
+use crate::report::Audit;
+
+/// Reads a signing setup written as `[<name>=]<scheme>_<curve>_<t>of<n>`,
+/// which is labelled with its name if given.
+pub fn parse(setup: &str) -> Result<(PathBuf, Threshold), String> {
+  let (name, key) = setup.split_once('=').unwrap_or((setup, setup));
+  match key.parse() {
+    Ok(key) => Ok((name.into(), key)),
+    Err(_) => Err("expected <scheme>_<curve>_<t>of<n>, e.g. frost_ed25519_2of3".to_string()),
+  }
+}
+
+/// Audits a threshold or multi-signature signing setup. The curve is
+/// assessed as the signature algorithm, while a setup that does not
+/// split the key fails whatever its curve.
+pub fn audit(settings: Settings, label: &Path, key: Threshold) -> Audit {
+  let Settings {
+    ctx,
+    guide,
+    locale,
+    hazards,
+    ..
+  } = settings;
+  let hazards = hazards.unwrap_or(guide.hazards());
+  let curve = Asymmetric::Ecc(key.curve);
+  let mut audit = Audit::new(label, guide.name(), None, curve);
+  let verdict = guide.assess_threshold(ctx, key);
+  audit.cite(verdict.citation);
+  audit.explain(&verdict, ctx, key, locale);
+  let want = match verdict.into_result() {
+    Ok(want) => {
+      audit.compliant_signature(want.curve.into());
+      want
+    },
+    Err(want) => {
+      if want.curve != key.curve {
+        audit.noncompliant_signature(want.curve.into());
+      }
+      want
+    },
+  };
+  if key.t < 2 {
+    audit.noncompliant_setting(
+      "t",
+      format!(
+        "any single party signs on its own, so the key is copied rather than split; use {}",
+        want
+      ),
+    );
+  } else if key.t > key.n {
+    audit.noncompliant_setting(
+      "t",
+      format!(
+        "more parties are needed to sign than there are; use {}",
+        want
+      ),
+    );
+  } else if key.t == key.n {
+    audit.warn(format!(
+      "every one of the {} parties has to sign, so losing a single share loses the key",
+      key.n
+    ));
+  }
+  if !key.scheme.fits(key.curve) {
+    audit.noncompliant_setting(
+      "scheme",
+      format!(
+        "{} is not defined over {}; use {}",
+        key.scheme, key.curve, want
+      ),
+    );
+  }
+  if let Some(hazard) = Hazard::of_threshold(key).filter(|&h| hazards.contains(h)) {
+    audit.advise(hazard.advisory(), key);
+  }
+  audit
+}
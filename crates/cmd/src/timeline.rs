@@ -0,0 +1,202 @@
+//! Plan the migration of keys away from primitives that cease to be
+//! compliant.
+//!
+//! Every primitive a key uses is assessed year by year under each guide
+//! to find the first year up to the end of the plan in which it is no
+//! longer compliant. Keys are listed starting with those that need to be
+//! replaced first.
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::json;
+use wardstone_core::context::Context;
+use wardstone_core::standard::window::{self, HORIZON};
+
+use crate::guide::Guide;
+use crate::key::Key;
+
+/// A primitive used by a key and the year it ceases to be compliant
+/// under each guide.
+#[derive(Serialize)]
+struct Milestone {
+  /// What the primitive is used for.
+  usage: &'static str,
+  name: String,
+  /// The first year in which each guide no longer deems the primitive
+  /// compliant, or `None` if it remains compliant up to the end of the
+  /// plan.
+  ceases: BTreeMap<&'static str, Option<u16>>,
+}
+
+/// The primitives used by a key.
+#[derive(Serialize)]
+struct Artifact {
+  path: PathBuf,
+  /// The earliest year in which one of the primitives ceases to be
+  /// compliant under one of the guides.
+  ceases: Option<u16>,
+  primitives: Vec<Milestone>,
+}
+
+impl Artifact {
+  // Keys whose primitives remain compliant up to the end of the plan
+  // come last.
+  fn urgency(&self) -> u16 {
+    self.ceases.unwrap_or(u16::MAX)
+  }
+}
+
+/// A migration plan listing keys by how soon they need to be replaced.
+pub struct Timeline {
+  ctx: Context,
+  /// The last year of the plan.
+  to: u16,
+  guides: Vec<Guide>,
+  artifacts: Vec<Artifact>,
+  json: bool,
+}
+
+impl Timeline {
+  pub fn new(ctx: Context, guides: Vec<Guide>, json: bool) -> Self {
+    Self {
+      ctx,
+      to: HORIZON,
+      guides,
+      artifacts: Vec::new(),
+      json,
+    }
+  }
+
+  /// Ends the plan in `year` rather than at [`HORIZON`]. A plan cannot
+  /// end before it starts.
+  pub fn until(mut self, year: u16) -> Self {
+    self.to = year.max(self.ctx.year());
+    self
+  }
+
+  fn milestone<T, F>(&self, usage: &'static str, primitive: T, f: F) -> Milestone
+  where
+    T: Copy + Display,
+    F: Fn(Guide, Context, T) -> Result<T, T>,
+  {
+    let ceases = self
+      .guides
+      .iter()
+      .map(|&guide| {
+        let years = self.ctx.year()..=self.to;
+        let ceases = window::first_non_compliant(self.ctx, primitive, years, |ctx, primitive| {
+          f(guide, ctx, primitive)
+        });
+        (guide.name(), ceases)
+      })
+      .collect();
+    Milestone {
+      usage,
+      name: primitive.to_string(),
+      ceases,
+    }
+  }
+
+  /// Adds the primitives used by a key to the plan.
+  pub fn push(&mut self, path: &Path, key: &dyn Key) {
+    let mut primitives = Vec::new();
+    if let Some(hash) = key.hash_function() {
+      primitives.push(self.milestone("hash function", hash, |guide, ctx, hash| {
+        guide.validate_hash_function(ctx, hash)
+      }));
+    }
+    let signature_algorithm = key.signature_algorithm();
+    primitives.push(
+      self.milestone("signature", signature_algorithm, |guide, ctx, key| {
+        guide.validate_signature_algorithm(ctx, key)
+      }),
+    );
+    // The key of a self-signed certificate is its own issuer.
+    if let Some(issuer_key) = key.issuer_key().filter(|&k| k != signature_algorithm) {
+      primitives.push(self.milestone("issuer key", issuer_key, |guide, ctx, key| {
+        guide.validate_signature_algorithm(ctx, key)
+      }));
+    }
+    let ceases = primitives
+      .iter()
+      .flat_map(|milestone| milestone.ceases.values())
+      .flatten()
+      .copied()
+      .min();
+    let artifact = Artifact {
+      path: path.to_path_buf(),
+      ceases,
+      primitives,
+    };
+    // Keys that cease to be compliant in the same year stay in the order
+    // they were given.
+    let i = self
+      .artifacts
+      .partition_point(|a| a.urgency() <= artifact.urgency());
+    self.artifacts.insert(i, artifact);
+  }
+
+  pub fn to_json_string(&self) -> String {
+    json!({
+      "year": self.ctx.year(),
+      "horizon": self.to,
+      "timeline": &self.artifacts,
+    })
+    .to_string()
+  }
+
+  /// Prints the plan to the standard output.
+  pub fn print(&self) {
+    if self.json {
+      println!("{}", self.to_json_string())
+    } else {
+      print!("{}", self)
+    }
+  }
+
+  fn cell(&self, ceases: Option<u16>) -> String {
+    match ceases {
+      Some(year) if year == self.ctx.year() => "now".to_string(),
+      Some(year) => year.to_string(),
+      None => format!(">{}", self.to),
+    }
+  }
+}
+
+impl Display for Timeline {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    let mut rows = vec![["path", "use", "primitive"]
+      .into_iter()
+      .map(String::from)
+      .chain(self.guides.iter().map(|guide| guide.name().to_string()))
+      .collect::<Vec<_>>()];
+    for artifact in self.artifacts.iter() {
+      for (i, milestone) in artifact.primitives.iter().enumerate() {
+        // The path is only shown once for all the primitives of a key.
+        let path = if i == 0 {
+          artifact.path.display().to_string()
+        } else {
+          String::new()
+        };
+        let mut row = vec![path, milestone.usage.to_string(), milestone.name.clone()];
+        for guide in self.guides.iter() {
+          row.push(self.cell(milestone.ceases.get(guide.name()).copied().flatten()));
+        }
+        rows.push(row);
+      }
+    }
+    let widths: Vec<_> = (0..rows[0].len())
+      .map(|i| rows.iter().map(|row| row[i].len()).max().unwrap_or(0))
+      .collect();
+    for row in rows.iter() {
+      let mut s = String::new();
+      for (cell, width) in row.iter().zip(widths.iter()) {
+        s.push_str(format!("{:width$}  ", cell).as_str());
+      }
+      writeln!(f, "{}", s.trim_end())?;
+    }
+    Ok(())
+  }
+}
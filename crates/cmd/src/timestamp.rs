@@ -0,0 +1,342 @@
+//! Find the layers of time-stamps that keep signatures verifiable.
+//!
+//! A time-stamp token (RFC 3161) is a signature of a time-stamping
+//! authority (TSA) over the digest of some data and the time it was
+//! made. Long-term signatures build on them in layers:
+//!
+//! - CAdES (ETSI EN 319 122-1) adds a time-stamp over the signature,
+//!   time-stamps over the references to its validation data and archive
+//!   time-stamps over all of these as unsigned attributes of the signer.
+//! - PAdES (ETSI EN 319 142-1) signs PDF documents with CAdES signatures
+//!   and adds document time-stamps, which a TSA makes over the whole
+//!   document, in incremental updates.
+//!
+//! A layer stays verifiable for as long as a later time-stamp over it
+//! relies on a hash function and key that have not been broken, which
+//! is why archives add a layer before the last one becomes weak.
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use x509_parser::pem;
+
+use crate::assess::{audit_audiences, audit_key, Audiences, Settings};
+use crate::input;
+use crate::key::signed::{self, Signed};
+use crate::key::{Error, Key};
+use crate::reader::X509Reader;
+use crate::report::Report;
+
+/// The unsigned attributes of a signer that hold time-stamp tokens.
+const ATTRIBUTES: &[(&str, Kind)] = &[
+  // id-aa-signatureTimeStampToken
+  ("1.2.840.113549.1.9.16.2.14", Kind::SignatureTimestamp),
+  // id-aa-ets-escTimeStamp
+  ("1.2.840.113549.1.9.16.2.25", Kind::ReferencesTimestamp),
+  // id-aa-ets-certCRLTimestamp
+  ("1.2.840.113549.1.9.16.2.26", Kind::ReferencesTimestamp),
+  // id-aa-ets-archiveTimestamp
+  ("1.2.840.113549.1.9.16.2.27", Kind::ArchiveTimestamp),
+  // id-aa-ets-archiveTimestampV2
+  ("1.2.840.113549.1.9.16.2.48", Kind::ArchiveTimestamp),
+  // id-aa-ets-archiveTimestampV3
+  ("0.4.0.1733.2.4", Kind::ArchiveTimestamp),
+];
+
+/// What a layer of a long-term signature is.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Kind {
+  /// A signature, which is the first layer.
+  Signature,
+  /// A time-stamp token on its own.
+  Timestamp,
+  /// A time-stamp over a signature.
+  SignatureTimestamp,
+  /// A time-stamp over the references to the certificates and
+  /// revocation data a signature was validated with.
+  ReferencesTimestamp,
+  /// A time-stamp over a signature and every layer before it.
+  ArchiveTimestamp,
+  /// A time-stamp over a whole PDF document and the signatures in it.
+  DocumentTimestamp,
+}
+
+impl Kind {
+  /// Returns whether a time-stamp of this kind is over an earlier layer
+  /// of the given kind.
+  pub fn covers(self, earlier: Kind) -> bool {
+    match self {
+      Self::SignatureTimestamp => earlier == Self::Signature,
+      Self::ReferencesTimestamp => {
+        matches!(earlier, Self::Signature | Self::SignatureTimestamp)
+      },
+      Self::ArchiveTimestamp | Self::DocumentTimestamp => true,
+      Self::Signature | Self::Timestamp => false,
+    }
+  }
+}
+
+impl fmt::Display for Kind {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Signature => write!(f, "signature"),
+      Self::Timestamp => write!(f, "timestamp"),
+      Self::SignatureTimestamp => write!(f, "signature-timestamp"),
+      Self::ReferencesTimestamp => write!(f, "references-timestamp"),
+      Self::ArchiveTimestamp => write!(f, "archive-timestamp"),
+      Self::DocumentTimestamp => write!(f, "document-timestamp"),
+    }
+  }
+}
+
+/// A layer of a long-term signature.
+pub struct Layer {
+  pub kind: Kind,
+  /// Where the layer was found, such as `contract.p7s:signature` or
+  /// `contract.p7s:archive-timestamp:2`.
+  pub label: PathBuf,
+  /// When a time-stamp was made, such as `2024-03-01 12:00:00 UTC`.
+  pub time: Option<String>,
+  pub signed: Result<Signed, Error>,
+}
+
+/// Returns the layers of the time-stamp token, CAdES signature or PAdES
+/// signed PDF document at `path` in the order they were added.
+pub fn layers(path: &Path) -> Result<Vec<Layer>, Error> {
+  let data = input::open(path)?;
+  let mut found = Vec::new();
+  if data.starts_with(b"%PDF-") {
+    // Signatures and document time-stamps are added to a document in
+    // incremental updates, so they come in the order they were made.
+    for (kind, contents) in pdf_signatures(&data) {
+      found.extend(cms(&contents, kind));
+    }
+  } else {
+    let der = if data.starts_with(b"-----BEGIN") {
+      let (_, pem) = pem::parse_x509_pem(&data)?;
+      pem.contents
+    } else {
+      data.to_vec()
+    };
+    let token = signed::response_token(&der)?.unwrap_or(&der);
+    let kind = match signed::time_stamped(token)? {
+      Some(_) => Kind::Timestamp,
+      None => Kind::Signature,
+    };
+    found.extend(cms(token, kind));
+  }
+  if found.is_empty() {
+    return Err(Error::Unsigned);
+  }
+  // Layers are numbered among those of their kind when there are
+  // several, as archives gather one archive time-stamp after another.
+  let kinds: Vec<Kind> = found.iter().map(|(kind, _, _)| *kind).collect();
+  let count = |kinds: &[Kind], kind| kinds.iter().filter(|&&other| other == kind).count();
+  Ok(
+    found
+      .into_iter()
+      .enumerate()
+      .map(|(i, (kind, signed, time))| {
+        let label = if count(&kinds, kind) > 1 {
+          format!("{}:{}:{}", path.display(), kind, count(&kinds[..=i], kind))
+        } else {
+          format!("{}:{}", path.display(), kind)
+        };
+        Layer {
+          kind,
+          label: label.into(),
+          time: time.as_deref().map(display_time),
+          signed,
+        }
+      })
+      .collect(),
+  )
+}
+
+/// Returns the layers of a PKCS #7 SignedData structure, that is itself
+/// and the time-stamps in the unsigned attributes of its signer.
+fn cms(data: &[u8], kind: Kind) -> Vec<(Kind, Result<Signed, Error>, Option<String>)> {
+  let time = signed::time_stamped(data).ok().flatten();
+  let mut layers = vec![(kind, Signed::from_pkcs7(data), time)];
+  let mut timestamps = Vec::new();
+  for (oid, values) in signed::unsigned_attributes(data).unwrap_or_default() {
+    let Some(&(_, kind)) = ATTRIBUTES.iter().find(|(attribute, _)| *attribute == oid) else {
+      continue;
+    };
+    for token in values {
+      let time = signed::time_stamped(token).ok().flatten();
+      timestamps.push((kind, Signed::from_pkcs7(token), time));
+    }
+  }
+  // DER sorts attributes and the values of each by their encoding, so
+  // the time-stamps are put back in the order they were made in.
+  timestamps.sort_by(|(_, _, a), (_, _, b)| a.cmp(b));
+  layers.extend(timestamps);
+  layers
+}
+
+/// Returns the kind and the contents of every signature dictionary
+/// in a PDF document, which are the only dictionaries with a byte range.
+fn pdf_signatures(data: &[u8]) -> Vec<(Kind, Vec<u8>)> {
+  let mut signatures = Vec::new();
+  let mut from = 0;
+  while let Some(at) = find(&data[from..], b"/ByteRange").map(|i| from + i) {
+    // Signature dictionaries are indirect objects, since their contents
+    // are left out of what is signed by their offset in the file.
+    let start = rfind(&data[..at], b"obj").unwrap_or(0);
+    let end = find(&data[at..], b"endobj").map_or(data.len(), |i| at + i);
+    from = end.max(at + 1);
+    let dictionary = &data[start..end];
+    let Some(contents) = hex_string(dictionary, b"/Contents") else {
+      continue;
+    };
+    let kind = if find(dictionary, b"/ETSI.RFC3161").is_some()
+      || find(dictionary, b"/DocTimeStamp").is_some()
+    {
+      Kind::DocumentTimestamp
+    } else {
+      Kind::Signature
+    };
+    signatures.push((kind, contents));
+  }
+  signatures
+}
+
+/// Returns the bytes of the hexadecimal string that follows a key in a
+/// PDF dictionary. Signatures are padded with zeros, which come after
+/// their encoding and are not read.
+fn hex_string(dictionary: &[u8], key: &[u8]) -> Option<Vec<u8>> {
+  let at = find(dictionary, key)? + key.len();
+  let rest = dictionary[at..].trim_ascii_start().strip_prefix(b"<")?;
+  let end = rest.iter().position(|&b| b == b'>')?;
+  let digits: Vec<u8> = rest[..end]
+    .iter()
+    .filter(|b| !b.is_ascii_whitespace())
+    .map(|&b| (b as char).to_digit(16).map(|digit| digit as u8))
+    .collect::<Option<_>>()?;
+  Some(
+    digits
+      .chunks(2)
+      .map(|pair| pair[0] << 4 | pair.get(1).copied().unwrap_or_default())
+      .collect(),
+  )
+}
+
+/// Returns a GeneralizedTime such as `20240301120000Z` in a form that
+/// is easier to read.
+fn display_time(time: &str) -> String {
+  match time.as_bytes() {
+    [digits @ .., b'Z'] if digits.len() >= 14 && digits[..14].iter().all(u8::is_ascii_digit) => {
+      format!(
+        "{}-{}-{} {}:{}:{} UTC",
+        &time[..4],
+        &time[4..6],
+        &time[6..8],
+        &time[8..10],
+        &time[10..12],
+        &time[12..14]
+      )
+    },
+    _ => time.to_string(),
+  }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+  haystack
+    .windows(needle.len())
+    .position(|window| window == needle)
+}
+
+fn rfind(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+  haystack
+    .windows(needle.len())
+    .rposition(|window| window == needle)
+}
+
+/// Audits every layer of time-stamp tokens and long-term signatures.
+/// A layer that fails is reported with the later time-stamp that keeps
+/// it verifiable, or else as needing to be time-stamped again. A file
+/// or layer that cannot be read is skipped.
+pub fn assess(audiences: &Audiences, paths: &[PathBuf], report: &mut Report) {
+  for path in paths {
+    let layers = match layers(path) {
+      Ok(layers) => layers,
+      Err(err) => {
+        report.skip(err.in_file(path));
+        continue;
+      },
+    };
+    let mut read = Vec::new();
+    for layer in layers {
+      match layer.signed {
+        Ok(ref signed) => {
+          let audits = audit_audiences(audiences, |settings| {
+            audit_key(settings, &X509Reader, &layer.label, signed)
+          });
+          read.push((layer, audits));
+        },
+        Err(err) => report.skip(err.in_file(layer.label)),
+      }
+    }
+    // Whether a layer is covered depends on which later time-stamps
+    // comply, which differs between audiences.
+    for i in 0..read.len() {
+      let (later, layer) = (&read[i + 1..], &read[i].0);
+      let warnings: Vec<_> = audiences
+        .iter()
+        .enumerate()
+        .map(|(j, (_, settings))| {
+          let covers = later
+            .iter()
+            .filter(|(other, _)| other.kind.covers(layer.kind))
+            .collect::<Vec<_>>();
+          if covers.is_empty() && layer.kind == Kind::Signature {
+            return Some(
+              "the signature has no time-stamp, so it cannot be shown to have been made while its certificate was valid"
+                .to_string(),
+            );
+          }
+          if read[i].1[j].passed() {
+            return None;
+          }
+          // The latest time-stamp is the one that is furthest from
+          // being broken.
+          if let Some((other, _)) = covers.iter().rev().find(|(_, audits)| audits[j].passed()) {
+            let made = other
+              .time
+              .as_ref()
+              .map(|time| format!(" made on {}", time))
+              .unwrap_or_default();
+            return Some(format!(
+              "the {} stays verifiable through {}, a later time-stamp{} that complies",
+              layer.kind,
+              other.label.display(),
+              made
+            ));
+          }
+          let Settings { ctx, guide, .. } = settings;
+          let with = match layer.signed.as_ref().ok().and_then(Key::hash_function) {
+            Some(got) => match guide.assess_hash_function(*ctx, got).into_result() {
+              Ok(_) => "from a time-stamping authority whose key complies".to_string(),
+              Err(want) => format!("that uses {}", want),
+            },
+            None => "that complies".to_string(),
+          };
+          Some(format!(
+            "no later time-stamp that complies covers the {}, so it needs to be time-stamped again with an archive or document time-stamp {}",
+            layer.kind, with
+          ))
+        })
+        .collect();
+      for (audit, warning) in read[i].1.iter_mut().zip(warnings) {
+        if let Some(warning) = warning {
+          audit.warn(warning);
+        }
+      }
+    }
+    for (_, audits) in read {
+      for audit in audits {
+        report.push(audit);
+      }
+    }
+  }
+}
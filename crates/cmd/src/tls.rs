@@ -0,0 +1,483 @@
+//! Negotiate a TLS session with a server to find out which primitives
+//! it uses.
+//!
+//! Only a single handshake is performed, so the parameters are those
+//! the server picks from what this program offers. A server that also
+//! supports weaker parameters might negotiate those with other clients.
+//!
+//! A second handshake can be asked for to find out how the server
+//! resumes sessions. How the server protects its session tickets
+//! cannot be seen by a client, and neither can whether it would resume
+//! a TLS 1.3 session without a fresh key exchange (`psk_ke`), since
+//! only `psk_dhe_ke` is ever offered. Nor are key updates probed for,
+//! as servers only update keys after much more data than a scan sends.
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::{
+  ClientSessionMemoryCache, ClientSessionStore, Tls12ClientSessionValue, Tls13ClientSessionValue,
+};
+use rustls::crypto::{self, CryptoProvider};
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{
+  CipherSuite, ClientConfig, ClientConnection, DigitallySignedStruct, HandshakeKind, NamedGroup,
+  ProtocolVersion, RootCertStore, SignatureScheme, StreamOwned,
+};
+use serde::Serialize;
+use wardstone_core::advisory::Hazard;
+use wardstone_core::primitive::asymmetric::Asymmetric;
+use wardstone_core::primitive::symmetric::{Mode, Symmetric};
+use wardstone_core::registry;
+
+use crate::assess::{advise_cipher, audit_audiences, audit_key, Audiences, Settings};
+use crate::key::certificate::Certificate;
+use crate::key::Error;
+use crate::reader::X509Reader;
+use crate::report::Audit;
+use crate::store::Entry;
+
+/// How long to wait for a server unless told otherwise.
+pub const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long to wait for the session tickets that a TLS 1.3 server sends
+/// after the handshake.
+const TICKET_WAIT: Duration = Duration::from_secs(1);
+
+/// The certificates and parameters negotiated with a server.
+pub struct Handshake {
+  /// The certificate chain sent by the server starting with that of
+  /// the server itself.
+  pub chain: Vec<Entry>,
+  /// The group used for the key exchange or `None` if it is not
+  /// recognised.
+  pub key_exchange: Option<Asymmetric>,
+  /// The cipher used to protect the session or `None` if it is not
+  /// recognised.
+  pub cipher: Option<Symmetric>,
+  /// The name of the negotiated key exchange group.
+  pub group: String,
+  /// The name of the negotiated cipher suite.
+  pub suite: String,
+  /// How the server resumes sessions, if a second handshake was made
+  /// to find out.
+  pub resumption: Option<Resumption>,
+}
+
+/// How a server resumes sessions, as seen by resuming the session of a
+/// first handshake in a second one.
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct Resumption {
+  /// Whether the server handed out a session ticket, or in TLS 1.2 a
+  /// session identifier, to resume the session with.
+  pub issued: bool,
+  /// Whether the server resumed the session in the second handshake.
+  pub resumed: bool,
+  /// Whether the resumed session agreed on fresh keys with a key
+  /// exchange, as the `psk_dhe_ke` mode of TLS 1.3 does. Resuming a
+  /// TLS 1.2 session reuses the master secret instead.
+  pub fresh_keys: bool,
+}
+
+impl Display for Resumption {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    let description = match self {
+      Self {
+        resumed: true,
+        fresh_keys: true,
+        ..
+      } => "resumed with a fresh key exchange",
+      Self { resumed: true, .. } => "resumed without a fresh key exchange",
+      Self { issued: true, .. } => "offered but not accepted",
+      _ => "not offered",
+    };
+    write!(f, "{}", description)
+  }
+}
+
+/// Remembers sessions like the default store of the client while noting
+/// whether the server handed out any.
+#[derive(Debug)]
+struct Sessions {
+  cache: ClientSessionMemoryCache,
+  issued: AtomicBool,
+}
+
+impl Sessions {
+  fn new() -> Self {
+    Self {
+      cache: ClientSessionMemoryCache::new(256),
+      issued: AtomicBool::new(false),
+    }
+  }
+}
+
+impl ClientSessionStore for Sessions {
+  fn set_kx_hint(&self, server_name: ServerName<'static>, group: NamedGroup) {
+    self.cache.set_kx_hint(server_name, group)
+  }
+
+  fn kx_hint(&self, server_name: &ServerName<'_>) -> Option<NamedGroup> {
+    self.cache.kx_hint(server_name)
+  }
+
+  fn set_tls12_session(&self, server_name: ServerName<'static>, value: Tls12ClientSessionValue) {
+    self.issued.store(true, Ordering::Relaxed);
+    self.cache.set_tls12_session(server_name, value)
+  }
+
+  fn tls12_session(&self, server_name: &ServerName<'_>) -> Option<Tls12ClientSessionValue> {
+    self.cache.tls12_session(server_name)
+  }
+
+  fn remove_tls12_session(&self, server_name: &ServerName<'static>) {
+    self.cache.remove_tls12_session(server_name)
+  }
+
+  fn insert_tls13_ticket(&self, server_name: ServerName<'static>, value: Tls13ClientSessionValue) {
+    self.issued.store(true, Ordering::Relaxed);
+    self.cache.insert_tls13_ticket(server_name, value)
+  }
+
+  fn take_tls13_ticket(
+    &self,
+    server_name: &ServerName<'static>,
+  ) -> Option<Tls13ClientSessionValue> {
+    self.cache.take_tls13_ticket(server_name)
+  }
+}
+
+// The certificates are assessed rather than trusted, so any chain is
+// accepted. Signatures made during the handshake are still verified
+// to make sure the server holds the key in its certificate.
+#[derive(Debug)]
+struct AcceptAnyCertificate(CryptoProvider);
+
+impl ServerCertVerifier for AcceptAnyCertificate {
+  fn verify_server_cert(
+    &self,
+    _end_entity: &CertificateDer<'_>,
+    _intermediates: &[CertificateDer<'_>],
+    _server_name: &ServerName<'_>,
+    _ocsp_response: &[u8],
+    _now: UnixTime,
+  ) -> Result<ServerCertVerified, rustls::Error> {
+    Ok(ServerCertVerified::assertion())
+  }
+
+  fn verify_tls12_signature(
+    &self,
+    message: &[u8],
+    cert: &CertificateDer<'_>,
+    dss: &DigitallySignedStruct,
+  ) -> Result<HandshakeSignatureValid, rustls::Error> {
+    crypto::verify_tls12_signature(
+      message,
+      cert,
+      dss,
+      &self.0.signature_verification_algorithms,
+    )
+  }
+
+  fn verify_tls13_signature(
+    &self,
+    message: &[u8],
+    cert: &CertificateDer<'_>,
+    dss: &DigitallySignedStruct,
+  ) -> Result<HandshakeSignatureValid, rustls::Error> {
+    crypto::verify_tls13_signature(
+      message,
+      cert,
+      dss,
+      &self.0.signature_verification_algorithms,
+    )
+  }
+
+  fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+    self.0.signature_verification_algorithms.supported_schemes()
+  }
+}
+
+/// Splits a URL such as `https://example.com:8443/path` into the host
+/// and port to connect to.
+///
+/// The scheme is optional and the port defaults to 443.
+pub fn parse_url(url: &str) -> Result<(String, u16), String> {
+  let rest = url.strip_prefix("https://").unwrap_or(url);
+  if rest.contains("://") {
+    return Err("only https URLs are supported".to_string());
+  }
+  let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+  let authority = authority.rsplit('@').next().unwrap_or_default();
+  // IPv6 addresses are enclosed in brackets to set them apart from the
+  // port.
+  let (host, port) = match authority.strip_prefix('[') {
+    Some(bracketed) => {
+      let (host, rest) = bracketed
+        .split_once(']')
+        .ok_or_else(|| "unterminated IPv6 address".to_string())?;
+      (host, rest.strip_prefix(':'))
+    },
+    None => match authority.rsplit_once(':') {
+      Some((host, port)) => (host, Some(port)),
+      None => (authority, None),
+    },
+  };
+  if host.is_empty() {
+    return Err("missing host".to_string());
+  }
+  let port = match port {
+    Some(port) => port
+      .parse()
+      .map_err(|_| format!("invalid port: {}", port))?,
+    None => 443,
+  };
+  Ok((host.to_string(), port))
+}
+
+/// Performs a TLS handshake with the server and returns the parameters
+/// that were negotiated, giving up on a server that does not respond
+/// within `timeout`.
+///
+/// If `resume` is set, a second handshake tries to resume the session
+/// of the first to find out how the server resumes sessions.
+pub fn handshake(
+  host: &str,
+  port: u16,
+  timeout: Duration,
+  resume: bool,
+) -> Result<Handshake, Error> {
+  let provider = crypto::ring::default_provider();
+  let verifier = AcceptAnyCertificate(provider.clone());
+  let sessions = Arc::new(Sessions::new());
+  let mut config = ClientConfig::builder_with_provider(Arc::new(provider))
+    .with_safe_default_protocol_versions()
+    .map_err(|err| Error::Tls(err.to_string()))?
+    .dangerous()
+    .with_custom_certificate_verifier(Arc::new(verifier))
+    .with_no_client_auth();
+  config.resumption = rustls::client::Resumption::store(sessions.clone());
+  let config = Arc::new(config);
+  let server_name =
+    ServerName::try_from(host.to_string()).map_err(|err| Error::Tls(err.to_string()))?;
+  let (mut connection, mut socket) = connect_tls(&config, &server_name, host, port, timeout)?;
+  let resumption = if resume {
+    // The tickets of TLS 1.3 only arrive after the handshake.
+    if connection.protocol_version() == Some(ProtocolVersion::TLSv1_3) {
+      receive_tickets(&mut connection, &mut socket, timeout)?;
+    }
+    // Some servers only serve one connection at a time.
+    connection.send_close_notify();
+    let _ = connection.write_tls(&mut socket);
+    drop(socket);
+    let (resumed, _) = connect_tls(&config, &server_name, host, port, timeout)?;
+    let fresh_keys = resumed.protocol_version() == Some(ProtocolVersion::TLSv1_3);
+    let resumed = resumed.handshake_kind() == Some(HandshakeKind::Resumed);
+    Some(Resumption {
+      issued: sessions.issued.load(Ordering::Relaxed),
+      resumed,
+      fresh_keys: resumed && fresh_keys,
+    })
+  } else {
+    None
+  };
+
+  let origin = if host.contains(':') {
+    format!("[{}]:{}", host, port)
+  } else {
+    format!("{}:{}", host, port)
+  };
+  let chain = connection
+    .peer_certificates()
+    .unwrap_or_default()
+    .iter()
+    .map(|der| Entry::new(&origin, der.to_vec()))
+    .collect();
+  let group = connection
+    .negotiated_key_exchange_group()
+    .map(|group| group.name());
+  let suite = connection
+    .negotiated_cipher_suite()
+    .map(|suite| suite.suite());
+  Ok(Handshake {
+    chain,
+    key_exchange: group.and_then(key_exchange),
+    cipher: suite.and_then(cipher),
+    group: group
+      .map(|group| format!("{:?}", group))
+      .unwrap_or_default(),
+    suite: suite
+      .map(|suite| format!("{:?}", suite))
+      .unwrap_or_default(),
+    resumption,
+  })
+}
+
+/// Connects to the server and completes a TLS handshake with it.
+fn connect_tls(
+  config: &Arc<ClientConfig>,
+  server_name: &ServerName<'static>,
+  host: &str,
+  port: u16,
+  timeout: Duration,
+) -> Result<(ClientConnection, TcpStream), Error> {
+  let mut connection = ClientConnection::new(config.clone(), server_name.clone())
+    .map_err(|err| Error::Tls(err.to_string()))?;
+  let mut socket = connect(host, port, timeout).map_err(|err| Error::Tls(err.to_string()))?;
+  while connection.is_handshaking() {
+    connection
+      .complete_io(&mut socket)
+      .map_err(|err| Error::Tls(err.to_string()))?;
+  }
+  Ok((connection, socket))
+}
+
+/// Reads the session tickets the server sends after the handshake,
+/// waiting briefly since a server need not send any.
+fn receive_tickets(
+  connection: &mut ClientConnection,
+  socket: &mut TcpStream,
+  timeout: Duration,
+) -> Result<(), Error> {
+  socket
+    .set_read_timeout(Some(TICKET_WAIT.min(timeout)))
+    .map_err(|err| Error::Tls(err.to_string()))?;
+  if connection.read_tls(socket).is_ok() {
+    connection
+      .process_new_packets()
+      .map_err(|err| Error::Tls(err.to_string()))?;
+  }
+  Ok(())
+}
+
+/// Connects to a server over TLS, verifying its certificate against the
+/// trust store of the operating system.
+pub fn connect_verified(
+  host: &str,
+  port: u16,
+) -> Result<StreamOwned<ClientConnection, TcpStream>, Error> {
+  let mut roots = RootCertStore::empty();
+  roots.add_parsable_certificates(rustls_native_certs::load_native_certs().certs);
+  let config = ClientConfig::builder_with_provider(Arc::new(crypto::ring::default_provider()))
+    .with_safe_default_protocol_versions()
+    .map_err(|err| Error::Tls(err.to_string()))?
+    .with_root_certificates(roots)
+    .with_no_client_auth();
+  let server_name =
+    ServerName::try_from(host.to_string()).map_err(|err| Error::Tls(err.to_string()))?;
+  let connection = ClientConnection::new(Arc::new(config), server_name)
+    .map_err(|err| Error::Tls(err.to_string()))?;
+  let socket = connect(host, port, TIMEOUT).map_err(|err| Error::Tls(err.to_string()))?;
+  Ok(StreamOwned::new(connection, socket))
+}
+
+/// Opens a TCP connection to a server which times out reads and writes
+/// after `timeout` as well.
+pub fn connect(host: &str, port: u16, timeout: Duration) -> io::Result<TcpStream> {
+  let mut last_err = io::Error::new(io::ErrorKind::NotFound, "host not found");
+  for addr in (host, port).to_socket_addrs()? {
+    match TcpStream::connect_timeout(&addr, timeout) {
+      Ok(socket) => {
+        socket.set_read_timeout(Some(timeout))?;
+        socket.set_write_timeout(Some(timeout))?;
+        return Ok(socket);
+      },
+      Err(err) => last_err = err,
+    }
+  }
+  Err(last_err)
+}
+
+fn key_exchange(group: NamedGroup) -> Option<Asymmetric> {
+  registry::find_tls_group(group.into()).map(Asymmetric::from)
+}
+
+fn cipher(suite: CipherSuite) -> Option<Symmetric> {
+  registry::find_tls_cipher_suite(suite.into())
+}
+
+/// Audits the certificate chain of a handshake for every audience, with
+/// the negotiated parameters reported alongside the certificate of the
+/// server. A certificate that cannot be read is left out.
+pub fn audit(audiences: &Audiences, handshake: Handshake) -> Vec<Audit> {
+  let mut audits = Vec::new();
+  for (i, entry) in handshake.chain.iter().enumerate() {
+    match Certificate::from_bytes(&entry.der) {
+      Ok(certificate) => {
+        audits.extend(audit_audiences(audiences, |settings| {
+          let mut audit = audit_key(settings, &X509Reader, &entry.label, &certificate);
+          // The negotiated parameters are reported alongside the
+          // certificate of the server itself.
+          if i == 0 {
+            audit_session(settings, &handshake, &mut audit);
+          }
+          audit
+        }));
+      },
+      Err(err) => eprintln!("{}: {}", entry.label.display(), err),
+    }
+  }
+  audits
+}
+
+/// Assesses the key exchange and cipher of a handshake, and how the
+/// server resumes sessions.
+fn audit_session(settings: Settings, handshake: &Handshake, audit: &mut Audit) {
+  let Settings {
+    ctx,
+    guide,
+    locale,
+    hazards,
+    ..
+  } = settings;
+  let hazards = hazards.unwrap_or(guide.hazards());
+  match handshake.key_exchange {
+    Some(got) => {
+      let verdict = guide.assess_signature_algorithm(ctx, got);
+      audit.cite(verdict.citation);
+      audit.explain(&verdict, ctx, got, locale);
+      match verdict.into_result() {
+        Ok(want) => audit.compliant_key_exchange(got, want),
+        Err(want) => audit.noncompliant_key_exchange(got, want),
+      }
+    },
+    None => audit.warn(format!(
+      "key exchange group {} is not assessed",
+      handshake.group
+    )),
+  }
+  match handshake.cipher {
+    Some(got) => {
+      let verdict = guide.assess_symmetric(ctx, got);
+      audit.cite(verdict.citation);
+      audit.explain(&verdict, ctx, got, locale);
+      match verdict.into_result() {
+        Ok(want) => audit.compliant_cipher(got, want),
+        Err(want) => audit.noncompliant_cipher(got, want),
+      }
+      // Every cipher suite with 64-bit blocks uses them in CBC mode.
+      advise_cipher(settings, got, Mode::Cbc, audit);
+    },
+    None => audit.warn(format!("cipher of {} is not assessed", handshake.suite)),
+  }
+  for hazard in Hazard::of_tls_cipher_suite(&handshake.suite) {
+    if hazards.contains(hazard) {
+      audit.advise(hazard.advisory(), &handshake.suite);
+    }
+  }
+  if let Some(resumption) = handshake.resumption {
+    audit.resume(resumption);
+    // BSI TR-02102-2 recommends resuming TLS 1.3 sessions with
+    // psk_dhe_ke alone so that resumed sessions keep forward secrecy.
+    if resumption.resumed && !resumption.fresh_keys {
+      audit.warn(
+        "the server resumes sessions without a fresh key exchange, so they lack forward secrecy; BSI TR-02102-2 recommends TLS 1.3 resumption with psk_dhe_ke"
+          .to_string(),
+      );
+    }
+  }
+}
@@ -0,0 +1,140 @@
+//! Monitor files and directories for keys that have been created or
+//! modified.
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Keeps track of the modification times of the files found under a
+/// set of paths so that only keys that have changed between scans are
+/// assessed again.
+///
+/// Directories are traversed recursively, without following links to
+/// other directories. This works by rescanning the file system on every
+/// call to [`Watcher::changed`] instead of relying on platform specific
+/// notification mechanisms such as inotify which keeps it portable and
+/// means it also works for network mounts where notifications are not
+/// always delivered.
+pub struct Watcher {
+  paths: Vec<PathBuf>,
+  seen: HashMap<PathBuf, SystemTime>,
+}
+
+impl Watcher {
+  pub fn new(paths: &[PathBuf]) -> Self {
+    Self {
+      paths: paths.to_vec(),
+      seen: HashMap::new(),
+    }
+  }
+
+  /// Returns the files that have been created or modified since the
+  /// last call, sorted by path. The first call returns every file.
+  pub fn changed(&mut self) -> Vec<PathBuf> {
+    let mut current = HashMap::new();
+    for path in self.paths.iter() {
+      Self::walk(path, &mut current);
+    }
+    let mut changed: Vec<_> = current
+      .iter()
+      .filter(|(path, modified)| self.seen.get(*path) != Some(modified))
+      .map(|(path, _)| path.clone())
+      .collect();
+    changed.sort();
+    // Replacing the map also forgets about files that have since been
+    // removed so they are picked up again if they are restored.
+    self.seen = current;
+    changed
+  }
+
+  /// Returns the files that were found by the last scan.
+  pub fn files(&self) -> impl Iterator<Item = &Path> {
+    self.seen.keys().map(PathBuf::as_path)
+  }
+
+  fn walk(path: &Path, files: &mut HashMap<PathBuf, SystemTime>) {
+    let metadata = match fs::metadata(path) {
+      Ok(metadata) => metadata,
+      // The file could have been removed in the middle of a scan.
+      Err(_) => return,
+    };
+    if metadata.is_dir() {
+      if let Ok(entries) = fs::read_dir(path) {
+        for entry in entries.flatten() {
+          let path = entry.path();
+          // Links to directories are not followed since they could
+          // lead back to a directory that is being walked.
+          let link = fs::symlink_metadata(&path).is_ok_and(|m| m.is_symlink());
+          if link && path.is_dir() {
+            continue;
+          }
+          Self::walk(&path, files);
+        }
+      }
+    } else if let Ok(modified) = metadata.modified() {
+      files.insert(path.to_path_buf(), modified);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::fs::File;
+  use std::time::Duration;
+
+  use super::*;
+
+  /// Moves the modification time of a file forward, since writing to it
+  /// again may leave it unchanged on file systems with coarse times.
+  fn touch(path: &Path) {
+    let later = SystemTime::now() + Duration::from_secs(60);
+    File::options()
+      .write(true)
+      .open(path)
+      .and_then(|file| file.set_modified(later))
+      .unwrap();
+  }
+
+  #[test]
+  fn reports_created_and_modified_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let a = dir.path().join("a.pem");
+    let b = dir.path().join("b.pem");
+    fs::write(&a, "a").unwrap();
+    let mut watcher = Watcher::new(&[dir.path().to_path_buf()]);
+    assert_eq!(watcher.changed(), vec![a.clone()]);
+    assert!(watcher.changed().is_empty());
+    fs::write(&b, "b").unwrap();
+    assert_eq!(watcher.changed(), vec![b.clone()]);
+    touch(&a);
+    assert_eq!(watcher.changed(), vec![a]);
+  }
+
+  #[test]
+  fn forgets_deleted_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let a = dir.path().join("a.pem");
+    fs::write(&a, "a").unwrap();
+    let mut watcher = Watcher::new(&[dir.path().to_path_buf()]);
+    assert_eq!(watcher.changed(), vec![a.clone()]);
+    fs::remove_file(&a).unwrap();
+    assert!(watcher.changed().is_empty());
+    assert_eq!(watcher.files().count(), 0);
+    fs::write(&a, "a").unwrap();
+    assert_eq!(watcher.changed(), vec![a]);
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn skips_links_to_directories() {
+    let dir = tempfile::tempdir().unwrap();
+    let keys = dir.path().join("keys");
+    fs::create_dir(&keys).unwrap();
+    let key = keys.join("a.pem");
+    fs::write(&key, "a").unwrap();
+    std::os::unix::fs::symlink(&keys, keys.join("loop")).unwrap();
+    std::os::unix::fs::symlink(&key, keys.join("b.pem")).unwrap();
+    let mut watcher = Watcher::new(&[dir.path().to_path_buf()]);
+    assert_eq!(watcher.changed(), vec![key, keys.join("b.pem")]);
+  }
+}
@@ -0,0 +1,191 @@
+//! Measure the time taken by the hot paths of validation.
+//!
+//! Run with `cargo bench -p wardstone_core --all-features --bench
+//! validation`, optionally followed by `--` and a filter such as
+//! `single` to run only the benchmarks whose name contains it.
+//! Criterion compares every run with the previous one and reports
+//! changes it finds significant, and `--save-baseline` and `--baseline`
+//! compare against a named run instead. The benchmarks of certificates
+//! need the `x509` feature and the parallel one the `rayon` feature too.
+use std::hint::black_box;
+
+#[cfg(feature = "x509")]
+use criterion::Throughput;
+use criterion::{criterion_group, criterion_main, Criterion};
+#[cfg(feature = "rayon")]
+use wardstone_core::batch;
+#[cfg(feature = "x509")]
+use wardstone_core::certificate;
+use wardstone_core::context::Context;
+use wardstone_core::primitive::asymmetric::Asymmetric;
+use wardstone_core::primitive::ecc::P256;
+use wardstone_core::primitive::ffc::DSA_2048_224;
+use wardstone_core::primitive::hash::{Hash, SHA1, SHA256};
+use wardstone_core::primitive::ifc::RSA_PKCS1_2048;
+use wardstone_core::primitive::symmetric::AES128;
+use wardstone_core::registry;
+use wardstone_core::standard::bsi::Bsi;
+use wardstone_core::standard::cnsa::Cnsa;
+use wardstone_core::standard::ecrypt::Ecrypt;
+use wardstone_core::standard::lenstra::Lenstra;
+use wardstone_core::standard::nist::Nist;
+use wardstone_core::standard::Standard;
+
+/// Certificates using the kinds of keys most commonly found in the
+/// wild. The test certificates are not used since they are stored with
+/// Git LFS and may not have been fetched.
+#[cfg(feature = "x509")]
+const CERTIFICATES: [&str; 3] = [
+  "-----BEGIN CERTIFICATE-----
+MIIBejCCASGgAwIBAgIUSkfQi8lpbUwDjTrBZUkTgDfmvwAwCgYIKoZIzj0EAwIw
+EjEQMA4GA1UEAwwHZXhhbXBsZTAgFw0yNjEwMTYyMDQ5NTVaGA8yMTI2MDkyMjIw
+NDk1NVowEjEQMA4GA1UEAwwHZXhhbXBsZTBZMBMGByqGSM49AgEGCCqGSM49AwEH
+A0IABFoLH6kUTKAy7dIJbNygmxVFEXpf5Y7Y8ILTn/Q/OR/YQtNBs4z50/60kKk/
+zg66ozJm+m95/wh35omP3iqlyFajUzBRMB0GA1UdDgQWBBS8EyJf2Bu65jxlb0ZU
+XgtbTYUvBzAfBgNVHSMEGDAWgBS8EyJf2Bu65jxlb0ZUXgtbTYUvBzAPBgNVHRMB
+Af8EBTADAQH/MAoGCCqGSM49BAMCA0cAMEQCIDoP0IQrCmFzVPqcjxcyvPSawygy
+PQbqQ9GlrnYRUFWzAiBgQCc+zmR0YqrTeTSimZMekTaXG7osnyGlISHED8gaFA==
+-----END CERTIFICATE-----
+",
+  "-----BEGIN CERTIFICATE-----
+MIIDBzCCAe+gAwIBAgIUKbGyrctmzm8NtR68JqRSFQ2woZ0wDQYJKoZIhvcNAQEL
+BQAwEjEQMA4GA1UEAwwHZXhhbXBsZTAgFw0yNjEwMTYyMDQ5NTVaGA8yMTI2MDky
+MjIwNDk1NVowEjEQMA4GA1UEAwwHZXhhbXBsZTCCASIwDQYJKoZIhvcNAQEBBQAD
+ggEPADCCAQoCggEBALVQD0dDFwPJS4IbT3d2Prnj/Dltk9TGWreLUMlM4gZhI/6z
+RG6foSz5xtQoChX/ykQkrnqjZ59TeVQ6qLJiJ05QgVS7Ht0LP7hQO8hrhx3XoHDC
+8iTvr58O24v1cUFG8LOt/cnXCqEwxSQYVcuDzbcMBRUf8bMwNPopzIi/i89M/L3j
+HsKeRD3zTdzU3bxi+amaYql2LlQ23yv05Emlfx+xGsBzKvS53vui5Kkufdu4NE/Z
+1/2K4qPgDVDLLqd0yYjxvawIvarKyrLQpWrjiteGXJ4vNy2B1V7k9obzPfk8KRUc
+zotSXPokA8fs3vcLfDWAi5lidH+X0McKjRvJmX8CAwEAAaNTMFEwHQYDVR0OBBYE
+FPGfYHrKA73Im/f+qkLdECnEukHpMB8GA1UdIwQYMBaAFPGfYHrKA73Im/f+qkLd
+ECnEukHpMA8GA1UdEwEB/wQFMAMBAf8wDQYJKoZIhvcNAQELBQADggEBAB2vY/nS
+OPekYb8GbwAGkSBE9aLeQZyzmj1ElPl4hZLZEEZ2xXth0tiZjqVOyh4sdY8t8idv
++5MzZqoL1evfbgX1DOp0ri7nB0OAVlmUcP7/QruwTKeOj9qFPfYXnZ+no1tKuCEM
+tqBLSjAw+bMWZdea4nRDY4Xp65GnTGM9dsrjJXIBBmxX3qAgpcccmLExHNPFdmIU
+Kn+RA0fj9NKOBIH+lmANM5zbXBLAkJJJo33lSAVGHVEm8NkAbJtcf4U4AFwCwzPl
+KkFby7Tsu50uz5ku8Eg65f6MGmWLTozNMthnPGWBxqGw7YqJyxEP1JFYCSf1D1VW
+HjGmfEToczYqlOw=
+-----END CERTIFICATE-----
+",
+  "-----BEGIN CERTIFICATE-----
+MIIBOjCB7aADAgECAhQfvM/UvfvJH5qQyoL/HQIEDfsd7zAFBgMrZXAwEjEQMA4G
+A1UEAwwHZXhhbXBsZTAgFw0yNjEwMTYxOTI3MTdaGA8yMTI2MDkyMjE5MjcxN1ow
+EjEQMA4GA1UEAwwHZXhhbXBsZTAqMAUGAytlcAMhAPh0tWZ6Op7H46skElql6we8
+RGB/qDeYr6DFSZwTF8gZo1MwUTAdBgNVHQ4EFgQU+iXZ5UI+5kFamJr3o6HCPTTM
+WN0wHwYDVR0jBBgwFoAU+iXZ5UI+5kFamJr3o6HCPTTMWN0wDwYDVR0TAQH/BAUw
+AwEB/zAFBgMrZXADQQAijC7N6FmH6y6CPqey6m2bEcoz3/hzgc7zzQJ3FPYEO65o
+8fX6tAwKyLe1blqxx1OEW/PNUUZ0Facie8F3/iIF
+-----END CERTIFICATE-----
+",
+];
+
+/// The number of certificates assessed at once in the bulk benchmarks,
+/// on the order of a large trust store.
+#[cfg(feature = "x509")]
+const BULK: usize = 1_000;
+
+/// Validates the primitives of a certificate against every guide.
+fn validate(ctx: Context, hash: Option<Hash>, key: Asymmetric) {
+  if let Some(hash) = hash {
+    let _ = black_box(Bsi::validate_hash(ctx, hash));
+    let _ = black_box(Cnsa::validate_hash(ctx, hash));
+    let _ = black_box(Ecrypt::validate_hash(ctx, hash));
+    let _ = black_box(Lenstra::validate_hash(ctx, hash));
+    let _ = black_box(Nist::validate_hash(ctx, hash));
+  }
+  let _ = black_box(Bsi::validate_asymmetric(ctx, key));
+  let _ = black_box(Cnsa::validate_asymmetric(ctx, key));
+  let _ = black_box(Ecrypt::validate_asymmetric(ctx, key));
+  let _ = black_box(Lenstra::validate_asymmetric(ctx, key));
+  let _ = black_box(Nist::validate_asymmetric(ctx, key));
+}
+
+fn single(c: &mut Criterion) {
+  let ctx = Context::default();
+  let mut group = c.benchmark_group("single");
+  group.bench_function("validate_hash", |b| {
+    b.iter(|| Nist::validate_hash(black_box(ctx), black_box(SHA1)))
+  });
+  group.bench_function("validate_ecc", |b| {
+    b.iter(|| Nist::validate_ecc(black_box(ctx), black_box(P256)))
+  });
+  group.bench_function("validate_ffc", |b| {
+    b.iter(|| Nist::validate_ffc(black_box(ctx), black_box(DSA_2048_224)))
+  });
+  group.bench_function("validate_ifc", |b| {
+    b.iter(|| Nist::validate_ifc(black_box(ctx), black_box(RSA_PKCS1_2048)))
+  });
+  group.bench_function("validate_symmetric", |b| {
+    b.iter(|| Nist::validate_symmetric(black_box(ctx), black_box(AES128)))
+  });
+  group.bench_function("assess_hash", |b| {
+    b.iter(|| Nist::assess_hash(black_box(ctx), black_box(SHA1)))
+  });
+  group.bench_function("registry_find", |b| {
+    b.iter(|| registry::find(black_box("sha256")))
+  });
+  group.bench_function("registry_find_oid", |b| {
+    b.iter(|| registry::find_oid(black_box("1.2.840.10045.3.1.7")))
+  });
+  group.finish();
+}
+
+fn composite(c: &mut Criterion) {
+  let ctx = Context::default();
+  let mut group = c.benchmark_group("composite");
+  group.bench_function("validate_asymmetric", |b| {
+    b.iter(|| Bsi::validate_asymmetric(black_box(ctx), black_box(RSA_PKCS1_2048.into())))
+  });
+  group.bench_function("assess_asymmetric", |b| {
+    b.iter(|| Bsi::assess_asymmetric(black_box(ctx), black_box(RSA_PKCS1_2048.into())))
+  });
+  group.bench_function("every_guide", |b| {
+    b.iter(|| validate(black_box(ctx), Some(SHA256), black_box(P256.into())))
+  });
+  group.finish();
+}
+
+#[cfg(feature = "x509")]
+fn bulk(c: &mut Criterion) {
+  let ctx = Context::default();
+  for pem in CERTIFICATES {
+    certificate::primitives(pem.as_bytes()).expect("benchmark certificate is valid");
+  }
+  let certificates: Vec<&[u8]> = CERTIFICATES
+    .iter()
+    .cycle()
+    .take(BULK)
+    .map(|pem| pem.as_bytes())
+    .collect();
+  let mut group = c.benchmark_group("bulk");
+  group.bench_function("certificate_primitives", |b| {
+    b.iter(|| certificate::primitives(black_box(certificates[0])))
+  });
+  group.throughput(Throughput::Elements(BULK as u64));
+  group.bench_function("assess_certificates", |b| {
+    b.iter(|| {
+      for data in certificates.iter() {
+        if let Ok((hash, key)) = certificate::primitives(data) {
+          validate(ctx, hash, key);
+        }
+      }
+    })
+  });
+  #[cfg(feature = "rayon")]
+  group.bench_function("assess_certificates_parallel", |b| {
+    b.iter(|| {
+      batch::validate_all(ctx, &certificates, |ctx, data| {
+        if let Ok((hash, key)) = certificate::primitives(data) {
+          validate(ctx, hash, key);
+        }
+      })
+    })
+  });
+  group.finish();
+}
+
+#[cfg(feature = "x509")]
+criterion_group!(benches, single, composite, bulk);
+#[cfg(not(feature = "x509"))]
+criterion_group!(benches, single, composite);
+criterion_main!(benches);
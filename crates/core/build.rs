@@ -0,0 +1,183 @@
+//! Generate the tables of the registry from the files in `data`.
+//!
+//! The elliptic curves are listed in `curves.csv` along with the names
+//! and object identifiers they are known by. The codepoints used in TLS
+//! are read from snapshots of the IANA TLS Supported Groups and TLS
+//! Cipher Suites registries, downloaded as CSV from
+//! <https://www.iana.org/assignments/tls-parameters/>, and matched to
+//! primitives by name. Keeping the mappings current is a matter of
+//! replacing the snapshots.
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+const DATA: &str = "data";
+
+/// An elliptic curve as listed in `curves.csv`.
+struct Curve {
+  name: String,
+  aliases: Vec<String>,
+  oids: Vec<String>,
+  primitive: String,
+}
+
+fn main() {
+  println!("cargo:rerun-if-changed={}", DATA);
+  let curves: Vec<Curve> = read("curves.csv")
+    .into_iter()
+    .map(|record| Curve {
+      name: record[0].clone(),
+      aliases: words(&record[1]),
+      oids: words(&record[2]),
+      primitive: record[3].clone(),
+    })
+    .collect();
+
+  let mut out = String::new();
+  writeln!(
+    out,
+    "/// The elliptic curves defined in [`crate::primitive::ecc`].\npub static CURVES: [Entry<Ecc>; {}] = [",
+    curves.len()
+  )
+  .unwrap();
+  for curve in curves.iter() {
+    writeln!(
+      out,
+      "  Entry {{ name: {:?}, aliases: &{:?}, oids: &{:?}, primitive: {} }},",
+      curve.name, curve.aliases, curve.oids, curve.primitive
+    )
+    .unwrap();
+  }
+  out.push_str("];\n\n");
+
+  // The brainpool curves were given new codepoints for TLS 1.3 which
+  // only differ from the old ones in how they may be used.
+  let groups = codepoints("tls-supported-groups.csv", |description| {
+    let name = normalize(description.trim_end_matches("tls13"));
+    curves
+      .iter()
+      .find(|curve| {
+        normalize(&curve.name) == name || curve.aliases.iter().any(|alias| normalize(alias) == name)
+      })
+      .map(|curve| curve.primitive.clone())
+  });
+  map(
+    &mut out,
+    "The elliptic curves by their codepoints in the TLS Supported Groups\n/// registry.",
+    "TLS_GROUPS",
+    "Ecc",
+    &groups,
+  );
+
+  let ciphers = read("tls-ciphers.csv");
+  let suites = codepoints("tls-cipher-suites.csv", |description| {
+    // Export grade suites use weakened keys which are not modelled.
+    if description.contains("EXPORT") {
+      return None;
+    }
+    let suite = description.strip_prefix("TLS_")?;
+    // Suites for TLS 1.3 leave out the key exchange and authentication.
+    let bulk = suite.split_once("_WITH_").map_or(suite, |(_, bulk)| bulk);
+    ciphers
+      .iter()
+      .find(|cipher| {
+        bulk
+          .strip_prefix(cipher[0].as_str())
+          .is_some_and(|rest| rest.is_empty() || rest.starts_with('_'))
+      })
+      .map(|cipher| cipher[1].clone())
+  });
+  map(
+    &mut out,
+    "The symmetric ciphers by the codepoints of the TLS cipher suites\n/// that use them.",
+    "TLS_CIPHER_SUITES",
+    "Symmetric",
+    &suites,
+  );
+
+  let path = Path::new(&env::var_os("OUT_DIR").unwrap()).join("tables.rs");
+  fs::write(path, out).unwrap();
+}
+
+/// Reads the codepoints in an IANA registry whose descriptions `f`
+/// finds a primitive for. Ranges of unassigned or reserved codepoints
+/// are skipped.
+fn codepoints(file: &str, f: impl Fn(&str) -> Option<String>) -> BTreeMap<u16, String> {
+  let mut codepoints = BTreeMap::new();
+  for record in read(file) {
+    let value = record[0].split(',').try_fold(0u16, |value, byte| {
+      let byte = byte.trim();
+      let byte = byte.strip_prefix("0x").map_or_else(
+        || byte.parse::<u16>().ok(),
+        |hex| u8::from_str_radix(hex, 16).ok().map(u16::from),
+      )?;
+      value.checked_mul(0x100).map(|value| value + byte)
+    });
+    let (Some(value), Some(primitive)) = (value, f(&record[1])) else {
+      continue;
+    };
+    codepoints.insert(value, primitive);
+  }
+  codepoints
+}
+
+fn map(out: &mut String, doc: &str, name: &str, ty: &str, entries: &BTreeMap<u16, String>) {
+  writeln!(
+    out,
+    "/// {}\nstatic {}: phf::Map<u16, {}> = phf::phf_map! {{",
+    doc, name, ty
+  )
+  .unwrap();
+  for (value, primitive) in entries {
+    writeln!(out, "  {:#06x}u16 => {},", value, primitive).unwrap();
+  }
+  out.push_str("};\n\n");
+}
+
+/// Reads the records of a CSV file without its header.
+fn read(file: &str) -> Vec<Vec<String>> {
+  let path = Path::new(DATA).join(file);
+  let data = fs::read_to_string(&path)
+    .unwrap_or_else(|err| panic!("cannot read {}: {}", path.display(), err));
+  let mut records = Vec::new();
+  let mut record = Vec::new();
+  let mut field = String::new();
+  let mut quoted = false;
+  let mut chars = data.chars().peekable();
+  while let Some(c) = chars.next() {
+    match c {
+      '"' if quoted && chars.peek() == Some(&'"') => {
+        field.push('"');
+        chars.next();
+      },
+      '"' => quoted = !quoted,
+      ',' if !quoted => record.push(std::mem::take(&mut field)),
+      '\n' if !quoted => {
+        record.push(std::mem::take(&mut field));
+        records.push(std::mem::take(&mut record));
+      },
+      '\r' if !quoted => {},
+      c => field.push(c),
+    }
+  }
+  if !field.is_empty() || !record.is_empty() {
+    record.push(field);
+    records.push(record);
+  }
+  records.into_iter().skip(1).collect()
+}
+
+fn words(field: &str) -> Vec<String> {
+  field.split_whitespace().map(String::from).collect()
+}
+
+/// Normalises names the same way as the registry does.
+fn normalize(name: &str) -> String {
+  name
+    .chars()
+    .filter(|c| !matches!(c, '-' | '_' | '/'))
+    .flat_map(char::to_lowercase)
+    .collect()
+}
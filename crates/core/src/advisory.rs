@@ -0,0 +1,478 @@
+//! Flag primitives whose implementations are known to be easy to get
+//! wrong.
+//!
+//! A primitive can be strong enough for a standard and still be a
+//! hazard to implement: the security of ECDSA rests on every nonce
+//! being secret and uniform, decrypting with PKCS #1 v1.5 padding tends
+//! to act as a padding oracle and so does CBC when the MAC is checked
+//! after decrypting, and an authenticated mode with a truncated tag
+//! trades resistance to forgeries for shorter messages. These hazards do not make a primitive
+//! non-compliant, so they are raised as informational advisories next
+//! to the verdicts of a standard. The same goes for threshold signing,
+//! where a single party that cheats in threshold ECDSA or aggregates a
+//! rogue BLS key can undo the split of the key, and for stateful
+//! hash-based signatures, where signing twice with the same one-time
+//! key lets anyone forge. A cipher with 64-bit blocks that encrypts
+//! bulk data is raised too, as its blocks collide long before its key
+//! is at risk, with the amount of data the standard lets one key
+//! encrypt (see
+//! [`Standard::block_limit`](crate::standard::Standard::block_limit)).
+//!
+//! Every standard raises the hazards returned by
+//! [`Standard::hazards`](crate::standard::Standard::hazards), which
+//! callers may narrow further, for example from a policy file.
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::primitive::asymmetric::Asymmetric;
+use crate::primitive::ecc::{ED25519, ED25519PH, ED448, ED448PH, X25519, X448};
+use crate::primitive::ifc::ID_RSA_PKCS1;
+use crate::primitive::symmetric::{Mode, Symmetric};
+use crate::primitive::threshold::{Scheme, Threshold};
+use crate::primitive::Primitive;
+use crate::standard::rule::Citation;
+use crate::Error;
+
+/// A class of implementation hazard.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Hazard {
+  /// CBC with the MAC checked after decrypting, which leaks whether the
+  /// padding is valid.
+  CbcPadding,
+  /// Decrypting with RSA and PKCS #1 v1.5 padding, which leaks whether
+  /// the padding is valid as Bleichenbacher showed.
+  Pkcs1Encryption,
+  /// Signature schemes such as ECDSA and DSA whose key is recovered from
+  /// a reused or biased nonce.
+  NonceSensitivity,
+  /// Authenticated encryption with a truncated tag, such as AES-CCM with
+  /// an 8-byte tag, which makes forgeries more likely to succeed.
+  ShortTag,
+  /// Threshold ECDSA protocols in which a party that sends ill-formed
+  /// values, such as a Paillier modulus with small factors, learns the
+  /// shares of the others.
+  MaliciousParty,
+  /// Aggregated signatures such as BLS, where a party that chooses its
+  /// key after seeing those of the others forges for all of them.
+  RogueKey,
+  /// Stateful hash-based signatures such as LMS and XMSS, where signing
+  /// twice from the same state, as after restoring a backup, reuses a
+  /// one-time key.
+  StateReuse,
+  /// Ciphers with 64-bit blocks such as TDEA and Blowfish, whose blocks
+  /// are likely to collide and leak plaintext once a few gigabytes are
+  /// encrypted under one key, as the Sweet32 attack showed.
+  SmallBlock,
+}
+
+impl Hazard {
+  /// Every class of hazard in the order they are declared.
+  pub const ALL: [Hazard; 8] = [
+    Self::CbcPadding,
+    Self::Pkcs1Encryption,
+    Self::NonceSensitivity,
+    Self::ShortTag,
+    Self::MaliciousParty,
+    Self::RogueKey,
+    Self::StateReuse,
+    Self::SmallBlock,
+  ];
+
+  /// Returns the advisory raised for the hazard.
+  pub fn advisory(&self) -> &'static Advisory {
+    &ADVISORIES[*self as usize]
+  }
+
+  /// Returns the hazard of an asymmetric key primitive, if any.
+  ///
+  /// Keys of rsaEncryption may be used to decrypt as well as to sign,
+  /// so they are flagged whatever they are used for. Elliptic curve
+  /// keys are taken to be ECDSA keys unless they are EdDSA or X25519
+  /// and X448 keys.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use wardstone_core::advisory::Hazard;
+  /// use wardstone_core::primitive::ecc::{ED25519, P256};
+  ///
+  /// assert_eq!(Hazard::of(P256.into()), Some(Hazard::NonceSensitivity));
+  /// assert_eq!(Hazard::of(ED25519.into()), None);
+  /// ```
+  pub fn of(key: Asymmetric) -> Option<Self> {
+    if key.is_placeholder() {
+      return None;
+    }
+    match key {
+      Asymmetric::Ifc(ifc) if ifc.id == ID_RSA_PKCS1 || matches!(ifc.id, 1..=8) => {
+        Some(Self::Pkcs1Encryption)
+      },
+      Asymmetric::Ifc(_) => None,
+      Asymmetric::Ecc(ecc) if [ED25519, ED25519PH, ED448, ED448PH, X25519, X448].contains(&ecc) => {
+        None
+      },
+      Asymmetric::Ecc(_) => Some(Self::NonceSensitivity),
+      Asymmetric::Ffc(ffc) if ffc.is_key_agreement() => None,
+      Asymmetric::Ffc(_) => Some(Self::NonceSensitivity),
+      Asymmetric::Pqc(_) => None,
+      Asymmetric::Hybrid(hybrid) => Self::of(hybrid.ecc.into()),
+      Asymmetric::Stateful(_) => Some(Self::StateReuse),
+    }
+  }
+
+  /// Returns the hazards of a TLS cipher suite given by its name in the
+  /// IANA TLS Cipher Suites registry, such as
+  /// `TLS_RSA_WITH_AES_128_CBC_SHA`.
+  ///
+  /// CBC suites check the MAC after decrypting unless the encrypt then
+  /// MAC extension of RFC 7366 is negotiated, suites whose key exchange
+  /// is RSA have the server decrypt the premaster secret with PKCS #1
+  /// v1.5 padding, and `_CCM_8` suites authenticate records with an
+  /// 8-byte tag.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use wardstone_core::advisory::Hazard;
+  ///
+  /// let hazards = Hazard::of_tls_cipher_suite("TLS_RSA_WITH_AES_128_CBC_SHA");
+  /// assert_eq!(hazards.len(), 2);
+  /// assert!(Hazard::of_tls_cipher_suite("TLS_AES_128_GCM_SHA256").is_empty());
+  /// ```
+  pub fn of_tls_cipher_suite(name: &str) -> Vec<Self> {
+    let mut hazards = Vec::new();
+    let name = name.to_ascii_uppercase();
+    if name.contains("_CBC_") {
+      hazards.push(Self::CbcPadding);
+    }
+    if name.starts_with("TLS_RSA_WITH_") {
+      hazards.push(Self::Pkcs1Encryption);
+    }
+    if name.contains("_CCM_8") {
+      hazards.push(Self::ShortTag);
+    }
+    hazards
+  }
+
+  /// Returns the hazard of a cipher used in `mode`, if any. Ciphers with
+  /// 64-bit blocks are flagged in every mode that encrypts bulk data,
+  /// whatever the size of their key.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use wardstone_core::advisory::Hazard;
+  /// use wardstone_core::primitive::symmetric::{Mode, AES128, TDEA3};
+  ///
+  /// assert_eq!(Hazard::of_cipher(TDEA3, Mode::Cbc), Some(Hazard::SmallBlock));
+  /// assert_eq!(Hazard::of_cipher(TDEA3, Mode::Kw), None);
+  /// assert_eq!(Hazard::of_cipher(AES128, Mode::Cbc), None);
+  /// ```
+  pub fn of_cipher(key: Symmetric, mode: Mode) -> Option<Self> {
+    (key.block_size() == 64 && mode.is_bulk()).then_some(Self::SmallBlock)
+  }
+
+  /// Returns the hazard of a threshold or multi-signature configuration,
+  /// if any. The keys of a multi-signature are separate, so they have
+  /// the hazards of their curve.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use wardstone_core::advisory::Hazard;
+  /// use wardstone_core::primitive::ecc::{ED25519, SECP256K1};
+  /// use wardstone_core::primitive::threshold::{Scheme, Threshold};
+  ///
+  /// let key = Threshold::new(Scheme::Ecdsa, SECP256K1, 2, 3);
+  /// assert_eq!(Hazard::of_threshold(key), Some(Hazard::MaliciousParty));
+  /// let key = Threshold::new(Scheme::Frost, ED25519, 2, 3);
+  /// assert_eq!(Hazard::of_threshold(key), None);
+  /// ```
+  pub fn of_threshold(key: Threshold) -> Option<Self> {
+    match key.scheme {
+      Scheme::Ecdsa => Some(Self::MaliciousParty),
+      Scheme::Frost => None,
+      Scheme::Bls => Some(Self::RogueKey),
+      Scheme::Multisig => Self::of(key.curve.into()),
+    }
+  }
+}
+
+impl Display for Hazard {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    let name = match self {
+      Self::CbcPadding => "cbc-padding",
+      Self::Pkcs1Encryption => "pkcs1-encryption",
+      Self::NonceSensitivity => "nonce-sensitivity",
+      Self::ShortTag => "short-tag",
+      Self::MaliciousParty => "malicious-party",
+      Self::RogueKey => "rogue-key",
+      Self::StateReuse => "state-reuse",
+      Self::SmallBlock => "small-block",
+    };
+    write!(f, "{}", name)
+  }
+}
+
+/// The error returned when a hazard is not known by the given name,
+/// which is now [`Error::UnknownHazard`].
+#[deprecated(note = "use wardstone_core::Error instead")]
+pub type ParseHazardError = Error;
+
+impl FromStr for Hazard {
+  type Err = Error;
+
+  /// Finds a hazard by the name it is written with, such as
+  /// `nonce-sensitivity`.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Self::ALL
+      .into_iter()
+      .find(|hazard| hazard.to_string().eq_ignore_ascii_case(s))
+      .ok_or_else(|| Error::UnknownHazard(s.to_string()))
+  }
+}
+
+/// A set of hazards, such as those a standard raises.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Hazards(u8);
+
+impl Hazards {
+  /// Every hazard.
+  pub const ALL: Hazards = Hazards(0b1111_1111);
+
+  /// No hazard.
+  pub const NONE: Hazards = Hazards(0);
+
+  pub fn contains(&self, hazard: Hazard) -> bool {
+    self.0 & (1 << hazard as u8) != 0
+  }
+}
+
+impl FromIterator<Hazard> for Hazards {
+  fn from_iter<I: IntoIterator<Item = Hazard>>(iter: I) -> Self {
+    Self(
+      iter
+        .into_iter()
+        .fold(0, |bits, hazard| bits | 1 << hazard as u8),
+    )
+  }
+}
+
+/// What is raised for a hazard, along with the publication that
+/// describes how to avoid it.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+pub struct Advisory {
+  /// A stable identifier in the style of the identifiers of rules.
+  pub id: &'static str,
+  pub hazard: Hazard,
+  /// What the hazard is and how to avoid it, written to follow the name
+  /// of the primitive.
+  pub summary: &'static str,
+  pub citation: Citation,
+}
+
+impl Advisory {
+  /// Returns the advisory as a sentence about the given primitive.
+  pub fn message(&self, primitive: impl Display) -> String {
+    format!("{} {}", primitive, self.summary)
+  }
+}
+
+/// The advisories in the order of the hazards they are raised for.
+static ADVISORIES: [Advisory; 8] = [
+  Advisory {
+    id: "ADV-CBC-001",
+    hazard: Hazard::CbcPadding,
+    summary: "uses CBC with the MAC checked after decrypting, which is open to padding oracles such as Lucky Thirteen; use an AEAD or encrypt then MAC",
+    citation: Citation {
+      document: "RFC 7366",
+      section: "§1",
+      url: Some("https://datatracker.ietf.org/doc/html/rfc7366#section-1"),
+    },
+  },
+  Advisory {
+    id: "ADV-RSA-001",
+    hazard: Hazard::Pkcs1Encryption,
+    summary: "is open to Bleichenbacher's padding oracle wherever it decrypts with PKCS #1 v1.5 padding, as in TLS RSA key exchange; decrypt with RSAES-OAEP or use an ephemeral key exchange",
+    citation: Citation {
+      document: "RFC 8017",
+      section: "§7.2",
+      url: Some("https://datatracker.ietf.org/doc/html/rfc8017#section-7.2"),
+    },
+  },
+  Advisory {
+    id: "ADV-NONCE-001",
+    hazard: Hazard::NonceSensitivity,
+    summary: "signatures give away the private key if a nonce is ever reused or slightly biased; derive nonces deterministically or prefer EdDSA",
+    citation: Citation {
+      document: "RFC 6979",
+      section: "§1",
+      url: Some("https://datatracker.ietf.org/doc/html/rfc6979#section-1"),
+    },
+  },
+  Advisory {
+    id: "ADV-TAG-001",
+    hazard: Hazard::ShortTag,
+    summary: "authenticates with a 64-bit tag, so every forged message is accepted with a probability of 2⁻⁶⁴; this suits constrained links that limit failed decryptions but not bulk traffic, where the 16-byte tag of CCM or GCM should be used",
+    citation: Citation {
+      document: "NIST SP 800-38C",
+      section: "Appendix B",
+      url: Some("https://csrc.nist.gov/pubs/sp/800/38/c/upd1/final"),
+    },
+  },
+  Advisory {
+    id: "ADV-TSS-001",
+    hazard: Hazard::MaliciousParty,
+    summary: "gives the whole key away to a single party that cheats in implementations of GG18 and GG20 that do not check the Paillier keys and range proofs of the others; use a protocol that proves every value it receives is well formed, such as CGGMP21",
+    citation: Citation {
+      document: "NVD",
+      section: "CVE-2023-33241",
+      url: Some("https://nvd.nist.gov/vuln/detail/CVE-2023-33241"),
+    },
+  },
+  Advisory {
+    id: "ADV-BLS-001",
+    hazard: Hazard::RogueKey,
+    summary: "is open to rogue key attacks wherever the signatures of several keys are aggregated, which let one party forge an aggregate for all of them; have every party prove possession of its key",
+    citation: Citation {
+      document: "draft-irtf-cfrg-bls-signature-05",
+      section: "§3.3",
+      url: Some("https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05#section-3.3"),
+    },
+  },
+  Advisory {
+    id: "ADV-HBS-001",
+    hazard: Hazard::StateReuse,
+    summary: "signatures let anyone forge once a one-time key is used twice, as happens when a key is copied or its state restored from a backup; keep the key and its state in a hardware module that cannot export them",
+    citation: Citation {
+      document: "NIST SP 800-208",
+      section: "§8.1",
+      url: Some("https://csrc.nist.gov/pubs/sp/800/208/final"),
+    },
+  },
+  Advisory {
+    id: "ADV-BLOCK-001",
+    hazard: Hazard::SmallBlock,
+    summary: "has 64-bit blocks, which are likely to collide and leak plaintext once about 2^32 of them (32 GiB) are encrypted under one key, as in the Sweet32 attack; change keys well before that or use a cipher with 128-bit blocks such as AES",
+    citation: Citation {
+      document: "Bhargavan and Leurent, On the Practical (In-)Security of 64-bit Block Ciphers",
+      section: "§1",
+      url: Some("https://sweet32.info/SWEET32_CCS16.pdf"),
+    },
+  },
+];
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::primitive::ecc::{BLS12_381, ED25519, P256, X25519};
+  use crate::primitive::ffc::{DSA_2048_256, FFDHE2048};
+  use crate::primitive::hybrid::Hybrid;
+  use crate::primitive::ifc::{RSA_PKCS1_2048, RSA_PSS_2048};
+  use crate::primitive::pqc::ML_DSA_65;
+  use crate::primitive::stateful::LMS_SHA256_192;
+  use crate::primitive::symmetric::{AES256, BLOWFISH, IDEA};
+
+  #[test]
+  fn advisories_follow_hazards() {
+    for hazard in Hazard::ALL {
+      assert_eq!(hazard.advisory().hazard, hazard);
+    }
+  }
+
+  #[test]
+  fn rsa_pkcs1_may_decrypt() {
+    assert_eq!(
+      Hazard::of(RSA_PKCS1_2048.into()),
+      Some(Hazard::Pkcs1Encryption)
+    );
+    assert_eq!(Hazard::of(RSA_PSS_2048.into()), None);
+  }
+
+  #[test]
+  fn nonces_matter_to_ecdsa_and_dsa() {
+    assert_eq!(Hazard::of(P256.into()), Some(Hazard::NonceSensitivity));
+    assert_eq!(
+      Hazard::of(DSA_2048_256.into()),
+      Some(Hazard::NonceSensitivity)
+    );
+    assert_eq!(Hazard::of(X25519.into()), None);
+    assert_eq!(Hazard::of(FFDHE2048.into()), None);
+  }
+
+  #[test]
+  fn state_matters_to_stateful_signatures() {
+    assert_eq!(Hazard::of(LMS_SHA256_192.into()), Some(Hazard::StateReuse));
+    assert_eq!(Hazard::of(ML_DSA_65.into()), None);
+    assert_eq!(
+      Hazard::of(Hybrid::new(ML_DSA_65, P256).into()),
+      Some(Hazard::NonceSensitivity)
+    );
+    assert_eq!(Hazard::of(Hybrid::new(ML_DSA_65, ED25519).into()), None);
+  }
+
+  #[test]
+  fn small_blocks_matter_in_bulk_modes() {
+    assert_eq!(
+      Hazard::of_cipher(BLOWFISH, Mode::Cbc),
+      Some(Hazard::SmallBlock)
+    );
+    assert_eq!(Hazard::of_cipher(IDEA, Mode::Ctr), Some(Hazard::SmallBlock));
+    assert_eq!(Hazard::of_cipher(IDEA, Mode::Kw), None);
+    assert_eq!(Hazard::of_cipher(AES256, Mode::Xts), None);
+  }
+
+  #[test]
+  fn aead_suites_are_not_flagged() {
+    assert!(Hazard::of_tls_cipher_suite("TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256").is_empty());
+    assert_eq!(
+      Hazard::of_tls_cipher_suite("TLS_ECDHE_RSA_WITH_AES_128_CBC_SHA"),
+      vec![Hazard::CbcPadding]
+    );
+  }
+
+  #[test]
+  fn ccm_8_suites_have_short_tags() {
+    assert_eq!(
+      Hazard::of_tls_cipher_suite("TLS_ECDHE_ECDSA_WITH_AES_128_CCM_8"),
+      vec![Hazard::ShortTag]
+    );
+    assert_eq!(
+      Hazard::of_tls_cipher_suite("TLS_AES_128_CCM_8_SHA256"),
+      vec![Hazard::ShortTag]
+    );
+    assert!(Hazard::of_tls_cipher_suite("TLS_AES_128_CCM_SHA256").is_empty());
+  }
+
+  #[test]
+  fn multisig_keys_keep_their_hazards() {
+    assert_eq!(
+      Hazard::of_threshold(Threshold::new(Scheme::Multisig, P256, 2, 3)),
+      Some(Hazard::NonceSensitivity)
+    );
+    assert_eq!(
+      Hazard::of_threshold(Threshold::new(Scheme::Bls, BLS12_381, 2, 3)),
+      Some(Hazard::RogueKey)
+    );
+  }
+
+  #[test]
+  fn hazards_round_trip() {
+    for hazard in Hazard::ALL {
+      assert_eq!(hazard.to_string().parse(), Ok(hazard));
+    }
+    assert!("timing".parse::<Hazard>().is_err());
+  }
+
+  #[test]
+  fn sets_hold_what_they_are_built_from() {
+    let hazards: Hazards = [Hazard::NonceSensitivity].into_iter().collect();
+    assert!(hazards.contains(Hazard::NonceSensitivity));
+    assert!(!hazards.contains(Hazard::CbcPadding));
+    assert!(Hazard::ALL.into_iter().all(|h| Hazards::ALL.contains(h)));
+    assert!(!Hazards::NONE.contains(Hazard::CbcPadding));
+  }
+}
@@ -0,0 +1,69 @@
+//! Validate many primitives or artifacts in parallel.
+//!
+//! Validations are independent of each other so scans of large fleets
+//! can spread them over every core. This is only available with the
+//! `rayon` feature.
+//!
+//! ```
+//! use wardstone_core::batch;
+//! use wardstone_core::context::Context;
+//! use wardstone_core::primitive::hash::{SHA1, SHA224, SHA256};
+//! use wardstone_core::standard::nist::Nist;
+//! use wardstone_core::standard::Standard;
+//!
+//! let ctx = Context::default();
+//! let results = batch::validate_all(ctx, &[SHA1, SHA256], Nist::validate_hash);
+//! assert_eq!(results, vec![Err(SHA224), Ok(SHA256)]);
+//! ```
+use rayon::prelude::*;
+
+use crate::context::Context;
+
+/// Applies `f` to every item in parallel and returns the outcomes in
+/// the order of the items.
+///
+/// `f` is typically one of the validation or assessment functions of a
+/// [`Standard`](crate::standard::Standard), but it can be any function
+/// that assesses an artifact made of several primitives, such as a
+/// closure that reads and audits the certificate at a path.
+pub fn validate_all<T, R, F>(ctx: Context, items: &[T], f: F) -> Vec<R>
+where
+  T: Copy + Sync,
+  R: Send,
+  F: Fn(Context, T) -> R + Sync,
+{
+  items.par_iter().map(|&item| f(ctx, item)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::primitive::asymmetric::Asymmetric;
+  use crate::primitive::ecc::{P224, P256};
+  use crate::primitive::ifc::{RSA_PKCS1_1024, RSA_PKCS1_2048};
+  use crate::standard::nist::Nist;
+  use crate::standard::Standard;
+
+  #[test]
+  fn verdicts_keep_order() {
+    let ctx = Context::default();
+    let keys: Vec<Asymmetric> = [RSA_PKCS1_1024, RSA_PKCS1_2048]
+      .into_iter()
+      .map(Asymmetric::from)
+      .chain([P224.into(), P256.into()])
+      .cycle()
+      .take(1000)
+      .collect();
+    let verdicts = validate_all(ctx, &keys, Nist::assess_asymmetric);
+    assert_eq!(verdicts.len(), keys.len());
+    for (&key, verdict) in keys.iter().zip(verdicts.iter()) {
+      assert_eq!(*verdict, Nist::assess_asymmetric(ctx, key));
+    }
+  }
+
+  #[test]
+  fn empty_batch() {
+    let results = validate_all(Context::default(), &[], Nist::validate_hash);
+    assert!(results.is_empty());
+  }
+}
@@ -0,0 +1,158 @@
+//! Find the primitives used by an X.509 certificate.
+//!
+//! The certificate is parsed and the hash function of its signature
+//! algorithm and its subject public key are mapped to the primitives in
+//! the [`registry`](crate::registry). This is only available with the
+//! `x509` feature and does not depend on any system library so that it
+//! can also be used when targeting WebAssembly.
+use x509_parser::der_parser::der::parse_der_integer;
+use x509_parser::pem;
+use x509_parser::prelude::{FromDer, TbsCertificate, X509Certificate};
+use x509_parser::public_key::RSAPublicKey;
+use x509_parser::signature_algorithm::SignatureAlgorithm;
+
+use crate::primitive::asymmetric::Asymmetric;
+use crate::primitive::ffc::{Ffc, ID_DSA};
+use crate::primitive::hash::Hash;
+use crate::primitive::ifc::{Ifc, ID_RSA_PKCS1, ID_RSA_PSS};
+use crate::registry::{self, Family};
+pub use crate::Error;
+
+fn malformed() -> Error {
+  Error::Parse("certificate".to_string())
+}
+
+fn invalid<E>(_: E) -> Error {
+  malformed()
+}
+
+/// Reads the hash function and subject public key of a DER or PEM
+/// encoded certificate.
+///
+/// The hash function is `None` if the signature algorithm does not use
+/// a separate one, as is the case for EdDSA.
+pub fn primitives(data: &[u8]) -> Result<(Option<Hash>, Asymmetric), Error> {
+  // Certificates do not own their data.
+  let pem;
+  let tbs_certificate = if matches!(data, [0x30, 0x81..=0x83, ..]) {
+    let (_, x509_certificate) = X509Certificate::from_der(data).map_err(invalid)?;
+    x509_certificate.tbs_certificate
+  } else {
+    (_, pem) = pem::parse_x509_pem(data).map_err(invalid)?;
+    let x509_certificate = pem.parse_x509().map_err(invalid)?;
+    x509_certificate.tbs_certificate
+  };
+  let oid = tbs_certificate.signature.oid().to_id_string();
+  let signature = registry::find_signature(&oid)
+    .ok_or(Error::UnknownPrimitive(oid))?
+    .primitive;
+  let hash_function = match signature.family {
+    // The hash function is part of the parameters of the signature.
+    Family::RsaPss => match SignatureAlgorithm::try_from(&tbs_certificate.signature) {
+      Ok(SignatureAlgorithm::RSASSA_PSS(parameters)) => {
+        let oid = parameters.hash_algorithm_oid().to_id_string();
+        let entry = registry::HASH_FUNCTIONS
+          .iter()
+          .find(|entry| entry.oids.contains(&oid.as_str()))
+          .ok_or(Error::UnknownPrimitive(oid))?;
+        Some(entry.primitive)
+      },
+      _ => return Err(malformed()),
+    },
+    _ => signature.hash,
+  };
+  Ok((hash_function, key(&tbs_certificate)?))
+}
+
+/// Extracts the subject public key based on the algorithm identifier
+/// in the subject public key info.
+fn key(tbs_certificate: &TbsCertificate) -> Result<Asymmetric, Error> {
+  let algorithm = &tbs_certificate.subject_pki.algorithm;
+  let oid = algorithm.algorithm.to_id_string();
+  match oid.as_str() {
+    "1.2.840.10045.2.1" => {
+      let curve = algorithm
+        .parameters
+        .as_ref()
+        .and_then(|parameters| parameters.clone().oid().ok())
+        .ok_or_else(malformed)?
+        .to_id_string();
+      let entry = registry::CURVES
+        .iter()
+        .find(|entry| entry.oids.contains(&curve.as_str()))
+        .ok_or(Error::UnknownPrimitive(curve))?;
+      Ok(entry.primitive.into())
+    },
+    "1.2.840.10040.4.1" => {
+      // The sizes of the prime modulus and subgroup order are read from
+      // the Dss-Parms (RFC 3279 Section 2.3.2).
+      let parameters = algorithm.parameters.as_ref().ok_or_else(malformed)?;
+      let bits = |input| -> Result<(&[u8], u16), Error> {
+        let (rest, integer) = parse_der_integer(input).map_err(invalid)?;
+        let n = integer.as_biguint().map_err(invalid)?.bits();
+        Ok((rest, n as u16))
+      };
+      let (rest, l) = bits(parameters.data)?;
+      let (_, n) = bits(rest)?;
+      let ffc = registry::FFC
+        .iter()
+        .map(|entry| entry.primitive)
+        .find(|ffc| ffc.l == l && ffc.n == n)
+        .unwrap_or(Ffc::new(ID_DSA, l, n));
+      Ok(ffc.into())
+    },
+    "1.2.840.113549.1.1.1" | "1.2.840.113549.1.1.10" => {
+      let (_, public_key) =
+        RSAPublicKey::from_der(&tbs_certificate.subject_pki.subject_public_key.data)
+          .map_err(invalid)?;
+      let k = public_key.key_size() as u16;
+      let id = if oid == "1.2.840.113549.1.1.1" {
+        ID_RSA_PKCS1
+      } else {
+        ID_RSA_PSS
+      };
+      let ifc = registry::IFC
+        .iter()
+        .find(|entry| entry.oids.contains(&oid.as_str()) && entry.primitive.k == k)
+        .map(|entry| entry.primitive)
+        .unwrap_or(Ifc::new(id, k));
+      Ok(ifc.into())
+    },
+    _ => {
+      // Keys such as X25519 and Ed25519 are identified by the
+      // algorithm alone (RFC 8410 Section 3).
+      let entry = registry::CURVES
+        .iter()
+        .find(|entry| entry.oids.contains(&oid.as_str()))
+        .ok_or(Error::UnknownPrimitive(oid.clone()))?;
+      Ok(entry.primitive.into())
+    },
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::primitive::ecc::ED25519;
+
+  const CERTIFICATE: &[u8] = b"-----BEGIN CERTIFICATE-----
+MIIBOjCB7aADAgECAhQfvM/UvfvJH5qQyoL/HQIEDfsd7zAFBgMrZXAwEjEQMA4G
+A1UEAwwHZXhhbXBsZTAgFw0yNjEwMTYxOTI3MTdaGA8yMTI2MDkyMjE5MjcxN1ow
+EjEQMA4GA1UEAwwHZXhhbXBsZTAqMAUGAytlcAMhAPh0tWZ6Op7H46skElql6we8
+RGB/qDeYr6DFSZwTF8gZo1MwUTAdBgNVHQ4EFgQU+iXZ5UI+5kFamJr3o6HCPTTM
+WN0wHwYDVR0jBBgwFoAU+iXZ5UI+5kFamJr3o6HCPTTMWN0wDwYDVR0TAQH/BAUw
+AwEB/zAFBgMrZXADQQAijC7N6FmH6y6CPqey6m2bEcoz3/hzgc7zzQJ3FPYEO65o
+8fX6tAwKyLe1blqxx1OEW/PNUUZ0Facie8F3/iIF
+-----END CERTIFICATE-----
+";
+
+  #[test]
+  fn primitives_ed25519() {
+    assert_eq!(primitives(CERTIFICATE), Ok((None, ED25519.into())));
+  }
+
+  #[test]
+  fn primitives_malformed() {
+    assert_eq!(primitives(b"junk"), Err(malformed()));
+  }
+}
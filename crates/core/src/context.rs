@@ -1,15 +1,21 @@
 //! Specifies the context in which a cryptographic primitive will be
 //! assessed against.
 use crate::primitive::Security;
+use crate::standard::rule::Scope;
+use crate::Error;
 
 /// Represents the context in which a cryptographic primitive will be
 /// assessed against such as the year and minimum security required by
 /// the user.
-#[repr(C)]
+///
+/// The cutoff years after which a standard stops accepting primitives
+/// can be moved for each [`Scope`], so that a change of date by the body
+/// behind a standard can be followed without waiting for a release.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct Context {
   security: Security,
   year: u16,
+  cutoffs: [u16; Scope::ALL.len()],
 }
 
 impl Context {
@@ -18,6 +24,9 @@ impl Context {
   // in the standard.
   const DEFAULT_SECURITY: u16 = 0;
   const DEFAULT_YEAR: u16 = 2023;
+  // The security of the strongest primitives, such as AES-256 and
+  // SHA-512.
+  const MAX_SECURITY: u16 = 256;
 
   /// Creates a new context.
   ///
@@ -25,17 +34,94 @@ impl Context {
   /// to `0` then it will default to using the minimum security outlined
   /// in the standard. `year` is the year one expects the primitive to
   /// remain secure.
-  pub fn new(security: Security, year: u16) -> Self {
-    Self { security, year }
+  pub const fn new(security: Security, year: u16) -> Self {
+    Self {
+      security,
+      year,
+      cutoffs: [0; Scope::ALL.len()],
+    }
   }
 
-  pub fn security(&self) -> Security {
+  /// Creates a new context like [`Context::new`], but returns an
+  /// [`Error::InvalidContext`] instead of a context that no primitive
+  /// can satisfy, such as one that requires more than 256 bits of
+  /// security.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use wardstone_core::context::Context;
+  /// use wardstone_core::Error;
+  ///
+  /// assert_eq!(Context::try_new(128, 2030), Ok(Context::new(128, 2030)));
+  /// assert!(matches!(Context::try_new(512, 2030), Err(Error::InvalidContext(_))));
+  /// ```
+  pub fn try_new(security: Security, year: u16) -> Result<Self, Error> {
+    if security > Self::MAX_SECURITY {
+      return Err(Error::InvalidContext(format!(
+        "no primitive offers {} bits of security",
+        security
+      )));
+    }
+    Ok(Self::new(security, year))
+  }
+
+  /// Returns the context with the minimum security required replaced,
+  /// keeping everything else.
+  pub const fn with_security(mut self, security: Security) -> Self {
+    self.security = security;
+    self
+  }
+
+  /// Returns the context with the year replaced, keeping everything
+  /// else.
+  pub const fn with_year(mut self, year: u16) -> Self {
+    self.year = year;
+    self
+  }
+
+  /// Returns the context with the cutoff year of the standard for
+  /// primitives in `scope` replaced by `year`. If `year` is `0` then the
+  /// cutoff year specified in the standard is used.
+  ///
+  /// # Example
+  ///
+  /// The following example assesses 2048-bit RSA as if NIST had moved
+  /// its deprecation from 2031 to 2035.
+  ///
+  /// ```
+  /// use wardstone_core::context::Context;
+  /// use wardstone_core::primitive::ifc::RSA_PSS_2048;
+  /// use wardstone_core::standard::nist::Nist;
+  /// use wardstone_core::standard::rule::Scope;
+  /// use wardstone_core::standard::Standard;
+  ///
+  /// let ctx = Context::new(0, 2033);
+  /// assert!(Nist::validate_ifc(ctx, RSA_PSS_2048).is_err());
+  /// let ctx = ctx.with_cutoff(Scope::Ifc, 2035);
+  /// assert_eq!(Nist::validate_ifc(ctx, RSA_PSS_2048), Ok(RSA_PSS_2048));
+  /// ```
+  pub const fn with_cutoff(mut self, scope: Scope, year: u16) -> Self {
+    self.cutoffs[scope as usize] = year;
+    self
+  }
+
+  pub const fn security(&self) -> Security {
     self.security
   }
 
-  pub fn year(&self) -> u16 {
+  pub const fn year(&self) -> u16 {
     self.year
   }
+
+  /// Returns the cutoff year to use for primitives in `scope`, which is
+  /// `year` unless the context replaces it.
+  pub const fn cutoff(&self, scope: Scope, year: u16) -> u16 {
+    match self.cutoffs[scope as usize] {
+      0 => year,
+      cutoff => cutoff,
+    }
+  }
 }
 
 impl Default for Context {
@@ -0,0 +1,76 @@
+//! Describes the errors that the library can report.
+//!
+//! Every fallible function of the library returns [`Error`], so that
+//! consumers can tell what went wrong by matching on it rather than by
+//! reading a message or catching a panic.
+use std::fmt::{self, Display, Formatter};
+
+/// Represents an error that could arise as a result of using the
+/// library.
+///
+/// New variants may be added in minor releases, so a match on it needs
+/// a wildcard arm.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+  /// A name or object identifier does not refer to a primitive of the
+  /// type it is read as.
+  UnknownPrimitive(String),
+  /// A name does not refer to an implementation hazard.
+  UnknownHazard(String),
+  /// The operation does not apply to the given input, such as a
+  /// primitive a guide does not specify.
+  Unsupported(String),
+  /// The context cannot be assessed in, such as one that requires more
+  /// security than any primitive offers.
+  InvalidContext(String),
+  /// An encoded input, such as a certificate, is malformed.
+  Parse(String),
+}
+
+impl Display for Error {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::UnknownPrimitive(name) => write!(f, "unknown primitive: {}", name),
+      Self::UnknownHazard(name) => write!(f, "unknown hazard {}", name),
+      Self::Unsupported(what) => write!(f, "unsupported: {}", what),
+      Self::InvalidContext(reason) => write!(f, "invalid context: {}", reason),
+      Self::Parse(what) => write!(f, "malformed {}", what),
+    }
+  }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::advisory::Hazard;
+  use crate::context::Context;
+  use crate::primitive::hash::Hash;
+  use crate::primitive::kdf::Kdf;
+
+  #[test]
+  fn unknown_primitive() {
+    let err = "md6".parse::<Hash>().unwrap_err();
+    assert_eq!(err, Error::UnknownPrimitive("md6".to_string()));
+    assert_eq!(err.to_string(), "unknown primitive: md6");
+    let err = "argon2id_t3".parse::<Kdf>().unwrap_err();
+    assert_eq!(err, Error::UnknownPrimitive("argon2id_t3".to_string()));
+  }
+
+  #[test]
+  fn unknown_hazard() {
+    let err = "side-channel".parse::<Hazard>().unwrap_err();
+    assert_eq!(err, Error::UnknownHazard("side-channel".to_string()));
+  }
+
+  #[test]
+  fn invalid_context() {
+    assert!(Context::try_new(256, 2030).is_ok());
+    assert!(matches!(
+      Context::try_new(257, 2030),
+      Err(Error::InvalidContext(_))
+    ));
+  }
+}
@@ -28,6 +28,16 @@
 //!
 //! [SHA-256]: https://doi.org/10.6028/NIST.FIPS.180-4
 //! [guidance made by the NSA]: https://media.defense.gov/2022/Sep/07/2003071834/-1/-1/0/CSA_CNSA_2.0_ALGORITHMS_.PDF
+pub mod advisory;
+#[cfg(feature = "rayon")]
+pub mod batch;
+#[cfg(feature = "x509")]
+pub mod certificate;
 pub mod context;
+mod error;
+pub mod locale;
 pub mod primitive;
+pub mod registry;
 pub mod standard;
+
+pub use crate::error::Error;
@@ -0,0 +1,241 @@
+//! Give the explanations of verdicts in other languages.
+//!
+//! Only the rationale of a verdict and the warnings about a key are
+//! translated. The names of primitives and the titles of publications are left as they are since
+//! that is how they are referred to in every language.
+
+/// A language that explanations are given in.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Locale {
+  #[default]
+  English,
+  German,
+}
+
+impl Locale {
+  /// Picks the locale for a POSIX locale name such as `de_DE.UTF-8` or
+  /// a language tag such as `de-AT`, or returns `None` if there is no
+  /// translation for the language.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use wardstone_core::locale::Locale;
+  ///
+  /// assert_eq!(Locale::from_name("de_DE.UTF-8"), Some(Locale::German));
+  /// assert_eq!(Locale::from_name("C"), Some(Locale::English));
+  /// assert_eq!(Locale::from_name("fr_FR"), None);
+  /// ```
+  pub fn from_name(name: &str) -> Option<Self> {
+    let language = name
+      .split(['_', '-', '.', '@'])
+      .next()
+      .unwrap_or_default()
+      .to_ascii_lowercase();
+    match language.as_str() {
+      "c" | "posix" | "en" => Some(Self::English),
+      "de" => Some(Self::German),
+      _ => None,
+    }
+  }
+
+  /// Returns the language tag of the locale such as "de".
+  pub fn tag(&self) -> &'static str {
+    match self {
+      Self::English => "en",
+      Self::German => "de",
+    }
+  }
+
+  /// Warns that the curve of a key is given by explicit domain
+  /// parameters rather than by name.
+  pub fn explicit_curve(&self) -> &'static str {
+    match self {
+      Self::English => "the curve is given by explicit domain parameters, which RFC 5480 and the CA/Browser Forum Baseline Requirements forbid in certificates",
+      Self::German => "die Kurve ist durch explizite Domänenparameter angegeben, die RFC 5480 und die Baseline Requirements des CA/Browser Forum in Zertifikaten verbieten",
+    }
+  }
+
+  /// Warns that a primitive of a key that is compliant now is no longer
+  /// compliant in the year the key expires.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use wardstone_core::locale::{Locale, Use};
+  ///
+  /// assert_eq!(
+  ///   Locale::German.expires(Use::HashFunction, "sha1", 2031),
+  ///   "die Hashfunktion sha1 ist 2031, wenn der Schlüssel abläuft, nicht mehr konform",
+  /// );
+  /// ```
+  pub fn expires(&self, role: Use, primitive: &str, year: u16) -> String {
+    match (self, role) {
+      (Self::English, Use::HashFunction) => format!(
+        "hash function {} is no longer compliant in {} when the key expires",
+        primitive, year
+      ),
+      (Self::English, Use::SignatureAlgorithm) => format!(
+        "signature algorithm {} is no longer compliant in {} when the key expires",
+        primitive, year
+      ),
+      (Self::German, Use::HashFunction) => format!(
+        "die Hashfunktion {} ist {}, wenn der Schlüssel abläuft, nicht mehr konform",
+        primitive, year
+      ),
+      (Self::German, Use::SignatureAlgorithm) => format!(
+        "der Signaturalgorithmus {} ist {}, wenn der Schlüssel abläuft, nicht mehr konform",
+        primitive, year
+      ),
+    }
+  }
+
+  pub(crate) fn rationale(&self, rationale: &Rationale) -> String {
+    match self {
+      Self::English => english(rationale),
+      Self::German => german(rationale),
+    }
+  }
+}
+
+/// What a primitive is used for in a key.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Use {
+  HashFunction,
+  SignatureAlgorithm,
+}
+
+/// Why a primitive is or is not compliant.
+pub(crate) enum Reason {
+  /// The primitive meets the minimum security, possibly only until the
+  /// given year.
+  Meets { minimum: u16, until: Option<u16> },
+  /// The primitive is accepted, possibly only until the given year.
+  Accepted { until: Option<u16> },
+  /// The primitive falls short of the minimum security.
+  Below { minimum: u16 },
+  /// The primitive is no longer accepted after the cutoff year.
+  Expired { cutoff: u16 },
+  /// The primitive falls short of the minimum security for the year.
+  BelowIn { minimum: u16, year: u16 },
+  /// The primitive is not among those the standard approves of.
+  NotApproved,
+  /// The parameters of the primitive do not agree with each other.
+  Inconsistent,
+}
+
+/// The facts a rationale is made of, independently of the language.
+pub(crate) struct Rationale<'a> {
+  pub primitive: String,
+  /// Whether the primitive is a hash function whose security is a bound
+  /// on its collision resistance.
+  pub hash: bool,
+  pub security: u16,
+  /// The publication the rule is based on, if it is known.
+  pub document: Option<&'a str>,
+  pub reason: Reason,
+  /// The primitive to use instead if it is not compliant.
+  pub alternative: String,
+}
+
+fn english(r: &Rationale) -> String {
+  let document = r.document.unwrap_or("the guide");
+  let (bound, kind) = if r.hash {
+    ("at most ", "collision resistance")
+  } else {
+    ("", "security")
+  };
+  let mut s = format!(
+    "{} offers {}{} bits of {}",
+    r.primitive, bound, r.security, kind
+  );
+  let until = |until: Option<u16>| until.map_or(String::new(), |year| format!(" until {}", year));
+  match r.reason {
+    Reason::Meets {
+      minimum,
+      until: year,
+    } => {
+      return format!(
+        "{}, meeting the {}-bit minimum required by {}{}",
+        s,
+        minimum,
+        document,
+        until(year)
+      )
+    },
+    Reason::Accepted { until: year } => {
+      return format!("{}, which is accepted by {}{}", s, document, until(year))
+    },
+    Reason::Below { minimum } => s.push_str(&format!(
+      ", below the {}-bit minimum required by {}",
+      minimum, document
+    )),
+    Reason::Expired { cutoff } => s.push_str(&format!(
+      ", which {} no longer accepts after {}",
+      document, cutoff
+    )),
+    Reason::BelowIn { minimum, year } => s.push_str(&format!(
+      ", below the {}-bit minimum required by {} in {}",
+      minimum, document, year
+    )),
+    Reason::NotApproved => s.push_str(&format!(", but it is not approved by {}", document)),
+    Reason::Inconsistent => s.push_str(", but its parameters are inconsistent with each other"),
+  }
+  s.push_str(&format!("; use {}", r.alternative));
+  s
+}
+
+fn german(r: &Rationale) -> String {
+  let document = r.document.unwrap_or("dem Leitfaden");
+  let (bound, kind) = if r.hash {
+    ("höchstens ", "Kollisionsresistenz")
+  } else {
+    ("", "Sicherheit")
+  };
+  let mut s = format!(
+    "{} bietet {}{} Bit {}",
+    r.primitive, bound, r.security, kind
+  );
+  match r.reason {
+    Reason::Meets {
+      minimum,
+      until: Some(year),
+    } => {
+      return format!(
+        "{} und erfüllt damit bis {} das von {} geforderte Minimum von {} Bit",
+        s, year, document, minimum
+      )
+    },
+    Reason::Meets {
+      minimum,
+      until: None,
+    } => {
+      return format!(
+        "{} und erfüllt damit das von {} geforderte Minimum von {} Bit",
+        s, document, minimum
+      )
+    },
+    Reason::Accepted { until: Some(year) } => {
+      return format!("{}, was von {} bis {} akzeptiert wird", s, document, year)
+    },
+    Reason::Accepted { until: None } => {
+      return format!("{}, was von {} akzeptiert wird", s, document)
+    },
+    Reason::Below { minimum } => s.push_str(&format!(
+      ", weniger als das von {} geforderte Minimum von {} Bit",
+      document, minimum
+    )),
+    Reason::Expired { cutoff } => s.push_str(&format!(
+      ", was {} nach {} nicht mehr akzeptiert",
+      document, cutoff
+    )),
+    Reason::BelowIn { minimum, year } => s.push_str(&format!(
+      ", weniger als das von {} für {} geforderte Minimum von {} Bit",
+      document, year, minimum
+    )),
+    Reason::NotApproved => s.push_str(&format!(", ist aber von {} nicht zugelassen", document)),
+    Reason::Inconsistent => s.push_str(", aber seine Parameter passen nicht zueinander"),
+  }
+  s.push_str(&format!("; verwenden Sie {}", r.alternative));
+  s
+}
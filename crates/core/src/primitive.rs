@@ -1,10 +1,36 @@
 //! Specifies a cryptographic primitive.
+//!
+//! Primitives are plain values with `const` constructors, so those
+//! that are not defined here can be declared in a `static` or `const`
+//! and gathered into tables without any initialisation at run time.
+//!
+//! ```
+//! use wardstone_core::context::Context;
+//! use wardstone_core::primitive::ifc::{Ifc, ID_RSA_PKCS1, RSA_PKCS1_4096};
+//! use wardstone_core::standard::nist::Nist;
+//! use wardstone_core::standard::Standard;
+//!
+//! static RSA_PKCS1_6144: Ifc = Ifc::new(ID_RSA_PKCS1, 6144);
+//! static APPROVED: [Ifc; 2] = [RSA_PKCS1_4096, RSA_PKCS1_6144];
+//! const CTX: Context = Context::new(128, 2030);
+//!
+//! for key in APPROVED {
+//!   assert!(Nist::validate_ifc(CTX, key).is_ok());
+//! }
+//! ```
 pub mod asymmetric;
 pub mod ecc;
 pub mod ffc;
 pub mod hash;
+pub mod hybrid;
 pub mod ifc;
+pub mod kdf;
+pub mod pqc;
+pub mod stateful;
 pub mod symmetric;
+pub mod threshold;
+
+use crate::Error;
 
 /// The level of security of a symmetric cryptosystem which is a
 /// standard measure used to assess the security of all other
@@ -14,4 +40,38 @@ pub type Security = u16;
 /// Represents a cryptographic primitive.
 pub trait Primitive {
   fn security(&self) -> Security;
+
+  /// Returns whether the parameters of the primitive agree with each
+  /// other. Primitives defined by a single parameter always do, but the
+  /// sizes of a finite field modulus and its divisor can be mismatched.
+  fn is_consistent(&self) -> bool {
+    true
+  }
+
+  /// Returns whether the primitive is a placeholder, such as
+  /// [`ecc::ECC_NOT_ALLOWED`], that a standard recommends when none of
+  /// its primitives would do. Its security is not meaningful.
+  fn is_placeholder(&self) -> bool {
+    false
+  }
 }
+
+/// Returns the approximate *minimum* security provided by a modulus of
+/// the given size in bits according to table 2 of SP 800-57 Part 1
+/// Rev. 5, which applies to both integer factorisation and finite
+/// field cryptography.
+pub(crate) const fn modulus_security(bits: u16) -> Security {
+  match bits {
+    ..=1023 => 0,
+    1024..=2047 => 80,
+    2048..=3071 => 112,
+    3072..=7679 => 128,
+    7680..=15359 => 192,
+    15360.. => 256,
+  }
+}
+
+/// The error returned when a name does not refer to a primitive of the
+/// type it is parsed as, which is now [`Error::UnknownPrimitive`].
+#[deprecated(note = "use wardstone_core::Error instead")]
+pub type ParsePrimitiveError = Error;
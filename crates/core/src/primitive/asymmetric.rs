@@ -3,13 +3,27 @@
 //! This is just a thin wrapper around asymmetric key primitives defined
 //! in other modules that are bridged here to avoid incompatibility with
 //! C/C++.
+//!
+//! EdDSA keys are [`Ecc`] primitives on the Edwards curves and RSA-PSS
+//! keys are [`Ifc`] primitives with the RSA-PSS identifier, so they are
+//! already assessed by [`Standard::validate_asymmetric`]. Post-quantum,
+//! hybrid and stateful hash-based signatures fit neither and have
+//! variants of their own, each with a `validate_*` function on
+//! [`Standard`] that the dispatch in [`Standard::validate_asymmetric`]
+//! calls, so that callers holding an [`Asymmetric`] need not change.
+//!
+//! [`Standard`]: crate::standard::Standard
+//! [`Standard::validate_asymmetric`]: crate::standard::Standard::validate_asymmetric
 use std::fmt::{self, Display, Formatter};
 
 use serde::Serialize;
 
 use crate::primitive::ecc::Ecc;
 use crate::primitive::ffc::Ffc;
+use crate::primitive::hybrid::Hybrid;
 use crate::primitive::ifc::Ifc;
+use crate::primitive::pqc::Pqc;
+use crate::primitive::stateful::Stateful;
 use crate::primitive::{Primitive, Security};
 
 /// Represents an asymmetric key primitive.
@@ -18,6 +32,9 @@ pub enum Asymmetric {
   Ecc(Ecc),
   Ifc(Ifc),
   Ffc(Ffc),
+  Pqc(Pqc),
+  Hybrid(Hybrid),
+  Stateful(Stateful),
 }
 
 impl Primitive for Asymmetric {
@@ -26,6 +43,31 @@ impl Primitive for Asymmetric {
       Asymmetric::Ecc(ecc) => ecc.security(),
       Asymmetric::Ifc(ifc) => ifc.security(),
       Asymmetric::Ffc(ffc) => ffc.security(),
+      Asymmetric::Pqc(pqc) => pqc.security(),
+      Asymmetric::Hybrid(hybrid) => hybrid.security(),
+      Asymmetric::Stateful(stateful) => stateful.security(),
+    }
+  }
+
+  fn is_consistent(&self) -> bool {
+    match self {
+      Asymmetric::Ecc(ecc) => ecc.is_consistent(),
+      Asymmetric::Ifc(ifc) => ifc.is_consistent(),
+      Asymmetric::Ffc(ffc) => ffc.is_consistent(),
+      Asymmetric::Pqc(pqc) => pqc.is_consistent(),
+      Asymmetric::Hybrid(hybrid) => hybrid.is_consistent(),
+      Asymmetric::Stateful(stateful) => stateful.is_consistent(),
+    }
+  }
+
+  fn is_placeholder(&self) -> bool {
+    match self {
+      Asymmetric::Ecc(ecc) => ecc.is_placeholder(),
+      Asymmetric::Ifc(ifc) => ifc.is_placeholder(),
+      Asymmetric::Ffc(ffc) => ffc.is_placeholder(),
+      Asymmetric::Pqc(pqc) => pqc.is_placeholder(),
+      Asymmetric::Hybrid(hybrid) => hybrid.is_placeholder(),
+      Asymmetric::Stateful(stateful) => stateful.is_placeholder(),
     }
   }
 }
@@ -36,6 +78,9 @@ impl Display for Asymmetric {
       Asymmetric::Ecc(ecc) => ecc.fmt(f),
       Asymmetric::Ifc(ifc) => ifc.fmt(f),
       Asymmetric::Ffc(ffc) => ffc.fmt(f),
+      Asymmetric::Pqc(pqc) => pqc.fmt(f),
+      Asymmetric::Hybrid(hybrid) => hybrid.fmt(f),
+      Asymmetric::Stateful(stateful) => stateful.fmt(f),
     }
   }
 }
@@ -58,6 +103,24 @@ impl From<Ffc> for Asymmetric {
   }
 }
 
+impl From<Pqc> for Asymmetric {
+  fn from(pqc: Pqc) -> Self {
+    Self::Pqc(pqc)
+  }
+}
+
+impl From<Hybrid> for Asymmetric {
+  fn from(hybrid: Hybrid) -> Self {
+    Self::Hybrid(hybrid)
+  }
+}
+
+impl From<Stateful> for Asymmetric {
+  fn from(stateful: Stateful) -> Self {
+    Self::Stateful(stateful)
+  }
+}
+
 impl Serialize for Asymmetric {
   fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
   where
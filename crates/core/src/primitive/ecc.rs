@@ -1,10 +1,11 @@
 //! Elliptic curve primitive and some common instances.
-use std::collections::HashMap;
 use std::fmt::{Display, Formatter, Result};
-
-use once_cell::sync::Lazy;
+use std::result;
+use std::str::FromStr;
 
 use crate::primitive::{Primitive, Security};
+use crate::registry::{self, Builtin};
+use crate::Error;
 
 /// Represents an elliptic curve cryptography primitive used for digital
 /// signatures and key establishment where f is the key size (the size
@@ -22,112 +23,72 @@ impl Ecc {
   }
 }
 
-// The name is kept in a lookup table instead of being embedded in the
-// type because sharing strings across language boundaries is a bit
-// dicey.
-pub static REPR: Lazy<HashMap<Ecc, &str>> = Lazy::new(|| {
-  let mut m = HashMap::new();
-  m.insert(SM2, "SM2");
-  m.insert(B163, "nistb163 or sect163r2");
-  m.insert(B233, "nistb233, sect233r1, or wap-wsg-idm-ecid-wtls11");
-  m.insert(B283, "nistb283 or sect283r1");
-  m.insert(B409, "nistb409 or sect409r1");
-  m.insert(B571, "nistb571 or sect571r1");
-  m.insert(BRAINPOOLP160R1, "brainpoolP160r1");
-  m.insert(BRAINPOOLP160T1, "brainpoolP160t1");
-  m.insert(BRAINPOOLP192R1, "brainpoolP192r1");
-  m.insert(BRAINPOOLP192T1, "brainpoolP192t1");
-  m.insert(BRAINPOOLP224R1, "brainpoolP224r1");
-  m.insert(BRAINPOOLP224T1, "brainpoolP224t1");
-  m.insert(BRAINPOOLP256R1, "brainpoolP256r1");
-  m.insert(BRAINPOOLP256T1, "brainpoolP256t1");
-  m.insert(BRAINPOOLP320R1, "brainpoolP320r1");
-  m.insert(BRAINPOOLP320T1, "brainpoolP320t1");
-  m.insert(BRAINPOOLP384R1, "brainpoolP384r1");
-  m.insert(BRAINPOOLP384T1, "brainpoolP384t1");
-  m.insert(BRAINPOOLP512R1, "brainpoolP512r1");
-  m.insert(BRAINPOOLP512T1, "brainpoolP512t1");
-  m.insert(C2PNB163V1, "c2pnb163v1 or wap-wsg-idm-ecid-wtls5");
-  m.insert(C2PNB163V2, "c2pnb163v2");
-  m.insert(C2PNB163V3, "c2pnb163v3");
-  m.insert(C2PNB176V1, "c2pnb176v1");
-  m.insert(C2PNB208W1, "c2pnb208w1");
-  m.insert(C2PNB272W1, "c2pnb272w1");
-  m.insert(C2PNB304W1, "c2pnb304w1");
-  m.insert(C2PNB368W1, "c2pnb368w1");
-  m.insert(C2TNB191V1, "c2tnb191v1");
-  m.insert(C2TNB191V2, "c2tnb191v2");
-  m.insert(C2TNB191V3, "c2tnb191v3");
-  m.insert(C2TNB239V1, "c2tnb239v1");
-  m.insert(C2TNB239V2, "c2tnb239v2");
-  m.insert(C2TNB239V3, "c2tnb239v3");
-  m.insert(C2TNB359V1, "c2tnb359v1");
-  m.insert(C2TNB431R1, "c2tnb431r1");
-  m.insert(ECC_NOT_ALLOWED, "not allowed");
-  m.insert(ED25519, "ed25519");
-  m.insert(ED448, "ed448");
-  m.insert(K163, "nistk163, sect163k1, or wap-wsg-idm-ecid-wtls3");
-  m.insert(K233, "nistk233, sect233k1, or wap-wsg-idm-ecid-wtls10");
-  m.insert(K283, "nistk283 or sect283k1");
-  m.insert(K409, "nistk409 or sect409k1");
-  m.insert(K571, "nistk571");
-  m.insert(P192, "nistp192, prime192v1, or secp192r1");
-  m.insert(P224, "nistp224, secp224r1, or wap-wsg-idm-ecid-wtls12");
-  m.insert(P256, "nistp256, prime256v1, or secp256r1");
-  m.insert(P384, "nistp384 or secp384r1");
-  m.insert(P521, "nistp521 or secp521r1");
-  m.insert(PRIME192V2, "prime192v2");
-  m.insert(PRIME192V3, "prime192v3");
-  m.insert(PRIME239V1, "prime239v1");
-  m.insert(PRIME239V2, "prime239v2");
-  m.insert(PRIME239V3, "prime239v3");
-  m.insert(SECP112R1, "secp112r1 or wap-wsg-idm-ecid-wtls6");
-  m.insert(SECP112R2, "secp112r2");
-  m.insert(SECP128R1, "secp128r1");
-  m.insert(SECP128R2, "secp128r2");
-  m.insert(SECP160K1, "secp160k1");
-  m.insert(SECP160R1, "secp160r1 or wap-wsg-idm-ecid-wtls7");
-  m.insert(SECP160R2, "secp160r2");
-  m.insert(SECP192K1, "secp192k1");
-  m.insert(SECP224K1, "secp224k1");
-  m.insert(SECP256K1, "secp256k1");
-  m.insert(SECT113R1, "sect113r1 or wap-wsg-idm-ecid-wtls4");
-  m.insert(SECT113R2, "sect113r2");
-  m.insert(SECT131R1, "sect131r1");
-  m.insert(SECT131R2, "sect131r2");
-  m.insert(SECT163R1, "sect163r1");
-  m.insert(SECT193R1, "sect193r1");
-  m.insert(SECT193R2, "sect193r2");
-  m.insert(SECT239K1, "sect239k1");
-  m.insert(SM2, "sm2");
-  m.insert(WAP_WSG_IDM_ECID_WTLS1, "wap-wsg-idm-ecid-wtls1");
-  m.insert(WAP_WSG_IDM_ECID_WTLS8, "wap-wsg-idm-ecid-wtls8");
-  m.insert(WAP_WSG_IDM_ECID_WTLS9, "wap-wsg-idm-ecid-wtls9");
-  m.insert(X25519, "x25519");
-  m.insert(X448, "x448");
-  m.insert(ECC_224, "any approved 224-bit elliptic curve");
-  m.insert(ECC_256, "any approved 256-bit elliptic curve");
-  m.insert(ECC_384, "any approved 384-bit elliptic curve");
-  m.insert(ECC_512, "any approved 512-bit elliptic curve");
-  m
-});
+// The names of the placeholders, which stand in for a recommendation
+// rather than a curve and so are not in the registry.
+static PLACEHOLDERS: [(Ecc, &str); 5] = [
+  (ECC_224, "any approved 224-bit elliptic curve"),
+  (ECC_256, "any approved 256-bit elliptic curve"),
+  (ECC_384, "any approved 384-bit elliptic curve"),
+  (ECC_512, "any approved 512-bit elliptic curve"),
+  (ECC_NOT_ALLOWED, "not allowed"),
+];
 
 impl Display for Ecc {
+  /// Writes the name the curve has in the [`registry`] or, for custom
+  /// curves, `ecc_<f>`.
   fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-    let unrecognised = "unrecognised";
-    let name = REPR.get(self).unwrap_or(&unrecognised);
+    if self.id == ID_ECC {
+      return write!(f, "ecc_{}", self.f);
+    }
+    let name = registry::name_of(&registry::CURVES, self)
+      .or_else(|| {
+        PLACEHOLDERS
+          .iter()
+          .find(|(primitive, _)| primitive == self)
+          .map(|&(_, name)| name)
+      })
+      .unwrap_or("unrecognised");
     write!(f, "{name}")
   }
 }
 
+impl FromStr for Ecc {
+  type Err = Error;
+
+  /// Finds a curve by any of the names it is known by in the
+  /// [`registry`] or reads a custom curve of any size written as
+  /// `ecc_<f>`.
+  fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+    if let Some(Builtin::Ecc(entry)) = registry::find(s) {
+      return Ok(entry.primitive);
+    }
+    s.to_ascii_lowercase()
+      .replace('-', "_")
+      .strip_prefix("ecc_")
+      .and_then(|f| f.parse().ok())
+      .map(|f| Ecc::new(ID_ECC, f))
+      .ok_or_else(|| Error::UnknownPrimitive(s.to_string()))
+  }
+}
+
 impl Primitive for Ecc {
   /// Returns the security level of an elliptic curve key (which is
   /// approximately len(n)/2).
   fn security(&self) -> Security {
     self.f >> 1
   }
+
+  fn is_placeholder(&self) -> bool {
+    *self == ECC_NOT_ALLOWED
+  }
 }
 
+/// An identifier for custom curves, such as those given by explicit
+/// domain parameters rather than by name, where only the size of the
+/// order of the base point is known.
+#[no_mangle]
+pub static ID_ECC: u16 = 65530;
+
 /// Represents the Weierstrass curve B-163 over a prime field. Also
 /// known as sect163r2.
 #[no_mangle]
@@ -153,6 +114,15 @@ pub static B409: Ecc = Ecc::new(4, 409);
 #[no_mangle]
 pub static B571: Ecc = Ecc::new(5, 571);
 
+/// Represents the pairing-friendly curve BLS12-381 that BLS signatures
+/// are made over, as specified in [draft-irtf-cfrg-pairing-friendly-curves].
+/// Its key size is that of the order of its subgroups, although attacks
+/// on the pairing leave it with somewhat less security than that.
+///
+/// [draft-irtf-cfrg-pairing-friendly-curves]: https://datatracker.ietf.org/doc/draft-irtf-cfrg-pairing-friendly-curves/
+#[no_mangle]
+pub static BLS12_381: Ecc = Ecc::new(79, 255);
+
 /// Represents the curve brainpoolP160r1 specified in [RFC 5639].
 ///
 /// [RFC 5639]: https://datatracker.ietf.org/doc/rfc5639
@@ -308,6 +278,18 @@ pub static C2TNB431R1: Ecc = Ecc::new(35, 359);
 #[no_mangle]
 pub static ED25519: Ecc = Ecc::new(36, 256);
 
+/// Represents Ed25519ph, the variant of Ed25519 that signs the SHA-512
+/// digest of a message rather than the message itself, as specified in
+/// [RFC 8032].
+///
+/// The keys are the same as those of Ed25519, but a signature made with
+/// one variant does not verify with the other. X.509, TLS and SSH only
+/// define the pure variant.
+///
+/// [RFC 8032]: https://datatracker.ietf.org/doc/html/rfc8032#section-5.1
+#[no_mangle]
+pub static ED25519PH: Ecc = Ecc::new(77, 256);
+
 /// Represents the Ed448 signature algorithm as specified in the paper
 /// [High-speed high-security signatures].
 ///
@@ -315,6 +297,14 @@ pub static ED25519: Ecc = Ecc::new(36, 256);
 #[no_mangle]
 pub static ED448: Ecc = Ecc::new(37, 448);
 
+/// Represents Ed448ph, the variant of Ed448 that signs the SHAKE256
+/// digest of a message rather than the message itself, as specified in
+/// [RFC 8032].
+///
+/// [RFC 8032]: https://datatracker.ietf.org/doc/html/rfc8032#section-5.2
+#[no_mangle]
+pub static ED448PH: Ecc = Ecc::new(78, 448);
+
 /// Represents the Weierstrass curve K-163 over a prime field. Also
 /// known as sect163k1 and wap-wsg-idm-ecid-wtls3.
 #[no_mangle]
@@ -613,6 +603,12 @@ pub static SECT571R1: Ecc = B571;
 /// Represents the SM2 digital signature algorithm as defined in
 /// draft-shen-sm2-ecdsa-02.
 ///
+/// SM2 is specified by the Chinese State Cryptography Administration
+/// (OSCCA) in GM/T 0003-2012 rather than by any of the guides in this
+/// crate. Those that list the curves they approve, such as NIST and
+/// BSI, reject it in favour of a curve of their own, while those that
+/// only set key sizes judge it like any other 256-bit curve.
+///
 /// [draft-shen-sm2-ecdsa-02]: https://datatracker.ietf.org/doc/html/draft-shen-sm2-ecdsa-02
 #[no_mangle]
 pub static SM2: Ecc = Ecc::new(71, 256);
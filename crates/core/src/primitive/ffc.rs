@@ -1,7 +1,11 @@
 //! Finite field primitive and some common instances.
 use std::fmt::{Display, Formatter, Result};
+use std::result;
+use std::str::FromStr;
 
-use crate::primitive::{Primitive, Security};
+use crate::primitive::{self, Primitive, Security};
+use crate::registry::{self, Builtin};
+use crate::Error;
 
 /// Represents a finite field cryptography primitive used to implement
 /// discrete logarithm cryptography.
@@ -12,6 +16,11 @@ use crate::primitive::{Primitive, Security};
 /// Some of the primitives that fall under this category include
 /// signature algorithms such as DSA and key establishment algorithms
 /// such as Diffie-Hellman and MQV.
+///
+/// The groups used for key establishment, such as the safe-prime groups
+/// of RFC 3526 and RFC 7919, are named by their modulus alone and have
+/// an n of `0`. Whether a primitive is used for key establishment is
+/// told by its identifier (see [`Ffc::is_key_agreement`]).
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct Ffc {
@@ -25,13 +34,35 @@ impl Primitive for Ffc {
   /// the minimum of the (L, N) pair where the hash function used
   /// provides at least the same level of security.
   fn security(&self) -> Security {
+    // The prime divisor of a safe-prime group is one bit shorter than
+    // the modulus, so the modulus alone bounds its security.
+    if self.n == 0 {
+      return primitive::modulus_security(self.l);
+    }
     // FIPS-186-4 cites that the security strength associated with the
     // DSA digital signature process is no greater than the minimum of
     // the security strength of the (L, N) pair (2013, p. 15). The
-    // public keys are usually the shorter of the two and this value is
-    // divided by 2 to produce the security value (see page 54 of
-    // SP-800-57 Part 1 Rev. 5).
-    self.l.min(self.n) >> 1
+    // strength of the modulus is taken from table 2 and that of the
+    // divisor is half its length (see page 54 of SP-800-57 Part 1 Rev.
+    // 5).
+    primitive::modulus_security(self.l).min(self.n >> 1)
+  }
+
+  /// Returns whether the divisor is shorter than the modulus and long
+  /// enough not to weaken it. A 3072-bit modulus with a 224-bit divisor
+  /// is not, for example, since the divisor caps it at 112 bits of
+  /// security rather than 128. Every pair approved by FIPS-186-4 is.
+  ///
+  /// Only groups used for key establishment may leave out the divisor.
+  fn is_consistent(&self) -> bool {
+    if self.n == 0 {
+      return self.is_key_agreement();
+    }
+    self.n < self.l && self.n >> 1 >= primitive::modulus_security(self.l)
+  }
+
+  fn is_placeholder(&self) -> bool {
+    *self == FFC_NOT_SUPPORTED
   }
 }
 
@@ -39,11 +70,56 @@ impl Ffc {
   pub const fn new(id: u16, l: u16, n: u16) -> Self {
     Self { id, l, n }
   }
+
+  /// Returns whether the primitive is a group used for key
+  /// establishment such as Diffie-Hellman rather than DSA domain
+  /// parameters.
+  pub fn is_key_agreement(&self) -> bool {
+    self.id == ID_DH || matches!(self.id, 7..=18)
+  }
 }
 
 impl Display for Ffc {
+  /// Writes the name the primitive has in the [`registry`] or, for
+  /// custom sizes, `dsa_<l>_<n>`, `dh_<l>` or `dh_<l>_<n>`.
   fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-    write!(f, "dsa_{}_{}", self.l, self.n)
+    if let Some(name) = registry::name_of(&registry::FFC, self) {
+      write!(f, "{}", name)
+    } else if *self == FFC_NOT_SUPPORTED {
+      write!(f, "not supported")
+    } else if !self.is_key_agreement() {
+      write!(f, "dsa_{}_{}", self.l, self.n)
+    } else if self.n == 0 {
+      write!(f, "dh_{}", self.l)
+    } else {
+      write!(f, "dh_{}_{}", self.l, self.n)
+    }
+  }
+}
+
+impl FromStr for Ffc {
+  type Err = Error;
+
+  /// Finds a key by any of the names it is known by in the
+  /// [`registry`] or reads one of any size written as `dsa_<l>_<n>`,
+  /// `dh_<l>` or `dh_<l>_<n>`.
+  fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+    if let Some(Builtin::Ffc(entry)) = registry::find(s) {
+      return Ok(entry.primitive);
+    }
+    let name = s.to_ascii_lowercase().replace('-', "_");
+    let dsa = name
+      .strip_prefix("dsa_")
+      .and_then(|sizes| sizes.split_once('_'))
+      .and_then(|(l, n)| Some(Ffc::new(ID_DSA, l.parse().ok()?, n.parse().ok()?)));
+    let dh = || {
+      let sizes = name.strip_prefix("dh_")?;
+      let (l, n) = sizes.split_once('_').unwrap_or((sizes, "0"));
+      Some(Ffc::new(ID_DH, l.parse().ok()?, n.parse().ok()?))
+    };
+    dsa
+      .or_else(dh)
+      .ok_or_else(|| Error::UnknownPrimitive(s.to_string()))
   }
 }
 
@@ -51,6 +127,10 @@ impl Display for Ffc {
 #[no_mangle]
 pub static ID_DSA: u16 = 65534;
 
+/// An identifier for custom Diffie-Hellman groups.
+#[no_mangle]
+pub static ID_DH: u16 = 65533;
+
 /// Generic instance that represents a choice of L = 1024 and N = 160
 /// for a finite field cryptography primitive.
 #[no_mangle]
@@ -81,6 +161,54 @@ pub static DSA_7680_384: Ffc = Ffc::new(5, 7680, 384);
 #[no_mangle]
 pub static DSA_15360_512: Ffc = Ffc::new(6, 15360, 512);
 
+/// The 2048-bit finite field group ffdhe2048 defined in RFC 7919.
+#[no_mangle]
+pub static FFDHE2048: Ffc = Ffc::new(7, 2048, 0);
+
+/// The 3072-bit finite field group ffdhe3072 defined in RFC 7919.
+#[no_mangle]
+pub static FFDHE3072: Ffc = Ffc::new(8, 3072, 0);
+
+/// The 4096-bit finite field group ffdhe4096 defined in RFC 7919.
+#[no_mangle]
+pub static FFDHE4096: Ffc = Ffc::new(9, 4096, 0);
+
+/// The 6144-bit finite field group ffdhe6144 defined in RFC 7919.
+#[no_mangle]
+pub static FFDHE6144: Ffc = Ffc::new(10, 6144, 0);
+
+/// The 8192-bit finite field group ffdhe8192 defined in RFC 7919.
+#[no_mangle]
+pub static FFDHE8192: Ffc = Ffc::new(11, 8192, 0);
+
+/// The 1024-bit MODP group, known as Oakley group 2, defined in RFC 2409.
+#[no_mangle]
+pub static MODP_1024: Ffc = Ffc::new(12, 1024, 0);
+
+/// The 1536-bit MODP group, known as group 5, defined in RFC 3526.
+#[no_mangle]
+pub static MODP_1536: Ffc = Ffc::new(13, 1536, 0);
+
+/// The 2048-bit MODP group, known as group 14, defined in RFC 3526.
+#[no_mangle]
+pub static MODP_2048: Ffc = Ffc::new(14, 2048, 0);
+
+/// The 3072-bit MODP group, known as group 15, defined in RFC 3526.
+#[no_mangle]
+pub static MODP_3072: Ffc = Ffc::new(15, 3072, 0);
+
+/// The 4096-bit MODP group, known as group 16, defined in RFC 3526.
+#[no_mangle]
+pub static MODP_4096: Ffc = Ffc::new(16, 4096, 0);
+
+/// The 6144-bit MODP group, known as group 17, defined in RFC 3526.
+#[no_mangle]
+pub static MODP_6144: Ffc = Ffc::new(17, 6144, 0);
+
+/// The 8192-bit MODP group, known as group 18, defined in RFC 3526.
+#[no_mangle]
+pub static MODP_8192: Ffc = Ffc::new(18, 8192, 0);
+
 /// Placeholder for use in where this primitive is not supported.
 #[no_mangle]
 pub static FFC_NOT_SUPPORTED: Ffc = Ffc::new(u16::MAX, u16::MAX, u16::MAX);
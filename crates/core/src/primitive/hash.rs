@@ -1,11 +1,12 @@
 //! Hash function primitive and some common instances.
-use std::collections::HashMap;
 use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
 
-use once_cell::sync::Lazy;
 use serde::Serialize;
 
 use crate::primitive::{Primitive, Security};
+use crate::registry::{self, Builtin};
+use crate::Error;
 
 /// Represents a hash or hash-based function cryptographic primitive
 /// where `id` is a unique identifier and `n` the digest length.
@@ -20,50 +21,48 @@ impl Hash {
   pub const fn new(id: u16, n: u16) -> Self {
     Self { id, n }
   }
+
+  /// Returns whether the hash function belongs to the SHA-3 family
+  /// defined in FIPS 202, which includes the SHAKE extendable-output
+  /// functions.
+  pub fn is_sha3(&self) -> bool {
+    SHA3_FAMILY.contains(self)
+  }
+
+  /// Returns whether the function is an extendable-output function
+  /// (XOF), whose output can be of any length.
+  pub fn is_xof(&self) -> bool {
+    *self == SHAKE128 || *self == SHAKE256
+  }
 }
 
-// The name is kept in a lookup table instead of being embedded in the
-// type because sharing strings across language boundaries is a bit
-// dicey.
-static REPR: Lazy<HashMap<Hash, &str>> = Lazy::new(|| {
-  let mut m = HashMap::new();
-  m.insert(BLAKE_224, "blake224");
-  m.insert(BLAKE_256, "blake256");
-  m.insert(BLAKE_384, "blake384");
-  m.insert(BLAKE_512, "blake512");
-  m.insert(BLAKE2B_256, "blake2b256");
-  m.insert(BLAKE2B_384, "blake2b384");
-  m.insert(BLAKE2B_512, "blake2b512");
-  m.insert(BLAKE2S_256, "blake2s256");
-  m.insert(BLAKE3, "blake3");
-  m.insert(MD4, "md4");
-  m.insert(MD5, "md5");
-  m.insert(RIPEMD160, "ripemd160");
-  m.insert(SHA1, "sha1");
-  m.insert(SHA224, "sha224");
-  m.insert(SHA256, "sha256");
-  m.insert(SHA384, "sha384");
-  m.insert(SHA512, "sha512");
-  m.insert(SHA3_224, "sha3_224");
-  m.insert(SHA3_256, "sha3_256");
-  m.insert(SHA3_384, "sha3_384");
-  m.insert(SHA3_512, "sha3_512");
-  m.insert(SHA512_224, "sha512/224");
-  m.insert(SHA512_256, "sha512/256");
-  m.insert(SHAKE128, "shake128");
-  m.insert(SHAKE256, "shake256");
-  m.insert(WHIRLPOOL, "whirlpool");
-  m
-});
+static SHA3_FAMILY: [Hash; 6] = [SHA3_224, SHA3_256, SHA3_384, SHA3_512, SHAKE128, SHAKE256];
 
 impl Display for Hash {
+  /// Writes the name the hash function has in the [`registry`].
   fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-    let unrecognised = "unrecognised";
-    let name = REPR.get(self).unwrap_or(&unrecognised);
+    let name = match registry::name_of(&registry::HASH_FUNCTIONS, self) {
+      Some(name) => name,
+      None if *self == HASH_NOT_SUPPORTED => "not supported",
+      None => "unrecognised",
+    };
     write!(f, "{name}")
   }
 }
 
+impl FromStr for Hash {
+  type Err = Error;
+
+  /// Finds a hash function by any of the names it is known by in the
+  /// [`registry`].
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match registry::find(s) {
+      Some(Builtin::Hash(entry)) => Ok(entry.primitive),
+      _ => Err(Error::UnknownPrimitive(s.to_string())),
+    }
+  }
+}
+
 impl Primitive for Hash {
   /// Returns the security of a hash function measured as the collision
   /// resistance strength of a hash function.
@@ -74,8 +73,21 @@ impl Primitive for Hash {
   /// Some applications that use hash functions only require pre-image
   /// resistance which imposes a less stringent security requirement of
   /// just L (see page 7 of NIST SP-800-107).
+  ///
+  /// Extendable-output functions offer their full security strength
+  /// against collisions when their output is at least twice as long
+  /// (see appendix A.1 of FIPS 202). The length of the output is not
+  /// known so it is assumed to be long enough.
   fn security(&self) -> Security {
-    self.n >> 1
+    if self.is_xof() {
+      self.n
+    } else {
+      self.n >> 1
+    }
+  }
+
+  fn is_placeholder(&self) -> bool {
+    *self == HASH_NOT_SUPPORTED
   }
 }
 
@@ -234,14 +246,16 @@ pub static SHA512_224: Hash = Hash::new(22, 224);
 pub static SHA512_256: Hash = Hash::new(23, 256);
 
 /// The SHAKE128 extendable-output function as defined in [FIPS 202].
-/// This assumes an output length of 128-bits.
+/// The length is that of its security strength of 128 bits, which it
+/// offers against collisions with an output of at least 256 bits.
 ///
 /// [FIPS 202]: https://doi.org/10.6028/NIST.FIPS.202
 #[no_mangle]
 pub static SHAKE128: Hash = Hash::new(24, 128);
 
 /// The SHAKE256 extendable-output function as defined in [FIPS 202].
-/// This assumes an output length of 256-bits.
+/// The length is that of its security strength of 256 bits, which it
+/// offers against collisions with an output of at least 512 bits.
 ///
 /// [FIPS 202]: https://doi.org/10.6028/NIST.FIPS.202
 #[no_mangle]
@@ -0,0 +1,78 @@
+//! Post-quantum signatures combined with a classical one.
+//!
+//! A hybrid, or composite, signature is made of an ML-DSA or SLH-DSA
+//! signature and an elliptic curve signature over the same message, as
+//! in the composite signatures of draft-ietf-lamps-pq-composite-sigs
+//! that use ECDSA or EdDSA. Both have to verify, so a forger has to
+//! break both schemes and the combination is as strong as the stronger
+//! of the two. This hedges against flaws in schemes that have seen far
+//! less analysis than elliptic curves while keeping the protection
+//! against quantum computers.
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::primitive::ecc::Ecc;
+use crate::primitive::pqc::Pqc;
+use crate::primitive::{Primitive, Security};
+use crate::Error;
+
+/// Represents a hybrid signature of a post-quantum primitive and an
+/// elliptic curve.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Hybrid {
+  pub pqc: Pqc,
+  pub ecc: Ecc,
+}
+
+impl Hybrid {
+  pub const fn new(pqc: Pqc, ecc: Ecc) -> Self {
+    Self { pqc, ecc }
+  }
+}
+
+impl Display for Hybrid {
+  /// Writes the combination as `<pqc>+<curve>`, such as
+  /// `ML-DSA-65+nistp256`.
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "{}+{}", self.pqc, self.ecc)
+  }
+}
+
+impl FromStr for Hybrid {
+  type Err = Error;
+
+  /// Reads a combination written as `<pqc>+<curve>`, where the curve is
+  /// given by any of the names it is known by.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let err = || Error::UnknownPrimitive(s.to_string());
+    let (pqc, ecc) = s.split_once('+').ok_or_else(err)?;
+    Ok(Self::new(
+      pqc.parse().map_err(|_| err())?,
+      ecc.parse().map_err(|_| err())?,
+    ))
+  }
+}
+
+impl Primitive for Hybrid {
+  /// Returns the security of the stronger of the two primitives against
+  /// classical computers.
+  fn security(&self) -> Security {
+    self.pqc.security().max(self.ecc.security())
+  }
+
+  fn is_placeholder(&self) -> bool {
+    self.pqc.is_placeholder() && self.ecc.is_placeholder()
+  }
+}
+
+impl Serialize for Hybrid {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: serde::Serializer,
+  {
+    serializer.serialize_str(&self.to_string())
+  }
+}
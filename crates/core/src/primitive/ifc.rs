@@ -1,7 +1,10 @@
 //! Integer factorisation primitive and some common instances.
 use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
 
-use crate::primitive::{Primitive, Security};
+use crate::primitive::{self, Primitive, Security};
+use crate::registry::{self, Builtin};
+use crate::Error;
 
 /// Represents an integer factorisation cryptography primitive the most
 /// common of which is the RSA signature algorithm where k indicates the
@@ -17,13 +20,20 @@ impl Ifc {
   pub const fn new(id: u16, k: u16) -> Self {
     Self { id, k }
   }
+
+  /// Returns whether the key is used with the RSA-PSS signature scheme
+  /// rather than PKCS #1 v1.5, which includes the RSA-PSS instances
+  /// with the identifiers 9 to 17.
+  pub fn is_pss(&self) -> bool {
+    self.id == ID_RSA_PSS || matches!(self.id, 9..=17)
+  }
 }
 
 impl Display for Ifc {
   fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
     if self.id == ID_RSA_PKCS1 || matches!(self.id, 1..=8) {
       write!(f, "rsa_pkcs1_{}", self.k)
-    } else if self.id == ID_RSA_PSS || matches!(self.id, 9..=17) {
+    } else if self.is_pss() {
       write!(f, "rsa_pss_{}", self.k)
     } else if self.id == u16::MAX {
       write!(f, "not allowed")
@@ -33,18 +43,33 @@ impl Display for Ifc {
   }
 }
 
+impl FromStr for Ifc {
+  type Err = Error;
+
+  /// Finds a key by any of the names it is known by in the
+  /// [`registry`] or reads one of any size written as `rsa_pkcs1_<k>`
+  /// or `rsa_pss_<k>`.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    if let Some(Builtin::Ifc(entry)) = registry::find(s) {
+      return Ok(entry.primitive);
+    }
+    let name = s.to_ascii_lowercase().replace('-', "_");
+    [("rsa_pkcs1_", ID_RSA_PKCS1), ("rsa_pss_", ID_RSA_PSS)]
+      .into_iter()
+      .find_map(|(prefix, id)| Some(Ifc::new(id, name.strip_prefix(prefix)?.parse().ok()?)))
+      .ok_or_else(|| Error::UnknownPrimitive(s.to_string()))
+  }
+}
+
 impl Primitive for Ifc {
   /// Returns the approximate *minimum* security provided by a key of
   /// the size `k`.
   fn security(&self) -> Security {
-    match self.k {
-      ..=1023 => 0,
-      1024..=2047 => 80,
-      2048..=3071 => 112,
-      3072..=7679 => 128,
-      7680..=15359 => 192,
-      15360.. => 256,
-    }
+    primitive::modulus_security(self.k)
+  }
+
+  fn is_placeholder(&self) -> bool {
+    *self == IFC_NOT_ALLOWED
   }
 }
 
@@ -0,0 +1,267 @@
+//! Password-based key derivation functions.
+//!
+//! Disk encryption and key stores derive the key that unwraps the master
+//! key from a passphrase, so that guessing the passphrase costs as much
+//! as the derivation is made to. PBKDF2 is only made to cost time, by its
+//! number of iterations of HMAC, while Argon2 is also made to cost
+//! memory, which keeps GPUs and ASICs from running many guesses at once.
+//!
+//! None of the guides sets the cost of a derivation, so it is assessed
+//! against the smallest costs of the OWASP Password Storage Cheat Sheet.
+//! PBKDF2 takes more iterations with hash functions that are cheaper to
+//! compute, such as SHA-1.
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::primitive::hash::*;
+use crate::standard::rule::Citation;
+use crate::Error;
+
+/// The Argon2 costs that are equally recommended as `(iterations,
+/// memory in KiB)`, which trade memory for time.
+const ARGON2_COSTS: [(u32, u32); 5] = [(1, 47104), (2, 19456), (3, 12288), (4, 9216), (5, 7168)];
+
+/// How a key is derived from a passphrase.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Function {
+  /// PBKDF2 of PKCS #5 (RFC 8018) with HMAC over a hash function.
+  Pbkdf2,
+  /// Argon2i of RFC 9106, whose memory accesses do not depend on the
+  /// passphrase but which falls sooner to trading time for memory.
+  Argon2i,
+  /// Argon2id of RFC 9106, which RFC 9106 recommends over Argon2i.
+  Argon2id,
+}
+
+impl Display for Function {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    let name = match self {
+      Self::Pbkdf2 => "pbkdf2",
+      Self::Argon2i => "argon2i",
+      Self::Argon2id => "argon2id",
+    };
+    write!(f, "{}", name)
+  }
+}
+
+/// Represents the derivation of a key where `iterations` is the number
+/// of iterations of PBKDF2 or passes of Argon2, and `memory` and
+/// `parallelism` the KiB and lanes of Argon2, which are 0 for PBKDF2.
+/// Argon2 hashes with BLAKE2b.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Kdf {
+  pub function: Function,
+  pub hash: Hash,
+  pub iterations: u32,
+  pub memory: u32,
+  pub parallelism: u32,
+}
+
+impl Kdf {
+  pub const fn pbkdf2(hash: Hash, iterations: u32) -> Self {
+    Self {
+      function: Function::Pbkdf2,
+      hash,
+      iterations,
+      memory: 0,
+      parallelism: 0,
+    }
+  }
+
+  pub const fn argon2(function: Function, iterations: u32, memory: u32, parallelism: u32) -> Self {
+    Self {
+      function,
+      hash: BLAKE2B_512,
+      iterations,
+      memory,
+      parallelism,
+    }
+  }
+
+  /// Returns the cheapest derivation that is recommended with the same
+  /// function and hash function. Hash functions other than SHA-1, SHA-256
+  /// and SHA-512 take as many iterations of PBKDF2 as SHA-256.
+  pub fn minimum(&self) -> Self {
+    match self.function {
+      Function::Pbkdf2 => {
+        let iterations = match self.hash {
+          hash if hash == SHA1 => 1_300_000,
+          hash if hash == SHA512 => 210_000,
+          _ => 600_000,
+        };
+        Self::pbkdf2(self.hash, iterations)
+      },
+      function => {
+        // Of the costs that are equally recommended, the one nearest to
+        // the number of passes.
+        let (iterations, memory) = ARGON2_COSTS
+          .into_iter()
+          .rev()
+          .find(|&(iterations, _)| iterations <= self.iterations.max(1))
+          .unwrap_or(ARGON2_COSTS[0]);
+        Self::argon2(function, iterations, memory, 1)
+      },
+    }
+  }
+
+  /// Returns whether guessing a passphrase costs at least as much as the
+  /// cheapest derivation that is recommended.
+  pub fn is_costly_enough(&self) -> bool {
+    match self.function {
+      Function::Pbkdf2 => self.iterations >= self.minimum().iterations,
+      _ => ARGON2_COSTS
+        .iter()
+        .any(|&(iterations, memory)| self.iterations >= iterations && self.memory >= memory),
+    }
+  }
+
+  /// Returns the publication the costs are taken from.
+  pub fn citation(&self) -> Citation {
+    Citation {
+      document: "OWASP Password Storage Cheat Sheet",
+      section: match self.function {
+        Function::Pbkdf2 => "PBKDF2",
+        _ => "Argon2id",
+      },
+      url: Some("https://cheatsheetseries.owasp.org/cheatsheets/Password_Storage_Cheat_Sheet.html"),
+    }
+  }
+}
+
+impl Display for Kdf {
+  /// Writes the derivation as `pbkdf2_<hash>_i<iterations>` or as
+  /// `<function>_t<passes>_m<KiB>_p<lanes>`, such as `pbkdf2_sha256_i600000`
+  /// or `argon2id_t2_m19456_p1`.
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self.function {
+      Function::Pbkdf2 => write!(f, "pbkdf2_{}_i{}", self.hash, self.iterations),
+      function => write!(
+        f,
+        "{}_t{}_m{}_p{}",
+        function, self.iterations, self.memory, self.parallelism
+      ),
+    }
+  }
+}
+
+impl FromStr for Kdf {
+  type Err = Error;
+
+  /// Reads a derivation written as it is displayed, where the hash
+  /// function of PBKDF2 is given by any of the names it is known by.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let err = || Error::UnknownPrimitive(s.to_string());
+    let lower = s.to_ascii_lowercase();
+    if let Some(rest) = lower.strip_prefix("pbkdf2_") {
+      let (hash, iterations) = rest.rsplit_once("_i").ok_or_else(err)?;
+      let hash = hash.parse().map_err(|_| err())?;
+      return Ok(Self::pbkdf2(hash, iterations.parse().map_err(|_| err())?));
+    }
+    let (function, costs) = lower.split_once('_').ok_or_else(err)?;
+    let function = match function {
+      "argon2i" => Function::Argon2i,
+      "argon2id" => Function::Argon2id,
+      _ => return Err(err()),
+    };
+    let mut parts = costs.split('_');
+    let mut cost = |prefix| -> Result<u32, Error> {
+      parts
+        .next()
+        .and_then(|part| part.strip_prefix(prefix))
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(err)
+    };
+    let kdf = Self::argon2(function, cost("t")?, cost("m")?, cost("p")?);
+    match parts.next() {
+      Some(_) => Err(err()),
+      None => Ok(kdf),
+    }
+  }
+}
+
+impl Serialize for Kdf {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: serde::Serializer,
+  {
+    serializer.serialize_str(&self.to_string())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn names_are_written_with_their_costs() {
+    assert_eq!(
+      Kdf::pbkdf2(SHA256, 600_000).to_string(),
+      "pbkdf2_sha256_i600000"
+    );
+    assert_eq!(
+      Kdf::argon2(Function::Argon2id, 4, 1_048_576, 4).to_string(),
+      "argon2id_t4_m1048576_p4"
+    );
+  }
+
+  #[test]
+  fn names_round_trip() {
+    for kdf in [
+      Kdf::pbkdf2(SHA512_256, 210_000),
+      Kdf::argon2(Function::Argon2i, 3, 65536, 4),
+    ] {
+      assert_eq!(kdf.to_string().parse(), Ok(kdf));
+    }
+    assert_eq!(
+      "PBKDF2_SHA-1_I1300000".parse(),
+      Ok(Kdf::pbkdf2(SHA1, 1_300_000))
+    );
+  }
+
+  #[test]
+  fn unknown_names_are_rejected() {
+    assert!("pbkdf2_sha256".parse::<Kdf>().is_err());
+    assert!("scrypt_n32768_r8_p1".parse::<Kdf>().is_err());
+    assert!("argon2id_t2_m19456".parse::<Kdf>().is_err());
+    assert!("argon2id_t2_m19456_p1_x2".parse::<Kdf>().is_err());
+  }
+
+  #[test]
+  fn pbkdf2_iterations_depend_on_the_hash_function() {
+    assert!(Kdf::pbkdf2(SHA512, 210_000).is_costly_enough());
+    assert!(!Kdf::pbkdf2(SHA256, 210_000).is_costly_enough());
+    assert!(!Kdf::pbkdf2(SHA1, 600_000).is_costly_enough());
+    assert_eq!(
+      Kdf::pbkdf2(SHA1, 2000).minimum(),
+      Kdf::pbkdf2(SHA1, 1_300_000)
+    );
+  }
+
+  #[test]
+  fn argon2_trades_memory_for_passes() {
+    assert!(Kdf::argon2(Function::Argon2id, 1, 47104, 1).is_costly_enough());
+    assert!(Kdf::argon2(Function::Argon2id, 5, 7168, 1).is_costly_enough());
+    assert!(!Kdf::argon2(Function::Argon2id, 1, 19456, 1).is_costly_enough());
+    assert!(!Kdf::argon2(Function::Argon2i, 10, 4096, 4).is_costly_enough());
+  }
+
+  #[test]
+  fn argon2_minimum_keeps_the_passes() {
+    assert_eq!(
+      Kdf::argon2(Function::Argon2id, 2, 1024, 4).minimum(),
+      Kdf::argon2(Function::Argon2id, 2, 19456, 1)
+    );
+    assert_eq!(
+      Kdf::argon2(Function::Argon2i, 0, 1024, 1).minimum(),
+      Kdf::argon2(Function::Argon2i, 1, 47104, 1)
+    );
+    assert_eq!(
+      Kdf::argon2(Function::Argon2id, 12, 1024, 1).minimum(),
+      Kdf::argon2(Function::Argon2id, 5, 7168, 1)
+    );
+  }
+}
@@ -0,0 +1,224 @@
+//! Post-quantum signature primitives and some common instances.
+//!
+//! ML-DSA, the lattice-based scheme of FIPS 204, and SLH-DSA, the
+//! stateless hash-based scheme of FIPS 205, come in parameter sets that
+//! each meet one of the security categories of the NIST post-quantum
+//! project. A category is defined as being at least as hard to break as
+//! a key search on AES or a collision search on SHA-2 of a given size,
+//! so the security of a parameter set is given as that of the AES key
+//! of its category: 128 bits for categories 1 and 2, 192 bits for
+//! category 3 and 256 bits for category 5.
+//!
+//! Stateful hash-based signatures are in [`stateful`] and post-quantum
+//! signatures combined with a classical one in [`hybrid`].
+//!
+//! [`stateful`]: crate::primitive::stateful
+//! [`hybrid`]: crate::primitive::hybrid
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::primitive::{Primitive, Security};
+use crate::registry;
+use crate::Error;
+
+/// Represents a post-quantum signature primitive where `security` is
+/// that of the category its parameter set meets.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Pqc {
+  pub id: u16,
+  pub security: u16,
+}
+
+impl Pqc {
+  pub const fn new(id: u16, security: u16) -> Self {
+    Self { id, security }
+  }
+
+  /// Returns the name of the parameter set as given in FIPS 204 and
+  /// FIPS 205.
+  pub fn name(&self) -> &'static str {
+    NAMES
+      .iter()
+      .find(|(primitive, _)| primitive == self)
+      .map_or("unrecognised", |&(_, name)| name)
+  }
+
+  /// Returns whether the primitive is an ML-DSA parameter set.
+  pub fn is_ml_dsa(&self) -> bool {
+    FAMILIES[0].contains(self)
+  }
+
+  /// Returns whether the primitive is an SLH-DSA parameter set.
+  pub fn is_slh_dsa(&self) -> bool {
+    FAMILIES[1..].iter().any(|family| family.contains(self))
+  }
+
+  /// Returns the parameter set of the same family, such as SLH-DSA with
+  /// SHAKE and small signatures, that meets the lowest category of at
+  /// least `security` bits, or the highest category if none does.
+  /// Primitives that are not specified by FIPS 204 or FIPS 205 have no
+  /// family and give `None`.
+  pub fn at_least(&self, security: Security) -> Option<Pqc> {
+    let family = FAMILIES.iter().find(|family| family.contains(self))?;
+    family
+      .iter()
+      .find(|key| key.security >= security)
+      .or(family.last())
+      .copied()
+  }
+}
+
+impl Display for Pqc {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.name())
+  }
+}
+
+impl FromStr for Pqc {
+  type Err = Error;
+
+  /// Finds a parameter set by its name in FIPS 204 or FIPS 205, such as
+  /// `ML-DSA-65` or `SLH-DSA-SHAKE-128s`, ignoring case as well as
+  /// hyphens and underscores.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let name = registry::normalize(s);
+    PARAMETER_SETS
+      .into_iter()
+      .find(|key| registry::normalize(key.name()) == name)
+      .ok_or_else(|| Error::UnknownPrimitive(s.to_string()))
+  }
+}
+
+impl Primitive for Pqc {
+  fn security(&self) -> Security {
+    self.security
+  }
+
+  fn is_placeholder(&self) -> bool {
+    *self == PQC_NOT_ALLOWED
+  }
+}
+
+impl Serialize for Pqc {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: serde::Serializer,
+  {
+    serializer.serialize_str(&self.to_string())
+  }
+}
+
+/// ML-DSA-44 as defined in FIPS 204, which meets category 2.
+#[no_mangle]
+pub static ML_DSA_44: Pqc = Pqc::new(1, 128);
+
+/// ML-DSA-65 as defined in FIPS 204, which meets category 3.
+#[no_mangle]
+pub static ML_DSA_65: Pqc = Pqc::new(2, 192);
+
+/// ML-DSA-87 as defined in FIPS 204, which meets category 5.
+#[no_mangle]
+pub static ML_DSA_87: Pqc = Pqc::new(3, 256);
+
+/// SLH-DSA-SHA2-128s as defined in FIPS 205, which meets category 1.
+#[no_mangle]
+pub static SLH_DSA_SHA2_128S: Pqc = Pqc::new(4, 128);
+
+/// SLH-DSA-SHA2-192s as defined in FIPS 205, which meets category 3.
+#[no_mangle]
+pub static SLH_DSA_SHA2_192S: Pqc = Pqc::new(5, 192);
+
+/// SLH-DSA-SHA2-256s as defined in FIPS 205, which meets category 5.
+#[no_mangle]
+pub static SLH_DSA_SHA2_256S: Pqc = Pqc::new(6, 256);
+
+/// SLH-DSA-SHA2-128f as defined in FIPS 205, which meets category 1.
+#[no_mangle]
+pub static SLH_DSA_SHA2_128F: Pqc = Pqc::new(7, 128);
+
+/// SLH-DSA-SHA2-192f as defined in FIPS 205, which meets category 3.
+#[no_mangle]
+pub static SLH_DSA_SHA2_192F: Pqc = Pqc::new(8, 192);
+
+/// SLH-DSA-SHA2-256f as defined in FIPS 205, which meets category 5.
+#[no_mangle]
+pub static SLH_DSA_SHA2_256F: Pqc = Pqc::new(9, 256);
+
+/// SLH-DSA-SHAKE-128s as defined in FIPS 205, which meets category 1.
+#[no_mangle]
+pub static SLH_DSA_SHAKE_128S: Pqc = Pqc::new(10, 128);
+
+/// SLH-DSA-SHAKE-192s as defined in FIPS 205, which meets category 3.
+#[no_mangle]
+pub static SLH_DSA_SHAKE_192S: Pqc = Pqc::new(11, 192);
+
+/// SLH-DSA-SHAKE-256s as defined in FIPS 205, which meets category 5.
+#[no_mangle]
+pub static SLH_DSA_SHAKE_256S: Pqc = Pqc::new(12, 256);
+
+/// SLH-DSA-SHAKE-128f as defined in FIPS 205, which meets category 1.
+#[no_mangle]
+pub static SLH_DSA_SHAKE_128F: Pqc = Pqc::new(13, 128);
+
+/// SLH-DSA-SHAKE-192f as defined in FIPS 205, which meets category 3.
+#[no_mangle]
+pub static SLH_DSA_SHAKE_192F: Pqc = Pqc::new(14, 192);
+
+/// SLH-DSA-SHAKE-256f as defined in FIPS 205, which meets category 5.
+#[no_mangle]
+pub static SLH_DSA_SHAKE_256F: Pqc = Pqc::new(15, 256);
+
+/// Placeholder for use in where this primitive is not allowed.
+#[no_mangle]
+pub static PQC_NOT_ALLOWED: Pqc = Pqc::new(u16::MAX, u16::MAX);
+
+/// Every parameter set of FIPS 204 and FIPS 205.
+pub static PARAMETER_SETS: [Pqc; 15] = [
+  ML_DSA_44,
+  ML_DSA_65,
+  ML_DSA_87,
+  SLH_DSA_SHA2_128S,
+  SLH_DSA_SHA2_192S,
+  SLH_DSA_SHA2_256S,
+  SLH_DSA_SHA2_128F,
+  SLH_DSA_SHA2_192F,
+  SLH_DSA_SHA2_256F,
+  SLH_DSA_SHAKE_128S,
+  SLH_DSA_SHAKE_192S,
+  SLH_DSA_SHAKE_256S,
+  SLH_DSA_SHAKE_128F,
+  SLH_DSA_SHAKE_192F,
+  SLH_DSA_SHAKE_256F,
+];
+
+// The parameter sets that only differ in their category, from the
+// lowest to the highest.
+static FAMILIES: [[Pqc; 3]; 5] = [
+  [ML_DSA_44, ML_DSA_65, ML_DSA_87],
+  [SLH_DSA_SHA2_128S, SLH_DSA_SHA2_192S, SLH_DSA_SHA2_256S],
+  [SLH_DSA_SHA2_128F, SLH_DSA_SHA2_192F, SLH_DSA_SHA2_256F],
+  [SLH_DSA_SHAKE_128S, SLH_DSA_SHAKE_192S, SLH_DSA_SHAKE_256S],
+  [SLH_DSA_SHAKE_128F, SLH_DSA_SHAKE_192F, SLH_DSA_SHAKE_256F],
+];
+
+static NAMES: [(Pqc, &str); 16] = [
+  (ML_DSA_44, "ML-DSA-44"),
+  (ML_DSA_65, "ML-DSA-65"),
+  (ML_DSA_87, "ML-DSA-87"),
+  (SLH_DSA_SHA2_128S, "SLH-DSA-SHA2-128s"),
+  (SLH_DSA_SHA2_192S, "SLH-DSA-SHA2-192s"),
+  (SLH_DSA_SHA2_256S, "SLH-DSA-SHA2-256s"),
+  (SLH_DSA_SHA2_128F, "SLH-DSA-SHA2-128f"),
+  (SLH_DSA_SHA2_192F, "SLH-DSA-SHA2-192f"),
+  (SLH_DSA_SHA2_256F, "SLH-DSA-SHA2-256f"),
+  (SLH_DSA_SHAKE_128S, "SLH-DSA-SHAKE-128s"),
+  (SLH_DSA_SHAKE_192S, "SLH-DSA-SHAKE-192s"),
+  (SLH_DSA_SHAKE_256S, "SLH-DSA-SHAKE-256s"),
+  (SLH_DSA_SHAKE_128F, "SLH-DSA-SHAKE-128f"),
+  (SLH_DSA_SHAKE_192F, "SLH-DSA-SHAKE-192f"),
+  (SLH_DSA_SHAKE_256F, "SLH-DSA-SHAKE-256f"),
+  (PQC_NOT_ALLOWED, "not allowed"),
+];
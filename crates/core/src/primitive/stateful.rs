@@ -0,0 +1,175 @@
+//! Stateful hash-based signature primitives and some common instances.
+//!
+//! LMS (RFC 8554) and XMSS (RFC 8391), along with their multi-tree
+//! variants HSS and XMSS^MT, sign with one-time keys derived from a
+//! single private key. They are secure against quantum computers as
+//! long as the hash function is, but only if no one-time key is ever
+//! used twice, which is why NIST SP 800-208 only approves them where
+//! the state is kept in hardware.
+//!
+//! The security of a parameter set is the size `n` of the output of its
+//! hash function in bits. Forging a signature amounts to finding a
+//! second pre-image, and the tree height only limits how many messages
+//! can be signed, so the multi-tree variants and the tree heights share
+//! an instance here.
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::primitive::{Primitive, Security};
+use crate::registry;
+use crate::Error;
+
+/// Represents a stateful hash-based signature primitive where `n` is
+/// the size of the hash output in bits.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Stateful {
+  pub id: u16,
+  pub n: u16,
+}
+
+impl Stateful {
+  pub const fn new(id: u16, n: u16) -> Self {
+    Self { id, n }
+  }
+
+  /// Returns the name of the parameter set, which is that of its hash
+  /// function in SP 800-208.
+  pub fn name(&self) -> &'static str {
+    NAMES
+      .iter()
+      .find(|(primitive, _)| primitive == self)
+      .map_or("unrecognised", |&(_, name)| name)
+  }
+
+  /// Returns the parameter set of the same scheme and hash function
+  /// with the smallest output of at least `security` bits, or the
+  /// largest if none is. Primitives that are not approved by SP 800-208
+  /// give `None`.
+  pub fn at_least(&self, security: Security) -> Option<Stateful> {
+    let family = FAMILIES.iter().find(|family| family.contains(self))?;
+    family
+      .iter()
+      .find(|key| key.n >= security)
+      .or(family.last())
+      .copied()
+  }
+}
+
+impl Display for Stateful {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.name())
+  }
+}
+
+impl FromStr for Stateful {
+  type Err = Error;
+
+  /// Finds a parameter set by its name, such as `LMS-SHA256-192` or
+  /// `XMSS-SHAKE256-256`, ignoring case as well as hyphens and
+  /// underscores.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let name = registry::normalize(s);
+    PARAMETER_SETS
+      .into_iter()
+      .find(|key| registry::normalize(key.name()) == name)
+      .ok_or_else(|| Error::UnknownPrimitive(s.to_string()))
+  }
+}
+
+impl Primitive for Stateful {
+  fn security(&self) -> Security {
+    self.n
+  }
+
+  fn is_placeholder(&self) -> bool {
+    *self == STATEFUL_NOT_ALLOWED
+  }
+}
+
+impl Serialize for Stateful {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: serde::Serializer,
+  {
+    serializer.serialize_str(&self.to_string())
+  }
+}
+
+/// LMS and HSS with SHA-256/192 as defined in SP 800-208.
+#[no_mangle]
+pub static LMS_SHA256_192: Stateful = Stateful::new(1, 192);
+
+/// LMS and HSS with SHA-256 as defined in RFC 8554.
+#[no_mangle]
+pub static LMS_SHA256_256: Stateful = Stateful::new(2, 256);
+
+/// LMS and HSS with SHAKE256/192 as defined in SP 800-208.
+#[no_mangle]
+pub static LMS_SHAKE256_192: Stateful = Stateful::new(3, 192);
+
+/// LMS and HSS with SHAKE256/256 as defined in SP 800-208.
+#[no_mangle]
+pub static LMS_SHAKE256_256: Stateful = Stateful::new(4, 256);
+
+/// XMSS and XMSS^MT with SHA-256/192 as defined in SP 800-208.
+#[no_mangle]
+pub static XMSS_SHA256_192: Stateful = Stateful::new(5, 192);
+
+/// XMSS and XMSS^MT with SHA-256 as defined in RFC 8391.
+#[no_mangle]
+pub static XMSS_SHA256_256: Stateful = Stateful::new(6, 256);
+
+/// XMSS and XMSS^MT with SHAKE256/192 as defined in SP 800-208.
+#[no_mangle]
+pub static XMSS_SHAKE256_192: Stateful = Stateful::new(7, 192);
+
+/// XMSS and XMSS^MT with SHAKE256/256 as defined in SP 800-208.
+#[no_mangle]
+pub static XMSS_SHAKE256_256: Stateful = Stateful::new(8, 256);
+
+/// XMSS and XMSS^MT with SHA-512 as defined in RFC 8391, which SP
+/// 800-208 does not approve.
+#[no_mangle]
+pub static XMSS_SHA512_512: Stateful = Stateful::new(9, 512);
+
+/// Placeholder for use in where this primitive is not allowed.
+#[no_mangle]
+pub static STATEFUL_NOT_ALLOWED: Stateful = Stateful::new(u16::MAX, u16::MAX);
+
+/// Every parameter set of RFC 8391, RFC 8554 and SP 800-208.
+pub static PARAMETER_SETS: [Stateful; 9] = [
+  LMS_SHA256_192,
+  LMS_SHA256_256,
+  LMS_SHAKE256_192,
+  LMS_SHAKE256_256,
+  XMSS_SHA256_192,
+  XMSS_SHA256_256,
+  XMSS_SHAKE256_192,
+  XMSS_SHAKE256_256,
+  XMSS_SHA512_512,
+];
+
+// The parameter sets approved by SP 800-208 that only differ in the
+// size of the hash output, from the smallest to the largest.
+static FAMILIES: [[Stateful; 2]; 4] = [
+  [LMS_SHA256_192, LMS_SHA256_256],
+  [LMS_SHAKE256_192, LMS_SHAKE256_256],
+  [XMSS_SHA256_192, XMSS_SHA256_256],
+  [XMSS_SHAKE256_192, XMSS_SHAKE256_256],
+];
+
+static NAMES: [(Stateful, &str); 10] = [
+  (LMS_SHA256_192, "LMS-SHA256-192"),
+  (LMS_SHA256_256, "LMS-SHA256-256"),
+  (LMS_SHAKE256_192, "LMS-SHAKE256-192"),
+  (LMS_SHAKE256_256, "LMS-SHAKE256-256"),
+  (XMSS_SHA256_192, "XMSS-SHA256-192"),
+  (XMSS_SHA256_256, "XMSS-SHA256-256"),
+  (XMSS_SHAKE256_192, "XMSS-SHAKE256-192"),
+  (XMSS_SHAKE256_256, "XMSS-SHAKE256-256"),
+  (XMSS_SHA512_512, "XMSS-SHA512-512"),
+  (STATEFUL_NOT_ALLOWED, "not allowed"),
+];
@@ -1,5 +1,13 @@
-//! Symmetric key primitive and some common instances.
+//! Symmetric key primitive, the modes it is used in and some common
+//! instances.
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use serde::Serialize;
+
 use crate::primitive::{Primitive, Security};
+use crate::registry::{self, Builtin};
+use crate::Error;
 
 /// Represents a symmetric key cryptography primitive.
 #[repr(C)]
@@ -13,6 +21,139 @@ impl Symmetric {
   pub const fn new(id: u16, security: u16) -> Self {
     Self { id, security }
   }
+
+  /// Returns the size in bits of the blocks the cipher encrypts.
+  ///
+  /// Blocks are likely to collide once about 2^(b/2) of them have been
+  /// encrypted under one key in modes such as CBC, which leaks the
+  /// plaintext. This is within reach for 64-bit blocks, as the Sweet32
+  /// attack showed, however strong the key.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use wardstone_core::primitive::symmetric::{AES128, TDEA3};
+  ///
+  /// assert_eq!(AES128.block_size(), 128);
+  /// assert_eq!(TDEA3.block_size(), 64);
+  /// ```
+  pub fn block_size(&self) -> u16 {
+    if SMALL_BLOCKS.contains(self) {
+      64
+    } else {
+      128
+    }
+  }
+}
+
+static SMALL_BLOCKS: [Symmetric; 6] = [BLOWFISH, DES, DESX, IDEA, TDEA2, TDEA3];
+
+/// A mode of operation of a block cipher.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Mode {
+  Ecb,
+  Cbc,
+  Cfb,
+  Ofb,
+  Ctr,
+  /// XEX with ciphertext stealing as used for disks, defined in IEEE
+  /// 1619.
+  Xts,
+  Ccm,
+  Gcm,
+  /// Key wrapping as defined in NIST SP 800-38F and RFC 3394.
+  Kw,
+}
+
+impl Mode {
+  /// Every mode in the order they are declared.
+  pub const ALL: [Mode; 9] = [
+    Self::Ecb,
+    Self::Cbc,
+    Self::Cfb,
+    Self::Ofb,
+    Self::Ctr,
+    Self::Xts,
+    Self::Ccm,
+    Self::Gcm,
+    Self::Kw,
+  ];
+
+  /// Returns whether the mode encrypts data of any size, as opposed to
+  /// key wrapping, which only ever encrypts a few keys under one key.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use wardstone_core::primitive::symmetric::Mode;
+  ///
+  /// assert!(Mode::Cbc.is_bulk());
+  /// assert!(!Mode::Kw.is_bulk());
+  /// ```
+  pub fn is_bulk(&self) -> bool {
+    *self != Self::Kw
+  }
+}
+
+impl Display for Mode {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    let name = match self {
+      Self::Ecb => "ecb",
+      Self::Cbc => "cbc",
+      Self::Cfb => "cfb",
+      Self::Ofb => "ofb",
+      Self::Ctr => "ctr",
+      Self::Xts => "xts",
+      Self::Ccm => "ccm",
+      Self::Gcm => "gcm",
+      Self::Kw => "kw",
+    };
+    write!(f, "{}", name)
+  }
+}
+
+impl FromStr for Mode {
+  type Err = Error;
+
+  /// Finds a mode by its name, such as `cbc`, ignoring case.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Self::ALL
+      .into_iter()
+      .find(|mode| mode.to_string().eq_ignore_ascii_case(s))
+      .ok_or_else(|| Error::UnknownPrimitive(s.to_string()))
+  }
+}
+
+impl Display for Symmetric {
+  /// Writes the name the primitive has in the [`registry`].
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    let name = registry::name_of(&registry::SYMMETRIC, self).unwrap_or("unrecognised");
+    write!(f, "{name}")
+  }
+}
+
+impl FromStr for Symmetric {
+  type Err = Error;
+
+  /// Finds a primitive by any of the names it is known by in the
+  /// [`registry`].
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match registry::find(s) {
+      Some(Builtin::Symmetric(entry)) => Ok(entry.primitive),
+      _ => Err(Error::UnknownPrimitive(s.to_string())),
+    }
+  }
+}
+
+impl Serialize for Symmetric {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: serde::Serializer,
+  {
+    let s = format!("{}", self);
+    serializer.serialize_str(&s)
+  }
 }
 
 impl Primitive for Symmetric {
@@ -70,6 +211,11 @@ pub static DESX: Symmetric = Symmetric::new(9, 120);
 #[no_mangle]
 pub static IDEA: Symmetric = Symmetric::new(10, 126 /* See Wikipedia article. */);
 
+/// The Blowfish encryption algorithm with a 128-bit key, which is how
+/// it is mostly used although it takes keys of up to 448 bits.
+#[no_mangle]
+pub static BLOWFISH: Symmetric = Symmetric::new(16, 128);
+
 /// The Serpent encryption algorithm.
 #[no_mangle]
 pub static SERPENT128: Symmetric = Symmetric::new(11, 128);
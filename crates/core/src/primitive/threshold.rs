@@ -0,0 +1,232 @@
+//! Threshold and multi-signature configurations.
+//!
+//! Custody of high-value keys is commonly split between several parties
+//! so that any `t` of `n` of them have to take part in signing. The key
+//! is either shared with a threshold signing protocol, so that a single
+//! signature is verified with a single public key, or every party keeps
+//! a key of its own and `t` of the `n` signatures are required, as with
+//! multi-signature scripts.
+//!
+//! Splitting the key does not make it any stronger, so the security of
+//! a configuration is that of its curve. What sharing adds is that fewer
+//! than `t` parties learn nothing about the key, which only holds if at
+//! least two parties are needed to sign.
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::primitive::ecc::*;
+use crate::primitive::{Primitive, Security};
+use crate::Error;
+
+/// How the signing key of a configuration is split between parties.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Scheme {
+  /// Threshold ECDSA, such as the protocols of Gennaro and Goldfeder
+  /// (GG18 and GG20) or of Canetti et al. (CGGMP21).
+  Ecdsa,
+  /// FROST, the threshold Schnorr signatures of RFC 9591, which are
+  /// verified as Ed25519 or Ed448 signatures on the Edwards curves.
+  Frost,
+  /// Threshold BLS signatures over BLS12-381, as in
+  /// draft-irtf-cfrg-bls-signature.
+  Bls,
+  /// A key for every party, of which `t` have to sign.
+  Multisig,
+}
+
+impl Scheme {
+  /// Every scheme in the order they are declared.
+  pub const ALL: [Scheme; 4] = [Self::Ecdsa, Self::Frost, Self::Bls, Self::Multisig];
+
+  /// Returns whether the scheme is defined over the given curve. FROST
+  /// is only defined for the ciphersuites of RFC 9591 and threshold
+  /// ECDSA for curves in short Weierstrass form.
+  pub fn fits(&self, curve: Ecc) -> bool {
+    let edwards = [ED25519, ED25519PH, ED448, ED448PH];
+    let exchange = [X25519, X448];
+    match self {
+      Self::Ecdsa => curve != BLS12_381 && !edwards.contains(&curve) && !exchange.contains(&curve),
+      Self::Frost => [ED25519, ED448, P256, SECP256K1].contains(&curve),
+      Self::Bls => curve == BLS12_381,
+      Self::Multisig => !exchange.contains(&curve),
+    }
+  }
+}
+
+impl Display for Scheme {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    let name = match self {
+      Self::Ecdsa => "ecdsa",
+      Self::Frost => "frost",
+      Self::Bls => "bls",
+      Self::Multisig => "multisig",
+    };
+    write!(f, "{}", name)
+  }
+}
+
+/// Represents a `t`-of-`n` signing configuration where `scheme` is how
+/// the key is split and `curve` what the signatures are made over.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Threshold {
+  pub scheme: Scheme,
+  pub curve: Ecc,
+  pub t: u16,
+  pub n: u16,
+}
+
+impl Threshold {
+  pub const fn new(scheme: Scheme, curve: Ecc, t: u16, n: u16) -> Self {
+    Self {
+      scheme,
+      curve,
+      t,
+      n,
+    }
+  }
+}
+
+impl Display for Threshold {
+  /// Writes the configuration as `<scheme>_<curve>_<t>of<n>`, such as
+  /// `frost_ed25519_2of3`.
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "{}_{}_{}of{}", self.scheme, self.curve, self.t, self.n)
+  }
+}
+
+impl FromStr for Threshold {
+  type Err = Error;
+
+  /// Reads a configuration written as `<scheme>_<curve>_<t>of<n>`,
+  /// where the curve is given by any of the names it is known by.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let err = || Error::UnknownPrimitive(s.to_string());
+    let (scheme, rest) = s.split_once(['_', '-']).ok_or_else(err)?;
+    let scheme = Scheme::ALL
+      .into_iter()
+      .find(|candidate| candidate.to_string().eq_ignore_ascii_case(scheme))
+      .ok_or_else(err)?;
+    let (curve, quorum) = rest.rsplit_once(['_', '-']).ok_or_else(err)?;
+    let (t, n) = quorum
+      .to_ascii_lowercase()
+      .split_once("of")
+      .and_then(|(t, n)| Some((t.parse().ok()?, n.parse().ok()?)))
+      .ok_or_else(err)?;
+    Ok(Self::new(scheme, curve.parse().map_err(|_| err())?, t, n))
+  }
+}
+
+impl Primitive for Threshold {
+  /// Returns the security of the curve, which splitting the key does
+  /// not add to.
+  fn security(&self) -> Security {
+    self.curve.security()
+  }
+
+  /// Returns whether `t` is between 2 and `n` and the scheme is defined
+  /// over the curve. With `t` of 1, any party signs on its own and the
+  /// key is merely copied rather than split.
+  fn is_consistent(&self) -> bool {
+    (2..=self.n).contains(&self.t) && self.scheme.fits(self.curve)
+  }
+
+  fn is_placeholder(&self) -> bool {
+    self.curve.is_placeholder()
+  }
+}
+
+impl Serialize for Threshold {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: serde::Serializer,
+  {
+    serializer.serialize_str(&self.to_string())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::context::Context;
+  use crate::standard::nist::Nist;
+  use crate::standard::Standard;
+
+  #[test]
+  fn names_round_trip() {
+    let key = Threshold::new(Scheme::Frost, ED25519, 2, 3);
+    assert_eq!(key.to_string(), "frost_ed25519_2of3");
+    assert_eq!("frost_ed25519_2of3".parse(), Ok(key));
+    assert_eq!(
+      "ECDSA-secp256k1-3of5".parse(),
+      Ok(Threshold::new(Scheme::Ecdsa, SECP256K1, 3, 5))
+    );
+    assert_eq!(
+      "bls_bls12_381_7of10".parse(),
+      Ok(Threshold::new(Scheme::Bls, BLS12_381, 7, 10))
+    );
+  }
+
+  #[test]
+  fn unknown_names_are_rejected() {
+    assert!("frost_ed25519".parse::<Threshold>().is_err());
+    assert!("shamir_ed25519_2of3".parse::<Threshold>().is_err());
+    assert!("frost_curve_2of3".parse::<Threshold>().is_err());
+  }
+
+  #[test]
+  fn quorum_must_fit_the_parties() {
+    assert!(Threshold::new(Scheme::Ecdsa, P256, 2, 3).is_consistent());
+    assert!(!Threshold::new(Scheme::Ecdsa, P256, 4, 3).is_consistent());
+    assert!(!Threshold::new(Scheme::Ecdsa, P256, 1, 3).is_consistent());
+  }
+
+  #[test]
+  fn schemes_fit_their_curves() {
+    assert!(!Threshold::new(Scheme::Ecdsa, ED25519, 2, 3).is_consistent());
+    assert!(!Threshold::new(Scheme::Frost, BRAINPOOLP256R1, 2, 3).is_consistent());
+    assert!(!Threshold::new(Scheme::Bls, P256, 2, 3).is_consistent());
+    assert!(Threshold::new(Scheme::Multisig, ED25519, 2, 3).is_consistent());
+  }
+
+  #[test]
+  fn curve_decides_compliance() {
+    let ctx = Context::default();
+    let key = Threshold::new(Scheme::Frost, ED25519, 2, 3);
+    assert_eq!(
+      Nist::validate_threshold(ctx, key),
+      Ok(Threshold { curve: P256, ..key })
+    );
+    let key = Threshold::new(Scheme::Bls, BLS12_381, 3, 5);
+    assert_eq!(
+      Nist::validate_threshold(ctx, key),
+      Err(Threshold::new(Scheme::Ecdsa, P256, 3, 5))
+    );
+  }
+
+  #[test]
+  fn single_signers_do_not_comply() {
+    let ctx = Context::default();
+    let key = Threshold::new(Scheme::Ecdsa, P256, 1, 3);
+    assert_eq!(
+      Nist::validate_threshold(ctx, key),
+      Err(Threshold::new(Scheme::Ecdsa, P256, 2, 3))
+    );
+    let key = Threshold::new(Scheme::Multisig, P256, 1, 1);
+    assert_eq!(
+      Nist::validate_threshold(ctx, key),
+      Err(Threshold::new(Scheme::Multisig, P256, 2, 2))
+    );
+  }
+
+  #[test]
+  fn security_is_that_of_the_curve() {
+    assert_eq!(
+      Threshold::new(Scheme::Frost, ED448, 3, 5).security(),
+      ED448.security()
+    );
+  }
+}
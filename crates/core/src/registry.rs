@@ -0,0 +1,1047 @@
+//! Enumerate the primitives built into the library.
+//!
+//! Every primitive instance defined in [`crate::primitive`] is listed
+//! here together with the names it is commonly known by and the object
+//! identifiers used to refer to it in certificates. This makes it
+//! possible to find a primitive from user input rather than having to
+//! know the name of the corresponding static. Lookups go through
+//! perfect hash maps that are built from the tables at compile time.
+//!
+//! The elliptic curves and the codepoints that refer to primitives in
+//! TLS are generated by the build script from the files in the `data`
+//! directory of the crate, which include snapshots of the IANA TLS
+//! registries.
+//!
+//! ```
+//! use wardstone_core::primitive::ecc::P256;
+//! use wardstone_core::registry::{self, Builtin};
+//!
+//! assert!(matches!(registry::find("P-256"), Some(Builtin::Ecc(entry)) if entry.primitive == P256));
+//! ```
+use crate::primitive::ecc::*;
+use crate::primitive::ffc::*;
+use crate::primitive::hash::*;
+use crate::primitive::ifc::*;
+use crate::primitive::symmetric::*;
+
+mod index;
+
+/// A primitive along with the names and object identifiers it is known
+/// by.
+#[derive(Debug)]
+pub struct Entry<T: 'static> {
+  /// The name used to display the primitive.
+  pub name: &'static str,
+  pub aliases: &'static [&'static str],
+  pub oids: &'static [&'static str],
+  pub primitive: T,
+}
+
+impl<T> Entry<T> {
+  /// Returns whether the given name refers to this primitive.
+  ///
+  /// The comparison ignores case as well as hyphens, underscores and
+  /// slashes so that, for example, "SHA-256", "sha_256" and "sha256"
+  /// are considered to be the same name.
+  pub fn is_named(&self, name: &str) -> bool {
+    let name = normalize(name);
+    normalize(self.name) == name || self.aliases.iter().any(|alias| normalize(alias) == name)
+  }
+}
+
+/// Returns the name used to display `primitive` if it has an entry in
+/// `entries`.
+pub(crate) fn name_of<T: PartialEq>(
+  entries: &'static [Entry<T>],
+  primitive: &T,
+) -> Option<&'static str> {
+  entries
+    .iter()
+    .find(|entry| entry.primitive == *primitive)
+    .map(|entry| entry.name)
+}
+
+/// A reference to an entry in one of the tables of the registry.
+#[derive(Clone, Copy, Debug)]
+pub enum Builtin {
+  Ecc(&'static Entry<Ecc>),
+  Ffc(&'static Entry<Ffc>),
+  Hash(&'static Entry<Hash>),
+  Ifc(&'static Entry<Ifc>),
+  Symmetric(&'static Entry<Symmetric>),
+}
+
+impl Builtin {
+  pub fn name(&self) -> &'static str {
+    match self {
+      Self::Ecc(entry) => entry.name,
+      Self::Ffc(entry) => entry.name,
+      Self::Hash(entry) => entry.name,
+      Self::Ifc(entry) => entry.name,
+      Self::Symmetric(entry) => entry.name,
+    }
+  }
+}
+
+/// The type of key used to produce a signature.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Family {
+  Dsa,
+  Ecdsa,
+  EdDsa,
+  RsaPkcs1,
+  RsaPss,
+}
+
+/// A signature algorithm which combines a hash function with a type of
+/// key.
+///
+/// The hash function is absent for algorithms where it is either
+/// implied by the key, as with EdDSA, or specified separately in the
+/// parameters of the algorithm, as with RSASSA-PSS.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Signature {
+  pub hash: Option<Hash>,
+  pub family: Family,
+}
+
+impl Signature {
+  pub const fn new(hash: Option<Hash>, family: Family) -> Self {
+    Self { hash, family }
+  }
+}
+
+/// Looks up a signature algorithm by its object identifier.
+pub fn find_signature(oid: &str) -> Option<&'static Entry<Signature>> {
+  index::SIGNATURES.get(oid).copied()
+}
+
+/// Looks up a primitive by its name or one of its aliases.
+pub fn find(name: &str) -> Option<Builtin> {
+  index::NAMES.get(normalize(name).as_str()).copied()
+}
+
+/// Looks up a primitive by one of its object identifiers.
+///
+/// Object identifiers that are shared by keys of every size, such as
+/// rsaEncryption, do not refer to a single primitive and return `None`.
+pub fn find_oid(oid: &str) -> Option<Builtin> {
+  index::OIDS.get(oid).copied()
+}
+
+/// Looks up the elliptic curve used for key exchange by its codepoint
+/// in the TLS Supported Groups registry.
+///
+/// Groups that are not elliptic curves, such as finite field groups and
+/// hybrid post-quantum groups, return `None`.
+pub fn find_tls_group(codepoint: u16) -> Option<Ecc> {
+  TLS_GROUPS.get(&codepoint).copied()
+}
+
+/// Looks up the symmetric cipher used by a TLS cipher suite by its
+/// codepoint.
+///
+/// Suites whose cipher is not in the registry, such as ChaCha20 or
+/// export grade ciphers, return `None`.
+pub fn find_tls_cipher_suite(codepoint: u16) -> Option<Symmetric> {
+  TLS_CIPHER_SUITES.get(&codepoint).copied()
+}
+
+pub(crate) fn normalize(name: &str) -> String {
+  name
+    .chars()
+    .filter(|c| !matches!(c, '-' | '_' | '/'))
+    .flat_map(char::to_lowercase)
+    .collect()
+}
+
+include!(concat!(env!("OUT_DIR"), "/tables.rs"));
+
+/// The DSA domain parameters and key establishment groups defined in
+/// [`crate::primitive::ffc`].
+pub static FFC: [Entry<Ffc>; 18] = [
+  Entry {
+    name: "dsa_1024_160",
+    aliases: &[],
+    oids: &["1.2.840.10040.4.1"],
+    primitive: DSA_1024_160,
+  },
+  Entry {
+    name: "dsa_2048_224",
+    aliases: &[],
+    oids: &["1.2.840.10040.4.1"],
+    primitive: DSA_2048_224,
+  },
+  Entry {
+    name: "dsa_2048_256",
+    aliases: &[],
+    oids: &["1.2.840.10040.4.1"],
+    primitive: DSA_2048_256,
+  },
+  Entry {
+    name: "dsa_3072_256",
+    aliases: &[],
+    oids: &["1.2.840.10040.4.1"],
+    primitive: DSA_3072_256,
+  },
+  Entry {
+    name: "dsa_7680_384",
+    aliases: &[],
+    oids: &["1.2.840.10040.4.1"],
+    primitive: DSA_7680_384,
+  },
+  Entry {
+    name: "dsa_15360_512",
+    aliases: &[],
+    oids: &["1.2.840.10040.4.1"],
+    primitive: DSA_15360_512,
+  },
+  Entry {
+    name: "ffdhe2048",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.3.1"],
+    primitive: FFDHE2048,
+  },
+  Entry {
+    name: "ffdhe3072",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.3.1"],
+    primitive: FFDHE3072,
+  },
+  Entry {
+    name: "ffdhe4096",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.3.1"],
+    primitive: FFDHE4096,
+  },
+  Entry {
+    name: "ffdhe6144",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.3.1"],
+    primitive: FFDHE6144,
+  },
+  Entry {
+    name: "ffdhe8192",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.3.1"],
+    primitive: FFDHE8192,
+  },
+  Entry {
+    name: "modp_1024",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.3.1"],
+    primitive: MODP_1024,
+  },
+  Entry {
+    name: "modp_1536",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.3.1"],
+    primitive: MODP_1536,
+  },
+  Entry {
+    name: "modp_2048",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.3.1"],
+    primitive: MODP_2048,
+  },
+  Entry {
+    name: "modp_3072",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.3.1"],
+    primitive: MODP_3072,
+  },
+  Entry {
+    name: "modp_4096",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.3.1"],
+    primitive: MODP_4096,
+  },
+  Entry {
+    name: "modp_6144",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.3.1"],
+    primitive: MODP_6144,
+  },
+  Entry {
+    name: "modp_8192",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.3.1"],
+    primitive: MODP_8192,
+  },
+];
+
+/// The hash functions defined in [`crate::primitive::hash`].
+pub static HASH_FUNCTIONS: [Entry<Hash>; 26] = [
+  Entry {
+    name: "blake224",
+    aliases: &[],
+    oids: &[],
+    primitive: BLAKE_224,
+  },
+  Entry {
+    name: "blake256",
+    aliases: &[],
+    oids: &[],
+    primitive: BLAKE_256,
+  },
+  Entry {
+    name: "blake384",
+    aliases: &[],
+    oids: &[],
+    primitive: BLAKE_384,
+  },
+  Entry {
+    name: "blake512",
+    aliases: &[],
+    oids: &[],
+    primitive: BLAKE_512,
+  },
+  Entry {
+    name: "blake2b256",
+    aliases: &[],
+    oids: &["1.3.6.1.4.1.1722.12.2.1.8"],
+    primitive: BLAKE2B_256,
+  },
+  Entry {
+    name: "blake2b384",
+    aliases: &[],
+    oids: &["1.3.6.1.4.1.1722.12.2.1.12"],
+    primitive: BLAKE2B_384,
+  },
+  Entry {
+    name: "blake2b512",
+    aliases: &[],
+    oids: &["1.3.6.1.4.1.1722.12.2.1.16"],
+    primitive: BLAKE2B_512,
+  },
+  Entry {
+    name: "blake2s256",
+    aliases: &[],
+    oids: &["1.3.6.1.4.1.1722.12.2.2.8"],
+    primitive: BLAKE2S_256,
+  },
+  Entry {
+    name: "blake3",
+    aliases: &[],
+    oids: &[],
+    primitive: BLAKE3,
+  },
+  Entry {
+    name: "md4",
+    aliases: &[],
+    oids: &["1.2.840.113549.2.4"],
+    primitive: MD4,
+  },
+  Entry {
+    name: "md5",
+    aliases: &[],
+    oids: &["1.2.840.113549.2.5"],
+    primitive: MD5,
+  },
+  Entry {
+    name: "ripemd160",
+    aliases: &[],
+    oids: &["1.3.36.3.2.1"],
+    primitive: RIPEMD160,
+  },
+  Entry {
+    name: "sha1",
+    aliases: &[],
+    oids: &["1.3.14.3.2.26"],
+    primitive: SHA1,
+  },
+  Entry {
+    name: "sha224",
+    aliases: &[],
+    oids: &["2.16.840.1.101.3.4.2.4"],
+    primitive: SHA224,
+  },
+  Entry {
+    name: "sha256",
+    aliases: &[],
+    oids: &["2.16.840.1.101.3.4.2.1"],
+    primitive: SHA256,
+  },
+  Entry {
+    name: "sha384",
+    aliases: &[],
+    oids: &["2.16.840.1.101.3.4.2.2"],
+    primitive: SHA384,
+  },
+  Entry {
+    name: "sha3_224",
+    aliases: &[],
+    oids: &["2.16.840.1.101.3.4.2.7"],
+    primitive: SHA3_224,
+  },
+  Entry {
+    name: "sha3_256",
+    aliases: &[],
+    oids: &["2.16.840.1.101.3.4.2.8"],
+    primitive: SHA3_256,
+  },
+  Entry {
+    name: "sha3_384",
+    aliases: &[],
+    oids: &["2.16.840.1.101.3.4.2.9"],
+    primitive: SHA3_384,
+  },
+  Entry {
+    name: "sha3_512",
+    aliases: &[],
+    oids: &["2.16.840.1.101.3.4.2.10"],
+    primitive: SHA3_512,
+  },
+  Entry {
+    name: "sha512",
+    aliases: &[],
+    oids: &["2.16.840.1.101.3.4.2.3"],
+    primitive: SHA512,
+  },
+  Entry {
+    name: "sha512/224",
+    aliases: &[],
+    oids: &["2.16.840.1.101.3.4.2.5"],
+    primitive: SHA512_224,
+  },
+  Entry {
+    name: "sha512/256",
+    aliases: &[],
+    oids: &["2.16.840.1.101.3.4.2.6"],
+    primitive: SHA512_256,
+  },
+  Entry {
+    name: "shake128",
+    aliases: &[],
+    oids: &["2.16.840.1.101.3.4.2.11"],
+    primitive: SHAKE128,
+  },
+  Entry {
+    name: "shake256",
+    aliases: &[],
+    oids: &["2.16.840.1.101.3.4.2.12"],
+    primitive: SHAKE256,
+  },
+  Entry {
+    name: "whirlpool",
+    aliases: &[],
+    oids: &["1.0.10118.3.0.55"],
+    primitive: WHIRLPOOL,
+  },
+];
+
+/// The RSA instances defined in [`crate::primitive::ifc`].
+pub static IFC: [Entry<Ifc>; 17] = [
+  Entry {
+    name: "rsa_pkcs1_1024",
+    aliases: &["rsa1024"],
+    oids: &["1.2.840.113549.1.1.1"],
+    primitive: RSA_PKCS1_1024,
+  },
+  Entry {
+    name: "rsa_pkcs1_1536",
+    aliases: &["rsa1536"],
+    oids: &["1.2.840.113549.1.1.1"],
+    primitive: RSA_PKCS1_1536,
+  },
+  Entry {
+    name: "rsa_pkcs1_2048",
+    aliases: &["rsa2048"],
+    oids: &["1.2.840.113549.1.1.1"],
+    primitive: RSA_PKCS1_2048,
+  },
+  Entry {
+    name: "rsa_pkcs1_3072",
+    aliases: &["rsa3072"],
+    oids: &["1.2.840.113549.1.1.1"],
+    primitive: RSA_PKCS1_3072,
+  },
+  Entry {
+    name: "rsa_pkcs1_4096",
+    aliases: &["rsa4096"],
+    oids: &["1.2.840.113549.1.1.1"],
+    primitive: RSA_PKCS1_4096,
+  },
+  Entry {
+    name: "rsa_pkcs1_7680",
+    aliases: &["rsa7680"],
+    oids: &["1.2.840.113549.1.1.1"],
+    primitive: RSA_PKCS1_7680,
+  },
+  Entry {
+    name: "rsa_pkcs1_8192",
+    aliases: &["rsa8192"],
+    oids: &["1.2.840.113549.1.1.1"],
+    primitive: RSA_PKCS1_8192,
+  },
+  Entry {
+    name: "rsa_pkcs1_15360",
+    aliases: &["rsa15360"],
+    oids: &["1.2.840.113549.1.1.1"],
+    primitive: RSA_PKCS1_15360,
+  },
+  Entry {
+    name: "rsa_pss_1024",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.1.10"],
+    primitive: RSA_PSS_1024,
+  },
+  Entry {
+    name: "rsa_pss_1280",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.1.10"],
+    primitive: RSA_PSS_1280,
+  },
+  Entry {
+    name: "rsa_pss_1536",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.1.10"],
+    primitive: RSA_PSS_1536,
+  },
+  Entry {
+    name: "rsa_pss_2048",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.1.10"],
+    primitive: RSA_PSS_2048,
+  },
+  Entry {
+    name: "rsa_pss_3072",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.1.10"],
+    primitive: RSA_PSS_3072,
+  },
+  Entry {
+    name: "rsa_pss_4096",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.1.10"],
+    primitive: RSA_PSS_4096,
+  },
+  Entry {
+    name: "rsa_pss_7680",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.1.10"],
+    primitive: RSA_PSS_7680,
+  },
+  Entry {
+    name: "rsa_pss_8192",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.1.10"],
+    primitive: RSA_PSS_8192,
+  },
+  Entry {
+    name: "rsa_pss_15360",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.1.10"],
+    primitive: RSA_PSS_15360,
+  },
+];
+
+/// The symmetric key primitives defined in
+/// [`crate::primitive::symmetric`].
+pub static SYMMETRIC: [Entry<Symmetric>; 15] = [
+  Entry {
+    name: "aes128",
+    aliases: &[],
+    oids: &[],
+    primitive: AES128,
+  },
+  Entry {
+    name: "aes192",
+    aliases: &[],
+    oids: &[],
+    primitive: AES192,
+  },
+  Entry {
+    name: "aes256",
+    aliases: &[],
+    oids: &[],
+    primitive: AES256,
+  },
+  Entry {
+    name: "blowfish",
+    aliases: &["bf"],
+    oids: &[],
+    primitive: BLOWFISH,
+  },
+  Entry {
+    name: "camellia128",
+    aliases: &[],
+    oids: &[],
+    primitive: CAMELLIA128,
+  },
+  Entry {
+    name: "camellia192",
+    aliases: &[],
+    oids: &[],
+    primitive: CAMELLIA192,
+  },
+  Entry {
+    name: "camellia256",
+    aliases: &[],
+    oids: &[],
+    primitive: CAMELLIA256,
+  },
+  Entry {
+    name: "des",
+    aliases: &[],
+    oids: &[],
+    primitive: DES,
+  },
+  Entry {
+    name: "desx",
+    aliases: &[],
+    oids: &[],
+    primitive: DESX,
+  },
+  Entry {
+    name: "idea",
+    aliases: &[],
+    oids: &[],
+    primitive: IDEA,
+  },
+  Entry {
+    name: "serpent128",
+    aliases: &[],
+    oids: &[],
+    primitive: SERPENT128,
+  },
+  Entry {
+    name: "serpent192",
+    aliases: &[],
+    oids: &[],
+    primitive: SERPENT192,
+  },
+  Entry {
+    name: "serpent256",
+    aliases: &[],
+    oids: &[],
+    primitive: SERPENT256,
+  },
+  Entry {
+    name: "tdea2",
+    aliases: &["2tdea"],
+    oids: &[],
+    primitive: TDEA2,
+  },
+  Entry {
+    name: "tdea3",
+    aliases: &["3tdea", "3des"],
+    oids: &[],
+    primitive: TDEA3,
+  },
+];
+
+/// The signature algorithms used to sign certificates.
+///
+/// These are not returned by [`find`] since they are combinations of
+/// the primitives above rather than primitives in their own right.
+pub static SIGNATURE_ALGORITHMS: [Entry<Signature>; 34] = [
+  Entry {
+    name: "ecdsa-with-SHA1",
+    aliases: &[],
+    oids: &["1.2.840.10045.4.1"],
+    primitive: Signature::new(Some(SHA1), Family::Ecdsa),
+  },
+  Entry {
+    name: "ecdsa-with-SHA224",
+    aliases: &[],
+    oids: &["1.2.840.10045.4.3.1"],
+    primitive: Signature::new(Some(SHA224), Family::Ecdsa),
+  },
+  Entry {
+    name: "ecdsa-with-SHA256",
+    aliases: &[],
+    oids: &["1.2.840.10045.4.3.2"],
+    primitive: Signature::new(Some(SHA256), Family::Ecdsa),
+  },
+  Entry {
+    name: "ecdsa-with-SHA384",
+    aliases: &[],
+    oids: &["1.2.840.10045.4.3.3"],
+    primitive: Signature::new(Some(SHA384), Family::Ecdsa),
+  },
+  Entry {
+    name: "ecdsa-with-SHA512",
+    aliases: &[],
+    oids: &["1.2.840.10045.4.3.4"],
+    primitive: Signature::new(Some(SHA512), Family::Ecdsa),
+  },
+  Entry {
+    name: "dsa-with-sha1",
+    aliases: &[],
+    oids: &["1.2.840.10040.4.3"],
+    primitive: Signature::new(Some(SHA1), Family::Dsa),
+  },
+  Entry {
+    name: "dsa-with-sha224",
+    aliases: &[],
+    oids: &["2.16.840.1.101.3.4.3.1"],
+    primitive: Signature::new(Some(SHA224), Family::Dsa),
+  },
+  Entry {
+    name: "dsa-with-sha256",
+    aliases: &[],
+    oids: &["2.16.840.1.101.3.4.3.2"],
+    primitive: Signature::new(Some(SHA256), Family::Dsa),
+  },
+  Entry {
+    name: "dsa-with-sha384",
+    aliases: &[],
+    oids: &["2.16.840.1.101.3.4.3.3"],
+    primitive: Signature::new(Some(SHA384), Family::Dsa),
+  },
+  Entry {
+    name: "dsa-with-sha512",
+    aliases: &[],
+    oids: &["2.16.840.1.101.3.4.3.4"],
+    primitive: Signature::new(Some(SHA512), Family::Dsa),
+  },
+  Entry {
+    name: "id-dsa-with-sha3-224",
+    aliases: &[],
+    oids: &["2.16.840.1.101.3.4.3.5"],
+    primitive: Signature::new(Some(SHA3_224), Family::Dsa),
+  },
+  Entry {
+    name: "id-dsa-with-sha3-256",
+    aliases: &[],
+    oids: &["2.16.840.1.101.3.4.3.6"],
+    primitive: Signature::new(Some(SHA3_256), Family::Dsa),
+  },
+  Entry {
+    name: "id-dsa-with-sha3-384",
+    aliases: &[],
+    oids: &["2.16.840.1.101.3.4.3.7"],
+    primitive: Signature::new(Some(SHA3_384), Family::Dsa),
+  },
+  Entry {
+    name: "id-dsa-with-sha3-512",
+    aliases: &[],
+    oids: &["2.16.840.1.101.3.4.3.8"],
+    primitive: Signature::new(Some(SHA3_512), Family::Dsa),
+  },
+  Entry {
+    name: "id-ecdsa-with-sha3-224",
+    aliases: &[],
+    oids: &["2.16.840.1.101.3.4.3.9"],
+    primitive: Signature::new(Some(SHA3_224), Family::Ecdsa),
+  },
+  Entry {
+    name: "id-ecdsa-with-sha3-256",
+    aliases: &[],
+    oids: &["2.16.840.1.101.3.4.3.10"],
+    primitive: Signature::new(Some(SHA3_256), Family::Ecdsa),
+  },
+  Entry {
+    name: "id-ecdsa-with-sha3-384",
+    aliases: &[],
+    oids: &["2.16.840.1.101.3.4.3.11"],
+    primitive: Signature::new(Some(SHA3_384), Family::Ecdsa),
+  },
+  Entry {
+    name: "id-ecdsa-with-sha3-512",
+    aliases: &[],
+    oids: &["2.16.840.1.101.3.4.3.12"],
+    primitive: Signature::new(Some(SHA3_512), Family::Ecdsa),
+  },
+  Entry {
+    name: "id-rsassa-pkcs1-v1_5-with-sha3-224",
+    aliases: &[],
+    oids: &["2.16.840.1.101.3.4.3.13"],
+    primitive: Signature::new(Some(SHA3_224), Family::RsaPkcs1),
+  },
+  Entry {
+    name: "id-rsassa-pkcs1-v1_5-with-sha3-256",
+    aliases: &[],
+    oids: &["2.16.840.1.101.3.4.3.14"],
+    primitive: Signature::new(Some(SHA3_256), Family::RsaPkcs1),
+  },
+  Entry {
+    name: "id-rsassa-pkcs1-v1_5-with-sha3-384",
+    aliases: &[],
+    oids: &["2.16.840.1.101.3.4.3.15"],
+    primitive: Signature::new(Some(SHA3_384), Family::RsaPkcs1),
+  },
+  Entry {
+    name: "id-rsassa-pkcs1-v1_5-with-sha3-512",
+    aliases: &[],
+    oids: &["2.16.840.1.101.3.4.3.16"],
+    primitive: Signature::new(Some(SHA3_512), Family::RsaPkcs1),
+  },
+  Entry {
+    name: "md4WithRSAEncryption",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.1.3"],
+    primitive: Signature::new(Some(MD4), Family::RsaPkcs1),
+  },
+  Entry {
+    name: "md5WithRSAEncryption",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.1.4"],
+    primitive: Signature::new(Some(MD5), Family::RsaPkcs1),
+  },
+  Entry {
+    name: "sha1WithRSAEncryption",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.1.5"],
+    primitive: Signature::new(Some(SHA1), Family::RsaPkcs1),
+  },
+  Entry {
+    name: "sha256WithRSAEncryption",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.1.11"],
+    primitive: Signature::new(Some(SHA256), Family::RsaPkcs1),
+  },
+  Entry {
+    name: "sha384WithRSAEncryption",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.1.12"],
+    primitive: Signature::new(Some(SHA384), Family::RsaPkcs1),
+  },
+  Entry {
+    name: "sha512WithRSAEncryption",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.1.13"],
+    primitive: Signature::new(Some(SHA512), Family::RsaPkcs1),
+  },
+  Entry {
+    name: "sha224WithRSAEncryption",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.1.14"],
+    primitive: Signature::new(Some(SHA224), Family::RsaPkcs1),
+  },
+  Entry {
+    name: "sha512-224WithRSAEncryption",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.1.15"],
+    primitive: Signature::new(Some(SHA512_224), Family::RsaPkcs1),
+  },
+  Entry {
+    name: "sha512-256WithRSAEncryption",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.1.16"],
+    primitive: Signature::new(Some(SHA512_256), Family::RsaPkcs1),
+  },
+  Entry {
+    name: "RSASSA-PSS",
+    aliases: &[],
+    oids: &["1.2.840.113549.1.1.10"],
+    primitive: Signature::new(None, Family::RsaPss),
+  },
+  Entry {
+    name: "Ed25519",
+    aliases: &[],
+    oids: &["1.3.101.112"],
+    primitive: Signature::new(None, Family::EdDsa),
+  },
+  Entry {
+    name: "Ed448",
+    aliases: &[],
+    oids: &["1.3.101.113"],
+    primitive: Signature::new(None, Family::EdDsa),
+  },
+];
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashSet;
+
+  use super::*;
+
+  fn names() -> Vec<&'static str> {
+    let mut v = Vec::new();
+    v.extend(
+      CURVES
+        .iter()
+        .flat_map(|e| e.aliases.iter().copied().chain([e.name])),
+    );
+    v.extend(
+      FFC
+        .iter()
+        .flat_map(|e| e.aliases.iter().copied().chain([e.name])),
+    );
+    v.extend(
+      HASH_FUNCTIONS
+        .iter()
+        .flat_map(|e| e.aliases.iter().copied().chain([e.name])),
+    );
+    v.extend(
+      IFC
+        .iter()
+        .flat_map(|e| e.aliases.iter().copied().chain([e.name])),
+    );
+    v.extend(
+      SYMMETRIC
+        .iter()
+        .flat_map(|e| e.aliases.iter().copied().chain([e.name])),
+    );
+    v
+  }
+
+  #[test]
+  fn names_are_unique() {
+    let mut seen = HashSet::new();
+    for name in names() {
+      assert!(seen.insert(normalize(name)), "{} is ambiguous", name);
+    }
+  }
+
+  #[test]
+  fn find_alias() {
+    assert!(matches!(find("secp256r1"), Some(Builtin::Ecc(e)) if e.primitive == P256));
+    assert!(matches!(find("SHA-256"), Some(Builtin::Hash(e)) if e.primitive == SHA256));
+    assert!(matches!(find("SHA512/256"), Some(Builtin::Hash(e)) if e.primitive == SHA512_256));
+    assert!(matches!(find("RSA-3072"), Some(Builtin::Ifc(e)) if e.primitive == RSA_PKCS1_3072));
+    assert!(matches!(find("3des"), Some(Builtin::Symmetric(e)) if e.primitive == TDEA3));
+  }
+
+  #[test]
+  fn find_signature_oid() {
+    let signature = find_signature("1.2.840.113549.1.1.11").map(|e| e.primitive);
+    assert_eq!(
+      signature,
+      Some(Signature::new(Some(SHA256), Family::RsaPkcs1))
+    );
+    assert!(find_signature("1.2.840.113549.1.1.1").is_none());
+  }
+
+  #[test]
+  fn find_primitive_oid() {
+    assert!(
+      matches!(find_oid("1.2.840.10045.3.1.7"), Some(Builtin::Ecc(e)) if e.primitive == P256)
+    );
+    assert!(
+      matches!(find_oid("2.16.840.1.101.3.4.2.1"), Some(Builtin::Hash(e)) if e.primitive == SHA256)
+    );
+    assert!(find_oid("1.2.840.113549.1.1.1").is_none());
+  }
+
+  /// Renders the index of the tables which is kept in `index.rs`.
+  fn render_index() -> String {
+    let mut names = String::new();
+    let mut oids: Vec<(&str, String)> = Vec::new();
+    let mut add = |table: &str,
+                   variant: &str,
+                   i: usize,
+                   name: &'static str,
+                   aliases: &'static [&'static str],
+                   ids: &'static [&'static str]| {
+      let value = format!("Builtin::{}(&{}[{}])", variant, table, i);
+      for name in [name].iter().chain(aliases) {
+        names.push_str(&format!("  \"{}\" => {},\n", normalize(name), value));
+      }
+      for &oid in ids {
+        oids.push((oid, value.clone()));
+      }
+    };
+    for (i, e) in CURVES.iter().enumerate() {
+      add("CURVES", "Ecc", i, e.name, e.aliases, e.oids);
+    }
+    for (i, e) in FFC.iter().enumerate() {
+      add("FFC", "Ffc", i, e.name, e.aliases, e.oids);
+    }
+    for (i, e) in HASH_FUNCTIONS.iter().enumerate() {
+      add("HASH_FUNCTIONS", "Hash", i, e.name, e.aliases, e.oids);
+    }
+    for (i, e) in IFC.iter().enumerate() {
+      add("IFC", "Ifc", i, e.name, e.aliases, e.oids);
+    }
+    for (i, e) in SYMMETRIC.iter().enumerate() {
+      add("SYMMETRIC", "Symmetric", i, e.name, e.aliases, e.oids);
+    }
+    // Object identifiers shared by several primitives are left out.
+    let mut unique = String::new();
+    for (oid, value) in oids.iter() {
+      if oids.iter().filter(|(other, _)| other == oid).count() == 1 {
+        unique.push_str(&format!("  \"{}\" => {},\n", oid, value));
+      }
+    }
+    // The first signature algorithm with an object identifier wins.
+    let mut seen = HashSet::new();
+    let mut signatures = String::new();
+    for (i, e) in SIGNATURE_ALGORITHMS.iter().enumerate() {
+      for &oid in e.oids.iter().filter(|&&oid| seen.insert(oid)) {
+        signatures.push_str(&format!("  \"{}\" => &SIGNATURE_ALGORITHMS[{}],\n", oid, i));
+      }
+    }
+    format!(
+      "{}\n/// The primitives by their normalised names and aliases.\npub(super) static NAMES: phf::Map<&'static str, Builtin> = phf::phf_map! {{\n{}}};\n\n/// The primitives by the object identifiers that refer to them alone.\npub(super) static OIDS: phf::Map<&'static str, Builtin> = phf::phf_map! {{\n{}}};\n\n/// The signature algorithms by their object identifiers.\npub(super) static SIGNATURES: phf::Map<&'static str, &'static Entry<Signature>> = phf::phf_map! {{\n{}}};\n",
+      INDEX_HEADER, names, unique, signatures
+    )
+  }
+
+  const INDEX_HEADER: &str = "//! Perfect hash maps from the names and object identifiers of\n//! primitives to their entries in the tables of the registry.\n//!\n//! This file is generated from the tables by the `index_is_current`\n//! test. Run it with `WARDSTONE_UPDATE_INDEX=1` set after changing a\n//! table to update the maps.\nuse super::*;\n";
+
+  #[test]
+  fn index_is_current() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/src/registry/index.rs");
+    let index = render_index();
+    if std::env::var_os("WARDSTONE_UPDATE_INDEX").is_some() {
+      std::fs::write(path, &index).unwrap();
+    }
+    let current = std::fs::read_to_string(path).unwrap_or_default();
+    assert!(
+      current == index,
+      "the index is out of date, run this test with WARDSTONE_UPDATE_INDEX=1"
+    );
+  }
+
+  #[test]
+  fn find_tls_codepoints() {
+    assert_eq!(find_tls_group(23), Some(P256));
+    assert_eq!(find_tls_group(29), Some(X25519));
+    assert_eq!(find_tls_group(31), Some(BRAINPOOLP256R1));
+    assert_eq!(find_tls_group(256), None);
+    assert_eq!(find_tls_cipher_suite(0x1301), Some(AES128));
+    assert_eq!(find_tls_cipher_suite(0xc030), Some(AES256));
+    assert_eq!(find_tls_cipher_suite(0x000a), Some(TDEA3));
+    assert_eq!(find_tls_cipher_suite(0x0008), None);
+    assert_eq!(find_tls_cipher_suite(0xcca8), None);
+  }
+
+  #[test]
+  fn find_unknown() {
+    assert!(find("rot13").is_none());
+  }
+
+  fn round_trips<T>(entries: &[Entry<T>]) -> bool
+  where
+    T: Copy + std::fmt::Display + std::str::FromStr + PartialEq,
+  {
+    entries.iter().all(|entry| {
+      entry.primitive.to_string() == entry.name
+        && entry
+          .aliases
+          .iter()
+          .chain([&entry.name])
+          .all(|name| name.parse::<T>().ok() == Some(entry.primitive))
+    })
+  }
+
+  #[test]
+  fn names_round_trip() {
+    assert!(round_trips(&CURVES));
+    assert!(round_trips(&FFC));
+    assert!(round_trips(&HASH_FUNCTIONS));
+    assert!(round_trips(&IFC));
+    assert!(round_trips(&SYMMETRIC));
+  }
+
+  #[test]
+  fn parse_custom_sizes() {
+    let rsa = Ifc::new(ID_RSA_PSS, 6144);
+    assert_eq!(rsa.to_string().parse(), Ok(rsa));
+    assert_eq!("RSA-PKCS1-4000".parse(), Ok(Ifc::new(ID_RSA_PKCS1, 4000)));
+    let dsa = Ffc::new(ID_DSA, 4096, 256);
+    assert_eq!(dsa.to_string().parse(), Ok(dsa));
+    assert!("dsa_4096".parse::<Ffc>().is_err());
+    let dh = Ffc::new(ID_DH, 3000, 0);
+    assert_eq!(dh.to_string(), "dh_3000");
+    assert_eq!(dh.to_string().parse(), Ok(dh));
+    assert_eq!("dh_2048_224".parse(), Ok(Ffc::new(ID_DH, 2048, 224)));
+    let ecc = Ecc::new(ID_ECC, 255);
+    assert_eq!(ecc.to_string(), "ecc_255");
+    assert_eq!(ecc.to_string().parse(), Ok(ecc));
+    assert!("sha256".parse::<Ecc>().is_err());
+  }
+}
@@ -0,0 +1,366 @@
+//! Perfect hash maps from the names and object identifiers of
+//! primitives to their entries in the tables of the registry.
+//!
+//! This file is generated from the tables by the `index_is_current`
+//! test. Run it with `WARDSTONE_UPDATE_INDEX=1` set after changing a
+//! table to update the maps.
+use super::*;
+
+/// The primitives by their normalised names and aliases.
+pub(super) static NAMES: phf::Map<&'static str, Builtin> = phf::phf_map! {
+  "nistb163" => Builtin::Ecc(&CURVES[0]),
+  "sect163r2" => Builtin::Ecc(&CURVES[0]),
+  "b163" => Builtin::Ecc(&CURVES[0]),
+  "nistb233" => Builtin::Ecc(&CURVES[1]),
+  "sect233r1" => Builtin::Ecc(&CURVES[1]),
+  "wapwsgidmecidwtls11" => Builtin::Ecc(&CURVES[1]),
+  "b233" => Builtin::Ecc(&CURVES[1]),
+  "nistb283" => Builtin::Ecc(&CURVES[2]),
+  "sect283r1" => Builtin::Ecc(&CURVES[2]),
+  "b283" => Builtin::Ecc(&CURVES[2]),
+  "nistb409" => Builtin::Ecc(&CURVES[3]),
+  "sect409r1" => Builtin::Ecc(&CURVES[3]),
+  "b409" => Builtin::Ecc(&CURVES[3]),
+  "nistb571" => Builtin::Ecc(&CURVES[4]),
+  "sect571r1" => Builtin::Ecc(&CURVES[4]),
+  "b571" => Builtin::Ecc(&CURVES[4]),
+  "bls12381" => Builtin::Ecc(&CURVES[5]),
+  "brainpoolp160r1" => Builtin::Ecc(&CURVES[6]),
+  "brainpoolp160t1" => Builtin::Ecc(&CURVES[7]),
+  "brainpoolp192r1" => Builtin::Ecc(&CURVES[8]),
+  "brainpoolp192t1" => Builtin::Ecc(&CURVES[9]),
+  "brainpoolp224r1" => Builtin::Ecc(&CURVES[10]),
+  "brainpoolp224t1" => Builtin::Ecc(&CURVES[11]),
+  "brainpoolp256r1" => Builtin::Ecc(&CURVES[12]),
+  "brainpoolp256t1" => Builtin::Ecc(&CURVES[13]),
+  "brainpoolp320r1" => Builtin::Ecc(&CURVES[14]),
+  "brainpoolp320t1" => Builtin::Ecc(&CURVES[15]),
+  "brainpoolp384r1" => Builtin::Ecc(&CURVES[16]),
+  "brainpoolp384t1" => Builtin::Ecc(&CURVES[17]),
+  "brainpoolp512r1" => Builtin::Ecc(&CURVES[18]),
+  "brainpoolp512t1" => Builtin::Ecc(&CURVES[19]),
+  "c2pnb163v1" => Builtin::Ecc(&CURVES[20]),
+  "wapwsgidmecidwtls5" => Builtin::Ecc(&CURVES[20]),
+  "c2pnb163v2" => Builtin::Ecc(&CURVES[21]),
+  "c2pnb163v3" => Builtin::Ecc(&CURVES[22]),
+  "c2pnb176v1" => Builtin::Ecc(&CURVES[23]),
+  "c2pnb208w1" => Builtin::Ecc(&CURVES[24]),
+  "c2pnb272w1" => Builtin::Ecc(&CURVES[25]),
+  "c2pnb304w1" => Builtin::Ecc(&CURVES[26]),
+  "c2pnb368w1" => Builtin::Ecc(&CURVES[27]),
+  "c2tnb191v1" => Builtin::Ecc(&CURVES[28]),
+  "c2tnb191v2" => Builtin::Ecc(&CURVES[29]),
+  "c2tnb191v3" => Builtin::Ecc(&CURVES[30]),
+  "c2tnb239v1" => Builtin::Ecc(&CURVES[31]),
+  "c2tnb239v2" => Builtin::Ecc(&CURVES[32]),
+  "c2tnb239v3" => Builtin::Ecc(&CURVES[33]),
+  "c2tnb359v1" => Builtin::Ecc(&CURVES[34]),
+  "c2tnb431r1" => Builtin::Ecc(&CURVES[35]),
+  "ed25519" => Builtin::Ecc(&CURVES[36]),
+  "ed25519ph" => Builtin::Ecc(&CURVES[37]),
+  "ed448" => Builtin::Ecc(&CURVES[38]),
+  "ed448ph" => Builtin::Ecc(&CURVES[39]),
+  "nistk163" => Builtin::Ecc(&CURVES[40]),
+  "sect163k1" => Builtin::Ecc(&CURVES[40]),
+  "wapwsgidmecidwtls3" => Builtin::Ecc(&CURVES[40]),
+  "k163" => Builtin::Ecc(&CURVES[40]),
+  "nistk233" => Builtin::Ecc(&CURVES[41]),
+  "sect233k1" => Builtin::Ecc(&CURVES[41]),
+  "wapwsgidmecidwtls10" => Builtin::Ecc(&CURVES[41]),
+  "k233" => Builtin::Ecc(&CURVES[41]),
+  "nistk283" => Builtin::Ecc(&CURVES[42]),
+  "sect283k1" => Builtin::Ecc(&CURVES[42]),
+  "k283" => Builtin::Ecc(&CURVES[42]),
+  "nistk409" => Builtin::Ecc(&CURVES[43]),
+  "sect409k1" => Builtin::Ecc(&CURVES[43]),
+  "k409" => Builtin::Ecc(&CURVES[43]),
+  "nistk571" => Builtin::Ecc(&CURVES[44]),
+  "k571" => Builtin::Ecc(&CURVES[44]),
+  "sect571k1" => Builtin::Ecc(&CURVES[44]),
+  "nistp192" => Builtin::Ecc(&CURVES[45]),
+  "prime192v1" => Builtin::Ecc(&CURVES[45]),
+  "secp192r1" => Builtin::Ecc(&CURVES[45]),
+  "p192" => Builtin::Ecc(&CURVES[45]),
+  "nistp224" => Builtin::Ecc(&CURVES[46]),
+  "secp224r1" => Builtin::Ecc(&CURVES[46]),
+  "wapwsgidmecidwtls12" => Builtin::Ecc(&CURVES[46]),
+  "p224" => Builtin::Ecc(&CURVES[46]),
+  "nistp256" => Builtin::Ecc(&CURVES[47]),
+  "prime256v1" => Builtin::Ecc(&CURVES[47]),
+  "secp256r1" => Builtin::Ecc(&CURVES[47]),
+  "p256" => Builtin::Ecc(&CURVES[47]),
+  "nistp384" => Builtin::Ecc(&CURVES[48]),
+  "secp384r1" => Builtin::Ecc(&CURVES[48]),
+  "p384" => Builtin::Ecc(&CURVES[48]),
+  "nistp521" => Builtin::Ecc(&CURVES[49]),
+  "secp521r1" => Builtin::Ecc(&CURVES[49]),
+  "p521" => Builtin::Ecc(&CURVES[49]),
+  "prime192v2" => Builtin::Ecc(&CURVES[50]),
+  "prime192v3" => Builtin::Ecc(&CURVES[51]),
+  "prime239v1" => Builtin::Ecc(&CURVES[52]),
+  "prime239v2" => Builtin::Ecc(&CURVES[53]),
+  "prime239v3" => Builtin::Ecc(&CURVES[54]),
+  "secp112r1" => Builtin::Ecc(&CURVES[55]),
+  "wapwsgidmecidwtls6" => Builtin::Ecc(&CURVES[55]),
+  "secp112r2" => Builtin::Ecc(&CURVES[56]),
+  "secp128r1" => Builtin::Ecc(&CURVES[57]),
+  "secp128r2" => Builtin::Ecc(&CURVES[58]),
+  "secp160r1" => Builtin::Ecc(&CURVES[59]),
+  "wapwsgidmecidwtls7" => Builtin::Ecc(&CURVES[59]),
+  "secp160k1" => Builtin::Ecc(&CURVES[60]),
+  "secp160r2" => Builtin::Ecc(&CURVES[61]),
+  "secp192k1" => Builtin::Ecc(&CURVES[62]),
+  "secp224k1" => Builtin::Ecc(&CURVES[63]),
+  "secp256k1" => Builtin::Ecc(&CURVES[64]),
+  "sect113r1" => Builtin::Ecc(&CURVES[65]),
+  "wapwsgidmecidwtls4" => Builtin::Ecc(&CURVES[65]),
+  "sect113r2" => Builtin::Ecc(&CURVES[66]),
+  "sect131r1" => Builtin::Ecc(&CURVES[67]),
+  "sect131r2" => Builtin::Ecc(&CURVES[68]),
+  "sect163r1" => Builtin::Ecc(&CURVES[69]),
+  "sect193r1" => Builtin::Ecc(&CURVES[70]),
+  "sect193r2" => Builtin::Ecc(&CURVES[71]),
+  "sect239k1" => Builtin::Ecc(&CURVES[72]),
+  "sm2" => Builtin::Ecc(&CURVES[73]),
+  "curvesm2" => Builtin::Ecc(&CURVES[73]),
+  "wapwsgidmecidwtls1" => Builtin::Ecc(&CURVES[74]),
+  "wapwsgidmecidwtls8" => Builtin::Ecc(&CURVES[75]),
+  "wapwsgidmecidwtls9" => Builtin::Ecc(&CURVES[76]),
+  "x25519" => Builtin::Ecc(&CURVES[77]),
+  "x448" => Builtin::Ecc(&CURVES[78]),
+  "dsa1024160" => Builtin::Ffc(&FFC[0]),
+  "dsa2048224" => Builtin::Ffc(&FFC[1]),
+  "dsa2048256" => Builtin::Ffc(&FFC[2]),
+  "dsa3072256" => Builtin::Ffc(&FFC[3]),
+  "dsa7680384" => Builtin::Ffc(&FFC[4]),
+  "dsa15360512" => Builtin::Ffc(&FFC[5]),
+  "ffdhe2048" => Builtin::Ffc(&FFC[6]),
+  "ffdhe3072" => Builtin::Ffc(&FFC[7]),
+  "ffdhe4096" => Builtin::Ffc(&FFC[8]),
+  "ffdhe6144" => Builtin::Ffc(&FFC[9]),
+  "ffdhe8192" => Builtin::Ffc(&FFC[10]),
+  "modp1024" => Builtin::Ffc(&FFC[11]),
+  "modp1536" => Builtin::Ffc(&FFC[12]),
+  "modp2048" => Builtin::Ffc(&FFC[13]),
+  "modp3072" => Builtin::Ffc(&FFC[14]),
+  "modp4096" => Builtin::Ffc(&FFC[15]),
+  "modp6144" => Builtin::Ffc(&FFC[16]),
+  "modp8192" => Builtin::Ffc(&FFC[17]),
+  "blake224" => Builtin::Hash(&HASH_FUNCTIONS[0]),
+  "blake256" => Builtin::Hash(&HASH_FUNCTIONS[1]),
+  "blake384" => Builtin::Hash(&HASH_FUNCTIONS[2]),
+  "blake512" => Builtin::Hash(&HASH_FUNCTIONS[3]),
+  "blake2b256" => Builtin::Hash(&HASH_FUNCTIONS[4]),
+  "blake2b384" => Builtin::Hash(&HASH_FUNCTIONS[5]),
+  "blake2b512" => Builtin::Hash(&HASH_FUNCTIONS[6]),
+  "blake2s256" => Builtin::Hash(&HASH_FUNCTIONS[7]),
+  "blake3" => Builtin::Hash(&HASH_FUNCTIONS[8]),
+  "md4" => Builtin::Hash(&HASH_FUNCTIONS[9]),
+  "md5" => Builtin::Hash(&HASH_FUNCTIONS[10]),
+  "ripemd160" => Builtin::Hash(&HASH_FUNCTIONS[11]),
+  "sha1" => Builtin::Hash(&HASH_FUNCTIONS[12]),
+  "sha224" => Builtin::Hash(&HASH_FUNCTIONS[13]),
+  "sha256" => Builtin::Hash(&HASH_FUNCTIONS[14]),
+  "sha384" => Builtin::Hash(&HASH_FUNCTIONS[15]),
+  "sha3224" => Builtin::Hash(&HASH_FUNCTIONS[16]),
+  "sha3256" => Builtin::Hash(&HASH_FUNCTIONS[17]),
+  "sha3384" => Builtin::Hash(&HASH_FUNCTIONS[18]),
+  "sha3512" => Builtin::Hash(&HASH_FUNCTIONS[19]),
+  "sha512" => Builtin::Hash(&HASH_FUNCTIONS[20]),
+  "sha512224" => Builtin::Hash(&HASH_FUNCTIONS[21]),
+  "sha512256" => Builtin::Hash(&HASH_FUNCTIONS[22]),
+  "shake128" => Builtin::Hash(&HASH_FUNCTIONS[23]),
+  "shake256" => Builtin::Hash(&HASH_FUNCTIONS[24]),
+  "whirlpool" => Builtin::Hash(&HASH_FUNCTIONS[25]),
+  "rsapkcs11024" => Builtin::Ifc(&IFC[0]),
+  "rsa1024" => Builtin::Ifc(&IFC[0]),
+  "rsapkcs11536" => Builtin::Ifc(&IFC[1]),
+  "rsa1536" => Builtin::Ifc(&IFC[1]),
+  "rsapkcs12048" => Builtin::Ifc(&IFC[2]),
+  "rsa2048" => Builtin::Ifc(&IFC[2]),
+  "rsapkcs13072" => Builtin::Ifc(&IFC[3]),
+  "rsa3072" => Builtin::Ifc(&IFC[3]),
+  "rsapkcs14096" => Builtin::Ifc(&IFC[4]),
+  "rsa4096" => Builtin::Ifc(&IFC[4]),
+  "rsapkcs17680" => Builtin::Ifc(&IFC[5]),
+  "rsa7680" => Builtin::Ifc(&IFC[5]),
+  "rsapkcs18192" => Builtin::Ifc(&IFC[6]),
+  "rsa8192" => Builtin::Ifc(&IFC[6]),
+  "rsapkcs115360" => Builtin::Ifc(&IFC[7]),
+  "rsa15360" => Builtin::Ifc(&IFC[7]),
+  "rsapss1024" => Builtin::Ifc(&IFC[8]),
+  "rsapss1280" => Builtin::Ifc(&IFC[9]),
+  "rsapss1536" => Builtin::Ifc(&IFC[10]),
+  "rsapss2048" => Builtin::Ifc(&IFC[11]),
+  "rsapss3072" => Builtin::Ifc(&IFC[12]),
+  "rsapss4096" => Builtin::Ifc(&IFC[13]),
+  "rsapss7680" => Builtin::Ifc(&IFC[14]),
+  "rsapss8192" => Builtin::Ifc(&IFC[15]),
+  "rsapss15360" => Builtin::Ifc(&IFC[16]),
+  "aes128" => Builtin::Symmetric(&SYMMETRIC[0]),
+  "aes192" => Builtin::Symmetric(&SYMMETRIC[1]),
+  "aes256" => Builtin::Symmetric(&SYMMETRIC[2]),
+  "blowfish" => Builtin::Symmetric(&SYMMETRIC[3]),
+  "bf" => Builtin::Symmetric(&SYMMETRIC[3]),
+  "camellia128" => Builtin::Symmetric(&SYMMETRIC[4]),
+  "camellia192" => Builtin::Symmetric(&SYMMETRIC[5]),
+  "camellia256" => Builtin::Symmetric(&SYMMETRIC[6]),
+  "des" => Builtin::Symmetric(&SYMMETRIC[7]),
+  "desx" => Builtin::Symmetric(&SYMMETRIC[8]),
+  "idea" => Builtin::Symmetric(&SYMMETRIC[9]),
+  "serpent128" => Builtin::Symmetric(&SYMMETRIC[10]),
+  "serpent192" => Builtin::Symmetric(&SYMMETRIC[11]),
+  "serpent256" => Builtin::Symmetric(&SYMMETRIC[12]),
+  "tdea2" => Builtin::Symmetric(&SYMMETRIC[13]),
+  "2tdea" => Builtin::Symmetric(&SYMMETRIC[13]),
+  "tdea3" => Builtin::Symmetric(&SYMMETRIC[14]),
+  "3tdea" => Builtin::Symmetric(&SYMMETRIC[14]),
+  "3des" => Builtin::Symmetric(&SYMMETRIC[14]),
+};
+
+/// The primitives by the object identifiers that refer to them alone.
+pub(super) static OIDS: phf::Map<&'static str, Builtin> = phf::phf_map! {
+  "1.3.132.0.15" => Builtin::Ecc(&CURVES[0]),
+  "1.3.132.0.27" => Builtin::Ecc(&CURVES[1]),
+  "2.23.43.1.4.11" => Builtin::Ecc(&CURVES[1]),
+  "1.3.132.0.17" => Builtin::Ecc(&CURVES[2]),
+  "1.3.132.0.37" => Builtin::Ecc(&CURVES[3]),
+  "1.3.132.0.39" => Builtin::Ecc(&CURVES[4]),
+  "1.3.36.3.3.2.8.1.1.1" => Builtin::Ecc(&CURVES[6]),
+  "1.3.36.3.3.2.8.1.1.2" => Builtin::Ecc(&CURVES[7]),
+  "1.3.36.3.3.2.8.1.1.3" => Builtin::Ecc(&CURVES[8]),
+  "1.3.36.3.3.2.8.1.1.4" => Builtin::Ecc(&CURVES[9]),
+  "1.3.36.3.3.2.8.1.1.5" => Builtin::Ecc(&CURVES[10]),
+  "1.3.36.3.3.2.8.1.1.6" => Builtin::Ecc(&CURVES[11]),
+  "1.3.36.3.3.2.8.1.1.7" => Builtin::Ecc(&CURVES[12]),
+  "1.3.36.3.3.2.8.1.1.8" => Builtin::Ecc(&CURVES[13]),
+  "1.3.36.3.3.2.8.1.1.9" => Builtin::Ecc(&CURVES[14]),
+  "1.3.36.3.3.2.8.1.1.10" => Builtin::Ecc(&CURVES[15]),
+  "1.3.36.3.3.2.8.1.1.11" => Builtin::Ecc(&CURVES[16]),
+  "1.3.36.3.3.2.8.1.1.12" => Builtin::Ecc(&CURVES[17]),
+  "1.3.36.3.3.2.8.1.1.13" => Builtin::Ecc(&CURVES[18]),
+  "1.3.36.3.3.2.8.1.1.14" => Builtin::Ecc(&CURVES[19]),
+  "1.2.840.10045.3.0.1" => Builtin::Ecc(&CURVES[20]),
+  "2.23.43.1.4.5" => Builtin::Ecc(&CURVES[20]),
+  "1.2.840.10045.3.0.2" => Builtin::Ecc(&CURVES[21]),
+  "1.2.840.10045.3.0.3" => Builtin::Ecc(&CURVES[22]),
+  "1.2.840.10045.3.0.4" => Builtin::Ecc(&CURVES[23]),
+  "1.2.840.10045.3.0.10" => Builtin::Ecc(&CURVES[24]),
+  "1.2.840.10045.3.0.16" => Builtin::Ecc(&CURVES[25]),
+  "1.2.840.10045.3.0.17" => Builtin::Ecc(&CURVES[26]),
+  "1.2.840.10045.3.0.19" => Builtin::Ecc(&CURVES[27]),
+  "1.2.840.10045.3.0.5" => Builtin::Ecc(&CURVES[28]),
+  "1.2.840.10045.3.0.6" => Builtin::Ecc(&CURVES[29]),
+  "1.2.840.10045.3.0.7" => Builtin::Ecc(&CURVES[30]),
+  "1.2.840.10045.3.0.11" => Builtin::Ecc(&CURVES[31]),
+  "1.2.840.10045.3.0.12" => Builtin::Ecc(&CURVES[32]),
+  "1.2.840.10045.3.0.13" => Builtin::Ecc(&CURVES[33]),
+  "1.2.840.10045.3.0.18" => Builtin::Ecc(&CURVES[34]),
+  "1.2.840.10045.3.0.20" => Builtin::Ecc(&CURVES[35]),
+  "1.3.101.112" => Builtin::Ecc(&CURVES[36]),
+  "1.3.101.113" => Builtin::Ecc(&CURVES[38]),
+  "1.3.132.0.1" => Builtin::Ecc(&CURVES[40]),
+  "2.23.43.1.4.3" => Builtin::Ecc(&CURVES[40]),
+  "1.3.132.0.26" => Builtin::Ecc(&CURVES[41]),
+  "2.23.43.1.4.10" => Builtin::Ecc(&CURVES[41]),
+  "1.3.132.0.16" => Builtin::Ecc(&CURVES[42]),
+  "1.3.132.0.36" => Builtin::Ecc(&CURVES[43]),
+  "1.3.132.0.38" => Builtin::Ecc(&CURVES[44]),
+  "1.2.840.10045.3.1.1" => Builtin::Ecc(&CURVES[45]),
+  "1.3.132.0.33" => Builtin::Ecc(&CURVES[46]),
+  "2.23.43.1.4.12" => Builtin::Ecc(&CURVES[46]),
+  "1.2.840.10045.3.1.7" => Builtin::Ecc(&CURVES[47]),
+  "1.3.132.0.34" => Builtin::Ecc(&CURVES[48]),
+  "1.3.132.0.35" => Builtin::Ecc(&CURVES[49]),
+  "1.2.840.10045.3.1.2" => Builtin::Ecc(&CURVES[50]),
+  "1.2.840.10045.3.1.3" => Builtin::Ecc(&CURVES[51]),
+  "1.2.840.10045.3.1.4" => Builtin::Ecc(&CURVES[52]),
+  "1.2.840.10045.3.1.5" => Builtin::Ecc(&CURVES[53]),
+  "1.2.840.10045.3.1.6" => Builtin::Ecc(&CURVES[54]),
+  "1.3.132.0.6" => Builtin::Ecc(&CURVES[55]),
+  "2.23.43.1.4.6" => Builtin::Ecc(&CURVES[55]),
+  "1.3.132.0.7" => Builtin::Ecc(&CURVES[56]),
+  "1.3.132.0.28" => Builtin::Ecc(&CURVES[57]),
+  "1.3.132.0.29" => Builtin::Ecc(&CURVES[58]),
+  "1.3.132.0.8" => Builtin::Ecc(&CURVES[59]),
+  "2.23.43.1.4.7" => Builtin::Ecc(&CURVES[59]),
+  "1.3.132.0.9" => Builtin::Ecc(&CURVES[60]),
+  "1.3.132.0.30" => Builtin::Ecc(&CURVES[61]),
+  "1.3.132.0.31" => Builtin::Ecc(&CURVES[62]),
+  "1.3.132.0.32" => Builtin::Ecc(&CURVES[63]),
+  "1.3.132.0.10" => Builtin::Ecc(&CURVES[64]),
+  "1.3.132.0.4" => Builtin::Ecc(&CURVES[65]),
+  "2.23.43.1.4.4" => Builtin::Ecc(&CURVES[65]),
+  "1.3.132.0.5" => Builtin::Ecc(&CURVES[66]),
+  "1.3.132.0.22" => Builtin::Ecc(&CURVES[67]),
+  "1.3.132.0.23" => Builtin::Ecc(&CURVES[68]),
+  "1.3.132.0.2" => Builtin::Ecc(&CURVES[69]),
+  "1.3.132.0.24" => Builtin::Ecc(&CURVES[70]),
+  "1.3.132.0.25" => Builtin::Ecc(&CURVES[71]),
+  "1.3.132.0.3" => Builtin::Ecc(&CURVES[72]),
+  "1.2.156.10197.1.301" => Builtin::Ecc(&CURVES[73]),
+  "2.23.43.1.4.1" => Builtin::Ecc(&CURVES[74]),
+  "2.23.43.1.4.8" => Builtin::Ecc(&CURVES[75]),
+  "2.23.43.1.4.9" => Builtin::Ecc(&CURVES[76]),
+  "1.3.101.110" => Builtin::Ecc(&CURVES[77]),
+  "1.3.101.111" => Builtin::Ecc(&CURVES[78]),
+  "1.3.6.1.4.1.1722.12.2.1.8" => Builtin::Hash(&HASH_FUNCTIONS[4]),
+  "1.3.6.1.4.1.1722.12.2.1.12" => Builtin::Hash(&HASH_FUNCTIONS[5]),
+  "1.3.6.1.4.1.1722.12.2.1.16" => Builtin::Hash(&HASH_FUNCTIONS[6]),
+  "1.3.6.1.4.1.1722.12.2.2.8" => Builtin::Hash(&HASH_FUNCTIONS[7]),
+  "1.2.840.113549.2.4" => Builtin::Hash(&HASH_FUNCTIONS[9]),
+  "1.2.840.113549.2.5" => Builtin::Hash(&HASH_FUNCTIONS[10]),
+  "1.3.36.3.2.1" => Builtin::Hash(&HASH_FUNCTIONS[11]),
+  "1.3.14.3.2.26" => Builtin::Hash(&HASH_FUNCTIONS[12]),
+  "2.16.840.1.101.3.4.2.4" => Builtin::Hash(&HASH_FUNCTIONS[13]),
+  "2.16.840.1.101.3.4.2.1" => Builtin::Hash(&HASH_FUNCTIONS[14]),
+  "2.16.840.1.101.3.4.2.2" => Builtin::Hash(&HASH_FUNCTIONS[15]),
+  "2.16.840.1.101.3.4.2.7" => Builtin::Hash(&HASH_FUNCTIONS[16]),
+  "2.16.840.1.101.3.4.2.8" => Builtin::Hash(&HASH_FUNCTIONS[17]),
+  "2.16.840.1.101.3.4.2.9" => Builtin::Hash(&HASH_FUNCTIONS[18]),
+  "2.16.840.1.101.3.4.2.10" => Builtin::Hash(&HASH_FUNCTIONS[19]),
+  "2.16.840.1.101.3.4.2.3" => Builtin::Hash(&HASH_FUNCTIONS[20]),
+  "2.16.840.1.101.3.4.2.5" => Builtin::Hash(&HASH_FUNCTIONS[21]),
+  "2.16.840.1.101.3.4.2.6" => Builtin::Hash(&HASH_FUNCTIONS[22]),
+  "2.16.840.1.101.3.4.2.11" => Builtin::Hash(&HASH_FUNCTIONS[23]),
+  "2.16.840.1.101.3.4.2.12" => Builtin::Hash(&HASH_FUNCTIONS[24]),
+  "1.0.10118.3.0.55" => Builtin::Hash(&HASH_FUNCTIONS[25]),
+};
+
+/// The signature algorithms by their object identifiers.
+pub(super) static SIGNATURES: phf::Map<&'static str, &'static Entry<Signature>> = phf::phf_map! {
+  "1.2.840.10045.4.1" => &SIGNATURE_ALGORITHMS[0],
+  "1.2.840.10045.4.3.1" => &SIGNATURE_ALGORITHMS[1],
+  "1.2.840.10045.4.3.2" => &SIGNATURE_ALGORITHMS[2],
+  "1.2.840.10045.4.3.3" => &SIGNATURE_ALGORITHMS[3],
+  "1.2.840.10045.4.3.4" => &SIGNATURE_ALGORITHMS[4],
+  "1.2.840.10040.4.3" => &SIGNATURE_ALGORITHMS[5],
+  "2.16.840.1.101.3.4.3.1" => &SIGNATURE_ALGORITHMS[6],
+  "2.16.840.1.101.3.4.3.2" => &SIGNATURE_ALGORITHMS[7],
+  "2.16.840.1.101.3.4.3.3" => &SIGNATURE_ALGORITHMS[8],
+  "2.16.840.1.101.3.4.3.4" => &SIGNATURE_ALGORITHMS[9],
+  "2.16.840.1.101.3.4.3.5" => &SIGNATURE_ALGORITHMS[10],
+  "2.16.840.1.101.3.4.3.6" => &SIGNATURE_ALGORITHMS[11],
+  "2.16.840.1.101.3.4.3.7" => &SIGNATURE_ALGORITHMS[12],
+  "2.16.840.1.101.3.4.3.8" => &SIGNATURE_ALGORITHMS[13],
+  "2.16.840.1.101.3.4.3.9" => &SIGNATURE_ALGORITHMS[14],
+  "2.16.840.1.101.3.4.3.10" => &SIGNATURE_ALGORITHMS[15],
+  "2.16.840.1.101.3.4.3.11" => &SIGNATURE_ALGORITHMS[16],
+  "2.16.840.1.101.3.4.3.12" => &SIGNATURE_ALGORITHMS[17],
+  "2.16.840.1.101.3.4.3.13" => &SIGNATURE_ALGORITHMS[18],
+  "2.16.840.1.101.3.4.3.14" => &SIGNATURE_ALGORITHMS[19],
+  "2.16.840.1.101.3.4.3.15" => &SIGNATURE_ALGORITHMS[20],
+  "2.16.840.1.101.3.4.3.16" => &SIGNATURE_ALGORITHMS[21],
+  "1.2.840.113549.1.1.3" => &SIGNATURE_ALGORITHMS[22],
+  "1.2.840.113549.1.1.4" => &SIGNATURE_ALGORITHMS[23],
+  "1.2.840.113549.1.1.5" => &SIGNATURE_ALGORITHMS[24],
+  "1.2.840.113549.1.1.11" => &SIGNATURE_ALGORITHMS[25],
+  "1.2.840.113549.1.1.12" => &SIGNATURE_ALGORITHMS[26],
+  "1.2.840.113549.1.1.13" => &SIGNATURE_ALGORITHMS[27],
+  "1.2.840.113549.1.1.14" => &SIGNATURE_ALGORITHMS[28],
+  "1.2.840.113549.1.1.15" => &SIGNATURE_ALGORITHMS[29],
+  "1.2.840.113549.1.1.16" => &SIGNATURE_ALGORITHMS[30],
+  "1.2.840.113549.1.1.10" => &SIGNATURE_ALGORITHMS[31],
+  "1.3.101.112" => &SIGNATURE_ALGORITHMS[32],
+  "1.3.101.113" => &SIGNATURE_ALGORITHMS[33],
+};
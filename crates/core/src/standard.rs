@@ -1,20 +1,38 @@
 //! Assess the security of a cryptographic primitive against a standard
 //! or research publication.
+//!
+//! The primitives that each standard specifies are kept in arrays that
+//! are built at compile time. Validation therefore never allocates or
+//! runs an initialiser, which keeps it safe to call from constrained
+//! contexts such as the C API.
 pub mod bsi;
 pub mod cnsa;
 pub mod ecrypt;
+pub mod iot;
 pub mod lenstra;
 pub mod nist;
+pub mod rule;
+pub mod table;
 pub mod testing;
 mod utilities;
+pub mod verdict;
+pub mod window;
 
+use crate::advisory::{Hazard, Hazards};
 use crate::context::Context;
 use crate::primitive::asymmetric::Asymmetric;
 use crate::primitive::ecc::Ecc;
 use crate::primitive::ffc::Ffc;
 use crate::primitive::hash::Hash;
+use crate::primitive::hybrid::Hybrid;
 use crate::primitive::ifc::Ifc;
-use crate::primitive::symmetric::Symmetric;
+use crate::primitive::pqc::{Pqc, PQC_NOT_ALLOWED};
+use crate::primitive::stateful::{Stateful, STATEFUL_NOT_ALLOWED};
+use crate::primitive::symmetric::{Mode, Symmetric};
+use crate::primitive::threshold::{Scheme, Threshold};
+use crate::primitive::Primitive;
+use crate::standard::rule::{Rule, Scope};
+use crate::standard::verdict::Verdict;
 
 /// Represents a cryptographic standard or research publication.
 ///
@@ -32,6 +50,15 @@ pub trait Standard {
       Asymmetric::Ffc(ffc) => Self::validate_ffc(ctx, ffc)
         .map(Into::into)
         .map_err(Into::into),
+      Asymmetric::Pqc(pqc) => Self::validate_pqc(ctx, pqc)
+        .map(Into::into)
+        .map_err(Into::into),
+      Asymmetric::Hybrid(hybrid) => Self::validate_hybrid(ctx, hybrid)
+        .map(Into::into)
+        .map_err(Into::into),
+      Asymmetric::Stateful(stateful) => Self::validate_stateful(ctx, stateful)
+        .map(Into::into)
+        .map_err(Into::into),
     }
   }
 
@@ -40,4 +67,167 @@ pub trait Standard {
   fn validate_ifc(ctx: Context, key: Ifc) -> Result<Ifc, Ifc>;
   fn validate_hash(ctx: Context, hash: Hash) -> Result<Hash, Hash>;
   fn validate_symmetric(ctx: Context, key: Symmetric) -> Result<Symmetric, Symmetric>;
+
+  /// Validates an ML-DSA or SLH-DSA signature primitive. Standards that
+  /// approve neither keep this default, which rejects every parameter
+  /// set with [`PQC_NOT_ALLOWED`] as the alternative.
+  fn validate_pqc(_ctx: Context, _key: Pqc) -> Result<Pqc, Pqc> {
+    Err(PQC_NOT_ALLOWED)
+  }
+
+  /// Validates a stateful hash-based signature primitive. Standards that
+  /// approve none keep this default, which rejects every parameter set
+  /// with [`STATEFUL_NOT_ALLOWED`] as the alternative.
+  fn validate_stateful(_ctx: Context, _key: Stateful) -> Result<Stateful, Stateful> {
+    Err(STATEFUL_NOT_ALLOWED)
+  }
+
+  /// Validates a hybrid signature, which complies if either of its
+  /// halves does since a forger has to break both. The alternative holds
+  /// the recommendation for each half, or the half itself if it is
+  /// compliant when the other is not.
+  fn validate_hybrid(ctx: Context, key: Hybrid) -> Result<Hybrid, Hybrid> {
+    let pqc = Self::validate_pqc(ctx, key.pqc);
+    let ecc = Self::validate_ecc(ctx, key.ecc);
+    match (pqc, ecc) {
+      (Err(pqc), Err(ecc)) => Err(Hybrid::new(pqc, ecc)),
+      (pqc, ecc) => Ok(Hybrid::new(pqc.unwrap_or(key.pqc), ecc.unwrap_or(key.ecc))),
+    }
+  }
+
+  /// Validates a threshold or multi-signature configuration by its
+  /// curve, as splitting a key does not change what it takes to break
+  /// it. A configuration that is not consistent does not comply whatever
+  /// its curve. The alternative is the same configuration on the curve
+  /// the standard recommends, with at least two parties needed to sign,
+  /// or with threshold ECDSA or a multi-signature if the scheme is not
+  /// defined over it.
+  fn validate_threshold(ctx: Context, key: Threshold) -> Result<Threshold, Threshold> {
+    let curve = Self::validate_ecc(ctx, key.curve);
+    let n = key.n.max(2);
+    let mut want = Threshold::new(
+      key.scheme,
+      curve.unwrap_or_else(|curve| curve),
+      key.t.clamp(2, n),
+      n,
+    );
+    match curve {
+      Ok(_) if key.is_consistent() => Ok(want),
+      _ => {
+        if !want.scheme.fits(want.curve) {
+          want.scheme = [Scheme::Ecdsa, Scheme::Multisig]
+            .into_iter()
+            .find(|scheme| scheme.fits(want.curve))
+            .unwrap_or(Scheme::Multisig);
+        }
+        Err(want)
+      },
+    }
+  }
+
+  /// Returns the rules the validation functions are based on.
+  /// Standards that are not derived from a publication, such as the
+  /// mock standards used for testing, have none.
+  fn rules() -> &'static [Rule] {
+    &[]
+  }
+
+  /// Returns the implementation hazards that are raised as advisories
+  /// alongside the verdicts of the standard (see [`crate::advisory`]).
+  fn hazards() -> Hazards {
+    Hazards::ALL
+  }
+
+  /// Returns how many blocks a cipher may encrypt under one key in
+  /// `mode`, or `None` if there is no limit short of what the security
+  /// of the key allows. Ciphers with 64-bit blocks in a mode that
+  /// encrypts bulk data are limited to 2^32 blocks, around which two
+  /// blocks are likely to collide, unless the standard sets a stricter
+  /// limit. This is raised along with [`Hazard::SmallBlock`].
+  fn block_limit(key: Symmetric, mode: Mode) -> Option<u64> {
+    Hazard::of_cipher(key, mode).map(|_| 1 << 32)
+  }
+
+  /// Returns the rule that applies to the given type of primitive.
+  fn rule(scope: Scope) -> Option<&'static Rule> {
+    Self::rules().iter().find(|rule| rule.scope == scope)
+  }
+
+  /// Returns the rule that applies to a finite field primitive, which
+  /// differs between DSA domain parameters and the groups used for key
+  /// establishment (see [`rule::ffc_rule`]).
+  fn ffc_rule(key: Ffc) -> Option<&'static Rule> {
+    rule::ffc_rule(Self::rules(), key)
+  }
+
+  /// Validates an asymmetric key primitive and cites the rule that the
+  /// outcome is based on.
+  fn assess_asymmetric(ctx: Context, key: Asymmetric) -> Verdict<Asymmetric> {
+    let rule = rule::asymmetric_rule(Self::rules(), key);
+    Verdict::validate(ctx, key, Self::validate_asymmetric, rule)
+  }
+
+  /// Validates an elliptic curve cryptography primitive and cites the
+  /// rule that the outcome is based on.
+  fn assess_ecc(ctx: Context, key: Ecc) -> Verdict<Ecc> {
+    Verdict::validate(ctx, key, Self::validate_ecc, Self::rule(Scope::Ecc))
+  }
+
+  /// Validates a finite field cryptography primitive and cites the rule
+  /// that the outcome is based on.
+  fn assess_ffc(ctx: Context, key: Ffc) -> Verdict<Ffc> {
+    Verdict::validate(ctx, key, Self::validate_ffc, Self::ffc_rule(key))
+  }
+
+  /// Validates an integer factorisation cryptography primitive and cites
+  /// the rule that the outcome is based on.
+  fn assess_ifc(ctx: Context, key: Ifc) -> Verdict<Ifc> {
+    Verdict::validate(ctx, key, Self::validate_ifc, Self::rule(Scope::Ifc))
+  }
+
+  /// Validates a hash function used in digital signatures and cites the
+  /// rule that the outcome is based on.
+  fn assess_hash(ctx: Context, hash: Hash) -> Verdict<Hash> {
+    Verdict::validate(ctx, hash, Self::validate_hash, Self::rule(Scope::Hash))
+  }
+
+  /// Validates a threshold or multi-signature configuration and cites
+  /// the rule for elliptic curves that the outcome is based on.
+  fn assess_threshold(ctx: Context, key: Threshold) -> Verdict<Threshold> {
+    Verdict::validate(ctx, key, Self::validate_threshold, Self::rule(Scope::Ecc))
+  }
+
+  /// Validates a symmetric key primitive and cites the rule that the
+  /// outcome is based on.
+  fn assess_symmetric(ctx: Context, key: Symmetric) -> Verdict<Symmetric> {
+    Verdict::validate(
+      ctx,
+      key,
+      Self::validate_symmetric,
+      Self::rule(Scope::Symmetric),
+    )
+  }
+
+  /// Validates an ML-DSA or SLH-DSA signature primitive and cites the
+  /// rule that the outcome is based on.
+  fn assess_pqc(ctx: Context, key: Pqc) -> Verdict<Pqc> {
+    Verdict::validate(ctx, key, Self::validate_pqc, Self::rule(Scope::Pqc))
+  }
+
+  /// Validates a hybrid signature and cites the rule for post-quantum
+  /// signatures that the outcome is based on.
+  fn assess_hybrid(ctx: Context, key: Hybrid) -> Verdict<Hybrid> {
+    Verdict::validate(ctx, key, Self::validate_hybrid, Self::rule(Scope::Pqc))
+  }
+
+  /// Validates a stateful hash-based signature primitive and cites the
+  /// rule that the outcome is based on.
+  fn assess_stateful(ctx: Context, key: Stateful) -> Verdict<Stateful> {
+    Verdict::validate(
+      ctx,
+      key,
+      Self::validate_stateful,
+      Self::rule(Scope::Stateful),
+    )
+  }
 }
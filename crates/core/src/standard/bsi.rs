@@ -3,54 +3,171 @@
 //! guide.
 //!
 //! [BSI TR-02102-1 Cryptographic Mechanisms: Recommendations and Key Lengths]: https://www.bsi.bund.de/SharedDocs/Downloads/EN/BSI/Publications/TechGuidelines/TG02102/BSI-TR-02102-1.html
-use std::collections::HashSet;
-
-use once_cell::sync::Lazy;
-
 use crate::context::Context;
 use crate::primitive::ecc::*;
 use crate::primitive::ffc::*;
 use crate::primitive::hash::*;
+use crate::primitive::hybrid::Hybrid;
 use crate::primitive::ifc::*;
+use crate::primitive::pqc::*;
+use crate::primitive::stateful::*;
 use crate::primitive::symmetric::*;
 use crate::primitive::Primitive;
+use crate::standard::rule::{Citation, Rule, Scope, Usage};
+use crate::standard::utilities::{self, Application};
 use crate::standard::Standard;
 
 const CUTOFF_YEAR_RSA: u16 = 2023; // See p. 17.
 
-static SPECIFIED_CURVES: Lazy<HashSet<Ecc>> = Lazy::new(|| {
-  let mut s = HashSet::new();
-  s.insert(SECP256R1);
-  s.insert(SECP384R1);
-  s.insert(SECP521R1);
-  s.insert(BRAINPOOLP256R1);
-  s.insert(BRAINPOOLP320R1);
-  s.insert(BRAINPOOLP384R1);
-  s.insert(BRAINPOOLP512R1);
-  s
-});
-
-static SPECIFIED_HASH_FUNCTIONS: Lazy<HashSet<Hash>> = Lazy::new(|| {
-  let mut s = HashSet::new();
-  s.insert(SHA256);
-  s.insert(SHA384);
-  s.insert(SHA3_256);
-  s.insert(SHA3_384);
-  s.insert(SHA3_512);
-  s.insert(SHA512);
-  s.insert(SHA512_256);
-  s
-});
+// Post-quantum signatures are only recommended from security category 3
+// on.
+const MIN_SECURITY_PQC: u16 = 192;
+
+static SPECIFIED_CURVES: [Ecc; 7] = [
+  SECP256R1,
+  SECP384R1,
+  SECP521R1,
+  BRAINPOOLP256R1,
+  BRAINPOOLP320R1,
+  BRAINPOOLP384R1,
+  BRAINPOOLP512R1,
+];
+
+static SPECIFIED_HASH_FUNCTIONS: [Hash; 7] = [
+  SHA256, SHA384, SHA3_256, SHA3_384, SHA3_512, SHA512, SHA512_256,
+];
+
+static RECOMMENDED_HASH_FUNCTIONS: [(Hash, Application); 3] = [
+  (SHA256, Application::Collision),
+  (SHA384, Application::Collision),
+  (SHA512, Application::Collision),
+];
 
 // "The present version of this Technical Guideline does not recommend
 // any other block ciphers besides AES" (2023, p. 24).
-static SPECIFIED_SYMMETRIC_KEYS: Lazy<HashSet<Symmetric>> = Lazy::new(|| {
-  let mut s = HashSet::new();
-  s.insert(AES128);
-  s.insert(AES192);
-  s.insert(AES256);
-  s
-});
+static SPECIFIED_SYMMETRIC_KEYS: [Symmetric; 3] = [AES128, AES192, AES256];
+
+const DOCUMENT: &str = "BSI TR-02102-1";
+const URL: &str = "https://www.bsi.bund.de/SharedDocs/Downloads/EN/BSI/Publications/TechGuidelines/TG02102/BSI-TR-02102-1.html";
+
+static RULES: [Rule; 9] = [
+  Rule {
+    id: "BSI-ECC-001",
+    scope: Scope::Ecc,
+    usage: None,
+    minimum: Some(125),
+    cutoff: None,
+    summary: "Brainpool and NIST P curves of at least 250 bits. Curves are only accepted with domain parameters generated by a trustworthy authority.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "Table 1.2, p. 17 and p. 73",
+      url: Some(URL),
+    },
+  },
+  Rule {
+    id: "BSI-FFC-001",
+    scope: Scope::Ffc,
+    usage: Some(Usage::Signature),
+    minimum: Some(125),
+    cutoff: None,
+    summary: "DSA with a prime p of at least 3000 bits and a subgroup order q greater than 2²⁵⁰.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "Table 1.2, p. 17 and p. 48",
+      url: Some(URL),
+    },
+  },
+  Rule {
+    id: "BSI-DH-001",
+    scope: Scope::Ffc,
+    usage: Some(Usage::KeyAgreement),
+    minimum: Some(125),
+    cutoff: None,
+    summary: "Diffie-Hellman key agreement in groups with a prime p of at least 3000 bits and a subgroup order q greater than 2²⁵⁰.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "Table 1.2, p. 17 and §7.2.1",
+      url: Some(URL),
+    },
+  },
+  Rule {
+    id: "BSI-IFC-001",
+    scope: Scope::Ifc,
+    usage: None,
+    minimum: Some(112),
+    cutoff: Some(CUTOFF_YEAR_RSA),
+    summary: "RSA moduli of at least 2000 bits until the cutoff year and at least 3000 bits after.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "Table 1.2, p. 17",
+      url: Some(URL),
+    },
+  },
+  Rule {
+    id: "BSI-HASH-001",
+    scope: Scope::Hash,
+    usage: None,
+    minimum: Some(120),
+    cutoff: None,
+    summary: "SHA-2 and SHA-3 hash functions with a digest of at least 256 bits.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "p. 41",
+      url: Some(URL),
+    },
+  },
+  Rule {
+    id: "BSI-HASH-002",
+    scope: Scope::HashBased,
+    usage: None,
+    minimum: Some(128),
+    cutoff: None,
+    summary: "SHA-2 and SHA-3 hash functions with a digest of at least 256 bits when used in HMAC.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "p. 41 and p. 45",
+      url: Some(URL),
+    },
+  },
+  Rule {
+    id: "BSI-SYM-001",
+    scope: Scope::Symmetric,
+    usage: None,
+    minimum: Some(120),
+    cutoff: None,
+    summary: "AES with a key of at least 128 bits. No other block ciphers are recommended.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "p. 24",
+      url: Some(URL),
+    },
+  },  Rule {
+    id: "BSI-PQC-001",
+    scope: Scope::Pqc,
+    usage: None,
+    minimum: Some(MIN_SECURITY_PQC),
+    cutoff: None,
+    summary: "SLH-DSA with parameter sets of at least security category 3. ML-DSA of at least security category 3 only in a hybrid with a recommended elliptic curve signature.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "Chapter 5",
+      url: Some(URL),
+    },
+  },
+  Rule {
+    id: "BSI-HBS-001",
+    scope: Scope::Stateful,
+    usage: None,
+    minimum: Some(192),
+    cutoff: None,
+    summary: "LMS, HSS, XMSS and XMSS^MT with the parameter sets of NIST SP 800-208.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "Chapter 5",
+      url: Some(URL),
+    },
+  },
+];
 
 /// [`Standard`] implementation for the
 /// [BSI TR-02102-1 Cryptographic Mechanisms: Recommendations and Key
@@ -81,12 +198,10 @@ impl Bsi {
   /// be used with this primitive is 256 (see p. 41). This means any
   /// recommendation from this function will be likely too conservative.
   ///
-  /// An alternative might also be suggested for a compliant hash
-  /// functions with a similar security level in which a switch to the
-  /// recommended primitive would likely be unwarranted. For example,
-  /// when evaluating compliance for the `SHA3-256`, a recommendation
-  /// to use `SHA256` will be made but switching to this as a result
-  /// is likely unnecessary.
+  /// When the hash function belongs to the SHA-3 family, the
+  /// recommendation is the SHA-3 function with the same digest length
+  /// rather than its SHA-2 counterpart, so that a compliant `SHA3-256`
+  /// is not met with advice to switch to `SHA256`.
   ///
   /// # Example
   ///
@@ -105,17 +220,36 @@ impl Bsi {
   /// assert_eq!(Bsi::validate_hash_based(ctx, hmac_sha1), Err(hmac_sha256));
   /// ```
   pub fn validate_hash_based(ctx: Context, hash: Hash) -> Result<Hash, Hash> {
-    if SPECIFIED_HASH_FUNCTIONS.contains(&hash) {
-      let pre_image_resistance = hash.security() << 1;
-      let security = ctx.security().max(pre_image_resistance);
-      match security {
-        ..=127 => Err(SHA256),
-        128..=256 => Ok(SHA256),
-        257..=384 => Ok(SHA384),
-        385.. => Ok(SHA512),
+    let verdict = if SPECIFIED_HASH_FUNCTIONS.contains(&hash) {
+      let minimum = 128;
+      let application = Application::PreImage;
+      let security = ctx.security().max(application.strength(hash));
+      let recommended = utilities::recommend_hash(
+        &RECOMMENDED_HASH_FUNCTIONS,
+        application,
+        security.max(minimum),
+      );
+      if security < minimum {
+        Err(recommended)
+      } else {
+        Ok(recommended)
       }
     } else {
       Err(SHA256)
+    };
+    utilities::prefer_sha3(hash, verdict, &SPECIFIED_HASH_FUNCTIONS)
+  }
+
+  // Validates the post-quantum half of a signature, where ML-DSA is only
+  // accepted if the signature is a hybrid.
+  fn validate_post_quantum(ctx: Context, key: Pqc, hybrid: bool) -> Result<Pqc, Pqc> {
+    let security = ctx.security().max(key.security()).max(MIN_SECURITY_PQC);
+    let fallback = if hybrid { ML_DSA_65 } else { SLH_DSA_SHA2_192S };
+    let approved = key.is_slh_dsa() || (hybrid && key.is_ml_dsa());
+    match key.at_least(security) {
+      Some(want) if approved && key.security() >= MIN_SECURITY_PQC => Ok(want),
+      Some(want) if approved => Err(want),
+      _ => Err(fallback.at_least(security).unwrap_or(fallback)),
     }
   }
 }
@@ -169,10 +303,12 @@ impl Standard for Bsi {
   /// Validates a finite field cryptography primitive.
   ///
   /// Examples include the DSA and key establishment algorithms such as
-  /// Diffie-Hellman.
+  /// Diffie-Hellman, which the guide holds to the same key lengths.
   ///
   /// If the key is not compliant then `Err` will contain the
-  /// recommended key sizes L and N that one should use instead.
+  /// recommended key sizes L and N that one should use instead, or the
+  /// recommended group if the key is a group used for key
+  /// establishment.
   ///
   /// If the key is compliant but the context specifies a higher
   /// security level, `Ok` will also hold the recommended key sizes L
@@ -195,13 +331,15 @@ impl Standard for Bsi {
   /// ```
   fn validate_ffc(ctx: Context, key: Ffc) -> Result<Ffc, Ffc> {
     let security = ctx.security().max(key.security());
-    match security {
+    let verdict = match security {
       // Page 48 says q > 2²⁵⁰.
       ..=124 => Err(DSA_3072_256),
       125..=128 => Ok(DSA_3072_256),
       129..=192 => Ok(DSA_7680_384),
       193.. => Ok(DSA_15360_512),
-    }
+    };
+    let verdict = utilities::prefer_group(key, verdict);
+    utilities::reject_inconsistent(key, verdict)
   }
 
   /// Validates a hash function according to page 41 of the guide. The
@@ -220,12 +358,10 @@ impl Standard for Bsi {
   /// higher security level, `Ok` will also hold the recommended
   /// primitive with the desired security level.
   ///
-  /// **Note:** An alternative might be suggested for a compliant hash
-  /// function with a similar security level in which a switch to the
-  /// recommended primitive would likely be unwarranted. For example,
-  /// when evaluating compliance for the `SHA3-256`, a recommendation
-  /// to use `SHA256` will be made but switching to this as a result
-  /// is likely unnecessary.
+  /// When the hash function belongs to the SHA-3 family, the
+  /// recommendation is the SHA-3 function with the same digest length
+  /// rather than its SHA-2 counterpart, so that a compliant `SHA3-256`
+  /// is not met with advice to switch to `SHA256`.
   ///
   /// # Example
   ///
@@ -242,17 +378,24 @@ impl Standard for Bsi {
   /// assert_eq!(Bsi::validate_hash(ctx, SHA1), Err(SHA256));
   /// ```
   fn validate_hash(ctx: Context, hash: Hash) -> Result<Hash, Hash> {
-    if SPECIFIED_HASH_FUNCTIONS.contains(&hash) {
-      let security = ctx.security().max(hash.security());
-      match security {
-        ..=119 => Err(SHA256),
-        120..=128 => Ok(SHA256),
-        129..=192 => Ok(SHA384),
-        193.. => Ok(SHA512),
+    let verdict = if SPECIFIED_HASH_FUNCTIONS.contains(&hash) {
+      let minimum = 120;
+      let application = Application::Collision;
+      let security = ctx.security().max(application.strength(hash));
+      let recommended = utilities::recommend_hash(
+        &RECOMMENDED_HASH_FUNCTIONS,
+        application,
+        security.max(minimum),
+      );
+      if security < minimum {
+        Err(recommended)
+      } else {
+        Ok(recommended)
       }
     } else {
       Err(SHA256)
-    }
+    };
+    utilities::prefer_sha3(hash, verdict, &SPECIFIED_HASH_FUNCTIONS)
   }
 
   /// Validates  an integer factorisation cryptography primitive the
@@ -286,14 +429,14 @@ impl Standard for Bsi {
     let security = ctx.security().max(key.security());
     match security {
       ..=111 => {
-        if ctx.year() > CUTOFF_YEAR_RSA {
+        if ctx.year() > ctx.cutoff(Scope::Ifc, CUTOFF_YEAR_RSA) {
           Err(RSA_PSS_3072)
         } else {
           Err(RSA_PSS_2048)
         }
       },
       112..=127 => {
-        if ctx.year() > CUTOFF_YEAR_RSA {
+        if ctx.year() > ctx.cutoff(Scope::Ifc, CUTOFF_YEAR_RSA) {
           Err(RSA_PSS_3072)
         } else {
           Ok(RSA_PSS_2048)
@@ -342,12 +485,112 @@ impl Standard for Bsi {
       Err(AES128)
     }
   }
+
+  /// Validates an ML-DSA or SLH-DSA signature primitive used on its
+  /// own. Only SLH-DSA is recommended by itself, and only with
+  /// parameter sets of at least security category 3. ML-DSA is only
+  /// recommended in a hybrid (see
+  /// [`validate_hybrid`](crate::standard::bsi::Bsi::validate_hybrid)).
+  ///
+  /// If the key is not compliant then `Err` will contain the
+  /// recommended parameter set that one should use instead.
+  ///
+  /// If the key is compliant but the context specifies a higher
+  /// security level, `Ok` will also hold the parameter set of the same
+  /// family with the desired security level.
+  ///
+  /// # Example
+  ///
+  /// The following illustrates a call to validate a non-compliant key.
+  ///
+  /// ```
+  /// use wardstone_core::context::Context;
+  /// use wardstone_core::primitive::pqc::{ML_DSA_65, SLH_DSA_SHA2_192S};
+  /// use wardstone_core::standard::bsi::Bsi;
+  /// use wardstone_core::standard::Standard;
+  ///
+  /// let ctx = Context::default();
+  /// assert_eq!(Bsi::validate_pqc(ctx, ML_DSA_65), Err(SLH_DSA_SHA2_192S));
+  /// ```
+  fn validate_pqc(ctx: Context, key: Pqc) -> Result<Pqc, Pqc> {
+    Self::validate_post_quantum(ctx, key, false)
+  }
+
+  /// Validates a hybrid signature. Unlike the default, which accepts a
+  /// hybrid if either half is compliant, both halves have to be, with
+  /// ML-DSA accepted alongside SLH-DSA for the post-quantum half.
+  ///
+  /// If the key is not compliant then `Err` will contain the
+  /// recommended halves that one should use instead.
+  ///
+  /// # Example
+  ///
+  /// The following illustrates a call to validate a compliant key.
+  ///
+  /// ```
+  /// use wardstone_core::context::Context;
+  /// use wardstone_core::primitive::ecc::BRAINPOOLP384R1;
+  /// use wardstone_core::primitive::hybrid::Hybrid;
+  /// use wardstone_core::primitive::pqc::ML_DSA_87;
+  /// use wardstone_core::standard::bsi::Bsi;
+  /// use wardstone_core::standard::Standard;
+  ///
+  /// let ctx = Context::default();
+  /// let key = Hybrid::new(ML_DSA_87, BRAINPOOLP384R1);
+  /// assert_eq!(Bsi::validate_hybrid(ctx, key), Ok(key));
+  /// ```
+  fn validate_hybrid(ctx: Context, key: Hybrid) -> Result<Hybrid, Hybrid> {
+    let pqc = Self::validate_post_quantum(ctx, key.pqc, true);
+    let ecc = Self::validate_ecc(ctx, key.ecc);
+    match (pqc, ecc) {
+      (Ok(pqc), Ok(ecc)) => Ok(Hybrid::new(pqc, ecc)),
+      (pqc, ecc) => Err(Hybrid::new(
+        pqc.unwrap_or_else(|pqc| pqc),
+        ecc.unwrap_or_else(|ecc| ecc),
+      )),
+    }
+  }
+
+  /// Validates a stateful hash-based signature primitive. The parameter
+  /// sets of SP 800-208 are recommended.
+  ///
+  /// If the key is not compliant then `Err` will contain the
+  /// recommended parameter set that one should use instead.
+  ///
+  /// If the key is compliant but the context specifies a higher
+  /// security level, `Ok` will also hold the parameter set of the same
+  /// scheme and hash function with the desired security level.
+  ///
+  /// # Example
+  ///
+  /// The following illustrates a call to validate a compliant key.
+  ///
+  /// ```
+  /// use wardstone_core::context::Context;
+  /// use wardstone_core::primitive::stateful::XMSS_SHA256_256;
+  /// use wardstone_core::standard::bsi::Bsi;
+  /// use wardstone_core::standard::Standard;
+  ///
+  /// let ctx = Context::default();
+  /// assert_eq!(Bsi::validate_stateful(ctx, XMSS_SHA256_256), Ok(XMSS_SHA256_256));
+  /// ```
+  fn validate_stateful(ctx: Context, key: Stateful) -> Result<Stateful, Stateful> {
+    let security = ctx.security().max(key.security());
+    key.at_least(security).ok_or(XMSS_SHA256_256)
+  }
+
+  fn rules() -> &'static [Rule] {
+    &RULES
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
-  use crate::{test_ecc, test_ffc, test_hash, test_hash_based, test_ifc, test_symmetric};
+  use crate::{
+    test_ecc, test_ffc, test_hash, test_hash_based, test_hash_brackets, test_hybrid, test_ifc,
+    test_pqc, test_stateful, test_symmetric,
+  };
 
   test_ecc!(p224, Bsi, P224, Err(BRAINPOOLP256R1));
   test_ecc!(p256, Bsi, P256, Ok(BRAINPOOLP256R1));
@@ -357,18 +600,38 @@ mod tests {
   test_ecc!(x448, Bsi, X448, Err(BRAINPOOLP256R1));
   test_ecc!(ed25519, Bsi, ED25519, Err(BRAINPOOLP256R1));
   test_ecc!(ed448, Bsi, ED448, Err(BRAINPOOLP256R1));
+  test_ecc!(ed25519ph, Bsi, ED25519PH, Err(BRAINPOOLP256R1));
+  test_ecc!(ed448ph, Bsi, ED448PH, Err(BRAINPOOLP256R1));
   test_ecc!(brainpoolp224r1, Bsi, BRAINPOOLP224R1, Err(BRAINPOOLP256R1));
   test_ecc!(brainpoolp256r1, Bsi, BRAINPOOLP256R1, Ok(BRAINPOOLP256R1));
   test_ecc!(brainpoolp320r1, Bsi, BRAINPOOLP320R1, Ok(BRAINPOOLP320R1));
   test_ecc!(brainpoolp384r1, Bsi, BRAINPOOLP384R1, Ok(BRAINPOOLP384R1));
   test_ecc!(brainpoolp512r1, Bsi, BRAINPOOLP512R1, Ok(BRAINPOOLP512R1));
+  test_ecc!(bls12_381, Bsi, BLS12_381, Err(BRAINPOOLP256R1));
   test_ecc!(secp256k1, Bsi, SECP256K1, Err(BRAINPOOLP256R1));
+  test_ecc!(sm2, Bsi, SM2, Err(BRAINPOOLP256R1));
 
   test_ffc!(ffc_1024_160, Bsi, DSA_1024_160, Err(DSA_3072_256));
   test_ffc!(ffc_2048_224, Bsi, DSA_2048_224, Err(DSA_3072_256));
   test_ffc!(ffc_3072_256, Bsi, DSA_3072_256, Ok(DSA_3072_256));
   test_ffc!(ffc_7680_384, Bsi, DSA_7680_384, Ok(DSA_7680_384));
   test_ffc!(ffc_15360_512, Bsi, DSA_15360_512, Ok(DSA_15360_512));
+  test_ffc!(
+    ffc_3072_224,
+    Bsi,
+    Ffc::new(ID_DSA, 3072, 224),
+    Err(DSA_3072_256)
+  );
+  test_ffc!(
+    ffc_1024_256,
+    Bsi,
+    Ffc::new(ID_DSA, 1024, 256),
+    Err(DSA_3072_256)
+  );
+
+  test_ffc!(modp_2048, Bsi, MODP_2048, Err(FFDHE3072));
+  test_ffc!(ffdhe3072, Bsi, FFDHE3072, Ok(FFDHE3072));
+  test_ffc!(ffdhe8192, Bsi, FFDHE8192, Ok(FFDHE8192));
 
   test_ifc!(ifc_1024, Bsi, RSA_PSS_1024, Err(RSA_PSS_2048));
   test_ifc!(ifc_2048, Bsi, RSA_PSS_2048, Ok(RSA_PSS_2048));
@@ -407,10 +670,10 @@ mod tests {
   test_hash!(sha224_collision_resistance, Bsi, SHA224, Err(SHA256));
   test_hash!(sha256_collision_resistance, Bsi, SHA256, Ok(SHA256));
   test_hash!(sha384_collision_resistance, Bsi, SHA384, Ok(SHA384));
-  test_hash!(sha3_224_collision_resistance, Bsi, SHA3_224, Err(SHA256));
-  test_hash!(sha3_256_collision_resistance, Bsi, SHA3_256, Ok(SHA256));
-  test_hash!(sha3_384_collision_resistance, Bsi, SHA3_384, Ok(SHA384));
-  test_hash!(sha3_512_collision_resistance, Bsi, SHA3_512, Ok(SHA512));
+  test_hash!(sha3_224_collision_resistance, Bsi, SHA3_224, Err(SHA3_256));
+  test_hash!(sha3_256_collision_resistance, Bsi, SHA3_256, Ok(SHA3_256));
+  test_hash!(sha3_384_collision_resistance, Bsi, SHA3_384, Ok(SHA3_384));
+  test_hash!(sha3_512_collision_resistance, Bsi, SHA3_512, Ok(SHA3_512));
   test_hash!(sha512_collision_resistance, Bsi, SHA512, Ok(SHA512));
   test_hash!(
     sha512_224_collision_resistance,
@@ -419,8 +682,8 @@ mod tests {
     Err(SHA256)
   );
   test_hash!(sha512_256_collision_resistance, Bsi, SHA512_256, Ok(SHA256));
-  test_hash!(shake128_collision_resistance, Bsi, SHAKE128, Err(SHA256));
-  test_hash!(shake256_collision_resistance, Bsi, SHAKE256, Err(SHA256));
+  test_hash!(shake128_collision_resistance, Bsi, SHAKE128, Err(SHA3_256));
+  test_hash!(shake256_collision_resistance, Bsi, SHAKE256, Err(SHA3_256));
 
   test_hash_based!(
     blake2b_256_pre_image_resistance,
@@ -453,10 +716,10 @@ mod tests {
   test_hash_based!(sha224_pre_image_resistance, Bsi, SHA224, Err(SHA256));
   test_hash_based!(sha256_pre_image_resistance, Bsi, SHA256, Ok(SHA256));
   test_hash_based!(sha384_pre_image_resistance, Bsi, SHA384, Ok(SHA384));
-  test_hash_based!(sha3_224_pre_image_resistance, Bsi, SHA3_224, Err(SHA256));
-  test_hash_based!(sha3_256_pre_image_resistance, Bsi, SHA3_256, Ok(SHA256));
-  test_hash_based!(sha3_384_pre_image_resistance, Bsi, SHA3_384, Ok(SHA384));
-  test_hash_based!(sha3_512_pre_image_resistance, Bsi, SHA3_512, Ok(SHA512));
+  test_hash_based!(sha3_224_pre_image_resistance, Bsi, SHA3_224, Err(SHA3_256));
+  test_hash_based!(sha3_256_pre_image_resistance, Bsi, SHA3_256, Ok(SHA3_256));
+  test_hash_based!(sha3_384_pre_image_resistance, Bsi, SHA3_384, Ok(SHA3_384));
+  test_hash_based!(sha3_512_pre_image_resistance, Bsi, SHA3_512, Ok(SHA3_512));
   test_hash_based!(sha512_pre_image_resistance, Bsi, SHA512, Ok(SHA512));
   test_hash_based!(
     sha512_224_pre_image_resistance,
@@ -465,12 +728,64 @@ mod tests {
     Err(SHA256)
   );
   test_hash_based!(sha512_256_pre_image_resistance, Bsi, SHA512_256, Ok(SHA256));
-  test_hash_based!(shake128_pre_image_resistance, Bsi, SHAKE128, Err(SHA256));
-  test_hash_based!(shake256_pre_image_resistance, Bsi, SHAKE256, Err(SHA256));
+  test_hash_based!(shake128_pre_image_resistance, Bsi, SHAKE128, Err(SHA3_256));
+  test_hash_based!(shake256_pre_image_resistance, Bsi, SHAKE256, Err(SHA3_256));
 
   test_symmetric!(two_key_tdea, Bsi, TDEA2, Err(AES128));
   test_symmetric!(three_key_tdea, Bsi, TDEA3, Err(AES128));
   test_symmetric!(aes128, Bsi, AES128, Ok(AES128));
   test_symmetric!(aes192, Bsi, AES192, Ok(AES192));
   test_symmetric!(aes256, Bsi, AES256, Ok(AES256));
+
+  test_hash_brackets!(hash_brackets, Bsi);
+
+  test_pqc!(ml_dsa_44, Bsi, ML_DSA_44, Err(SLH_DSA_SHA2_192S));
+  test_pqc!(ml_dsa_87, Bsi, ML_DSA_87, Err(SLH_DSA_SHA2_256S));
+  test_pqc!(
+    slh_dsa_sha2_128s,
+    Bsi,
+    SLH_DSA_SHA2_128S,
+    Err(SLH_DSA_SHA2_192S)
+  );
+  test_pqc!(
+    slh_dsa_sha2_192s,
+    Bsi,
+    SLH_DSA_SHA2_192S,
+    Ok(SLH_DSA_SHA2_192S)
+  );
+  test_pqc!(
+    slh_dsa_shake_128f,
+    Bsi,
+    SLH_DSA_SHAKE_128F,
+    Err(SLH_DSA_SHAKE_192F)
+  );
+  test_pqc!(
+    slh_dsa_shake_256f,
+    Bsi,
+    SLH_DSA_SHAKE_256F,
+    Ok(SLH_DSA_SHAKE_256F)
+  );
+
+  test_stateful!(lms_sha256_192, Bsi, LMS_SHA256_192, Ok(LMS_SHA256_192));
+  test_stateful!(xmss_sha256_256, Bsi, XMSS_SHA256_256, Ok(XMSS_SHA256_256));
+  test_stateful!(xmss_sha512_512, Bsi, XMSS_SHA512_512, Err(XMSS_SHA256_256));
+
+  test_hybrid!(
+    ml_dsa_65_brainpoolp256r1,
+    Bsi,
+    Hybrid::new(ML_DSA_65, BRAINPOOLP256R1),
+    Ok(Hybrid::new(ML_DSA_65, BRAINPOOLP256R1))
+  );
+  test_hybrid!(
+    ml_dsa_44_brainpoolp256r1,
+    Bsi,
+    Hybrid::new(ML_DSA_44, BRAINPOOLP256R1),
+    Err(Hybrid::new(ML_DSA_65, BRAINPOOLP256R1))
+  );
+  test_hybrid!(
+    ml_dsa_65_ed25519,
+    Bsi,
+    Hybrid::new(ML_DSA_65, ED25519),
+    Err(Hybrid::new(ML_DSA_65, BRAINPOOLP256R1))
+  );
 }
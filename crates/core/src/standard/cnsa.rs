@@ -3,28 +3,137 @@
 //!
 //! [CNSA 1.0]: https://media.defense.gov/2021/Sep/27/2002862527/-1/-1/0/CNSS%20WORKSHEET.PDF
 //! [CNSA 2.0]: https://media.defense.gov/2022/Sep/07/2003071834/-1/-1/0/CSA_CNSA_2.0_ALGORITHMS_.PDF
-use std::collections::HashSet;
-
-use once_cell::sync::Lazy;
-
 use super::Standard;
 use crate::context::Context;
 use crate::primitive::ecc::*;
 use crate::primitive::ffc::*;
 use crate::primitive::hash::*;
 use crate::primitive::ifc::*;
+use crate::primitive::pqc::*;
+use crate::primitive::stateful::*;
 use crate::primitive::symmetric::*;
 use crate::primitive::Primitive;
+use crate::standard::rule::{Citation, Rule, Scope, Usage};
 
 // Exclusive use of CNSA 2.0 by then.
 const CUTOFF_YEAR: u16 = 2030;
 
-static SPECIFIED_HASH_FUNCTIONS: Lazy<HashSet<Hash>> = Lazy::new(|| {
-  let mut s = HashSet::new();
-  s.insert(SHA384);
-  s.insert(SHA512);
-  s
-});
+// The groups of RFC 3526 with a modulus of at least 3072 bits.
+static SPECIFIED_GROUPS: [Ffc; 4] = [MODP_3072, MODP_4096, MODP_6144, MODP_8192];
+
+static SPECIFIED_HASH_FUNCTIONS: [Hash; 2] = [SHA384, SHA512];
+
+const DOCUMENT: &str = "CNSA 1.0";
+const URL: &str = "https://media.defense.gov/2021/Sep/27/2002862527/-1/-1/0/CNSS%20WORKSHEET.PDF";
+const DOCUMENT_2: &str = "CNSA 2.0";
+const URL_2: &str =
+  "https://media.defense.gov/2022/Sep/07/2003071834/-1/-1/0/CSA_CNSA_2.0_ALGORITHMS_.PDF";
+
+static RULES: [Rule; 8] = [
+  Rule {
+    id: "CNSA-ECC-001",
+    scope: Scope::Ecc,
+    usage: None,
+    minimum: Some(192),
+    cutoff: Some(CUTOFF_YEAR),
+    summary: "ECDSA using curve P-384 only. Accepted until CNSA 2.0 is used exclusively after the cutoff year.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "Algorithm table",
+      url: Some(URL),
+    },
+  },
+  Rule {
+    id: "CNSA-FFC-001",
+    scope: Scope::Ffc,
+    usage: Some(Usage::Signature),
+    minimum: None,
+    cutoff: None,
+    summary: "DSA is not approved.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "Algorithm table",
+      url: Some(URL),
+    },
+  },
+  Rule {
+    id: "CNSA-DH-001",
+    scope: Scope::Ffc,
+    usage: Some(Usage::KeyAgreement),
+    minimum: Some(128),
+    cutoff: Some(CUTOFF_YEAR),
+    summary: "Diffie-Hellman key exchange using the groups of RFC 3526 with a modulus of at least 3072 bits. Accepted until CNSA 2.0 is used exclusively after the cutoff year.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "Algorithm table",
+      url: Some(URL),
+    },
+  },
+  Rule {
+    id: "CNSA-IFC-001",
+    scope: Scope::Ifc,
+    usage: None,
+    minimum: Some(128),
+    cutoff: Some(CUTOFF_YEAR),
+    summary: "RSA moduli of at least 3072 bits. Accepted until CNSA 2.0 is used exclusively after the cutoff year.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "Algorithm table",
+      url: Some(URL),
+    },
+  },
+  Rule {
+    id: "CNSA-HASH-001",
+    scope: Scope::Hash,
+    usage: None,
+    minimum: Some(192),
+    cutoff: None,
+    summary: "SHA-384 or SHA-512.",
+    citation: Citation {
+      document: DOCUMENT_2,
+      section: "Algorithm table",
+      url: Some(URL_2),
+    },
+  },
+  Rule {
+    id: "CNSA-SYM-001",
+    scope: Scope::Symmetric,
+    usage: None,
+    minimum: Some(256),
+    cutoff: None,
+    summary: "AES-256 only.",
+    citation: Citation {
+      document: DOCUMENT_2,
+      section: "Algorithm table",
+      url: Some(URL_2),
+    },
+  },  Rule {
+    id: "CNSA-PQC-001",
+    scope: Scope::Pqc,
+    usage: None,
+    minimum: Some(256),
+    cutoff: None,
+    summary: "ML-DSA-87 only. SLH-DSA is not approved.",
+    citation: Citation {
+      document: DOCUMENT_2,
+      section: "Algorithm table",
+      url: Some(URL_2),
+    },
+  },
+  Rule {
+    id: "CNSA-HBS-001",
+    scope: Scope::Stateful,
+    usage: None,
+    minimum: Some(192),
+    cutoff: None,
+    summary: "LMS and XMSS with any of the parameter sets approved by NIST SP 800-208, for signing software and firmware.",
+    citation: Citation {
+      document: DOCUMENT_2,
+      section: "Algorithm table",
+      url: Some(URL_2),
+    },
+  },
+];
 
 /// [`Standard`] implementation of the Commercial National Security
 /// Algorithm Suites, [CNSA 1.0] and [CNSA 2.0].
@@ -33,6 +142,44 @@ static SPECIFIED_HASH_FUNCTIONS: Lazy<HashSet<Hash>> = Lazy::new(|| {
 /// [CNSA 2.0]: https://media.defense.gov/2022/Sep/07/2003071834/-1/-1/0/CSA_CNSA_2.0_ALGORITHMS_.PDF
 pub struct Cnsa;
 
+impl Cnsa {
+  /// Validates a group used for Diffie-Hellman key exchange, which CNSA
+  /// 1.0 approves with the groups of RFC 3526 that have a modulus of at
+  /// least 3072 bits. CNSA 2.0 approves none.
+  ///
+  /// [`validate_ffc`](crate::standard::cnsa::Cnsa::validate_ffc) calls
+  /// this for every primitive that is a group used for key
+  /// establishment (see [`Ffc::is_key_agreement`]).
+  ///
+  /// If the group is not compliant then `Err` will contain the
+  /// recommended group that one should use instead.
+  ///
+  /// # Example
+  ///
+  /// The following illustrates a call to validate a non-compliant
+  /// group.
+  ///
+  /// ```
+  /// use wardstone_core::context::Context;
+  /// use wardstone_core::primitive::ffc::{MODP_2048, MODP_3072};
+  /// use wardstone_core::standard::cnsa::Cnsa;
+  ///
+  /// let ctx = Context::default();
+  /// assert_eq!(Cnsa::validate_dh(ctx, MODP_2048), Err(MODP_3072));
+  /// ```
+  pub fn validate_dh(ctx: Context, key: Ffc) -> Result<Ffc, Ffc> {
+    if ctx.year() > ctx.cutoff(Scope::Ffc, CUTOFF_YEAR) {
+      return Err(FFC_NOT_SUPPORTED);
+    }
+
+    if SPECIFIED_GROUPS.contains(&key) {
+      Ok(key)
+    } else {
+      Err(MODP_3072)
+    }
+  }
+}
+
 impl Standard for Cnsa {
   /// Validate an elliptic curve cryptography primitive used for digital
   /// signatures and key establishment.
@@ -58,7 +205,7 @@ impl Standard for Cnsa {
   /// assert_eq!(Cnsa::validate_ecc(ctx, P256), Err(P384));
   /// ```
   fn validate_ecc(ctx: Context, key: Ecc) -> Result<Ecc, Ecc> {
-    if ctx.year() > CUTOFF_YEAR {
+    if ctx.year() > ctx.cutoff(Scope::Ecc, CUTOFF_YEAR) {
       return Err(ECC_NOT_ALLOWED);
     }
 
@@ -74,8 +221,9 @@ impl Standard for Cnsa {
   /// Examples include the DSA and key establishment algorithms such as
   /// Diffie-Hellman and MQV which can also be implemented as such.
   ///
-  /// This primitive is not supported by either version of the CNSA
-  /// guidance.
+  /// DSA is not supported by either version of the CNSA guidance.
+  /// Groups used for key establishment are instead validated with
+  /// [`validate_dh`](crate::standard::cnsa::Cnsa::validate_dh).
   ///
   /// If the key is not compliant then `Err` will contain the
   /// recommended key sizes L and N that one should use instead.
@@ -98,7 +246,10 @@ impl Standard for Cnsa {
   /// let dsa_7680 = DSA_7680_384;
   /// assert_eq!(Cnsa::validate_ffc(ctx, dsa_7680), Err(FFC_NOT_SUPPORTED));
   /// ```
-  fn validate_ffc(_ctx: Context, _key: Ffc) -> Result<Ffc, Ffc> {
+  fn validate_ffc(ctx: Context, key: Ffc) -> Result<Ffc, Ffc> {
+    if key.is_key_agreement() {
+      return Self::validate_dh(ctx, key);
+    }
     Err(FFC_NOT_SUPPORTED)
   }
 
@@ -172,7 +323,7 @@ impl Standard for Cnsa {
   /// assert_eq!(Cnsa::validate_ifc(ctx, RSA_PSS_2048), Err(RSA_PSS_3072));
   /// ```
   fn validate_ifc(ctx: Context, key: Ifc) -> Result<Ifc, Ifc> {
-    if ctx.year() > CUTOFF_YEAR {
+    if ctx.year() > ctx.cutoff(Scope::Ifc, CUTOFF_YEAR) {
       return Err(IFC_NOT_ALLOWED);
     }
 
@@ -214,12 +365,75 @@ impl Standard for Cnsa {
       Ok(AES256)
     }
   }
+
+  /// Validates an ML-DSA or SLH-DSA signature primitive. CNSA 2.0
+  /// approves ML-DSA-87 alone.
+  ///
+  /// If the key is not compliant then `Err` will contain the
+  /// recommended parameter set that one should use instead.
+  ///
+  /// # Example
+  ///
+  /// The following illustrates a call to validate a non-compliant key.
+  ///
+  /// ```
+  /// use wardstone_core::context::Context;
+  /// use wardstone_core::primitive::pqc::{ML_DSA_65, ML_DSA_87};
+  /// use wardstone_core::standard::cnsa::Cnsa;
+  /// use wardstone_core::standard::Standard;
+  ///
+  /// let ctx = Context::default();
+  /// assert_eq!(Cnsa::validate_pqc(ctx, ML_DSA_65), Err(ML_DSA_87));
+  /// ```
+  fn validate_pqc(_ctx: Context, key: Pqc) -> Result<Pqc, Pqc> {
+    if key == ML_DSA_87 {
+      Ok(ML_DSA_87)
+    } else {
+      Err(ML_DSA_87)
+    }
+  }
+
+  /// Validates a stateful hash-based signature primitive. CNSA 2.0
+  /// approves every parameter set of SP 800-208 and recommends
+  /// SHA-256/192.
+  ///
+  /// If the key is not compliant then `Err` will contain the
+  /// recommended parameter set that one should use instead.
+  ///
+  /// If the key is compliant but the context specifies a higher
+  /// security level, `Ok` will also hold the parameter set of the same
+  /// scheme and hash function with the desired security level.
+  ///
+  /// # Example
+  ///
+  /// The following illustrates a call to validate a compliant key.
+  ///
+  /// ```
+  /// use wardstone_core::context::Context;
+  /// use wardstone_core::primitive::stateful::XMSS_SHA256_192;
+  /// use wardstone_core::standard::cnsa::Cnsa;
+  /// use wardstone_core::standard::Standard;
+  ///
+  /// let ctx = Context::default();
+  /// assert_eq!(Cnsa::validate_stateful(ctx, XMSS_SHA256_192), Ok(XMSS_SHA256_192));
+  /// ```
+  fn validate_stateful(ctx: Context, key: Stateful) -> Result<Stateful, Stateful> {
+    let security = ctx.security().max(key.security());
+    key.at_least(security).ok_or(LMS_SHA256_192)
+  }
+
+  fn rules() -> &'static [Rule] {
+    &RULES
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
-  use crate::{test_ecc, test_ffc, test_hash, test_ifc, test_symmetric};
+  use crate::primitive::hybrid::Hybrid;
+  use crate::{
+    test_ecc, test_ffc, test_hash, test_hybrid, test_ifc, test_pqc, test_stateful, test_symmetric,
+  };
 
   test_ecc!(p224, Cnsa, P224, Err(P384));
   test_ecc!(p256, Cnsa, P256, Err(P384));
@@ -227,6 +441,8 @@ mod tests {
   test_ecc!(p521, Cnsa, P521, Err(P384));
   test_ecc!(ed25519, Cnsa, ED25519, Err(P384));
   test_ecc!(ed448, Cnsa, ED448, Err(P384));
+  test_ecc!(ed25519ph, Cnsa, ED25519PH, Err(P384));
+  test_ecc!(ed448ph, Cnsa, ED448PH, Err(P384));
   test_ecc!(x25519, Cnsa, X25519, Err(P384));
   test_ecc!(x448, Cnsa, X448, Err(P384));
   test_ecc!(brainpoolp224r1, Cnsa, BRAINPOOLP224R1, Err(P384));
@@ -234,7 +450,9 @@ mod tests {
   test_ecc!(brainpoolp320r1, Cnsa, BRAINPOOLP320R1, Err(P384));
   test_ecc!(brainpoolp384r1, Cnsa, BRAINPOOLP384R1, Err(P384));
   test_ecc!(brainpoolp512r1, Cnsa, BRAINPOOLP512R1, Err(P384));
+  test_ecc!(bls12_381, Cnsa, BLS12_381, Err(P384));
   test_ecc!(secp256k1, Cnsa, SECP256K1, Err(P384));
+  test_ecc!(sm2, Cnsa, SM2, Err(P384));
 
   test_hash!(blake2b_256, Cnsa, BLAKE2B_256, Err(SHA384));
   test_hash!(blake2b_384, Cnsa, BLAKE2B_384, Err(SHA384));
@@ -263,6 +481,10 @@ mod tests {
   test_ffc!(ffc_7680_384, Cnsa, DSA_7680_384, Err(FFC_NOT_SUPPORTED));
   test_ffc!(ffc_15360_512, Cnsa, DSA_15360_512, Err(FFC_NOT_SUPPORTED));
 
+  test_ffc!(modp_2048, Cnsa, MODP_2048, Err(MODP_3072));
+  test_ffc!(modp_3072, Cnsa, MODP_3072, Ok(MODP_3072));
+  test_ffc!(ffdhe3072, Cnsa, FFDHE3072, Err(MODP_3072));
+
   test_ifc!(ifc_1024, Cnsa, RSA_PSS_1024, Err(RSA_PSS_3072));
   test_ifc!(ifc_2048, Cnsa, RSA_PSS_2048, Err(RSA_PSS_3072));
   test_ifc!(ifc_3072, Cnsa, RSA_PSS_3072, Ok(RSA_PSS_3072));
@@ -274,4 +496,46 @@ mod tests {
   test_symmetric!(aes128, Cnsa, AES128, Err(AES256));
   test_symmetric!(aes192, Cnsa, AES192, Err(AES256));
   test_symmetric!(aes256, Cnsa, AES256, Ok(AES256));
+
+  test_pqc!(ml_dsa_44, Cnsa, ML_DSA_44, Err(ML_DSA_87));
+  test_pqc!(ml_dsa_65, Cnsa, ML_DSA_65, Err(ML_DSA_87));
+  test_pqc!(ml_dsa_87, Cnsa, ML_DSA_87, Ok(ML_DSA_87));
+  test_pqc!(slh_dsa_sha2_256s, Cnsa, SLH_DSA_SHA2_256S, Err(ML_DSA_87));
+
+  test_stateful!(lms_sha256_192, Cnsa, LMS_SHA256_192, Ok(LMS_SHA256_192));
+  test_stateful!(lms_sha256_256, Cnsa, LMS_SHA256_256, Ok(LMS_SHA256_256));
+  test_stateful!(
+    xmss_shake256_192,
+    Cnsa,
+    XMSS_SHAKE256_192,
+    Ok(XMSS_SHAKE256_192)
+  );
+  test_stateful!(xmss_sha512_512, Cnsa, XMSS_SHA512_512, Err(LMS_SHA256_192));
+
+  test_hybrid!(
+    ml_dsa_87_p384,
+    Cnsa,
+    Hybrid::new(ML_DSA_87, P384),
+    Ok(Hybrid::new(ML_DSA_87, P384))
+  );
+  test_hybrid!(
+    ml_dsa_65_p256,
+    Cnsa,
+    Hybrid::new(ML_DSA_65, P256),
+    Err(Hybrid::new(ML_DSA_87, P384))
+  );
+
+  #[test]
+  fn hybrid_outlives_cnsa_1() {
+    use crate::context::Context;
+
+    let ctx = Context::new(128, 2031);
+    let key = Hybrid::new(ML_DSA_87, P384);
+    assert_eq!(Cnsa::validate_hybrid(ctx, key), Ok(key));
+    let key = Hybrid::new(ML_DSA_65, P384);
+    assert_eq!(
+      Cnsa::validate_hybrid(ctx, key),
+      Err(Hybrid::new(ML_DSA_87, ECC_NOT_ALLOWED))
+    );
+  }
 }
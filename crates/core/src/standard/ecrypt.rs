@@ -1,12 +1,12 @@
 //! Validate cryptographic primitives against the [ECRYPT-CSA D5.4
 //! Algorithms, Key Size and Protocols Report].
 //!
+//! The report predates the standardisation of post-quantum and stateful
+//! hash-based signatures and approves neither, so they are rejected on
+//! their own and a hybrid signature stands on its elliptic curve.
+//!
 //! [ECRYPT-CSA D5.4 Algorithms, Key Size and Protocols Report]: https://www.ecrypt.eu.org/csa/documents/D5.4-FinalAlgKeySizeProt.pdf
-use std::collections::HashSet;
-
-use once_cell::sync::Lazy;
-
-use super::Standard;
+use super::{utilities, Standard};
 use crate::context::Context;
 use crate::primitive::ecc::*;
 use crate::primitive::ffc::*;
@@ -14,6 +14,7 @@ use crate::primitive::hash::*;
 use crate::primitive::ifc::*;
 use crate::primitive::symmetric::*;
 use crate::primitive::Primitive;
+use crate::standard::rule::{Citation, Rule, Scope};
 
 // "Thus the key take home message is that decision makers now make
 // plans and preparations for the phasing out of what we term legacy
@@ -22,48 +23,115 @@ use crate::primitive::Primitive;
 // categories of legacy algorithms.
 const CUTOFF_YEAR: u16 = 2023;
 
-static SPECIFIED_HASH_FUNCTIONS: Lazy<HashSet<Hash>> = Lazy::new(|| {
-  let mut s = HashSet::new();
-  s.insert(BLAKE2B_256);
-  s.insert(BLAKE2B_384);
-  s.insert(BLAKE2B_512);
-  s.insert(BLAKE2S_256);
-  s.insert(BLAKE_224);
-  s.insert(BLAKE_256);
-  s.insert(BLAKE_384);
-  s.insert(BLAKE_512);
-  s.insert(RIPEMD160);
-  s.insert(SHA224);
-  s.insert(SHA256);
-  s.insert(SHA384);
-  s.insert(SHA3_224);
-  s.insert(SHA3_256);
-  s.insert(SHA3_384);
-  s.insert(SHA3_512);
-  s.insert(SHA512);
-  s.insert(SHA512_224);
-  s.insert(SHA512_256);
-  s.insert(SHAKE128);
-  s.insert(SHAKE256);
-  s.insert(WHIRLPOOL);
-  s
-});
+static SPECIFIED_HASH_FUNCTIONS: [Hash; 22] = [
+  BLAKE2B_256,
+  BLAKE2B_384,
+  BLAKE2B_512,
+  BLAKE2S_256,
+  BLAKE_224,
+  BLAKE_256,
+  BLAKE_384,
+  BLAKE_512,
+  RIPEMD160,
+  SHA224,
+  SHA256,
+  SHA384,
+  SHA3_224,
+  SHA3_256,
+  SHA3_384,
+  SHA3_512,
+  SHA512,
+  SHA512_224,
+  SHA512_256,
+  SHAKE128,
+  SHAKE256,
+  WHIRLPOOL,
+];
+
+static SPECIFIED_SYMMETRIC_KEYS: [Symmetric; 11] = [
+  AES128,
+  AES192,
+  AES256,
+  CAMELLIA128,
+  CAMELLIA192,
+  CAMELLIA256,
+  SERPENT128,
+  SERPENT192,
+  SERPENT256,
+  TDEA2,
+  TDEA3,
+];
 
-static SPECIFIED_SYMMETRIC_KEYS: Lazy<HashSet<Symmetric>> = Lazy::new(|| {
-  let mut s = HashSet::new();
-  s.insert(AES128);
-  s.insert(AES192);
-  s.insert(AES256);
-  s.insert(CAMELLIA128);
-  s.insert(CAMELLIA192);
-  s.insert(CAMELLIA256);
-  s.insert(SERPENT128);
-  s.insert(SERPENT192);
-  s.insert(SERPENT256);
-  s.insert(TDEA2);
-  s.insert(TDEA3);
-  s
-});
+const DOCUMENT: &str = "ECRYPT-CSA D5.4";
+const URL: &str = "https://www.ecrypt.eu.org/csa/documents/D5.4-FinalAlgKeySizeProt.pdf";
+
+static RULES: [Rule; 5] = [
+  Rule {
+    id: "ECRYPT-ECC-001",
+    scope: Scope::Ecc,
+    usage: None,
+    minimum: Some(80),
+    cutoff: Some(CUTOFF_YEAR),
+    summary: "Curves providing at least 128 bits of security. Legacy curves providing at least 80 bits of security are accepted until the cutoff year.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "p. 47",
+      url: Some(URL),
+    },
+  },
+  Rule {
+    id: "ECRYPT-FFC-001",
+    scope: Scope::Ffc,
+    usage: None,
+    minimum: Some(80),
+    cutoff: Some(CUTOFF_YEAR),
+    summary: "DSA domain parameters and Diffie-Hellman groups providing at least 128 bits of security. Legacy parameters providing at least 80 bits of security are accepted until the cutoff year.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "p. 47",
+      url: Some(URL),
+    },
+  },
+  Rule {
+    id: "ECRYPT-IFC-001",
+    scope: Scope::Ifc,
+    usage: None,
+    minimum: Some(80),
+    cutoff: Some(CUTOFF_YEAR),
+    summary: "RSA moduli providing at least 128 bits of security. Legacy moduli providing at least 80 bits of security are accepted until the cutoff year.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "pp. 47-48",
+      url: Some(URL),
+    },
+  },
+  Rule {
+    id: "ECRYPT-HASH-001",
+    scope: Scope::Hash,
+    usage: None,
+    minimum: Some(80),
+    cutoff: Some(CUTOFF_YEAR),
+    summary: "Hash functions providing at least 128 bits of collision resistance. Legacy hash functions providing at least 80 bits are accepted until the cutoff year.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "pp. 40-43",
+      url: Some(URL),
+    },
+  },
+  Rule {
+    id: "ECRYPT-SYM-001",
+    scope: Scope::Symmetric,
+    usage: None,
+    minimum: Some(80),
+    cutoff: Some(CUTOFF_YEAR),
+    summary: "Block ciphers providing at least 128 bits of security. Legacy ciphers providing at least 80 bits of security are accepted until the cutoff year.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "pp. 37-40",
+      url: Some(URL),
+    },
+  },
+];
 
 /// [`Standard`] implementation for the
 /// [ECRYPT-CSA D5.4 Algorithms, Key Size and Protocols Report].
@@ -104,7 +172,7 @@ impl Standard for Ecrypt {
     match security {
       ..=79 => Err(ECC_256),
       80..=127 => {
-        if ctx.year() > CUTOFF_YEAR {
+        if ctx.year() > ctx.cutoff(Scope::Ecc, CUTOFF_YEAR) {
           Err(ECC_256)
         } else {
           Ok(ECC_256)
@@ -124,7 +192,9 @@ impl Standard for Ecrypt {
   /// according to page 47 of the report.
   ///
   /// If the key is not compliant then `Err` will contain the
-  /// recommended key sizes L and N that one should use instead.
+  /// recommended key sizes L and N that one should use instead, or the
+  /// recommended group if the key is a group used for key
+  /// establishment.
   ///
   /// If the key is compliant but the context specifies a higher
   /// security level, `Ok` will also hold the recommended key sizes L
@@ -147,10 +217,10 @@ impl Standard for Ecrypt {
   /// ```
   fn validate_ffc(ctx: Context, key: Ffc) -> Result<Ffc, Ffc> {
     let security = ctx.security().max(key.security());
-    match security {
+    let verdict = match security {
       ..=79 => Err(DSA_3072_256),
       80..=127 => {
-        if ctx.year() > CUTOFF_YEAR {
+        if ctx.year() > ctx.cutoff(Scope::Ffc, CUTOFF_YEAR) {
           Err(DSA_3072_256)
         } else {
           Ok(DSA_3072_256)
@@ -159,7 +229,9 @@ impl Standard for Ecrypt {
       128 => Ok(DSA_3072_256),
       129..=192 => Ok(DSA_7680_384),
       193.. => Ok(DSA_15360_512),
-    }
+    };
+    let verdict = utilities::prefer_group(key, verdict);
+    utilities::reject_inconsistent(key, verdict)
   }
 
   /// Validates a hash function according to pages 40-43 of the report.
@@ -171,12 +243,10 @@ impl Standard for Ecrypt {
   /// higher security level, `Ok` will also hold the recommended
   /// primitive with the desired security level.
   ///
-  /// **Note:** An alternative might be suggested for a compliant hash
-  /// function with a similar security level in which a switch to the
-  /// recommended primitive would likely be unwarranted. For example,
-  /// when evaluating compliance for the `SHA3-256`, a recommendation
-  /// to use `SHA256` will be made but switching to this as a result
-  /// is likely unnecessary.
+  /// When the hash function belongs to the SHA-3 family, the
+  /// recommendation is the SHA-3 function with the same digest length
+  /// rather than its SHA-2 counterpart, so that a compliant `SHA3-256`
+  /// is not met with advice to switch to `SHA256`.
   ///
   /// # Example
   ///
@@ -193,12 +263,12 @@ impl Standard for Ecrypt {
   /// assert_eq!(Ecrypt::validate_hash(ctx, SHA1), Err(SHA256));
   /// ```
   fn validate_hash(ctx: Context, hash: Hash) -> Result<Hash, Hash> {
-    if SPECIFIED_HASH_FUNCTIONS.contains(&hash) {
+    let verdict = if SPECIFIED_HASH_FUNCTIONS.contains(&hash) {
       let security = ctx.security().max(hash.security());
       match security {
         ..=79 => Err(SHA256),
         80..=127 => {
-          if ctx.year() > CUTOFF_YEAR {
+          if ctx.year() > ctx.cutoff(Scope::Hash, CUTOFF_YEAR) {
             Err(SHA256)
           } else {
             Ok(SHA256)
@@ -210,7 +280,8 @@ impl Standard for Ecrypt {
       }
     } else {
       Err(SHA256)
-    }
+    };
+    utilities::prefer_sha3(hash, verdict, &SPECIFIED_HASH_FUNCTIONS)
   }
 
   /// Validates  an integer factorisation cryptography primitive the
@@ -246,7 +317,7 @@ impl Standard for Ecrypt {
     match security {
       ..=79 => Err(RSA_PSS_3072),
       80..=127 => {
-        if ctx.year() > CUTOFF_YEAR {
+        if ctx.year() > ctx.cutoff(Scope::Ifc, CUTOFF_YEAR) {
           Err(RSA_PSS_3072)
         } else {
           Ok(RSA_PSS_3072)
@@ -287,7 +358,7 @@ impl Standard for Ecrypt {
       match security {
         ..=79 => Err(AES128),
         80..=127 => {
-          if ctx.year() > CUTOFF_YEAR {
+          if ctx.year() > ctx.cutoff(Scope::Symmetric, CUTOFF_YEAR) {
             Err(AES128)
           } else {
             Ok(AES128)
@@ -301,12 +372,21 @@ impl Standard for Ecrypt {
       Err(AES128)
     }
   }
+
+  fn rules() -> &'static [Rule] {
+    &RULES
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
-  use crate::{test_ecc, test_ffc, test_hash, test_ifc, test_symmetric};
+  use crate::primitive::hybrid::Hybrid;
+  use crate::primitive::pqc::*;
+  use crate::primitive::stateful::*;
+  use crate::{
+    test_ecc, test_ffc, test_hash, test_hybrid, test_ifc, test_pqc, test_stateful, test_symmetric,
+  };
 
   test_ecc!(p224, Ecrypt, P224, Ok(ECC_256));
   test_ecc!(p256, Ecrypt, P256, Ok(ECC_256));
@@ -314,6 +394,8 @@ mod tests {
   test_ecc!(p521, Ecrypt, P521, Ok(ECC_512));
   test_ecc!(ed25519, Ecrypt, ED25519, Ok(ECC_256));
   test_ecc!(ed448, Ecrypt, ED448, Ok(ECC_512));
+  test_ecc!(ed25519ph, Ecrypt, ED25519PH, Ok(ECC_256));
+  test_ecc!(ed448ph, Ecrypt, ED448PH, Ok(ECC_512));
   test_ecc!(x25519, Ecrypt, X25519, Ok(ECC_256));
   test_ecc!(x448, Ecrypt, X448, Ok(ECC_512));
   test_ecc!(brainpoolp224r1, Ecrypt, BRAINPOOLP224R1, Ok(ECC_256));
@@ -321,13 +403,27 @@ mod tests {
   test_ecc!(brainpoolp320r1, Ecrypt, BRAINPOOLP320R1, Ok(ECC_384));
   test_ecc!(brainpoolp384r1, Ecrypt, BRAINPOOLP384R1, Ok(ECC_384));
   test_ecc!(brainpoolp512r1, Ecrypt, BRAINPOOLP512R1, Ok(ECC_512));
+  test_ecc!(bls12_381, Ecrypt, BLS12_381, Ok(ECC_256));
   test_ecc!(secp256k1, Ecrypt, SECP256K1, Ok(ECC_256));
+  test_ecc!(sm2, Ecrypt, SM2, Ok(ECC_256));
 
   test_ffc!(ffc_1024_160, Ecrypt, DSA_1024_160, Ok(DSA_3072_256));
   test_ffc!(ffc_2048_224, Ecrypt, DSA_2048_224, Ok(DSA_3072_256));
   test_ffc!(ffc_3072_256, Ecrypt, DSA_3072_256, Ok(DSA_3072_256));
   test_ffc!(ffc_7680_384, Ecrypt, DSA_7680_384, Ok(DSA_7680_384));
   test_ffc!(ffc_15360_512, Ecrypt, DSA_15360_512, Ok(DSA_15360_512));
+  test_ffc!(
+    ffc_3072_224,
+    Ecrypt,
+    Ffc::new(ID_DSA, 3072, 224),
+    Err(DSA_3072_256)
+  );
+  test_ffc!(
+    ffc_1024_256,
+    Ecrypt,
+    Ffc::new(ID_DSA, 1024, 256),
+    Ok(DSA_3072_256)
+  );
 
   test_hash!(blake_224, Ecrypt, BLAKE_224, Ok(SHA256));
   test_hash!(blake_256, Ecrypt, BLAKE_256, Ok(SHA256));
@@ -344,17 +440,20 @@ mod tests {
   test_hash!(sha224, Ecrypt, SHA224, Ok(SHA256));
   test_hash!(sha256, Ecrypt, SHA256, Ok(SHA256));
   test_hash!(sha384, Ecrypt, SHA384, Ok(SHA384));
-  test_hash!(sha3_224, Ecrypt, SHA3_224, Ok(SHA256));
-  test_hash!(sha3_256, Ecrypt, SHA3_256, Ok(SHA256));
-  test_hash!(sha3_384, Ecrypt, SHA3_384, Ok(SHA384));
-  test_hash!(sha3_512, Ecrypt, SHA3_512, Ok(SHA512));
+  test_hash!(sha3_224, Ecrypt, SHA3_224, Ok(SHA3_256));
+  test_hash!(sha3_256, Ecrypt, SHA3_256, Ok(SHA3_256));
+  test_hash!(sha3_384, Ecrypt, SHA3_384, Ok(SHA3_384));
+  test_hash!(sha3_512, Ecrypt, SHA3_512, Ok(SHA3_512));
   test_hash!(sha512, Ecrypt, SHA512, Ok(SHA512));
   test_hash!(sha512_224, Ecrypt, SHA512_224, Ok(SHA256));
   test_hash!(sha512_256, Ecrypt, SHA512_256, Ok(SHA256));
-  test_hash!(shake128, Ecrypt, SHAKE128, Err(SHA256));
-  test_hash!(shake256, Ecrypt, SHAKE256, Ok(SHA256));
+  test_hash!(shake128, Ecrypt, SHAKE128, Ok(SHA3_256));
+  test_hash!(shake256, Ecrypt, SHAKE256, Ok(SHA3_512));
   test_hash!(whirlpool, Ecrypt, WHIRLPOOL, Ok(SHA512));
 
+  test_ffc!(modp_1024, Ecrypt, MODP_1024, Ok(FFDHE3072));
+  test_ffc!(ffdhe4096, Ecrypt, FFDHE4096, Ok(FFDHE3072));
+
   test_ifc!(ifc_1024, Ecrypt, RSA_PSS_1024, Ok(RSA_PSS_3072));
   test_ifc!(ifc_2048, Ecrypt, RSA_PSS_2048, Ok(RSA_PSS_3072));
   test_ifc!(ifc_3072, Ecrypt, RSA_PSS_3072, Ok(RSA_PSS_3072));
@@ -372,4 +471,32 @@ mod tests {
   test_symmetric!(serpent256, Ecrypt, SERPENT256, Ok(AES256));
   test_symmetric!(three_key_tdea, Ecrypt, TDEA3, Ok(AES128));
   test_symmetric!(two_key_tdea, Ecrypt, TDEA2, Ok(AES128));
+
+  test_pqc!(ml_dsa_65, Ecrypt, ML_DSA_65, Err(PQC_NOT_ALLOWED));
+  test_pqc!(
+    slh_dsa_sha2_128s,
+    Ecrypt,
+    SLH_DSA_SHA2_128S,
+    Err(PQC_NOT_ALLOWED)
+  );
+
+  test_stateful!(
+    lms_sha256_192,
+    Ecrypt,
+    LMS_SHA256_192,
+    Err(STATEFUL_NOT_ALLOWED)
+  );
+  test_stateful!(
+    xmss_sha256_256,
+    Ecrypt,
+    XMSS_SHA256_256,
+    Err(STATEFUL_NOT_ALLOWED)
+  );
+
+  test_hybrid!(
+    ml_dsa_65_p256,
+    Ecrypt,
+    Hybrid::new(ML_DSA_65, P256),
+    Ok(Hybrid::new(ML_DSA_65, ECC_256))
+  );
 }
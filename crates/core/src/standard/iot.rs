@@ -0,0 +1,402 @@
+//! Validate cryptographic primitives used by constrained devices such as
+//! sensors, meters and other embedded systems, following [BSI
+//! TR-03116-3] and the device cybersecurity baseline of [NIST IR 8259A].
+//!
+//! The profile keeps the 128-bit security target of general purpose
+//! guidance but favours what small devices can afford: elliptic curves
+//! over RSA and finite field cryptography, and AES in an authenticated
+//! mode. AES-CCM with an 8-byte tag, as mandated by the TLS and DTLS
+//! profiles for the Internet of Things, is accepted as AES-128 and its
+//! shorter tag is raised as an advisory (see
+//! [`Hazard::ShortTag`](crate::advisory::Hazard::ShortTag)).
+//!
+//! Neither document approves post-quantum or stateful hash-based
+//! signatures, whose keys and signatures are also beyond what most
+//! constrained devices can afford, so they are rejected on their own and
+//! a hybrid signature stands on its elliptic curve.
+//!
+//! [BSI TR-03116-3]: https://www.bsi.bund.de/SharedDocs/Downloads/DE/BSI/Publikationen/TechnischeRichtlinien/TR03116/BSI-TR-03116-3.html
+//! [NIST IR 8259A]: https://csrc.nist.gov/pubs/ir/8259/a/final
+use crate::context::Context;
+use crate::primitive::ecc::*;
+use crate::primitive::ffc::*;
+use crate::primitive::hash::*;
+use crate::primitive::ifc::*;
+use crate::primitive::symmetric::*;
+use crate::primitive::Primitive;
+use crate::standard::rule::{Citation, Rule, Scope};
+use crate::standard::utilities::{self, Application};
+use crate::standard::Standard;
+
+// The Brainpool and NIST curves of the guideline along with the curves
+// of RFC 7748 and RFC 8032, which SP 800-186 approves and which are
+// what most constrained stacks implement.
+static SPECIFIED_CURVES: [Ecc; 11] = [
+  P256,
+  P384,
+  P521,
+  BRAINPOOLP256R1,
+  BRAINPOOLP320R1,
+  BRAINPOOLP384R1,
+  BRAINPOOLP512R1,
+  ED25519,
+  ED448,
+  X25519,
+  X448,
+];
+
+static SPECIFIED_HASH_FUNCTIONS: [Hash; 7] = [
+  SHA256, SHA384, SHA3_256, SHA3_384, SHA3_512, SHA512, SHA512_256,
+];
+
+static RECOMMENDED_HASH_FUNCTIONS: [(Hash, Application); 3] = [
+  (SHA256, Application::Collision),
+  (SHA384, Application::Collision),
+  (SHA512, Application::Collision),
+];
+
+static SPECIFIED_SYMMETRIC_KEYS: [Symmetric; 3] = [AES128, AES192, AES256];
+
+const DOCUMENT: &str = "BSI TR-03116-3";
+const URL: &str = "https://www.bsi.bund.de/SharedDocs/Downloads/DE/BSI/Publikationen/TechnischeRichtlinien/TR03116/BSI-TR-03116-3.html";
+const DOCUMENT_IR: &str = "NIST IR 8259A";
+const URL_IR: &str = "https://csrc.nist.gov/pubs/ir/8259/a/final";
+const DOCUMENT_RFC: &str = "RFC 7925";
+const URL_RFC: &str = "https://datatracker.ietf.org/doc/html/rfc7925#section-4";
+
+static RULES: [Rule; 5] = [
+  Rule {
+    id: "IOT-ECC-001",
+    scope: Scope::Ecc,
+    usage: None,
+    minimum: Some(128),
+    cutoff: None,
+    summary: "Brainpool and NIST P curves of at least 256 bits, or Curve25519 and Curve448. Elliptic curves are preferred on constrained devices for their short keys and signatures.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "Elliptic curves",
+      url: Some(URL),
+    },
+  },
+  Rule {
+    id: "IOT-FFC-001",
+    scope: Scope::Ffc,
+    usage: None,
+    minimum: None,
+    cutoff: None,
+    summary: "DSA and finite field Diffie-Hellman are not accepted since the keys they need for 128-bit security are too costly for constrained devices. Use elliptic curves instead.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "Elliptic curves",
+      url: Some(URL),
+    },
+  },
+  Rule {
+    id: "IOT-IFC-001",
+    scope: Scope::Ifc,
+    usage: None,
+    minimum: Some(128),
+    cutoff: None,
+    summary: "RSA moduli of at least 3072 bits to match the 128-bit security of the profile, although elliptic curves are preferred.",
+    citation: Citation {
+      document: DOCUMENT_IR,
+      section: "Data Protection",
+      url: Some(URL_IR),
+    },
+  },
+  Rule {
+    id: "IOT-HASH-001",
+    scope: Scope::Hash,
+    usage: None,
+    minimum: Some(128),
+    cutoff: None,
+    summary: "SHA-2 and SHA-3 hash functions with a digest of at least 256 bits.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "Hash functions",
+      url: Some(URL),
+    },
+  },
+  Rule {
+    id: "IOT-SYM-001",
+    scope: Scope::Symmetric,
+    usage: None,
+    minimum: Some(128),
+    cutoff: None,
+    summary: "AES with a key of at least 128 bits in an authenticated mode such as CCM or GCM. CCM with an 8-byte tag is accepted for constrained links and raised as an advisory.",
+    citation: Citation {
+      document: DOCUMENT_RFC,
+      section: "§4",
+      url: Some(URL_RFC),
+    },
+  },
+];
+
+/// [`Standard`] implementation of a profile for constrained devices
+/// after [BSI TR-03116-3] and [NIST IR 8259A].
+///
+/// [BSI TR-03116-3]: https://www.bsi.bund.de/SharedDocs/Downloads/DE/BSI/Publikationen/TechnischeRichtlinien/TR03116/BSI-TR-03116-3.html
+/// [NIST IR 8259A]: https://csrc.nist.gov/pubs/ir/8259/a/final
+pub struct Iot;
+
+impl Standard for Iot {
+  /// Validate an elliptic curve cryptography primitive used for digital
+  /// signatures and key establishment.
+  ///
+  /// If the key is not compliant then `Err` will contain the
+  /// recommended primitive that one should use instead.
+  ///
+  /// If the key is compliant but the context specifies a higher
+  /// security level, `Ok` will also hold the recommended primitive
+  /// with the desired security level.
+  ///
+  /// # Example
+  ///
+  /// The following illustrates a call to validate a compliant key.
+  ///
+  /// ```
+  /// use wardstone_core::context::Context;
+  /// use wardstone_core::primitive::ecc::{P256, X25519};
+  /// use wardstone_core::standard::iot::Iot;
+  /// use wardstone_core::standard::Standard;
+  ///
+  /// let ctx = Context::default();
+  /// assert_eq!(Iot::validate_ecc(ctx, X25519), Ok(P256));
+  /// ```
+  fn validate_ecc(ctx: Context, key: Ecc) -> Result<Ecc, Ecc> {
+    if SPECIFIED_CURVES.contains(&key) {
+      let security = ctx.security().max(key.security());
+      match security {
+        ..=127 => Err(P256),
+        128 => Ok(P256),
+        129..=192 => Ok(P384),
+        193.. => Ok(P521),
+      }
+    } else {
+      Err(P256)
+    }
+  }
+
+  /// Validates a finite field cryptography primitive.
+  ///
+  /// Examples include the DSA and key establishment algorithms such as
+  /// Diffie-Hellman. Neither is accepted by the profile, which uses
+  /// elliptic curves for both.
+  ///
+  /// # Example
+  ///
+  /// The following illustrates a call to validate a key that would be
+  /// compliant for general purpose use.
+  ///
+  /// ```
+  /// use wardstone_core::context::Context;
+  /// use wardstone_core::primitive::ffc::{DSA_3072_256, FFC_NOT_SUPPORTED};
+  /// use wardstone_core::standard::iot::Iot;
+  /// use wardstone_core::standard::Standard;
+  ///
+  /// let ctx = Context::default();
+  /// assert_eq!(Iot::validate_ffc(ctx, DSA_3072_256), Err(FFC_NOT_SUPPORTED));
+  /// ```
+  fn validate_ffc(_ctx: Context, _key: Ffc) -> Result<Ffc, Ffc> {
+    Err(FFC_NOT_SUPPORTED)
+  }
+
+  /// Validates a hash function. The reference is made with regards to
+  /// applications that require collision resistance such as digital
+  /// signatures.
+  ///
+  /// If the hash function is not compliant then `Err` will contain the
+  /// recommended primitive that one should use instead.
+  ///
+  /// If the hash function is compliant but the context specifies a
+  /// higher security level, `Ok` will also hold the recommended
+  /// primitive with the desired security level.
+  ///
+  /// # Example
+  ///
+  /// The following illustrates a call to validate a non-compliant hash
+  /// function.
+  ///
+  /// ```
+  /// use wardstone_core::context::Context;
+  /// use wardstone_core::primitive::hash::{SHA1, SHA256};
+  /// use wardstone_core::standard::iot::Iot;
+  /// use wardstone_core::standard::Standard;
+  ///
+  /// let ctx = Context::default();
+  /// assert_eq!(Iot::validate_hash(ctx, SHA1), Err(SHA256));
+  /// ```
+  fn validate_hash(ctx: Context, hash: Hash) -> Result<Hash, Hash> {
+    let verdict = if SPECIFIED_HASH_FUNCTIONS.contains(&hash) {
+      let minimum = 128;
+      let application = Application::Collision;
+      let security = ctx.security().max(application.strength(hash));
+      let recommended = utilities::recommend_hash(
+        &RECOMMENDED_HASH_FUNCTIONS,
+        application,
+        security.max(minimum),
+      );
+      if security < minimum {
+        Err(recommended)
+      } else {
+        Ok(recommended)
+      }
+    } else {
+      Err(SHA256)
+    };
+    utilities::prefer_sha3(hash, verdict, &SPECIFIED_HASH_FUNCTIONS)
+  }
+
+  /// Validates an integer factorisation cryptography primitive the most
+  /// common of which is the RSA signature algorithm.
+  ///
+  /// If the key is not compliant then `Err` will contain the
+  /// recommended key size that one should use instead.
+  ///
+  /// If the key is compliant but the context specifies a higher
+  /// security level, `Ok` will also hold the recommended key size
+  /// with the desired security level.
+  ///
+  /// # Example
+  ///
+  /// The following illustrates a call to validate a non-compliant key.
+  ///
+  /// ```
+  /// use wardstone_core::context::Context;
+  /// use wardstone_core::primitive::ifc::{RSA_PSS_2048, RSA_PSS_3072};
+  /// use wardstone_core::standard::iot::Iot;
+  /// use wardstone_core::standard::Standard;
+  ///
+  /// let ctx = Context::default();
+  /// assert_eq!(Iot::validate_ifc(ctx, RSA_PSS_2048), Err(RSA_PSS_3072));
+  /// ```
+  fn validate_ifc(ctx: Context, key: Ifc) -> Result<Ifc, Ifc> {
+    let security = ctx.security().max(key.security());
+    match security {
+      ..=127 => Err(RSA_PSS_3072),
+      128..=191 => Ok(RSA_PSS_3072),
+      192..=255 => Ok(RSA_PSS_7680),
+      256.. => Ok(RSA_PSS_15360),
+    }
+  }
+
+  /// Validates a symmetric key primitive.
+  ///
+  /// The tag length of an authenticated mode is not part of the
+  /// primitive, so AES-128 in CCM with an 8-byte tag is accepted here
+  /// like any other use of AES-128.
+  ///
+  /// If the key is not compliant then `Err` will contain the
+  /// recommended primitive that one should use instead.
+  ///
+  /// If the key is compliant but the context specifies a higher
+  /// security level, `Ok` will also hold the recommended primitive
+  /// with the desired security level.
+  ///
+  /// # Example
+  ///
+  /// The following illustrates a call to validate a non-compliant key.
+  ///
+  /// ```
+  /// use wardstone_core::context::Context;
+  /// use wardstone_core::primitive::symmetric::{AES128, TDEA3};
+  /// use wardstone_core::standard::iot::Iot;
+  /// use wardstone_core::standard::Standard;
+  ///
+  /// let ctx = Context::default();
+  /// assert_eq!(Iot::validate_symmetric(ctx, TDEA3), Err(AES128));
+  /// ```
+  fn validate_symmetric(ctx: Context, key: Symmetric) -> Result<Symmetric, Symmetric> {
+    if SPECIFIED_SYMMETRIC_KEYS.contains(&key) {
+      let security = ctx.security().max(key.security());
+      match security {
+        ..=127 => Err(AES128),
+        128 => Ok(AES128),
+        129..=192 => Ok(AES192),
+        193.. => Ok(AES256),
+      }
+    } else {
+      Err(AES128)
+    }
+  }
+
+  fn rules() -> &'static [Rule] {
+    &RULES
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::primitive::hybrid::Hybrid;
+  use crate::primitive::pqc::*;
+  use crate::primitive::stateful::*;
+  use crate::{
+    test_ecc, test_ffc, test_hash, test_hybrid, test_ifc, test_pqc, test_stateful, test_symmetric,
+  };
+
+  test_ecc!(p224, Iot, P224, Err(P256));
+  test_ecc!(p256, Iot, P256, Ok(P256));
+  test_ecc!(p384, Iot, P384, Ok(P384));
+  test_ecc!(p521, Iot, P521, Ok(P521));
+  test_ecc!(ed25519, Iot, ED25519, Ok(P256));
+  test_ecc!(ed448, Iot, ED448, Ok(P521));
+  test_ecc!(ed25519ph, Iot, ED25519PH, Err(P256));
+  test_ecc!(x25519, Iot, X25519, Ok(P256));
+  test_ecc!(x448, Iot, X448, Ok(P521));
+  test_ecc!(brainpoolp224r1, Iot, BRAINPOOLP224R1, Err(P256));
+  test_ecc!(brainpoolp256r1, Iot, BRAINPOOLP256R1, Ok(P256));
+  test_ecc!(brainpoolp512r1, Iot, BRAINPOOLP512R1, Ok(P521));
+  test_ecc!(bls12_381, Iot, BLS12_381, Err(P256));
+  test_ecc!(secp256k1, Iot, SECP256K1, Err(P256));
+  test_ecc!(sm2, Iot, SM2, Err(P256));
+
+  test_hash!(md5, Iot, MD5, Err(SHA256));
+  test_hash!(sha1, Iot, SHA1, Err(SHA256));
+  test_hash!(sha224, Iot, SHA224, Err(SHA256));
+  test_hash!(sha256, Iot, SHA256, Ok(SHA256));
+  test_hash!(sha384, Iot, SHA384, Ok(SHA384));
+  test_hash!(sha3_256, Iot, SHA3_256, Ok(SHA3_256));
+  test_hash!(sha512_256, Iot, SHA512_256, Ok(SHA256));
+  test_hash!(blake2s_256, Iot, BLAKE2S_256, Err(SHA256));
+
+  test_ffc!(ffc_3072_256, Iot, DSA_3072_256, Err(FFC_NOT_SUPPORTED));
+  test_ffc!(ffdhe3072, Iot, FFDHE3072, Err(FFC_NOT_SUPPORTED));
+
+  test_ifc!(ifc_2048, Iot, RSA_PSS_2048, Err(RSA_PSS_3072));
+  test_ifc!(ifc_3072, Iot, RSA_PSS_3072, Ok(RSA_PSS_3072));
+  test_ifc!(ifc_7680, Iot, RSA_PSS_7680, Ok(RSA_PSS_7680));
+
+  test_symmetric!(three_key_tdea, Iot, TDEA3, Err(AES128));
+  test_symmetric!(camellia128, Iot, CAMELLIA128, Err(AES128));
+  test_symmetric!(aes128, Iot, AES128, Ok(AES128));
+  test_symmetric!(aes256, Iot, AES256, Ok(AES256));
+
+  test_pqc!(ml_dsa_65, Iot, ML_DSA_65, Err(PQC_NOT_ALLOWED));
+  test_pqc!(
+    slh_dsa_sha2_128s,
+    Iot,
+    SLH_DSA_SHA2_128S,
+    Err(PQC_NOT_ALLOWED)
+  );
+
+  test_stateful!(
+    lms_sha256_192,
+    Iot,
+    LMS_SHA256_192,
+    Err(STATEFUL_NOT_ALLOWED)
+  );
+  test_stateful!(
+    xmss_sha256_256,
+    Iot,
+    XMSS_SHA256_256,
+    Err(STATEFUL_NOT_ALLOWED)
+  );
+
+  test_hybrid!(
+    ml_dsa_65_p256,
+    Iot,
+    Hybrid::new(ML_DSA_65, P256),
+    Ok(Hybrid::new(ML_DSA_65, P256))
+  );
+}
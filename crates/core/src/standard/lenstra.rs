@@ -1,49 +1,131 @@
 //! Validate cryptographic primitives against the levels of security
 //! mentioned in the paper Key Lengths, Arjen K. Lenstra, The Handbook
 //! of Information Security, 06/2004.
-use std::collections::HashSet;
-
-use once_cell::sync::Lazy;
-
+//!
+//! The paper predates post-quantum and stateful hash-based signatures,
+//! which are held to the same security as every other primitive, taken
+//! to be that of their security category and of their hash output
+//! respectively.
 use crate::context::Context;
 use crate::primitive::ecc::*;
 use crate::primitive::ffc::*;
 use crate::primitive::hash::*;
 use crate::primitive::ifc::*;
+use crate::primitive::pqc::*;
+use crate::primitive::stateful::*;
 use crate::primitive::symmetric::*;
 use crate::primitive::Primitive;
-use crate::standard::Standard;
+use crate::standard::rule::{Citation, Rule, Scope};
+use crate::standard::{utilities, Standard};
+use crate::Error;
 
-#[derive(PartialEq, Eq, Debug)]
-pub enum ValidationError {
-  SecurityLevelTooLow,
-}
+/// The error returned when the year of a context precedes the base year
+/// of the formula, which is now [`Error::InvalidContext`].
+#[deprecated(note = "use wardstone_core::Error instead")]
+pub type ValidationError = Error;
 
 const BASE_YEAR: u16 = 1982;
 const BASE_SECURITY: u16 = 56;
 
-static SPECIFIED_HASH_FUNCTIONS: Lazy<HashSet<Hash>> = Lazy::new(|| {
-  let mut s = HashSet::new();
-  s.insert(RIPEMD160);
-  s.insert(SHA1);
-  s.insert(SHA256);
-  s.insert(SHA384);
-  s.insert(SHA512);
-  s
-});
+static SPECIFIED_HASH_FUNCTIONS: [Hash; 5] = [RIPEMD160, SHA1, SHA256, SHA384, SHA512];
+
+static SPECIFIED_SYMMETRIC_KEYS: [Symmetric; 8] =
+  [AES128, AES192, AES256, DES, DESX, IDEA, TDEA2, TDEA3];
 
-static SPECIFIED_SYMMETRIC_KEYS: Lazy<HashSet<Symmetric>> = Lazy::new(|| {
-  let mut s = HashSet::new();
-  s.insert(AES128);
-  s.insert(AES192);
-  s.insert(AES256);
-  s.insert(DES);
-  s.insert(DESX);
-  s.insert(IDEA);
-  s.insert(TDEA2);
-  s.insert(TDEA3);
-  s
-});
+const DOCUMENT: &str = "Lenstra, Key Lengths (2004)";
+
+static RULES: [Rule; 7] = [
+  Rule {
+    id: "LENSTRA-ECC-001",
+    scope: Scope::Ecc,
+    usage: None,
+    minimum: None,
+    cutoff: None,
+    summary: "Curves providing the security that is expected to remain adequate in the year of the context, which grows from 56 bits in 1982 by one bit every eighteen months.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "p. 7",
+      url: None,
+    },
+  },
+  Rule {
+    id: "LENSTRA-FFC-001",
+    scope: Scope::Ffc,
+    usage: None,
+    minimum: None,
+    cutoff: None,
+    summary: "DSA domain parameters and Diffie-Hellman groups providing the security that is expected to remain adequate in the year of the context.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "p. 7",
+      url: None,
+    },
+  },
+  Rule {
+    id: "LENSTRA-IFC-001",
+    scope: Scope::Ifc,
+    usage: None,
+    minimum: None,
+    cutoff: None,
+    summary: "RSA moduli providing the security that is expected to remain adequate in the year of the context.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "pp. 17-25, Table 4",
+      url: None,
+    },
+  },
+  Rule {
+    id: "LENSTRA-HASH-001",
+    scope: Scope::Hash,
+    usage: None,
+    minimum: None,
+    cutoff: None,
+    summary: "Hash functions providing the collision resistance that is expected to remain adequate in the year of the context.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "pp. 12-14",
+      url: None,
+    },
+  },
+  Rule {
+    id: "LENSTRA-SYM-001",
+    scope: Scope::Symmetric,
+    usage: None,
+    minimum: None,
+    cutoff: None,
+    summary: "Block ciphers providing the security that is expected to remain adequate in the year of the context.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "pp. 9-12",
+      url: None,
+    },
+  },  Rule {
+    id: "LENSTRA-PQC-001",
+    scope: Scope::Pqc,
+    usage: None,
+    minimum: None,
+    cutoff: None,
+    summary: "ML-DSA and SLH-DSA parameter sets whose security category provides the security that is expected to remain adequate in the year of the context.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "p. 7",
+      url: None,
+    },
+  },
+  Rule {
+    id: "LENSTRA-HBS-001",
+    scope: Scope::Stateful,
+    usage: None,
+    minimum: None,
+    cutoff: None,
+    summary: "LMS and XMSS parameter sets whose hash output provides the security that is expected to remain adequate in the year of the context.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "p. 7",
+      url: None,
+    },
+  },
+];
 
 /// [`Standard`] implementation of the paper Key Lengths,
 /// Arjen K. Lenstra, The Handbook of Information Security, 06/2004.
@@ -51,10 +133,14 @@ pub struct Lenstra;
 
 impl Lenstra {
   /// Calculates the security according to the formula on page 7. If the
-  /// year is less than the BASE_YEAR, a ValidationError is returned.
-  fn calculate_security(year: u16) -> Result<u16, ValidationError> {
+  /// year is less than the BASE_YEAR, an [`Error::InvalidContext`] is
+  /// returned.
+  fn calculate_security(year: u16) -> Result<u16, Error> {
     if year < BASE_YEAR {
-      Err(ValidationError::SecurityLevelTooLow)
+      Err(Error::InvalidContext(format!(
+        "the formula does not apply to years before {}",
+        BASE_YEAR
+      )))
     } else {
       let mut lambda = (year - BASE_YEAR) << 1;
       lambda /= 3;
@@ -114,7 +200,9 @@ impl Standard for Lenstra {
   /// Diffie-Hellman and MQV which can also be implemented as such.
   ///
   /// If the key is not compliant then `Err` will contain the
-  /// recommended key sizes L and N that one should use instead.
+  /// recommended key sizes L and N that one should use instead, or the
+  /// recommended group if the key is a group used for key
+  /// establishment.
   ///
   /// If the key is compliant but the context specifies a higher
   /// security level, `Ok` will also hold the recommended key sizes L
@@ -148,11 +236,13 @@ impl Standard for Lenstra {
       129..=192 => DSA_7680_384,
       193.. => DSA_15360_512,
     };
-    if implied_security < min_security {
+    let verdict = if implied_security < min_security {
       Err(recommendation)
     } else {
       Ok(recommendation)
-    }
+    };
+    let verdict = utilities::prefer_group(key, verdict);
+    utilities::reject_inconsistent(key, verdict)
   }
 
   /// Validates a hash function according to pages 12-14 of the paper.
@@ -323,12 +413,98 @@ impl Standard for Lenstra {
       Err(AES128)
     }
   }
+
+  /// Validates an ML-DSA or SLH-DSA signature primitive by the
+  /// security of its category.
+  ///
+  /// If the key is not compliant then `Err` will contain the
+  /// recommended parameter set that one should use instead.
+  ///
+  /// If the key is compliant but the context specifies a higher
+  /// security level, `Ok` will also hold the parameter set of the same
+  /// family with the desired security level.
+  ///
+  /// # Example
+  ///
+  /// The following illustrates a call to validate a compliant key.
+  ///
+  /// ```
+  /// use wardstone_core::context::Context;
+  /// use wardstone_core::primitive::pqc::ML_DSA_44;
+  /// use wardstone_core::standard::lenstra::Lenstra;
+  /// use wardstone_core::standard::Standard;
+  ///
+  /// let ctx = Context::default();
+  /// assert_eq!(Lenstra::validate_pqc(ctx, ML_DSA_44), Ok(ML_DSA_44));
+  /// ```
+  fn validate_pqc(ctx: Context, key: Pqc) -> Result<Pqc, Pqc> {
+    let implied_security = ctx.security().max(key.security());
+    let min_security = match Lenstra::calculate_security(ctx.year()) {
+      Ok(security) => security,
+      Err(_) => return Err(PQC_NOT_ALLOWED),
+    };
+    match key.at_least(implied_security.max(min_security)) {
+      Some(recommendation) if implied_security >= min_security => Ok(recommendation),
+      Some(recommendation) => Err(recommendation),
+      None if !key.is_placeholder() && implied_security >= min_security => Ok(key),
+      None => Err(ML_DSA_44.at_least(min_security).unwrap_or(ML_DSA_87)),
+    }
+  }
+
+  /// Validates a stateful hash-based signature primitive by the size of
+  /// its hash output, including the parameter sets of RFC 8391 that SP
+  /// 800-208 leaves out.
+  ///
+  /// If the key is not compliant then `Err` will contain the
+  /// recommended parameter set that one should use instead.
+  ///
+  /// If the key is compliant but the context specifies a higher
+  /// security level, `Ok` will also hold the parameter set of the same
+  /// scheme and hash function with the desired security level.
+  ///
+  /// # Example
+  ///
+  /// The following illustrates a call to validate a compliant key.
+  ///
+  /// ```
+  /// use wardstone_core::context::Context;
+  /// use wardstone_core::primitive::stateful::LMS_SHA256_192;
+  /// use wardstone_core::standard::lenstra::Lenstra;
+  /// use wardstone_core::standard::Standard;
+  ///
+  /// let ctx = Context::default();
+  /// assert_eq!(Lenstra::validate_stateful(ctx, LMS_SHA256_192), Ok(LMS_SHA256_192));
+  /// ```
+  fn validate_stateful(ctx: Context, key: Stateful) -> Result<Stateful, Stateful> {
+    let implied_security = ctx.security().max(key.security());
+    let min_security = match Lenstra::calculate_security(ctx.year()) {
+      Ok(security) => security,
+      Err(_) => return Err(STATEFUL_NOT_ALLOWED),
+    };
+    match key.at_least(implied_security.max(min_security)) {
+      Some(recommendation) if implied_security >= min_security => Ok(recommendation),
+      Some(recommendation) => Err(recommendation),
+      None if !key.is_placeholder() && implied_security >= min_security => Ok(key),
+      None => Err(
+        LMS_SHA256_192
+          .at_least(min_security)
+          .unwrap_or(LMS_SHA256_256),
+      ),
+    }
+  }
+
+  fn rules() -> &'static [Rule] {
+    &RULES
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
-  use crate::{test_ecc, test_ffc, test_hash, test_ifc, test_symmetric};
+  use crate::primitive::hybrid::Hybrid;
+  use crate::{
+    test_ecc, test_ffc, test_hash, test_hybrid, test_ifc, test_pqc, test_stateful, test_symmetric,
+  };
 
   test_ecc!(p224, Lenstra, P224, Ok(ECC_224));
   test_ecc!(p256, Lenstra, P256, Ok(ECC_256));
@@ -336,6 +512,8 @@ mod tests {
   test_ecc!(p521, Lenstra, P521, Ok(ECC_512));
   test_ecc!(ed25519, Lenstra, ED25519, Ok(ECC_256));
   test_ecc!(ed448, Lenstra, ED448, Ok(ECC_512));
+  test_ecc!(ed25519ph, Lenstra, ED25519PH, Ok(ECC_256));
+  test_ecc!(ed448ph, Lenstra, ED448PH, Ok(ECC_512));
   test_ecc!(x25519, Lenstra, X25519, Ok(ECC_256));
   test_ecc!(x448, Lenstra, X448, Ok(ECC_512));
   test_ecc!(brainpoolp224r1, Lenstra, BRAINPOOLP224R1, Ok(ECC_224));
@@ -343,13 +521,30 @@ mod tests {
   test_ecc!(brainpoolp320r1, Lenstra, BRAINPOOLP320R1, Ok(ECC_384));
   test_ecc!(brainpoolp384r1, Lenstra, BRAINPOOLP384R1, Ok(ECC_384));
   test_ecc!(brainpoolp512r1, Lenstra, BRAINPOOLP512R1, Ok(ECC_512));
+  test_ecc!(bls12_381, Lenstra, BLS12_381, Ok(ECC_256));
   test_ecc!(secp256k1, Lenstra, SECP256K1, Ok(ECC_256));
+  test_ecc!(sm2, Lenstra, SM2, Ok(ECC_256));
 
   test_ffc!(ffc_1024_160, Lenstra, DSA_1024_160, Err(DSA_2048_224));
   test_ffc!(ffc_2048_224, Lenstra, DSA_2048_224, Ok(DSA_2048_224));
   test_ffc!(ffc_3072_256, Lenstra, DSA_3072_256, Ok(DSA_3072_256));
   test_ffc!(ffc_7680_384, Lenstra, DSA_7680_384, Ok(DSA_7680_384));
   test_ffc!(ffc_15360_512, Lenstra, DSA_15360_512, Ok(DSA_15360_512));
+  test_ffc!(
+    ffc_3072_224,
+    Lenstra,
+    Ffc::new(ID_DSA, 3072, 224),
+    Err(DSA_2048_224)
+  );
+  test_ffc!(
+    ffc_1024_256,
+    Lenstra,
+    Ffc::new(ID_DSA, 1024, 256),
+    Err(DSA_2048_224)
+  );
+
+  test_ffc!(modp_1024, Lenstra, MODP_1024, Err(FFDHE2048));
+  test_ffc!(ffdhe3072, Lenstra, FFDHE3072, Ok(FFDHE3072));
 
   test_ifc!(ifc_1024, Lenstra, RSA_PSS_1024, Err(RSA_PSS_2048));
   test_ifc!(ifc_1280, Lenstra, RSA_PSS_1280, Err(RSA_PSS_2048));
@@ -401,4 +596,54 @@ mod tests {
   test_symmetric!(serpent256, Lenstra, SERPENT256, Err(AES128));
   test_symmetric!(three_key_tdea, Lenstra, TDEA3, Ok(TDEA3));
   test_symmetric!(two_key_tdea, Lenstra, TDEA2, Ok(TDEA2));
+
+  test_pqc!(ml_dsa_44, Lenstra, ML_DSA_44, Ok(ML_DSA_44));
+  test_pqc!(ml_dsa_87, Lenstra, ML_DSA_87, Ok(ML_DSA_87));
+  test_pqc!(
+    slh_dsa_sha2_128f,
+    Lenstra,
+    SLH_DSA_SHA2_128F,
+    Ok(SLH_DSA_SHA2_128F)
+  );
+  test_pqc!(pqc_not_allowed, Lenstra, PQC_NOT_ALLOWED, Err(ML_DSA_44));
+
+  test_stateful!(lms_sha256_192, Lenstra, LMS_SHA256_192, Ok(LMS_SHA256_192));
+  test_stateful!(
+    xmss_shake256_256,
+    Lenstra,
+    XMSS_SHAKE256_256,
+    Ok(XMSS_SHAKE256_256)
+  );
+  test_stateful!(
+    xmss_sha512_512,
+    Lenstra,
+    XMSS_SHA512_512,
+    Ok(XMSS_SHA512_512)
+  );
+
+  test_hybrid!(
+    ml_dsa_44_ed25519,
+    Lenstra,
+    Hybrid::new(ML_DSA_44, ED25519),
+    Ok(Hybrid::new(ML_DSA_44, ECC_256))
+  );
+
+  #[test]
+  fn pqc_by_year() {
+    use crate::context::Context;
+
+    // 2150 calls for 168 bits of security.
+    let ctx = Context::new(0, 2150);
+    assert_eq!(Lenstra::validate_pqc(ctx, ML_DSA_44), Err(ML_DSA_65));
+    assert_eq!(
+      Lenstra::validate_pqc(ctx, SLH_DSA_SHAKE_128S),
+      Err(SLH_DSA_SHAKE_192S)
+    );
+    assert_eq!(
+      Lenstra::validate_stateful(ctx, LMS_SHA256_192),
+      Ok(LMS_SHA256_192)
+    );
+    let ctx = Context::new(0, 1980);
+    assert_eq!(Lenstra::validate_pqc(ctx, ML_DSA_87), Err(PQC_NOT_ALLOWED));
+  }
 }
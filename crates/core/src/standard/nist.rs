@@ -1,68 +1,198 @@
 //! Validate cryptographic primitives against the [NIST Special
 //! Publication 800-57 Part 1 Revision 5 standard].
 //!
+//! Post-quantum signatures are validated against [FIPS 204] and [FIPS
+//! 205], and stateful hash-based signatures against [SP 800-208].
+//!
 //! [NIST Special Publication 800-57 Part 1 Revision 5 standard]: https://doi.org/10.6028/NIST.SP.800-57pt1r5
-use std::collections::HashSet;
-
-use once_cell::sync::Lazy;
-
+//! [FIPS 204]: https://doi.org/10.6028/NIST.FIPS.204
+//! [FIPS 205]: https://doi.org/10.6028/NIST.FIPS.205
+//! [SP 800-208]: https://doi.org/10.6028/NIST.SP.800-208
+use super::utilities::{self, Application};
 use super::Standard;
+use crate::advisory::Hazard;
 use crate::context::Context;
 use crate::primitive::ecc::*;
 use crate::primitive::ffc::*;
 use crate::primitive::hash::*;
 use crate::primitive::ifc::*;
+use crate::primitive::pqc::*;
+use crate::primitive::stateful::*;
 use crate::primitive::symmetric::*;
 use crate::primitive::Primitive;
+use crate::standard::rule::{Citation, Rule, Scope, Usage};
 
 const CUTOFF_YEAR: u16 = 2031; // See p. 59.
 const CUTOFF_YEAR_3TDEA: u16 = 2023; // See footnote on p. 54.
 const CUTOFF_YEAR_DSA: u16 = 2023; // See FIPS-186-5 p. 16.
 
-static SPECIFIED_CURVES: Lazy<HashSet<Ecc>> = Lazy::new(|| {
-  let mut s = HashSet::new();
-  s.insert(ED25519);
-  s.insert(ED448);
-  s.insert(P224);
-  s.insert(P256);
-  s.insert(P384);
-  s.insert(P521);
-  s.insert(BRAINPOOLP224R1);
-  s.insert(BRAINPOOLP256R1);
-  s.insert(BRAINPOOLP320R1);
-  s.insert(BRAINPOOLP384R1);
-  s.insert(BRAINPOOLP512R1);
-  s.insert(SECP256K1);
-  s
-});
-
-static SPECIFIED_HASH_FUNCTIONS: Lazy<HashSet<Hash>> = Lazy::new(|| {
-  let mut s = HashSet::new();
-  s.insert(SHA1);
-  s.insert(SHA224);
-  s.insert(SHA256);
-  s.insert(SHA384);
-  s.insert(SHA3_224);
-  s.insert(SHA3_256);
-  s.insert(SHA3_384);
-  s.insert(SHA3_512);
-  s.insert(SHA512);
-  s.insert(SHA512_224);
-  s.insert(SHA512_256);
-  s.insert(SHAKE128);
-  s.insert(SHAKE256);
-  s
-});
-
-static SPECIFIED_SYMMETRIC_KEYS: Lazy<HashSet<Symmetric>> = Lazy::new(|| {
-  let mut s = HashSet::new();
-  s.insert(AES128);
-  s.insert(AES192);
-  s.insert(AES256);
-  s.insert(TDEA2);
-  s.insert(TDEA3);
-  s
-});
+// FIPS-186-5 approves the prehashed variants of EdDSA, HashEdDSA,
+// alongside the pure ones.
+static SPECIFIED_CURVES: [Ecc; 14] = [
+  ED25519,
+  ED25519PH,
+  ED448,
+  ED448PH,
+  P224,
+  P256,
+  P384,
+  P521,
+  BRAINPOOLP224R1,
+  BRAINPOOLP256R1,
+  BRAINPOOLP320R1,
+  BRAINPOOLP384R1,
+  BRAINPOOLP512R1,
+  SECP256K1,
+];
+
+// The safe-prime groups of Appendix D of SP 800-56A Rev. 3.
+static SPECIFIED_GROUPS: [Ffc; 10] = [
+  FFDHE2048, FFDHE3072, FFDHE4096, FFDHE6144, FFDHE8192, MODP_2048, MODP_3072, MODP_4096,
+  MODP_6144, MODP_8192,
+];
+
+static SPECIFIED_HASH_FUNCTIONS: [Hash; 13] = [
+  SHA1, SHA224, SHA256, SHA384, SHA3_224, SHA3_256, SHA3_384, SHA3_512, SHA512, SHA512_224,
+  SHA512_256, SHAKE128, SHAKE256,
+];
+
+// See table 3 on p. 56 and SP 800-107 Rev. 1 for applications that only
+// rely on pre-image resistance.
+static RECOMMENDED_HASH_FUNCTIONS: [(Hash, Application); 6] = [
+  (SHAKE128, Application::PreImage),
+  (SHA1, Application::PreImage),
+  (SHA224, Application::Collision),
+  (SHA256, Application::Collision),
+  (SHA384, Application::Collision),
+  (SHA512, Application::Collision),
+];
+
+static SPECIFIED_SYMMETRIC_KEYS: [Symmetric; 5] = [AES128, AES192, AES256, TDEA2, TDEA3];
+
+const DOCUMENT: &str = "NIST SP 800-57 Part 1 Rev. 5";
+const URL: &str = "https://doi.org/10.6028/NIST.SP.800-57pt1r5";
+const DH_DOCUMENT: &str = "NIST SP 800-56A Rev. 3";
+const DH_URL: &str = "https://doi.org/10.6028/NIST.SP.800-56Ar3";
+
+static RULES: [Rule; 9] = [
+  Rule {
+    id: "NIST-ECC-001",
+    scope: Scope::Ecc,
+    usage: None,
+    minimum: Some(112),
+    cutoff: Some(CUTOFF_YEAR),
+    summary: "Curves specified in FIPS 186-5 and SP 800-186 providing at least 112 bits of security. Curves providing less than 128 bits of security are disallowed after the cutoff year.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "§5.6.1.1, Table 2, pp. 54-55 and §5.6.3, Table 4, p. 59",
+      url: Some(URL),
+    },
+  },
+  Rule {
+    id: "NIST-FFC-001",
+    scope: Scope::Ffc,
+    usage: Some(Usage::Signature),
+    minimum: Some(112),
+    cutoff: Some(CUTOFF_YEAR_DSA),
+    summary: "DSA domain parameters (L, N) of (2048, 224), (3072, 256), (7680, 384) or (15360, 512). DSA is no longer approved for signature generation by FIPS 186-5 after the cutoff year.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "§5.6.1.1, Table 2, pp. 54-55",
+      url: Some(URL),
+    },
+  },
+  Rule {
+    id: "NIST-DH-001",
+    scope: Scope::Ffc,
+    usage: Some(Usage::KeyAgreement),
+    minimum: Some(112),
+    cutoff: Some(CUTOFF_YEAR),
+    summary: "Safe-prime groups ffdhe2048 to ffdhe8192 and MODP-2048 to MODP-8192, or domain parameters (L, N) of (2048, 224) or (2048, 256), for key establishment. Groups providing less than 128 bits of security are disallowed after the cutoff year per SP 800-57 Part 1 Rev. 5.",
+    citation: Citation {
+      document: DH_DOCUMENT,
+      section: "§5.5.1.1 and Appendix D",
+      url: Some(DH_URL),
+    },
+  },
+  Rule {
+    id: "NIST-IFC-001",
+    scope: Scope::Ifc,
+    usage: None,
+    minimum: Some(112),
+    cutoff: Some(CUTOFF_YEAR),
+    summary: "RSA moduli of at least 2048 bits providing 112 bits of security. Moduli providing less than 128 bits of security are disallowed after the cutoff year.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "§5.6.1.1, Table 2, pp. 54-55 and §5.6.3, Table 4, p. 59",
+      url: Some(URL),
+    },
+  },
+  Rule {
+    id: "NIST-HASH-001",
+    scope: Scope::Hash,
+    usage: None,
+    minimum: Some(112),
+    cutoff: Some(CUTOFF_YEAR),
+    summary: "Hash functions specified in FIPS 180-4 and FIPS 202 providing at least 112 bits of collision resistance for digital signatures. Hash functions providing less than 128 bits of collision resistance are disallowed after the cutoff year.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "§5.6.1.2, Table 3, p. 56",
+      url: Some(URL),
+    },
+  },
+  Rule {
+    id: "NIST-HASH-002",
+    scope: Scope::HashBased,
+    usage: None,
+    minimum: Some(112),
+    cutoff: Some(CUTOFF_YEAR),
+    summary: "Hash functions specified in FIPS 180-4 and FIPS 202 providing at least 112 bits of pre-image resistance for HMAC, key derivation and random bit generation. Hash functions providing less than 128 bits of pre-image resistance are disallowed after the cutoff year.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "§5.6.1.2, Table 3, p. 56",
+      url: Some(URL),
+    },
+  },
+  Rule {
+    id: "NIST-SYM-001",
+    scope: Scope::Symmetric,
+    usage: None,
+    minimum: Some(112),
+    cutoff: Some(CUTOFF_YEAR),
+    summary: "AES and three-key TDEA providing at least 112 bits of security. Three-key TDEA is disallowed after 2023 per SP 800-131A Rev. 2 and keys providing less than 128 bits of security are disallowed after the cutoff year.",
+    citation: Citation {
+      document: DOCUMENT,
+      section: "§5.6.1.1, Table 2, pp. 54-55",
+      url: Some(URL),
+    },
+  },
+  Rule {
+    id: "NIST-PQC-001",
+    scope: Scope::Pqc,
+    usage: None,
+    minimum: Some(128),
+    cutoff: None,
+    summary: "ML-DSA as specified in FIPS 204 and SLH-DSA as specified in FIPS 205 with any of their parameter sets. Hybrid signatures are accepted if either half is.",
+    citation: Citation {
+      document: "FIPS 204",
+      section: "§4, Table 1",
+      url: Some("https://doi.org/10.6028/NIST.FIPS.204"),
+    },
+  },
+  Rule {
+    id: "NIST-HBS-001",
+    scope: Scope::Stateful,
+    usage: None,
+    minimum: Some(192),
+    cutoff: None,
+    summary: "LMS, HSS, XMSS and XMSS^MT with SHA-256, SHA-256/192, SHAKE256/256 or SHAKE256/192, with keys generated and used in hardware cryptographic modules.",
+    citation: Citation {
+      document: "NIST SP 800-208",
+      section: "§4 and §5",
+      url: Some("https://doi.org/10.6028/NIST.SP.800-208"),
+    },
+  },
+];
 
 /// [`Standard`] implementation of the [NIST Special Publication 800-57
 /// Part 1 Revision 5 standard].
@@ -88,12 +218,10 @@ impl Nist {
   /// higher security level, `Ok` will also hold the recommended
   /// primitive with the desired security level.
   ///
-  /// **Note:** that this means an alternative might be suggested for a
-  /// compliant hash functions with a similar security level in which a
-  /// switch to the recommended primitive would likely be unwarranted.
-  /// For example, when evaluating compliance for the `SHA3-256`, a
-  /// recommendation to use `SHA256` will be made but switching to this
-  /// as a result is likely unnecessary.
+  /// When the hash function belongs to the SHA-3 family, the
+  /// recommendation is the SHA-3 function with the same digest length
+  /// rather than its SHA-2 counterpart, so that a compliant `SHA3-256`
+  /// is not met with advice to switch to `SHA256`.
   ///
   /// # Example
   ///
@@ -111,27 +239,91 @@ impl Nist {
   /// assert_eq!(Nist::validate_hash_based(ctx, hmac_sha1), Ok(hmac_sha1));
   /// ```
   pub fn validate_hash_based(ctx: Context, hash: Hash) -> Result<Hash, Hash> {
-    if SPECIFIED_HASH_FUNCTIONS.contains(&hash) {
-      let pre_image_resistance = hash.security() << 1;
-      let security = ctx.security().max(pre_image_resistance);
-      match security {
-        ..=111 => Err(SHAKE128),
-        112..=127 => {
-          if ctx.year() > CUTOFF_YEAR {
-            Err(SHAKE128)
-          } else {
-            Ok(SHAKE128)
-          }
-        },
-        128 => Ok(SHAKE128),
-        129..=160 => Ok(SHA1),
-        161..=224 => Ok(SHA224),
-        225..=256 => Ok(SHA256),
-        257..=394 => Ok(SHA384),
-        395.. => Ok(SHA512),
+    let verdict = if SPECIFIED_HASH_FUNCTIONS.contains(&hash) {
+      let minimum = if ctx.year() > ctx.cutoff(Scope::HashBased, CUTOFF_YEAR) {
+        128
+      } else {
+        112
+      };
+      let application = Application::PreImage;
+      let security = ctx.security().max(application.strength(hash));
+      let recommended = utilities::recommend_hash(
+        &RECOMMENDED_HASH_FUNCTIONS,
+        application,
+        security.max(minimum),
+      );
+      if security < minimum {
+        Err(recommended)
+      } else {
+        Ok(recommended)
       }
     } else {
       Err(SHAKE128)
+    };
+    utilities::prefer_sha3(hash, verdict, &SPECIFIED_HASH_FUNCTIONS)
+  }
+
+  /// Validates a group used for key establishment such as
+  /// Diffie-Hellman and MQV according to section 5.5.1.1 of SP 800-56A
+  /// Rev. 3, which approves the safe-prime groups listed in its appendix
+  /// D and the domain parameter sets FB and FC. Unlike DSA, these
+  /// remain approved after FIPS-186-5.
+  ///
+  /// [`validate_ffc`](crate::standard::nist::Nist::validate_ffc) calls
+  /// this for every primitive that is a group used for key
+  /// establishment (see [`Ffc::is_key_agreement`]).
+  ///
+  /// If the group is not compliant then `Err` will contain the
+  /// recommended group that one should use instead.
+  ///
+  /// If the group is compliant but the context specifies a higher
+  /// security level, `Ok` will also hold the recommended group with the
+  /// desired security level.
+  ///
+  /// **Note:** The cutoff year of a context for finite field primitives
+  /// replaces the one after which groups providing less than 128 bits of
+  /// security are disallowed as well as the one for DSA.
+  ///
+  /// # Example
+  ///
+  /// The following illustrates a call to validate a group that is
+  /// disallowed after 2030.
+  ///
+  /// ```
+  /// use wardstone_core::context::Context;
+  /// use wardstone_core::primitive::ffc::{FFDHE3072, MODP_2048};
+  /// use wardstone_core::standard::nist::Nist;
+  ///
+  /// let ctx = Context::new(0, 2031);
+  /// assert!(Nist::validate_dh(ctx, MODP_2048).is_ok());
+  /// let ctx = Context::new(0, 2032);
+  /// assert_eq!(Nist::validate_dh(ctx, MODP_2048), Err(FFDHE3072));
+  /// ```
+  pub fn validate_dh(ctx: Context, key: Ffc) -> Result<Ffc, Ffc> {
+    let expired = ctx.year() > ctx.cutoff(Scope::Ffc, CUTOFF_YEAR);
+    let parameter_set = key.l == 2048 && matches!(key.n, 224 | 256);
+    if !SPECIFIED_GROUPS.contains(&key) && !parameter_set {
+      return Err(if expired { FFDHE3072 } else { FFDHE2048 });
+    }
+    let security = ctx.security().max(key.security());
+    match security {
+      ..=111 => {
+        if expired {
+          Err(FFDHE3072)
+        } else {
+          Err(FFDHE2048)
+        }
+      },
+      112..=127 => {
+        if expired {
+          Err(FFDHE3072)
+        } else {
+          Ok(FFDHE2048)
+        }
+      },
+      128..=191 => Ok(FFDHE3072),
+      192 => Ok(FFDHE8192),
+      _ => Err(FFC_NOT_SUPPORTED),
     }
   }
 }
@@ -166,14 +358,14 @@ impl Standard for Nist {
       let security = ctx.security().max(key.security());
       match security {
         ..=111 => {
-          if ctx.year() > CUTOFF_YEAR {
+          if ctx.year() > ctx.cutoff(Scope::Ecc, CUTOFF_YEAR) {
             Err(P256)
           } else {
             Err(P224)
           }
         },
         112..=127 => {
-          if ctx.year() > CUTOFF_YEAR {
+          if ctx.year() > ctx.cutoff(Scope::Ecc, CUTOFF_YEAR) {
             Err(P256)
           } else {
             Ok(P224)
@@ -195,7 +387,8 @@ impl Standard for Nist {
   /// according to page 54-55 of the standard.
   ///
   /// A newer revision of FIPS-186, FIPS-186-5 no longer approves the
-  /// DSA.
+  /// DSA. Groups used for key establishment are instead validated with
+  /// [`validate_dh`](crate::standard::nist::Nist::validate_dh).
   ///
   /// If the key is not compliant then `Err` will contain the
   /// recommended key sizes L and N that one should use instead.
@@ -225,12 +418,17 @@ impl Standard for Nist {
   /// assert_eq!(Nist::validate_ffc(ctx, dsa_2048), Ok(dsa_2048));
   /// ```
   fn validate_ffc(ctx: Context, key: Ffc) -> Result<Ffc, Ffc> {
-    if ctx.year() > CUTOFF_YEAR_DSA {
+    if key.is_key_agreement() {
+      return Self::validate_dh(ctx, key);
+    }
+    if ctx.year() > ctx.cutoff(Scope::Ffc, CUTOFF_YEAR_DSA) {
       return Err(FFC_NOT_SUPPORTED);
     }
 
+    // The cutoff year of the rule is the one from which DSA may no
+    // longer be used to sign, so it is the only one a context replaces.
     let security = ctx.security().max(key.security());
-    match security {
+    let verdict = match security {
       80 => {
         if ctx.year() > CUTOFF_YEAR {
           Err(DSA_3072_256)
@@ -249,7 +447,8 @@ impl Standard for Nist {
       192 => Ok(DSA_7680_384),
       256 => Ok(DSA_15360_512),
       _ => Err(FFC_NOT_SUPPORTED),
-    }
+    };
+    utilities::reject_inconsistent(key, verdict)
   }
 
   /// Validates a hash function according to page 56 of the standard.
@@ -268,12 +467,10 @@ impl Standard for Nist {
   /// higher security level, `Ok` will also hold the recommended
   /// primitive with the desired security level.
   ///
-  /// **Note:** An alternative might be suggested for a compliant hash
-  /// functions with a similar security level in which a switch to the
-  /// recommended primitive would likely be unwarranted. For example,
-  /// when evaluating compliance for the `SHA3-256`, a recommendation
-  /// to use `SHA256` will be made but switching to this as a result
-  /// is likely unnecessary.
+  /// When the hash function belongs to the SHA-3 family, the
+  /// recommendation is the SHA-3 function with the same digest length
+  /// rather than its SHA-2 counterpart, so that a compliant `SHA3-256`
+  /// is not met with advice to switch to `SHA256`.
   ///
   /// # Example
   ///
@@ -290,30 +487,28 @@ impl Standard for Nist {
   /// assert_eq!(Nist::validate_hash(ctx, SHA1), Err(SHA224));
   /// ```
   fn validate_hash(ctx: Context, hash: Hash) -> Result<Hash, Hash> {
-    if SPECIFIED_HASH_FUNCTIONS.contains(&hash) {
-      let security = ctx.security().max(hash.security());
-      match security {
-        ..=111 => {
-          if ctx.year() > CUTOFF_YEAR {
-            Err(SHA256)
-          } else {
-            Err(SHA224)
-          }
-        },
-        112..=127 => {
-          if ctx.year() > CUTOFF_YEAR {
-            Err(SHA256)
-          } else {
-            Ok(SHA224)
-          }
-        },
-        128..=191 => Ok(SHA256),
-        192..=255 => Ok(SHA384),
-        256.. => Ok(SHA512),
+    let verdict = if SPECIFIED_HASH_FUNCTIONS.contains(&hash) {
+      let minimum = if ctx.year() > ctx.cutoff(Scope::Hash, CUTOFF_YEAR) {
+        128
+      } else {
+        112
+      };
+      let application = Application::Collision;
+      let security = ctx.security().max(application.strength(hash));
+      let recommended = utilities::recommend_hash(
+        &RECOMMENDED_HASH_FUNCTIONS,
+        application,
+        security.max(minimum),
+      );
+      if security < minimum {
+        Err(recommended)
+      } else {
+        Ok(recommended)
       }
     } else {
       Err(SHA256)
-    }
+    };
+    utilities::prefer_sha3(hash, verdict, &SPECIFIED_HASH_FUNCTIONS)
   }
 
   /// Validates  an integer factorisation cryptography primitive the
@@ -347,14 +542,14 @@ impl Standard for Nist {
     let security = ctx.security().max(key.security());
     match security {
       ..=111 => {
-        if ctx.year() > CUTOFF_YEAR {
+        if ctx.year() > ctx.cutoff(Scope::Ifc, CUTOFF_YEAR) {
           Err(RSA_PSS_3072)
         } else {
           Err(RSA_PSS_2048)
         }
       },
       112..=127 => {
-        if ctx.year() > CUTOFF_YEAR {
+        if ctx.year() > ctx.cutoff(Scope::Ifc, CUTOFF_YEAR) {
           Err(RSA_PSS_3072)
         } else {
           Ok(RSA_PSS_2048)
@@ -400,7 +595,7 @@ impl Standard for Nist {
           let cutoff = if key.id == TDEA3.id {
             CUTOFF_YEAR_3TDEA
           } else {
-            CUTOFF_YEAR
+            ctx.cutoff(Scope::Symmetric, CUTOFF_YEAR)
           };
           if ctx.year() > cutoff {
             Err(AES128)
@@ -416,12 +611,109 @@ impl Standard for Nist {
       Err(AES128)
     }
   }
+
+  /// Validates an ML-DSA or SLH-DSA signature primitive. Every
+  /// parameter set of FIPS 204 and FIPS 205 meets at least security
+  /// category 1.
+  ///
+  /// If the key is not compliant then `Err` will contain the
+  /// recommended parameter set that one should use instead.
+  ///
+  /// If the key is compliant but the context specifies a higher
+  /// security level, `Ok` will also hold the parameter set of the same
+  /// family with the desired security level.
+  ///
+  /// # Example
+  ///
+  /// The following illustrates a call to validate a compliant key in a
+  /// context that asks for more security.
+  ///
+  /// ```
+  /// use wardstone_core::context::Context;
+  /// use wardstone_core::primitive::pqc::{SLH_DSA_SHAKE_128F, SLH_DSA_SHAKE_192F};
+  /// use wardstone_core::standard::nist::Nist;
+  /// use wardstone_core::standard::Standard;
+  ///
+  /// let ctx = Context::new(192, 2030);
+  /// assert_eq!(Nist::validate_pqc(ctx, SLH_DSA_SHAKE_128F), Ok(SLH_DSA_SHAKE_192F));
+  /// ```
+  fn validate_pqc(ctx: Context, key: Pqc) -> Result<Pqc, Pqc> {
+    let security = ctx.security().max(key.security());
+    key.at_least(security).ok_or(match ctx.security() {
+      ..=128 => ML_DSA_44,
+      129..=192 => ML_DSA_65,
+      193.. => ML_DSA_87,
+    })
+  }
+
+  /// Validates a stateful hash-based signature primitive according to
+  /// SP 800-208, which approves LMS and XMSS with SHA-256 and SHAKE256
+  /// truncated to 192 bits or in full.
+  ///
+  /// If the key is not compliant then `Err` will contain the
+  /// recommended parameter set that one should use instead.
+  ///
+  /// If the key is compliant but the context specifies a higher
+  /// security level, `Ok` will also hold the parameter set of the same
+  /// scheme and hash function with the desired security level.
+  ///
+  /// # Example
+  ///
+  /// The following illustrates a call to validate a non-compliant key.
+  ///
+  /// ```
+  /// use wardstone_core::context::Context;
+  /// use wardstone_core::primitive::stateful::{LMS_SHA256_192, XMSS_SHA512_512};
+  /// use wardstone_core::standard::nist::Nist;
+  /// use wardstone_core::standard::Standard;
+  ///
+  /// let ctx = Context::default();
+  /// assert_eq!(Nist::validate_stateful(ctx, XMSS_SHA512_512), Err(LMS_SHA256_192));
+  /// ```
+  fn validate_stateful(ctx: Context, key: Stateful) -> Result<Stateful, Stateful> {
+    let security = ctx.security().max(key.security());
+    key.at_least(security).ok_or(match ctx.security() {
+      ..=192 => LMS_SHA256_192,
+      193.. => LMS_SHA256_256,
+    })
+  }
+
+  /// Limits a TDEA key to 2^20 blocks, as required by SP 800-67 Rev. 2
+  /// since the Sweet32 attack, and other ciphers with 64-bit blocks to
+  /// 2^32 blocks.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use wardstone_core::primitive::symmetric::{Mode, BLOWFISH, TDEA3};
+  /// use wardstone_core::standard::nist::Nist;
+  /// use wardstone_core::standard::Standard;
+  ///
+  /// assert_eq!(Nist::block_limit(TDEA3, Mode::Cbc), Some(1 << 20));
+  /// assert_eq!(Nist::block_limit(BLOWFISH, Mode::Cbc), Some(1 << 32));
+  /// ```
+  fn block_limit(key: Symmetric, mode: Mode) -> Option<u64> {
+    Hazard::of_cipher(key, mode)?;
+    if key == TDEA2 || key == TDEA3 {
+      Some(1 << 20)
+    } else {
+      Some(1 << 32)
+    }
+  }
+
+  fn rules() -> &'static [Rule] {
+    &RULES
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
-  use crate::{test_ecc, test_ffc, test_hash, test_hash_based, test_ifc, test_symmetric};
+  use crate::primitive::hybrid::Hybrid;
+  use crate::{
+    test_ecc, test_ffc, test_hash, test_hash_based, test_hash_brackets, test_hybrid, test_ifc,
+    test_pqc, test_stateful, test_symmetric,
+  };
 
   test_ecc!(p224, Nist, P224, Ok(P224));
   test_ecc!(p256, Nist, P256, Ok(P256));
@@ -429,6 +721,8 @@ mod tests {
   test_ecc!(p521, Nist, P521, Ok(P521));
   test_ecc!(ed25519, Nist, ED25519, Ok(P256));
   test_ecc!(ed448, Nist, ED448, Ok(P384));
+  test_ecc!(ed25519ph, Nist, ED25519PH, Ok(P256));
+  test_ecc!(ed448ph, Nist, ED448PH, Ok(P384));
   test_ecc!(x25519, Nist, X25519, Err(P256));
   test_ecc!(x448, Nist, X448, Err(P256));
   test_ecc!(brainpoolp224r1, Nist, BRAINPOOLP224R1, Ok(P224));
@@ -436,13 +730,56 @@ mod tests {
   test_ecc!(brainpoolp320r1, Nist, BRAINPOOLP320R1, Ok(P256));
   test_ecc!(brainpoolp384r1, Nist, BRAINPOOLP384R1, Ok(P384));
   test_ecc!(brainpoolp512r1, Nist, BRAINPOOLP512R1, Ok(P521));
+  test_ecc!(bls12_381, Nist, BLS12_381, Err(P256));
   test_ecc!(secp256k1, Nist, SECP256K1, Ok(P256));
+  test_ecc!(sm2, Nist, SM2, Err(P256));
 
   test_ffc!(ffc_1024_160, Nist, DSA_1024_160, Err(DSA_2048_224));
   test_ffc!(ffc_2048_224, Nist, DSA_2048_224, Ok(DSA_2048_224));
   test_ffc!(ffc_3072_256, Nist, DSA_3072_256, Ok(DSA_3072_256));
   test_ffc!(ffc_7680_384, Nist, DSA_7680_384, Ok(DSA_7680_384));
   test_ffc!(ffc_15360_512, Nist, DSA_15360_512, Ok(DSA_15360_512));
+  test_ffc!(
+    ffc_3072_224,
+    Nist,
+    Ffc::new(ID_DSA, 3072, 224),
+    Err(DSA_2048_224)
+  );
+  test_ffc!(
+    ffc_1024_256,
+    Nist,
+    Ffc::new(ID_DSA, 1024, 256),
+    Err(DSA_2048_224)
+  );
+
+  test_ffc!(ffdhe2048, Nist, FFDHE2048, Ok(FFDHE2048));
+  test_ffc!(ffdhe4096, Nist, FFDHE4096, Ok(FFDHE3072));
+  test_ffc!(ffdhe8192, Nist, FFDHE8192, Ok(FFDHE8192));
+  test_ffc!(modp_1024, Nist, MODP_1024, Err(FFDHE2048));
+  test_ffc!(modp_2048, Nist, MODP_2048, Ok(FFDHE2048));
+  test_ffc!(dh_2048_224, Nist, Ffc::new(ID_DH, 2048, 224), Ok(FFDHE2048));
+  test_ffc!(dh_4096, Nist, Ffc::new(ID_DH, 4096, 0), Err(FFDHE2048));
+
+  #[test]
+  fn dh_outlives_dsa() {
+    let ctx = Context::new(0, 2030);
+    assert_eq!(
+      Nist::validate_ffc(ctx, DSA_2048_224),
+      Err(FFC_NOT_SUPPORTED)
+    );
+    assert_eq!(Nist::validate_ffc(ctx, MODP_2048), Ok(FFDHE2048));
+    let ctx = Context::new(0, 2032);
+    assert_eq!(Nist::validate_ffc(ctx, MODP_2048), Err(FFDHE3072));
+    assert_eq!(Nist::validate_ffc(ctx, MODP_3072), Ok(FFDHE3072));
+  }
+
+  #[test]
+  fn dh_cites_key_establishment() {
+    let ctx = Context::default();
+    let rule = |key| Nist::assess_ffc(ctx, key).rule.map(|rule| rule.id);
+    assert_eq!(rule(DSA_2048_224), Some("NIST-FFC-001"));
+    assert_eq!(rule(FFDHE2048), Some("NIST-DH-001"));
+  }
 
   test_ifc!(ifc_1024, Nist, RSA_PSS_1024, Err(RSA_PSS_2048));
   test_ifc!(ifc_2048, Nist, RSA_PSS_2048, Ok(RSA_PSS_2048));
@@ -481,10 +818,10 @@ mod tests {
   test_hash!(sha224_collision_resistance, Nist, SHA224, Ok(SHA224));
   test_hash!(sha256_collision_resistance, Nist, SHA256, Ok(SHA256));
   test_hash!(sha384_collision_resistance, Nist, SHA384, Ok(SHA384));
-  test_hash!(sha3_224_collision_resistance, Nist, SHA3_224, Ok(SHA224));
-  test_hash!(sha3_256_collision_resistance, Nist, SHA3_256, Ok(SHA256));
-  test_hash!(sha3_384_collision_resistance, Nist, SHA3_384, Ok(SHA384));
-  test_hash!(sha3_512_collision_resistance, Nist, SHA3_512, Ok(SHA512));
+  test_hash!(sha3_224_collision_resistance, Nist, SHA3_224, Ok(SHA3_224));
+  test_hash!(sha3_256_collision_resistance, Nist, SHA3_256, Ok(SHA3_256));
+  test_hash!(sha3_384_collision_resistance, Nist, SHA3_384, Ok(SHA3_384));
+  test_hash!(sha3_512_collision_resistance, Nist, SHA3_512, Ok(SHA3_512));
   test_hash!(sha512_collision_resistance, Nist, SHA512, Ok(SHA512));
   test_hash!(
     sha512_224_collision_resistance,
@@ -498,8 +835,8 @@ mod tests {
     SHA512_256,
     Ok(SHA256)
   );
-  test_hash!(shake128_collision_resistance, Nist, SHAKE128, Err(SHA224));
-  test_hash!(shake256_collision_resistance, Nist, SHAKE256, Ok(SHA256));
+  test_hash!(shake128_collision_resistance, Nist, SHAKE128, Ok(SHA3_256));
+  test_hash!(shake256_collision_resistance, Nist, SHAKE256, Ok(SHA3_512));
 
   test_hash_based!(
     blake2b_256_pre_image_resistance,
@@ -537,10 +874,10 @@ mod tests {
   test_hash_based!(sha224_pre_image_resistance, Nist, SHA224, Ok(SHA224));
   test_hash_based!(sha256_pre_image_resistance, Nist, SHA256, Ok(SHA256));
   test_hash_based!(sha384_pre_image_resistance, Nist, SHA384, Ok(SHA384));
-  test_hash_based!(sha3_224_pre_image_resistance, Nist, SHA3_224, Ok(SHA224));
-  test_hash_based!(sha3_256_pre_image_resistance, Nist, SHA3_256, Ok(SHA256));
-  test_hash_based!(sha3_384_pre_image_resistance, Nist, SHA3_384, Ok(SHA384));
-  test_hash_based!(sha3_512_pre_image_resistance, Nist, SHA3_512, Ok(SHA512));
+  test_hash_based!(sha3_224_pre_image_resistance, Nist, SHA3_224, Ok(SHA3_224));
+  test_hash_based!(sha3_256_pre_image_resistance, Nist, SHA3_256, Ok(SHA3_256));
+  test_hash_based!(sha3_384_pre_image_resistance, Nist, SHA3_384, Ok(SHA3_384));
+  test_hash_based!(sha3_512_pre_image_resistance, Nist, SHA3_512, Ok(SHA3_512));
   test_hash_based!(sha512_pre_image_resistance, Nist, SHA512, Ok(SHA512));
   test_hash_based!(
     sha512_224_pre_image_resistance,
@@ -555,11 +892,115 @@ mod tests {
     Ok(SHA256)
   );
   test_hash_based!(shake128_pre_image_resistance, Nist, SHAKE128, Ok(SHAKE128));
-  test_hash_based!(shake256_pre_image_resistance, Nist, SHAKE256, Ok(SHA256));
+  test_hash_based!(shake256_pre_image_resistance, Nist, SHAKE256, Ok(SHA3_256));
 
   test_symmetric!(two_key_tdea, Nist, TDEA2, Err(AES128));
   test_symmetric!(three_key_tdea, Nist, TDEA3, Ok(AES128));
   test_symmetric!(aes128, Nist, AES128, Ok(AES128));
   test_symmetric!(aes192, Nist, AES192, Ok(AES192));
   test_symmetric!(aes256, Nist, AES256, Ok(AES256));
+
+  test_hash_brackets!(hash_brackets, Nist);
+
+  test_pqc!(ml_dsa_44, Nist, ML_DSA_44, Ok(ML_DSA_44));
+  test_pqc!(ml_dsa_65, Nist, ML_DSA_65, Ok(ML_DSA_65));
+  test_pqc!(ml_dsa_87, Nist, ML_DSA_87, Ok(ML_DSA_87));
+  test_pqc!(
+    slh_dsa_sha2_128s,
+    Nist,
+    SLH_DSA_SHA2_128S,
+    Ok(SLH_DSA_SHA2_128S)
+  );
+  test_pqc!(
+    slh_dsa_shake_256f,
+    Nist,
+    SLH_DSA_SHAKE_256F,
+    Ok(SLH_DSA_SHAKE_256F)
+  );
+  test_pqc!(pqc_not_allowed, Nist, PQC_NOT_ALLOWED, Err(ML_DSA_44));
+
+  test_stateful!(lms_sha256_192, Nist, LMS_SHA256_192, Ok(LMS_SHA256_192));
+  test_stateful!(
+    lms_shake256_256,
+    Nist,
+    LMS_SHAKE256_256,
+    Ok(LMS_SHAKE256_256)
+  );
+  test_stateful!(xmss_sha256_256, Nist, XMSS_SHA256_256, Ok(XMSS_SHA256_256));
+  test_stateful!(
+    xmss_shake256_192,
+    Nist,
+    XMSS_SHAKE256_192,
+    Ok(XMSS_SHAKE256_192)
+  );
+  test_stateful!(xmss_sha512_512, Nist, XMSS_SHA512_512, Err(LMS_SHA256_192));
+
+  test_hybrid!(
+    ml_dsa_65_p256,
+    Nist,
+    Hybrid::new(ML_DSA_65, P256),
+    Ok(Hybrid::new(ML_DSA_65, P256))
+  );
+  test_hybrid!(
+    ml_dsa_44_x25519,
+    Nist,
+    Hybrid::new(ML_DSA_44, X25519),
+    Ok(Hybrid::new(ML_DSA_44, X25519))
+  );
+  test_hybrid!(
+    pqc_not_allowed_p256,
+    Nist,
+    Hybrid::new(PQC_NOT_ALLOWED, P256),
+    Ok(Hybrid::new(PQC_NOT_ALLOWED, P256))
+  );
+  test_hybrid!(
+    pqc_not_allowed_x25519,
+    Nist,
+    Hybrid::new(PQC_NOT_ALLOWED, X25519),
+    Err(Hybrid::new(ML_DSA_44, P256))
+  );
+
+  #[test]
+  fn pqc_in_the_desired_family() {
+    let ctx = Context::new(192, 2030);
+    assert_eq!(Nist::validate_pqc(ctx, ML_DSA_44), Ok(ML_DSA_65));
+    assert_eq!(
+      Nist::validate_pqc(ctx, SLH_DSA_SHA2_128F),
+      Ok(SLH_DSA_SHA2_192F)
+    );
+    assert_eq!(
+      Nist::validate_stateful(ctx, XMSS_SHAKE256_192),
+      Ok(XMSS_SHAKE256_192)
+    );
+    let ctx = Context::new(256, 2030);
+    assert_eq!(
+      Nist::validate_stateful(ctx, XMSS_SHAKE256_192),
+      Ok(XMSS_SHAKE256_256)
+    );
+  }
+
+  #[test]
+  fn assess_asymmetric_cites_pqc_rules() {
+    use crate::primitive::asymmetric::Asymmetric;
+
+    let ctx = Context::default();
+    let id = |key: Asymmetric| Nist::assess_asymmetric(ctx, key).rule.map(|rule| rule.id);
+    assert_eq!(id(ML_DSA_65.into()), Some("NIST-PQC-001"));
+    assert_eq!(
+      id(Hybrid::new(ML_DSA_65, P256).into()),
+      Some("NIST-PQC-001")
+    );
+    assert_eq!(id(LMS_SHA256_192.into()), Some("NIST-HBS-001"));
+  }
+
+  #[test]
+  fn tdea_is_limited_to_fewer_blocks() {
+    use crate::standard::bsi::Bsi;
+
+    assert_eq!(Nist::block_limit(TDEA2, Mode::Cbc), Some(1 << 20));
+    assert_eq!(Bsi::block_limit(TDEA2, Mode::Cbc), Some(1 << 32));
+    assert_eq!(Nist::block_limit(IDEA, Mode::Cfb), Some(1 << 32));
+    assert_eq!(Nist::block_limit(TDEA3, Mode::Kw), None);
+    assert_eq!(Nist::block_limit(AES128, Mode::Gcm), None);
+  }
 }
@@ -0,0 +1,263 @@
+//! Describes the rules that a standard uses to assess primitives.
+//!
+//! The validation functions defined by each standard encode thresholds
+//! and cutoff years taken from the underlying publication. The types in
+//! this module record those same values along with a reference to the
+//! part of the publication they come from so that they can be cited
+//! without having to read the code.
+use std::fmt::{self, Display, Formatter};
+
+use serde::Serialize;
+
+use crate::context::Context;
+use crate::primitive::asymmetric::Asymmetric;
+use crate::primitive::ffc::Ffc;
+use crate::primitive::Security;
+
+/// The type of primitive a rule applies to.
+///
+/// Hash functions are split by application because some standards
+/// relax their requirements for applications that only rely on
+/// pre-image resistance. Hybrid signatures fall under [`Scope::Pqc`],
+/// as their post-quantum half is what sets them apart.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Scope {
+  Ecc,
+  Ffc,
+  Ifc,
+  Hash,
+  HashBased,
+  Symmetric,
+  Pqc,
+  Stateful,
+}
+
+impl Scope {
+  /// Every type of primitive in the order they are declared. Adding a
+  /// scope means growing the cutoff years kept by a context.
+  pub const ALL: [Scope; 8] = [
+    Self::Ecc,
+    Self::Ffc,
+    Self::Ifc,
+    Self::Hash,
+    Self::HashBased,
+    Self::Symmetric,
+    Self::Pqc,
+    Self::Stateful,
+  ];
+}
+
+impl Display for Scope {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    let name = match self {
+      Self::Ecc => "ecc",
+      Self::Ffc => "ffc",
+      Self::Ifc => "ifc",
+      Self::Hash => "hash",
+      Self::HashBased => "hash-based",
+      Self::Symmetric => "symmetric",
+      Self::Pqc => "pqc",
+      Self::Stateful => "stateful",
+    };
+    write!(f, "{}", name)
+  }
+}
+
+/// The use of a primitive that a rule is limited to, which tells apart
+/// rules that share a scope.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Usage {
+  Signature,
+  KeyAgreement,
+}
+
+/// A reference to the part of a publication a rule is based on.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+pub struct Citation {
+  /// The short name of the publication.
+  pub document: &'static str,
+  /// The section, table, or page within the publication.
+  pub section: &'static str,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub url: Option<&'static str>,
+}
+
+impl Display for Citation {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "{}, {}", self.document, self.section)?;
+    if let Some(url) = self.url {
+      write!(f, " <{}>", url)?;
+    }
+    Ok(())
+  }
+}
+
+/// A summary of how a standard assesses a type of primitive.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+pub struct Rule {
+  /// A stable identifier such as `NIST-HASH-001` that findings based on
+  /// the rule can be referred to by. It is never reused or changed once
+  /// published.
+  pub id: &'static str,
+  pub scope: Scope,
+  /// The use the rule is limited to or `None` if it applies to every
+  /// primitive in its scope.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub usage: Option<Usage>,
+  /// The minimum security accepted using the default context or `None`
+  /// if this is derived from the year in which the primitive is
+  /// expected to remain secure.
+  pub minimum: Option<Security>,
+  /// The last year in which the minimum security is accepted, if the
+  /// standard specifies one.
+  pub cutoff: Option<u16>,
+  /// A human readable description of the thresholds.
+  pub summary: &'static str,
+  pub citation: Citation,
+}
+
+impl Rule {
+  /// Returns the cutoff year of the rule unless the context replaces
+  /// it, or `None` if the standard does not specify one.
+  pub const fn cutoff_in(&self, ctx: Context) -> Option<u16> {
+    match self.cutoff {
+      Some(cutoff) => Some(ctx.cutoff(self.scope, cutoff)),
+      None => None,
+    }
+  }
+}
+
+/// Returns the rule in `rules` that applies to a finite field primitive.
+///
+/// Both DSA domain parameters and the groups used for key establishment
+/// fall under [`Scope::Ffc`], which keeps a single cutoff year for them
+/// in a context. A standard that judges the two apart limits its rules
+/// to a [`Usage`] each, while one that does not has a single rule for
+/// both.
+pub fn ffc_rule(rules: &'static [Rule], key: Ffc) -> Option<&'static Rule> {
+  let usage = if key.is_key_agreement() {
+    Usage::KeyAgreement
+  } else {
+    Usage::Signature
+  };
+  let rules = || rules.iter().filter(|rule| rule.scope == Scope::Ffc);
+  rules()
+    .find(|rule| rule.usage == Some(usage))
+    .or_else(|| rules().find(|rule| rule.usage.is_none()))
+}
+
+/// Returns the rule in `rules` that applies to an asymmetric key
+/// primitive, which for finite field primitives depends on their use
+/// (see [`ffc_rule`]).
+pub fn asymmetric_rule(rules: &'static [Rule], key: Asymmetric) -> Option<&'static Rule> {
+  let scope = match key {
+    Asymmetric::Ecc(_) => Scope::Ecc,
+    Asymmetric::Ffc(ffc) => return ffc_rule(rules, ffc),
+    Asymmetric::Ifc(_) => Scope::Ifc,
+    Asymmetric::Pqc(_) | Asymmetric::Hybrid(_) => Scope::Pqc,
+    Asymmetric::Stateful(_) => Scope::Stateful,
+  };
+  rules.iter().find(|rule| rule.scope == scope)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::standard::bsi::Bsi;
+  use crate::standard::cnsa::Cnsa;
+  use crate::standard::ecrypt::Ecrypt;
+  use crate::standard::iot::Iot;
+  use crate::standard::lenstra::Lenstra;
+  use crate::standard::nist::Nist;
+  use crate::standard::testing::strong::Strong;
+  use crate::standard::Standard;
+
+  fn documents_primitives<T: Standard>() -> bool {
+    [
+      Scope::Ecc,
+      Scope::Ffc,
+      Scope::Ifc,
+      Scope::Hash,
+      Scope::Symmetric,
+    ]
+    .into_iter()
+    .all(|scope| T::rule(scope).is_some())
+  }
+
+  #[test]
+  fn rules_cover_primitives() {
+    assert!(documents_primitives::<Bsi>());
+    assert!(documents_primitives::<Cnsa>());
+    assert!(documents_primitives::<Ecrypt>());
+    assert!(documents_primitives::<Iot>());
+    assert!(documents_primitives::<Lenstra>());
+    assert!(documents_primitives::<Nist>());
+  }
+
+  fn identifies_rules<T: Standard>(prefix: &str) -> bool {
+    T::rules().iter().all(|rule| {
+      let mut parts = rule.id.splitn(3, '-');
+      parts.next() == Some(prefix)
+        && parts.next().is_some_and(|scope| !scope.is_empty())
+        && parts
+          .next()
+          .is_some_and(|n| n.len() == 3 && n.bytes().all(|b| b.is_ascii_digit()))
+    })
+  }
+
+  #[test]
+  fn rules_have_stable_ids() {
+    assert!(identifies_rules::<Bsi>("BSI"));
+    assert!(identifies_rules::<Cnsa>("CNSA"));
+    assert!(identifies_rules::<Ecrypt>("ECRYPT"));
+    assert!(identifies_rules::<Iot>("IOT"));
+    assert!(identifies_rules::<Lenstra>("LENSTRA"));
+    assert!(identifies_rules::<Nist>("NIST"));
+    let mut ids: Vec<_> = [
+      Bsi::rules(),
+      Cnsa::rules(),
+      Ecrypt::rules(),
+      Iot::rules(),
+      Lenstra::rules(),
+      Nist::rules(),
+    ]
+    .concat()
+    .into_iter()
+    .map(|rule| rule.id)
+    .collect();
+    let n = ids.len();
+    ids.sort_unstable();
+    ids.dedup();
+    assert_eq!(ids.len(), n);
+  }
+
+  #[test]
+  fn scopes_are_in_order() {
+    assert!(Scope::ALL
+      .iter()
+      .enumerate()
+      .all(|(i, scope)| *scope as usize == i));
+  }
+
+  #[test]
+  fn ffc_rules_by_usage() {
+    use crate::primitive::ffc::{DSA_2048_224, FFDHE2048};
+
+    let id = |key| ffc_rule(Nist::rules(), key).map(|rule| rule.id);
+    assert_eq!(id(DSA_2048_224), Some("NIST-FFC-001"));
+    assert_eq!(id(FFDHE2048), Some("NIST-DH-001"));
+    // The rules of a standard that does not tell the uses apart apply to
+    // both.
+    let id = |key| ffc_rule(Ecrypt::rules(), key).map(|rule| rule.id);
+    assert_eq!(id(DSA_2048_224), Some("ECRYPT-FFC-001"));
+    assert_eq!(id(FFDHE2048), Some("ECRYPT-FFC-001"));
+  }
+
+  #[test]
+  fn rules_mock_standard() {
+    assert!(Strong::rules().is_empty());
+  }
+}
@@ -0,0 +1,166 @@
+//! Tabulate the thresholds of a standard and the primitives it accepts.
+//!
+//! The tables are built by running the validation functions of the
+//! standard over every primitive in the [`registry`](crate::registry)
+//! and every post-quantum and stateful hash-based parameter set, so that documentation and compliance tools generated from them
+//! always agree with the assessments of the library.
+//!
+//! ```
+//! use wardstone_core::context::Context;
+//! use wardstone_core::standard::cnsa::Cnsa;
+//! use wardstone_core::standard::rule::Scope;
+//! use wardstone_core::standard::table::Table;
+//!
+//! let table = Table::of::<Cnsa>(Context::default());
+//! let hash = table.sections.iter().find(|s| s.rule.scope == Scope::Hash).unwrap();
+//! assert_eq!(hash.allowed.as_deref(), Some(&["sha384", "sha512"][..]));
+//! ```
+use serde::Serialize;
+
+use crate::context::Context;
+use crate::primitive::{pqc, stateful};
+use crate::registry;
+use crate::standard::rule::{self, Rule, Scope};
+use crate::standard::Standard;
+
+/// A rule of a standard along with the built-in primitives it accepts.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Section {
+  #[serde(flatten)]
+  pub rule: &'static Rule,
+  /// The names of the built-in primitives that comply with the rule in
+  /// the context the table was built in, or `None` if the standard
+  /// assesses primitives under this rule outside of [`Standard`], such
+  /// as hash functions used in hash-based applications.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub allowed: Option<Vec<&'static str>>,
+}
+
+/// The rules of a standard, in the order the standard lists them.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Table {
+  pub sections: Vec<Section>,
+}
+
+impl Table {
+  /// Builds the table of the standard `S` in the given context.
+  ///
+  /// Standards that are not derived from a publication have no rules
+  /// and therefore an empty table.
+  pub fn of<S: Standard>(ctx: Context) -> Self {
+    let mut sections: Vec<Section> = S::rules()
+      .iter()
+      .map(|rule| Section {
+        rule,
+        allowed: (rule.scope != Scope::HashBased).then(Vec::new),
+      })
+      .collect();
+    let mut allow = |rule: Option<&'static Rule>, name| {
+      let section = rule.and_then(|rule| sections.iter_mut().find(|s| s.rule == rule));
+      if let Some(allowed) = section.and_then(|s| s.allowed.as_mut()) {
+        allowed.push(name);
+      }
+    };
+    for e in registry::CURVES.iter() {
+      if S::validate_ecc(ctx, e.primitive).is_ok() {
+        allow(S::rule(Scope::Ecc), e.name);
+      }
+    }
+    for e in registry::FFC.iter() {
+      if S::validate_ffc(ctx, e.primitive).is_ok() {
+        allow(rule::ffc_rule(S::rules(), e.primitive), e.name);
+      }
+    }
+    for e in registry::IFC.iter() {
+      if S::validate_ifc(ctx, e.primitive).is_ok() {
+        allow(S::rule(Scope::Ifc), e.name);
+      }
+    }
+    for e in registry::HASH_FUNCTIONS.iter() {
+      if S::validate_hash(ctx, e.primitive).is_ok() {
+        allow(S::rule(Scope::Hash), e.name);
+      }
+    }
+    for e in registry::SYMMETRIC.iter() {
+      if S::validate_symmetric(ctx, e.primitive).is_ok() {
+        allow(S::rule(Scope::Symmetric), e.name);
+      }
+    }
+    for key in pqc::PARAMETER_SETS {
+      if S::validate_pqc(ctx, key).is_ok() {
+        allow(S::rule(Scope::Pqc), key.name());
+      }
+    }
+    for key in stateful::PARAMETER_SETS {
+      if S::validate_stateful(ctx, key).is_ok() {
+        allow(S::rule(Scope::Stateful), key.name());
+      }
+    }
+    Self { sections }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::standard::lenstra::Lenstra;
+  use crate::standard::nist::Nist;
+  use crate::standard::testing::weak::Weak;
+
+  #[test]
+  fn follows_rules() {
+    let table = Table::of::<Nist>(Context::default());
+    let ids: Vec<_> = table.sections.iter().map(|s| s.rule.id).collect();
+    let rules: Vec<_> = Nist::rules().iter().map(|rule| rule.id).collect();
+    assert_eq!(ids, rules);
+  }
+
+  #[test]
+  fn agrees_with_validators() {
+    let ctx = Context::default();
+    let table = Table::of::<Lenstra>(ctx);
+    let ifc = table
+      .sections
+      .iter()
+      .find(|s| s.rule.scope == Scope::Ifc)
+      .and_then(|s| s.allowed.as_ref())
+      .unwrap();
+    for e in registry::IFC.iter() {
+      let ok = Lenstra::validate_ifc(ctx, e.primitive).is_ok();
+      assert_eq!(ifc.contains(&e.name), ok, "{}", e.name);
+    }
+  }
+
+  #[test]
+  fn hash_based_is_not_listed() {
+    let table = Table::of::<Nist>(Context::default());
+    let section = table
+      .sections
+      .iter()
+      .find(|s| s.rule.scope == Scope::HashBased)
+      .unwrap();
+    assert_eq!(section.allowed, None);
+  }
+
+  #[test]
+  fn lists_post_quantum_parameter_sets() {
+    use crate::standard::cnsa::Cnsa;
+
+    let table = Table::of::<Cnsa>(Context::default());
+    let allowed = |scope| {
+      table
+        .sections
+        .iter()
+        .find(|s| s.rule.scope == scope)
+        .and_then(|s| s.allowed.clone())
+        .unwrap()
+    };
+    assert_eq!(allowed(Scope::Pqc), ["ML-DSA-87"]);
+    assert_eq!(allowed(Scope::Stateful).len(), 8);
+  }
+
+  #[test]
+  fn no_rules() {
+    assert!(Table::of::<Weak>(Context::default()).sections.is_empty());
+  }
+}
@@ -6,6 +6,7 @@
 //! as of writing does not appear to be a practical concern. However,
 //! bumping the security parameter may not be enough for some signature
 //! schemes such as those that use elliptic curves.
+use crate::advisory::Hazards;
 use crate::context::Context;
 use crate::primitive::ecc::*;
 use crate::primitive::ffc::*;
@@ -21,6 +22,12 @@ use crate::standard::Standard;
 pub struct Strong;
 
 impl Standard for Strong {
+  /// Raises no advisories, since the mock standard is only concerned
+  /// with security levels.
+  fn hazards() -> Hazards {
+    Hazards::NONE
+  }
+
   /// Validate an elliptic curve cryptography primitive used for digital
   /// signatures and key establishment.
   ///
@@ -222,6 +229,7 @@ mod tests {
     BRAINPOOLP512R1,
     Err(ECC_NOT_ALLOWED)
   );
+  test_ecc!(bls12_381, Strong, BLS12_381, Err(ECC_NOT_ALLOWED));
   test_ecc!(secp256k1, Strong, SECP256K1, Err(ECC_NOT_ALLOWED));
 
   test_ffc!(ffc_1024_160, Strong, DSA_1024_160, Err(FFC_NOT_SUPPORTED));
@@ -263,7 +271,7 @@ mod tests {
   test_hash!(sha512_224, Strong, SHA512_224, Err(SHA512));
   test_hash!(sha512_256, Strong, SHA512_256, Err(SHA512));
   test_hash!(shake128, Strong, SHAKE128, Err(SHA512));
-  test_hash!(shake256, Strong, SHAKE256, Err(SHA512));
+  test_hash!(shake256, Strong, SHAKE256, Ok(SHA512));
   test_hash!(whirlpool, Strong, WHIRLPOOL, Ok(SHA512));
 
   test_symmetric!(aes128, Strong, AES128, Err(AES256));
@@ -6,6 +6,7 @@
 //! SHA1. For secure applications use any of the other standards defined
 //! in this crate.
 
+use crate::advisory::Hazards;
 use crate::context::Context;
 use crate::primitive::ecc::*;
 use crate::primitive::ffc::*;
@@ -21,6 +22,12 @@ use crate::standard::Standard;
 pub struct Weak;
 
 impl Standard for Weak {
+  /// Raises no advisories, since the mock standard is only concerned
+  /// with security levels.
+  fn hazards() -> Hazards {
+    Hazards::NONE
+  }
+
   /// Validate an elliptic curve cryptography primitive used for digital
   /// signatures and key establishment.
   ///
@@ -231,6 +238,7 @@ mod tests {
   test_ecc!(brainpoolp320r1, Weak, BRAINPOOLP320R1, Ok(BRAINPOOLP320R1));
   test_ecc!(brainpoolp384r1, Weak, BRAINPOOLP384R1, Ok(P384));
   test_ecc!(brainpoolp512r1, Weak, BRAINPOOLP512R1, Ok(BRAINPOOLP512R1));
+  test_ecc!(bls12_381, Weak, BLS12_381, Ok(ED25519));
   test_ecc!(secp256k1, Weak, SECP256K1, Ok(ED25519));
 
   test_ffc!(ffc_1024_160, Weak, DSA_1024_160, Ok(DSA_1024_160));
@@ -271,8 +279,8 @@ mod tests {
   test_hash!(sha512, Weak, SHA512, Ok(BLAKE2B_512));
   test_hash!(sha512_224, Weak, SHA512_224, Ok(SHA224));
   test_hash!(sha512_256, Weak, SHA512_256, Ok(BLAKE3));
-  test_hash!(shake128, Weak, SHAKE128, Ok(SHAKE128));
-  test_hash!(shake256, Weak, SHAKE256, Ok(BLAKE3));
+  test_hash!(shake128, Weak, SHAKE128, Ok(BLAKE3));
+  test_hash!(shake256, Weak, SHAKE256, Ok(BLAKE2B_512));
   test_hash!(whirlpool, Weak, WHIRLPOOL, Ok(BLAKE2B_512));
 
   test_symmetric!(aes128, Weak, AES128, Ok(AES128));
@@ -1 +1,132 @@
+//! Helpers shared by the standards.
+use crate::primitive::ffc::*;
+use crate::primitive::hash::*;
+use crate::primitive::{Primitive, Security};
+
 pub mod testing;
+
+/// The SHA-3 functions with the same digest length as SHA-2 functions.
+static SHA3_EQUIVALENTS: [(Hash, Hash); 6] = [
+  (SHA224, SHA3_224),
+  (SHA256, SHA3_256),
+  (SHA384, SHA3_384),
+  (SHA512, SHA3_512),
+  (SHA512_224, SHA3_224),
+  (SHA512_256, SHA3_256),
+];
+
+/// Recommends the SHA-3 function with the same digest length in place
+/// of a SHA-2 function when `hash` belongs to the SHA-3 family and the
+/// standard specifies that function.
+///
+/// The recommendations of a standard are expressed in terms of SHA-2
+/// but telling users of SHA-3 to switch back to SHA-2 would be
+/// unwarranted when a SHA-3 function of the same strength complies.
+pub(crate) fn prefer_sha3(
+  hash: Hash,
+  verdict: Result<Hash, Hash>,
+  specified: &[Hash],
+) -> Result<Hash, Hash> {
+  if !hash.is_sha3() {
+    return verdict;
+  }
+  let prefer = |want: Hash| {
+    SHA3_EQUIVALENTS
+      .iter()
+      .find(|&&(sha2, sha3)| sha2 == want && specified.contains(&sha3))
+      .map_or(want, |&(_, sha3)| sha3)
+  };
+  verdict.map(prefer).map_err(prefer)
+}
+
+/// The named groups used for key establishment, one for every modulus
+/// length, from the shortest to the longest.
+static GROUPS: [Ffc; 6] = [
+  MODP_1024, FFDHE2048, FFDHE3072, FFDHE4096, FFDHE6144, FFDHE8192,
+];
+
+/// Recommends the shortest named group used for key establishment with
+/// a modulus at least as long in place of DSA domain parameters when
+/// `key` is such a group.
+///
+/// Standards that judge both by their security alone express their
+/// recommendations as DSA domain parameters, but telling users of
+/// Diffie-Hellman to switch to DSA would be meaningless. Moduli longer
+/// than every named group are recommended as a custom group.
+pub(crate) fn prefer_group(key: Ffc, verdict: Result<Ffc, Ffc>) -> Result<Ffc, Ffc> {
+  if !key.is_key_agreement() {
+    return verdict;
+  }
+  let prefer = |want: Ffc| {
+    if want == FFC_NOT_SUPPORTED || want.is_key_agreement() {
+      return want;
+    }
+    GROUPS
+      .iter()
+      .copied()
+      .find(|group| group.l >= want.l)
+      .unwrap_or(Ffc::new(ID_DH, want.l, 0))
+  };
+  verdict.map(prefer).map_err(prefer)
+}
+
+/// Rejects a primitive whose parameters are inconsistent, such as a
+/// finite field modulus paired with a divisor too short for it, even if
+/// its security would otherwise be accepted. The recommendation is kept
+/// since it is a consistent primitive of the security of `key`.
+pub(crate) fn reject_inconsistent<T: Primitive>(key: T, verdict: Result<T, T>) -> Result<T, T> {
+  if key.is_consistent() {
+    verdict
+  } else {
+    Err(verdict.unwrap_or_else(|alternative| alternative))
+  }
+}
+
+/// The kinds of application a hash function is used in, from the least
+/// to the most demanding.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub(crate) enum Application {
+  /// Message authentication codes, key derivation functions and random
+  /// bit generation, which rely on pre-image resistance.
+  PreImage,
+  /// Digital signatures and others that rely on collision resistance.
+  Collision,
+}
+
+impl Application {
+  /// Returns the security that `hash` offers in the application.
+  pub(crate) fn strength(self, hash: Hash) -> Security {
+    match self {
+      // The digest length for hash functions and the security strength
+      // for extendable-output functions.
+      Self::PreImage => hash.n,
+      Self::Collision => hash.security(),
+    }
+  }
+}
+
+/// Returns the weakest hash function in `table` that is recommended for
+/// `application` and offers at least `security` in it, or the strongest
+/// recommended one if none does.
+///
+/// A table lists the hash functions a standard recommends from the
+/// weakest to the strongest, each with the most demanding application
+/// it is recommended for. Deriving the recommendations for every
+/// application from the one table keeps them from drifting apart.
+pub(crate) fn recommend_hash(
+  table: &[(Hash, Application)],
+  application: Application,
+  security: Security,
+) -> Hash {
+  let mut recommended = table
+    .iter()
+    .filter(|&&(_, most)| most >= application)
+    .map(|&(hash, _)| hash);
+  let strongest = recommended.clone().next_back();
+  recommended
+    .find(|&hash| application.strength(hash) >= security)
+    .or(strongest)
+    // Every table of a standard recommends a hash function for every
+    // application, so this is only reached by an empty table.
+    .unwrap_or(SHA512)
+}
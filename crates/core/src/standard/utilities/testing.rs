@@ -77,3 +77,73 @@ macro_rules! test_symmetric {
     }
   };
 }
+
+/// Expands a unit test for a post-quantum signature primitive.
+#[macro_export]
+macro_rules! test_pqc {
+  ($name:ident, $standard:ident, $input:expr, $want:expr) => {
+    #[test]
+    fn $name() {
+      use $crate::context::Context;
+      let ctx = Context::default();
+      assert_eq!($standard::validate_pqc(ctx, $input), $want);
+    }
+  };
+}
+
+/// Expands a unit test for a hybrid signature primitive.
+#[macro_export]
+macro_rules! test_hybrid {
+  ($name:ident, $standard:ident, $input:expr, $want:expr) => {
+    #[test]
+    fn $name() {
+      use $crate::context::Context;
+      let ctx = Context::default();
+      assert_eq!($standard::validate_hybrid(ctx, $input), $want);
+    }
+  };
+}
+
+/// Expands a unit test for a stateful hash-based signature primitive.
+#[macro_export]
+macro_rules! test_stateful {
+  ($name:ident, $standard:ident, $input:expr, $want:expr) => {
+    #[test]
+    fn $name() {
+      use $crate::context::Context;
+      let ctx = Context::default();
+      assert_eq!($standard::validate_stateful(ctx, $input), $want);
+    }
+  };
+}
+
+/// Expands a unit test that checks that the hash functions a standard
+/// recommends for applications that rely on collision resistance are
+/// no weaker in pre-image resistance than those it recommends for
+/// applications that only rely on the latter, and that both meet the
+/// required security whenever a recommended function can.
+#[macro_export]
+macro_rules! test_hash_brackets {
+  ($name:ident, $standard:ident) => {
+    #[test]
+    fn $name() {
+      use $crate::context::Context;
+      use $crate::primitive::hash::SHA512;
+      let strongest = |verdict: Result<Hash, Hash>| verdict.unwrap_or_else(|hash| hash);
+      for security in 0..=600 {
+        let ctx = Context::new(security, 2023);
+        let collision = strongest($standard::validate_hash(ctx, SHA512));
+        let pre_image = strongest($standard::validate_hash_based(ctx, SHA512));
+        assert!(
+          collision.n >= pre_image.n,
+          "{}: {} < {}",
+          security,
+          collision,
+          pre_image
+        );
+        assert!(collision.security() >= security.min(SHA512.security()));
+        assert!(pre_image.n >= security.min(SHA512.n));
+      }
+    }
+  };
+}
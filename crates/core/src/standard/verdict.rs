@@ -0,0 +1,531 @@
+//! Describes the outcome of validating a primitive against a standard.
+use std::fmt::{self, Display, Formatter};
+
+use serde::{Serialize, Serializer};
+
+use crate::context::Context;
+use crate::locale::{Locale, Rationale, Reason};
+use crate::primitive::{Primitive, Security};
+use crate::standard::rule::{Citation, Rule, Scope};
+
+/// How serious a finding is, from least to most severe.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Severity {
+  /// The primitive is compliant.
+  Info,
+  /// The primitive is compliant but will not be after the cutoff year
+  /// of the rule.
+  Legacy,
+  /// The primitive was compliant until the cutoff year of the rule.
+  Deprecated,
+  /// The primitive does not meet the requirements of the rule.
+  NonCompliant,
+}
+
+impl Display for Severity {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    let name = match self {
+      Self::Info => "info",
+      Self::Legacy => "legacy",
+      Self::Deprecated => "deprecated",
+      Self::NonCompliant => "non-compliant",
+    };
+    write!(f, "{}", name)
+  }
+}
+
+fn serialize_id<S: Serializer>(
+  rule: &Option<&'static Rule>,
+  serializer: S,
+) -> Result<S::Ok, S::Error> {
+  match rule {
+    Some(rule) => serializer.serialize_some(rule.id),
+    None => serializer.serialize_none(),
+  }
+}
+
+/// The outcome of validating a primitive against a standard.
+///
+/// It holds the same information as the `Result` returned by the
+/// validation functions along with a reference to the part of the
+/// publication the outcome is based on, so that a report can be checked
+/// against its source.
+///
+/// Verdicts are small enough to copy and refer to rules kept in static
+/// tables, so assessing a primitive never allocates. Only explaining a
+/// verdict with [`Verdict::rationale`] does.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+pub struct Verdict<T> {
+  pub compliant: bool,
+  pub severity: Severity,
+  /// The recommended primitive that one should use instead, or the
+  /// primitive with the desired security level if it is already
+  /// compliant.
+  pub alternative: T,
+  /// The part of the publication the rule is based on or `None` if the
+  /// standard does not document its rules, as is the case for the mock
+  /// standards used for testing.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub citation: Option<Citation>,
+  /// The rule the outcome is based on, which is used to explain it. It
+  /// is serialised as the identifier of the rule.
+  #[serde(
+    rename = "rule_id",
+    serialize_with = "serialize_id",
+    skip_serializing_if = "Option::is_none"
+  )]
+  pub rule: Option<&'static Rule>,
+  /// The bits of security the primitive offers or `None` if the verdict
+  /// was created from a result alone with [`Verdict::new`].
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub security: Option<Security>,
+  /// The bits of security the rule and context require, which makes
+  /// the gap to [`Verdict::security`] quantitative. It is `None` when
+  /// neither sets a minimum and the primitive is accepted, or when no
+  /// primitive would meet the requirement.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub required: Option<Security>,
+}
+
+impl<T> Verdict<T> {
+  /// Creates a verdict from the result of a validation function and the
+  /// rule it applies.
+  ///
+  /// The severity is either [`Severity::Info`] or
+  /// [`Severity::NonCompliant`] since the result alone does not tell
+  /// whether the cutoff year of the rule matters. Use
+  /// [`Verdict::validate`] to tell them apart.
+  pub fn new(result: Result<T, T>, rule: Option<&'static Rule>) -> Self {
+    let (compliant, severity, alternative) = match result {
+      Ok(alternative) => (true, Severity::Info, alternative),
+      Err(alternative) => (false, Severity::NonCompliant, alternative),
+    };
+    Self {
+      compliant,
+      severity,
+      alternative,
+      citation: rule.map(|rule| rule.citation),
+      rule,
+      security: None,
+      required: None,
+    }
+  }
+
+  /// Validates a primitive with the validation function `f` and grades
+  /// the outcome using the cutoff year of the rule it applies.
+  ///
+  /// A compliant primitive is [`Severity::Legacy`] if it fails when
+  /// validated the year after the cutoff, and a non-compliant one is
+  /// [`Severity::Deprecated`] if it passes when validated in the cutoff
+  /// year.
+  ///
+  /// The verdict also records the security the primitive offers and the
+  /// security required of it, if known.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use wardstone_core::context::Context;
+  /// use wardstone_core::primitive::ifc::RSA_PKCS1_2048;
+  /// use wardstone_core::standard::nist::Nist;
+  /// use wardstone_core::standard::Standard;
+  ///
+  /// let ctx = Context::default().with_security(128);
+  /// let verdict = Nist::assess_ifc(ctx, RSA_PKCS1_2048);
+  /// assert_eq!(verdict.security, Some(112));
+  /// assert_eq!(verdict.required, Some(128));
+  /// ```
+  pub fn validate<F>(ctx: Context, primitive: T, f: F, rule: Option<&'static Rule>) -> Self
+  where
+    T: Copy + Primitive,
+    F: Fn(Context, T) -> Result<T, T>,
+  {
+    let mut verdict = Self::new(f(ctx, primitive), rule);
+    let security = primitive.security();
+    verdict.security = Some(security);
+    verdict.required = verdict.requirement(ctx, security);
+    if let Some(cutoff) = rule.and_then(|rule| rule.cutoff_in(ctx)) {
+      let year = ctx.year();
+      let later = ctx.with_year(cutoff.saturating_add(1));
+      let until = ctx.with_year(cutoff);
+      if verdict.compliant && year <= cutoff && f(later, primitive).is_err() {
+        verdict.severity = Severity::Legacy;
+      } else if !verdict.compliant && year > cutoff && f(until, primitive).is_ok() {
+        verdict.severity = Severity::Deprecated;
+      }
+    }
+    verdict
+  }
+
+  /// Returns the security required of a primitive offering `security`
+  /// bits, which is the minimum set by the rule or the context.
+  /// Standards that do not set a minimum derive it from the year, in
+  /// which case the security of the alternative to a non-compliant
+  /// primitive is the one required.
+  fn requirement(&self, ctx: Context, security: Security) -> Option<Security>
+  where
+    T: Primitive,
+  {
+    let minimum = self.rule.and_then(|rule| rule.minimum).unwrap_or_default();
+    let minimum = minimum.max(ctx.security());
+    if minimum > 0 {
+      return Some(minimum);
+    }
+    let wanted = self.alternative.security();
+    let derived = !self.compliant && !self.alternative.is_placeholder() && wanted > security;
+    derived.then_some(wanted)
+  }
+
+  /// Returns the verdict in the form used by the validation functions.
+  pub fn into_result(self) -> Result<T, T> {
+    if self.compliant {
+      Ok(self.alternative)
+    } else {
+      Err(self.alternative)
+    }
+  }
+}
+
+impl<T: Display + Primitive> Verdict<T> {
+  /// Explains the verdict on `primitive` in a sentence such as "sha1
+  /// offers at most 80 bits of collision resistance, below the 112-bit
+  /// minimum required by NIST SP 800-57 Part 1 Rev. 5; use sha224".
+  ///
+  /// The context must be the one the primitive was validated with.
+  ///
+  /// # Example
+  ///
+  /// The following example explains why SHA-1 is not compliant with the
+  /// NIST guidelines.
+  ///
+  /// ```
+  /// use wardstone_core::context::Context;
+  /// use wardstone_core::primitive::hash::SHA1;
+  /// use wardstone_core::standard::nist::Nist;
+  /// use wardstone_core::standard::Standard;
+  ///
+  /// let ctx = Context::default();
+  /// let verdict = Nist::assess_hash(ctx, SHA1);
+  /// assert!(verdict.rationale(ctx, SHA1).starts_with("sha1 offers at most 80 bits"));
+  /// ```
+  pub fn rationale(&self, ctx: Context, primitive: T) -> String {
+    self.rationale_in(ctx, primitive, Locale::English)
+  }
+
+  /// Explains the verdict on `primitive` like [`Verdict::rationale`] in
+  /// the language of `locale`.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use wardstone_core::context::Context;
+  /// use wardstone_core::locale::Locale;
+  /// use wardstone_core::primitive::hash::SHA1;
+  /// use wardstone_core::standard::nist::Nist;
+  /// use wardstone_core::standard::Standard;
+  ///
+  /// let ctx = Context::default();
+  /// let verdict = Nist::assess_hash(ctx, SHA1);
+  /// let rationale = verdict.rationale_in(ctx, SHA1, Locale::German);
+  /// assert!(rationale.starts_with("sha1 bietet höchstens 80 Bit"));
+  /// ```
+  pub fn rationale_in(&self, ctx: Context, primitive: T, locale: Locale) -> String {
+    let security = primitive.security();
+    let minimum = self.rule.and_then(|rule| rule.minimum).unwrap_or_default();
+    let minimum = minimum.max(ctx.security());
+    let cutoff = self.rule.and_then(|rule| rule.cutoff_in(ctx));
+    let reason = if self.compliant {
+      let until = cutoff.filter(|_| self.severity == Severity::Legacy);
+      if minimum > 0 {
+        Reason::Meets { minimum, until }
+      } else {
+        Reason::Accepted { until }
+      }
+    } else {
+      let wanted = self.alternative.security();
+      match cutoff {
+        _ if !primitive.is_consistent() => Reason::Inconsistent,
+        _ if security < minimum => Reason::Below { minimum },
+        Some(cutoff) if ctx.year() > cutoff => Reason::Expired { cutoff },
+        // Standards that do not set a minimum derive it from the year the
+        // primitive is expected to remain secure.
+        _ if security < wanted => Reason::BelowIn {
+          minimum: wanted,
+          year: ctx.year(),
+        },
+        _ => Reason::NotApproved,
+      }
+    };
+    locale.rationale(&Rationale {
+      primitive: primitive.to_string(),
+      // The collision resistance of a hash function is a generic bound
+      // that attacks on a specific function can lower.
+      hash: self.rule.is_some_and(|rule| rule.scope == Scope::Hash),
+      security,
+      document: self.citation.map(|citation| citation.document),
+      reason,
+      alternative: self.alternative.to_string(),
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::alloc::{GlobalAlloc, Layout, System};
+  use std::cell::Cell;
+
+  use super::*;
+  use crate::context::Context;
+  use crate::locale::Locale;
+  use crate::primitive::asymmetric::Asymmetric;
+  use crate::primitive::ecc::{P256, SECP112R1};
+  use crate::primitive::ffc::{Ffc, ID_DSA};
+  use crate::primitive::hash::{SHA1, SHA256};
+  use crate::primitive::ifc::{RSA_PKCS1_1024, RSA_PKCS1_2048, RSA_PKCS1_3072};
+  use crate::primitive::symmetric::AES128;
+  use crate::standard::bsi::Bsi;
+  use crate::standard::cnsa::Cnsa;
+  use crate::standard::ecrypt::Ecrypt;
+  use crate::standard::iot::Iot;
+  use crate::standard::lenstra::Lenstra;
+  use crate::standard::nist::Nist;
+  use crate::standard::rule::Scope;
+  use crate::standard::testing::strong::Strong;
+  use crate::standard::Standard;
+
+  thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+  }
+
+  /// Counts the allocations made by every thread so that a test can
+  /// check its own without being disturbed by those running alongside.
+  struct Counting;
+
+  unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+      let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+      System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+      System.dealloc(ptr, layout)
+    }
+  }
+
+  #[global_allocator]
+  static ALLOCATOR: Counting = Counting;
+
+  fn allocations<R>(f: impl FnOnce() -> R) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    std::hint::black_box(f());
+    ALLOCATIONS.with(Cell::get) - before
+  }
+
+  fn assess_every_primitive<T: Standard>(ctx: Context) {
+    T::assess_ecc(ctx, P256);
+    T::assess_hash(ctx, SHA1);
+    T::assess_ifc(ctx, RSA_PKCS1_2048);
+    T::assess_symmetric(ctx, AES128);
+    T::assess_asymmetric(ctx, Asymmetric::Ecc(P256));
+  }
+
+  #[test]
+  fn assessing_does_not_allocate() {
+    for ctx in [Context::default(), Context::new(0, 2032)] {
+      assert_eq!(allocations(|| assess_every_primitive::<Bsi>(ctx)), 0);
+      assert_eq!(allocations(|| assess_every_primitive::<Cnsa>(ctx)), 0);
+      assert_eq!(allocations(|| assess_every_primitive::<Ecrypt>(ctx)), 0);
+      assert_eq!(allocations(|| assess_every_primitive::<Iot>(ctx)), 0);
+      assert_eq!(allocations(|| assess_every_primitive::<Lenstra>(ctx)), 0);
+      assert_eq!(allocations(|| assess_every_primitive::<Nist>(ctx)), 0);
+    }
+    // Explaining a verdict does, which shows that allocations are seen.
+    let ctx = Context::default();
+    assert!(allocations(|| Nist::assess_hash(ctx, SHA1).rationale(ctx, SHA1)) > 0);
+  }
+
+  fn cites_every_verdict<T: Standard>() -> bool {
+    let ctx = Context::default();
+    T::assess_ecc(ctx, P256).citation.is_some()
+      && T::assess_hash(ctx, SHA1).citation.is_some()
+      && T::assess_ifc(ctx, RSA_PKCS1_2048).citation.is_some()
+      && T::assess_symmetric(ctx, AES128).citation.is_some()
+      && T::assess_asymmetric(ctx, Asymmetric::Ecc(P256))
+        .citation
+        .is_some()
+  }
+
+  #[test]
+  fn verdicts_are_cited() {
+    assert!(cites_every_verdict::<Bsi>());
+    assert!(cites_every_verdict::<Cnsa>());
+    assert!(cites_every_verdict::<Ecrypt>());
+    assert!(cites_every_verdict::<Iot>());
+    assert!(cites_every_verdict::<Lenstra>());
+    assert!(cites_every_verdict::<Nist>());
+  }
+
+  #[test]
+  fn verdict_matches_result() {
+    let ctx = Context::default();
+    assert_eq!(
+      Nist::assess_hash(ctx, SHA1).into_result(),
+      Nist::validate_hash(ctx, SHA1)
+    );
+    assert_eq!(
+      Nist::assess_hash(ctx, SHA256).into_result(),
+      Nist::validate_hash(ctx, SHA256)
+    );
+    assert_eq!(
+      Nist::assess_hash(ctx, SHA1).citation,
+      Nist::rule(Scope::Hash).map(|rule| rule.citation)
+    );
+  }
+
+  #[test]
+  fn verdict_mock_standard() {
+    let ctx = Context::default();
+    assert_eq!(Strong::assess_hash(ctx, SHA256).citation, None);
+  }
+
+  #[test]
+  fn rationale_explains_minimum() {
+    let ctx = Context::default();
+    assert_eq!(
+      Nist::assess_hash(ctx, SHA1).rationale(ctx, SHA1),
+      "sha1 offers at most 80 bits of collision resistance, below the 112-bit minimum required by NIST SP 800-57 Part 1 Rev. 5; use sha224"
+    );
+    assert_eq!(
+      Nist::assess_symmetric(ctx, AES128).rationale(ctx, AES128),
+      "aes128 offers 128 bits of security, meeting the 112-bit minimum required by NIST SP 800-57 Part 1 Rev. 5"
+    );
+  }
+
+  #[test]
+  fn rationale_explains_cutoff() {
+    let ctx = Context::new(0, 2032);
+    let rationale = Nist::assess_ifc(ctx, RSA_PKCS1_2048).rationale(ctx, RSA_PKCS1_2048);
+    assert!(rationale.contains("which NIST SP 800-57 Part 1 Rev. 5 no longer accepts after 2031"));
+  }
+
+  #[test]
+  fn rationale_explains_inconsistency() {
+    let ctx = Context::default();
+    let key = Ffc::new(ID_DSA, 3072, 224);
+    assert_eq!(
+      Nist::assess_ffc(ctx, key).rationale(ctx, key),
+      "dsa_3072_224 offers 112 bits of security, but its parameters are inconsistent with each other; use dsa_2048_224"
+    );
+  }
+
+  #[test]
+  fn rationale_mock_standard() {
+    let ctx = Context::default();
+    assert_eq!(
+      Strong::assess_hash(ctx, SHA256).rationale(ctx, SHA256),
+      "sha256 offers 128 bits of security, below the 256-bit minimum required by the guide in 2023; use sha512"
+    );
+  }
+
+  #[test]
+  fn rationale_in_german() {
+    let ctx = Context::default();
+    assert_eq!(
+      Nist::assess_hash(ctx, SHA1).rationale_in(ctx, SHA1, Locale::German),
+      "sha1 bietet höchstens 80 Bit Kollisionsresistenz, weniger als das von NIST SP 800-57 Part 1 Rev. 5 geforderte Minimum von 112 Bit; verwenden Sie sha224"
+    );
+    assert_eq!(
+      Nist::assess_ifc(ctx, RSA_PKCS1_2048).rationale_in(ctx, RSA_PKCS1_2048, Locale::German),
+      "rsa_pkcs1_2048 bietet 112 Bit Sicherheit und erfüllt damit bis 2031 das von NIST SP 800-57 Part 1 Rev. 5 geforderte Minimum von 112 Bit"
+    );
+    let ctx = Context::new(0, 2032);
+    let rationale =
+      Nist::assess_ifc(ctx, RSA_PKCS1_2048).rationale_in(ctx, RSA_PKCS1_2048, Locale::German);
+    assert!(rationale.contains("was NIST SP 800-57 Part 1 Rev. 5 nach 2031 nicht mehr akzeptiert"));
+  }
+
+  #[test]
+  fn verdict_quantifies_gap() {
+    let ctx = Context::default().with_security(128);
+    let verdict = Nist::assess_ifc(ctx, RSA_PKCS1_2048);
+    assert_eq!((verdict.security, verdict.required), (Some(112), Some(128)));
+    let ctx = Context::default();
+    let verdict = Nist::assess_hash(ctx, SHA1);
+    assert_eq!((verdict.security, verdict.required), (Some(80), Some(112)));
+    let verdict = Nist::assess_symmetric(ctx, AES128);
+    assert_eq!((verdict.security, verdict.required), (Some(128), Some(112)));
+  }
+
+  #[test]
+  fn verdict_derives_required_from_year() {
+    let ctx = Context::default();
+    let verdict = Lenstra::assess_ifc(ctx, RSA_PKCS1_1024);
+    assert_eq!((verdict.security, verdict.required), (Some(80), Some(112)));
+    let verdict = Lenstra::assess_ifc(ctx, RSA_PKCS1_3072);
+    assert_eq!((verdict.security, verdict.required), (Some(128), None));
+    // No curve is recommended below the year's minimum, so the
+    // requirement cannot be told from the alternative.
+    let verdict = Lenstra::assess_ecc(ctx, SECP112R1);
+    assert_eq!((verdict.security, verdict.required), (Some(56), None));
+  }
+
+  #[test]
+  fn verdict_from_result_has_no_security() {
+    let ctx = Context::default();
+    let verdict = Verdict::new(Nist::validate_hash(ctx, SHA1), Nist::rule(Scope::Hash));
+    assert_eq!((verdict.security, verdict.required), (None, None));
+  }
+
+  #[test]
+  fn severity_follows_cutoff() {
+    let ctx = Context::default();
+    assert_eq!(Nist::assess_symmetric(ctx, AES128).severity, Severity::Info);
+    assert_eq!(
+      Nist::assess_hash(ctx, SHA1).severity,
+      Severity::NonCompliant
+    );
+    assert_eq!(
+      Nist::assess_ifc(ctx, RSA_PKCS1_2048).severity,
+      Severity::Legacy
+    );
+    let ctx = Context::new(0, 2032);
+    assert_eq!(
+      Nist::assess_ifc(ctx, RSA_PKCS1_2048).severity,
+      Severity::Deprecated
+    );
+    assert_eq!(
+      Nist::assess_hash(ctx, SHA1).severity,
+      Severity::NonCompliant
+    );
+  }
+
+  #[test]
+  fn severity_follows_replaced_cutoff() {
+    let ctx = Context::new(0, 2032).with_cutoff(Scope::Ifc, 2035);
+    assert_eq!(
+      Nist::assess_ifc(ctx, RSA_PKCS1_2048).severity,
+      Severity::Legacy
+    );
+    let ctx = Context::default().with_cutoff(Scope::Ifc, 2022);
+    assert_eq!(
+      Nist::assess_ifc(ctx, RSA_PKCS1_2048).severity,
+      Severity::Deprecated
+    );
+    let rationale = Nist::assess_ifc(ctx, RSA_PKCS1_2048).rationale(ctx, RSA_PKCS1_2048);
+    assert!(rationale.contains("no longer accepts after 2022"));
+  }
+
+  #[test]
+  fn severity_without_cutoff() {
+    let ctx = Context::default();
+    assert_eq!(
+      Strong::assess_hash(ctx, SHA256).severity,
+      Severity::NonCompliant
+    );
+    assert_eq!(
+      Verdict::new(Nist::validate_ifc(ctx, RSA_PKCS1_2048), None).severity,
+      Severity::Info
+    );
+  }
+}
@@ -0,0 +1,261 @@
+//! Work out for how long a primitive remains compliant with a standard.
+use std::ops::RangeInclusive;
+
+use serde::Serialize;
+
+use crate::context::Context;
+
+/// The last year a primitive is assessed in when looking for the year
+/// it ceases to be compliant.
+///
+/// Standards rarely look further ahead than a decade or two, so a
+/// primitive that is still compliant by then is considered compliant
+/// for the foreseeable future.
+pub const HORIZON: u16 = 2100;
+
+/// The years in which a primitive is compliant, starting with the year
+/// of the context it was assessed in.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+pub struct Window {
+  /// The year of the context the primitive was assessed in.
+  pub from: u16,
+  /// The first year in which the primitive is no longer compliant, or
+  /// `None` if it remains compliant up to and including [`HORIZON`].
+  pub ceases: Option<u16>,
+}
+
+impl Window {
+  /// Returns whether the primitive is compliant in the year of the
+  /// context.
+  pub fn is_compliant(&self) -> bool {
+    self.ceases != Some(self.from)
+  }
+
+  /// Returns the number of years left before the primitive ceases to be
+  /// compliant, or `None` if it remains compliant up to [`HORIZON`].
+  pub fn remaining(&self) -> Option<u16> {
+    self.ceases.map(|year| year - self.from)
+  }
+}
+
+/// Finds the years in which a primitive is compliant according to the
+/// validation function `f`, starting with the year of the context.
+///
+/// Standards only ever raise their requirements over time, so the
+/// primitive is assumed to remain non-compliant once it has ceased to
+/// be compliant. This allows the year to be found with a handful of
+/// validations.
+///
+/// # Example
+///
+/// The following example shows that NIST disallows RSA moduli providing
+/// less than 128 bits of security after 2031.
+///
+/// ```
+/// use wardstone_core::context::Context;
+/// use wardstone_core::primitive::ifc::RSA_PSS_2048;
+/// use wardstone_core::standard::nist::Nist;
+/// use wardstone_core::standard::window;
+/// use wardstone_core::standard::Standard;
+///
+/// let ctx = Context::default();
+/// let window = window::compliance_window(ctx, RSA_PSS_2048, Nist::validate_ifc);
+/// assert_eq!(window.ceases, Some(2032));
+/// ```
+pub fn compliance_window<T, F>(ctx: Context, primitive: T, f: F) -> Window
+where
+  T: Copy,
+  F: Fn(Context, T) -> Result<T, T>,
+{
+  let from = ctx.year();
+  let ceases = first_non_compliant(ctx, primitive, from..=HORIZON.max(from), f);
+  Window { from, ceases }
+}
+
+/// Finds the first year in `years` in which a primitive is no longer
+/// compliant according to the validation function `f`, or `None` if it
+/// remains compliant throughout.
+///
+/// Every year is assessed in the context with only its year replaced.
+/// Like [`compliance_window`], this assumes that a primitive does not
+/// become compliant again once it has ceased to be.
+///
+/// # Example
+///
+/// The following example shows that NIST disallows RSA moduli providing
+/// less than 128 bits of security after 2031, unless the cutoff is
+/// moved.
+///
+/// ```
+/// use wardstone_core::context::Context;
+/// use wardstone_core::primitive::ifc::RSA_PSS_2048;
+/// use wardstone_core::standard::nist::Nist;
+/// use wardstone_core::standard::rule::Scope;
+/// use wardstone_core::standard::window;
+/// use wardstone_core::standard::Standard;
+///
+/// let ctx = Context::default();
+/// let year = window::first_non_compliant(ctx, RSA_PSS_2048, 2025..=2040, Nist::validate_ifc);
+/// assert_eq!(year, Some(2032));
+/// let ctx = ctx.with_cutoff(Scope::Ifc, 2035);
+/// let year = window::first_non_compliant(ctx, RSA_PSS_2048, 2025..=2040, Nist::validate_ifc);
+/// assert_eq!(year, Some(2036));
+/// ```
+pub fn first_non_compliant<T, F>(
+  ctx: Context,
+  primitive: T,
+  years: RangeInclusive<u16>,
+  f: F,
+) -> Option<u16>
+where
+  T: Copy,
+  F: Fn(Context, T) -> Result<T, T>,
+{
+  let compliant = |year| f(ctx.with_year(year), primitive).is_ok();
+  let (from, to) = years.into_inner();
+  if from > to {
+    return None;
+  }
+  if !compliant(from) {
+    return Some(from);
+  }
+  if compliant(to) {
+    return None;
+  }
+  // The primitive is compliant in `lo` and not in `hi`.
+  let (mut lo, mut hi) = (from, to);
+  while hi - lo > 1 {
+    let mid = lo + (hi - lo) / 2;
+    if compliant(mid) {
+      lo = mid;
+    } else {
+      hi = mid;
+    }
+  }
+  Some(hi)
+}
+
+/// Assesses a primitive in every year of `years` according to the
+/// validation function `f`, returning the outcome of each year in order.
+///
+/// Unlike [`first_non_compliant`], this makes no assumption about how
+/// the requirements of a standard change over time.
+///
+/// # Example
+///
+/// ```
+/// use wardstone_core::context::Context;
+/// use wardstone_core::primitive::ifc::RSA_PSS_2048;
+/// use wardstone_core::standard::nist::Nist;
+/// use wardstone_core::standard::window;
+/// use wardstone_core::standard::Standard;
+///
+/// let ctx = Context::default();
+/// let years = window::yearly(ctx, RSA_PSS_2048, 2030..=2033, Nist::validate_ifc);
+/// let compliant: Vec<_> = years.iter().map(|(year, result)| (*year, result.is_ok())).collect();
+/// assert_eq!(compliant, [(2030, true), (2031, true), (2032, false), (2033, false)]);
+/// ```
+pub fn yearly<T, F>(
+  ctx: Context,
+  primitive: T,
+  years: RangeInclusive<u16>,
+  f: F,
+) -> Vec<(u16, Result<T, T>)>
+where
+  T: Copy,
+  F: Fn(Context, T) -> Result<T, T>,
+{
+  years
+    .map(|year| (year, f(ctx.with_year(year), primitive)))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::primitive::hash::{SHA1, SHA256};
+  use crate::primitive::ifc::RSA_PKCS1_2048;
+  use crate::primitive::symmetric::AES256;
+  use crate::standard::lenstra::Lenstra;
+  use crate::standard::nist::Nist;
+  use crate::standard::rule::Scope;
+  use crate::standard::Standard;
+
+  #[test]
+  fn non_compliant_primitive_has_ceased() {
+    let ctx = Context::default();
+    let window = compliance_window(ctx, SHA1, Nist::validate_hash);
+    assert_eq!(window.ceases, Some(ctx.year()));
+    assert!(!window.is_compliant());
+    assert_eq!(window.remaining(), Some(0));
+  }
+
+  #[test]
+  fn primitive_ceases_after_cutoff() {
+    let ctx = Context::default();
+    let window = compliance_window(ctx, RSA_PKCS1_2048, Nist::validate_ifc);
+    assert_eq!(window.ceases, Some(2032));
+    assert!(window.is_compliant());
+    assert_eq!(window.remaining(), Some(2032 - ctx.year()));
+  }
+
+  #[test]
+  fn primitive_remains_compliant() {
+    let ctx = Context::default();
+    let window = compliance_window(ctx, AES256, Nist::validate_symmetric);
+    assert_eq!(window.ceases, None);
+    assert_eq!(window.remaining(), None);
+  }
+
+  #[test]
+  fn primitive_ceases_as_security_grows() {
+    // Lenstra raises the required security every year rather than at a
+    // cutoff.
+    let ctx = Context::default();
+    let window = compliance_window(ctx, SHA256, Lenstra::validate_hash);
+    let ceases = window.ceases.expect("sha256 should cease to be compliant");
+    assert!(ceases > ctx.year());
+    let at = |year| Context::new(ctx.security(), year);
+    assert!(Lenstra::validate_hash(at(ceases - 1), SHA256).is_ok());
+    assert!(Lenstra::validate_hash(at(ceases), SHA256).is_err());
+  }
+
+  #[test]
+  fn window_keeps_cutoffs() {
+    let ctx = Context::default().with_cutoff(Scope::Ifc, 2035);
+    let window = compliance_window(ctx, RSA_PKCS1_2048, Nist::validate_ifc);
+    assert_eq!(window.ceases, Some(2036));
+  }
+
+  #[test]
+  fn first_non_compliant_within_years() {
+    let ctx = Context::default();
+    let first = |years| first_non_compliant(ctx, RSA_PKCS1_2048, years, Nist::validate_ifc);
+    assert_eq!(first(2025..=2031), None);
+    assert_eq!(first(2025..=2032), Some(2032));
+    assert_eq!(first(2040..=2050), Some(2040));
+    assert_eq!(first(RangeInclusive::new(2031, 2025)), None);
+  }
+
+  #[test]
+  fn yearly_matches_first_non_compliant() {
+    let ctx = Context::default();
+    let years = yearly(ctx, SHA256, 2025..=2100, Lenstra::validate_hash);
+    assert_eq!(years.len(), 76);
+    let first = years
+      .iter()
+      .find(|(_, result)| result.is_err())
+      .map(|&(year, _)| year);
+    assert_eq!(
+      first,
+      first_non_compliant(ctx, SHA256, 2025..=2100, Lenstra::validate_hash)
+    );
+  }
+
+  #[test]
+  fn year_beyond_horizon() {
+    let ctx = Context::new(0, HORIZON + 1);
+    let window = compliance_window(ctx, AES256, Nist::validate_symmetric);
+    assert_eq!(window.ceases, None);
+  }
+}
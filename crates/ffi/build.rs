@@ -7,14 +7,36 @@ fn main() {
   let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
   let target_dir = Path::new("../../target");
   let header = target_dir.join("wardstone.h");
+  // Variants are prefixed with the name of their enumeration such as
+  // `WS_ERR_NULL_CONTEXT` to avoid clashes in the global namespace.
+  let mut config = cbindgen::Config::default();
+  config.enumeration.rename_variants = cbindgen::RenameRule::QualifiedScreamingSnakeCase;
   cbindgen::Builder::new()
+    .with_config(config)
+    .include_item("Error")
+    .include_item("Guide")
+    .include_item("Scope")
+    .rename_item("Assessment", "ws_assessment")
+    .rename_item("AssessmentCallback", "ws_assessment_callback")
+    .rename_item("Compliance", "ws_compliance")
     .rename_item("Context", "ws_context")
     .rename_item("Ecc", "ws_ecc")
+    .rename_item("Error", "ws_err")
     .rename_item("Ffc", "ws_ffc")
+    .rename_item("Guide", "ws_guide")
     .rename_item("Hash", "ws_hash")
+    .rename_item("Hybrid", "ws_hybrid")
     .rename_item("Ifc", "ws_ifc")
+    .rename_item("Key", "ws_key")
+    .rename_item("Policy", "ws_policy")
+    .rename_item("Pqc", "ws_pqc")
+    .rename_item("Primitive", "ws_primitive")
+    .rename_item("Scope", "ws_scope")
     .rename_item("Security", "ws_security")
+    .rename_item("Stateful", "ws_stateful")
     .rename_item("Symmetric", "ws_symmetric")
+    .rename_item("Verdict", "ws_verdict")
+    .rename_item("VerdictCallback", "ws_verdict_callback")
     .with_cpp_compat(true)
     .with_crate(crate_dir)
     .with_parse_deps(true)
@@ -23,6 +45,7 @@ fn main() {
     .with_include_guard("WARDSTONE_H_")
     .with_language(cbindgen::Language::C)
     .with_no_includes()
+    .with_sys_include("stdbool.h")
     .with_sys_include("stdint.h")
     .generate()
     .expect("Unable to generate bindings")
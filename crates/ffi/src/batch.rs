@@ -0,0 +1,255 @@
+//! Assess many primitives or certificates in a single call.
+//!
+//! Scanners that assess thousands of items can hand all of them to the
+//! library at once instead of crossing the boundary for each one. The
+//! library calls back into the program with the outcome of every item
+//! in the order they were given.
+use std::ffi::{c_int, c_void};
+use std::{ptr, slice};
+
+use wardstone_core::context::Context as CoreContext;
+use wardstone_core::standard::bsi::Bsi;
+use wardstone_core::standard::cnsa::Cnsa;
+use wardstone_core::standard::ecrypt::Ecrypt;
+use wardstone_core::standard::iot::Iot;
+use wardstone_core::standard::lenstra::Lenstra;
+use wardstone_core::standard::nist::Nist;
+
+use crate::certificate::{self, Assessment, Guide};
+use crate::context::Context;
+use crate::error::{self, Error};
+use crate::registry::Primitive;
+use crate::verdict::{self, Compliance, Verdict};
+
+/// Called with the outcome of assessing the primitive at `index`.
+///
+/// `result` is `1` if the primitive is compliant, `0` if it is not, and
+/// a negative `ws_err` code if it could not be assessed, in which case
+/// `verdict` is null. The verdict is only valid for the duration of the
+/// call.
+pub type VerdictCallback = Option<
+  unsafe extern "C" fn(userdata: *mut c_void, index: usize, result: c_int, verdict: *const Verdict),
+>;
+
+/// Called with the outcome of assessing the certificate at `index`.
+///
+/// `result` is `1` if the certificate is compliant, `0` if it is not,
+/// and a negative `ws_err` code if it could not be assessed, in which
+/// case `assessment` is null. The assessment is only valid for the
+/// duration of the call.
+pub type AssessmentCallback = Option<
+  unsafe extern "C" fn(
+    userdata: *mut c_void,
+    index: usize,
+    result: c_int,
+    assessment: *const Assessment,
+  ),
+>;
+
+fn evaluate(ctx: CoreContext, guide: Guide, primitive: Primitive) -> Result<Verdict, c_int> {
+  match guide {
+    Guide::Bsi => verdict::evaluate::<Bsi>(ctx, primitive),
+    Guide::Cnsa => verdict::evaluate::<Cnsa>(ctx, primitive),
+    Guide::Ecrypt => verdict::evaluate::<Ecrypt>(ctx, primitive),
+    Guide::Lenstra => verdict::evaluate::<Lenstra>(ctx, primitive),
+    Guide::Nist => verdict::evaluate::<Nist>(ctx, primitive),
+    Guide::Iot => verdict::evaluate::<Iot>(ctx, primitive),
+  }
+}
+
+/// Returns the length of the DER encoded `SEQUENCE` at the start of
+/// `data`, including its header, or `None` if it is malformed or
+/// truncated.
+fn der_len(data: &[u8]) -> Option<usize> {
+  let (&tag, rest) = data.split_first()?;
+  let (&first, rest) = rest.split_first()?;
+  if tag != 0x30 {
+    return None;
+  }
+  let (header, len) = match first {
+    0..=0x7f => (2, first as usize),
+    // Certificates are well below 4 GiB so longer lengths are rejected.
+    0x81..=0x84 => {
+      let n = (first & 0x7f) as usize;
+      let bytes = rest.get(..n)?;
+      let len = bytes.iter().fold(0, |len, &b| (len << 8) | b as usize);
+      (2 + n, len)
+    },
+    _ => return None,
+  };
+  header.checked_add(len).filter(|&total| total <= data.len())
+}
+
+/// Assesses `count` primitives against a standard and calls `callback`
+/// with the outcome of each of them in turn, passing `userdata` along.
+///
+/// A primitive that cannot be assessed does not stop the others from
+/// being assessed; the callback receives its error code instead.
+///
+/// The function returns the number of compliant primitives, and a
+/// negative `ws_err` code if the primitives or the callback are missing
+/// or `guide` is not one of the `ws_guide` values.
+///
+/// # Safety
+///
+/// `primitives` must point to at least `count` initialised primitives.
+#[no_mangle]
+pub unsafe extern "C" fn ws_assess_batch(
+  ctx: Context,
+  guide: c_int,
+  primitives: *const Primitive,
+  count: usize,
+  callback: VerdictCallback,
+  userdata: *mut c_void,
+) -> c_int {
+  let guide = match certificate::guide(guide) {
+    Ok(guide) => guide,
+    Err(err) => return err,
+  };
+  let ctx = CoreContext::from(ctx);
+  let Some(callback) = callback else {
+    return error::fail(Error::NullPrimitive, "callback is null");
+  };
+  if primitives.is_null() && count > 0 {
+    return error::fail(Error::NullPrimitive, "primitives are null");
+  }
+  if count == 0 {
+    return 0;
+  }
+  let mut compliant: c_int = 0;
+  for (index, &primitive) in slice::from_raw_parts(primitives, count).iter().enumerate() {
+    match evaluate(ctx, guide, primitive) {
+      Ok(verdict) => {
+        let result = (verdict.compliance != Compliance::NonCompliant) as c_int;
+        compliant = compliant.saturating_add(result);
+        callback(userdata, index, result, &verdict);
+      },
+      Err(err) => callback(userdata, index, err, ptr::null()),
+    }
+  }
+  compliant
+}
+
+/// Assesses `count` primitives against a standard like
+/// `ws_assess_batch` but stores the outcomes in arrays provided by the
+/// caller instead of calling back, which suits languages such as Go
+/// that cannot easily pass a function to C.
+///
+/// `results` receives `1`, `0` or a negative `ws_err` code for each
+/// primitive as the callback would. If `alternatives` is not null, it
+/// receives the recommended alternative of each primitive, which is left
+/// untouched if the primitive could not be assessed.
+///
+/// The function returns the number of compliant primitives, and a
+/// negative `ws_err` code if the primitives or results are missing or
+/// `guide` is not one of the `ws_guide` values.
+///
+/// # Safety
+///
+/// `primitives` must point to at least `count` initialised primitives,
+/// and `results` and `alternatives`, if not null, to at least `count`
+/// writable elements.
+#[no_mangle]
+pub unsafe extern "C" fn ws_assess_batch_results(
+  ctx: Context,
+  guide: c_int,
+  primitives: *const Primitive,
+  count: usize,
+  results: *mut c_int,
+  alternatives: *mut Primitive,
+) -> c_int {
+  let guide = match certificate::guide(guide) {
+    Ok(guide) => guide,
+    Err(err) => return err,
+  };
+  let ctx = CoreContext::from(ctx);
+  if (primitives.is_null() || results.is_null()) && count > 0 {
+    return error::fail(Error::NullPrimitive, "primitives or results are null");
+  }
+  if count == 0 {
+    return 0;
+  }
+  let mut compliant: c_int = 0;
+  for (index, &primitive) in slice::from_raw_parts(primitives, count).iter().enumerate() {
+    let result = match evaluate(ctx, guide, primitive) {
+      Ok(verdict) => {
+        if !alternatives.is_null() {
+          *alternatives.add(index) = verdict.alternative;
+        }
+        (verdict.compliance != Compliance::NonCompliant) as c_int
+      },
+      Err(err) => err,
+    };
+    *results.add(index) = result;
+    compliant = compliant.saturating_add(result.max(0));
+  }
+  compliant
+}
+
+/// Assesses a buffer of `len` bytes holding DER encoded X.509
+/// certificates one after the other, as produced by concatenating their
+/// files, and calls `callback` with the outcome of each of them in turn,
+/// passing `userdata` along.
+///
+/// A certificate that uses a primitive that is not recognised does not
+/// stop the others from being assessed; the callback receives its error
+/// code instead. If the buffer cannot be split into certificates, the
+/// callback receives `WS_ERR_PARSE` for the offending one and no more
+/// certificates are assessed.
+///
+/// The function returns the number of compliant certificates, and a
+/// negative `ws_err` code if the buffer cannot be split, an argument is
+/// missing or `guide` is not one of the `ws_guide` values.
+///
+/// # Safety
+///
+/// `data` must point to at least `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ws_assess_x509_batch(
+  ctx: Context,
+  guide: c_int,
+  data: *const u8,
+  len: usize,
+  callback: AssessmentCallback,
+  userdata: *mut c_void,
+) -> c_int {
+  let guide = match certificate::guide(guide) {
+    Ok(guide) => guide,
+    Err(err) => return err,
+  };
+  let ctx = CoreContext::from(ctx);
+  let Some(callback) = callback else {
+    return error::fail(Error::NullPrimitive, "callback is null");
+  };
+  if data.is_null() && len > 0 {
+    return error::fail(Error::NullPrimitive, "certificate data is null");
+  }
+  if len == 0 {
+    return 0;
+  }
+  let mut data = slice::from_raw_parts(data, len);
+  let mut compliant: c_int = 0;
+  let mut index = 0;
+  while !data.is_empty() {
+    let Some(n) = der_len(data) else {
+      let err = error::fail(
+        Error::Parse,
+        &format!("certificate {} is not a DER encoded sequence", index),
+      );
+      callback(userdata, index, err, ptr::null());
+      return err;
+    };
+    let (der, rest) = data.split_at(n);
+    match certificate::evaluate(ctx, guide, der) {
+      Ok(assessment) => {
+        let result = (assessment.hash_function_compliant && assessment.key_compliant) as c_int;
+        compliant = compliant.saturating_add(result);
+        callback(userdata, index, result, &assessment);
+      },
+      Err(err) => callback(userdata, index, err, ptr::null()),
+    }
+    data = rest;
+    index += 1;
+  }
+  compliant
+}
@@ -0,0 +1,266 @@
+//! Assess X.509 certificates against a standard in a single call.
+//!
+//! The certificate is parsed, the primitives it uses are mapped to
+//! those built into the library and each one is validated so that C
+//! callers do not have to do any of this themselves.
+use std::ffi::{c_char, c_int};
+use std::{mem, ptr, slice};
+
+use wardstone_core::certificate;
+use wardstone_core::context::Context as CoreContext;
+use wardstone_core::primitive::asymmetric::Asymmetric;
+use wardstone_core::primitive::ecc::Ecc;
+use wardstone_core::primitive::ffc::Ffc;
+use wardstone_core::primitive::hash::{Hash, HASH_NOT_SUPPORTED};
+use wardstone_core::primitive::hybrid::Hybrid;
+use wardstone_core::primitive::ifc::Ifc;
+use wardstone_core::primitive::pqc::Pqc;
+use wardstone_core::primitive::stateful::Stateful;
+use wardstone_core::standard::bsi::Bsi;
+use wardstone_core::standard::cnsa::Cnsa;
+use wardstone_core::standard::ecrypt::Ecrypt;
+use wardstone_core::standard::iot::Iot;
+use wardstone_core::standard::lenstra::Lenstra;
+use wardstone_core::standard::nist::Nist;
+use wardstone_core::standard::Standard;
+
+use crate::context::Context;
+use crate::error::{self, Error};
+use crate::verdict;
+use crate::version::{self, Versioned};
+
+/// The standards that a certificate can be assessed against.
+///
+/// Functions take a guide as an `int` holding one of these values
+/// rather than the enumeration itself, since a value outside of it
+/// could not be checked once it has been read as one.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Guide {
+  Bsi,
+  Cnsa,
+  Ecrypt,
+  Lenstra,
+  Nist,
+  // Added after the others so that their values do not change.
+  Iot,
+}
+
+impl Guide {
+  const ALL: [Self; 6] = [
+    Self::Bsi,
+    Self::Cnsa,
+    Self::Ecrypt,
+    Self::Lenstra,
+    Self::Nist,
+    Self::Iot,
+  ];
+}
+
+impl TryFrom<c_int> for Guide {
+  type Error = Error;
+
+  fn try_from(value: c_int) -> Result<Self, Self::Error> {
+    Self::ALL
+      .into_iter()
+      .find(|&guide| guide as c_int == value)
+      .ok_or(Error::InvalidArgument)
+  }
+}
+
+/// Returns the guide a caller passed as an `int`, or records an error
+/// and returns its code if it is not one of the `ws_guide` values.
+pub(crate) fn guide(value: c_int) -> Result<Guide, c_int> {
+  Guide::try_from(value).map_err(|err| error::fail(err, &format!("{} is not a ws_guide", value)))
+}
+
+/// An asymmetric key primitive.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Key {
+  Ecc(Ecc),
+  Ffc(Ffc),
+  Ifc(Ifc),
+  Pqc(Pqc),
+  Hybrid(Hybrid),
+  Stateful(Stateful),
+}
+
+impl Versioned for Assessment {
+  const MIN_SIZE: usize = mem::size_of::<Self>();
+}
+
+impl From<Asymmetric> for Key {
+  fn from(key: Asymmetric) -> Self {
+    match key {
+      Asymmetric::Ecc(ecc) => Self::Ecc(ecc),
+      Asymmetric::Ffc(ffc) => Self::Ffc(ffc),
+      Asymmetric::Ifc(ifc) => Self::Ifc(ifc),
+      Asymmetric::Pqc(pqc) => Self::Pqc(pqc),
+      Asymmetric::Hybrid(hybrid) => Self::Hybrid(hybrid),
+      Asymmetric::Stateful(stateful) => Self::Stateful(stateful),
+    }
+  }
+}
+
+/// The outcome of assessing the primitives used by a certificate.
+///
+/// The alternatives hold the recommended primitives that one should use
+/// instead, or the primitives with the desired security level if they
+/// are already compliant.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct Assessment {
+  /// The size of the structure which must be set by the caller to
+  /// `sizeof(struct ws_assessment)`.
+  pub size: u32,
+  /// Whether the signature algorithm uses a separate hash function,
+  /// which is not the case for EdDSA. If not, the hash function fields
+  /// hold `WS_HASH_NOT_SUPPORTED`.
+  pub has_hash_function: bool,
+  pub hash_function: Hash,
+  pub hash_function_alternative: Hash,
+  pub hash_function_compliant: bool,
+  /// The subject public key of the certificate.
+  pub key: Key,
+  pub key_alternative: Key,
+  pub key_compliant: bool,
+  /// The parts of the standard the assessments of the hash function and
+  /// key are based on, or null if the standard does not cite one or
+  /// there is no separate hash function. The strings must not be freed.
+  pub hash_function_citation: *const c_char,
+  pub key_citation: *const c_char,
+}
+
+/// Assesses the hash function and subject public key of a DER or PEM
+/// encoded X.509 certificate against a standard.
+///
+/// If `struct ws_assessment*` is not null, it will hold the primitives
+/// used by the certificate and whether each of them is compliant along
+/// with their recommended alternatives. Its `size` must be set before
+/// the call.
+///
+/// The function returns `1` if the certificate is compliant, `0` if it
+/// is not, and a negative `ws_err` code if the certificate cannot be
+/// parsed, uses a primitive that is not recognised, or an argument is
+/// missing or `guide` is not one of the `ws_guide` values.
+///
+/// # Safety
+///
+/// `data` must point to at least `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ws_assess_x509(
+  ctx: Context,
+  guide: c_int,
+  data: *const u8,
+  len: usize,
+  assessment: *mut Assessment,
+) -> c_int {
+  match self::guide(guide) {
+    Ok(guide) => assess_x509(ctx.into(), guide, data, len, assessment),
+    Err(err) => err,
+  }
+}
+
+/// Assesses a certificate for `ws_assess_x509` and `ws_policy_assess_x509`
+/// once the guide has been checked, with a context that may replace the
+/// cutoff years of the standard.
+pub(crate) unsafe fn assess_x509(
+  ctx: CoreContext,
+  guide: Guide,
+  data: *const u8,
+  len: usize,
+  assessment: *mut Assessment,
+) -> c_int {
+  if data.is_null() {
+    return error::fail(Error::NullPrimitive, "certificate data is null");
+  }
+  let result = match evaluate(ctx, guide, slice::from_raw_parts(data, len)) {
+    Ok(result) => result,
+    Err(err) => return err,
+  };
+  if !assessment.is_null() {
+    let err = version::write(assessment, result);
+    if err < 0 {
+      return err;
+    }
+  }
+  (result.hash_function_compliant && result.key_compliant) as c_int
+}
+
+/// Assesses a certificate or returns the code of the error that
+/// prevented it.
+pub(crate) fn evaluate(ctx: CoreContext, guide: Guide, data: &[u8]) -> Result<Assessment, c_int> {
+  let (hash_function, key) = match certificate::primitives(data) {
+    Ok(primitives) => primitives,
+    Err(certificate::Error::UnknownPrimitive(oid)) => {
+      return Err(error::fail(Error::UnknownPrimitive, &oid))
+    },
+    Err(err) => return Err(error::fail(Error::Parse, &err.to_string())),
+  };
+  let result = match guide {
+    Guide::Bsi => assess::<Bsi>(ctx, hash_function, key),
+    Guide::Cnsa => assess::<Cnsa>(ctx, hash_function, key),
+    Guide::Ecrypt => assess::<Ecrypt>(ctx, hash_function, key),
+    Guide::Lenstra => assess::<Lenstra>(ctx, hash_function, key),
+    Guide::Nist => assess::<Nist>(ctx, hash_function, key),
+    Guide::Iot => assess::<Iot>(ctx, hash_function, key),
+  };
+  Ok(result)
+}
+
+fn assess<T: Standard>(
+  ctx: CoreContext,
+  hash_function: Option<Hash>,
+  key: Asymmetric,
+) -> Assessment {
+  let (hash_function, hash_function_verdict) = match hash_function {
+    Some(hash) => (hash, Some(T::assess_hash(ctx, hash))),
+    None => (HASH_NOT_SUPPORTED, None),
+  };
+  let key_verdict = T::assess_asymmetric(ctx, key);
+  Assessment {
+    size: mem::size_of::<Assessment>() as u32,
+    has_hash_function: hash_function != HASH_NOT_SUPPORTED,
+    hash_function,
+    hash_function_alternative: hash_function_verdict.map_or(HASH_NOT_SUPPORTED, |v| v.alternative),
+    hash_function_compliant: hash_function_verdict.is_none_or(|v| v.compliant),
+    key: key.into(),
+    key_alternative: key_verdict.alternative.into(),
+    key_compliant: key_verdict.compliant,
+    hash_function_citation: hash_function_verdict
+      .and_then(|v| v.citation)
+      .map_or(ptr::null(), verdict::citation),
+    key_citation: key_verdict.citation.map_or(ptr::null(), verdict::citation),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::context;
+
+  #[test]
+  fn unknown_guide() {
+    let ctx = context::ws_context_default();
+    let data = b"junk";
+    let got = unsafe { ws_assess_x509(ctx, 42, data.as_ptr(), data.len(), ptr::null_mut()) };
+    assert_eq!(got, Error::InvalidArgument as c_int);
+  }
+
+  #[test]
+  fn known_guide() {
+    let ctx = context::ws_context_default();
+    let data = b"junk";
+    let got = unsafe {
+      ws_assess_x509(
+        ctx,
+        Guide::Nist as c_int,
+        data.as_ptr(),
+        data.len(),
+        ptr::null_mut(),
+      )
+    };
+    assert_eq!(got, Error::Parse as c_int);
+  }
+}
@@ -1,10 +1,110 @@
 //! Specifies the context in which a cryptographic primitive will be
 //! assessed against.
-use wardstone_core::context::Context;
+//!
+//! Contexts should be created and changed through these functions
+//! rather than by setting their fields directly so that programs do not
+//! depend on their layout.
+use std::ffi::c_int;
+
+use wardstone_core::context::Context as CoreContext;
+use wardstone_core::primitive::Security;
+
+use crate::error::{self, Error};
+
+/// Represents the context in which a cryptographic primitive will be
+/// assessed against such as the year and minimum security required by
+/// the user.
+///
+/// Contexts are passed by value, so unlike the structures that start
+/// with a `size` field they cannot grow without breaking programs built
+/// against an older header. The cutoff years of a standard are replaced
+/// through a `ws_policy` instead.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Context {
+  security: Security,
+  year: u16,
+}
+
+impl From<Context> for CoreContext {
+  fn from(ctx: Context) -> Self {
+    CoreContext::new(ctx.security, ctx.year)
+  }
+}
+
+impl From<CoreContext> for Context {
+  fn from(ctx: CoreContext) -> Self {
+    Self {
+      security: ctx.security(),
+      year: ctx.year(),
+    }
+  }
+}
 
 /// Creates a context which will default to the year 2023 and will use
 /// the minimum security defined by the standard.
 #[no_mangle]
 pub extern "C" fn ws_context_default() -> Context {
-  Context::default()
+  CoreContext::default().into()
+}
+
+/// Creates a context where `year` is the year one expects the primitive
+/// to remain secure and `security` is the minimum security required.
+///
+/// If `security` is `0` then it will default to using the minimum
+/// security outlined in the standard.
+#[no_mangle]
+pub extern "C" fn ws_context_new(year: u16, security: Security) -> Context {
+  Context { security, year }
+}
+
+/// Returns the year one expects the primitive to remain secure.
+#[no_mangle]
+pub extern "C" fn ws_context_year(ctx: Context) -> u16 {
+  ctx.year
+}
+
+/// Returns the minimum security required.
+#[no_mangle]
+pub extern "C" fn ws_context_security(ctx: Context) -> Security {
+  ctx.security
+}
+
+/// Sets the year one expects the primitive to remain secure.
+///
+/// The function returns `0` on success and `WS_ERR_NULL_CONTEXT` if
+/// `ctx` is null.
+///
+/// # Safety
+///
+/// `ctx` must be null or point to a valid context.
+#[no_mangle]
+pub unsafe extern "C" fn ws_context_set_year(ctx: *mut Context, year: u16) -> c_int {
+  match ctx.as_mut() {
+    Some(ctx) => {
+      ctx.year = year;
+      0
+    },
+    None => error::fail(Error::NullContext, "cannot set year"),
+  }
+}
+
+/// Sets the minimum security required, where `0` denotes the minimum
+/// security outlined in the standard.
+///
+/// The function returns `0` on success and `WS_ERR_NULL_CONTEXT` if
+/// `ctx` is null.
+///
+/// # Safety
+///
+/// `ctx` must be null or point to a valid context.
+#[no_mangle]
+pub unsafe extern "C" fn ws_context_set_security(ctx: *mut Context, security: Security) -> c_int {
+  match ctx.as_mut() {
+    Some(ctx) => {
+      ctx.security = security;
+      0
+    },
+    None => error::fail(Error::NullContext, "cannot set security"),
+  }
 }
@@ -0,0 +1,122 @@
+//! Describes the errors that functions in the C API can report.
+//!
+//! Functions that assess a primitive return `1` if it is compliant and
+//! `0` if it is not. Any negative value is one of the error codes below
+//! which sets apart misuse of the API from genuine non-compliance.
+//! [`ws_strerror`] describes an error code and [`ws_last_error`] returns
+//! a more detailed message about the most recent error on the calling
+//! thread.
+use std::cell::RefCell;
+use std::ffi::{c_char, c_int, CString};
+use std::ptr;
+
+/// An error returned by a function in the C API.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+  /// A pointer to a context or policy is null.
+  NullContext = -1,
+  /// A pointer to a primitive or its description is null.
+  NullPrimitive = -2,
+  /// A primitive is not recognised.
+  UnknownPrimitive = -3,
+  /// A certificate or other encoded input cannot be parsed.
+  Parse = -4,
+  /// The `size` of a structure is smaller than its first revision.
+  UnsupportedSize = -5,
+  /// A file cannot be read.
+  Io = -6,
+  /// An argument that should be one of the values of an enumeration,
+  /// such as a `ws_guide`, is not.
+  InvalidArgument = -7,
+}
+
+// The descriptions are terminated with a null byte so that they can be
+// handed to C as they are.
+const NO_ERROR: &str = "no error\0";
+const UNKNOWN_ERROR: &str = "unknown error\0";
+
+impl Error {
+  fn description(self) -> &'static str {
+    match self {
+      Self::NullContext => "context is null\0",
+      Self::NullPrimitive => "primitive is null\0",
+      Self::UnknownPrimitive => "primitive is not recognised\0",
+      Self::Parse => "input cannot be parsed\0",
+      Self::UnsupportedSize => "structure size is not supported\0",
+      Self::Io => "file cannot be read\0",
+      Self::InvalidArgument => "argument is not valid\0",
+    }
+  }
+
+  fn from_code(code: c_int) -> Option<Self> {
+    match code {
+      -1 => Some(Self::NullContext),
+      -2 => Some(Self::NullPrimitive),
+      -3 => Some(Self::UnknownPrimitive),
+      -4 => Some(Self::Parse),
+      -5 => Some(Self::UnsupportedSize),
+      -6 => Some(Self::Io),
+      -7 => Some(Self::InvalidArgument),
+      _ => None,
+    }
+  }
+}
+
+thread_local! {
+  static LAST_ERROR: RefCell<Option<(Error, CString)>> = const { RefCell::new(None) };
+}
+
+/// Records the error as the last one on this thread along with details
+/// about what caused it and returns its code.
+pub(crate) fn fail(error: Error, detail: &str) -> c_int {
+  let description = error.description().trim_end_matches('\0');
+  // The details might come from the caller and contain a null byte, in
+  // which case the description is used on its own.
+  let message = CString::new(format!("{}: {}", description, detail))
+    .unwrap_or_else(|_| CString::new(description).unwrap_or_default());
+  LAST_ERROR.with(|last| *last.borrow_mut() = Some((error, message)));
+  error as c_int
+}
+
+/// Returns a static string that describes an error code.
+///
+/// The string must not be freed.
+#[no_mangle]
+pub extern "C" fn ws_strerror(code: c_int) -> *const c_char {
+  match Error::from_code(code) {
+    Some(error) => error.description().as_ptr().cast(),
+    None if code >= 0 => NO_ERROR.as_ptr().cast(),
+    None => UNKNOWN_ERROR.as_ptr().cast(),
+  }
+}
+
+/// Returns a message about the most recent error that occurred on the
+/// calling thread or null if no error has occurred.
+///
+/// Successful calls do not clear the message. The string must not be
+/// freed and remains valid until another error occurs on the same
+/// thread.
+#[no_mangle]
+pub extern "C" fn ws_last_error() -> *const c_char {
+  LAST_ERROR.with(|last| match last.borrow().as_ref() {
+    Some((_, message)) => message.as_ptr(),
+    None => ptr::null(),
+  })
+}
+
+/// Returns the code of the most recent error that occurred on the
+/// calling thread or `0` if no error has occurred.
+///
+/// This suits functions that report failure by returning null, such as
+/// `ws_policy_load`, and callers that want the code along with the
+/// message from `ws_last_error`.
+#[no_mangle]
+pub extern "C" fn ws_last_error_code() -> c_int {
+  LAST_ERROR.with(|last| {
+    last
+      .borrow()
+      .as_ref()
+      .map_or(0, |(error, _)| *error as c_int)
+  })
+}
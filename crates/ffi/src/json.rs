@@ -0,0 +1,258 @@
+//! Render the outcome of an assessment as JSON.
+//!
+//! Callers that would rather not bind every field of `ws_verdict` and
+//! `ws_assessment`, such as scripting languages or programs that pass
+//! the result on to another service, can get the same information as a
+//! single string. The keys follow those of the JSON output of the
+//! command line tool.
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::{ptr, slice};
+
+use serde::Serialize;
+use wardstone_core::context::Context as CoreContext;
+use wardstone_core::primitive::ffc::Ffc;
+use wardstone_core::standard::bsi::Bsi;
+use wardstone_core::standard::cnsa::Cnsa;
+use wardstone_core::standard::ecrypt::Ecrypt;
+use wardstone_core::standard::iot::Iot;
+use wardstone_core::standard::lenstra::Lenstra;
+use wardstone_core::standard::nist::Nist;
+use wardstone_core::standard::rule::{Citation, Rule, Scope};
+use wardstone_core::standard::Standard;
+
+use crate::certificate::{self, Guide, Key};
+use crate::context::Context;
+use crate::error::{self, Error};
+use crate::registry::Primitive;
+use crate::verdict::{self, Compliance};
+
+#[derive(Serialize)]
+struct VerdictJson {
+  passed: bool,
+  compliance: &'static str,
+  got: String,
+  want: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  cutoff: Option<u16>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  rule_id: Option<&'static str>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  citation: Option<String>,
+}
+
+#[derive(Serialize)]
+struct AssessmentJson {
+  passed: bool,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  got_hash_function: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  want_hash_function: Option<String>,
+  got_signature: String,
+  want_signature: String,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  references: Vec<Citation>,
+}
+
+fn name(primitive: Primitive) -> String {
+  match primitive {
+    Primitive::Ecc(key) => key.to_string(),
+    Primitive::Ffc(key) => key.to_string(),
+    Primitive::Hash(hash) => hash.to_string(),
+    Primitive::Ifc(key) => key.to_string(),
+    Primitive::Symmetric(key) => key.to_string(),
+  }
+}
+
+fn key_name(key: Key) -> String {
+  match key {
+    Key::Ecc(key) => key.to_string(),
+    Key::Ffc(key) => key.to_string(),
+    Key::Ifc(key) => key.to_string(),
+    Key::Pqc(key) => key.to_string(),
+    Key::Hybrid(key) => key.to_string(),
+    Key::Stateful(key) => key.to_string(),
+  }
+}
+
+fn rule(guide: Guide, scope: Scope) -> Option<&'static Rule> {
+  match guide {
+    Guide::Bsi => Bsi::rule(scope),
+    Guide::Cnsa => Cnsa::rule(scope),
+    Guide::Ecrypt => Ecrypt::rule(scope),
+    Guide::Lenstra => Lenstra::rule(scope),
+    Guide::Nist => Nist::rule(scope),
+    Guide::Iot => Iot::rule(scope),
+  }
+}
+
+fn ffc_rule(guide: Guide, key: Ffc) -> Option<&'static Rule> {
+  match guide {
+    Guide::Bsi => Bsi::ffc_rule(key),
+    Guide::Cnsa => Cnsa::ffc_rule(key),
+    Guide::Ecrypt => Ecrypt::ffc_rule(key),
+    Guide::Lenstra => Lenstra::ffc_rule(key),
+    Guide::Nist => Nist::ffc_rule(key),
+    Guide::Iot => Iot::ffc_rule(key),
+  }
+}
+
+// Hands the JSON to the caller who frees it with `ws_free`.
+fn into_raw<T: Serialize>(value: &T) -> *mut c_char {
+  // The structures only hold strings, numbers and booleans and JSON
+  // escapes null bytes, so neither conversion can fail.
+  let json = serde_json::to_string(value).unwrap_or_default();
+  CString::new(json).unwrap_or_default().into_raw()
+}
+
+/// Assesses a primitive of any kind against a standard in the same way
+/// as `ws_nist_assess` and the other `ws_*_assess` functions and returns
+/// the outcome as a JSON object such as:
+///
+/// ```json
+/// {"passed":true,"compliance":"expiring","got":"rsa_pkcs1_2048","want":"rsa_pss_2048","cutoff":2031,"rule_id":"NIST-IFC-001","citation":"..."}
+/// ```
+///
+/// `compliance` is one of `"compliant"`, `"expiring"` and
+/// `"non_compliant"`, and `want` the recommended alternative. `rule_id`
+/// is the stable identifier of the rule that applies to the primitive.
+/// `cutoff`, `rule_id` and `citation` are absent if the rule does not
+/// specify them.
+///
+/// The string must be freed with `ws_free`. Null is returned if the
+/// primitive cannot be assessed or `guide` is not one of the `ws_guide`
+/// values, in which case `ws_last_error` describes the problem.
+#[no_mangle]
+pub extern "C" fn ws_assess_to_json(
+  ctx: Context,
+  guide: c_int,
+  primitive: Primitive,
+) -> *mut c_char {
+  let Ok(guide) = certificate::guide(guide) else {
+    // The error was recorded when the code was returned.
+    return ptr::null_mut();
+  };
+  let ctx = CoreContext::from(ctx);
+  let result = match guide {
+    Guide::Bsi => verdict::evaluate::<Bsi>(ctx, primitive),
+    Guide::Cnsa => verdict::evaluate::<Cnsa>(ctx, primitive),
+    Guide::Ecrypt => verdict::evaluate::<Ecrypt>(ctx, primitive),
+    Guide::Lenstra => verdict::evaluate::<Lenstra>(ctx, primitive),
+    Guide::Nist => verdict::evaluate::<Nist>(ctx, primitive),
+    Guide::Iot => verdict::evaluate::<Iot>(ctx, primitive),
+  };
+  let verdict = match result {
+    Ok(verdict) => verdict,
+    // The error was recorded when the code was returned.
+    Err(_) => return ptr::null_mut(),
+  };
+  let compliance = match verdict.compliance {
+    Compliance::Compliant => "compliant",
+    Compliance::Expiring => "expiring",
+    Compliance::NonCompliant => "non_compliant",
+  };
+  // Citations are static strings owned by the library.
+  let citation = (!verdict.citation.is_null()).then(|| {
+    unsafe { CStr::from_ptr(verdict.citation) }
+      .to_string_lossy()
+      .into_owned()
+  });
+  let rule = match primitive {
+    Primitive::Ecc(_) => rule(guide, Scope::Ecc),
+    Primitive::Ffc(key) => ffc_rule(guide, key),
+    Primitive::Hash(_) => rule(guide, Scope::Hash),
+    Primitive::Ifc(_) => rule(guide, Scope::Ifc),
+    Primitive::Symmetric(_) => rule(guide, Scope::Symmetric),
+  };
+  into_raw(&VerdictJson {
+    passed: verdict.compliance != Compliance::NonCompliant,
+    compliance,
+    got: name(primitive),
+    want: name(verdict.alternative),
+    cutoff: (verdict.cutoff != 0).then_some(verdict.cutoff),
+    rule_id: rule.map(|rule| rule.id),
+    citation,
+  })
+}
+
+/// Assesses a DER or PEM encoded X.509 certificate against a standard in
+/// the same way as `ws_assess_x509` and returns the outcome as a JSON
+/// object such as:
+///
+/// ```json
+/// {"passed":false,"got_hash_function":"sha1","want_hash_function":"sha224","got_signature":"rsa_pkcs1_2048","want_signature":"rsa_pss_2048"}
+/// ```
+///
+/// The hash function keys are absent if the signature algorithm does
+/// not use a separate hash function, as is the case for EdDSA.
+///
+/// The string must be freed with `ws_free`. Null is returned if the
+/// certificate cannot be assessed or `guide` is not one of the
+/// `ws_guide` values, in which case `ws_last_error` describes the
+/// problem.
+///
+/// # Safety
+///
+/// `data` must point to at least `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ws_assess_x509_to_json(
+  ctx: Context,
+  guide: c_int,
+  data: *const u8,
+  len: usize,
+) -> *mut c_char {
+  let Ok(guide) = certificate::guide(guide) else {
+    // The error was recorded when the code was returned.
+    return ptr::null_mut();
+  };
+  let ctx = CoreContext::from(ctx);
+  if data.is_null() {
+    error::fail(Error::NullPrimitive, "certificate data is null");
+    return ptr::null_mut();
+  }
+  let assessment = match certificate::evaluate(ctx, guide, slice::from_raw_parts(data, len)) {
+    Ok(assessment) => assessment,
+    // The error was recorded when the code was returned.
+    Err(_) => return ptr::null_mut(),
+  };
+  let has_hash_function = assessment.has_hash_function;
+  let key_scope = match assessment.key {
+    Key::Ecc(_) => Scope::Ecc,
+    Key::Ffc(_) => Scope::Ffc,
+    Key::Ifc(_) => Scope::Ifc,
+    Key::Pqc(_) | Key::Hybrid(_) => Scope::Pqc,
+    Key::Stateful(_) => Scope::Stateful,
+  };
+  let mut references = Vec::new();
+  let scopes = [has_hash_function.then_some(Scope::Hash), Some(key_scope)];
+  for rule in scopes
+    .into_iter()
+    .flatten()
+    .filter_map(|scope| rule(guide, scope))
+  {
+    if !references.contains(&rule.citation) {
+      references.push(rule.citation);
+    }
+  }
+  into_raw(&AssessmentJson {
+    passed: assessment.hash_function_compliant && assessment.key_compliant,
+    got_hash_function: has_hash_function.then(|| assessment.hash_function.to_string()),
+    want_hash_function: has_hash_function.then(|| assessment.hash_function_alternative.to_string()),
+    got_signature: key_name(assessment.key),
+    want_signature: key_name(assessment.key_alternative),
+    references,
+  })
+}
+
+/// Frees a string returned by `ws_assess_to_json` or
+/// `ws_assess_x509_to_json`. Passing null does nothing.
+///
+/// # Safety
+///
+/// `s` must have been returned by the library and not been freed
+/// already.
+#[no_mangle]
+pub unsafe extern "C" fn ws_free(s: *mut c_char) {
+  if !s.is_null() {
+    drop(CString::from_raw(s));
+  }
+}
@@ -0,0 +1,84 @@
+//! Pins the layout of every structure and enumeration in the C API.
+//!
+//! Programs built against one release of the header must keep working
+//! with later releases of the library, so the sizes, alignments and
+//! field offsets below may only change by fields being appended to the
+//! structures that start with a `size` field. Any other change fails
+//! the build. `examples/ffi/layout.c` makes the same assertions against
+//! the generated header so that the two cannot drift apart.
+use std::mem::{align_of, offset_of, size_of};
+
+use wardstone_core::primitive::ecc::Ecc;
+use wardstone_core::primitive::ffc::Ffc;
+use wardstone_core::primitive::hash::Hash;
+use wardstone_core::primitive::hybrid::Hybrid;
+use wardstone_core::primitive::ifc::Ifc;
+use wardstone_core::primitive::pqc::Pqc;
+use wardstone_core::primitive::stateful::Stateful;
+use wardstone_core::primitive::symmetric::Symmetric;
+use wardstone_core::standard::rule::Scope;
+
+use crate::certificate::{Assessment, Guide, Key};
+use crate::context::Context;
+use crate::error::Error;
+use crate::registry::Primitive;
+use crate::verdict::{Compliance, Verdict};
+
+macro_rules! assert_layout {
+  ($t:ty, $size:expr, $align:expr $(, $field:ident: $offset:expr)*) => {
+    const _: () = assert!(size_of::<$t>() == $size);
+    const _: () = assert!(align_of::<$t>() == $align);
+    $(const _: () = assert!(offset_of!($t, $field) == $offset);)*
+  };
+}
+
+// The fields of a context are private and only checked by the header.
+assert_layout!(Context, 4, 2);
+assert_layout!(Ecc, 4, 2, id: 0, f: 2);
+assert_layout!(Ffc, 6, 2, id: 0, l: 2, n: 4);
+assert_layout!(Hash, 4, 2, id: 0, n: 2);
+assert_layout!(Ifc, 4, 2, id: 0, k: 2);
+assert_layout!(Symmetric, 4, 2, id: 0, security: 2);
+assert_layout!(Pqc, 4, 2, id: 0, security: 2);
+assert_layout!(Hybrid, 8, 2, pqc: 0, ecc: 4);
+assert_layout!(Stateful, 4, 2, id: 0, n: 2);
+
+// Tagged unions are laid out as a C enumeration followed by a union of
+// the variants.
+assert_layout!(Primitive, 12, 4);
+assert_layout!(Key, 12, 4);
+
+assert_layout!(Compliance, 4, 4);
+assert_layout!(Error, 4, 4);
+assert_layout!(Guide, 4, 4);
+assert_layout!(Scope, 4, 4);
+
+// Citations are pointers whose size depends on the platform.
+#[cfg(target_pointer_width = "64")]
+assert_layout!(
+  Assessment,
+  64,
+  8,
+  size: 0,
+  has_hash_function: 4,
+  hash_function: 6,
+  hash_function_alternative: 10,
+  hash_function_compliant: 14,
+  key: 16,
+  key_alternative: 28,
+  key_compliant: 40,
+  hash_function_citation: 48,
+  key_citation: 56
+);
+
+#[cfg(target_pointer_width = "64")]
+assert_layout!(
+  Verdict,
+  32,
+  8,
+  size: 0,
+  compliance: 4,
+  cutoff: 8,
+  alternative: 12,
+  citation: 24
+);
@@ -30,8 +30,23 @@
 //! }
 //! ```
 //!
+//! Functions return a negative `ws_err` code instead of `0` or `1` when
+//! they are used incorrectly, for example when passed a primitive that
+//! was never initialised. `ws_strerror` describes the code and
+//! `ws_last_error` returns a message about the most recent error on the
+//! calling thread.
+//!
 //! [`cbindgen`]: https://github.com/mozilla/cbindgen
+pub mod batch;
+pub mod certificate;
 pub mod context;
+pub mod error;
+pub mod json;
+mod layout;
+pub mod policy;
 pub mod primitives;
+pub mod registry;
 pub mod standards;
 mod utilities;
+pub mod verdict;
+pub mod version;
@@ -0,0 +1,293 @@
+//! Load organisation specific policies from a file at runtime.
+//!
+//! A policy builds on one of the standards and raises the security level
+//! and year that primitives are assessed against, so that C callers can
+//! enforce stricter requirements without recompiling the library. It is
+//! written as `key = value` lines where `#` starts a comment:
+//!
+//! ```text
+//! # Assess against NIST but require 192 bits of security until 2040.
+//! guide = nist
+//! security = 192
+//! year = 2040
+//! # Accept 112-bit RSA keys until the end of 2035.
+//! cutoff.ifc = 2035
+//! ```
+//!
+//! `guide` is required and names the standard as on the command line.
+//! `security` and `year` are optional and act as floors: the context
+//! passed to an assessment is raised to them but never lowered.
+//! `cutoff.` followed by a type of primitive such as `ecc` or
+//! `hash-based` replaces the cutoff year of the standard for that type.
+//! The cutoff years can also be replaced once the policy is loaded with
+//! `ws_policy_set_cutoff`.
+use std::ffi::{c_char, c_int, CStr};
+use std::fs;
+use std::ptr;
+
+use wardstone_core::context::Context as CoreContext;
+use wardstone_core::standard::bsi::Bsi;
+use wardstone_core::standard::cnsa::Cnsa;
+use wardstone_core::standard::ecrypt::Ecrypt;
+use wardstone_core::standard::iot::Iot;
+use wardstone_core::standard::lenstra::Lenstra;
+use wardstone_core::standard::nist::Nist;
+use wardstone_core::standard::rule::Scope;
+
+use crate::certificate::{self, Assessment, Guide};
+use crate::context::Context;
+use crate::error::{self, Error};
+use crate::registry::Primitive;
+use crate::verdict::{self, Verdict};
+
+/// An organisation specific policy loaded with `ws_policy_load`.
+///
+/// It is opaque to C callers and must be released with
+/// `ws_policy_free`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Policy {
+  guide: Guide,
+  security: u16,
+  year: u16,
+  cutoffs: [u16; Scope::ALL.len()],
+}
+
+impl Policy {
+  fn parse(text: &str) -> Result<Self, String> {
+    let mut guide = None;
+    let mut security = 0;
+    let mut year = 0;
+    let mut cutoffs = [0; Scope::ALL.len()];
+    for (i, line) in text.lines().enumerate() {
+      let line = line.split('#').next().unwrap_or_default().trim();
+      if line.is_empty() {
+        continue;
+      }
+      let (key, value) = line
+        .split_once('=')
+        .ok_or_else(|| format!("line {}: expected key = value", i + 1))?;
+      let value = value.trim();
+      match key.trim() {
+        "guide" => {
+          guide = Some(match value.to_ascii_lowercase().as_str() {
+            "bsi" => Guide::Bsi,
+            "cnsa" => Guide::Cnsa,
+            "ecrypt" => Guide::Ecrypt,
+            "iot" => Guide::Iot,
+            "lenstra" => Guide::Lenstra,
+            "nist" => Guide::Nist,
+            _ => return Err(format!("line {}: unknown guide {}", i + 1, value)),
+          })
+        },
+        "security" => {
+          security = value
+            .parse()
+            .map_err(|_| format!("line {}: invalid security {}", i + 1, value))?
+        },
+        "year" => {
+          year = value
+            .parse()
+            .map_err(|_| format!("line {}: invalid year {}", i + 1, value))?
+        },
+        key if key.starts_with("cutoff.") => {
+          let scope = Scope::ALL
+            .into_iter()
+            .find(|scope| key.strip_prefix("cutoff.") == Some(scope.to_string().as_str()))
+            .ok_or_else(|| format!("line {}: unknown type of primitive {}", i + 1, key))?;
+          cutoffs[scope as usize] = value
+            .parse()
+            .map_err(|_| format!("line {}: invalid year {}", i + 1, value))?
+        },
+        key => return Err(format!("line {}: unknown key {}", i + 1, key)),
+      }
+    }
+    let guide = guide.ok_or("guide is missing")?;
+    Ok(Self {
+      guide,
+      security,
+      year,
+      cutoffs,
+    })
+  }
+
+  fn context(&self, ctx: Context) -> CoreContext {
+    let ctx = CoreContext::from(ctx);
+    let ctx = ctx
+      .with_security(ctx.security().max(self.security))
+      .with_year(ctx.year().max(self.year));
+    Scope::ALL.into_iter().fold(ctx, |ctx, scope| {
+      ctx.with_cutoff(scope, self.cutoffs[scope as usize])
+    })
+  }
+}
+
+/// Returns the type of primitive a caller passed as an `int`, or records
+/// an error and returns its code if it is not one of the `ws_scope`
+/// values.
+fn scope(value: c_int) -> Result<Scope, c_int> {
+  Scope::ALL
+    .into_iter()
+    .find(|&scope| scope as c_int == value)
+    .ok_or_else(|| {
+      error::fail(
+        Error::InvalidArgument,
+        &format!("{} is not a ws_scope", value),
+      )
+    })
+}
+
+/// Loads a policy from the file at `path`.
+///
+/// The function returns a handle that must be released with
+/// `ws_policy_free`, or null if the file cannot be read or is not a
+/// valid policy, in which case `ws_last_error` describes the problem.
+///
+/// # Safety
+///
+/// `path` must be a null-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn ws_policy_load(path: *const c_char) -> *mut Policy {
+  if path.is_null() {
+    error::fail(Error::NullPrimitive, "path is null");
+    return ptr::null_mut();
+  }
+  let path = match CStr::from_ptr(path).to_str() {
+    Ok(path) => path,
+    Err(err) => {
+      error::fail(Error::Parse, &err.to_string());
+      return ptr::null_mut();
+    },
+  };
+  let text = match fs::read_to_string(path) {
+    Ok(text) => text,
+    Err(err) => {
+      error::fail(Error::Io, &format!("{}: {}", path, err));
+      return ptr::null_mut();
+    },
+  };
+  match Policy::parse(&text) {
+    Ok(policy) => Box::into_raw(Box::new(policy)),
+    Err(err) => {
+      error::fail(Error::Parse, &format!("{}: {}", path, err));
+      ptr::null_mut()
+    },
+  }
+}
+
+/// Releases a policy returned by `ws_policy_load`. Passing null does
+/// nothing.
+///
+/// # Safety
+///
+/// `policy` must have been returned by `ws_policy_load` and not been
+/// freed already.
+#[no_mangle]
+pub unsafe extern "C" fn ws_policy_free(policy: *mut Policy) {
+  if !policy.is_null() {
+    drop(Box::from_raw(policy));
+  }
+}
+
+/// Assesses a primitive of any kind against a policy in the same way as
+/// `ws_nist_assess` and the other `ws_*_assess` functions do against a
+/// standard.
+///
+/// The function returns `1` if the primitive is compliant, `0` if it is
+/// not, and a negative `ws_err` code if the policy is null or an error
+/// occurs as a result of an invalid argument.
+///
+/// # Safety
+///
+/// `policy` must have been returned by `ws_policy_load`.
+#[no_mangle]
+pub unsafe extern "C" fn ws_policy_assess(
+  policy: *const Policy,
+  ctx: Context,
+  primitive: Primitive,
+  verdict: *mut Verdict,
+) -> c_int {
+  let Some(policy) = policy.as_ref() else {
+    return error::fail(Error::NullContext, "policy is null");
+  };
+  let ctx = policy.context(ctx);
+  match policy.guide {
+    Guide::Bsi => verdict::assess::<Bsi>(ctx, primitive, verdict),
+    Guide::Cnsa => verdict::assess::<Cnsa>(ctx, primitive, verdict),
+    Guide::Ecrypt => verdict::assess::<Ecrypt>(ctx, primitive, verdict),
+    Guide::Lenstra => verdict::assess::<Lenstra>(ctx, primitive, verdict),
+    Guide::Nist => verdict::assess::<Nist>(ctx, primitive, verdict),
+    Guide::Iot => verdict::assess::<Iot>(ctx, primitive, verdict),
+  }
+}
+
+/// Assesses a DER or PEM encoded X.509 certificate against a policy in
+/// the same way as `ws_assess_x509` does against a guide.
+///
+/// # Safety
+///
+/// `policy` must have been returned by `ws_policy_load` and `data` must
+/// point to at least `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ws_policy_assess_x509(
+  policy: *const Policy,
+  ctx: Context,
+  data: *const u8,
+  len: usize,
+  assessment: *mut Assessment,
+) -> c_int {
+  let Some(policy) = policy.as_ref() else {
+    return error::fail(Error::NullContext, "policy is null");
+  };
+  certificate::assess_x509(policy.context(ctx), policy.guide, data, len, assessment)
+}
+
+/// Returns the year that replaces the cutoff year of the standard for
+/// primitives in `scope`, one of the `ws_scope` values, or `0` if the
+/// standard's own is used.
+///
+/// The function returns a negative `ws_err` code if the policy is null
+/// or `scope` is not one of the `ws_scope` values.
+///
+/// # Safety
+///
+/// `policy` must have been returned by `ws_policy_load`.
+#[no_mangle]
+pub unsafe extern "C" fn ws_policy_cutoff(policy: *const Policy, scope: c_int) -> c_int {
+  let Some(policy) = policy.as_ref() else {
+    return error::fail(Error::NullContext, "policy is null");
+  };
+  match self::scope(scope) {
+    Ok(scope) => policy.cutoffs[scope as usize].into(),
+    Err(err) => err,
+  }
+}
+
+/// Replaces the cutoff year of the standard for primitives in `scope`,
+/// one of the `ws_scope` values such as `WS_SCOPE_IFC`, so that a date
+/// moved by the body behind a standard can be followed straight away.
+/// Setting `year` to `0` restores the cutoff year specified in the
+/// standard.
+///
+/// The function returns `0` on success and a negative `ws_err` code if
+/// the policy is null or `scope` is not one of the `ws_scope` values.
+///
+/// # Safety
+///
+/// `policy` must have been returned by `ws_policy_load`.
+#[no_mangle]
+pub unsafe extern "C" fn ws_policy_set_cutoff(
+  policy: *mut Policy,
+  scope: c_int,
+  year: u16,
+) -> c_int {
+  let Some(policy) = policy.as_mut() else {
+    return error::fail(Error::NullContext, "policy is null");
+  };
+  match self::scope(scope) {
+    Ok(scope) => {
+      policy.cutoffs[scope as usize] = year;
+      0
+    },
+    Err(err) => err,
+  }
+}
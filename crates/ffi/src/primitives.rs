@@ -1,5 +1,9 @@
 //! Submodules that contain common cryptographic primitives and their
 //! instances.
+//!
+//! Each instance is exported both as a static, such as `WS_SHA256`, and
+//! as a function that returns it, such as `ws_hash_sha256`, for callers
+//! such as language bindings that cannot read exported data.
 pub mod ecc;
 pub mod ffc;
 pub mod hash;
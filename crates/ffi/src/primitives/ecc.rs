@@ -1,7 +1,11 @@
 //! Specifies a set of commonly used elliptic curve cryptography
 //! instances.
+use std::ffi::{c_char, c_int};
+
 use wardstone_core::primitive::ecc::*;
 
+use crate::utilities;
+
 /// Represents the Weierstrass curve B-163 over a prime field. Also
 /// known as sect163r2.
 #[no_mangle]
@@ -27,6 +31,11 @@ pub static WS_B409: Ecc = B409;
 #[no_mangle]
 pub static WS_B571: Ecc = B571;
 
+/// Represents the pairing-friendly curve BLS12-381 that BLS signatures
+/// are made over.
+#[no_mangle]
+pub static WS_BLS12_381: Ecc = BLS12_381;
+
 /// Represents the curve brainpoolP160r1 specified in [RFC 5639].
 ///
 /// [RFC 5639]: https://datatracker.ietf.org/doc/rfc5639
@@ -182,6 +191,13 @@ pub static WS_C2TNB431R1: Ecc = C2TNB431R1;
 #[no_mangle]
 pub static WS_ED25519: Ecc = ED25519;
 
+/// Represents Ed25519ph, the variant of Ed25519 that signs the SHA-512
+/// digest of a message, as specified in [RFC 8032].
+///
+/// [RFC 8032]: https://datatracker.ietf.org/doc/html/rfc8032#section-5.1
+#[no_mangle]
+pub static WS_ED25519PH: Ecc = ED25519PH;
+
 /// Represents the Ed448 signature algorithm as specified in the paper
 /// [High-speed high-security signatures].
 ///
@@ -189,6 +205,13 @@ pub static WS_ED25519: Ecc = ED25519;
 #[no_mangle]
 pub static WS_ED448: Ecc = ED448;
 
+/// Represents Ed448ph, the variant of Ed448 that signs the SHAKE256
+/// digest of a message, as specified in [RFC 8032].
+///
+/// [RFC 8032]: https://datatracker.ietf.org/doc/html/rfc8032#section-5.2
+#[no_mangle]
+pub static WS_ED448PH: Ecc = ED448PH;
+
 /// Represents the Weierstrass curve K-163 over a prime field. Also
 /// known as wap-wsg-idm-ecid-wtls3.
 #[no_mangle]
@@ -585,3 +608,655 @@ pub static WS_ECC_512: Ecc = ECC_512;
 /// implies is not allowed.
 #[no_mangle]
 pub static WS_ECC_NOT_ALLOWED: Ecc = ECC_NOT_ALLOWED;
+
+/// Returns `WS_B163`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_b163() -> Ecc {
+  B163
+}
+
+/// Returns `WS_B233`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_b233() -> Ecc {
+  B233
+}
+
+/// Returns `WS_B283`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_b283() -> Ecc {
+  B283
+}
+
+/// Returns `WS_B409`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_b409() -> Ecc {
+  B409
+}
+
+/// Returns `WS_B571`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_b571() -> Ecc {
+  B571
+}
+
+/// Returns `WS_BLS12_381`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_bls12_381() -> Ecc {
+  BLS12_381
+}
+
+/// Returns `WS_BRAINPOOLP160R1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_brainpoolp160r1() -> Ecc {
+  BRAINPOOLP160R1
+}
+
+/// Returns `WS_BRAINPOOLP160T1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_brainpoolp160t1() -> Ecc {
+  BRAINPOOLP160T1
+}
+
+/// Returns `WS_BRAINPOOLP192R1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_brainpoolp192r1() -> Ecc {
+  BRAINPOOLP192R1
+}
+
+/// Returns `WS_BRAINPOOLP192T1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_brainpoolp192t1() -> Ecc {
+  BRAINPOOLP192T1
+}
+
+/// Returns `WS_BRAINPOOLP224R1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_brainpoolp224r1() -> Ecc {
+  BRAINPOOLP224R1
+}
+
+/// Returns `WS_BRAINPOOLP224T1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_brainpoolp224t1() -> Ecc {
+  BRAINPOOLP224T1
+}
+
+/// Returns `WS_BRAINPOOLP256R1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_brainpoolp256r1() -> Ecc {
+  BRAINPOOLP256R1
+}
+
+/// Returns `WS_BRAINPOOLP256T1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_brainpoolp256t1() -> Ecc {
+  BRAINPOOLP256T1
+}
+
+/// Returns `WS_BRAINPOOLP320R1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_brainpoolp320r1() -> Ecc {
+  BRAINPOOLP320R1
+}
+
+/// Returns `WS_BRAINPOOLP320T1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_brainpoolp320t1() -> Ecc {
+  BRAINPOOLP320T1
+}
+
+/// Returns `WS_BRAINPOOLP384R1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_brainpoolp384r1() -> Ecc {
+  BRAINPOOLP384R1
+}
+
+/// Returns `WS_BRAINPOOLP384T1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_brainpoolp384t1() -> Ecc {
+  BRAINPOOLP384T1
+}
+
+/// Returns `WS_BRAINPOOLP512R1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_brainpoolp512r1() -> Ecc {
+  BRAINPOOLP512R1
+}
+
+/// Returns `WS_BRAINPOOLP512T1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_brainpoolp512t1() -> Ecc {
+  BRAINPOOLP512T1
+}
+
+/// Returns `WS_C2PNB163V1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_c2pnb163v1() -> Ecc {
+  C2PNB163V1
+}
+
+/// Returns `WS_C2PNB163V2`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_c2pnb163v2() -> Ecc {
+  C2PNB163V2
+}
+
+/// Returns `WS_C2PNB163V3`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_c2pnb163v3() -> Ecc {
+  C2PNB163V3
+}
+
+/// Returns `WS_C2PNB176V1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_c2pnb176v1() -> Ecc {
+  C2PNB176V1
+}
+
+/// Returns `WS_C2PNB208W1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_c2pnb208w1() -> Ecc {
+  C2PNB208W1
+}
+
+/// Returns `WS_C2PNB272W1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_c2pnb272w1() -> Ecc {
+  C2PNB272W1
+}
+
+/// Returns `WS_C2PNB304W1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_c2pnb304w1() -> Ecc {
+  C2PNB304W1
+}
+
+/// Returns `WS_C2PNB368W1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_c2pnb368w1() -> Ecc {
+  C2PNB368W1
+}
+
+/// Returns `WS_C2TNB191V1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_c2tnb191v1() -> Ecc {
+  C2TNB191V1
+}
+
+/// Returns `WS_C2TNB191V2`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_c2tnb191v2() -> Ecc {
+  C2TNB191V2
+}
+
+/// Returns `WS_C2TNB191V3`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_c2tnb191v3() -> Ecc {
+  C2TNB191V3
+}
+
+/// Returns `WS_C2TNB239V1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_c2tnb239v1() -> Ecc {
+  C2TNB239V1
+}
+
+/// Returns `WS_C2TNB239V2`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_c2tnb239v2() -> Ecc {
+  C2TNB239V2
+}
+
+/// Returns `WS_C2TNB239V3`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_c2tnb239v3() -> Ecc {
+  C2TNB239V3
+}
+
+/// Returns `WS_C2TNB359V1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_c2tnb359v1() -> Ecc {
+  C2TNB359V1
+}
+
+/// Returns `WS_C2TNB431R1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_c2tnb431r1() -> Ecc {
+  C2TNB431R1
+}
+
+/// Returns `WS_ED25519`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_ed25519() -> Ecc {
+  ED25519
+}
+
+/// Returns `WS_ED25519PH`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_ed25519ph() -> Ecc {
+  ED25519PH
+}
+
+/// Returns `WS_ED448`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_ed448() -> Ecc {
+  ED448
+}
+
+/// Returns `WS_ED448PH`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_ed448ph() -> Ecc {
+  ED448PH
+}
+
+/// Returns `WS_K163`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_k163() -> Ecc {
+  K163
+}
+
+/// Returns `WS_K233`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_k233() -> Ecc {
+  K233
+}
+
+/// Returns `WS_K409`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_k409() -> Ecc {
+  K409
+}
+
+/// Returns `WS_K571`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_k571() -> Ecc {
+  K571
+}
+
+/// Returns `WS_P192`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_p192() -> Ecc {
+  P192
+}
+
+/// Returns `WS_P224`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_p224() -> Ecc {
+  P224
+}
+
+/// Returns `WS_P256`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_p256() -> Ecc {
+  P256
+}
+
+/// Returns `WS_P384`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_p384() -> Ecc {
+  P384
+}
+
+/// Returns `WS_P521`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_p521() -> Ecc {
+  P521
+}
+
+/// Returns `WS_PRIME192V1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_prime192v1() -> Ecc {
+  PRIME192V1
+}
+
+/// Returns `WS_PRIME192V2`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_prime192v2() -> Ecc {
+  PRIME192V2
+}
+
+/// Returns `WS_PRIME192V3`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_prime192v3() -> Ecc {
+  PRIME192V3
+}
+
+/// Returns `WS_PRIME239V1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_prime239v1() -> Ecc {
+  PRIME239V1
+}
+
+/// Returns `WS_PRIME239V2`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_prime239v2() -> Ecc {
+  PRIME239V2
+}
+
+/// Returns `WS_PRIME239V3`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_prime239v3() -> Ecc {
+  PRIME239V3
+}
+
+/// Returns `WS_PRIME256V1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_prime256v1() -> Ecc {
+  PRIME256V1
+}
+
+/// Returns `WS_SECP112R1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_secp112r1() -> Ecc {
+  SECP112R1
+}
+
+/// Returns `WS_SECP112R2`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_secp112r2() -> Ecc {
+  SECP112R2
+}
+
+/// Returns `WS_SECP128R1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_secp128r1() -> Ecc {
+  SECP128R1
+}
+
+/// Returns `WS_SECP128R2`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_secp128r2() -> Ecc {
+  SECP128R2
+}
+
+/// Returns `WS_SECP160R1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_secp160r1() -> Ecc {
+  SECP160R1
+}
+
+/// Returns `WS_SECP160R2`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_secp160r2() -> Ecc {
+  SECP160R2
+}
+
+/// Returns `WS_SECP192R1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_secp192r1() -> Ecc {
+  SECP192R1
+}
+
+/// Returns `WS_SECP192K1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_secp192k1() -> Ecc {
+  SECP192K1
+}
+
+/// Returns `WS_SECP224R1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_secp224r1() -> Ecc {
+  SECP224R1
+}
+
+/// Returns `WS_SECP224K1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_secp224k1() -> Ecc {
+  SECP224K1
+}
+
+/// Returns `WS_SECP256K1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_secp256k1() -> Ecc {
+  SECP256K1
+}
+
+/// Returns `WS_SECP256R1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_secp256r1() -> Ecc {
+  SECP256R1
+}
+
+/// Returns `WS_SECP384R1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_secp384r1() -> Ecc {
+  SECP384R1
+}
+
+/// Returns `WS_SECP521R1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_secp521r1() -> Ecc {
+  SECP521R1
+}
+
+/// Returns `WS_SECT113R1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_sect113r1() -> Ecc {
+  SECT113R1
+}
+
+/// Returns `WS_SECT113R2`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_sect113r2() -> Ecc {
+  SECT113R2
+}
+
+/// Returns `WS_SECT131R1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_sect131r1() -> Ecc {
+  SECT131R1
+}
+
+/// Returns `WS_SECT131R2`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_sect131r2() -> Ecc {
+  SECT131R2
+}
+
+/// Returns `WS_SECT163K1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_sect163k1() -> Ecc {
+  SECT163K1
+}
+
+/// Returns `WS_SECT163R1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_sect163r1() -> Ecc {
+  SECT163R1
+}
+
+/// Returns `WS_SECT163R2`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_sect163r2() -> Ecc {
+  SECT163R2
+}
+
+/// Returns `WS_SECT193R1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_sect193r1() -> Ecc {
+  SECT193R1
+}
+
+/// Returns `WS_SECT193R2`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_sect193r2() -> Ecc {
+  SECT193R2
+}
+
+/// Returns `WS_SECT233K1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_sect233k1() -> Ecc {
+  SECT233K1
+}
+
+/// Returns `WS_SECT233R1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_sect233r1() -> Ecc {
+  SECT233R1
+}
+
+/// Returns `WS_SECT239K1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_sect239k1() -> Ecc {
+  SECT239K1
+}
+
+/// Returns `WS_SECT283R1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_sect283r1() -> Ecc {
+  SECT283R1
+}
+
+/// Returns `WS_SECT409K1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_sect409k1() -> Ecc {
+  SECT409K1
+}
+
+/// Returns `WS_SECT409R1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_sect409r1() -> Ecc {
+  SECT409R1
+}
+
+/// Returns `WS_SECT571K1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_sect571k1() -> Ecc {
+  SECT571K1
+}
+
+/// Returns `WS_SECT571R1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_sect571r1() -> Ecc {
+  SECT571R1
+}
+
+/// Returns `WS_SM2`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_sm2() -> Ecc {
+  SM2
+}
+
+/// Returns `WS_WAP_WSG_IDM_ECID_WTLS1`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_wap_wsg_idm_ecid_wtls1() -> Ecc {
+  WAP_WSG_IDM_ECID_WTLS1
+}
+
+/// Returns `WS_WAP_WSG_IDM_ECID_WTLS3`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_wap_wsg_idm_ecid_wtls3() -> Ecc {
+  WAP_WSG_IDM_ECID_WTLS3
+}
+
+/// Returns `WS_WAP_WSG_IDM_ECID_WTLS4`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_wap_wsg_idm_ecid_wtls4() -> Ecc {
+  WAP_WSG_IDM_ECID_WTLS4
+}
+
+/// Returns `WS_WAP_WSG_IDM_ECID_WTLS5`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_wap_wsg_idm_ecid_wtls5() -> Ecc {
+  WAP_WSG_IDM_ECID_WTLS5
+}
+
+/// Returns `WS_WAP_WSG_IDM_ECID_WTLS6`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_wap_wsg_idm_ecid_wtls6() -> Ecc {
+  WAP_WSG_IDM_ECID_WTLS6
+}
+
+/// Returns `WS_WAP_WSG_IDM_ECID_WTLS7`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_wap_wsg_idm_ecid_wtls7() -> Ecc {
+  WAP_WSG_IDM_ECID_WTLS7
+}
+
+/// Returns `WS_WAP_WSG_IDM_ECID_WTLS8`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_wap_wsg_idm_ecid_wtls8() -> Ecc {
+  WAP_WSG_IDM_ECID_WTLS8
+}
+
+/// Returns `WS_WAP_WSG_IDM_ECID_WTLS9`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_wap_wsg_idm_ecid_wtls9() -> Ecc {
+  WAP_WSG_IDM_ECID_WTLS9
+}
+
+/// Returns `WS_WAP_WSG_IDM_ECID_WTLS10`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_wap_wsg_idm_ecid_wtls10() -> Ecc {
+  WAP_WSG_IDM_ECID_WTLS10
+}
+
+/// Returns `WS_WAP_WSG_IDM_ECID_WTLS11`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_wap_wsg_idm_ecid_wtls11() -> Ecc {
+  WAP_WSG_IDM_ECID_WTLS11
+}
+
+/// Returns `WS_WAP_WSG_IDM_ECID_WTLS12`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_wap_wsg_idm_ecid_wtls12() -> Ecc {
+  WAP_WSG_IDM_ECID_WTLS12
+}
+
+/// Returns `WS_X25519`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_x25519() -> Ecc {
+  X25519
+}
+
+/// Returns `WS_X448`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_x448() -> Ecc {
+  X448
+}
+
+/// Returns `WS_ECC_224`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_224() -> Ecc {
+  ECC_224
+}
+
+/// Returns `WS_ECC_256`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_256() -> Ecc {
+  ECC_256
+}
+
+/// Returns `WS_ECC_384`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_384() -> Ecc {
+  ECC_384
+}
+
+/// Returns `WS_ECC_512`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_512() -> Ecc {
+  ECC_512
+}
+
+/// Returns `WS_ECC_NOT_ALLOWED`.
+#[no_mangle]
+pub extern "C" fn ws_ecc_not_allowed() -> Ecc {
+  ECC_NOT_ALLOWED
+}
+
+/// Writes the name of an elliptic curve
+/// to `buf`, which holds `len` bytes.
+///
+/// The name is truncated to fit and always null-terminated unless `len`
+/// is `0`. The function returns the length of the whole name, excluding
+/// the null byte, and a negative `ws_err` code if the primitive is not
+/// recognised or `buf` is null.
+///
+/// # Safety
+///
+/// `buf` must point to at least `len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ws_ecc_to_string(ecc: Ecc, buf: *mut c_char, len: usize) -> c_int {
+  utilities::to_string(ecc, buf, len)
+}
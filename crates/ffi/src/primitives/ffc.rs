@@ -1,7 +1,11 @@
 //! Specifies a set of commonly used finite field cryptography
 //! instances.
+use std::ffi::{c_char, c_int};
+
 use wardstone_core::primitive::ffc::*;
 
+use crate::utilities;
+
 /// Generic instance that represents a choice of L = 1024 and N = 160
 /// for a finite field cryptography primitive.
 #[no_mangle]
@@ -32,6 +36,199 @@ pub static WS_DSA_7680_384: Ffc = DSA_7680_384;
 #[no_mangle]
 pub static WS_DSA_15360_512: Ffc = DSA_15360_512;
 
+/// The 2048-bit finite field group ffdhe2048 defined in RFC 7919.
+#[no_mangle]
+pub static WS_FFDHE2048: Ffc = FFDHE2048;
+
+/// The 3072-bit finite field group ffdhe3072 defined in RFC 7919.
+#[no_mangle]
+pub static WS_FFDHE3072: Ffc = FFDHE3072;
+
+/// The 4096-bit finite field group ffdhe4096 defined in RFC 7919.
+#[no_mangle]
+pub static WS_FFDHE4096: Ffc = FFDHE4096;
+
+/// The 6144-bit finite field group ffdhe6144 defined in RFC 7919.
+#[no_mangle]
+pub static WS_FFDHE6144: Ffc = FFDHE6144;
+
+/// The 8192-bit finite field group ffdhe8192 defined in RFC 7919.
+#[no_mangle]
+pub static WS_FFDHE8192: Ffc = FFDHE8192;
+
+/// The 1024-bit MODP group, known as Oakley group 2, defined in RFC 2409.
+#[no_mangle]
+pub static WS_MODP_1024: Ffc = MODP_1024;
+
+/// The 1536-bit MODP group, known as group 5, defined in RFC 3526.
+#[no_mangle]
+pub static WS_MODP_1536: Ffc = MODP_1536;
+
+/// The 2048-bit MODP group, known as group 14, defined in RFC 3526.
+#[no_mangle]
+pub static WS_MODP_2048: Ffc = MODP_2048;
+
+/// The 3072-bit MODP group, known as group 15, defined in RFC 3526.
+#[no_mangle]
+pub static WS_MODP_3072: Ffc = MODP_3072;
+
+/// The 4096-bit MODP group, known as group 16, defined in RFC 3526.
+#[no_mangle]
+pub static WS_MODP_4096: Ffc = MODP_4096;
+
+/// The 6144-bit MODP group, known as group 17, defined in RFC 3526.
+#[no_mangle]
+pub static WS_MODP_6144: Ffc = MODP_6144;
+
+/// The 8192-bit MODP group, known as group 18, defined in RFC 3526.
+#[no_mangle]
+pub static WS_MODP_8192: Ffc = MODP_8192;
+
 /// Placeholder for use in where this primitive is not supported.
 #[no_mangle]
 pub static WS_FFC_NOT_SUPPORTED: Ffc = FFC_NOT_SUPPORTED;
+
+/// Returns a DSA instance with an L-bit prime modulus and an N-bit
+/// subgroup order for parameters that have no instance of their own.
+#[no_mangle]
+pub extern "C" fn ws_ffc_dsa(l: u16, n: u16) -> Ffc {
+  Ffc::new(ID_DSA, l, n)
+}
+
+/// Returns a Diffie-Hellman group with an L-bit prime modulus for groups
+/// that have no instance of their own. `n` is the length of the subgroup
+/// order or `0` for a safe-prime group.
+#[no_mangle]
+pub extern "C" fn ws_ffc_dh(l: u16, n: u16) -> Ffc {
+  Ffc::new(ID_DH, l, n)
+}
+
+/// Returns `WS_DSA_1024_160`.
+#[no_mangle]
+pub extern "C" fn ws_ffc_dsa_1024_160() -> Ffc {
+  DSA_1024_160
+}
+
+/// Returns `WS_DSA_2048_224`.
+#[no_mangle]
+pub extern "C" fn ws_ffc_dsa_2048_224() -> Ffc {
+  DSA_2048_224
+}
+
+/// Returns `WS_DSA_2048_256`.
+#[no_mangle]
+pub extern "C" fn ws_ffc_dsa_2048_256() -> Ffc {
+  DSA_2048_256
+}
+
+/// Returns `WS_DSA_3072_256`.
+#[no_mangle]
+pub extern "C" fn ws_ffc_dsa_3072_256() -> Ffc {
+  DSA_3072_256
+}
+
+/// Returns `WS_DSA_7680_384`.
+#[no_mangle]
+pub extern "C" fn ws_ffc_dsa_7680_384() -> Ffc {
+  DSA_7680_384
+}
+
+/// Returns `WS_DSA_15360_512`.
+#[no_mangle]
+pub extern "C" fn ws_ffc_dsa_15360_512() -> Ffc {
+  DSA_15360_512
+}
+
+/// Returns `WS_FFDHE2048`.
+#[no_mangle]
+pub extern "C" fn ws_ffc_ffdhe2048() -> Ffc {
+  FFDHE2048
+}
+
+/// Returns `WS_FFDHE3072`.
+#[no_mangle]
+pub extern "C" fn ws_ffc_ffdhe3072() -> Ffc {
+  FFDHE3072
+}
+
+/// Returns `WS_FFDHE4096`.
+#[no_mangle]
+pub extern "C" fn ws_ffc_ffdhe4096() -> Ffc {
+  FFDHE4096
+}
+
+/// Returns `WS_FFDHE6144`.
+#[no_mangle]
+pub extern "C" fn ws_ffc_ffdhe6144() -> Ffc {
+  FFDHE6144
+}
+
+/// Returns `WS_FFDHE8192`.
+#[no_mangle]
+pub extern "C" fn ws_ffc_ffdhe8192() -> Ffc {
+  FFDHE8192
+}
+
+/// Returns `WS_MODP_1024`.
+#[no_mangle]
+pub extern "C" fn ws_ffc_modp_1024() -> Ffc {
+  MODP_1024
+}
+
+/// Returns `WS_MODP_1536`.
+#[no_mangle]
+pub extern "C" fn ws_ffc_modp_1536() -> Ffc {
+  MODP_1536
+}
+
+/// Returns `WS_MODP_2048`.
+#[no_mangle]
+pub extern "C" fn ws_ffc_modp_2048() -> Ffc {
+  MODP_2048
+}
+
+/// Returns `WS_MODP_3072`.
+#[no_mangle]
+pub extern "C" fn ws_ffc_modp_3072() -> Ffc {
+  MODP_3072
+}
+
+/// Returns `WS_MODP_4096`.
+#[no_mangle]
+pub extern "C" fn ws_ffc_modp_4096() -> Ffc {
+  MODP_4096
+}
+
+/// Returns `WS_MODP_6144`.
+#[no_mangle]
+pub extern "C" fn ws_ffc_modp_6144() -> Ffc {
+  MODP_6144
+}
+
+/// Returns `WS_MODP_8192`.
+#[no_mangle]
+pub extern "C" fn ws_ffc_modp_8192() -> Ffc {
+  MODP_8192
+}
+
+/// Returns `WS_FFC_NOT_SUPPORTED`.
+#[no_mangle]
+pub extern "C" fn ws_ffc_not_supported() -> Ffc {
+  FFC_NOT_SUPPORTED
+}
+
+/// Writes the name of a finite field cryptography primitive
+/// to `buf`, which holds `len` bytes.
+///
+/// The name is truncated to fit and always null-terminated unless `len`
+/// is `0`. The function returns the length of the whole name, excluding
+/// the null byte, and a negative `ws_err` code if the primitive is not
+/// recognised or `buf` is null.
+///
+/// # Safety
+///
+/// `buf` must point to at least `len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ws_ffc_to_string(ffc: Ffc, buf: *mut c_char, len: usize) -> c_int {
+  utilities::to_string(ffc, buf, len)
+}
@@ -1,7 +1,11 @@
 //! Specifies a hash or hash-based cryptography primitive and a set of
 //! commonly used instances.
+use std::ffi::{c_char, c_int};
+
 use wardstone_core::primitive::hash::*;
 
+use crate::utilities;
+
 /// The BLAKE-224 hash function.
 #[no_mangle]
 pub static WS_BLAKE_224: Hash = BLAKE_224;
@@ -147,14 +151,16 @@ pub static WS_SHA512_224: Hash = SHA512_224;
 pub static WS_SHA512_256: Hash = SHA512_256;
 
 /// The SHAKE128 extendable-output function as defined in [FIPS 202].
-/// This assumes an output length of 128-bits.
+/// The length is that of its security strength of 128 bits, which it
+/// offers against collisions with an output of at least 256 bits.
 ///
 /// [FIPS 202]: https://doi.org/10.6028/NIST.FIPS.202
 #[no_mangle]
 pub static WS_SHAKE128: Hash = SHAKE128;
 
 /// The SHAKE256 extendable-output function as defined in [FIPS 202].
-/// This assumes an output length of 256-bits.
+/// The length is that of its security strength of 256 bits, which it
+/// offers against collisions with an output of at least 512 bits.
 ///
 /// [FIPS 202]: https://doi.org/10.6028/NIST.FIPS.202
 #[no_mangle]
@@ -163,3 +169,175 @@ pub static WS_SHAKE256: Hash = SHAKE256;
 /// The WHIRLPOOL hash function as defined in ISO/IEC 10118-3.
 #[no_mangle]
 pub static WS_WHIRLPOOL: Hash = WHIRLPOOL;
+
+/// Returns `WS_BLAKE_224`.
+#[no_mangle]
+pub extern "C" fn ws_hash_blake_224() -> Hash {
+  BLAKE_224
+}
+
+/// Returns `WS_BLAKE_256`.
+#[no_mangle]
+pub extern "C" fn ws_hash_blake_256() -> Hash {
+  BLAKE_256
+}
+
+/// Returns `WS_BLAKE_384`.
+#[no_mangle]
+pub extern "C" fn ws_hash_blake_384() -> Hash {
+  BLAKE_384
+}
+
+/// Returns `WS_BLAKE_512`.
+#[no_mangle]
+pub extern "C" fn ws_hash_blake_512() -> Hash {
+  BLAKE_512
+}
+
+/// Returns `WS_BLAKE2B_256`.
+#[no_mangle]
+pub extern "C" fn ws_hash_blake2b_256() -> Hash {
+  BLAKE2B_256
+}
+
+/// Returns `WS_BLAKE2B_384`.
+#[no_mangle]
+pub extern "C" fn ws_hash_blake2b_384() -> Hash {
+  BLAKE2B_384
+}
+
+/// Returns `WS_BLAKE2B_512`.
+#[no_mangle]
+pub extern "C" fn ws_hash_blake2b_512() -> Hash {
+  BLAKE2B_512
+}
+
+/// Returns `WS_BLAKE2S_256`.
+#[no_mangle]
+pub extern "C" fn ws_hash_blake2s_256() -> Hash {
+  BLAKE2S_256
+}
+
+/// Returns `WS_BLAKE3`.
+#[no_mangle]
+pub extern "C" fn ws_hash_blake3() -> Hash {
+  BLAKE3
+}
+
+/// Returns `WS_MD4`.
+#[no_mangle]
+pub extern "C" fn ws_hash_md4() -> Hash {
+  MD4
+}
+
+/// Returns `WS_MD5`.
+#[no_mangle]
+pub extern "C" fn ws_hash_md5() -> Hash {
+  MD5
+}
+
+/// Returns `WS_RIPEMD160`.
+#[no_mangle]
+pub extern "C" fn ws_hash_ripemd160() -> Hash {
+  RIPEMD160
+}
+
+/// Returns `WS_SHA1`.
+#[no_mangle]
+pub extern "C" fn ws_hash_sha1() -> Hash {
+  SHA1
+}
+
+/// Returns `WS_SHA224`.
+#[no_mangle]
+pub extern "C" fn ws_hash_sha224() -> Hash {
+  SHA224
+}
+
+/// Returns `WS_SHA256`.
+#[no_mangle]
+pub extern "C" fn ws_hash_sha256() -> Hash {
+  SHA256
+}
+
+/// Returns `WS_SHA384`.
+#[no_mangle]
+pub extern "C" fn ws_hash_sha384() -> Hash {
+  SHA384
+}
+
+/// Returns `WS_SHA3_224`.
+#[no_mangle]
+pub extern "C" fn ws_hash_sha3_224() -> Hash {
+  SHA3_224
+}
+
+/// Returns `WS_SHA3_256`.
+#[no_mangle]
+pub extern "C" fn ws_hash_sha3_256() -> Hash {
+  SHA3_256
+}
+
+/// Returns `WS_SHA3_384`.
+#[no_mangle]
+pub extern "C" fn ws_hash_sha3_384() -> Hash {
+  SHA3_384
+}
+
+/// Returns `WS_SHA3_512`.
+#[no_mangle]
+pub extern "C" fn ws_hash_sha3_512() -> Hash {
+  SHA3_512
+}
+
+/// Returns `WS_SHA512`.
+#[no_mangle]
+pub extern "C" fn ws_hash_sha512() -> Hash {
+  SHA512
+}
+
+/// Returns `WS_SHA512_224`.
+#[no_mangle]
+pub extern "C" fn ws_hash_sha512_224() -> Hash {
+  SHA512_224
+}
+
+/// Returns `WS_SHA512_256`.
+#[no_mangle]
+pub extern "C" fn ws_hash_sha512_256() -> Hash {
+  SHA512_256
+}
+
+/// Returns `WS_SHAKE128`.
+#[no_mangle]
+pub extern "C" fn ws_hash_shake128() -> Hash {
+  SHAKE128
+}
+
+/// Returns `WS_SHAKE256`.
+#[no_mangle]
+pub extern "C" fn ws_hash_shake256() -> Hash {
+  SHAKE256
+}
+
+/// Returns `WS_WHIRLPOOL`.
+#[no_mangle]
+pub extern "C" fn ws_hash_whirlpool() -> Hash {
+  WHIRLPOOL
+}
+
+/// Writes the name of a hash function
+/// to `buf`, which holds `len` bytes.
+///
+/// The name is truncated to fit and always null-terminated unless `len`
+/// is `0`. The function returns the length of the whole name, excluding
+/// the null byte, and a negative `ws_err` code if the primitive is not
+/// recognised or `buf` is null.
+///
+/// # Safety
+///
+/// `buf` must point to at least `len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ws_hash_to_string(hash: Hash, buf: *mut c_char, len: usize) -> c_int {
+  utilities::to_string(hash, buf, len)
+}
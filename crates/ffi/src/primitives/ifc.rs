@@ -1,7 +1,11 @@
 //! Specifies a integer factorisation cryptography primitive and a set
 //! of commonly used instances.
+use std::ffi::{c_char, c_int};
+
 use wardstone_core::primitive::ifc::*;
 
+use crate::utilities;
+
 /// An identifier for custom RSA with PKCS #1 v1.5 padding keys.
 ///
 /// This for use in creating custom keys in that can be used in
@@ -87,3 +91,141 @@ pub static WS_RSA_PSS_15360: Ifc = RSA_PSS_15360;
 /// Placeholder for use in where this primitive is not allowed.
 #[no_mangle]
 pub static WS_IFC_NOT_ALLOWED: Ifc = IFC_NOT_ALLOWED;
+
+/// Returns a k-bit RSA with PKCS #1 v1.5 padding instance for key
+/// sizes that have no instance of their own.
+#[no_mangle]
+pub extern "C" fn ws_ifc_rsa_pkcs1(k: u16) -> Ifc {
+  Ifc::new(ID_RSA_PKCS1, k)
+}
+
+/// Returns a k-bit RSA with PSS encoding instance for key sizes that
+/// have no instance of their own.
+#[no_mangle]
+pub extern "C" fn ws_ifc_rsa_pss(k: u16) -> Ifc {
+  Ifc::new(ID_RSA_PSS, k)
+}
+
+/// Returns `WS_RSA_PKCS1_1024`.
+#[no_mangle]
+pub extern "C" fn ws_ifc_rsa_pkcs1_1024() -> Ifc {
+  RSA_PKCS1_1024
+}
+
+/// Returns `WS_RSA_PKCS1_1536`.
+#[no_mangle]
+pub extern "C" fn ws_ifc_rsa_pkcs1_1536() -> Ifc {
+  RSA_PKCS1_1536
+}
+
+/// Returns `WS_RSA_PKCS1_2048`.
+#[no_mangle]
+pub extern "C" fn ws_ifc_rsa_pkcs1_2048() -> Ifc {
+  RSA_PKCS1_2048
+}
+
+/// Returns `WS_RSA_PKCS1_3072`.
+#[no_mangle]
+pub extern "C" fn ws_ifc_rsa_pkcs1_3072() -> Ifc {
+  RSA_PKCS1_3072
+}
+
+/// Returns `WS_RSA_PKCS1_4096`.
+#[no_mangle]
+pub extern "C" fn ws_ifc_rsa_pkcs1_4096() -> Ifc {
+  RSA_PKCS1_4096
+}
+
+/// Returns `WS_RSA_PKCS1_7680`.
+#[no_mangle]
+pub extern "C" fn ws_ifc_rsa_pkcs1_7680() -> Ifc {
+  RSA_PKCS1_7680
+}
+
+/// Returns `WS_RSA_PKCS1_8192`.
+#[no_mangle]
+pub extern "C" fn ws_ifc_rsa_pkcs1_8192() -> Ifc {
+  RSA_PKCS1_8192
+}
+
+/// Returns `WS_RSA_PKCS1_15360`.
+#[no_mangle]
+pub extern "C" fn ws_ifc_rsa_pkcs1_15360() -> Ifc {
+  RSA_PKCS1_15360
+}
+
+/// Returns `WS_RSA_PSS_1024`.
+#[no_mangle]
+pub extern "C" fn ws_ifc_rsa_pss_1024() -> Ifc {
+  RSA_PSS_1024
+}
+
+/// Returns `WS_RSA_PSS_1280`.
+#[no_mangle]
+pub extern "C" fn ws_ifc_rsa_pss_1280() -> Ifc {
+  RSA_PSS_1280
+}
+
+/// Returns `WS_RSA_PSS_1536`.
+#[no_mangle]
+pub extern "C" fn ws_ifc_rsa_pss_1536() -> Ifc {
+  RSA_PSS_1536
+}
+
+/// Returns `WS_RSA_PSS_2048`.
+#[no_mangle]
+pub extern "C" fn ws_ifc_rsa_pss_2048() -> Ifc {
+  RSA_PSS_2048
+}
+
+/// Returns `WS_RSA_PSS_3072`.
+#[no_mangle]
+pub extern "C" fn ws_ifc_rsa_pss_3072() -> Ifc {
+  RSA_PSS_3072
+}
+
+/// Returns `WS_RSA_PSS_4096`.
+#[no_mangle]
+pub extern "C" fn ws_ifc_rsa_pss_4096() -> Ifc {
+  RSA_PSS_4096
+}
+
+/// Returns `WS_RSA_PSS_7680`.
+#[no_mangle]
+pub extern "C" fn ws_ifc_rsa_pss_7680() -> Ifc {
+  RSA_PSS_7680
+}
+
+/// Returns `WS_RSA_PSS_8192`.
+#[no_mangle]
+pub extern "C" fn ws_ifc_rsa_pss_8192() -> Ifc {
+  RSA_PSS_8192
+}
+
+/// Returns `WS_RSA_PSS_15360`.
+#[no_mangle]
+pub extern "C" fn ws_ifc_rsa_pss_15360() -> Ifc {
+  RSA_PSS_15360
+}
+
+/// Returns `WS_IFC_NOT_ALLOWED`.
+#[no_mangle]
+pub extern "C" fn ws_ifc_not_allowed() -> Ifc {
+  IFC_NOT_ALLOWED
+}
+
+/// Writes the name of an integer factorisation cryptography primitive
+/// to `buf`, which holds `len` bytes.
+///
+/// The name is truncated to fit and always null-terminated unless `len`
+/// is `0`. The function returns the length of the whole name, excluding
+/// the null byte, and a negative `ws_err` code if the primitive is not
+/// recognised or `buf` is null.
+///
+/// # Safety
+///
+/// `buf` must point to at least `len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ws_ifc_to_string(ifc: Ifc, buf: *mut c_char, len: usize) -> c_int {
+  utilities::to_string(ifc, buf, len)
+}
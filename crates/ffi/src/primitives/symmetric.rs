@@ -1,7 +1,11 @@
 //! Specifies a symmetric key cryptography primitive and a set of
 //! commonly used instances.
+use std::ffi::{c_char, c_int};
+
 use wardstone_core::primitive::symmetric::*;
 
+use crate::utilities;
+
 /// The Advanced Encryption Standard algorithm as defined in [FIPS 197].
 ///
 /// [FIPS 197]: https://doi.org/10.6028/NIST.FIPS.197
@@ -50,6 +54,11 @@ pub static WS_DESX: Symmetric = DESX;
 #[no_mangle]
 pub static WS_IDEA: Symmetric = IDEA;
 
+/// The Blowfish encryption algorithm with a 128-bit key, which is how
+/// it is mostly used although it takes keys of up to 448 bits.
+#[no_mangle]
+pub static WS_BLOWFISH: Symmetric = BLOWFISH;
+
 /// The Serpent encryption algorithm.
 #[no_mangle]
 pub static WS_SERPENT128: Symmetric = SERPENT128;
@@ -75,3 +84,113 @@ pub static WS_TDEA2: Symmetric = TDEA2;
 /// [SP800-67]: https://doi.org/10.6028/NIST.SP.800-67r2
 #[no_mangle]
 pub static WS_TDEA3: Symmetric = TDEA3;
+
+/// Returns `WS_AES128`.
+#[no_mangle]
+pub extern "C" fn ws_symmetric_aes128() -> Symmetric {
+  AES128
+}
+
+/// Returns `WS_AES192`.
+#[no_mangle]
+pub extern "C" fn ws_symmetric_aes192() -> Symmetric {
+  AES192
+}
+
+/// Returns `WS_AES256`.
+#[no_mangle]
+pub extern "C" fn ws_symmetric_aes256() -> Symmetric {
+  AES256
+}
+
+/// Returns `WS_CAMELLIA128`.
+#[no_mangle]
+pub extern "C" fn ws_symmetric_camellia128() -> Symmetric {
+  CAMELLIA128
+}
+
+/// Returns `WS_CAMELLIA192`.
+#[no_mangle]
+pub extern "C" fn ws_symmetric_camellia192() -> Symmetric {
+  CAMELLIA192
+}
+
+/// Returns `WS_CAMELLIA256`.
+#[no_mangle]
+pub extern "C" fn ws_symmetric_camellia256() -> Symmetric {
+  CAMELLIA256
+}
+
+/// Returns `WS_DES`.
+#[no_mangle]
+pub extern "C" fn ws_symmetric_des() -> Symmetric {
+  DES
+}
+
+/// Returns `WS_DESX`.
+#[no_mangle]
+pub extern "C" fn ws_symmetric_desx() -> Symmetric {
+  DESX
+}
+
+/// Returns `WS_IDEA`.
+#[no_mangle]
+pub extern "C" fn ws_symmetric_idea() -> Symmetric {
+  IDEA
+}
+
+/// Returns `WS_BLOWFISH`.
+#[no_mangle]
+pub extern "C" fn ws_symmetric_blowfish() -> Symmetric {
+  BLOWFISH
+}
+
+/// Returns `WS_SERPENT128`.
+#[no_mangle]
+pub extern "C" fn ws_symmetric_serpent128() -> Symmetric {
+  SERPENT128
+}
+
+/// Returns `WS_SERPENT192`.
+#[no_mangle]
+pub extern "C" fn ws_symmetric_serpent192() -> Symmetric {
+  SERPENT192
+}
+
+/// Returns `WS_SERPENT256`.
+#[no_mangle]
+pub extern "C" fn ws_symmetric_serpent256() -> Symmetric {
+  SERPENT256
+}
+
+/// Returns `WS_TDEA2`.
+#[no_mangle]
+pub extern "C" fn ws_symmetric_tdea2() -> Symmetric {
+  TDEA2
+}
+
+/// Returns `WS_TDEA3`.
+#[no_mangle]
+pub extern "C" fn ws_symmetric_tdea3() -> Symmetric {
+  TDEA3
+}
+
+/// Writes the name of a symmetric key primitive
+/// to `buf`, which holds `len` bytes.
+///
+/// The name is truncated to fit and always null-terminated unless `len`
+/// is `0`. The function returns the length of the whole name, excluding
+/// the null byte, and a negative `ws_err` code if the primitive is not
+/// recognised or `buf` is null.
+///
+/// # Safety
+///
+/// `buf` must point to at least `len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ws_symmetric_to_string(
+  symmetric: Symmetric,
+  buf: *mut c_char,
+  len: usize,
+) -> c_int {
+  utilities::to_string(symmetric, buf, len)
+}
@@ -0,0 +1,128 @@
+//! Look up the primitives built into the library by the names and
+//! object identifiers they are commonly known by.
+//!
+//! This spares C callers that already have a textual identifier, such
+//! as one read from a configuration file or a certificate, from mapping
+//! it to one of the exported instances themselves.
+use std::ffi::{c_char, c_int, CStr};
+
+use wardstone_core::primitive::ecc::Ecc;
+use wardstone_core::primitive::ffc::Ffc;
+use wardstone_core::primitive::hash::Hash;
+use wardstone_core::primitive::ifc::Ifc;
+use wardstone_core::primitive::symmetric::Symmetric;
+use wardstone_core::registry::{self, Builtin};
+
+use crate::error::{self, Error};
+use crate::utilities;
+
+/// A primitive of any kind.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Primitive {
+  Ecc(Ecc),
+  Ffc(Ffc),
+  Hash(Hash),
+  Ifc(Ifc),
+  Symmetric(Symmetric),
+}
+
+impl From<Builtin> for Primitive {
+  fn from(builtin: Builtin) -> Self {
+    match builtin {
+      Builtin::Ecc(entry) => Self::Ecc(entry.primitive),
+      Builtin::Ffc(entry) => Self::Ffc(entry.primitive),
+      Builtin::Hash(entry) => Self::Hash(entry.primitive),
+      Builtin::Ifc(entry) => Self::Ifc(entry.primitive),
+      Builtin::Symmetric(entry) => Self::Symmetric(entry.primitive),
+    }
+  }
+}
+
+unsafe fn lookup(
+  text: *const c_char,
+  primitive: *mut Primitive,
+  find: fn(&str) -> Option<Builtin>,
+) -> c_int {
+  if text.is_null() {
+    return error::fail(Error::NullPrimitive, "identifier is null");
+  }
+  let text = match CStr::from_ptr(text).to_str() {
+    Ok(text) => text,
+    Err(_) => return error::fail(Error::Parse, "identifier is not valid UTF-8"),
+  };
+  let builtin = match find(text) {
+    Some(builtin) => builtin,
+    None => return error::fail(Error::UnknownPrimitive, text),
+  };
+  if !primitive.is_null() {
+    *primitive = builtin.into();
+  }
+  0
+}
+
+/// Looks up a primitive by its name or one of its aliases such as
+/// "P-256", "secp256r1" or "SHA-256".
+///
+/// The comparison ignores case as well as hyphens, underscores and
+/// slashes. If `struct ws_primitive*` is not null, it will hold the
+/// primitive that was found.
+///
+/// The function returns `0` if the primitive is found and a negative
+/// `ws_err` code if the name is not recognised or is missing.
+///
+/// # Safety
+///
+/// `name` must be a null-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn ws_primitive_from_name(
+  name: *const c_char,
+  primitive: *mut Primitive,
+) -> c_int {
+  lookup(name, primitive, registry::find)
+}
+
+/// Looks up a primitive by its object identifier in dotted decimal
+/// notation such as "1.2.840.10045.3.1.7".
+///
+/// Object identifiers that are shared by keys of every size, such as
+/// rsaEncryption, do not refer to a single primitive and are not
+/// recognised. If `struct ws_primitive*` is not null, it will hold the
+/// primitive that was found.
+///
+/// The function returns `0` if the primitive is found and a negative
+/// `ws_err` code if the object identifier is not recognised or is
+/// missing.
+///
+/// # Safety
+///
+/// `oid` must be a null-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn ws_primitive_from_oid(
+  oid: *const c_char,
+  primitive: *mut Primitive,
+) -> c_int {
+  lookup(oid, primitive, registry::find_oid)
+}
+
+/// Writes the name of a primitive of any kind to `buf`, which holds
+/// `len` bytes, in the same way as `ws_hash_to_string` and the other
+/// `ws_*_to_string` functions.
+///
+/// # Safety
+///
+/// `buf` must point to at least `len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ws_primitive_to_string(
+  primitive: Primitive,
+  buf: *mut c_char,
+  len: usize,
+) -> c_int {
+  match primitive {
+    Primitive::Ecc(key) => utilities::to_string(key, buf, len),
+    Primitive::Ffc(key) => utilities::to_string(key, buf, len),
+    Primitive::Hash(hash) => utilities::to_string(hash, buf, len),
+    Primitive::Ifc(key) => utilities::to_string(key, buf, len),
+    Primitive::Symmetric(key) => utilities::to_string(key, buf, len),
+  }
+}
@@ -1,6 +1,12 @@
 //! Submodules that validate cryptographic primitives according to
 //! selected standards and research publications.
 //!
+//! Every standard exposes the same functions prefixed with its name,
+//! for example `ws_bsi_validate_ecc` and `ws_lenstra_validate_ecc`. The
+//! BSI and NIST guidance additionally covers hash-based functions.
+//! Functions such as `ws_nist_assess` accept a primitive of any kind
+//! and describe the outcome in a `ws_verdict`.
+//!
 //! # Safety
 //!
 //! This module contains functions that use raw pointers as arguments
@@ -10,10 +16,11 @@
 //! consistency with existing conventions.
 //!
 //! Checks against null dereferences are made in which the function will
-//! return `-1` if the argument is required.pub mod bsi;
+//! return a negative `ws_err` code if the argument is required.
 pub mod bsi;
 pub mod cnsa;
 pub mod ecrypt;
+pub mod iot;
 pub mod lenstra;
 pub mod nist;
 pub mod strong;
@@ -5,7 +5,6 @@
 //! [BSI TR-02102-1 Cryptographic Mechanisms: Recommendations and Key Lengths]: https://www.bsi.bund.de/SharedDocs/Downloads/EN/BSI/Publications/TechGuidelines/TG02102/BSI-TR-02102-1.html
 use std::ffi::c_int;
 
-use wardstone_core::context::Context;
 use wardstone_core::primitive::ecc::Ecc;
 use wardstone_core::primitive::ffc::Ffc;
 use wardstone_core::primitive::hash::Hash;
@@ -14,7 +13,10 @@ use wardstone_core::primitive::symmetric::Symmetric;
 use wardstone_core::standard::bsi::Bsi;
 use wardstone_core::standard::Standard;
 
+use crate::context::Context;
+use crate::registry::Primitive;
 use crate::utilities;
+use crate::verdict::{self, Verdict};
 
 /// Validate an elliptic curve cryptography primitive used for digital
 /// signatures and key establishment where f is the key size.
@@ -27,8 +29,8 @@ use crate::utilities;
 /// desired security level.
 ///
 /// The function returns `1` if the hash function is compliant, `0` if
-/// it is not, and `-1` if an error occurs as a result of a missing or
-/// invalid argument.
+/// it is not, and a negative `ws_err` code if an error occurs as a
+/// result of a missing or invalid argument.
 ///
 /// **Note:** While the guide allows for elliptic curve system
 /// parameters "that are provided by a trustworthy authority"
@@ -61,8 +63,8 @@ pub unsafe extern "C" fn ws_bsi_validate_ecc(
 /// with the desired security level.
 ///
 /// The function returns `1` if the hash function is compliant, `0` if
-/// it is not, and `-1` if an error occurs as a result of a missing or
-/// invalid argument.
+/// it is not, and a negative `ws_err` code if an error occurs as a
+/// result of a missing or invalid argument.
 ///
 /// # Safety
 ///
@@ -93,8 +95,8 @@ pub unsafe extern "C" fn ws_bsi_validate_ffc(
 /// primitive with the desired security level.
 ///
 /// The function returns `1` if the hash function is compliant, `0` if
-/// it is not, and `-1` if an error occurs as a result of a missing or
-/// invalid argument.
+/// it is not, and a negative `ws_err` code if an error occurs as a
+/// result of a missing or invalid argument.
 ///
 /// **Caution:** Unlike the NIST standard, the guide does not make a
 /// distinction between security requirements based on usage. For
@@ -104,12 +106,9 @@ pub unsafe extern "C" fn ws_bsi_validate_ffc(
 /// `validate_hash_based` function and the recommendation returned may
 /// be overly conservative.
 ///
-/// **Note:** An alternative might be suggested for a compliant hash
-/// function with a similar security level in which a switch to the
-/// recommended primitive would likely be unwarranted. For example, when
-/// evaluating compliance for the `SHA3-256`, a recommendation to use
-/// `SHA256` will be made but switching to this as a result is likely
-/// unnecessary.
+/// See `Bsi::validate_hash` in `wardstone_core` for the
+/// recommendation made when the hash function belongs to the SHA-3
+/// family.
 ///
 /// # Safety
 ///
@@ -140,20 +139,17 @@ pub unsafe extern "C" fn ws_bsi_validate_hash(
 /// primitive with the desired security level.
 ///
 /// The function returns `1` if the hash function is compliant, `0` if
-/// it is not, and `-1` if an error occurs as a result of a missing or
-/// invalid argument.
+/// it is not, and a negative `ws_err` code if an error occurs as a
+/// result of a missing or invalid argument.
 ///
 /// **Note:** For an HMAC the minimum security required is ≥ 128 (see
 /// p. 45) but the minimum digest length for a hash function that can be
 /// used with this primitive is 256 (see p. 41). This means any
 /// recommendation from this function will be likely too conservative.
 ///
-/// An alternative might also be suggested for a compliant hash
-/// functions with a similar security level in which a switch to the
-/// recommended primitive would likely be unwarranted. For example, when
-/// evaluating compliance for the `SHA3-256`, a recommendation to use
-/// `SHA256` will be made but switching to this as a result is likely
-/// unnecessary.
+/// See `Bsi::validate_hash_based` in `wardstone_core` for the
+/// recommendation made when the hash function belongs to the SHA-3
+/// family.
 ///
 /// # Safety
 ///
@@ -178,8 +174,8 @@ pub unsafe extern "C" fn ws_bsi_validate_hash_based(
 /// the desired security level.
 ///
 /// The function returns `1` if the hash function is compliant, `0` if
-/// it is not, and `-1` if an error occurs as a result of a missing or
-/// invalid argument.
+/// it is not, and a negative `ws_err` code if an error occurs as a
+/// result of a missing or invalid argument.
 //
 /// **Note:** Unlike other functions in this module, this will return a
 /// generic structure that specifies minimum private and public key
@@ -208,8 +204,8 @@ pub unsafe extern "C" fn ws_bsi_validate_ifc(
 /// primitive with the desired security level.
 ///
 /// The function returns `1` if the key is compliant, `0` if it is not,
-/// and `-1` if an error occurs as a result of a missing or invalid
-/// argument.
+/// and a negative `ws_err` code if an error occurs as a result of a
+/// missing or invalid argument.
 ///
 /// # Safety
 ///
@@ -222,3 +218,26 @@ pub unsafe extern "C" fn ws_bsi_validate_symmetric(
 ) -> c_int {
   utilities::c_call(Bsi::validate_symmetric, ctx, key, alternative)
 }
+
+/// Assesses a primitive of any kind against the standard.
+///
+/// If `struct ws_verdict*` is not null, it will hold whether the
+/// primitive is compliant and until when, the recommended alternative,
+/// and the part of the standard the assessment is based on. Its `size`
+/// must be set before the call.
+///
+/// The function returns `1` if the primitive is compliant, `0` if it is
+/// not, and a negative `ws_err` code if an error occurs as a result of
+/// a missing or invalid argument.
+///
+/// # Safety
+///
+/// See crate documentation for comment on safety.
+#[no_mangle]
+pub unsafe extern "C" fn ws_bsi_assess(
+  ctx: Context,
+  primitive: Primitive,
+  verdict: *mut Verdict,
+) -> c_int {
+  verdict::assess::<Bsi>(ctx.into(), primitive, verdict)
+}
@@ -4,7 +4,6 @@
 //! [ECRYPT-CSA D5.4 Algorithms, Key Size and Protocols Report]: https://www.ecrypt.eu.org/csa/documents/D5.4-FinalAlgKeySizeProt.pdf
 use std::ffi::c_int;
 
-use wardstone_core::context::Context;
 use wardstone_core::primitive::ecc::Ecc;
 use wardstone_core::primitive::ffc::Ffc;
 use wardstone_core::primitive::hash::Hash;
@@ -13,7 +12,10 @@ use wardstone_core::primitive::symmetric::Symmetric;
 use wardstone_core::standard::ecrypt::Ecrypt;
 use wardstone_core::standard::Standard;
 
+use crate::context::Context;
+use crate::registry::Primitive;
 use crate::utilities;
+use crate::verdict::{self, Verdict};
 
 /// Validate an elliptic curve cryptography primitive used for digital
 /// signatures and key establishment where f is the key size according
@@ -27,8 +29,8 @@ use crate::utilities;
 /// desired security level.
 ///
 /// The function returns `1` if the hash function is compliant, `0` if
-/// it is not, and `-1` if an error occurs as a result of a missing or
-/// invalid argument.
+/// it is not, and a negative `ws_err` code if an error occurs as a
+/// result of a missing or invalid argument.
 ///
 /// **Note:** This will return a generic structure that specifies key
 /// sizes.
@@ -59,8 +61,8 @@ pub unsafe extern "C" fn ws_ecrypt_validate_ecc(
 /// with the desired security level.
 ///
 /// The function returns `1` if the hash function is compliant, `0` if
-/// it is not, and `-1` if an error occurs as a result of a missing or
-/// invalid argument.
+/// it is not, and a negative `ws_err` code if an error occurs as a
+/// result of a missing or invalid argument.
 ///
 /// **Note:** The choice of security specified in the `Context` is
 /// restricted to the values 160, 224, 256, 384, and 512.
@@ -93,8 +95,8 @@ pub unsafe extern "C" fn ws_ecrypt_validate_ffc(
 /// primitive with the desired security level.
 ///
 /// The function returns `1` if the hash function is compliant, `0` if
-/// it is not, and `-1` if an error occurs as a result of a missing or
-/// invalid argument.
+/// it is not, and a negative `ws_err` code if an error occurs as a
+/// result of a missing or invalid argument.
 ///
 /// # Safety
 ///
@@ -120,8 +122,8 @@ pub unsafe extern "C" fn ws_ecrypt_validate_hash(
 /// the desired security level.
 ///
 /// The function returns `1` if the hash function is compliant, `0` if
-/// it is not, and `-1` if an error occurs as a result of a missing or
-/// invalid argument.
+/// it is not, and a negative `ws_err` code if an error occurs as a
+/// result of a missing or invalid argument.
 //
 /// **Note:** Unlike other functions in this module, this will return a
 /// generic structure that specifies minimum private and public key
@@ -150,8 +152,8 @@ pub unsafe extern "C" fn ws_ecrypt_validate_ifc(
 /// primitive with the desired security level.
 ///
 /// The function returns `1` if the hash function is compliant, `0` if
-/// it is not, and `-1` if an error occurs as a result of a missing or
-/// invalid argument.
+/// it is not, and a negative `ws_err` code if an error occurs as a
+/// result of a missing or invalid argument.
 ///
 /// # Safety
 ///
@@ -164,3 +166,26 @@ pub unsafe extern "C" fn ws_ecrypt_validate_symmetric(
 ) -> c_int {
   utilities::c_call(Ecrypt::validate_symmetric, ctx, key, alternative)
 }
+
+/// Assesses a primitive of any kind against the standard.
+///
+/// If `struct ws_verdict*` is not null, it will hold whether the
+/// primitive is compliant and until when, the recommended alternative,
+/// and the part of the standard the assessment is based on. Its `size`
+/// must be set before the call.
+///
+/// The function returns `1` if the primitive is compliant, `0` if it is
+/// not, and a negative `ws_err` code if an error occurs as a result of
+/// a missing or invalid argument.
+///
+/// # Safety
+///
+/// See crate documentation for comment on safety.
+#[no_mangle]
+pub unsafe extern "C" fn ws_ecrypt_assess(
+  ctx: Context,
+  primitive: Primitive,
+  verdict: *mut Verdict,
+) -> c_int {
+  verdict::assess::<Ecrypt>(ctx.into(), primitive, verdict)
+}
@@ -0,0 +1,185 @@
+//! Validate cryptographic primitives used by constrained devices
+//! following [BSI TR-03116-3] and [NIST IR 8259A].
+//!
+//! [BSI TR-03116-3]: https://www.bsi.bund.de/SharedDocs/Downloads/DE/BSI/Publikationen/TechnischeRichtlinien/TR03116/BSI-TR-03116-3.html
+//! [NIST IR 8259A]: https://csrc.nist.gov/pubs/ir/8259/a/final
+use std::ffi::c_int;
+
+use wardstone_core::primitive::ecc::Ecc;
+use wardstone_core::primitive::ffc::Ffc;
+use wardstone_core::primitive::hash::Hash;
+use wardstone_core::primitive::ifc::Ifc;
+use wardstone_core::primitive::symmetric::Symmetric;
+use wardstone_core::standard::iot::Iot;
+use wardstone_core::standard::Standard;
+
+use crate::context::Context;
+use crate::registry::Primitive;
+use crate::utilities;
+use crate::verdict::{self, Verdict};
+
+/// Validate an elliptic curve cryptography primitive used for digital
+/// signatures and key establishment.
+///
+/// If the key is not compliant then `ws_ecc*` will contain the
+/// recommended primitive that one should use instead.
+///
+/// If the key is compliant but the context specifies a higher security
+/// level, `ws_ecc*` will also hold the recommended primitive with the
+/// desired security level.
+///
+/// The function returns `1` if the hash function is compliant, `0` if
+/// it is not, and a negative `ws_err` code if an error occurs as a
+/// result of a missing or invalid argument.
+///
+/// # Safety
+///
+/// See crate documentation for comment on safety.
+#[no_mangle]
+pub unsafe extern "C" fn ws_iot_validate_ecc(
+  ctx: Context,
+  key: Ecc,
+  alternative: *mut Ecc,
+) -> c_int {
+  utilities::c_call(Iot::validate_ecc, ctx, key, alternative)
+}
+
+/// Validates a finite field cryptography primitive function.
+///
+/// Examples include the DSA and key establishment algorithms such as
+/// Diffie-Hellman and MQV which can also be implemented as such.
+///
+/// This primitive is not accepted by the profile, which uses elliptic
+/// curves instead.
+///
+/// If the key is not compliant then `struct ws_ffc*` will point to the
+/// recommended primitive that one should use instead.
+///
+/// If the key is compliant but the context specifies a higher security
+/// level, `struct ws_ffc` will also point to the recommended primitive
+/// with the desired security level.
+///
+/// The function returns `1` if the hash function is compliant, `0` if
+/// it is not, and a negative `ws_err` code if an error occurs as a
+/// result of a missing or invalid argument.
+///
+/// # Safety
+///
+/// See crate documentation for comment on safety.
+#[no_mangle]
+pub unsafe extern "C" fn ws_iot_validate_ffc(
+  ctx: Context,
+  key: Ffc,
+  alternative: *mut Ffc,
+) -> c_int {
+  utilities::c_call(Iot::validate_ffc, ctx, key, alternative)
+}
+
+/// Validates a hash function.
+///
+/// Unlike other functions in this module, there is no distinction in
+/// security based on the application. As such this module does not have
+/// a corresponding `validate_hash_based` function. All hash function
+/// and hash based application are assessed by this single function.
+///
+/// If the hash function is not compliant then
+/// `struct ws_hash* alternative` will point to the recommended
+/// primitive that one should use instead.
+///
+/// If the hash function is compliant but the context specifies a higher
+/// security level, `struct ws_hash*` will also point to the recommended
+/// primitive with the desired security level.
+///
+/// The function returns `1` if the hash function is compliant, `0` if
+/// it is not, and a negative `ws_err` code if an error occurs as a
+/// result of a missing or invalid argument.
+///
+/// # Safety
+///
+/// See crate documentation for comment on safety.
+#[no_mangle]
+pub unsafe extern "C" fn ws_iot_validate_hash(
+  ctx: Context,
+  hash: Hash,
+  alternative: *mut Hash,
+) -> c_int {
+  utilities::c_call(Iot::validate_hash, ctx, hash, alternative)
+}
+
+/// Validates  an integer factorisation cryptography primitive the most
+/// common of which is the RSA signature algorithm.
+///
+/// If the key is not compliant then `ws_ifc*` will point to the
+/// recommended key size that one should use instead.
+///
+/// If the key is compliant but the context specifies a higher security
+/// level, `ws_ifc*` will also point to the recommended key size with
+/// the desired security level.
+///
+/// The function returns `1` if the hash function is compliant, `0` if
+/// it is not, and a negative `ws_err` code if an error occurs as a
+/// result of a missing or invalid argument.
+//
+/// **Note:** Unlike other functions in this module, this will return a
+/// generic structure that specifies minimum private and public key
+/// sizes.
+///
+/// # Safety
+///
+/// See crate documentation for comment on safety.
+#[no_mangle]
+pub unsafe extern "C" fn ws_iot_validate_ifc(
+  ctx: Context,
+  key: Ifc,
+  alternative: *mut Ifc,
+) -> c_int {
+  utilities::c_call(Iot::validate_ifc, ctx, key, alternative)
+}
+
+/// Validates a symmetric key primitive.
+///
+/// If the key is not compliant then `struct ws_symmetric* alternative`
+/// will point to the recommended primitive that one should use instead.
+///
+/// If the key is compliant but the context specifies a higher security
+/// level, `struct ws_symmetric*` will also point to the recommended
+/// primitive with the desired security level.
+///
+/// The function returns `1` if the hash function is compliant, `0` if
+/// it is not, and a negative `ws_err` code if an error occurs as a
+/// result of a missing or invalid argument.
+///
+/// # Safety
+///
+/// See crate documentation for comment on safety.
+#[no_mangle]
+pub unsafe extern "C" fn ws_iot_validate_symmetric(
+  ctx: Context,
+  key: Symmetric,
+  alternative: *mut Symmetric,
+) -> c_int {
+  utilities::c_call(Iot::validate_symmetric, ctx, key, alternative)
+}
+
+/// Assesses a primitive of any kind against the standard.
+///
+/// If `struct ws_verdict*` is not null, it will hold whether the
+/// primitive is compliant and until when, the recommended alternative,
+/// and the part of the standard the assessment is based on. Its `size`
+/// must be set before the call.
+///
+/// The function returns `1` if the primitive is compliant, `0` if it is
+/// not, and a negative `ws_err` code if an error occurs as a result of
+/// a missing or invalid argument.
+///
+/// # Safety
+///
+/// See crate documentation for comment on safety.
+#[no_mangle]
+pub unsafe extern "C" fn ws_iot_assess(
+  ctx: Context,
+  primitive: Primitive,
+  verdict: *mut Verdict,
+) -> c_int {
+  verdict::assess::<Iot>(ctx.into(), primitive, verdict)
+}
@@ -3,7 +3,6 @@
 //! of Information Security, 06/2004.
 use std::ffi::c_int;
 
-use wardstone_core::context::Context;
 use wardstone_core::primitive::ecc::Ecc;
 use wardstone_core::primitive::ffc::Ffc;
 use wardstone_core::primitive::hash::Hash;
@@ -12,7 +11,10 @@ use wardstone_core::primitive::symmetric::Symmetric;
 use wardstone_core::standard::lenstra::Lenstra;
 use wardstone_core::standard::Standard;
 
+use crate::context::Context;
+use crate::registry::Primitive;
 use crate::utilities;
+use crate::verdict::{self, Verdict};
 
 /// Validate an elliptic curve cryptography primitive used for digital
 /// signatures and key establishment where f is the key size.
@@ -25,8 +27,8 @@ use crate::utilities;
 /// desired security level.
 ///
 /// The function returns `1` if the hash function is compliant, `0` if
-/// it is not, and `-1` if an error occurs as a result of a missing or
-/// invalid argument.
+/// it is not, and a negative `ws_err` code if an error occurs as a
+/// result of a missing or invalid argument.
 ///
 /// # Safety
 ///
@@ -52,8 +54,8 @@ pub unsafe extern "C" fn ws_lenstra_validate_ecc(
 /// with the desired security level.
 ///
 /// The function returns `1` if the hash function is compliant, `0` if
-/// it is not, and `-1` if an error occurs as a result of a missing or
-/// invalid argument.
+/// it is not, and a negative `ws_err` code if an error occurs as a
+/// result of a missing or invalid argument.
 ///
 /// **Note:** Unlike other functions in this module, this will return a
 /// generic structure that specifies minimum private and public key
@@ -82,8 +84,8 @@ pub unsafe extern "C" fn ws_lenstra_validate_ffc(
 /// primitive with the desired security level.
 ///
 /// The function returns `1` if the hash function is compliant, `0` if
-/// it is not, and `-1` if an error occurs as a result of a missing or
-/// invalid argument.
+/// it is not, and a negative `ws_err` code if an error occurs as a
+/// result of a missing or invalid argument.
 ///
 /// **Note:** that this means an alternative might be suggested for a
 /// compliant hash functions with a similar security level in which a
@@ -115,8 +117,8 @@ pub unsafe extern "C" fn ws_lenstra_validate_hash(
 /// the desired security level.
 ///
 /// The function returns `1` if the hash function is compliant, `0` if
-/// it is not, and `-1` if an error occurs as a result of a missing or
-/// invalid argument.
+/// it is not, and a negative `ws_err` code if an error occurs as a
+/// result of a missing or invalid argument.
 //
 /// **Note:** Unlike other functions in this module, this will return a
 /// generic structure that specifies minimum private and public key
@@ -145,8 +147,8 @@ pub unsafe extern "C" fn ws_lenstra_validate_ifc(
 /// primitive with the desired security level.
 ///
 /// The function returns `1` if the hash function is compliant, `0` if
-/// it is not, and `-1` if an error occurs as a result of a missing or
-/// invalid argument.
+/// it is not, and a negative `ws_err` code if an error occurs as a
+/// result of a missing or invalid argument.
 ///
 /// # Safety
 ///
@@ -159,3 +161,26 @@ pub unsafe extern "C" fn ws_lenstra_validate_symmetric(
 ) -> c_int {
   utilities::c_call(Lenstra::validate_symmetric, ctx, key, alternative)
 }
+
+/// Assesses a primitive of any kind against the standard.
+///
+/// If `struct ws_verdict*` is not null, it will hold whether the
+/// primitive is compliant and until when, the recommended alternative,
+/// and the part of the standard the assessment is based on. Its `size`
+/// must be set before the call.
+///
+/// The function returns `1` if the primitive is compliant, `0` if it is
+/// not, and a negative `ws_err` code if an error occurs as a result of
+/// a missing or invalid argument.
+///
+/// # Safety
+///
+/// See crate documentation for comment on safety.
+#[no_mangle]
+pub unsafe extern "C" fn ws_lenstra_assess(
+  ctx: Context,
+  primitive: Primitive,
+  verdict: *mut Verdict,
+) -> c_int {
+  verdict::assess::<Lenstra>(ctx.into(), primitive, verdict)
+}
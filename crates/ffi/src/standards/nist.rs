@@ -4,7 +4,6 @@
 //! [NIST Special Publication 800-57 Part 1 Revision 5 standard]: https://doi.org/10.6028/NIST.SP.800-57pt1r5
 use std::ffi::c_int;
 
-use wardstone_core::context::Context;
 use wardstone_core::primitive::ecc::Ecc;
 use wardstone_core::primitive::ffc::Ffc;
 use wardstone_core::primitive::hash::Hash;
@@ -13,7 +12,10 @@ use wardstone_core::primitive::symmetric::Symmetric;
 use wardstone_core::standard::nist::Nist;
 use wardstone_core::standard::Standard;
 
+use crate::context::Context;
+use crate::registry::Primitive;
 use crate::utilities;
+use crate::verdict::{self, Verdict};
 
 /// Validate an elliptic curve cryptography primitive used for digital
 /// signatures and key establishment where f is the key size according
@@ -26,9 +28,9 @@ use crate::utilities;
 /// level, `ws_ecc*` will also hold the recommended primitive with the
 /// desired security level.
 ///
-/// The function returns `1` if the hash function is compliant, `0` if
-/// it is not, and `-1` if an error occurs as a result of a missing or
-/// invalid argument.
+/// The function returns `1` if the key is compliant, `0` if it is not,
+/// and a negative `ws_err` code if an error occurs as a result of a
+/// missing or invalid argument.
 ///
 /// # Safety
 ///
@@ -54,8 +56,8 @@ pub unsafe extern "C" fn ws_nist_validate_ecc(
 /// with the desired security level.
 ///
 /// The function returns `1` if the hash function is compliant, `0` if
-/// it is not, and `-1` if an error occurs as a result of a missing or
-/// invalid argument.
+/// it is not, and a negative `ws_err` code if an error occurs as a
+/// result of a missing or invalid argument.
 ///
 /// **Note:** Unlike other functions in this module, this will return a
 /// generic structure that specifies minimum private and public key
@@ -73,7 +75,7 @@ pub unsafe extern "C" fn ws_nist_validate_ffc(
   utilities::c_call(Nist::validate_ffc, ctx, key, alternative)
 }
 
-/// Validates  an integer factorisation cryptography primitive the most
+/// Validates an integer factorisation cryptography primitive the most
 /// common of which is the RSA signature algorithm where k indicates the
 /// key size according to page 54-55 of the standard.
 ///
@@ -84,10 +86,10 @@ pub unsafe extern "C" fn ws_nist_validate_ffc(
 /// level, `ws_ifc*` will also point to the recommended key size with
 /// the desired security level.
 ///
-/// The function returns `1` if the hash function is compliant, `0` if
-/// it is not, and `-1` if an error occurs as a result of a missing or
-/// invalid argument.
-//
+/// The function returns `1` if the key is compliant, `0` if it is not,
+/// and a negative `ws_err` code if an error occurs as a result of a
+/// missing or invalid argument.
+///
 /// **Note:** Unlike other functions in this module, this will return a
 /// generic structure that specifies minimum private and public key
 /// sizes.
@@ -121,15 +123,12 @@ pub unsafe extern "C" fn ws_nist_validate_ifc(
 /// primitive with the desired security level.
 ///
 /// The function returns `1` if the hash function is compliant, `0` if
-/// it is not, and `-1` if an error occurs as a result of a missing or
-/// invalid argument.
+/// it is not, and a negative `ws_err` code if an error occurs as a
+/// result of a missing or invalid argument.
 ///
-/// **Note:** that this means an alternative might be suggested for a
-/// compliant hash functions with a similar security level in which a
-/// switch to the recommended primitive would likely be unwarranted. For
-/// example, when evaluating compliance for the `SHA3-256`, a
-/// recommendation to use `SHA256` will be made but this likely
-/// unnecessary.
+/// See `Nist::validate_hash` in `wardstone_core` for the
+/// recommendation made when the hash function belongs to the SHA-3
+/// family.
 ///
 /// # Safety
 ///
@@ -160,15 +159,12 @@ pub unsafe extern "C" fn ws_nist_validate_hash(
 /// primitive with the desired security level.
 ///
 /// The function returns `1` if the hash function is compliant, `0` if
-/// it is not, and `-1` if an error occurs as a result of a missing or
-/// invalid argument.
+/// it is not, and a negative `ws_err` code if an error occurs as a
+/// result of a missing or invalid argument.
 ///
-/// **Note:** that this means an alternative might be suggested for a
-/// compliant hash functions with a similar security level in which a
-/// switch to the recommended primitive would likely be unwarranted. For
-/// example, when evaluating compliance for the `SHA3-256`, a
-/// recommendation to use `SHA256` will be made but this likely
-/// unnecessary.
+/// See `Nist::validate_hash_based` in `wardstone_core` for the
+/// recommendation made when the hash function belongs to the SHA-3
+/// family.
 ///
 /// # Safety
 ///
@@ -193,8 +189,8 @@ pub unsafe extern "C" fn ws_nist_validate_hash_based(
 /// primitive with the desired security level.
 ///
 /// The function returns `1` if the hash function is compliant, `0` if
-/// it is not, and `-1` if an error occurs as a result of a missing or
-/// invalid argument.
+/// it is not, and a negative `ws_err` code if an error occurs as a
+/// result of a missing or invalid argument.
 ///
 /// # Safety
 ///
@@ -207,3 +203,26 @@ pub unsafe extern "C" fn ws_nist_validate_symmetric(
 ) -> c_int {
   utilities::c_call(Nist::validate_symmetric, ctx, key, alternative)
 }
+
+/// Assesses a primitive of any kind against the standard.
+///
+/// If `struct ws_verdict*` is not null, it will hold whether the
+/// primitive is compliant and until when, the recommended alternative,
+/// and the part of the standard the assessment is based on. Its `size`
+/// must be set before the call.
+///
+/// The function returns `1` if the primitive is compliant, `0` if it is
+/// not, and a negative `ws_err` code if an error occurs as a result of
+/// a missing or invalid argument.
+///
+/// # Safety
+///
+/// See crate documentation for comment on safety.
+#[no_mangle]
+pub unsafe extern "C" fn ws_nist_assess(
+  ctx: Context,
+  primitive: Primitive,
+  verdict: *mut Verdict,
+) -> c_int {
+  verdict::assess::<Nist>(ctx.into(), primitive, verdict)
+}
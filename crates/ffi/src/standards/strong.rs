@@ -8,7 +8,6 @@
 //! schemes such as those that use elliptic curves.
 use std::ffi::c_int;
 
-use wardstone_core::context::Context;
 use wardstone_core::primitive::ecc::Ecc;
 use wardstone_core::primitive::ffc::Ffc;
 use wardstone_core::primitive::hash::Hash;
@@ -17,6 +16,7 @@ use wardstone_core::primitive::symmetric::Symmetric;
 use wardstone_core::standard::testing::strong::Strong;
 use wardstone_core::standard::Standard;
 
+use crate::context::Context;
 use crate::utilities;
 
 /// Validate an elliptic curve cryptography primitive.
@@ -29,8 +29,8 @@ use crate::utilities;
 /// desired security level.
 ///
 /// The function returns `1` if the hash function is compliant, `0` if
-/// it is not, and `-1` if an error occurs as a result of a missing or
-/// invalid argument.
+/// it is not, and a negative `ws_err` code if an error occurs as a
+/// result of a missing or invalid argument.
 ///
 /// # Safety
 ///
@@ -54,8 +54,8 @@ pub unsafe extern "C" fn ws_strong_validate_ecc(
 /// with the desired security level.
 ///
 /// The function returns `1` if the hash function is compliant, `0` if
-/// it is not, and `-1` if an error occurs as a result of a missing or
-/// invalid argument.
+/// it is not, and a negative `ws_err` code if an error occurs as a
+/// result of a missing or invalid argument.
 ///
 /// **Note:** Unlike other functions in this module, this will return a
 /// generic structure that specifies minimum private and public key
@@ -84,8 +84,8 @@ pub unsafe extern "C" fn ws_strong_validate_ffc(
 /// the desired security level.
 ///
 /// The function returns `1` if the hash function is compliant, `0` if
-/// it is not, and `-1` if an error occurs as a result of a missing or
-/// invalid argument.
+/// it is not, and a negative `ws_err` code if an error occurs as a
+/// result of a missing or invalid argument.
 //
 /// **Note:** Unlike other functions in this module, this will return a
 /// generic structure that specifies minimum private and public key
@@ -114,8 +114,8 @@ pub unsafe extern "C" fn ws_strong_validate_ifc(
 /// primitive with the desired security level.
 ///
 /// The function returns `1` if the hash function is compliant, `0` if
-/// it is not, and `-1` if an error occurs as a result of a missing or
-/// invalid argument.
+/// it is not, and a negative `ws_err` code if an error occurs as a
+/// result of a missing or invalid argument.
 ///
 /// **Note:** that this means an alternative might be suggested for a
 /// compliant hash functions with a similar security level in which a
@@ -146,8 +146,8 @@ pub unsafe extern "C" fn ws_strong_validate_hash(
 /// primitive with the desired security level.
 ///
 /// The function returns `1` if the hash function is compliant, `0` if
-/// it is not, and `-1` if an error occurs as a result of a missing or
-/// invalid argument.
+/// it is not, and a negative `ws_err` code if an error occurs as a
+/// result of a missing or invalid argument.
 ///
 /// # Safety
 ///
@@ -1,16 +1,51 @@
-use std::ffi::c_int;
+use std::ffi::{c_char, c_int};
+use std::fmt::Display;
+use std::ptr;
 
-use wardstone_core::context::Context;
+use wardstone_core::context::Context as CoreContext;
+use wardstone_core::primitive::ecc::Ecc;
+use wardstone_core::primitive::ffc::Ffc;
+use wardstone_core::primitive::hash::Hash;
+use wardstone_core::primitive::ifc::Ifc;
+use wardstone_core::primitive::symmetric::Symmetric;
+
+use crate::context::Context;
+use crate::error::{self, Error};
+
+/// A primitive that can be passed across the C API.
+pub(crate) trait Identified {
+  fn id(&self) -> u16;
+}
+
+macro_rules! impl_identified {
+  ($($t:ty),*) => {
+    $(
+      impl Identified for $t {
+        fn id(&self) -> u16 {
+          self.id
+        }
+      }
+    )*
+  };
+}
+
+impl_identified!(Ecc, Ffc, Hash, Ifc, Symmetric);
 
 /// A utility function that abstracts a call to a Rust function `f` and
 /// returns a result following C error handling conventions.
-pub(crate) unsafe fn c_call<T>(
-  f: fn(Context, T) -> Result<T, T>,
+pub(crate) unsafe fn c_call<T: Identified>(
+  f: fn(CoreContext, T) -> Result<T, T>,
   ctx: Context,
   primitive: T,
   alternative: *mut T,
 ) -> c_int {
-  let (recommendation, is_compliant) = match f(ctx, primitive) {
+  // No primitive is assigned the identifier zero so this is most likely
+  // a structure that was zeroed but never set.
+  if primitive.id() == 0 {
+    return error::fail(Error::UnknownPrimitive, "identifier 0 is not assigned");
+  }
+
+  let (recommendation, is_compliant) = match f(ctx.into(), primitive) {
     Ok(recommendation) => (recommendation, true),
     Err(recommendation) => (recommendation, false),
   };
@@ -21,3 +56,30 @@ pub(crate) unsafe fn c_call<T>(
 
   is_compliant as c_int
 }
+
+/// A utility function that writes the name of a primitive to a buffer
+/// provided by the caller in the same way as `snprintf`.
+///
+/// At most `len - 1` bytes of the name are written followed by a null
+/// byte. The length of the whole name is returned so that the caller can
+/// tell whether it was truncated, and a buffer of the right size can be
+/// allocated by passing null and a length of zero first.
+pub(crate) unsafe fn to_string<T: Identified + Display>(
+  primitive: T,
+  buf: *mut c_char,
+  len: usize,
+) -> c_int {
+  if primitive.id() == 0 {
+    return error::fail(Error::UnknownPrimitive, "identifier 0 is not assigned");
+  }
+  if buf.is_null() && len > 0 {
+    return error::fail(Error::NullPrimitive, "buffer is null");
+  }
+  let name = primitive.to_string();
+  if len > 0 {
+    let n = name.len().min(len - 1);
+    ptr::copy_nonoverlapping(name.as_ptr().cast(), buf, n);
+    *buf.add(n) = 0;
+  }
+  name.len() as c_int
+}
@@ -0,0 +1,191 @@
+//! Describes the outcome of assessing a primitive in more detail than
+//! whether it is compliant or not.
+use std::ffi::{c_char, c_int, CString};
+use std::sync::OnceLock;
+use std::{mem, ptr};
+
+use wardstone_core::context::Context;
+use wardstone_core::standard::bsi::Bsi;
+use wardstone_core::standard::cnsa::Cnsa;
+use wardstone_core::standard::ecrypt::Ecrypt;
+use wardstone_core::standard::iot::Iot;
+use wardstone_core::standard::lenstra::Lenstra;
+use wardstone_core::standard::nist::Nist;
+use wardstone_core::standard::rule::{Citation, Scope};
+use wardstone_core::standard::Standard;
+
+use crate::error::{self, Error};
+use crate::registry::Primitive;
+use crate::utilities::Identified;
+use crate::version::{self, Versioned};
+
+/// Whether a primitive is compliant and for how long.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Compliance {
+  Compliant,
+  /// The primitive is compliant but will not be after the cutoff year.
+  Expiring,
+  NonCompliant,
+}
+
+/// The outcome of assessing a primitive against a standard.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct Verdict {
+  /// The size of the structure which must be set by the caller to
+  /// `sizeof(struct ws_verdict)`.
+  pub size: u32,
+  pub compliance: Compliance,
+  /// The cutoff year of the rule that applies to the primitive or `0`
+  /// if the standard does not specify one.
+  pub cutoff: u16,
+  /// The recommended primitive that one should use instead, or the
+  /// primitive with the desired security level if it is already
+  /// compliant.
+  pub alternative: Primitive,
+  /// The part of the standard the rule is based on or null if the rule
+  /// does not cite one. The string must not be freed.
+  pub citation: *const c_char,
+}
+
+impl Versioned for Verdict {
+  const MIN_SIZE: usize = mem::size_of::<Self>();
+}
+
+// Citations are formatted once and kept for the lifetime of the program
+// so that the pointers handed out remain valid. The citations of every
+// standard are formatted together the first time one is needed, so that
+// afterwards assessing a primitive neither takes a lock nor allocates,
+// which matters to callers that assess the parameters of every
+// handshake. There are only a few dozen of them.
+static CITATIONS: OnceLock<Vec<(Citation, CString)>> = OnceLock::new();
+
+fn citations() -> Vec<(Citation, CString)> {
+  let rules = [
+    Bsi::rules(),
+    Cnsa::rules(),
+    Ecrypt::rules(),
+    Iot::rules(),
+    Lenstra::rules(),
+    Nist::rules(),
+  ];
+  let mut citations: Vec<(Citation, CString)> = Vec::new();
+  for rule in rules.into_iter().flatten() {
+    if citations.iter().any(|(c, _)| *c == rule.citation) {
+      continue;
+    }
+    if let Ok(s) = CString::new(rule.citation.to_string()) {
+      citations.push((rule.citation, s));
+    }
+  }
+  citations
+}
+
+/// Returns a C string for the citation which remains valid for the
+/// lifetime of the program.
+pub(crate) fn citation(citation: Citation) -> *const c_char {
+  CITATIONS
+    .get_or_init(citations)
+    .iter()
+    .find(|(c, _)| *c == citation)
+    .map_or(ptr::null(), |(_, s)| s.as_ptr())
+}
+
+fn check<T: Identified + Copy>(
+  f: fn(Context, T) -> Result<T, T>,
+  ctx: Context,
+  primitive: T,
+  cutoff: Option<u16>,
+) -> (Compliance, T) {
+  match f(ctx, primitive) {
+    Ok(alternative) => {
+      // Assessing the primitive as if it were the year after the cutoff
+      // tells whether it is only compliant until then.
+      let expires = cutoff
+        .filter(|&cutoff| ctx.year() <= cutoff)
+        .is_some_and(|cutoff| f(ctx.with_year(cutoff + 1), primitive).is_err());
+      if expires {
+        (Compliance::Expiring, alternative)
+      } else {
+        (Compliance::Compliant, alternative)
+      }
+    },
+    Err(alternative) => (Compliance::NonCompliant, alternative),
+  }
+}
+
+/// A utility function that assesses a primitive against the standard
+/// `T` and returns a result following C error handling conventions.
+pub(crate) unsafe fn assess<T: Standard>(
+  ctx: Context,
+  primitive: Primitive,
+  verdict: *mut Verdict,
+) -> c_int {
+  let value = match evaluate::<T>(ctx, primitive) {
+    Ok(value) => value,
+    Err(err) => return err,
+  };
+  if !verdict.is_null() {
+    let err = version::write(verdict, value);
+    if err < 0 {
+      return err;
+    }
+  }
+  (value.compliance != Compliance::NonCompliant) as c_int
+}
+
+/// Assesses a primitive against the standard `T` or returns the code of
+/// the error that prevented it.
+pub(crate) fn evaluate<T: Standard>(ctx: Context, primitive: Primitive) -> Result<Verdict, c_int> {
+  let (id, scope) = match primitive {
+    Primitive::Ecc(key) => (key.id(), Scope::Ecc),
+    Primitive::Ffc(key) => (key.id(), Scope::Ffc),
+    Primitive::Hash(hash) => (hash.id(), Scope::Hash),
+    Primitive::Ifc(key) => (key.id(), Scope::Ifc),
+    Primitive::Symmetric(key) => (key.id(), Scope::Symmetric),
+  };
+  // See `utilities::c_call`.
+  if id == 0 {
+    return Err(error::fail(
+      Error::UnknownPrimitive,
+      "identifier 0 is not assigned",
+    ));
+  }
+
+  let rule = match primitive {
+    Primitive::Ffc(key) => T::ffc_rule(key),
+    _ => T::rule(scope),
+  };
+  let cutoff = rule.and_then(|rule| rule.cutoff_in(ctx));
+  let (compliance, alternative) = match primitive {
+    Primitive::Ecc(key) => {
+      let (compliance, alternative) = check(T::validate_ecc, ctx, key, cutoff);
+      (compliance, Primitive::Ecc(alternative))
+    },
+    Primitive::Ffc(key) => {
+      let (compliance, alternative) = check(T::validate_ffc, ctx, key, cutoff);
+      (compliance, Primitive::Ffc(alternative))
+    },
+    Primitive::Hash(hash) => {
+      let (compliance, alternative) = check(T::validate_hash, ctx, hash, cutoff);
+      (compliance, Primitive::Hash(alternative))
+    },
+    Primitive::Ifc(key) => {
+      let (compliance, alternative) = check(T::validate_ifc, ctx, key, cutoff);
+      (compliance, Primitive::Ifc(alternative))
+    },
+    Primitive::Symmetric(key) => {
+      let (compliance, alternative) = check(T::validate_symmetric, ctx, key, cutoff);
+      (compliance, Primitive::Symmetric(alternative))
+    },
+  };
+
+  Ok(Verdict {
+    size: mem::size_of::<Verdict>() as u32,
+    compliance,
+    cutoff: cutoff.unwrap_or_default(),
+    alternative,
+    citation: rule.map_or(ptr::null(), |rule| citation(rule.citation)),
+  })
+}
@@ -0,0 +1,63 @@
+//! Report the version of the library and the revision of its C API.
+//!
+//! Structures that the library fills in, such as `ws_assessment` and
+//! `ws_verdict`, start with a `size` field which the caller sets to
+//! `sizeof` the structure it was compiled against. The library writes
+//! no more than that many bytes, so a program built against an older
+//! header keeps working with a newer library whose structures have
+//! grown new fields at the end. Fields are never removed or reordered.
+use std::ffi::{c_char, c_int};
+use std::{mem, ptr};
+
+use crate::error::{self, Error};
+
+/// The revision of the C API the header was generated for.
+///
+/// It is incremented whenever a structure grows or a function is added
+/// so that it can be compared against [`ws_abi_version`] at runtime.
+pub const WS_ABI_VERSION: u32 = 1;
+
+const VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), "\0");
+
+/// Returns the version of the library such as "0.2.0".
+///
+/// The string must not be freed.
+#[no_mangle]
+pub extern "C" fn ws_version() -> *const c_char {
+  VERSION.as_ptr().cast()
+}
+
+/// Returns the revision of the C API implemented by the library, which
+/// is at least `WS_ABI_VERSION` if the program was built against the
+/// same or an older header.
+#[no_mangle]
+pub extern "C" fn ws_abi_version() -> u32 {
+  WS_ABI_VERSION
+}
+
+/// A structure that starts with a `size` field set by the caller.
+pub(crate) trait Versioned: Sized {
+  /// The size of the first revision of the structure, which is the
+  /// smallest size the library accepts.
+  const MIN_SIZE: usize;
+}
+
+/// Copies `value` to `dst` without writing more bytes than the caller
+/// says the structure at `dst` holds, leaving its `size` field as it is.
+///
+/// The function returns `0` on success and `WS_ERR_UNSUPPORTED_SIZE` if
+/// the structure is smaller than its first revision.
+pub(crate) unsafe fn write<T: Versioned>(dst: *mut T, value: T) -> c_int {
+  // The size is the first field of every versioned structure.
+  let size = ptr::read_unaligned(dst.cast::<u32>()) as usize;
+  if size < T::MIN_SIZE {
+    return error::fail(
+      Error::UnsupportedSize,
+      &format!("size is {} but must be at least {}", size, T::MIN_SIZE),
+    );
+  }
+  let len = size.min(mem::size_of::<T>()) - mem::size_of::<u32>();
+  let src = ptr::addr_of!(value).cast::<u8>().add(mem::size_of::<u32>());
+  ptr::copy_nonoverlapping(src, dst.cast::<u8>().add(mem::size_of::<u32>()), len);
+  0
+}
@@ -0,0 +1,171 @@
+//! # Wardstone Node.js
+//!
+//! The `wardstone_node` library wraps the validation functions of
+//! `wardstone_core` with [`napi-rs`] so that primitives and
+//! certificates can be assessed from Node.js, for example by tools that
+//! manage the life cycle of certificates.
+//!
+//! It is built with the [`@napi-rs/cli`] package:
+//!
+//! ```sh
+//! npx @napi-rs/cli build --release
+//! ```
+//!
+//! Guides are selected by the names used on the command line such as
+//! `"nist"` and primitives by the names in the registry such as
+//! `"P-256"`.
+//!
+//! [`napi-rs`]: https://napi.rs
+//! [`@napi-rs/cli`]: https://www.npmjs.com/package/@napi-rs/cli
+use std::fmt::Display;
+
+use napi::bindgen_prelude::Buffer;
+use napi::{Error, Result};
+use napi_derive::napi;
+use wardstone_core::certificate;
+use wardstone_core::context::Context;
+use wardstone_core::primitive::asymmetric::Asymmetric;
+use wardstone_core::primitive::hash::Hash;
+use wardstone_core::registry::{self, Builtin};
+use wardstone_core::standard::bsi::Bsi;
+use wardstone_core::standard::cnsa::Cnsa;
+use wardstone_core::standard::ecrypt::Ecrypt;
+use wardstone_core::standard::iot::Iot;
+use wardstone_core::standard::lenstra::Lenstra;
+use wardstone_core::standard::nist::Nist;
+use wardstone_core::standard::Standard;
+
+const GUIDES: [&str; 6] = ["bsi", "cnsa", "ecrypt", "iot", "lenstra", "nist"];
+
+// Calls the function with the standard named by the guide.
+macro_rules! with_guide {
+  ($guide:expr, $f:ident($($arg:expr),*)) => {
+    match $guide.to_ascii_lowercase().as_str() {
+      "bsi" => $f::<Bsi>($($arg),*),
+      "cnsa" => $f::<Cnsa>($($arg),*),
+      "ecrypt" => $f::<Ecrypt>($($arg),*),
+      "iot" => $f::<Iot>($($arg),*),
+      "lenstra" => $f::<Lenstra>($($arg),*),
+      "nist" => $f::<Nist>($($arg),*),
+      _ => return Err(Error::from_reason(format!("unknown guide: {}", $guide))),
+    }
+  };
+}
+
+/// The outcome of validating a single primitive.
+#[napi(object)]
+pub struct Verdict {
+  pub compliant: bool,
+  /// The recommended primitive that one should use instead, or the
+  /// primitive with the desired security level if it is already
+  /// compliant.
+  pub alternative: String,
+}
+
+/// The outcome of assessing the primitives used by a certificate.
+///
+/// The hash function fields are absent if the signature algorithm does
+/// not use a separate hash function, as is the case for EdDSA.
+#[napi(object)]
+pub struct Assessment {
+  pub passed: bool,
+  pub hash_function: Option<String>,
+  pub hash_function_alternative: Option<String>,
+  pub hash_function_compliant: bool,
+  pub key: String,
+  pub key_alternative: String,
+  pub key_compliant: bool,
+}
+
+fn context(year: Option<u16>, security: Option<u16>) -> Context {
+  let default = Context::default();
+  Context::new(
+    security.unwrap_or(default.security()),
+    year.unwrap_or(default.year()),
+  )
+}
+
+fn outcome<T: Display>(result: std::result::Result<T, T>) -> (bool, String) {
+  match result {
+    Ok(alternative) => (true, alternative.to_string()),
+    Err(alternative) => (false, alternative.to_string()),
+  }
+}
+
+fn validate_builtin<T: Standard>(ctx: Context, builtin: Builtin) -> (bool, String) {
+  match builtin {
+    Builtin::Ecc(entry) => outcome(T::validate_ecc(ctx, entry.primitive)),
+    Builtin::Ffc(entry) => outcome(T::validate_ffc(ctx, entry.primitive)),
+    Builtin::Hash(entry) => outcome(T::validate_hash(ctx, entry.primitive)),
+    Builtin::Ifc(entry) => outcome(T::validate_ifc(ctx, entry.primitive)),
+    Builtin::Symmetric(entry) => outcome(T::validate_symmetric(ctx, entry.primitive)),
+  }
+}
+
+fn assess<T: Standard>(ctx: Context, hash_function: Option<Hash>, key: Asymmetric) -> Assessment {
+  let (hash_function_compliant, hash_function_alternative) = match hash_function {
+    Some(hash) => {
+      let (compliant, alternative) = outcome(T::validate_hash(ctx, hash));
+      (compliant, Some(alternative))
+    },
+    None => (true, None),
+  };
+  let (key_compliant, key_alternative) = outcome(T::validate_asymmetric(ctx, key));
+  Assessment {
+    passed: hash_function_compliant && key_compliant,
+    hash_function: hash_function.map(|hash| hash.to_string()),
+    hash_function_alternative,
+    hash_function_compliant,
+    key: key.to_string(),
+    key_alternative,
+    key_compliant,
+  }
+}
+
+/// Returns the names of the guides that primitives can be assessed
+/// against.
+#[napi]
+pub fn guides() -> Vec<String> {
+  GUIDES.iter().map(|guide| guide.to_string()).collect()
+}
+
+/// Validates the primitive with the given name or object identifier
+/// against a guide.
+///
+/// `year` is the year one expects the primitive to remain secure and
+/// `security` the minimum security required, both of which default to
+/// those of the default context if they are omitted.
+#[napi]
+pub fn validate(
+  guide: String,
+  primitive: String,
+  year: Option<u16>,
+  security: Option<u16>,
+) -> Result<Verdict> {
+  let builtin = registry::find(&primitive)
+    .or_else(|| registry::find_oid(&primitive))
+    .ok_or_else(|| Error::from_reason(format!("unknown primitive: {}", primitive)))?;
+  let ctx = context(year, security);
+  let (compliant, alternative) = with_guide!(guide, validate_builtin(ctx, builtin));
+  Ok(Verdict {
+    compliant,
+    alternative,
+  })
+}
+
+/// Assesses the hash function and subject public key of a DER or PEM
+/// encoded X.509 certificate against a guide.
+///
+/// See [`validate`] for the meaning of `year` and `security`.
+#[napi]
+pub fn assess_certificate(
+  guide: String,
+  data: Buffer,
+  year: Option<u16>,
+  security: Option<u16>,
+) -> Result<Assessment> {
+  let (hash_function, key) =
+    certificate::primitives(&data).map_err(|err| Error::from_reason(err.to_string()))?;
+  let ctx = context(year, security);
+  Ok(with_guide!(guide, assess(ctx, hash_function, key)))
+}
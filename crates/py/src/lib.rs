@@ -0,0 +1,394 @@
+//! # Wardstone Python
+//!
+//! The `wardstone_py` library exposes the primitives, standards and
+//! key assessment of the `wardstone` command-line application as a
+//! Python module so that audits can be scripted without having to parse
+//! the output of the command.
+//!
+//! It uses [`PyO3`] and wheels can be built with [`maturin`]:
+//!
+//! ```python
+//! import wardstone
+//!
+//! ctx = wardstone.Context(year=2031)
+//! p256 = wardstone.Primitive.from_name("P-256")
+//! compliant, alternative = wardstone.validate("nist", p256, ctx)
+//! assert compliant
+//! ```
+//!
+//! [`PyO3`]: https://pyo3.rs
+//! [`maturin`]: https://www.maturin.rs
+use std::path::PathBuf;
+
+use pyo3::exceptions::{PyOSError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use wardstone::guide::Guide;
+use wardstone::key::certificate::Certificate;
+use wardstone::key::ssh::Ssh;
+use wardstone::key::{Error, Key};
+use wardstone_core::context::Context as CoreContext;
+use wardstone_core::primitive::asymmetric::Asymmetric;
+use wardstone_core::primitive::ecc::Ecc;
+use wardstone_core::primitive::ffc::{Ffc, ID_DH, ID_DSA};
+use wardstone_core::primitive::hash::Hash;
+use wardstone_core::primitive::hybrid::Hybrid;
+use wardstone_core::primitive::ifc::{Ifc, ID_RSA_PKCS1, ID_RSA_PSS};
+use wardstone_core::primitive::pqc::Pqc;
+use wardstone_core::primitive::stateful::Stateful;
+use wardstone_core::primitive::symmetric::Symmetric;
+use wardstone_core::primitive::Primitive as _;
+use wardstone_core::registry::{self, Builtin};
+
+const GUIDES: [Guide; 8] = [
+  Guide::Bsi,
+  Guide::Cnsa,
+  Guide::Ecrypt,
+  Guide::Iot,
+  Guide::Lenstra,
+  Guide::Nist,
+  Guide::Strong,
+  Guide::Weak,
+];
+
+fn guide(name: &str) -> PyResult<Guide> {
+  GUIDES
+    .into_iter()
+    .find(|guide| guide.name().eq_ignore_ascii_case(name))
+    .ok_or_else(|| PyValueError::new_err(format!("unknown guide: {}", name)))
+}
+
+fn key_error(err: Error) -> PyErr {
+  match err {
+    Error::Io(err) => PyOSError::new_err(err.to_string()),
+    err => PyValueError::new_err(err.to_string()),
+  }
+}
+
+/// The context in which a primitive is assessed such as the year it is
+/// expected to remain secure until and the minimum security required.
+///
+/// A security of `0` defaults to the minimum security defined by the
+/// standard.
+#[pyclass(frozen, module = "wardstone")]
+#[derive(Clone, Copy)]
+struct Context(CoreContext);
+
+#[pymethods]
+impl Context {
+  #[new]
+  #[pyo3(signature = (year=None, security=None))]
+  fn new(year: Option<u16>, security: Option<u16>) -> Self {
+    let default = CoreContext::default();
+    Self(CoreContext::new(
+      security.unwrap_or(default.security()),
+      year.unwrap_or(default.year()),
+    ))
+  }
+
+  #[getter]
+  fn year(&self) -> u16 {
+    self.0.year()
+  }
+
+  #[getter]
+  fn security(&self) -> u16 {
+    self.0.security()
+  }
+
+  fn __repr__(&self) -> String {
+    format!(
+      "Context(year={}, security={})",
+      self.0.year(),
+      self.0.security()
+    )
+  }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+  Ecc(Ecc),
+  Ffc(Ffc),
+  Hash(Hash),
+  Ifc(Ifc),
+  Symmetric(Symmetric),
+  Pqc(Pqc),
+  Hybrid(Hybrid),
+  Stateful(Stateful),
+}
+
+/// A cryptographic primitive such as an elliptic curve, a hash function
+/// or a symmetric cipher.
+#[pyclass(frozen, eq, module = "wardstone")]
+#[derive(Clone, Copy, PartialEq)]
+struct Primitive(Kind);
+
+impl From<Asymmetric> for Primitive {
+  fn from(key: Asymmetric) -> Self {
+    match key {
+      Asymmetric::Ecc(ecc) => Self(Kind::Ecc(ecc)),
+      Asymmetric::Ffc(ffc) => Self(Kind::Ffc(ffc)),
+      Asymmetric::Ifc(ifc) => Self(Kind::Ifc(ifc)),
+      Asymmetric::Pqc(pqc) => Self(Kind::Pqc(pqc)),
+      Asymmetric::Hybrid(hybrid) => Self(Kind::Hybrid(hybrid)),
+      Asymmetric::Stateful(stateful) => Self(Kind::Stateful(stateful)),
+    }
+  }
+}
+
+impl From<Builtin> for Primitive {
+  fn from(builtin: Builtin) -> Self {
+    match builtin {
+      Builtin::Ecc(entry) => Self(Kind::Ecc(entry.primitive)),
+      Builtin::Ffc(entry) => Self(Kind::Ffc(entry.primitive)),
+      Builtin::Hash(entry) => Self(Kind::Hash(entry.primitive)),
+      Builtin::Ifc(entry) => Self(Kind::Ifc(entry.primitive)),
+      Builtin::Symmetric(entry) => Self(Kind::Symmetric(entry.primitive)),
+    }
+  }
+}
+
+#[pymethods]
+impl Primitive {
+  /// Looks up a built-in primitive by its name or one of its aliases
+  /// such as "P-256", "secp256r1" or "SHA-256", or a post-quantum,
+  /// hybrid or stateful hash-based signature such as "ML-DSA-65",
+  /// "ML-DSA-65+P-256" or "LMS-SHA256-192".
+  #[staticmethod]
+  fn from_name(name: &str) -> PyResult<Self> {
+    registry::find(name)
+      .map(Into::into)
+      .or_else(|| name.parse().ok().map(|pqc| Self(Kind::Pqc(pqc))))
+      .or_else(|| name.parse().ok().map(|hybrid| Self(Kind::Hybrid(hybrid))))
+      .or_else(|| {
+        name
+          .parse()
+          .ok()
+          .map(|stateful| Self(Kind::Stateful(stateful)))
+      })
+      .ok_or_else(|| PyValueError::new_err(format!("unknown primitive: {}", name)))
+  }
+
+  /// Looks up a built-in primitive by its object identifier such as
+  /// "1.2.840.10045.3.1.7".
+  #[staticmethod]
+  fn from_oid(oid: &str) -> PyResult<Self> {
+    registry::find_oid(oid)
+      .map(Into::into)
+      .ok_or_else(|| PyValueError::new_err(format!("unknown primitive: {}", oid)))
+  }
+
+  /// Creates a DSA key with an L-bit prime modulus and an N-bit
+  /// subgroup order.
+  #[staticmethod]
+  fn dsa(l: u16, n: u16) -> Self {
+    Self(Kind::Ffc(Ffc::new(ID_DSA, l, n)))
+  }
+
+  /// Creates a Diffie-Hellman group with an L-bit prime modulus and an
+  /// N-bit subgroup order, or no subgroup order for a safe-prime group.
+  #[staticmethod]
+  #[pyo3(signature = (l, n=0))]
+  fn dh(l: u16, n: u16) -> Self {
+    Self(Kind::Ffc(Ffc::new(ID_DH, l, n)))
+  }
+
+  /// Creates a k-bit RSA key with PKCS #1 v1.5 padding.
+  #[staticmethod]
+  fn rsa_pkcs1(k: u16) -> Self {
+    Self(Kind::Ifc(Ifc::new(ID_RSA_PKCS1, k)))
+  }
+
+  /// Creates a k-bit RSA key with PSS encoding.
+  #[staticmethod]
+  fn rsa_pss(k: u16) -> Self {
+    Self(Kind::Ifc(Ifc::new(ID_RSA_PSS, k)))
+  }
+
+  /// The type of primitive which is one of "ecc", "ffc", "hash", "ifc",
+  /// "symmetric", "pqc", "hybrid" or "stateful".
+  #[getter]
+  fn kind(&self) -> &'static str {
+    match self.0 {
+      Kind::Ecc(_) => "ecc",
+      Kind::Ffc(_) => "ffc",
+      Kind::Hash(_) => "hash",
+      Kind::Ifc(_) => "ifc",
+      Kind::Symmetric(_) => "symmetric",
+      Kind::Pqc(_) => "pqc",
+      Kind::Hybrid(_) => "hybrid",
+      Kind::Stateful(_) => "stateful",
+    }
+  }
+
+  /// The security level of the primitive in bits.
+  #[getter]
+  fn security(&self) -> u16 {
+    match self.0 {
+      Kind::Ecc(ecc) => ecc.security(),
+      Kind::Ffc(ffc) => ffc.security(),
+      Kind::Hash(hash) => hash.security(),
+      Kind::Ifc(ifc) => ifc.security(),
+      Kind::Symmetric(symmetric) => symmetric.security(),
+      Kind::Pqc(pqc) => pqc.security(),
+      Kind::Hybrid(hybrid) => hybrid.security(),
+      Kind::Stateful(stateful) => stateful.security(),
+    }
+  }
+
+  fn __str__(&self) -> String {
+    match self.0 {
+      Kind::Ecc(ecc) => ecc.to_string(),
+      Kind::Ffc(ffc) => ffc.to_string(),
+      Kind::Hash(hash) => hash.to_string(),
+      Kind::Ifc(ifc) => ifc.to_string(),
+      Kind::Symmetric(symmetric) => symmetric.to_string(),
+      Kind::Pqc(pqc) => pqc.to_string(),
+      Kind::Hybrid(hybrid) => hybrid.to_string(),
+      Kind::Stateful(stateful) => stateful.to_string(),
+    }
+  }
+
+  fn __repr__(&self) -> String {
+    format!("<Primitive {} {}>", self.kind(), self.__str__())
+  }
+}
+
+/// Validates a primitive against a guide such as "nist" and returns
+/// whether it is compliant along with the recommended primitive that
+/// one should use instead, or the primitive with the desired security
+/// level if it is already compliant.
+#[pyfunction]
+#[pyo3(signature = (guide_name, primitive, context=None))]
+fn validate(
+  guide_name: &str,
+  primitive: Primitive,
+  context: Option<Context>,
+) -> PyResult<(bool, Primitive)> {
+  let guide = guide(guide_name)?;
+  let ctx = context.map_or_else(CoreContext::default, |context| context.0);
+  let result = match primitive.0 {
+    Kind::Ecc(ecc) => guide
+      .validate_signature_algorithm(ctx, ecc.into())
+      .map(Into::into)
+      .map_err(Into::into),
+    Kind::Ffc(ffc) => guide
+      .validate_signature_algorithm(ctx, ffc.into())
+      .map(Into::into)
+      .map_err(Into::into),
+    Kind::Hash(hash) => guide
+      .validate_hash_function(ctx, hash)
+      .map(|hash| Primitive(Kind::Hash(hash)))
+      .map_err(|hash| Primitive(Kind::Hash(hash))),
+    Kind::Ifc(ifc) => guide
+      .validate_signature_algorithm(ctx, ifc.into())
+      .map(Into::into)
+      .map_err(Into::into),
+    Kind::Symmetric(symmetric) => guide
+      .validate_symmetric(ctx, symmetric)
+      .map(|symmetric| Primitive(Kind::Symmetric(symmetric)))
+      .map_err(|symmetric| Primitive(Kind::Symmetric(symmetric))),
+    Kind::Pqc(pqc) => guide
+      .validate_signature_algorithm(ctx, pqc.into())
+      .map(Into::into)
+      .map_err(Into::into),
+    Kind::Hybrid(hybrid) => guide
+      .validate_signature_algorithm(ctx, hybrid.into())
+      .map(Into::into)
+      .map_err(Into::into),
+    Kind::Stateful(stateful) => guide
+      .validate_signature_algorithm(ctx, stateful.into())
+      .map(Into::into)
+      .map_err(Into::into),
+  };
+  Ok(match result {
+    Ok(alternative) => (true, alternative),
+    Err(alternative) => (false, alternative),
+  })
+}
+
+/// The outcome of assessing a key or certificate against a guide.
+///
+/// The alternatives hold the recommended primitives that one should use
+/// instead, or the primitives with the desired security level if they
+/// are already compliant. The hash function is `None` if the signature
+/// algorithm does not use a separate one or it cannot be determined.
+#[pyclass(frozen, get_all, module = "wardstone")]
+struct Assessment {
+  passed: bool,
+  hash_function: Option<Primitive>,
+  hash_function_alternative: Option<Primitive>,
+  signature_algorithm: Primitive,
+  signature_algorithm_alternative: Primitive,
+}
+
+#[pymethods]
+impl Assessment {
+  fn __repr__(&self) -> String {
+    format!(
+      "<Assessment passed={} signature_algorithm={}>",
+      if self.passed { "True" } else { "False" },
+      self.signature_algorithm.__str__()
+    )
+  }
+}
+
+fn assess(guide: Guide, ctx: CoreContext, key: &impl Key) -> Assessment {
+  let (hash_function_alternative, hash_function_passed) = match key.hash_function() {
+    Some(hash) => match guide.validate_hash_function(ctx, hash) {
+      Ok(alternative) => (Some(alternative), true),
+      Err(alternative) => (Some(alternative), false),
+    },
+    None => (None, true),
+  };
+  let signature_algorithm = key.signature_algorithm();
+  let (signature_algorithm_alternative, signature_algorithm_passed) =
+    match guide.validate_signature_algorithm(ctx, signature_algorithm) {
+      Ok(alternative) => (alternative, true),
+      Err(alternative) => (alternative, false),
+    };
+  Assessment {
+    passed: hash_function_passed && signature_algorithm_passed,
+    hash_function: key.hash_function().map(|hash| Primitive(Kind::Hash(hash))),
+    hash_function_alternative: hash_function_alternative.map(|hash| Primitive(Kind::Hash(hash))),
+    signature_algorithm: signature_algorithm.into(),
+    signature_algorithm_alternative: signature_algorithm_alternative.into(),
+  }
+}
+
+/// Assesses the hash function and subject public key of a DER or PEM
+/// encoded X.509 certificate against a guide.
+#[pyfunction]
+#[pyo3(signature = (guide_name, data, context=None))]
+fn assess_x509(
+  guide_name: &str,
+  data: &Bound<'_, PyBytes>,
+  context: Option<Context>,
+) -> PyResult<Assessment> {
+  let guide = guide(guide_name)?;
+  let ctx = context.map_or_else(CoreContext::default, |context| context.0);
+  let certificate = Certificate::from_bytes(data.as_bytes()).map_err(key_error)?;
+  Ok(assess(guide, ctx, &certificate))
+}
+
+/// Assesses an SSH public key file against a guide.
+#[pyfunction]
+#[pyo3(signature = (guide_name, path, context=None))]
+fn assess_ssh(guide_name: &str, path: PathBuf, context: Option<Context>) -> PyResult<Assessment> {
+  let guide = guide(guide_name)?;
+  let ctx = context.map_or_else(CoreContext::default, |context| context.0);
+  let key = Ssh::from_file(&path).map_err(key_error)?;
+  Ok(assess(guide, ctx, &key))
+}
+
+#[pymodule]
+#[pyo3(name = "wardstone")]
+fn init(m: &Bound<'_, PyModule>) -> PyResult<()> {
+  m.add_class::<Assessment>()?;
+  m.add_class::<Context>()?;
+  m.add_class::<Primitive>()?;
+  m.add_function(wrap_pyfunction!(assess_ssh, m)?)?;
+  m.add_function(wrap_pyfunction!(assess_x509, m)?)?;
+  m.add_function(wrap_pyfunction!(validate, m)?)?;
+  Ok(())
+}
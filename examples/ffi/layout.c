@@ -0,0 +1,91 @@
+// Checks the layout of the structures and enumerations in the generated
+// header against the values pinned by `crates/ffi/src/layout.rs`, so
+// that the header and the library agree and neither changes between
+// releases. The program does nothing at runtime; it fails to compile if
+// a layout is different.
+#include "wardstone.h"
+#include <stddef.h>
+#include <stdint.h>
+
+#define ASSERT_SIZE(t, size, align) \
+  _Static_assert(sizeof(t) == (size), "unexpected size of " #t); \
+  _Static_assert(_Alignof(t) == (align), "unexpected alignment of " #t)
+#define ASSERT_OFFSET(t, field, offset) \
+  _Static_assert(offsetof(t, field) == (offset), "unexpected offset of " #t "." #field)
+
+ASSERT_SIZE(struct ws_context, 4, 2);
+ASSERT_OFFSET(struct ws_context, security, 0);
+ASSERT_OFFSET(struct ws_context, year, 2);
+
+ASSERT_SIZE(struct ws_ecc, 4, 2);
+ASSERT_OFFSET(struct ws_ecc, id, 0);
+ASSERT_OFFSET(struct ws_ecc, f, 2);
+ASSERT_SIZE(struct ws_ffc, 6, 2);
+ASSERT_OFFSET(struct ws_ffc, id, 0);
+ASSERT_OFFSET(struct ws_ffc, l, 2);
+ASSERT_OFFSET(struct ws_ffc, n, 4);
+ASSERT_SIZE(struct ws_hash, 4, 2);
+ASSERT_OFFSET(struct ws_hash, id, 0);
+ASSERT_OFFSET(struct ws_hash, n, 2);
+ASSERT_SIZE(struct ws_ifc, 4, 2);
+ASSERT_OFFSET(struct ws_ifc, id, 0);
+ASSERT_OFFSET(struct ws_ifc, k, 2);
+ASSERT_SIZE(struct ws_symmetric, 4, 2);
+ASSERT_OFFSET(struct ws_symmetric, id, 0);
+ASSERT_OFFSET(struct ws_symmetric, security, 2);
+ASSERT_SIZE(struct ws_pqc, 4, 2);
+ASSERT_OFFSET(struct ws_pqc, id, 0);
+ASSERT_OFFSET(struct ws_pqc, security, 2);
+ASSERT_SIZE(struct ws_hybrid, 8, 2);
+ASSERT_OFFSET(struct ws_hybrid, pqc, 0);
+ASSERT_OFFSET(struct ws_hybrid, ecc, 4);
+ASSERT_SIZE(struct ws_stateful, 4, 2);
+ASSERT_OFFSET(struct ws_stateful, id, 0);
+ASSERT_OFFSET(struct ws_stateful, n, 2);
+
+ASSERT_SIZE(struct ws_primitive, 12, 4);
+ASSERT_OFFSET(struct ws_primitive, tag, 0);
+ASSERT_OFFSET(struct ws_primitive, ecc, 4);
+ASSERT_OFFSET(struct ws_primitive, ffc, 4);
+ASSERT_OFFSET(struct ws_primitive, hash, 4);
+ASSERT_OFFSET(struct ws_primitive, ifc, 4);
+ASSERT_OFFSET(struct ws_primitive, symmetric, 4);
+ASSERT_SIZE(struct ws_key, 12, 4);
+ASSERT_OFFSET(struct ws_key, tag, 0);
+ASSERT_OFFSET(struct ws_key, ecc, 4);
+ASSERT_OFFSET(struct ws_key, ffc, 4);
+ASSERT_OFFSET(struct ws_key, ifc, 4);
+ASSERT_OFFSET(struct ws_key, pqc, 4);
+ASSERT_OFFSET(struct ws_key, hybrid, 4);
+ASSERT_OFFSET(struct ws_key, stateful, 4);
+
+ASSERT_SIZE(enum ws_compliance, 4, 4);
+ASSERT_SIZE(enum ws_err, 4, 4);
+ASSERT_SIZE(enum ws_guide, 4, 4);
+ASSERT_SIZE(enum ws_scope, 4, 4);
+
+// Citations are pointers whose size depends on the platform.
+#if UINTPTR_MAX == UINT64_MAX
+ASSERT_SIZE(struct ws_assessment, 64, 8);
+ASSERT_OFFSET(struct ws_assessment, size, 0);
+ASSERT_OFFSET(struct ws_assessment, has_hash_function, 4);
+ASSERT_OFFSET(struct ws_assessment, hash_function, 6);
+ASSERT_OFFSET(struct ws_assessment, hash_function_alternative, 10);
+ASSERT_OFFSET(struct ws_assessment, hash_function_compliant, 14);
+ASSERT_OFFSET(struct ws_assessment, key, 16);
+ASSERT_OFFSET(struct ws_assessment, key_alternative, 28);
+ASSERT_OFFSET(struct ws_assessment, key_compliant, 40);
+ASSERT_OFFSET(struct ws_assessment, hash_function_citation, 48);
+ASSERT_OFFSET(struct ws_assessment, key_citation, 56);
+
+ASSERT_SIZE(struct ws_verdict, 32, 8);
+ASSERT_OFFSET(struct ws_verdict, size, 0);
+ASSERT_OFFSET(struct ws_verdict, compliance, 4);
+ASSERT_OFFSET(struct ws_verdict, cutoff, 8);
+ASSERT_OFFSET(struct ws_verdict, alternative, 12);
+ASSERT_OFFSET(struct ws_verdict, citation, 24);
+#endif
+
+int main(void) {
+  return 0;
+}
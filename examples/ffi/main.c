@@ -4,6 +4,21 @@
 #include <stdint.h>
 #include <string.h>
 
+// Counts the compliant primitives assessed in a batch.
+static void count(void *userdata, uintptr_t index, int result, const struct ws_verdict *verdict) {
+  (void)index;
+  if (result == 1 && verdict != NULL) {
+    *(int *)userdata += 1;
+  }
+}
+
+// Records the result of the last certificate assessed in a batch.
+static void record(void *userdata, uintptr_t index, int result, const struct ws_assessment *assessment) {
+  (void)index;
+  (void)assessment;
+  *(int *)userdata = result;
+}
+
 int main(void) {
   struct ws_hash got;
   memset(&got, 0, sizeof(struct ws_hash));
@@ -12,4 +27,146 @@ int main(void) {
   assert(ws_nist_validate_hash(ctx, WS_SHA1, &got) == false && "SHA1 should fail");
   assert(got.id == want.id && "unexpected hash function recommendation");
   assert(ws_nist_validate_hash(ctx, WS_SHA256, NULL) == true && "SHA256 should pass");
+
+  struct ws_ecc ecc;
+  assert(ws_nist_validate_ecc(ctx, WS_P224, &ecc) == true && "P-224 should pass");
+  assert(ws_nist_validate_ecc(ctx, WS_SECP192R1, &ecc) == false && "secp192r1 should fail");
+  struct ws_ifc ifc;
+  assert(ws_nist_validate_ifc(ctx, WS_RSA_PKCS1_2048, &ifc) == true && "RSA-2048 should pass");
+  assert(ws_nist_validate_ifc(ctx, WS_RSA_PKCS1_1024, &ifc) == false && "RSA-1024 should fail");
+  assert(ifc.k >= 2048 && "unexpected RSA key size recommendation");
+
+  // Misuse is reported with a negative error code rather than being
+  // mistaken for non-compliance.
+  struct ws_hash unset;
+  memset(&unset, 0, sizeof(struct ws_hash));
+  assert(ws_nist_validate_hash(ctx, unset, NULL) == WS_ERR_UNKNOWN_PRIMITIVE && "unset hash should be an error");
+  assert(ws_last_error() != NULL && "an error message should be recorded");
+  assert(ws_last_error_code() == WS_ERR_UNKNOWN_PRIMITIVE && "the error code should be recorded");
+  assert(strcmp(ws_strerror(WS_ERR_UNKNOWN_PRIMITIVE), "primitive is not recognised") == 0);
+
+  // Certificates can be assessed in one go.
+  const char *certificate =
+    "-----BEGIN CERTIFICATE-----\n"
+    "MIIBOjCB7aADAgECAhQfvM/UvfvJH5qQyoL/HQIEDfsd7zAFBgMrZXAwEjEQMA4G\n"
+    "A1UEAwwHZXhhbXBsZTAgFw0yNjEwMTYxOTI3MTdaGA8yMTI2MDkyMjE5MjcxN1ow\n"
+    "EjEQMA4GA1UEAwwHZXhhbXBsZTAqMAUGAytlcAMhAPh0tWZ6Op7H46skElql6we8\n"
+    "RGB/qDeYr6DFSZwTF8gZo1MwUTAdBgNVHQ4EFgQU+iXZ5UI+5kFamJr3o6HCPTTM\n"
+    "WN0wHwYDVR0jBBgwFoAU+iXZ5UI+5kFamJr3o6HCPTTMWN0wDwYDVR0TAQH/BAUw\n"
+    "AwEB/zAFBgMrZXADQQAijC7N6FmH6y6CPqey6m2bEcoz3/hzgc7zzQJ3FPYEO65o\n"
+    "8fX6tAwKyLe1blqxx1OEW/PNUUZ0Facie8F3/iIF\n"
+    "-----END CERTIFICATE-----\n";
+  struct ws_assessment assessment = {.size = sizeof(struct ws_assessment)};
+  assert(ws_assess_x509(ctx, WS_GUIDE_NIST, (const uint8_t *)certificate, strlen(certificate), &assessment) == true && "Ed25519 certificate should pass");
+  assert(assessment.has_hash_function == false && "EdDSA does not use a separate hash function");
+  assert(assessment.key.tag == WS_KEY_ECC && assessment.key.ecc.id == WS_ED25519.id && "unexpected subject key");
+  assert(assessment.key_citation != NULL && "the key verdict should cite the standard");
+  assert(ws_assess_x509(ctx, WS_GUIDE_NIST, (const uint8_t *)"junk", 4, NULL) == WS_ERR_PARSE && "junk should not parse");
+  assert(ws_assess_x509(ctx, 42, (const uint8_t *)certificate, strlen(certificate), NULL) == WS_ERR_INVALID_ARGUMENT && "an unknown guide should be an error");
+
+  // Every standard exposes the same functions under its own prefix.
+  assert(ws_bsi_validate_ecc(ctx, WS_BRAINPOOLP256R1, NULL) == true && "BSI: brainpoolP256r1 should pass");
+  assert(ws_bsi_validate_ifc(ctx, WS_RSA_PKCS1_1024, NULL) == false && "BSI: RSA-1024 should fail");
+  assert(ws_cnsa_validate_hash(ctx, WS_SHA256, NULL) == false && "CNSA: SHA256 should fail");
+  assert(ws_cnsa_validate_symmetric(ctx, WS_AES256, NULL) == true && "CNSA: AES-256 should pass");
+  assert(ws_ecrypt_validate_hash(ctx, WS_MD5, NULL) == false && "ECRYPT: MD5 should fail");
+  assert(ws_ecrypt_validate_symmetric(ctx, WS_AES128, NULL) == true && "ECRYPT: AES-128 should pass");
+  assert(ws_lenstra_validate_ecc(ctx, WS_P256, NULL) == true && "Lenstra: P-256 should pass");
+  assert(ws_lenstra_validate_symmetric(ctx, WS_DES, NULL) == false && "Lenstra: DES should fail");
+
+  // Instances can also be obtained from functions, which suits bindings
+  // that cannot read exported data, and keys of any size can be made.
+  assert(ws_nist_validate_hash(ctx, ws_hash_sha256(), NULL) == true && "SHA256 should pass");
+  assert(ws_nist_validate_ecc(ctx, ws_ecc_p256(), NULL) == true && "P-256 should pass");
+  assert(ws_nist_validate_ifc(ctx, ws_ifc_rsa_pkcs1(4000), NULL) == true && "RSA-4000 should pass");
+  assert(ws_nist_validate_ffc(ctx, ws_ffc_dsa(1024, 160), NULL) == false && "DSA-1024 should fail");
+
+  // Textual identifiers can be turned into primitives.
+  struct ws_primitive primitive;
+  assert(ws_primitive_from_name("secp256r1", &primitive) == 0 && "secp256r1 should be found");
+  assert(primitive.tag == WS_PRIMITIVE_ECC && primitive.ecc.id == WS_P256.id && "unexpected curve");
+  assert(ws_primitive_from_oid("2.16.840.1.101.3.4.2.1", &primitive) == 0 && "SHA256 should be found");
+  assert(primitive.tag == WS_PRIMITIVE_HASH && primitive.hash.id == WS_SHA256.id && "unexpected hash function");
+  assert(ws_primitive_from_name("rot13", NULL) == WS_ERR_UNKNOWN_PRIMITIVE && "rot13 should not be found");
+
+  // Contexts are built through functions rather than by their layout.
+  struct ws_context future = ws_context_new(2032, 0);
+  assert(ws_context_year(future) == 2032 && "unexpected year");
+  assert(ws_nist_validate_ifc(future, WS_RSA_PKCS1_2048, NULL) == false && "RSA-2048 should fail after 2031");
+  assert(ws_context_set_security(&future, 256) == 0 && "setting security should succeed");
+  struct ws_symmetric symmetric;
+  ws_nist_validate_symmetric(future, WS_AES128, &symmetric);
+  assert(symmetric.id == WS_AES256.id && "AES-256 should be recommended at 256 bits");
+  assert(ws_context_set_year(NULL, 2032) == WS_ERR_NULL_CONTEXT && "null context should be an error");
+
+  // A verdict describes the outcome in more detail.
+  struct ws_verdict verdict = {.size = sizeof(struct ws_verdict)};
+  struct ws_primitive rsa = {.tag = WS_PRIMITIVE_IFC, .ifc = WS_RSA_PKCS1_2048};
+  assert(ws_nist_assess(ctx, rsa, &verdict) == 1 && "RSA-2048 should pass");
+  assert(verdict.compliance == WS_COMPLIANCE_EXPIRING && "RSA-2048 should only pass until the cutoff");
+  assert(verdict.cutoff == 2031 && "unexpected cutoff year");
+  assert(verdict.citation != NULL && strstr(verdict.citation, "800-57") != NULL && "unexpected citation");
+  struct ws_primitive md5 = {.tag = WS_PRIMITIVE_HASH, .hash = WS_MD5};
+  assert(ws_bsi_assess(ctx, md5, &verdict) == 0 && "MD5 should fail");
+  assert(verdict.compliance == WS_COMPLIANCE_NON_COMPLIANT && verdict.alternative.tag == WS_PRIMITIVE_HASH);
+
+  // Programs can check that the library implements the API they were
+  // built against, and result structures smaller than their first
+  // revision are rejected.
+  assert(ws_abi_version() >= WS_ABI_VERSION && "library is older than the header");
+  assert(strlen(ws_version()) > 0 && "version should not be empty");
+  struct ws_verdict unsized = {.size = 0};
+  assert(ws_nist_assess(ctx, md5, &unsized) == WS_ERR_UNSUPPORTED_SIZE && "unset size should be an error");
+
+  // Many primitives or DER encoded certificates can be assessed in a
+  // single call which reports each outcome to a callback.
+  struct ws_primitive batch[] = {rsa, md5, {.tag = WS_PRIMITIVE_ECC, .ecc = WS_P256}};
+  int compliant = 0;
+  assert(ws_assess_batch(ctx, WS_GUIDE_NIST, batch, 3, count, &compliant) == 2 && "two primitives should pass");
+  assert(compliant == 2 && "the callback should see two compliant primitives");
+  int results[3];
+  struct ws_primitive alternatives[3];
+  assert(ws_assess_batch_results(ctx, WS_GUIDE_NIST, batch, 3, results, alternatives) == 2 && "two primitives should pass");
+  assert(results[0] == 1 && results[1] == 0 && results[2] == 1 && "unexpected results");
+  assert(alternatives[1].tag == WS_PRIMITIVE_HASH && "MD5 should have a hash function alternative");
+  int last = 0;
+  const uint8_t truncated[] = {0x30, 0x82, 0x01};
+  assert(ws_assess_x509_batch(ctx, WS_GUIDE_NIST, truncated, sizeof(truncated), record, &last) == WS_ERR_PARSE && "truncated buffer should not parse");
+  assert(last == WS_ERR_PARSE && "the callback should see the parse error");
+  assert(ws_assess_batch(ctx, -1, batch, 3, count, &compliant) == WS_ERR_INVALID_ARGUMENT && "an unknown guide should be an error");
+  assert(ws_assess_batch_results(ctx, -1, batch, 3, results, NULL) == WS_ERR_INVALID_ARGUMENT && "an unknown guide should be an error");
+  assert(ws_assess_x509_batch(ctx, -1, truncated, sizeof(truncated), record, &last) == WS_ERR_INVALID_ARGUMENT && "an unknown guide should be an error");
+
+  // Primitives can be printed by name, and a buffer of the right size
+  // can be allocated by asking for the length first.
+  char name[16];
+  assert(ws_hash_to_string(WS_SHA256, name, sizeof(name)) == 6 && strcmp(name, "sha256") == 0 && "unexpected name");
+  assert(ws_primitive_to_string(rsa, NULL, 0) == 14 && "unexpected length");
+  assert(ws_ifc_to_string(WS_RSA_PKCS1_2048, name, 4) == 14 && strcmp(name, "rsa") == 0 && "name should be truncated");
+
+  // Results can be rendered as JSON strings that are freed by the
+  // library.
+  char *json = ws_assess_to_json(ctx, WS_GUIDE_NIST, rsa);
+  assert(json != NULL && strstr(json, "\"compliance\":\"expiring\"") != NULL && "unexpected JSON");
+  assert(strstr(json, "\"rule_id\":\"NIST-IFC-001\"") != NULL && "the JSON should identify the rule");
+  ws_free(json);
+  json = ws_assess_x509_to_json(ctx, WS_GUIDE_NIST, (const uint8_t *)certificate, strlen(certificate));
+  assert(json != NULL && strstr(json, "\"got_signature\":\"ed25519\"") != NULL && "unexpected JSON");
+  assert(strstr(json, "\"references\"") != NULL && "the certificate JSON should cite the standard");
+  ws_free(json);
+  assert(ws_assess_to_json(ctx, 42, rsa) == NULL && ws_last_error_code() == WS_ERR_INVALID_ARGUMENT && "an unknown guide should be an error");
+  assert(ws_assess_x509_to_json(ctx, 42, (const uint8_t *)certificate, strlen(certificate)) == NULL && ws_last_error_code() == WS_ERR_INVALID_ARGUMENT && "an unknown guide should be an error");
+
+  // Policies loaded at runtime raise the bar set by a standard.
+  struct ws_policy *policy = ws_policy_load("policy.conf");
+  assert(policy != NULL && "policy should load");
+  assert(ws_policy_assess(policy, ctx, rsa, &verdict) == 0 && "RSA-2048 should fail after 2031");
+  struct ws_primitive p256 = {.tag = WS_PRIMITIVE_ECC, .ecc = WS_P256};
+  assert(ws_policy_assess(policy, ctx, p256, &verdict) == 1 && "P-256 should pass");
+  assert(ws_policy_set_cutoff(policy, WS_SCOPE_IFC, 2035) == 0 && "the cutoff should be replaced");
+  assert(ws_policy_cutoff(policy, WS_SCOPE_IFC) == 2035 && "unexpected cutoff year");
+  assert(ws_policy_assess(policy, ctx, rsa, &verdict) == 1 && "RSA-2048 should pass until the replaced cutoff");
+  assert(ws_policy_set_cutoff(policy, 42, 2035) == WS_ERR_INVALID_ARGUMENT && "an unknown scope should be an error");
+  ws_policy_free(policy);
+  assert(ws_policy_load("missing.conf") == NULL && "missing policy should not load");
 }
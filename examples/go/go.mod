@@ -0,0 +1,3 @@
+module github.com/openSUSE/wardstone/examples/go
+
+go 1.21
@@ -0,0 +1,66 @@
+// An example of how to call the wardstone library from Go.
+package main
+
+import (
+	"errors"
+
+	"github.com/openSUSE/wardstone/examples/go/wardstone"
+)
+
+const certificate = `-----BEGIN CERTIFICATE-----
+MIIBOjCB7aADAgECAhQfvM/UvfvJH5qQyoL/HQIEDfsd7zAFBgMrZXAwEjEQMA4G
+A1UEAwwHZXhhbXBsZTAgFw0yNjEwMTYxOTI3MTdaGA8yMTI2MDkyMjE5MjcxN1ow
+EjEQMA4GA1UEAwwHZXhhbXBsZTAqMAUGAytlcAMhAPh0tWZ6Op7H46skElql6we8
+RGB/qDeYr6DFSZwTF8gZo1MwUTAdBgNVHQ4EFgQU+iXZ5UI+5kFamJr3o6HCPTTM
+WN0wHwYDVR0jBBgwFoAU+iXZ5UI+5kFamJr3o6HCPTTMWN0wDwYDVR0TAQH/BAUw
+AwEB/zAFBgMrZXADQQAijC7N6FmH6y6CPqey6m2bEcoz3/hzgc7zzQJ3FPYEO65o
+8fX6tAwKyLe1blqxx1OEW/PNUUZ0Facie8F3/iIF
+-----END CERTIFICATE-----
+`
+
+func assert(ok bool, message string) {
+	if !ok {
+		panic(message)
+	}
+}
+
+func lookup(name string) wardstone.Primitive {
+	p, err := wardstone.Lookup(name)
+	assert(err == nil, name+" should be found")
+	return p
+}
+
+func main() {
+	assert(wardstone.Version() != "", "version should not be empty")
+	ctx := wardstone.DefaultContext()
+
+	// Primitives are looked up by name and assessed in a single call.
+	primitives := []wardstone.Primitive{lookup("RSA-2048"), lookup("MD5"), lookup("secp256r1")}
+	results := wardstone.Assess(ctx, wardstone.Nist, primitives)
+	assert(results[0].Compliant, "RSA-2048 should pass")
+	assert(!results[1].Compliant, "MD5 should fail")
+	assert(results[1].Alternative.String() == "sha256", "SHA256 should be recommended")
+	assert(results[2].Compliant, "P-256 should pass")
+
+	future := wardstone.NewContext(2032, 0)
+	results = wardstone.Assess(future, wardstone.Nist, primitives[:1])
+	assert(!results[0].Compliant, "RSA-2048 should fail after 2031")
+
+	// Errors carry the code and message reported by the library.
+	_, err := wardstone.Lookup("rot13")
+	var e *wardstone.Error
+	assert(errors.As(err, &e) && e.Message != "", "rot13 should not be found")
+
+	// Certificates are assessed in one go.
+	assessment, err := wardstone.AssessCertificate(ctx, wardstone.Nist, []byte(certificate))
+	assert(err == nil && assessment.Passed, "Ed25519 certificate should pass")
+	assert(assessment.GotSignature == "ed25519", "unexpected subject key")
+	_, err = wardstone.AssessCertificate(ctx, wardstone.Nist, []byte("junk"))
+	assert(err != nil, "junk should not parse")
+
+	// Policies loaded at runtime are opaque handles.
+	policy, err := wardstone.LoadPolicy("../ffi/policy.conf")
+	assert(err == nil, "policy should load")
+	defer policy.Close()
+	assert(!policy.Assess(ctx, primitives[0]).Compliant, "RSA-2048 should fail after 2031")
+}
@@ -0,0 +1,220 @@
+// Package wardstone wraps the C API of the wardstone library for Go
+// programs through cgo.
+//
+// The static library and header are expected in the target directory
+// at the root of the repository, where `cargo build --release` places
+// them.
+package wardstone
+
+/*
+#cgo CFLAGS: -I${SRCDIR}/../../../target
+#cgo LDFLAGS: ${SRCDIR}/../../../target/release/libwardstone.a -lm -ldl -lpthread
+#include <stdlib.h>
+#include "wardstone.h"
+*/
+import "C"
+
+import (
+	"encoding/json"
+	"runtime"
+	"unsafe"
+)
+
+// Guide is a standard that primitives are assessed against.
+type Guide uint32
+
+const (
+	Bsi     Guide = C.WS_GUIDE_BSI
+	Cnsa    Guide = C.WS_GUIDE_CNSA
+	Ecrypt  Guide = C.WS_GUIDE_ECRYPT
+	Lenstra Guide = C.WS_GUIDE_LENSTRA
+	Nist    Guide = C.WS_GUIDE_NIST
+	Iot     Guide = C.WS_GUIDE_IOT
+)
+
+// Error is returned when the library reports a negative ws_err code.
+type Error struct {
+	Code    int
+	Message string
+}
+
+func (e *Error) Error() string {
+	return e.Message
+}
+
+// Returns the most recent error recorded by the library. The caller
+// must be locked to the OS thread that made the failing call since
+// errors are kept per thread.
+func lastError() error {
+	code := C.ws_last_error_code()
+	message := C.ws_last_error()
+	if message == nil {
+		message = C.ws_strerror(code)
+	}
+	return &Error{Code: int(code), Message: C.GoString(message)}
+}
+
+// Returns the error for a code without a more detailed message, which
+// is what per-item codes in a batch carry.
+func codeError(code C.int) error {
+	return &Error{Code: int(code), Message: C.GoString(C.ws_strerror(code))}
+}
+
+// Version returns the version of the library.
+func Version() string {
+	return C.GoString(C.ws_version())
+}
+
+// Context holds the security level and year that primitives are
+// assessed against.
+type Context struct {
+	c C.struct_ws_context
+}
+
+// DefaultContext returns the context used when none is specified.
+func DefaultContext() Context {
+	return Context{C.ws_context_default()}
+}
+
+// NewContext returns a context for the given year and minimum security.
+func NewContext(year, security uint16) Context {
+	return Context{C.ws_context_new(C.uint16_t(year), C.ws_security(security))}
+}
+
+// Primitive is a primitive of any kind built into the library.
+type Primitive struct {
+	c C.struct_ws_primitive
+}
+
+// Lookup returns the primitive with a name such as "P-256" or an object
+// identifier such as "1.2.840.10045.3.1.7".
+func Lookup(name string) (Primitive, error) {
+	runtime.LockOSThread()
+	defer runtime.UnlockOSThread()
+	s := C.CString(name)
+	defer C.free(unsafe.Pointer(s))
+	var p Primitive
+	if code := C.ws_primitive_from_name(s, &p.c); code >= 0 {
+		return p, nil
+	}
+	if code := C.ws_primitive_from_oid(s, &p.c); code < 0 {
+		return Primitive{}, lastError()
+	}
+	return p, nil
+}
+
+func (p Primitive) String() string {
+	n := C.ws_primitive_to_string(p.c, nil, 0)
+	if n < 0 {
+		return "unrecognised"
+	}
+	buf := make([]byte, int(n)+1)
+	C.ws_primitive_to_string(p.c, (*C.char)(unsafe.Pointer(&buf[0])), C.uintptr_t(len(buf)))
+	return string(buf[:n])
+}
+
+// Result is the outcome of assessing a single primitive.
+//
+// Alternative is the recommended primitive that one should use instead,
+// or the primitive with the desired security level if it is already
+// compliant. Err is set if the primitive could not be assessed.
+type Result struct {
+	Compliant   bool
+	Alternative Primitive
+	Err         error
+}
+
+// Assess assesses many primitives against a guide in a single call.
+func Assess(ctx Context, guide Guide, primitives []Primitive) []Result {
+	n := len(primitives)
+	if n == 0 {
+		return nil
+	}
+	// The arrays are allocated as C types so that they can be handed to
+	// the library as they are.
+	in := make([]C.struct_ws_primitive, n)
+	for i, p := range primitives {
+		in[i] = p.c
+	}
+	codes := make([]C.int, n)
+	alternatives := make([]C.struct_ws_primitive, n)
+	C.ws_assess_batch_results(ctx.c, C.int(guide), &in[0], C.uintptr_t(n), &codes[0], &alternatives[0])
+	results := make([]Result, n)
+	for i, code := range codes {
+		if code < 0 {
+			results[i] = Result{Err: codeError(code)}
+		} else {
+			results[i] = Result{Compliant: code == 1, Alternative: Primitive{alternatives[i]}}
+		}
+	}
+	return results
+}
+
+// Assessment is the outcome of assessing the primitives used by a
+// certificate. The hash function fields are empty if the signature
+// algorithm does not use a separate hash function, as is the case for
+// EdDSA.
+type Assessment struct {
+	Passed           bool   `json:"passed"`
+	GotHashFunction  string `json:"got_hash_function,omitempty"`
+	WantHashFunction string `json:"want_hash_function,omitempty"`
+	GotSignature     string `json:"got_signature"`
+	WantSignature    string `json:"want_signature"`
+}
+
+// AssessCertificate assesses the hash function and subject public key
+// of a DER or PEM encoded X.509 certificate against a guide.
+func AssessCertificate(ctx Context, guide Guide, data []byte) (Assessment, error) {
+	runtime.LockOSThread()
+	defer runtime.UnlockOSThread()
+	var ptr *C.uint8_t
+	if len(data) > 0 {
+		ptr = (*C.uint8_t)(unsafe.Pointer(&data[0]))
+	}
+	s := C.ws_assess_x509_to_json(ctx.c, C.int(guide), ptr, C.uintptr_t(len(data)))
+	if s == nil {
+		return Assessment{}, lastError()
+	}
+	defer C.ws_free(s)
+	var assessment Assessment
+	err := json.Unmarshal([]byte(C.GoString(s)), &assessment)
+	return assessment, err
+}
+
+// Policy is an organisation specific policy loaded from a file. It must
+// be released with Close.
+type Policy struct {
+	p *C.struct_ws_policy
+}
+
+// LoadPolicy loads a policy from the file at path.
+func LoadPolicy(path string) (*Policy, error) {
+	runtime.LockOSThread()
+	defer runtime.UnlockOSThread()
+	s := C.CString(path)
+	defer C.free(unsafe.Pointer(s))
+	p := C.ws_policy_load(s)
+	if p == nil {
+		return nil, lastError()
+	}
+	return &Policy{p}, nil
+}
+
+// Close releases the policy.
+func (p *Policy) Close() {
+	C.ws_policy_free(p.p)
+	p.p = nil
+}
+
+// Assess assesses a primitive against the policy.
+func (p *Policy) Assess(ctx Context, primitive Primitive) Result {
+	runtime.LockOSThread()
+	defer runtime.UnlockOSThread()
+	var verdict C.struct_ws_verdict
+	verdict.size = C.uint32_t(unsafe.Sizeof(verdict))
+	code := C.ws_policy_assess(p.p, ctx.c, primitive.c, &verdict)
+	if code < 0 {
+		return Result{Err: lastError()}
+	}
+	return Result{Compliant: code == 1, Alternative: Primitive{verdict.alternative}}
+}
@@ -0,0 +1,28 @@
+const assert = require("node:assert");
+
+const wardstone = require(process.env.WARDSTONE_NODE ?? "../../crates/node");
+
+const CERTIFICATE = `-----BEGIN CERTIFICATE-----
+MIIBOjCB7aADAgECAhQfvM/UvfvJH5qQyoL/HQIEDfsd7zAFBgMrZXAwEjEQMA4G
+A1UEAwwHZXhhbXBsZTAgFw0yNjEwMTYxOTI3MTdaGA8yMTI2MDkyMjE5MjcxN1ow
+EjEQMA4GA1UEAwwHZXhhbXBsZTAqMAUGAytlcAMhAPh0tWZ6Op7H46skElql6we8
+RGB/qDeYr6DFSZwTF8gZo1MwUTAdBgNVHQ4EFgQU+iXZ5UI+5kFamJr3o6HCPTTM
+WN0wHwYDVR0jBBgwFoAU+iXZ5UI+5kFamJr3o6HCPTTMWN0wDwYDVR0TAQH/BAUw
+AwEB/zAFBgMrZXADQQAijC7N6FmH6y6CPqey6m2bEcoz3/hzgc7zzQJ3FPYEO65o
+8fX6tAwKyLe1blqxx1OEW/PNUUZ0Facie8F3/iIF
+-----END CERTIFICATE-----
+`;
+
+assert.deepStrictEqual(wardstone.guides(), ["bsi", "cnsa", "ecrypt", "iot", "lenstra", "nist"]);
+
+let verdict = wardstone.validate("nist", "SHA-1");
+assert.strictEqual(verdict.compliant, false, "SHA1 should fail");
+verdict = wardstone.validate("nist", "1.2.840.10045.3.1.7", 2023);
+assert.strictEqual(verdict.compliant, true, "P-256 should pass");
+assert.throws(() => wardstone.validate("nist", "rot13"), /unknown primitive/);
+
+const assessment = wardstone.assessCertificate("nist", Buffer.from(CERTIFICATE));
+assert.strictEqual(assessment.passed, true, "Ed25519 certificate should pass");
+assert.strictEqual(assessment.hashFunction, undefined, "EdDSA does not use a separate hash function");
+assert.strictEqual(assessment.keyCompliant, true);
+assert.throws(() => wardstone.assessCertificate("nist", Buffer.from("junk")), /malformed certificate/);
@@ -0,0 +1,49 @@
+import pathlib
+import tempfile
+
+import wardstone
+
+CERTIFICATE = b"""-----BEGIN CERTIFICATE-----
+MIIBOjCB7aADAgECAhQfvM/UvfvJH5qQyoL/HQIEDfsd7zAFBgMrZXAwEjEQMA4G
+A1UEAwwHZXhhbXBsZTAgFw0yNjEwMTYxOTI3MTdaGA8yMTI2MDkyMjE5MjcxN1ow
+EjEQMA4GA1UEAwwHZXhhbXBsZTAqMAUGAytlcAMhAPh0tWZ6Op7H46skElql6we8
+RGB/qDeYr6DFSZwTF8gZo1MwUTAdBgNVHQ4EFgQU+iXZ5UI+5kFamJr3o6HCPTTM
+WN0wHwYDVR0jBBgwFoAU+iXZ5UI+5kFamJr3o6HCPTTMWN0wDwYDVR0TAQH/BAUw
+AwEB/zAFBgMrZXADQQAijC7N6FmH6y6CPqey6m2bEcoz3/hzgc7zzQJ3FPYEO65o
+8fX6tAwKyLe1blqxx1OEW/PNUUZ0Facie8F3/iIF
+-----END CERTIFICATE-----
+"""
+
+SSH_KEY = "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIPsnRG3WnyBlKpIegFpfkNW5CUB/n/zvj6KJpBOQbBIb example\n"
+
+ctx = wardstone.Context(year=2031)
+assert ctx.year == 2031 and ctx.security == 0
+
+# Primitives can be found by name or object identifier.
+p256 = wardstone.Primitive.from_name("secp256r1")
+assert p256 == wardstone.Primitive.from_oid("1.2.840.10045.3.1.7")
+assert p256.kind == "ecc" and p256.security == 128
+
+compliant, alternative = wardstone.validate("nist", wardstone.Primitive.from_name("SHA-1"), ctx)
+assert not compliant and alternative.kind == "hash", "SHA1 should fail"
+compliant, _ = wardstone.validate("nist", wardstone.Primitive.rsa_pkcs1(2048), wardstone.Context(year=2032))
+assert not compliant, "RSA-2048 should fail after 2031"
+
+try:
+    wardstone.Primitive.from_name("rot13")
+except ValueError:
+    pass
+else:
+    raise AssertionError("rot13 should not be found")
+
+# Keys and certificates are assessed in a single call.
+assessment = wardstone.assess_x509("nist", CERTIFICATE, ctx)
+assert assessment.passed, "Ed25519 certificate should pass"
+assert assessment.hash_function is None, "EdDSA does not use a separate hash function"
+assert assessment.signature_algorithm == wardstone.Primitive.from_name("Ed25519")
+
+with tempfile.TemporaryDirectory() as directory:
+    path = pathlib.Path(directory) / "id_ed25519.pub"
+    path.write_text(SSH_KEY)
+    assessment = wardstone.assess_ssh("nist", path)
+    assert assessment.passed, "Ed25519 key should pass"